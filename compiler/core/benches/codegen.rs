@@ -0,0 +1,63 @@
+//! Code generation throughput on a 100k-expression program.
+//!
+//! Run with `cargo bench --bench codegen`. Each expression is a `Print` of a
+//! nested arithmetic expression, so the time is dominated by generating
+//! expression values rather than by per-statement overhead.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use w_core::ast::{Expression, Operator, Radix};
+use w_core::rust_codegen::RustCodeGenerator;
+
+const EXPRESSIONS: usize = 100_000;
+const RUNS: usize = 5;
+
+/// `x + i` combined with `[i, y]` by `-` and `*` in turn, `depth` levels deep.
+/// Only generated, never compiled, so the operand types do not matter.
+fn nested(i: i64, depth: usize) -> Expression {
+    if depth == 0 {
+        return Expression::BinaryOp {
+            left: Box::new(Expression::Identifier("x".to_string())),
+            operator: Operator::Add,
+            right: Box::new(Expression::Number(i, Radix::Decimal)),
+        };
+    }
+    let operator = if depth.is_multiple_of(2) { Operator::Multiply } else { Operator::Subtract };
+    Expression::BinaryOp {
+        left: Box::new(nested(i, depth - 1)),
+        operator,
+        right: Box::new(Expression::List(vec![Expression::Number(i, Radix::Decimal), Expression::Identifier("y".to_string())])),
+    }
+}
+
+fn program() -> Expression {
+    Expression::Program(
+        (0..EXPRESSIONS as i64)
+            .map(|i| Expression::FunctionCall {
+                function: Box::new(Expression::Identifier("Print".to_string())),
+                arguments: vec![nested(i, 6)],
+            })
+            .collect(),
+    )
+}
+
+fn main() {
+    let program = program();
+    let mut best = Duration::MAX;
+    let mut bytes = 0;
+    for _ in 0..RUNS {
+        let start = Instant::now();
+        let rust_code = RustCodeGenerator::new().generate(black_box(&program)).unwrap();
+        best = best.min(start.elapsed());
+        bytes = rust_code.len();
+    }
+    println!(
+        "codegen: {} expressions in {:.1} ms (best of {}), {:.0} expressions/s, {} bytes",
+        EXPRESSIONS,
+        best.as_secs_f64() * 1000.0,
+        RUNS,
+        EXPRESSIONS as f64 / best.as_secs_f64(),
+        bytes
+    );
+}
@@ -0,0 +1,753 @@
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub enum LogLevel {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq, Hash)]
+#[allow(dead_code)]
+pub enum Type {
+    // Signed integers
+    Int8,
+    Int16,
+    Int32,
+    Int64,
+    Int128,
+    Int,  // isize
+
+    // Unsigned integers
+    UInt8,
+    UInt16,
+    UInt32,
+    UInt64,
+    UInt128,
+    UInt,  // usize
+
+    // Floating point
+    Float32,
+    Float64,
+
+    // Other primitives
+    Bool,
+    Char,
+    String,
+
+    // Composite types
+    Tuple(Vec<Type>),                     // (T1, T2, T3, ...)
+
+    // Complex types
+    List(Box<Type>),                      // Vec<T>; also written Stack[T]
+    Queue(Box<Type>),                     // VecDeque<T>
+    Heap(Box<Type>),                      // BinaryHeap<T> (largest first)
+    Stream(Box<Type>),                    // Lazy, possibly infinite iterator; collected with Take
+    Bytes,                                // Vec<u8>
+    Array(Box<Type>, usize),              // [T; N] - fixed size
+    Slice(Box<Type>),                     // &[T]
+    Map(Box<Type>, Box<Type>),            // HashMap<K, V>
+    HashSet(Box<Type>),                   // HashSet<T>
+    BTreeMap(Box<Type>, Box<Type>),       // BTreeMap<K, V>
+    BTreeSet(Box<Type>),                  // BTreeSet<T>
+    Function(Vec<Type>, Box<Type>),
+
+    // Error handling types (crucial for Rust's safety model)
+    Option(Box<Type>),                    // Option<T>
+    Result(Box<Type>, Box<Type>),         // Result<T, E>
+
+    // Special types
+    LogLevel,
+    /// Not determined by the expression alone, e.g. the payload of `None`;
+    /// unifies with any type
+    Unknown,
+    /// Type of an expression that never produces a value, e.g. `Panic[...]`;
+    /// unifies with any type so diverging branches don't constrain the rest
+    Never,
+    /// Type parameter of a generic function, written as a single capital
+    /// letter, e.g. `T` in `First[list: List[T]]`
+    TypeVar(String),
+
+    // User-defined types
+    Custom(String),                       // Custom struct types
+}
+
+/// Renders the type in W source syntax, e.g. `Map[String, List[Int32]]`,
+/// except that type parameters are written `'T`
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn join(types: &[Type]) -> String {
+            types.iter().map(|t| t.to_string()).collect::<Vec<_>>().join(", ")
+        }
+        match self {
+            Type::Int8 => write!(f, "Int8"),
+            Type::Int16 => write!(f, "Int16"),
+            Type::Int32 => write!(f, "Int32"),
+            Type::Int64 => write!(f, "Int64"),
+            Type::Int128 => write!(f, "Int128"),
+            Type::Int => write!(f, "Int"),
+            Type::UInt8 => write!(f, "UInt8"),
+            Type::UInt16 => write!(f, "UInt16"),
+            Type::UInt32 => write!(f, "UInt32"),
+            Type::UInt64 => write!(f, "UInt64"),
+            Type::UInt128 => write!(f, "UInt128"),
+            Type::UInt => write!(f, "UInt"),
+            Type::Float32 => write!(f, "Float32"),
+            Type::Float64 => write!(f, "Float64"),
+            Type::Bool => write!(f, "Bool"),
+            Type::Char => write!(f, "Char"),
+            Type::String => write!(f, "String"),
+            Type::Tuple(types) if types.len() == 1 => write!(f, "({},)", types[0]),
+            Type::Tuple(types) => write!(f, "({})", join(types)),
+            Type::List(inner) => write!(f, "List[{}]", inner),
+            Type::Queue(inner) => write!(f, "Queue[{}]", inner),
+            Type::Heap(inner) => write!(f, "Heap[{}]", inner),
+            Type::Stream(inner) => write!(f, "Stream[{}]", inner),
+            Type::Bytes => write!(f, "Bytes"),
+            Type::Array(inner, size) => write!(f, "Array[{}, {}]", inner, size),
+            Type::Slice(inner) => write!(f, "Slice[{}]", inner),
+            Type::Map(key, value) => write!(f, "Map[{}, {}]", key, value),
+            Type::HashSet(inner) => write!(f, "HashSet[{}]", inner),
+            Type::BTreeMap(key, value) => write!(f, "BTreeMap[{}, {}]", key, value),
+            Type::BTreeSet(inner) => write!(f, "BTreeSet[{}]", inner),
+            Type::Function(parameters, result) if parameters.is_empty() => write!(f, "Function[{}]", result),
+            Type::Function(parameters, result) => write!(f, "Function[{}, {}]", join(parameters), result),
+            Type::Option(inner) => write!(f, "Option[{}]", inner),
+            Type::Result(ok, err) => write!(f, "Result[{}, {}]", ok, err),
+            Type::LogLevel => write!(f, "LogLevel"),
+            Type::Unknown => write!(f, "_"),
+            Type::Never => write!(f, "Never"),
+            // A type the checker has not worked out yet
+            Type::TypeVar(name) if name.starts_with('?') => write!(f, "_"),
+            // Marked so that `T` the type parameter and a type named `T` differ
+            Type::TypeVar(name) => write!(f, "'{}", name),
+            Type::Custom(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+/// Represents patterns for pattern matching
+#[derive(Debug, Clone, PartialEq, Hash)]
+#[allow(dead_code)]
+pub enum Pattern {
+    /// Wildcard pattern `_` - matches anything
+    Wildcard,
+    /// Literal pattern - matches exact value
+    Literal(Box<Expression>),
+    /// Variable pattern - binds value to variable name
+    Variable(String),
+    /// Constructor pattern - e.g., Some[x], Ok[val], Err[e]
+    Constructor {
+        name: String,
+        patterns: Vec<Pattern>,
+    },
+    /// Tuple pattern - e.g., (x, y, z)
+    Tuple(Vec<Pattern>),
+    /// List pattern - e.g., [x, y, z]
+    List(Vec<Pattern>),
+    /// Rest pattern `..` - skips the remaining struct fields, e.g.
+    /// `Point[x, ..]`, or any number of list elements, e.g. `[first, ..]`
+    Rest,
+    /// String prefix pattern - e.g., StartsWith["GET "]
+    StartsWith(String),
+    /// String suffix pattern - e.g., EndsWith[".w"]
+    EndsWith(String),
+    /// Or-pattern - matches if any alternative does, e.g. `1 | 2 | 3`
+    Or(Vec<Pattern>),
+    /// Binding pattern - binds the whole value to `name` when `pattern`
+    /// matches it, e.g. `whole @ Some[x]` or `n @ 1 | 2` (every alternative)
+    Binding {
+        name: String,
+        pattern: Box<Pattern>,
+    },
+}
+
+impl Pattern {
+    /// Whether the pattern matches every value of its type, so it can be used
+    /// in a plain `Let` binding. Struct constructors are irrefutable when
+    /// their field patterns are; `Some`/`Ok`/`Err`/`None`, literals and list
+    /// patterns other than `[..]` are not. Every other name is assumed to be
+    /// a struct; use `is_irrefutable_with` when enum variants are known.
+    pub fn is_irrefutable(&self) -> bool {
+        self.is_irrefutable_with(&|_| false)
+    }
+
+    /// Is this the rest of a list, `..` or `name @ ..`?
+    pub fn is_rest(&self) -> bool {
+        match self {
+            Pattern::Rest => true,
+            Pattern::Binding { pattern, .. } => pattern.is_rest(),
+            _ => false,
+        }
+    }
+
+    /// Like `is_irrefutable`, treating constructors and bare names for which
+    /// `is_variant` returns true as enum variants (which can fail to match)
+    pub fn is_irrefutable_with(&self, is_variant: &dyn Fn(&str) -> bool) -> bool {
+        match self {
+            Pattern::Wildcard | Pattern::Rest => true,
+            Pattern::Variable(name) => !is_variant(name),
+            Pattern::Literal(_) | Pattern::StartsWith(_) | Pattern::EndsWith(_) => false,
+            Pattern::Constructor { name, patterns } => {
+                !matches!(name.as_str(), "Some" | "None" | "Ok" | "Err")
+                    && !is_variant(name)
+                    && patterns.iter().all(|p| p.is_irrefutable_with(is_variant))
+            }
+            Pattern::Tuple(patterns) => patterns.iter().all(|p| p.is_irrefutable_with(is_variant)),
+            Pattern::List(patterns) => matches!(patterns.as_slice(), [rest] if rest.is_rest()),
+            Pattern::Or(alternatives) => alternatives.iter().any(|p| p.is_irrefutable_with(is_variant)),
+            Pattern::Binding { pattern, .. } => pattern.is_irrefutable_with(is_variant),
+        }
+    }
+}
+
+/// Renders the pattern in W source syntax, e.g. `Some[(x, _)]`
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn join(patterns: &[Pattern]) -> String {
+            patterns.iter().map(|p| p.to_string()).collect::<Vec<_>>().join(", ")
+        }
+        match self {
+            Pattern::Wildcard => write!(f, "_"),
+            Pattern::Rest => write!(f, ".."),
+            Pattern::Variable(name) => write!(f, "{}", name),
+            Pattern::Literal(literal) => match literal.as_ref() {
+                Expression::Number(n, radix) => write!(f, "{}", radix.format(*n)),
+                Expression::String(s) => write!(f, "{:?}", s),
+                Expression::Boolean(b) => write!(f, "{}", b),
+                other => write!(f, "{:?}", other),
+            },
+            Pattern::Constructor { name, patterns } if patterns.is_empty() => write!(f, "{}", name),
+            Pattern::Constructor { name, patterns } => write!(f, "{}[{}]", name, join(patterns)),
+            Pattern::Tuple(patterns) if patterns.len() == 1 => write!(f, "({},)", patterns[0]),
+            Pattern::Tuple(patterns) => write!(f, "({})", join(patterns)),
+            Pattern::List(patterns) => write!(f, "[{}]", join(patterns)),
+            Pattern::StartsWith(prefix) => write!(f, "StartsWith[{:?}]", prefix),
+            Pattern::EndsWith(suffix) => write!(f, "EndsWith[{:?}]", suffix),
+            Pattern::Or(alternatives) => {
+                write!(f, "{}", alternatives.iter().map(|p| p.to_string()).collect::<Vec<_>>().join(" | "))
+            }
+            Pattern::Binding { name, pattern } => write!(f, "{} @ {}", name, pattern),
+        }
+    }
+}
+
+/// One case of an enum: `Circle[Float64]` carries a payload, `Empty` does not
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub struct EnumVariant {
+    pub name: String,
+    pub fields: Vec<Type>,
+}
+
+/// A method a trait requires: `ToText[self, width: Int32] -> String`.
+/// Every method takes `self`, which is not listed in `parameters`
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub struct TraitMethod {
+    pub name: String,
+    pub parameters: Vec<TypeAnnotation>,
+    pub return_type: Type,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub struct TypeAnnotation {
+    pub name: String,
+    pub type_: Type,
+    /// Value of a function parameter the caller leaves out:
+    /// `Greet[name: String, greeting: String = "Hello"]`
+    pub default: Option<Box<Expression>>,
+}
+
+/// How an integer literal is written: in decimal or with a `0x`, `0o` or
+/// `0b` prefix. Generated Rust writes it the same way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Radix {
+    Decimal,
+    Hexadecimal,
+    Octal,
+    Binary,
+}
+
+impl Radix {
+    /// `value` written in this radix, which W and Rust write alike
+    pub fn format(self, value: i64) -> String {
+        match self {
+            _ if value < 0 => value.to_string(),
+            Radix::Decimal => value.to_string(),
+            Radix::Hexadecimal => format!("0x{:X}", value),
+            Radix::Octal => format!("0o{:o}", value),
+            Radix::Binary => format!("0b{:b}", value),
+        }
+    }
+}
+
+/// Whether a definition is exported from its module
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Visibility {
+    /// Visible to importing modules (`pub` in Rust); the default
+    #[default]
+    Public,
+    /// Only usable inside the defining module
+    Private,
+}
+
+/// A piece of an interpolated string literal
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub enum StringPart {
+    /// Text copied verbatim into the result
+    Literal(String),
+    /// `{value}` or `{value:spec}`; `format_spec` is the Rust format spec after `:`
+    Expression {
+        value: Expression,
+        format_spec: Option<String>,
+    },
+    /// `{}` or `{:spec}` in the template of `Print["x = {}", x]`, filled by
+    /// the next of the arguments that follow it
+    Placeholder { format_spec: Option<String> },
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expression {
+    /// Integer literal; Int32 like Rust's default if it fits, else Int64
+    Number(i64, Radix),
+    Float(f64),
+    String(String),
+    Boolean(bool),
+    /// String literal containing `{expr}` placeholders, e.g. `"value is {x}"`
+    InterpolatedString(Vec<StringPart>),
+    Tuple(Vec<Expression>),
+    List(Vec<Expression>),
+    Map(Vec<(Expression, Expression)>),
+    Identifier(String),
+    /// Name inside a module: `Math.Sqrt` or `Geometry::Area`
+    /// Structure: `path` holds the module segments (`["Math"]`), `name` the item
+    QualifiedName {
+        path: Vec<String>,
+        name: String,
+    },
+    FunctionCall {
+        function: Box<Expression>,
+        arguments: Vec<Expression>,
+    },
+    /// Read a struct field: `point.x` or `Field[point, x]`
+    FieldAccess {
+        object: Box<Expression>,
+        field: String,
+    },
+    /// Structure: `Name[params] := body`, optionally wrapped in `Public[...]`
+    /// or `Private[...]`
+    FunctionDefinition {
+        name: String,
+        parameters: Vec<TypeAnnotation>,
+        body: Box<Expression>,
+        visibility: Visibility,
+    },
+    Program(Vec<Expression>),  // Multiple top-level expressions
+    BinaryOp {
+        left: Box<Expression>,
+        operator: Operator,
+        right: Box<Expression>,
+    },
+    LogCall {
+        level: LogLevel,
+        message: Box<Expression>,
+    },
+    /// Conditional expression similar to LISP's `cond`
+    ///
+    /// Structure: `Cond[[condition1 statements1] [condition2 statements2] ... [default_statements]]`
+    ///
+    /// # Variants
+    /// - `conditions`: A list of condition-statement pairs
+    /// - `default_statements`: Optional statements to execute if no conditions match
+    /// - `from_if`: Written as `If[condition, then, else]`, so messages name If
+    Cond {
+        conditions: Vec<(Expression, Expression)>,
+        default_statements: Option<Box<Expression>>,
+        from_if: bool,
+    },
+
+    // Error handling expressions (Rust's safety model)
+    /// Represents None variant of Option
+    None,
+    /// Represents Some[value] variant of Option
+    Some {
+        value: Box<Expression>,
+    },
+    /// Represents Ok[value] variant of Result
+    Ok {
+        value: Box<Expression>,
+    },
+    /// Represents Err[error] variant of Result
+    Err {
+        error: Box<Expression>,
+    },
+
+    /// Logical negation `!expr`; the operand must be Bool
+    Not {
+        expr: Box<Expression>,
+    },
+
+    /// Error propagation operator `?`
+    /// Unwraps Result/Option, returning early on Err/None
+    Propagate {
+        expr: Box<Expression>,
+    },
+
+    /// A data-parallel kernel, `@[kernel] F[xs: List[Float64], k: Float64] := xs * k`:
+    /// `definition` is a function whose body computes one element of the
+    /// result, with each numeric list parameter standing for its element at
+    /// the same index (experimental)
+    Kernel {
+        definition: Box<Expression>,
+    },
+
+    /// Result propagation: Try[expr] is the Ok value of `expr`, returning its
+    /// Err early from the enclosing function, which must return Result
+    Try {
+        expr: Box<Expression>,
+    },
+
+    /// Checked conversion between numeric types: Convert[value, Float64]
+    Convert {
+        value: Box<Expression>,
+        target: Type,
+    },
+
+    /// Pattern matching expression
+    /// Structure: Match[value, [pattern1, result1], [pattern2, result2], ...]
+    Match {
+        value: Box<Expression>,
+        arms: Vec<(Pattern, Expression)>,
+    },
+
+    /// Single-pattern match: `then_branch` runs with the pattern's bindings
+    /// when `value` matches, `else_branch` (if any) otherwise
+    /// Structure: IfLet[pattern, value, then] or IfLet[pattern, value, then, else]
+    IfLet {
+        pattern: Pattern,
+        value: Box<Expression>,
+        then_branch: Box<Expression>,
+        else_branch: Option<Box<Expression>>,
+    },
+
+    /// Loop re-evaluating `value` and running `body` with the pattern's
+    /// bindings for as long as it matches
+    /// Structure: WhileLet[pattern, value, body]
+    WhileLet {
+        pattern: Pattern,
+        value: Box<Expression>,
+        body: Box<Expression>,
+    },
+
+    /// Loop running `body` once per element, for its side effects
+    /// Structure: For[x, collection, body]
+    For {
+        variable: String,
+        iterable: Box<Expression>,
+        body: Box<Expression>,
+    },
+
+    /// Lambda/Closure expression
+    /// Structure: Function[{param1, param2, ...}, body]
+    /// or: Function[{param1: Type1, param2: Type2}, body]
+    Lambda {
+        parameters: Vec<TypeAnnotation>,
+        body: Box<Expression>,
+    },
+
+    /// Struct definition
+    /// Structure: Struct[Name, [field1: Type1, field2: Type2, ...]]
+    StructDefinition {
+        name: String,
+        fields: Vec<TypeAnnotation>,
+        visibility: Visibility,
+    },
+
+    /// Enum (sum type) definition
+    /// Structure: Enum[Name, [Variant1[Type1, ...], Variant2, ...]]
+    /// Variants are constructed and matched by their bare name, e.g.
+    /// `Circle[1.0]` or `Match[s, [Circle[r], r]]`
+    EnumDefinition {
+        name: String,
+        variants: Vec<EnumVariant>,
+        visibility: Visibility,
+    },
+
+    /// Trait (interface) definition
+    /// Structure: Trait[Name, [Method[self, param: Type] -> ReturnType, ...]]
+    TraitDefinition {
+        name: String,
+        methods: Vec<TraitMethod>,
+        visibility: Visibility,
+    },
+
+    /// Implementation of a trait for a struct or enum
+    /// Structure: Impl[Trait, Type, Method[self, param] := body, ...]
+    /// `methods` are FunctionDefinitions without the `self` parameter;
+    /// untyped parameters take their type from the trait
+    Impl {
+        trait_name: String,
+        type_name: String,
+        methods: Vec<Expression>,
+    },
+
+    /// Load the definitions of another W file as a module
+    /// Structure: Import["geometry.w"] (path relative to the importing file)
+    /// Imports are resolved before code generation; see `imports`
+    Import(String),
+
+    /// Struct instantiation
+    /// Structure: StructName[value1, value2, ...]
+    /// Used when a struct type is called as a constructor
+    StructInstantiation {
+        struct_name: String,
+        field_values: Vec<Expression>,
+    },
+
+    /// Local binding visible only in `body`
+    /// Structure: `{ let name = value; body }`
+    /// Currently introduced by the optimizer (e.g. common-subexpression
+    /// elimination) rather than written in source.
+    Let {
+        name: String,
+        value: Box<Expression>,
+        body: Box<Expression>,
+    },
+
+    /// Statements run in order in a scope of their own; the value is the
+    /// last one's
+    /// Structure: `Block[statement, ..., value]`
+    /// `Let` bindings in it are visible to the statements after them only.
+    Block(Vec<Expression>),
+
+    /// Destructuring binding whose variables stay in scope for the rest of
+    /// the enclosing block
+    /// Structure: `Let[pattern, value]` or `LetElse[pattern, value, fallback]`
+    /// `Let` requires an irrefutable pattern; with `LetElse`, `fallback` runs
+    /// when the pattern does not match and the block is left afterwards.
+    LetPattern {
+        pattern: Pattern,
+        value: Box<Expression>,
+        fallback: Option<Box<Expression>>,
+    },
+}
+
+impl Expression {
+    /// Every direct sub-expression, including lambda bodies and branches that
+    /// may never run (patterns are not expressions and are skipped)
+    pub fn children(&self) -> Vec<&Expression> {
+        let mut children = Vec::new();
+        self.for_each_child(&mut |child| children.push(child));
+        children
+    }
+
+    /// Call `f` on each of `children()` in order, without collecting them;
+    /// for walks over every node of a large program
+    pub fn for_each_child<'a>(&'a self, f: &mut impl FnMut(&'a Expression)) {
+        match self {
+            Expression::Tuple(elements) | Expression::List(elements) | Expression::Program(elements)
+            | Expression::Block(elements) => elements.iter().for_each(f),
+            Expression::Map(entries) => entries.iter().flat_map(|(k, v)| [k, v]).for_each(f),
+            Expression::InterpolatedString(parts) => parts.iter()
+                .filter_map(|part| match part {
+                    StringPart::Expression { value, .. } => Some(value),
+                    StringPart::Literal(_) | StringPart::Placeholder { .. } => None,
+                })
+                .for_each(f),
+            Expression::FunctionCall { function, arguments } => {
+                std::iter::once(function.as_ref()).chain(arguments).for_each(f)
+            }
+            Expression::FunctionDefinition { body, .. } | Expression::Lambda { body, .. } => f(body),
+            Expression::BinaryOp { left, right, .. } => {
+                f(left);
+                f(right);
+            }
+            Expression::LogCall { message, .. } => f(message),
+            Expression::Cond { conditions, default_statements, .. } => conditions.iter()
+                .flat_map(|(condition, statements)| [condition, statements])
+                .chain(default_statements.as_deref())
+                .for_each(f),
+            Expression::Some { value } | Expression::Ok { value } => f(value),
+            Expression::Err { error } => f(error),
+            Expression::Not { expr } | Expression::Propagate { expr } | Expression::Try { expr } => f(expr),
+            Expression::Kernel { definition } => f(definition),
+            Expression::Convert { value, .. } => f(value),
+            Expression::FieldAccess { object, .. } => f(object),
+            Expression::Match { value, arms } => {
+                std::iter::once(value.as_ref()).chain(arms.iter().map(|(_, result)| result)).for_each(f)
+            }
+            Expression::IfLet { value, then_branch, else_branch, .. } => {
+                [value.as_ref(), then_branch.as_ref()].into_iter().chain(else_branch.as_deref()).for_each(f)
+            }
+            Expression::For { iterable, body, .. } => {
+                f(iterable);
+                f(body);
+            }
+            Expression::WhileLet { value, body, .. } | Expression::Let { value, body, .. } => {
+                f(value);
+                f(body);
+            }
+            Expression::StructInstantiation { field_values, .. } => field_values.iter().for_each(f),
+            Expression::LetPattern { value, fallback, .. } => {
+                std::iter::once(value.as_ref()).chain(fallback.as_deref()).for_each(f)
+            }
+            Expression::Number(..) | Expression::Float(_) | Expression::String(_) | Expression::Boolean(_)
+            | Expression::Identifier(_) | Expression::QualifiedName { .. } | Expression::None
+            | Expression::StructDefinition { .. } | Expression::EnumDefinition { .. }
+            | Expression::TraitDefinition { .. } | Expression::Import(_) => {}
+            Expression::Impl { methods, .. } => methods.iter().for_each(f),
+        }
+    }
+}
+
+/// Structural hashing of expressions.
+///
+/// Two expressions that compare equal hash identically. Floats hash by their
+/// bit pattern, which is consistent with `PartialEq` for every value except
+/// NaN (NaN never compares equal, so it can only cost a redundant bucket).
+impl Hash for Expression {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Expression::Number(n, radix) => {
+                n.hash(state);
+                radix.hash(state);
+            }
+            Expression::Float(f) => f.to_bits().hash(state),
+            Expression::String(s) => s.hash(state),
+            Expression::Boolean(b) => b.hash(state),
+            Expression::InterpolatedString(parts) => parts.hash(state),
+            Expression::Tuple(elements) | Expression::List(elements) | Expression::Program(elements)
+            | Expression::Block(elements) => elements.hash(state),
+            Expression::Map(entries) => entries.hash(state),
+            Expression::Identifier(name) => name.hash(state),
+            Expression::QualifiedName { path, name } => {
+                path.hash(state);
+                name.hash(state);
+            }
+            Expression::FunctionCall { function, arguments } => {
+                function.hash(state);
+                arguments.hash(state);
+            }
+            Expression::FieldAccess { object, field } => {
+                object.hash(state);
+                field.hash(state);
+            }
+            Expression::FunctionDefinition { name, parameters, body, visibility } => {
+                name.hash(state);
+                parameters.hash(state);
+                body.hash(state);
+                visibility.hash(state);
+            }
+            Expression::BinaryOp { left, operator, right } => {
+                left.hash(state);
+                operator.hash(state);
+                right.hash(state);
+            }
+            Expression::LogCall { level, message } => {
+                level.hash(state);
+                message.hash(state);
+            }
+            Expression::Cond { conditions, default_statements, from_if } => {
+                conditions.hash(state);
+                default_statements.hash(state);
+                from_if.hash(state);
+            }
+            Expression::None => {}
+            Expression::Some { value } | Expression::Ok { value } => value.hash(state),
+            Expression::Err { error } => error.hash(state),
+            Expression::Propagate { expr } | Expression::Try { expr } | Expression::Not { expr } => expr.hash(state),
+            Expression::Kernel { definition } => definition.hash(state),
+            Expression::Convert { value, target } => {
+                value.hash(state);
+                target.hash(state);
+            }
+            Expression::Match { value, arms } => {
+                value.hash(state);
+                arms.hash(state);
+            }
+            Expression::IfLet { pattern, value, then_branch, else_branch } => {
+                pattern.hash(state);
+                value.hash(state);
+                then_branch.hash(state);
+                else_branch.hash(state);
+            }
+            Expression::WhileLet { pattern, value, body } => {
+                pattern.hash(state);
+                value.hash(state);
+                body.hash(state);
+            }
+            Expression::For { variable, iterable, body } => {
+                variable.hash(state);
+                iterable.hash(state);
+                body.hash(state);
+            }
+            Expression::Lambda { parameters, body } => {
+                parameters.hash(state);
+                body.hash(state);
+            }
+            Expression::StructDefinition { name, fields, visibility } => {
+                name.hash(state);
+                fields.hash(state);
+                visibility.hash(state);
+            }
+            Expression::EnumDefinition { name, variants, visibility } => {
+                name.hash(state);
+                variants.hash(state);
+                visibility.hash(state);
+            }
+            Expression::TraitDefinition { name, methods, visibility } => {
+                name.hash(state);
+                methods.hash(state);
+                visibility.hash(state);
+            }
+            Expression::Impl { trait_name, type_name, methods } => {
+                trait_name.hash(state);
+                type_name.hash(state);
+                methods.hash(state);
+            }
+            Expression::Import(path) => path.hash(state),
+            Expression::StructInstantiation { struct_name, field_values } => {
+                struct_name.hash(state);
+                field_values.hash(state);
+            }
+            Expression::Let { name, value, body } => {
+                name.hash(state);
+                value.hash(state);
+                body.hash(state);
+            }
+            Expression::LetPattern { pattern, value, fallback } => {
+                pattern.hash(state);
+                value.hash(state);
+                fallback.hash(state);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Hash)]
+#[allow(dead_code)]
+pub enum Operator {
+    Add = 1,
+    Subtract = 2,
+    Multiply = 3,
+    Divide = 4,
+    Power = 5,
+    Equals = 6,
+    NotEquals = 7,
+    LessThan = 8,
+    GreaterThan = 9,
+    And = 10,
+    Or = 11,
+    BitAnd = 12,
+    BitOr = 13,
+    BitXor = 14,
+    ShiftLeft = 15,
+    ShiftRight = 16,
+    LessEqual = 17,
+    GreaterEqual = 18,
+}
@@ -0,0 +1,121 @@
+//! Code Generation Backends
+//!
+//! A backend turns a loaded, optimized program into the files to write and
+//! the command, if any, that builds them into something to run or link.
+//! `w --backend <name>` picks one from `BACKENDS`; the command line only
+//! writes the files and runs the build, so a new target is a new
+//! `CodegenBackend` here rather than another branch in main.rs.
+//!
+//! Rust is the only backend so far.
+
+use crate::imports::LoadedProgram;
+use crate::rust_codegen::{CodegenOptions, GeneratedFile, RustCodeGenerator};
+use crate::type_inference::TypeTable;
+use std::fmt;
+
+/// Backend used when `--backend` is not given
+pub const DEFAULT_BACKEND: &str = "rust";
+
+/// Names accepted by `--backend`
+pub const BACKENDS: &[&str] = &["rust"];
+
+/// Options shared by every backend
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BackendOptions {
+    /// Layout and target of the generated Rust; other backends read what
+    /// applies to them
+    pub codegen: CodegenOptions,
+    /// Write each imported module as its own file instead of one file
+    pub split_modules: bool,
+    /// Types of the program and its modules from type checking; without
+    /// them the backend checks the program itself
+    pub types: Option<TypeTable>,
+}
+
+/// A command to run once the files are written, such as `rustc generated.rs`
+#[derive(Debug, Clone, PartialEq)]
+pub struct BuildStep {
+    /// Program followed by its arguments
+    pub command: Vec<String>,
+    /// Files the command writes
+    pub outputs: Vec<String>,
+    /// What to do with the result, e.g. "Run ./output to see the result."
+    pub next_step: String,
+}
+
+/// What a backend produced
+#[derive(Debug, Clone, PartialEq)]
+pub struct Artifacts {
+    /// Files to write, relative to the working directory; the first is the
+    /// entry point
+    pub files: Vec<GeneratedFile>,
+    pub build: Option<BuildStep>,
+}
+
+/// A target W compiles to
+pub trait CodegenBackend {
+    /// Name given to `--backend`
+    fn name(&self) -> &'static str;
+
+    /// Generate `program`, whose modules are in dependency order
+    fn generate(&mut self, program: &LoadedProgram, options: &BackendOptions) -> Result<Artifacts, fmt::Error>;
+}
+
+/// The backend called `name`, if there is one
+pub fn backend(name: &str) -> Option<Box<dyn CodegenBackend>> {
+    match name {
+        "rust" => Some(Box::new(RustBackend)),
+        _ => None,
+    }
+}
+
+/// Rust source built with rustc: `generated.rs` (or `generated_modules/`
+/// with `split_modules`) compiled to `output`, or to `libgenerated.rlib`
+/// for `no_std`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RustBackend;
+
+impl CodegenBackend for RustBackend {
+    fn name(&self) -> &'static str {
+        "rust"
+    }
+
+    fn generate(&mut self, program: &LoadedProgram, options: &BackendOptions) -> Result<Artifacts, fmt::Error> {
+        let mut builder = RustCodeGenerator::builder().options(options.codegen.clone());
+        if let Some(types) = &options.types {
+            builder = builder.types(types.clone());
+        }
+        let mut codegen = builder.build();
+        let (entry, modules) = (&program.entry, &program.modules);
+        let files = if modules.is_empty() {
+            vec![GeneratedFile { path: "generated.rs".to_string(), contents: codegen.generate(entry)? }]
+        } else if options.split_modules {
+            codegen
+                .generate_modules(entry, modules)?
+                .into_iter()
+                .map(|file| GeneratedFile { path: format!("generated_modules/{}", file.path), ..file })
+                .collect()
+        } else {
+            vec![GeneratedFile {
+                path: "generated.rs".to_string(),
+                contents: codegen.generate_single_file(entry, modules)?,
+            }]
+        };
+
+        // Compile starting from the crate root. A no_std program is a
+        // library for another crate to link
+        let mut command = vec!["rustc".to_string(), files[0].path.clone()];
+        let (arguments, output, next_step): (&[&str], _, _) = if options.codegen.is_no_std() {
+            (
+                &["--crate-type", "lib", "--crate-name", "generated", "--edition", "2021", "-o"],
+                "libgenerated.rlib",
+                "Link libgenerated.rlib into a no_std crate to use it.",
+            )
+        } else {
+            (&["-o"], "output", "Run ./output to see the result.")
+        };
+        command.extend(arguments.iter().chain([&output]).map(|argument| argument.to_string()));
+        let build = BuildStep { command, outputs: vec![output.to_string()], next_step: next_step.to_string() };
+        Ok(Artifacts { files, build: Some(build) })
+    }
+}
@@ -0,0 +1,122 @@
+//! Build Plans
+//!
+//! `w --emit build-plan <file.w>` describes a compilation without carrying
+//! it out: the source files it reads (the entry and every file it imports),
+//! the files it writes, and the external commands it would run, as JSON on
+//! stdout. Build systems such as Bazel or Buck declare these as the inputs,
+//! outputs and actions of a hermetic build step.
+//!
+//! ```json
+//! {
+//!   "backend": "rust",
+//!   "inputs": ["main.w", "geometry.w"],
+//!   "imports": [{"module": "Geometry", "path": "geometry.w"}],
+//!   "outputs": ["generated.rs", "output"],
+//!   "commands": [["rustc", "generated.rs", "-o", "output"]]
+//! }
+//! ```
+//!
+//! Paths under the working directory are relative to it; others are
+//! absolute.
+
+use crate::backend::Artifacts;
+use crate::imports::LoadedProgram;
+use std::path::Path;
+
+/// One imported file and the module it becomes
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlannedImport {
+    pub module: String,
+    pub path: String,
+}
+
+/// What compiling a program reads, writes and runs
+#[derive(Debug, Clone, PartialEq)]
+pub struct BuildPlan {
+    pub backend: String,
+    /// The entry file, then each imported file in dependency order
+    pub inputs: Vec<String>,
+    pub imports: Vec<PlannedImport>,
+    /// Generated files, then the files the commands write
+    pub outputs: Vec<String>,
+    /// Each command as its program followed by its arguments, in the order run
+    pub commands: Vec<Vec<String>>,
+}
+
+impl BuildPlan {
+    /// The plan for `program`, read from `entry_path`, that a backend
+    /// generated `artifacts` for. `working_dir` is where the compiler runs.
+    pub fn new(
+        backend: &str,
+        entry_path: &Path,
+        program: &LoadedProgram,
+        artifacts: &Artifacts,
+        working_dir: &Path,
+    ) -> Self {
+        let imports: Vec<PlannedImport> = program
+            .modules
+            .iter()
+            .zip(&program.module_paths)
+            .map(|((module, _), path)| PlannedImport { module: module.clone(), path: relative_to(path, working_dir) })
+            .collect();
+        let mut outputs: Vec<String> = artifacts.files.iter().map(|file| file.path.clone()).collect();
+        outputs.extend(artifacts.build.iter().flat_map(|build| build.outputs.iter().cloned()));
+        BuildPlan {
+            backend: backend.to_string(),
+            inputs: std::iter::once(relative_to(entry_path, working_dir))
+                .chain(imports.iter().map(|import| import.path.clone()))
+                .collect(),
+            imports,
+            outputs,
+            commands: artifacts.build.iter().map(|build| build.command.clone()).collect(),
+        }
+    }
+
+    /// The plan as a JSON object, one field per line
+    pub fn to_json(&self) -> String {
+        let list = |items: &[String]| {
+            let items: Vec<String> = items.iter().map(|item| json_string(item)).collect();
+            format!("[{}]", items.join(", "))
+        };
+        let imports: Vec<String> = self
+            .imports
+            .iter()
+            .map(|import| format!("{{\"module\": {}, \"path\": {}}}", json_string(&import.module), json_string(&import.path)))
+            .collect();
+        let commands: Vec<String> = self.commands.iter().map(|command| list(command)).collect();
+
+        format!(
+            "{{\n  \"backend\": {},\n  \"inputs\": {},\n  \"imports\": [{}],\n  \"outputs\": {},\n  \"commands\": [{}]\n}}\n",
+            json_string(&self.backend),
+            list(&self.inputs),
+            imports.join(", "),
+            list(&self.outputs),
+            commands.join(", ")
+        )
+    }
+}
+
+/// `path` relative to `base` if it is inside it, otherwise as an absolute path
+fn relative_to(path: &Path, base: &Path) -> String {
+    let absolute = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let base = base.canonicalize().unwrap_or_else(|_| base.to_path_buf());
+    absolute.strip_prefix(&base).unwrap_or(&absolute).display().to_string()
+}
+
+/// `text` as a JSON string literal
+fn json_string(text: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if (c as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
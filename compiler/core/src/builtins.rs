@@ -0,0 +1,114 @@
+//! Builtin Registry
+//!
+//! Functions reachable through qualified names such as `Math.Sqrt[x]`,
+//! together with the Rust path each one compiles to.
+
+use std::sync::LazyLock;
+
+use crate::ast::Type;
+
+/// A builtin function living in a W namespace
+#[derive(Debug, Clone, PartialEq)]
+pub struct BuiltinFunction {
+    /// Namespace the function is reached through, e.g. `Math`
+    pub module: &'static str,
+    /// W name of the function, e.g. `Sqrt`
+    pub name: &'static str,
+    /// Rust path the call is generated as, e.g. `f64::sqrt`
+    pub rust_path: &'static str,
+    pub parameter_types: Vec<Type>,
+    pub return_type: Type,
+}
+
+impl BuiltinFunction {
+    pub fn arity(&self) -> usize {
+        self.parameter_types.len()
+    }
+}
+
+fn math(name: &'static str, rust_path: &'static str, arity: usize) -> BuiltinFunction {
+    BuiltinFunction {
+        module: "Math",
+        name,
+        rust_path,
+        parameter_types: vec![Type::Float64; arity],
+        return_type: Type::Float64,
+    }
+}
+
+/// Adjacency list used by the `Graph` namespace: node id to neighbours
+pub fn graph_type() -> Type {
+    Type::Map(Box::new(Type::Int32), Box::new(Type::List(Box::new(Type::Int32))))
+}
+
+fn graph(name: &'static str, rust_path: &'static str, parameter_types: Vec<Type>, return_type: Type) -> BuiltinFunction {
+    BuiltinFunction { module: "Graph", name, rust_path, parameter_types, return_type }
+}
+
+/// Every builtin function, grouped by namespace
+pub static BUILTIN_FUNCTIONS: LazyLock<Vec<BuiltinFunction>> = LazyLock::new(|| {
+    let edges = Type::List(Box::new(Type::Tuple(vec![Type::Int32, Type::Int32])));
+    let nodes = Type::List(Box::new(Type::Int32));
+    vec![
+        math("Sqrt", "f64::sqrt", 1),
+        math("Abs", "f64::abs", 1),
+        math("Floor", "f64::floor", 1),
+        math("Ceil", "f64::ceil", 1),
+        math("Round", "f64::round", 1),
+        math("Exp", "f64::exp", 1),
+        math("Ln", "f64::ln", 1),
+        math("Sin", "f64::sin", 1),
+        math("Cos", "f64::cos", 1),
+        math("Tan", "f64::tan", 1),
+        math("Pow", "f64::powf", 2),
+        math("Min", "f64::min", 2),
+        math("Max", "f64::max", 2),
+        graph("FromEdges", "__w_graph::from_edges", vec![edges.clone()], graph_type()),
+        graph("Undirected", "__w_graph::undirected", vec![edges], graph_type()),
+        graph("Bfs", "__w_graph::bfs", vec![graph_type(), Type::Int32], nodes.clone()),
+        graph("Dfs", "__w_graph::dfs", vec![graph_type(), Type::Int32], nodes.clone()),
+        graph(
+            "ShortestPath",
+            "__w_graph::shortest_path",
+            vec![graph_type(), Type::Int32, Type::Int32],
+            Type::Option(Box::new(nodes)),
+        ),
+    ]
+});
+
+/// Look up a builtin by its qualified name (`path` excludes the item name)
+pub fn lookup(path: &[String], name: &str) -> Option<&'static BuiltinFunction> {
+    match path {
+        [module] => BUILTIN_FUNCTIONS.iter().find(|f| f.module == module && f.name == name),
+        _ => None,
+    }
+}
+
+/// Whether generated code for `builtin` needs the standard library: float
+/// math beyond `abs`/`min`/`max` lives in std, and graphs are HashMaps
+pub fn requires_std(builtin: &BuiltinFunction) -> bool {
+    match builtin.module {
+        "Math" => !matches!(builtin.name, "Abs" | "Min" | "Max"),
+        _ => true,
+    }
+}
+
+/// Check whether `module` names a builtin namespace
+pub fn is_builtin_module(module: &str) -> bool {
+    BUILTIN_FUNCTIONS.iter().any(|f| f.module == module)
+}
+
+/// Rust source a namespace's builtins call into, as `(module name, source)`.
+/// It is emitted once into each generated file that uses the namespace.
+pub fn support_module(module: &str) -> Option<(&'static str, &'static str)> {
+    match module {
+        "Graph" => Some(("__w_graph", include_str!("stdlib/graph.rs"))),
+        // Not a namespace: emitted for `Convert` to an integer type
+        "Convert" => Some(("__w_convert", include_str!("stdlib/convert.rs"))),
+        // Not a namespace: emitted for checked or wrapping arithmetic
+        "Arithmetic" => Some(("__w_arithmetic", include_str!("stdlib/arithmetic.rs"))),
+        // Not a namespace: emitted for LogDebug, LogInfo, LogWarn and LogError
+        "Log" => Some(("__w_log", include_str!("stdlib/log.rs"))),
+        _ => None,
+    }
+}
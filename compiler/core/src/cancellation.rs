@@ -0,0 +1,37 @@
+//! Cancellation
+//!
+//! Editors start a new analysis on every keystroke, so a long parse or type
+//! check of the previous text should stop as soon as it is out of date. A
+//! `CancellationToken` is a shared flag: the embedder keeps one clone and
+//! calls `cancel`, while the parser and type inference check their clone as
+//! they go and give up at the next token or expression.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Shared flag asking an in-flight compilation to stop. Clones share the flag.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// A token that has not been cancelled
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ask every pass holding a clone of this token to stop
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+impl From<Arc<AtomicBool>> for CancellationToken {
+    /// Use an existing flag, e.g. one an editor already sets on new input
+    fn from(flag: Arc<AtomicBool>) -> Self {
+        CancellationToken(flag)
+    }
+}
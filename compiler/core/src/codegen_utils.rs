@@ -0,0 +1,339 @@
+//! Codegen Utilities
+//!
+//! Helpers shared by the Rust code generator: identifier mangling and
+//! hygienic names for compiler-introduced temporaries.
+
+/// Prefix reserved for identifiers introduced by the compiler.
+///
+/// A W identifier starts with a letter, or with one `_` and a letter (`_`
+/// alone and `__` lex as the wildcard), and `to_snake_case` never puts an
+/// underscore next to another, so no mangled user identifier can begin with
+/// this prefix: `_W_0_cse` becomes `_w_0_cse`.
+pub const GENSYM_PREFIX: &str = "__w_";
+
+/// Generator for hygienic temporary names.
+///
+/// Every name returned by `fresh` is unique for the lifetime of the generator
+/// and can never collide with a user identifier after snake_case mangling.
+#[derive(Debug, Default, Clone)]
+pub struct Gensym {
+    counter: usize,
+}
+
+impl Gensym {
+    pub fn new() -> Self {
+        Gensym { counter: 0 }
+    }
+
+    /// Produce a fresh temporary name, e.g. `__w_0_map` for the hint `map`.
+    ///
+    /// The hint only exists to make the generated code readable.
+    pub fn fresh(&mut self, hint: &str) -> String {
+        let name = format!("{}{}_{}", GENSYM_PREFIX, self.counter, hint);
+        self.counter += 1;
+        name
+    }
+
+    /// Restart numbering (used at the start of each `generate` call so output
+    /// is identical across runs)
+    pub fn reset(&mut self) {
+        self.counter = 0;
+    }
+}
+
+/// Check whether a Rust identifier was produced by `Gensym`
+pub fn is_gensym(name: &str) -> bool {
+    name.starts_with(GENSYM_PREFIX)
+}
+
+/// Convert PascalCase or camelCase to snake_case
+///
+/// No `_` is added after one that is already there, so `_Name` becomes
+/// `_name` rather than `__name`.
+pub fn to_snake_case(s: &str) -> String {
+    let mut result = String::new();
+    let mut prev_is_upper = false;
+
+    for (i, c) in s.chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 && !prev_is_upper && !result.ends_with('_') {
+                result.push('_');
+            }
+            result.push(c.to_ascii_lowercase());
+            prev_is_upper = true;
+        } else {
+            result.push(c);
+            prev_is_upper = false;
+        }
+    }
+
+    result
+}
+
+/// Render a string as a Rust string literal.
+///
+/// Text containing `"` or `\` but no control characters becomes a raw string
+/// (`r#"..."#`) so it stays readable; everything else is an escaped literal.
+/// The result is safe to use as a `format!` template only after braces have
+/// been doubled with `escape_format_braces`.
+pub fn rust_string_literal(s: &str) -> String {
+    let needs_escaping = s.contains('"') || s.contains('\\');
+    if needs_escaping && !s.chars().any(char::is_control) {
+        // One more `#` than the longest `"#...` run inside the string
+        let mut hashes = 0;
+        let mut run: Option<usize> = None;
+        for c in s.chars() {
+            run = match (c, run) {
+                ('"', _) => Some(0),
+                ('#', Some(n)) => Some(n + 1),
+                _ => None,
+            };
+            if let Some(n) = run {
+                hashes = hashes.max(n + 1);
+            }
+        }
+        let fence = "#".repeat(hashes);
+        format!("r{}\"{}\"{}", fence, s, fence)
+    } else {
+        format!("{:?}", s)
+    }
+}
+
+/// Double `{` and `}` so text is printed verbatim by `format!`-style macros
+pub fn escape_format_braces(s: &str) -> String {
+    s.replace('{', "{{").replace('}', "}}")
+}
+
+/// Stable 64-bit FNV-1a hash of source text.
+///
+/// Unlike `DefaultHasher`, the result is fixed across Rust versions and
+/// platforms, so it can be recorded in checked-in generated code.
+pub fn source_hash(source: &str) -> u64 {
+    fnv1a64(source.bytes())
+}
+
+/// Stable 64-bit FNV-1a hash of several source texts, in order.
+///
+/// A `0xff` byte, which never occurs in UTF-8, separates the sources, so
+/// moving text from one to the next changes the hash. One source hashes to
+/// its `source_hash`.
+pub fn sources_hash(sources: &[&str]) -> u64 {
+    fnv1a64(sources.iter().enumerate().flat_map(|(i, source)| (i > 0).then_some(0xff).into_iter().chain(source.bytes())))
+}
+
+fn fnv1a64(bytes: impl Iterator<Item = u8>) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    bytes.fold(OFFSET_BASIS, |hash, byte| (hash ^ byte as u64).wrapping_mul(PRIME))
+}
+
+/// Header comment identifying how a generated file was produced: from
+/// `input_file`, with a hash of `sources`, every W file the program was
+/// compiled from
+pub fn provenance_header(compiler_version: &str, input_file: &str, sources: &[&str], flags: &[String]) -> String {
+    let flags = if flags.is_empty() { "(none)".to_string() } else { flags.join(" ") };
+    let inputs = if sources.len() > 1 { format!("{} inputs, ", sources.len()) } else { String::new() };
+    format!(
+        "// Generated by w {}\n// Source: {} ({}fnv1a64: {:016x})\n// Flags: {}\n// Do not edit: regenerate from the W source instead.",
+        compiler_version,
+        input_file,
+        inputs,
+        sources_hash(sources),
+        flags
+    )
+}
+
+/// How a program's generated files were produced, recorded in a header
+/// comment at the top of each
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Provenance {
+    pub compiler_version: String,
+    /// Command line flags that affect the generated code
+    pub flags: Vec<String>,
+    /// `(file, source)` of each W input: the entry file first, then the
+    /// files it imports in dependency order
+    pub inputs: Vec<(String, String)>,
+}
+
+impl Provenance {
+    /// Header of the file generated from `inputs[input]`; its hash covers
+    /// every input, since each generated file depends on the others' types
+    pub fn header(&self, input: usize) -> String {
+        let sources: Vec<&str> = self.inputs.iter().map(|(_, source)| source.as_str()).collect();
+        let file = self.inputs.get(input).map_or("", |(file, _)| file.as_str());
+        provenance_header(&self.compiler_version, file, &sources, &self.flags)
+    }
+}
+
+/// Break lines of generated Rust that are longer than `max_width` characters.
+///
+/// A chain of method calls is split into one call per line; otherwise the
+/// widest bracketed group on the line is split into one comma-separated
+/// element per line. Pieces that are still too long are split again, and
+/// continuation lines are indented by `indent_width` more spaces. Lines are
+/// only broken between tokens, never inside a literal or comment, so the
+/// result compiles exactly like the input. A line with no place to break is
+/// left as it is.
+pub fn wrap_long_lines(source: &str, max_width: usize, indent_width: usize) -> String {
+    let mut lines = Vec::new();
+    for line in source.split('\n') {
+        wrap_line(line, max_width, indent_width, &mut lines);
+    }
+    lines.join("\n")
+}
+
+/// Push `line`, wrapped to `max_width`, onto `out`
+fn wrap_line(line: &str, max_width: usize, indent_width: usize, out: &mut Vec<String>) {
+    let body = line.trim_start();
+    let indent = &line[..line.len() - body.len()];
+    let breaks = match line_breaks(body) {
+        Some(breaks) if line.chars().count() > max_width => breaks,
+        _ => {
+            out.push(line.to_string());
+            return;
+        }
+    };
+    let continuation = format!("{}{}", indent, " ".repeat(indent_width));
+
+    // `receiver.first()` stays together, then one call per line
+    let calls: Vec<usize> = breaks.calls.iter().filter(|&&(_, depth)| depth == 0).map(|&(at, _)| at).collect();
+    if calls.len() >= 2 {
+        wrap_line(&format!("{}{}", indent, &body[..calls[1]]), max_width, indent_width, out);
+        for (i, &start) in calls.iter().enumerate().skip(1) {
+            let end = calls.get(i + 1).copied().unwrap_or(body.len());
+            wrap_line(&format!("{}{}", continuation, &body[start..end]), max_width, indent_width, out);
+        }
+        return;
+    }
+
+    let widest_group = breaks.groups.iter()
+        .filter(|&&(open, close, depth)| depth == 0 && !body[open + 1..close].trim().is_empty())
+        .max_by_key(|&&(open, close, _)| close - open);
+    if let Some(&(open, close, _)) = widest_group {
+        wrap_line(&format!("{}{}", indent, &body[..=open]), max_width, indent_width, out);
+        let mut start = open + 1;
+        for &(comma, _) in breaks.commas.iter().filter(|&&(at, depth)| depth == 1 && open < at && at < close) {
+            wrap_line(&format!("{}{}", continuation, body[start..=comma].trim()), max_width, indent_width, out);
+            start = comma + 1;
+        }
+        let last = body[start..close].trim();
+        if !last.is_empty() {
+            wrap_line(&format!("{}{}", continuation, last), max_width, indent_width, out);
+        }
+        wrap_line(&format!("{}{}", indent, &body[close..]), max_width, indent_width, out);
+        return;
+    }
+
+    out.push(line.to_string());
+}
+
+/// Places a line of generated Rust can be broken, each with the number of
+/// brackets open before it
+#[derive(Debug, Default)]
+struct LineBreaks {
+    /// Byte offsets of bracket pairs opened and closed on the line
+    groups: Vec<(usize, usize, usize)>,
+    commas: Vec<(usize, usize)>,
+    /// The `.` starting each method call
+    calls: Vec<(usize, usize)>,
+}
+
+/// Find the break points of one line, skipping literals and stopping at a
+/// comment. `None` if a string or char literal does not end on the line.
+fn line_breaks(line: &str) -> Option<LineBreaks> {
+    let chars: Vec<(usize, char)> = line.char_indices().collect();
+    let at = |i: usize| chars.get(i).map(|&(_, c)| c);
+    let mut breaks = LineBreaks::default();
+    let mut open = Vec::new();
+    // Generic arguments (`HashMap<K, V>`), whose commas are not break points
+    let mut generics = 0;
+    let mut i = 0;
+    while i < chars.len() {
+        let (byte, c) = chars[i];
+        match c {
+            '"' => {
+                i += 1;
+                loop {
+                    match at(i)? {
+                        '\\' => i += 2,
+                        '"' => break,
+                        _ => i += 1,
+                    }
+                }
+            }
+            'r' if !i.checked_sub(1).and_then(at).is_some_and(is_ident_char) => {
+                // Raw string `r#"..."#`; `r#name` is a raw identifier
+                let hashes = chars[i + 1..].iter().take_while(|&&(_, c)| c == '#').count();
+                if at(i + hashes + 1) == Some('"') {
+                    i += hashes + 2;
+                    while !(at(i)? == '"' && (1..=hashes).all(|k| at(i + k) == Some('#'))) {
+                        i += 1;
+                    }
+                    i += hashes;
+                }
+            }
+            '\'' => {
+                if at(i + 1) == Some('\\') {
+                    i += 3;
+                    while at(i)? != '\'' {
+                        i += 1;
+                    }
+                } else if at(i + 2) == Some('\'') {
+                    i += 2;
+                }
+                // Anything else is a lifetime
+            }
+            '/' if matches!(at(i + 1), Some('/' | '*')) => break,
+            '(' | '[' | '{' => open.push(byte),
+            ')' | ']' | '}' => {
+                if let Some(start) = open.pop() {
+                    breaks.groups.push((start, byte, open.len()));
+                }
+            }
+            '<' if i.checked_sub(1).and_then(at).is_some_and(|c| is_ident_char(c) || c == ':') => generics += 1,
+            '>' if generics > 0 && !matches!(i.checked_sub(1).and_then(at), Some('-' | '=')) => generics -= 1,
+            ',' if generics == 0 => breaks.commas.push((byte, open.len())),
+            '.' if is_method_call(&chars, i) => breaks.calls.push((byte, open.len())),
+            _ => {}
+        }
+        i += 1;
+    }
+    Some(breaks)
+}
+
+/// Whether the `.` at `chars[i]` starts `.name(` or `.name::<T>(`
+fn is_method_call(chars: &[(usize, char)], i: usize) -> bool {
+    let at = |i: usize| chars.get(i).map(|&(_, c)| c);
+    if i == 0 || at(i - 1) == Some('.') || !at(i + 1).is_some_and(|c| c.is_alphabetic() || c == '_') {
+        return false;
+    }
+    let mut j = i + 1;
+    while at(j).is_some_and(is_ident_char) {
+        j += 1;
+    }
+    if at(j) == Some(':') && at(j + 1) == Some(':') && at(j + 2) == Some('<') {
+        let mut depth = 0;
+        j += 2;
+        loop {
+            match at(j) {
+                Some('<') => depth += 1,
+                Some('>') => {
+                    depth -= 1;
+                    if depth == 0 {
+                        j += 1;
+                        break;
+                    }
+                }
+                Some(_) => {}
+                None => return false,
+            }
+            j += 1;
+        }
+    }
+    at(j) == Some('(')
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
@@ -0,0 +1,290 @@
+//! Constant Evaluation
+//!
+//! Integer arithmetic on literals is evaluated before code generation, and
+//! what is certain to fail is an error, rather than a panic when the program
+//! runs or rustc rejecting the generated code: division by an expression that
+//! is always zero, arithmetic whose result does not fit its type, and Array
+//! types too large for any value of them to exist.
+//!
+//! A literal takes its type from where it is used, so `2147483647 + 1` is
+//! only known to overflow where nothing else decides its type and it is an
+//! Int32: as a statement of its own, bound by `Let`, printed, converted,
+//! returned by a function, or compared with another constant. `^` always computes in
+//! Int32. Values bound by `Let` are not followed; the lints warn about those.
+
+use crate::ast::{Expression, Operator, Type, TypeAnnotation};
+use crate::lexer::Span;
+use std::fmt;
+
+/// What constant evaluation found
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConstErrorKind {
+    /// Integer division by an expression that is always zero
+    DivisionByZero,
+    /// `left operator right` on constants, whose result does not fit `type_`
+    Overflow { left: i128, operator: &'static str, right: i128, type_: Type },
+    /// An Array type whose values take at least `bytes` bytes, more than
+    /// rustc allows for one value on a 64-bit target
+    ArrayTooLarge { type_: Type, bytes: u128 },
+}
+
+impl ConstErrorKind {
+    /// Code explained by `w --explain`
+    pub fn code(&self) -> &'static str {
+        match self {
+            ConstErrorKind::DivisionByZero => "E0202",
+            ConstErrorKind::Overflow { .. } => "E0203",
+            ConstErrorKind::ArrayTooLarge { .. } => "E0204",
+        }
+    }
+}
+
+/// A constant evaluation error, located at the top-level expression
+/// containing it
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConstError {
+    pub kind: ConstErrorKind,
+    /// Function whose body contains the problem, if any
+    pub function: Option<String>,
+    /// Start of the top-level expression, when the parser's spans were given
+    pub span: Option<Span>,
+}
+
+impl ConstError {
+    pub fn code(&self) -> &'static str {
+        self.kind.code()
+    }
+}
+
+impl fmt::Display for ConstError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let within = self.function.as_ref().map(|function| format!(" in {}", function)).unwrap_or_default();
+        match &self.kind {
+            ConstErrorKind::DivisionByZero => write!(f, "Division by zero{}: the divisor is always 0", within),
+            ConstErrorKind::Overflow { left, operator, right, type_ } => {
+                write!(f, "{} {} {}{} overflows {}, so it always panics", left, operator, right, within, type_)
+            }
+            ConstErrorKind::ArrayTooLarge { type_, bytes } => write!(
+                f,
+                "{}{} needs at least {} bytes, more than rustc allows for one value",
+                type_, within, bytes
+            ),
+        }
+    }
+}
+
+/// Every constant evaluation error in `program`, in source order. `spans`
+/// are the parser's `expression_spans`, one per top-level expression (empty
+/// if unknown).
+pub fn check_constants(program: &Expression, spans: &[Span]) -> Vec<ConstError> {
+    let expressions = match program {
+        Expression::Program(expressions) => expressions.as_slice(),
+        other => std::slice::from_ref(other),
+    };
+    let mut errors = Vec::new();
+    for (i, expr) in expressions.iter().enumerate() {
+        let mut checker = Checker { function: None, span: spans.get(i).copied(), errors: &mut errors };
+        checker.check(expr, true);
+    }
+    errors
+}
+
+/// Largest size of a value that rustc accepts on a 64-bit target: below
+/// 2^61 bytes, which keeps bit offsets within `isize::MAX`. Larger Arrays
+/// are rejected by rustc as too big for the target architecture.
+const MAX_SIZE: u128 = (1 << 61) - 1;
+
+struct Checker<'a> {
+    function: Option<String>,
+    span: Option<Span>,
+    errors: &'a mut Vec<ConstError>,
+}
+
+impl Checker<'_> {
+    fn report(&mut self, kind: ConstErrorKind) {
+        self.errors.push(ConstError { kind, function: self.function.clone(), span: self.span });
+    }
+
+    /// Check `expr`; `int32` says a constant integer there is an Int32
+    fn check(&mut self, expr: &Expression, int32: bool) {
+        match expr {
+            Expression::BinaryOp { left, operator, right } => {
+                match evaluate(expr, int32) {
+                    Err(kind) => return self.report(kind),
+                    Ok(Some(_)) => return,
+                    Ok(None) => {}
+                }
+                if *operator == Operator::Divide && evaluate(right, false) == Ok(Some(0)) {
+                    self.report(ConstErrorKind::DivisionByZero);
+                }
+                // Each operand takes the other's type, so two constants
+                // compared are Int32s
+                let both_constant = is_constant(left) && is_constant(right);
+                self.check(left, both_constant);
+                self.check(right, both_constant);
+            }
+            Expression::FunctionDefinition { name, parameters, body, .. } => {
+                self.check_parameters(parameters);
+                let enclosing = self.function.replace(name.clone());
+                self.check(body, true);
+                self.function = enclosing;
+            }
+            Expression::Lambda { parameters, body } => {
+                self.check_parameters(parameters);
+                self.check(body, false);
+            }
+            Expression::LetPattern { value, fallback, .. } => {
+                self.check(value, true);
+                if let Some(fallback) = fallback {
+                    self.check(fallback, false);
+                }
+            }
+            Expression::Let { value, body, .. } => {
+                self.check(value, true);
+                self.check(body, int32);
+            }
+            Expression::FunctionCall { function, arguments }
+                if matches!(function.as_ref(), Expression::Identifier(name) if matches!(name.as_str(), "Print" | "PrintErr" | "PrintInline")) =>
+            {
+                arguments.iter().for_each(|argument| self.check(argument, true));
+            }
+            Expression::StructDefinition { fields, .. } => self.check_parameters(fields),
+            Expression::EnumDefinition { variants, .. } => {
+                variants.iter().flat_map(|variant| &variant.fields).for_each(|type_| self.check_type(type_));
+            }
+            Expression::TraitDefinition { methods, .. } => {
+                for method in methods {
+                    self.check_parameters(&method.parameters);
+                    self.check_type(&method.return_type);
+                }
+            }
+            Expression::Convert { value, target } => {
+                self.check_type(target);
+                self.check(value, true);
+            }
+            Expression::Program(expressions) => expressions.iter().for_each(|expr| self.check(expr, true)),
+            _ => expr.for_each_child(&mut |child| self.check(child, false)),
+        }
+    }
+
+    fn check_parameters(&mut self, parameters: &[TypeAnnotation]) {
+        for parameter in parameters {
+            self.check_type(&parameter.type_);
+            if let Some(default) = &parameter.default {
+                self.check(default, false);
+            }
+        }
+    }
+
+    fn check_type(&mut self, type_: &Type) {
+        let bytes = minimum_size(type_);
+        if bytes > MAX_SIZE {
+            self.report(ConstErrorKind::ArrayTooLarge { type_: type_.clone(), bytes });
+        }
+    }
+}
+
+/// The value of integer arithmetic on literals, `None` if `expr` is not
+/// that. With `int32`, results outside Int32 are an overflow; otherwise only
+/// division by zero is an error, as the type is not known.
+fn evaluate(expr: &Expression, int32: bool) -> Result<Option<i128>, ConstErrorKind> {
+    let Expression::BinaryOp { left, operator, right } = expr else {
+        return Ok(match expr {
+            Expression::Number(n, _) => Some(i128::from(*n)),
+            _ => None,
+        });
+    };
+    // An operand takes its type from the other, unless that is a variable
+    if !is_constant(left) || !is_constant(right) {
+        return Ok(None);
+    }
+    // `^` converts both operands and computes in Int32 whatever its context,
+    // and a literal beyond Int32 makes the arithmetic Int64
+    let power = *operator == Operator::Power;
+    let int32 = int32 && fits_int32(left) && fits_int32(right);
+    let (Some(l), Some(r)) = (evaluate(left, int32 && !power)?, evaluate(right, int32 && !power)?) else {
+        return Ok(None);
+    };
+    let overflow = |type_: Type| ConstErrorKind::Overflow { left: l, operator: symbol(operator), right: r, type_ };
+    let value = match operator {
+        Operator::Add => l.checked_add(r),
+        Operator::Subtract => l.checked_sub(r),
+        Operator::Multiply => l.checked_mul(r),
+        Operator::Divide if r == 0 => return Err(ConstErrorKind::DivisionByZero),
+        Operator::Divide => l.checked_div(r),
+        Operator::BitAnd => Some(l & r),
+        Operator::BitOr => Some(l | r),
+        Operator::BitXor => Some(l ^ r),
+        Operator::Power => {
+            return (l as i32).checked_pow(r as u32).map(|value| Some(i128::from(value))).ok_or_else(|| overflow(Type::Int32));
+        }
+        // The shift amount must be less than the width, which is only
+        // known for an Int32
+        Operator::ShiftLeft | Operator::ShiftRight if int32 => {
+            if !(0..32).contains(&r) {
+                return Err(overflow(Type::Int32));
+            }
+            let value = if *operator == Operator::ShiftLeft { (l as i32) << r } else { (l as i32) >> r };
+            Some(i128::from(value))
+        }
+        _ => return Ok(None),
+    };
+    match value {
+        Some(value) if int32 && i32::try_from(value).is_err() => Err(overflow(Type::Int32)),
+        Some(value) => Ok(Some(value)),
+        // Beyond every integer type's range
+        None => Ok(None),
+    }
+}
+
+/// Whether `expr` is built from integer literals by operators alone
+fn is_constant(expr: &Expression) -> bool {
+    match expr {
+        Expression::Number(..) => true,
+        Expression::BinaryOp { left, right, .. } => is_constant(left) && is_constant(right),
+        _ => false,
+    }
+}
+
+/// Whether every literal of constant `expr` is within Int32
+fn fits_int32(expr: &Expression) -> bool {
+    match expr {
+        Expression::Number(n, _) => i32::try_from(*n).is_ok(),
+        Expression::BinaryOp { left, right, .. } => fits_int32(left) && fits_int32(right),
+        _ => true,
+    }
+}
+
+/// How W writes an arithmetic `operator`
+pub(crate) fn symbol(operator: &Operator) -> &'static str {
+    match operator {
+        Operator::Add => "+",
+        Operator::Subtract => "-",
+        Operator::Multiply => "*",
+        Operator::Divide => "/",
+        Operator::Power => "^",
+        Operator::BitAnd => "&",
+        Operator::BitOr => "|",
+        Operator::BitXor => "^^",
+        Operator::ShiftLeft => "<<",
+        Operator::ShiftRight => ">>",
+        _ => unreachable!("only arithmetic is evaluated"),
+    }
+}
+
+/// A lower bound on the bytes a value of `type_` takes, counting only what
+/// is stored inline (collections and strings count as nothing)
+fn minimum_size(type_: &Type) -> u128 {
+    match type_ {
+        Type::Int8 | Type::UInt8 | Type::Bool => 1,
+        Type::Int16 | Type::UInt16 => 2,
+        Type::Int32 | Type::UInt32 | Type::Float32 | Type::Char => 4,
+        Type::Int64 | Type::UInt64 | Type::Float64 | Type::Int | Type::UInt => 8,
+        Type::Int128 | Type::UInt128 => 16,
+        Type::Tuple(types) => types.iter().map(minimum_size).fold(0, u128::saturating_add),
+        Type::Array(inner, size) => minimum_size(inner).saturating_mul(*size as u128),
+        Type::Option(inner) => minimum_size(inner),
+        Type::Result(ok, err) => minimum_size(ok).max(minimum_size(err)),
+        _ => 0,
+    }
+}
@@ -0,0 +1,49 @@
+//! Diagnostics
+//!
+//! Renders a problem found at a location in a W source file the way rustc
+//! does: its code and message, `file:line:column`, then the source line with
+//! the located part underlined.
+//!
+//! ```text
+//! error[E0001]: Type mismatch in arithmetic operation: expected Int32, got String
+//!  --> main.w:3:1
+//!   |
+//! 3 | Let[label, count + "s"]
+//!   | ^^^^^^^^^^^^^^^^^^^^^^^
+//! ```
+//!
+//! Locations are the start of the top-level expression the problem is in,
+//! so the underline runs from there to the end of its first line.
+
+use crate::lexer::Span;
+
+/// `message` with its `severity` ("error" or "warning") and `code`, located
+/// at `span` in `file`, whose text is `source`. Without a span, or with one
+/// past the end of `source`, only the file is given.
+pub fn render(severity: &str, code: &str, message: &str, file: &str, source: &str, span: Option<Span>) -> String {
+    let header = format!("{}[{}]: {}", severity, code, message);
+    let Some(span) = span else {
+        return format!("{}\n --> {}\n", header, file);
+    };
+    let Some(line) = span.line.checked_sub(1).and_then(|index| source.lines().nth(index)) else {
+        return format!("{}\n --> {}\n", header, file);
+    };
+
+    let number = span.line.to_string();
+    let gutter = " ".repeat(number.len());
+    let start = span.column.saturating_sub(1);
+    let underlined = line.chars().skip(start).collect::<String>().trim_end().chars().count().max(1);
+    format!(
+        "{}\n{}--> {}:{}\n{} |\n{} | {}\n{} | {}{}\n",
+        header,
+        gutter,
+        file,
+        span,
+        gutter,
+        number,
+        line,
+        gutter,
+        " ".repeat(start),
+        "^".repeat(underlined)
+    )
+}
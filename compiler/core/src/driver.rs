@@ -0,0 +1,406 @@
+//! Compilation Driver
+//!
+//! Runs the in-memory pipeline (parse, type check, optimize, generate Rust)
+//! for embedders such as editors and `w learn`, and with imports and a
+//! backend for the `w` command (`compile_file`). Each finished phase is
+//! reported to a `CompilerCallbacks` implementation with its timing and a few
+//! counts, so an embedder can show progress without patching the crate.
+//! Nothing is written to disk and rustc is not run.
+//!
+//! `compile_with_cancellation` also takes a `CancellationToken`, checked while
+//! parsing and type checking and between phases, so an editor can abandon a
+//! compilation of text that has since changed.
+
+use crate::ast::Expression;
+use crate::backend::{Artifacts, BackendOptions, CodegenBackend};
+use crate::cancellation::CancellationToken;
+use crate::imports::{load_imports, ImportError, LoadedProgram};
+use crate::lints::{lint_program, Lint};
+use crate::no_std::{check_no_std, NoStdError};
+use crate::const_eval::{check_constants, ConstError};
+use crate::profile::{check_profile, ProfileViolation, PROFILE_ERROR_CODE};
+use crate::optimizer::Optimizer;
+use crate::parser::{Parser, PARSE_ERROR_CODE};
+use crate::rust_codegen::{Arithmetic, CodegenOptions, RustCodeGenerator};
+use crate::lexer::Span;
+use crate::type_inference::{LocatedTypeError, TypeError, TypeInference, TypeTable};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Reported once the source has parsed
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseStats {
+    pub elapsed: Duration,
+    /// Length of the source in bytes
+    pub source_bytes: usize,
+    /// Number of top-level expressions
+    pub expressions: usize,
+}
+
+/// Reported once the program has type checked
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypecheckStats {
+    pub elapsed: Duration,
+    /// Number of top-level expressions checked
+    pub expressions: usize,
+}
+
+/// Reported once Rust code has been generated (optimization included)
+#[derive(Debug, Clone, PartialEq)]
+pub struct CodegenStats {
+    pub elapsed: Duration,
+    /// Lines of generated Rust
+    pub lines: usize,
+    /// Length of the generated Rust in bytes
+    pub bytes: usize,
+}
+
+/// Hooks called as each phase of `compile` finishes. Every method defaults to
+/// doing nothing; a phase that fails reports nothing.
+pub trait CompilerCallbacks {
+    fn on_parse_complete(&mut self, _stats: &ParseStats) {}
+    fn on_typecheck_complete(&mut self, _stats: &TypecheckStats) {}
+    /// Called for each lint, after type checking succeeds
+    fn on_lint(&mut self, _lint: &Lint) {}
+    fn on_codegen_complete(&mut self, _stats: &CodegenStats) {}
+}
+
+/// Callbacks that ignore every phase
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoCallbacks;
+
+impl CompilerCallbacks for NoCallbacks {}
+
+/// Why `compile` stopped
+#[derive(Debug, Clone, PartialEq)]
+pub enum CompileError {
+    /// The source does not parse; `code` is the lexing or parsing error code
+    Parse { code: &'static str, message: String },
+    /// A file named by Import cannot be loaded
+    Import(ImportError),
+    /// Imported modules do not type check (each error with the file it is in)
+    ModuleType(Vec<(PathBuf, TypeError)>),
+    /// The program parses but does not type check (holds every error, in
+    /// source order)
+    Type(Vec<LocatedTypeError>),
+    /// Arithmetic on constants that always fails (holds every error, in
+    /// source order)
+    Const(Vec<ConstError>),
+    /// The options ask for `no_std` code, but the program needs std
+    NoStd(NoStdError),
+    /// The program leaves the subset its profile allows (holds every violation)
+    Profile(Vec<ProfileViolation>),
+    /// The program uses something the code generator cannot translate yet
+    Unsupported,
+    /// The cancellation token was cancelled before compilation finished
+    Cancelled,
+}
+
+impl CompileError {
+    /// Error code explained by `w --explain`, if any
+    pub fn code(&self) -> Option<&'static str> {
+        match self {
+            CompileError::Parse { code, .. } => Some(code),
+            CompileError::Import(_) => None,
+            CompileError::ModuleType(errors) => errors.first().map(|(_, error)| error.code()),
+            CompileError::Type(errors) => errors.first().map(LocatedTypeError::code),
+            CompileError::Const(errors) => errors.first().map(ConstError::code),
+            CompileError::NoStd(error) => Some(error.code()),
+            CompileError::Profile(_) => Some(PROFILE_ERROR_CODE),
+            CompileError::Unsupported | CompileError::Cancelled => None,
+        }
+    }
+
+    /// The error as shown to the author of `source`, read from `file`: type
+    /// errors with the line they are on, others located in `file`
+    pub fn render(&self, file: &str, source: &str) -> String {
+        match self {
+            CompileError::Parse { code, message } => format!("error[{}]: {}:{}\n", code, file, message),
+            CompileError::Type(errors) => errors.iter().map(|error| error.render(file, source)).collect(),
+            CompileError::Const(errors) => errors.iter()
+                .map(|error| match error.span {
+                    Some(span) => format!("error[{}]: {}:{}: {}\n", error.code(), file, span, error),
+                    None => format!("error[{}]: {}: {}\n", error.code(), file, error),
+                })
+                .collect(),
+            CompileError::NoStd(error) => match error.span {
+                Some(span) => format!("error[{}]: {}:{}: {}\n", error.code(), file, span, error),
+                None => format!("error[{}]: {}: {}\n", error.code(), file, error),
+            },
+            CompileError::Profile(violations) => violations.iter()
+                .map(|violation| match violation.span {
+                    Some(span) => format!("error[{}]: {}:{}: {}\n", violation.code(), file, span, violation),
+                    None => format!("error[{}]: {}: {}\n", violation.code(), file, violation),
+                })
+                .collect(),
+            CompileError::Unsupported => format!("error: {}: {}\n", file, self),
+            other => format!("{}\n", other),
+        }
+    }
+}
+
+impl fmt::Display for CompileError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CompileError::Parse { code, message } => write!(f, "error[{}]: {}", code, message),
+            CompileError::Import(error) => write!(f, "error: {}", error),
+            CompileError::ModuleType(errors) => {
+                let lines: Vec<String> = errors.iter()
+                    .map(|(path, error)| format!("error[{}]: {}: {}", error.code(), path.display(), error))
+                    .collect();
+                write!(f, "{}", lines.join("\n"))
+            }
+            CompileError::Type(errors) => {
+                let lines: Vec<String> = errors.iter()
+                    .map(|error| format!("error[{}]: {}", error.code(), error))
+                    .collect();
+                write!(f, "{}", lines.join("\n"))
+            }
+            CompileError::Const(errors) => {
+                let lines: Vec<String> = errors.iter()
+                    .map(|error| match error.span {
+                        Some(span) => format!("error[{}]: {}: {}", error.code(), span, error),
+                        None => format!("error[{}]: {}", error.code(), error),
+                    })
+                    .collect();
+                write!(f, "{}", lines.join("\n"))
+            }
+            CompileError::NoStd(error) => match error.span {
+                Some(span) => write!(f, "error[{}]: {}: {}", error.code(), span, error),
+                None => write!(f, "error[{}]: {}", error.code(), error),
+            },
+            CompileError::Profile(violations) => {
+                let lines: Vec<String> = violations.iter()
+                    .map(|violation| match violation.span {
+                        Some(span) => format!("error[{}]: {}: {}", violation.code(), span, violation),
+                        None => format!("error[{}]: {}", violation.code(), violation),
+                    })
+                    .collect();
+                write!(f, "{}", lines.join("\n"))
+            }
+            CompileError::Unsupported => write!(f, "The program uses something W cannot compile yet"),
+            CompileError::Cancelled => write!(f, "Compilation was cancelled"),
+        }
+    }
+}
+
+/// Compile `source` to Rust, reporting each finished phase to `callbacks`
+pub fn compile(
+    source: &str,
+    options: &CodegenOptions,
+    callbacks: &mut dyn CompilerCallbacks,
+) -> Result<String, CompileError> {
+    compile_with_cancellation(source, options, callbacks, &CancellationToken::new())
+}
+
+/// Like `compile`, stopping with `CompileError::Cancelled` once `token` is
+/// cancelled
+pub fn compile_with_cancellation(
+    source: &str,
+    options: &CodegenOptions,
+    callbacks: &mut dyn CompilerCallbacks,
+    token: &CancellationToken,
+) -> Result<String, CompileError> {
+    let (program, spans) = parse(source, callbacks, token)?;
+    let inference = TypeInference::new().with_cancellation(token.clone());
+    let loaded = LoadedProgram { entry: program.clone(), modules: Vec::new(), module_paths: Vec::new(), module_sources: Vec::new() };
+    let (optimized, types) = check(&program, &spans, loaded, inference, options, callbacks, token)?;
+
+    let start = Instant::now();
+    let rust_code = RustCodeGenerator::builder()
+        .options(options.clone())
+        .types(types)
+        .build()
+        .generate(&optimized.entry)
+        .map_err(|_| CompileError::Unsupported)?;
+    callbacks.on_codegen_complete(&CodegenStats {
+        elapsed: start.elapsed(),
+        lines: rust_code.lines().count(),
+        bytes: rust_code.len(),
+    });
+    Ok(rust_code)
+}
+
+/// What `compile_file` produced
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompiledProgram {
+    /// The entry program and the modules it imports, as optimized and given
+    /// to the backend
+    pub program: LoadedProgram,
+    /// The files the backend generated and how to build them
+    pub artifacts: Artifacts,
+}
+
+/// Compile `source`, read from `path`, together with the files it imports,
+/// into what `backend` generates. This is the pipeline of the `w` command;
+/// nothing is written to disk and the build step is not run. The imported
+/// files are added to the inputs of the codegen options' provenance, if any,
+/// after the entry file.
+pub fn compile_file(
+    path: &Path,
+    source: &str,
+    backend: &mut dyn CodegenBackend,
+    options: &BackendOptions,
+    callbacks: &mut dyn CompilerCallbacks,
+) -> Result<CompiledProgram, CompileError> {
+    let token = CancellationToken::new();
+    let (program, spans) = parse(source, callbacks, &token)?;
+    let loaded = load_imports(path, program.clone()).map_err(CompileError::Import)?;
+    let (optimized, types) = check(&program, &spans, loaded, TypeInference::new(), &options.codegen, callbacks, &token)?;
+
+    let start = Instant::now();
+    let mut options = BackendOptions { types: Some(types), ..options.clone() };
+    if let Some(provenance) = &mut options.codegen.provenance {
+        for (module_path, source) in optimized.module_paths.iter().zip(&optimized.module_sources) {
+            provenance.inputs.push((input_name(path, module_path), source.clone()));
+        }
+    }
+    let artifacts = backend.generate(&optimized, &options).map_err(|_| CompileError::Unsupported)?;
+    callbacks.on_codegen_complete(&CodegenStats {
+        elapsed: start.elapsed(),
+        lines: artifacts.files.iter().map(|file| file.contents.lines().count()).sum(),
+        bytes: artifacts.files.iter().map(|file| file.contents.len()).sum(),
+    });
+    Ok(CompiledProgram { program: optimized, artifacts })
+}
+
+/// How to name `module`, a canonical path, in a generated header: relative
+/// to the directory of `entry` as it was given where possible
+fn input_name(entry: &Path, module: &Path) -> String {
+    let directory = entry.canonicalize().ok().and_then(|entry| entry.parent().map(Path::to_path_buf));
+    match directory.as_deref().and_then(|directory| module.strip_prefix(directory).ok()) {
+        Some(relative) => entry.parent().unwrap_or(Path::new("")).join(relative).display().to_string(),
+        None => module.display().to_string(),
+    }
+}
+
+/// Parse `source`, with the span of each top-level expression
+fn parse(
+    source: &str,
+    callbacks: &mut dyn CompilerCallbacks,
+    token: &CancellationToken,
+) -> Result<(Expression, Vec<Span>), CompileError> {
+    let start = Instant::now();
+    let mut parser = Parser::new(source.to_string()).with_cancellation(token.clone());
+    let program = match parser.parse() {
+        Some(program) => program,
+        None if parser.was_cancelled() => return Err(CompileError::Cancelled),
+        None => {
+            return Err(match parser.lex_error() {
+                Some(error) => CompileError::Parse { code: error.code(), message: error.to_string() },
+                None => CompileError::Parse {
+                    code: PARSE_ERROR_CODE,
+                    message: format!("{}: failed to parse expression", parser.current_span()),
+                },
+            });
+        }
+    };
+    callbacks.on_parse_complete(&ParseStats {
+        elapsed: start.elapsed(),
+        source_bytes: source.len(),
+        expressions: top_level(&program).len(),
+    });
+    Ok((program, parser.expression_spans().to_vec()))
+}
+
+/// Check `program` (parsed with `spans`) and the modules `loaded` from it,
+/// then optimize what was loaded. Returns it with its types.
+fn check(
+    program: &Expression,
+    spans: &[Span],
+    loaded: LoadedProgram,
+    inference: TypeInference,
+    options: &CodegenOptions,
+    callbacks: &mut dyn CompilerCallbacks,
+    token: &CancellationToken,
+) -> Result<(LoadedProgram, TypeTable), CompileError> {
+    let mut inference = checked_arithmetic(inference, options);
+    // Each module is checked before the files that import it, then the entry
+    let start = Instant::now();
+    let mut module_errors = Vec::new();
+    for ((name, module), path) in loaded.modules.iter().zip(&loaded.module_paths) {
+        for error in inference.check_module(name, top_level(module)) {
+            module_errors.push((path.clone(), error));
+        }
+    }
+    if module_errors.iter().any(|(_, error)| *error == TypeError::Cancelled) {
+        return Err(CompileError::Cancelled);
+    }
+    if !module_errors.is_empty() {
+        return Err(CompileError::ModuleType(module_errors));
+    }
+    let expressions = top_level(program);
+    let errors = inference.check_program_located(expressions, spans);
+    if errors.iter().any(|located| located.error == TypeError::Cancelled) {
+        return Err(CompileError::Cancelled);
+    }
+    if !errors.is_empty() {
+        return Err(CompileError::Type(errors));
+    }
+    callbacks.on_typecheck_complete(&TypecheckStats { elapsed: start.elapsed(), expressions: expressions.len() });
+    let errors = check_constants(program, spans);
+    if !errors.is_empty() {
+        return Err(CompileError::Const(errors));
+    }
+    for lint in lint_program(program, spans) {
+        callbacks.on_lint(&lint);
+    }
+
+    if token.is_cancelled() {
+        return Err(CompileError::Cancelled);
+    }
+    // Only the entry program's spans are known, less those of the Imports
+    // loading removed; modules are checked without
+    let entry_spans: Vec<Span> = expressions.iter().zip(spans)
+        .filter(|(expr, _)| !matches!(expr, Expression::Import(_)))
+        .map(|(_, span)| *span)
+        .collect();
+    let programs = || std::iter::once((&loaded.entry, entry_spans.as_slice()))
+        .chain(loaded.modules.iter().map(|(_, module)| (module, &[][..])));
+    let violations: Vec<_> = programs().flat_map(|(program, spans)| check_profile(program, options.profile, spans)).collect();
+    if !violations.is_empty() {
+        return Err(CompileError::Profile(violations));
+    }
+    if options.is_no_std() {
+        programs().try_for_each(|(program, spans)| check_no_std(program, spans)).map_err(CompileError::NoStd)?;
+    }
+    let optimized = LoadedProgram {
+        entry: Optimizer::new().optimize(&loaded.entry),
+        modules: loaded.modules.iter()
+            .map(|(name, module)| (name.clone(), Optimizer::new().optimize(module)))
+            .collect(),
+        module_paths: loaded.module_paths,
+        module_sources: loaded.module_sources,
+    };
+    let types = type_table(&optimized, options);
+    Ok((optimized, types))
+}
+
+/// The types of `program`, which type checks. Code generation looks up the
+/// type of each expression it generates, so they come from checking the
+/// optimized program rather than the source.
+fn type_table(program: &LoadedProgram, options: &CodegenOptions) -> TypeTable {
+    let mut inference = checked_arithmetic(TypeInference::new(), options);
+    for (name, module) in &program.modules {
+        inference.check_module(name, top_level(module));
+    }
+    inference.check_program_errors(top_level(&program.entry));
+    inference.type_table(top_level(&program.entry))
+}
+
+/// `inference`, checking arithmetic as `options` generate it
+fn checked_arithmetic(inference: TypeInference, options: &CodegenOptions) -> TypeInference {
+    match options.arithmetic {
+        Arithmetic::Checked => inference.with_checked_arithmetic(),
+        Arithmetic::Unchecked | Arithmetic::Wrapping => inference,
+    }
+}
+
+/// The top-level expressions of a parsed program
+fn top_level(program: &Expression) -> &[Expression] {
+    match program {
+        Expression::Program(expressions) => expressions,
+        other => std::slice::from_ref(other),
+    }
+}
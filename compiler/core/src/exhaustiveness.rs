@@ -0,0 +1,324 @@
+//! Match Exhaustiveness
+//!
+//! Checks that the arms of a `Match` cover every value of the matched type,
+//! and that every arm matches some value the arms before it do not. Both are
+//! decided by asking whether a pattern is *useful* after a list of others
+//! (Maranget, "Warnings for pattern matching"): the arms are exhaustive when
+//! `_` is not useful after all of them, and an arm is unreachable when it is
+//! not useful after the ones above it. A useful `_` comes with a witness, an
+//! example of a value no arm matches, which the error shows as a pattern.
+//!
+//! Option, Result, Bool, enums, structs, tuples and lists are covered by
+//! listing their cases; numbers and strings only by a `_` or a variable.
+//! Lists are split by length: `[]`, `[x]`, ... up to the longest list
+//! pattern, then "at least that many elements", which `[x, ..]` covers.
+
+use crate::ast::{Expression, Pattern, Type};
+use crate::type_inference::{TypeEnvironment, TypeError};
+
+/// A pattern reduced to the values it matches: variables and wildcards are
+/// both `Any`, and bindings are dropped
+#[derive(Debug, Clone, PartialEq)]
+enum Space {
+    Any,
+    Constructor(Constructor, Vec<Space>),
+    /// A list pattern: its elements before `..`, after it, and whether it
+    /// has one
+    List { prefix: Vec<Space>, suffix: Vec<Space>, rest: bool },
+    Or(Vec<Space>),
+}
+
+/// One case of a type, applied to a pattern per field
+#[derive(Debug, Clone, PartialEq)]
+enum Constructor {
+    /// Some, None, Ok, Err, an enum variant or a struct
+    Named(String),
+    Bool(bool),
+    Tuple(usize),
+    /// A list of exactly this many elements
+    Length(usize),
+    /// A list of at least this many elements
+    AtLeast(usize),
+    /// A number or string literal, or a StartsWith or EndsWith pattern, as
+    /// written; these never cover their whole type
+    Literal(String),
+}
+
+/// Check the patterns of the arms of a Match on a `value_type`, in order:
+/// each must be reachable and together they must cover every value
+pub(crate) fn check_match(env: &TypeEnvironment, value_type: &Type, patterns: &[&Pattern]) -> Result<(), TypeError> {
+    let checker = Checker { env };
+    let mut rows: Vec<Vec<Space>> = Vec::new();
+    for pattern in patterns {
+        let space = checker.lower(pattern);
+        if checker.useful(&rows, std::slice::from_ref(&space)).is_none() {
+            return Err(TypeError::UnreachableArm(pattern.to_string()));
+        }
+        rows.push(vec![space]);
+    }
+
+    match checker.useful(&rows, &[Space::Any]) {
+        Some(witness) => Err(TypeError::NonExhaustiveMatch {
+            type_: value_type.clone(),
+            missing: to_pattern(&witness[0]).to_string(),
+        }),
+        None => Ok(()),
+    }
+}
+
+struct Checker<'a> {
+    env: &'a TypeEnvironment,
+}
+
+impl Checker<'_> {
+    fn lower(&self, pattern: &Pattern) -> Space {
+        match pattern {
+            Pattern::Wildcard | Pattern::Rest => Space::Any,
+            Pattern::Variable(name) if name == "None" || self.env.lookup_variant(name).is_some() => {
+                Space::Constructor(Constructor::Named(name.clone()), vec![])
+            }
+            Pattern::Variable(_) => Space::Any,
+            Pattern::Literal(literal) => match literal.as_ref() {
+                Expression::Boolean(b) => Space::Constructor(Constructor::Bool(*b), vec![]),
+                _ => Space::Constructor(Constructor::Literal(pattern.to_string()), vec![]),
+            },
+            Pattern::StartsWith(_) | Pattern::EndsWith(_) => {
+                Space::Constructor(Constructor::Literal(pattern.to_string()), vec![])
+            }
+            Pattern::Constructor { name, patterns } => {
+                // `..` stands for the struct's remaining fields
+                let mut fields: Vec<Space> =
+                    patterns.iter().filter(|p| **p != Pattern::Rest).map(|p| self.lower(p)).collect();
+                let arity = self.arity(&Constructor::Named(name.clone()));
+                if fields.len() < arity {
+                    fields.resize(arity, Space::Any);
+                }
+                Space::Constructor(Constructor::Named(name.clone()), fields)
+            }
+            Pattern::Tuple(patterns) => {
+                Space::Constructor(Constructor::Tuple(patterns.len()), patterns.iter().map(|p| self.lower(p)).collect())
+            }
+            Pattern::List(patterns) => match patterns.iter().position(Pattern::is_rest) {
+                // `[..]` matches every list
+                Some(_) if patterns.len() == 1 => Space::Any,
+                Some(rest) => Space::List {
+                    prefix: patterns[..rest].iter().map(|p| self.lower(p)).collect(),
+                    suffix: patterns[rest + 1..].iter().map(|p| self.lower(p)).collect(),
+                    rest: true,
+                },
+                None => Space::List { prefix: patterns.iter().map(|p| self.lower(p)).collect(), suffix: vec![], rest: false },
+            },
+            Pattern::Or(alternatives) => Space::Or(alternatives.iter().map(|p| self.lower(p)).collect()),
+            Pattern::Binding { pattern, .. } => self.lower(pattern),
+        }
+    }
+
+    /// Number of fields `constructor` has
+    fn arity(&self, constructor: &Constructor) -> usize {
+        match constructor {
+            Constructor::Named(name) => match name.as_str() {
+                "Some" | "Ok" | "Err" => 1,
+                "None" => 0,
+                _ => match self.env.lookup_variant(name) {
+                    Some((_, variant)) => variant.fields.len(),
+                    None => self.env.lookup_struct(name).map_or(0, |fields| fields.len()),
+                },
+            },
+            Constructor::Tuple(n) | Constructor::Length(n) | Constructor::AtLeast(n) => *n,
+            Constructor::Bool(_) | Constructor::Literal(_) => 0,
+        }
+    }
+
+    /// Every case of the type `used` are cases of, or `None` if there are
+    /// too many to list. Arms have been type checked, so the cases they use
+    /// all belong to one type
+    fn signature(&self, used: &[Constructor], bound: usize) -> Option<Vec<Constructor>> {
+        let named = |names: &[&str]| names.iter().map(|name| Constructor::Named(name.to_string())).collect();
+        match used.first()? {
+            Constructor::Bool(_) => Some(vec![Constructor::Bool(true), Constructor::Bool(false)]),
+            Constructor::Named(name) => match name.as_str() {
+                "Some" | "None" => Some(named(&["Some", "None"])),
+                "Ok" | "Err" => Some(named(&["Ok", "Err"])),
+                _ => match self.env.lookup_variant(name) {
+                    Some((enum_name, _)) => self
+                        .env
+                        .lookup_enum(enum_name)
+                        .map(|variants| variants.iter().map(|v| Constructor::Named(v.name.clone())).collect()),
+                    None => Some(vec![Constructor::Named(name.clone())]),
+                },
+            },
+            Constructor::Tuple(n) => Some(vec![Constructor::Tuple(*n)]),
+            Constructor::Length(_) | Constructor::AtLeast(_) => {
+                Some((0..bound).map(Constructor::Length).chain([Constructor::AtLeast(bound)]).collect())
+            }
+            Constructor::Literal(_) => None,
+        }
+    }
+
+    /// A witness (one space per column of `query`) for a value `query`
+    /// matches and no row does, or `None` if every such value is matched by
+    /// some row
+    fn useful(&self, rows: &[Vec<Space>], query: &[Space]) -> Option<Vec<Space>> {
+        let Some((head, rest)) = query.split_first() else {
+            return if rows.is_empty() { Some(vec![]) } else { None };
+        };
+        let bound = list_bound(rows.iter().map(|row| &row[0]).chain([head]));
+        match head {
+            Space::Or(alternatives) => alternatives.iter().find_map(|alternative| {
+                let query: Vec<Space> = std::iter::once(alternative.clone()).chain(rest.iter().cloned()).collect();
+                self.useful(rows, &query)
+            }),
+            Space::Constructor(constructor, fields) => self.useful_constructor(rows, constructor, fields.clone(), rest, bound),
+            Space::List { .. } => list_cases(head, bound)
+                .into_iter()
+                .find_map(|(constructor, fields)| self.useful_constructor(rows, &constructor, fields, rest, bound)),
+            Space::Any => {
+                let mut used = Vec::new();
+                for row in rows {
+                    for constructor in head_constructors(&row[0], bound) {
+                        if !used.contains(&constructor) {
+                            used.push(constructor);
+                        }
+                    }
+                }
+                let signature = self.signature(&used, bound);
+                match signature {
+                    // Every case appears in this column, so one of them must
+                    // be missing further in
+                    Some(all) if all.iter().all(|c| used.contains(c)) => all.iter().find_map(|constructor| {
+                        let fields = vec![Space::Any; self.arity(constructor)];
+                        self.useful_constructor(rows, constructor, fields, rest, bound)
+                    }),
+                    // A case no row names is only matched by rows that match
+                    // anything here
+                    _ => {
+                        let default: Vec<Vec<Space>> =
+                            rows.iter().filter(|row| matches_anything(&row[0])).map(|row| row[1..].to_vec()).collect();
+                        let witness = self.useful(&default, rest)?;
+                        let missing = signature
+                            .and_then(|all| all.into_iter().find(|c| !used.contains(c)))
+                            .map(|constructor| {
+                                let fields = vec![Space::Any; self.arity(&constructor)];
+                                Space::Constructor(constructor, fields)
+                            })
+                            .unwrap_or(Space::Any);
+                        Some(std::iter::once(missing).chain(witness).collect())
+                    }
+                }
+            }
+        }
+    }
+
+    /// `useful` for a query whose head is `constructor` applied to `fields`
+    fn useful_constructor(
+        &self,
+        rows: &[Vec<Space>],
+        constructor: &Constructor,
+        fields: Vec<Space>,
+        rest: &[Space],
+        bound: usize,
+    ) -> Option<Vec<Space>> {
+        let arity = fields.len();
+        let specialized: Vec<Vec<Space>> = rows
+            .iter()
+            .flat_map(|row| {
+                specialize(&row[0], constructor, arity, bound).into_iter().map(|mut specialized| {
+                    specialized.extend_from_slice(&row[1..]);
+                    specialized
+                })
+            })
+            .collect();
+        let query: Vec<Space> = fields.into_iter().chain(rest.iter().cloned()).collect();
+        let mut witness = self.useful(&specialized, &query)?;
+        let others = witness.split_off(arity);
+        Some(std::iter::once(Space::Constructor(constructor.clone(), witness)).chain(others).collect())
+    }
+}
+
+/// The fields of `space` for each way it matches `constructor`; none if it
+/// does not match it
+fn specialize(space: &Space, constructor: &Constructor, arity: usize, bound: usize) -> Vec<Vec<Space>> {
+    match space {
+        Space::Any => vec![vec![Space::Any; arity]],
+        Space::Constructor(other, fields) if other == constructor => vec![fields.clone()],
+        Space::Constructor(..) => vec![],
+        Space::List { .. } => list_cases(space, bound)
+            .into_iter()
+            .filter(|(case, _)| case == constructor)
+            .map(|(_, fields)| fields)
+            .collect(),
+        Space::Or(alternatives) => {
+            alternatives.iter().flat_map(|alternative| specialize(alternative, constructor, arity, bound)).collect()
+        }
+    }
+}
+
+/// The constructors `space` matches some values of
+fn head_constructors(space: &Space, bound: usize) -> Vec<Constructor> {
+    match space {
+        Space::Any => vec![],
+        Space::Constructor(constructor, _) => vec![constructor.clone()],
+        Space::List { .. } => list_cases(space, bound).into_iter().map(|(constructor, _)| constructor).collect(),
+        Space::Or(alternatives) => alternatives.iter().flat_map(|alternative| head_constructors(alternative, bound)).collect(),
+    }
+}
+
+fn matches_anything(space: &Space) -> bool {
+    match space {
+        Space::Any => true,
+        Space::Or(alternatives) => alternatives.iter().any(matches_anything),
+        _ => false,
+    }
+}
+
+/// The list lengths `space` matches, each with the patterns its elements
+/// must match. Lengths of `bound` and more are one case, `AtLeast(bound)`
+fn list_cases(space: &Space, bound: usize) -> Vec<(Constructor, Vec<Space>)> {
+    let Space::List { prefix, suffix, rest } = space else {
+        return vec![];
+    };
+    if !rest {
+        return vec![(Constructor::Length(prefix.len()), prefix.clone())];
+    }
+    let minimum = prefix.len() + suffix.len();
+    let elements = |length: usize| -> Vec<Space> {
+        prefix.iter().cloned().chain(vec![Space::Any; length - minimum]).chain(suffix.iter().cloned()).collect()
+    };
+    (minimum..bound)
+        .map(|length| (Constructor::Length(length), elements(length)))
+        .chain([(Constructor::AtLeast(bound), elements(bound))])
+        .collect()
+}
+
+/// The length from which every list pattern in `column` matches lists
+/// alike: past the longest exact-length pattern, and no shorter than any
+/// pattern with `..` needs
+fn list_bound<'a>(column: impl Iterator<Item = &'a Space>) -> usize {
+    fn bound(space: &Space) -> usize {
+        match space {
+            Space::List { prefix, suffix, rest: true } => prefix.len() + suffix.len(),
+            Space::List { prefix, rest: false, .. } => prefix.len() + 1,
+            Space::Or(alternatives) => alternatives.iter().map(bound).max().unwrap_or(0),
+            _ => 0,
+        }
+    }
+    column.map(bound).max().unwrap_or(0)
+}
+
+/// A witness as W source, e.g. `Some[None]` or `[_, _, ..]`
+fn to_pattern(space: &Space) -> Pattern {
+    match space {
+        Space::Constructor(constructor, fields) => {
+            let fields: Vec<Pattern> = fields.iter().map(to_pattern).collect();
+            match constructor {
+                Constructor::Named(name) => Pattern::Constructor { name: name.clone(), patterns: fields },
+                Constructor::Bool(b) => Pattern::Literal(Box::new(Expression::Boolean(*b))),
+                Constructor::Tuple(_) => Pattern::Tuple(fields),
+                Constructor::Length(_) => Pattern::List(fields),
+                Constructor::AtLeast(_) => Pattern::List(fields.into_iter().chain([Pattern::Rest]).collect()),
+                Constructor::Literal(_) => Pattern::Wildcard,
+            }
+        }
+        _ => Pattern::Wildcard,
+    }
+}
@@ -0,0 +1,834 @@
+//! Error Code Explanations
+//!
+//! Every diagnostic has a stable code (`TypeError::code`, `LexError::code`,
+//! `parser::PARSE_ERROR_CODE`), as does every lint (`Lint::code`).
+//! `w --explain <code>` prints the longer
+//! explanation kept here, with an example of the mistake and its fix.
+
+/// One explained error code
+#[derive(Debug, Clone, PartialEq)]
+pub struct Explanation {
+    pub code: &'static str,
+    pub title: &'static str,
+    /// Paragraphs and examples, W code indented by four spaces
+    pub body: &'static str,
+}
+
+/// All explained codes, type errors (E00xx) before lexing and parsing (E01xx),
+/// then lints (W00xx)
+pub const EXPLANATIONS: &[Explanation] = &[
+    Explanation {
+        code: "E0001",
+        title: "type mismatch",
+        body: "\
+An expression has a different type from the one its context requires: an
+argument to a function, a branch of Cond or Match, or a value pushed into a
+collection.
+
+    Square[x: Int32] := x * x
+    Square[\"four\"]          # expected Int32, got String
+
+Pass a value of the expected type, or change the annotation:
+
+    Square[4]
+",
+    },
+    Explanation {
+        code: "E0002",
+        title: "undefined identifier",
+        body: "\
+A name is used that no Let, parameter, pattern or definition introduces.
+
+    Print[total]            # nothing defines `total`
+
+Bind the name before using it, and check its spelling:
+
+    Let[total, 10]
+    Print[total]
+",
+    },
+    Explanation {
+        code: "E0003",
+        title: "wrong number of arguments",
+        body: "\
+A function or builtin was called with more or fewer arguments than it takes.
+
+    Add[x: Int32, y: Int32] := x + y
+    Add[1]                  # Add expects 2 arguments, got 1
+
+Pass one argument per parameter:
+
+    Add[1, 2]
+",
+    },
+    Explanation {
+        code: "E0004",
+        title: "cannot infer type",
+        body: "\
+The type of an expression cannot be worked out from the expression alone,
+for example a map literal with nothing to say what its key and value types
+are, or a Let binding of `None` that nothing in the program uses as an
+Option of a particular type.
+
+    Let[ages, {\"ann\": 31}]
+    Let[nothing, None]
+
+Give the value a declared type, e.g. by passing it to a function whose
+parameter is annotated:
+
+    Oldest[ages: Map[String, Int32]] := Lookup[ages, \"ann\"]
+",
+    },
+    Explanation {
+        code: "E0005",
+        title: "undefined struct",
+        body: "\
+A struct name is used that no Struct definition introduces.
+
+    Point[1, 2]             # no Struct[Point, ...]
+
+Define the struct first:
+
+    Struct[Point, [x: Int32, y: Int32]]
+    Point[1, 2]
+",
+    },
+    Explanation {
+        code: "E0006",
+        title: "wrong number of struct fields",
+        body: "\
+A struct was constructed with a different number of values than it has
+fields. Values are given in the order the fields are declared.
+
+    Struct[Point, [x: Int32, y: Int32]]
+    Point[1]                # Point expects 2 fields, got 1
+
+Give a value for every field:
+
+    Point[1, 2]
+",
+    },
+    Explanation {
+        code: "E0007",
+        title: "undefined trait",
+        body: "\
+An Impl names a trait that no Trait definition introduces.
+
+    Impl[Printable, Point, ToText[self] := \"point\"]
+
+Declare the trait and its method signatures first:
+
+    Trait[Printable, [ToText[self] -> String]]
+    Impl[Printable, Point, ToText[self] := \"point\"]
+",
+    },
+    Explanation {
+        code: "E0008",
+        title: "missing trait method",
+        body: "\
+An Impl leaves out a method that its trait declares. Every method of the
+trait must be defined for the type.
+
+    Trait[Shape, [Area[self] -> Int32, Sides[self] -> Int32]]
+    Impl[Shape, Square, Area[self] := self.side * self.side]
+
+Define the missing method:
+
+    Impl[Shape, Square,
+      Area[self] := self.side * self.side,
+      Sides[self] := 4]
+",
+    },
+    Explanation {
+        code: "E0009",
+        title: "method not declared by the trait",
+        body: "\
+An Impl defines a method that its trait does not declare. Helper functions
+belong outside the Impl.
+
+    Trait[Shape, [Area[self] -> Int32]]
+    Impl[Shape, Square, Area[self] := 16, Perimeter[self] := 16]
+
+Either add the method to the Trait or define it as a plain function.
+",
+    },
+    Explanation {
+        code: "E0010",
+        title: "trait not implemented",
+        body: "\
+A trait method was called on, or a trait-typed parameter given, a value whose
+type has no Impl of that trait.
+
+    Trait[Shape, [Area[self] -> Int32]]
+    Area[Circle[2]]         # Circle does not implement Shape
+
+Add an Impl for the type:
+
+    Impl[Shape, Circle, Area[self] := 3 * self.r * self.r]
+",
+    },
+    Explanation {
+        code: "E0011",
+        title: "undefined field",
+        body: "\
+A field is read from a value whose struct has no field of that name, or from
+a value that is not a struct at all.
+
+    Struct[Point, [x: Int32, y: Int32]]
+    Let[p, Point[1, 2]]
+    p.z                     # Point has no field z
+
+Use one of the fields the Struct declares.
+",
+    },
+    Explanation {
+        code: "E0012",
+        title: "condition is not Bool",
+        body: "\
+A Cond or If condition has a type other than Bool. W has no truthiness:
+numbers, strings and collections are never implicitly true or false.
+
+    If[count, \"some\", \"none\"]
+
+Compare explicitly:
+
+    If[count != 0, \"some\", \"none\"]
+",
+    },
+    Explanation {
+        code: "E0013",
+        title: "values cannot be ordered",
+        body: "\
+`<`, `>`, `<=` or `>=` was used on a type without an ordering, such as a
+struct or a hash collection. Only `==` and `!=` work on those.
+
+    Point[1, 2] < Point[3, 4]
+
+Compare an ordered part of the value instead:
+
+    Point[1, 2].x < Point[3, 4].x
+",
+    },
+    Explanation {
+        code: "E0014",
+        title: "unhashable map key",
+        body: "\
+A Map key or HashSet element has a type that cannot be hashed or compared
+for equality, such as a float or another hash collection.
+
+    Prices[p: Map[Float64, String]] := p
+
+Use a hashable key, e.g. an integer number of cents:
+
+    Prices[p: Map[Int64, String]] := p
+",
+    },
+    Explanation {
+        code: "E0015",
+        title: "heap element without a total order",
+        body: "\
+A Heap needs to order every pair of elements, which floats cannot do
+(NaN is unordered) and hash collections do not support.
+
+    Heap[1.5, 2.5]
+
+Store an ordered stand-in, such as an integer priority:
+
+    Heap[15, 25]
+",
+    },
+    Explanation {
+        code: "E0016",
+        title: "refutable pattern in Let",
+        body: "\
+A Let pattern can fail to match, e.g. `Some[x]` when the value may be None.
+Let has nowhere to go when the pattern fails.
+
+    Let[Some[x], Pop[stack]]
+
+Give a fallback with LetElse, or handle every case with Match:
+
+    LetElse[Some[x], Pop[stack], Panic[\"empty stack\"]]
+",
+    },
+    Explanation {
+        code: "E0017",
+        title: "unbounded stream",
+        body: "\
+A possibly infinite stream (Repeat, Iterate, Lines) was consumed whole, by
+Print, Fold or SortWith, which would never finish.
+
+    Print[Repeat[1]]
+
+Bound the stream with Take first:
+
+    Print[Take[3, Repeat[1]]]
+",
+    },
+    Explanation {
+        code: "E0018",
+        title: "no lossless common numeric type",
+        body: "\
+Branches of a Cond or Match return numeric types that no single type holds
+without losing values, such as Int64 and Float64.
+
+    Pick[flag: Bool, a: Int64, b: Float64] := If[flag, a, b]
+
+Convert one branch explicitly so both have the same type:
+
+    Pick[flag: Bool, a: Int64, b: Float64] := If[flag, Convert[a, Float64], b]
+",
+    },
+    Explanation {
+        code: "E0019",
+        title: "or-pattern alternatives bind different variables",
+        body: "\
+The alternatives of an or-pattern must all bind the same variables, with the
+same types, so the arm's result can use them whichever alternative matched.
+
+    Match[pair, [(x, 0) | (0, y), x]]
+
+Bind the same name in every alternative:
+
+    Match[pair, [(x, 0) | (0, x), x]]
+",
+    },
+    Explanation {
+        code: "E0020",
+        title: "compilation cancelled",
+        body: "\
+An editor or other embedder cancelled the compilation before type checking
+finished, usually because the source changed in the meantime. This is not a
+problem with the program; the next compilation reports its real errors.
+Embedders cancel through the token they passed in (Rust):
+
+    token.cancel();         // the source changed; stop the old check
+",
+    },
+    Explanation {
+        code: "E0021",
+        title: "conversion between non-numeric types",
+        body: "\
+Convert only changes one numeric type into another; it cannot turn text into
+a number or a number into Bool.
+
+    Convert[\"42\", Int32]
+
+Convert numbers only, and compare a number to get a Bool:
+
+    Convert[total, Float64]
+    count != 0
+",
+    },
+    Explanation {
+        code: "E0022",
+        title: "Try outside a function that returns Result",
+        body: "\
+Try[expr] returns the Err of `expr` early from the enclosing function, so it
+can only appear in a function body (not at the top level or in a lambda),
+and that function must return a Result.
+
+    Double[n: Int32] := Try[ParsePositive[n]] * 2
+
+Wrap the function's value in Ok so it returns a Result:
+
+    Double[n: Int32] := Ok[Try[ParsePositive[n]] * 2]
+",
+    },
+    Explanation {
+        code: "E0023",
+        title: "kernel outside the data-parallel subset",
+        body: "\
+An @[kernel] function computes each element of its result independently, on
+several threads. Its parameters are numbers, or Lists and Arrays of numbers
+that stand for one element each, and its body is arithmetic, comparisons, If,
+Convert and Math functions on those numbers.
+
+    @[kernel] Shout[xs: List[Int32]] := Print[xs]
+
+Return the computed values, and print the whole result at the call site:
+
+    @[kernel] Double[xs: List[Int32]] := xs * 2
+    Print[Double[[1, 2, 3]]]
+",
+    },
+    Explanation {
+        code: "E0024",
+        title: "parameter without a default after one with a default",
+        body: "\
+Callers can only leave out trailing arguments, so once a parameter has a
+default value, every parameter after it needs one too.
+
+    Greet[greeting: String = \"Hello\", name: String] := greeting + \", \" + name
+
+Move the parameters with defaults to the end:
+
+    Greet[name: String, greeting: String = \"Hello\"] := greeting + \", \" + name
+",
+    },
+    Explanation {
+        code: "E0025",
+        title: "non-exhaustive Match",
+        body: "\
+The arms of a Match must cover every value it can be given. The error shows
+one value no arm matches, such as `None` below.
+
+    Match[Pop[stack], [Some[x], x]]
+
+Add an arm for the missing case, or a `_` arm for everything left over:
+
+    Match[Pop[stack], [Some[x], x], [None, 0]]
+",
+    },
+    Explanation {
+        code: "E0026",
+        title: "unreachable Match arm",
+        body: "\
+Arms are tried in order, so an arm whose values are all matched by earlier
+arms can never run. This is often a `_` or variable arm placed too early.
+
+    Match[n, [_, \"other\"], [0, \"zero\"]]
+
+Move the more specific arm first, or remove the unreachable one:
+
+    Match[n, [0, \"zero\"], [_, \"other\"]]
+",
+    },
+    Explanation {
+        code: "E0027",
+        title: "ambiguous name",
+        body: "\
+A name is defined more than once at the top level, as different things, so a
+use of it could mean either. Variants are used by their bare name, so two
+enums with a variant of the same name make that name ambiguous.
+
+    Enum[Shape, [Circle[Float64], Square[Float64]]]
+    Enum[Token, [Circle, Cross]]
+    Print[Circle]           # a variant of Shape or of Token?
+
+Rename one of the definitions:
+
+    Enum[Token, [Ring, Cross]]
+",
+    },
+    Explanation {
+        code: "E0028",
+        title: "recursive type",
+        body: "\
+A struct or enum holds a value of its own type, directly or through other
+structs and enums, so a value would contain itself without end and has no
+size. An Option or Result field counts as holding its value.
+
+    Struct[Node, [value: Int32, next: Option[Node]]]
+
+Hold the recursive value in a List, whose elements are stored separately:
+
+    Struct[Node, [value: Int32, next: List[Node]]]
+",
+    },
+    Explanation {
+        code: "E0029",
+        title: "format arguments",
+        body: "\
+The first argument of Print, PrintErr or PrintInline is a template when more
+arguments follow it: each `{}` or `{:spec}` in it is replaced by the next
+argument. The template has a different number of placeholders than there are
+arguments after it.
+
+    Print[\"{} of {}\", done]
+
+Give one argument per placeholder, or write `{{}}` for braces printed as they
+are:
+
+    Print[\"{} of {}\", done, total]
+",
+    },
+    Explanation {
+        code: "E0030",
+        title: "private item",
+        body: "\
+A definition wrapped in Private[...] can only be used by the other definitions
+of the file it is in. Another file that imports it cannot call or construct it,
+with or without the module name.
+
+    (* geometry.w *)
+    Private[Helper[x: Int32] := x + 1]
+
+    (* main.w *)
+    Import[\"geometry.w\"]
+    Print[Geometry.Helper[2]]
+
+Call a public definition of the module that uses it instead, or make it
+public (definitions are public unless marked Private):
+
+    Public[Helper[x: Int32] := x + 1]
+",
+    },
+    Explanation {
+        code: "E0031",
+        title: "checked arithmetic in a function that does not return Result",
+        body: "\
+With `--arithmetic checked`, integer arithmetic that overflows or divides by
+zero returns an Err from the function it is in, as Try would, with a message
+such as \"Arithmetic: * overflows i32\". So a function using it must return
+Result[T, String].
+
+    Scale[n: Int32, k: Int32] := n * k
+
+Return the value in Ok, and use Try or Match where it is called:
+
+    Scale[n: Int32, k: Int32] := Ok[n * k]
+
+Or build with `--arithmetic unchecked` or `--arithmetic wrapping`, which
+never fail. Top-level code, lambdas and kernels have no function to return
+the Err from, so their arithmetic panics with the message instead.
+Arithmetic on literals alone, such as `60 * 60`, is checked while compiling,
+and floats have nothing to check.
+",
+    },
+    Explanation {
+        code: "E0032",
+        title: "? in a function that cannot return early with its value",
+        body: "\
+`expr?` returns early from the enclosing function when `expr` is None or an
+Err, so it can only appear in a function body (not at the top level or in a
+lambda), and that function must return an Option when `expr` is an Option,
+or a Result when it is a Result.
+
+    Twice[n: Int32] := ParsePositive[n]? * 2
+
+Wrap the function's value in Ok (or Some for an Option) so it returns the
+same kind of value:
+
+    Twice[n: Int32] := Ok[ParsePositive[n]? * 2]
+",
+    },
+    Explanation {
+        code: "E0033",
+        title: "string pattern outside Match",
+        body: "\
+A string literal, StartsWith or EndsWith pattern is tested with a condition
+on the string, which only a Match arm can hold. LetElse, IfLet and WhileLet
+take patterns that match by shape alone.
+
+    LetElse[StartsWith[\"#\"], line, Print[\"not a comment\"]]
+
+Use Match, with an arm for the other values:
+
+    Match[line, [StartsWith[\"#\"], Print[\"comment\"]], [_, Print[\"not a comment\"]]]
+",
+    },
+    Explanation {
+        code: "E0100",
+        title: "unexpected character",
+        body: "\
+The source contains a character that cannot start any token. W comments are
+written `(* ... *)`, not with `#` or `//`.
+
+    Print[total]            # the sum
+
+Write comments between `(*` and `*)`:
+
+    Print[total]            (* the sum *)
+",
+    },
+    Explanation {
+        code: "E0101",
+        title: "unterminated string literal",
+        body: "\
+A string literal has no closing `\"` before the end of the file.
+
+    Print[\"hello]
+
+Close the string:
+
+    Print[\"hello\"]
+",
+    },
+    Explanation {
+        code: "E0102",
+        title: "invalid escape sequence",
+        body: "\
+A backslash inside a string is followed by a character that is not a known
+escape. The supported escapes are \\\" \\\\ \\n \\t and \\r.
+
+    Print[\"C:\\temp\\q\"]
+
+Write a literal backslash as `\\\\`:
+
+    Print[\"C:\\\\temp\\\\q\"]
+",
+    },
+    Explanation {
+        code: "E0103",
+        title: "failed to parse expression",
+        body: "\
+The tokens at the reported position do not form a W expression, often
+because a bracket was left unclosed.
+
+    Print[Add[1, 2]
+
+Every `[` needs a matching `]`:
+
+    Print[Add[1, 2]]
+",
+    },
+    Explanation {
+        code: "E0104",
+        title: "invalid number literal",
+        body: "\
+An integer literal has a digit its prefix does not allow, no digits after
+its `0x`, `0o` or `0b` prefix, a `_` separator that is not between two
+digits, or a value larger than the largest Int64 (9223372036854775807).
+
+    Let[mask, 0b102]
+    Let[million, 1__000_000]
+
+Use only the digits of the literal's base, and single `_` separators:
+
+    Let[mask, 0b101]
+    Let[million, 1_000_000]
+",
+    },
+    Explanation {
+        code: "E0200",
+        title: "needs the standard library under --no-std",
+        body: "\
+`--no-std` generates a `#![no_std]` library, which has no `main`, console,
+file system or OS randomness. Top-level statements, `Print`, `Lines`,
+`ReadFileBytes`, `Exit`, hash collections, graphs and float math other than
+`Math.Abs`, `Math.Min` and `Math.Max` all need std.
+
+    Area[r: Float64] := Print[3.14159 * r * r]
+
+Return values to the caller and keep the program to definitions:
+
+    Area[r: Float64] := 3.14159 * r * r
+",
+    },
+    Explanation {
+        code: "E0201",
+        title: "not allowed by the embedded profile",
+        body: "\
+`--profile embedded` generates a `#![no_std]` library that needs neither an
+allocator nor an FPU. Values must be integers, Bools, Arrays or Tuples of
+them, or structs and enums built from those; Strings (other than a literal
+Panic message), Lists, Maps, floats and I/O are left out. Every violation is
+reported, not just the first.
+
+    Average[xs: List[Int32]] := Fold[Function[{acc, x}, acc + x], 0, xs] / 4.0
+
+Take a fixed-size Array and keep to integer arithmetic:
+
+    Average[xs: Array[Int32, 4]] := Fold[Function[{acc, x}, acc + x], 0, xs] / 4
+",
+    },
+    Explanation {
+        code: "E0202",
+        title: "constant division by zero",
+        body: "\
+An integer is divided by arithmetic on literals that is always zero, so the
+division could only panic. It is reported before any Rust is generated.
+
+    Half[n: Int32] := n / (2 - 2)
+
+Fix the divisor:
+
+    Half[n: Int32] := n / 2
+",
+    },
+    Explanation {
+        code: "E0203",
+        title: "constant overflow",
+        body: "\
+Arithmetic on literals produces a value outside its type, so it could only
+panic. Literals are Int32 unless something else decides their type; `^`
+always computes in Int32, and a shift must be by 0 to 31.
+
+    Print[2147483647 + 1]
+    Print[2 ^ 31]
+
+Use a wider type where the value is used, or smaller operands:
+
+    Widen[n: Int64] := n + 1
+    Print[Widen[2147483647]]
+",
+    },
+    Explanation {
+        code: "E0204",
+        title: "Array too large",
+        body: "\
+An Array type's elements take 2^61 bytes or more, which rustc rejects as too
+big for a 64-bit target, so no value of it can exist. Nested Arrays multiply
+their sizes.
+
+    Grid[cells: Array[Array[Int64, 2000000000], 2000000000]] := 0
+
+Keep large data in a List, which grows as it is filled:
+
+    Grid[cells: List[List[Int64]]] := 0
+",
+    },
+    Explanation {
+        code: "W0001",
+        title: "division by zero",
+        body: "\
+An integer is divided by a value that is always zero, so the division panics
+when it runs. `Let` bindings of literal arithmetic are followed; dividing
+by the arithmetic itself is error E0202.
+
+    Let[buckets, 4 - 4]
+    Print[100 / buckets]
+
+Check for zero first, or fix the divisor:
+
+    Print[If[buckets == 0, 0, 100 / buckets]]
+",
+    },
+    Explanation {
+        code: "W0002",
+        title: "index out of bounds",
+        body: "\
+`At` (or `xs[i]`) is given an index outside a list literal, so it always
+returns None.
+
+    Let[days, [\"Mon\", \"Tue\", \"Wed\"]]
+    Print[days[3]]          # indices run from 0 to 2
+
+Use an index below the list's length:
+
+    Print[days[2]]
+",
+    },
+    Explanation {
+        code: "W0003",
+        title: "propagating a literal None or Err",
+        body: "\
+`?` or `Try` is applied to a literal None or Err, so the enclosing function
+always returns early at that point and the code after it never runs.
+
+    Double[n: Int32] := Ok[Try[Err[\"todo\"]] * 2]
+
+Return the error directly if that is what was meant:
+
+    Double[n: Int32] := Err[\"todo\"]
+",
+    },
+    Explanation {
+        code: "W0004",
+        title: "infinite recursion",
+        body: "\
+A function calls itself whichever Cond, If or Match branch it takes, so no
+call ever returns and the program overflows its stack. Branches whose
+conditions are constant are followed; others count as a possible base case.
+
+    Countdown[n: Int32] := If[n > 0, Countdown[n - 1], Countdown[n]]
+
+Give it a branch that returns without calling itself:
+
+    Countdown[n: Int32] := If[n > 0, Countdown[n - 1], 0]
+",
+    },
+    Explanation {
+        code: "W0005",
+        title: "unused lambda parameter",
+        body: "\
+A lambda takes a parameter its body never uses. Often another parameter was
+used by mistake; otherwise the lambda ignores that argument on purpose.
+
+    Fold[Function[{acc, x}, acc + 1], 0, [4, 5, 6]]
+
+Use it, or start its name with `_` to say it is ignored:
+
+    Fold[Function[{acc, _x}, acc + 1], 0, [4, 5, 6]]
+",
+    },
+    Explanation {
+        code: "W0006",
+        title: "unused Let binding",
+        body: "\
+A top-level `Let` binds a name no later statement uses, so its value is
+computed and thrown away.
+
+    Let[total, Sum[[1, 2, 3]]]
+    Print[Sum[[1, 2, 3]]]
+
+Use the name, or start it with `_` if only the computation matters:
+
+    Let[total, Sum[[1, 2, 3]]]
+    Print[total]
+",
+    },
+    Explanation {
+        code: "W0007",
+        title: "unused function",
+        body: "\
+A function is never called or passed to another function. In a file of
+definitions only, public functions are exported, so only `Private` ones are
+reported there.
+
+    Double[n: Int32] := n * 2
+    Print[3 + 3]
+
+Call it or remove it:
+
+    Print[Double[3]]
+",
+    },
+    Explanation {
+        code: "W0008",
+        title: "unreachable Cond branch",
+        body: "\
+A Cond condition is always true, so the branches after it, including the
+default, never run. Literal comparisons and `Let` bindings of integers are
+followed.
+
+    Let[limit, 10]
+    Print[Cond[[limit > 5, \"big\"], [limit > 0, \"small\"], Else[\"none\"]]]
+
+Fix the condition, or keep only the branch that runs:
+
+    Print[\"big\"]
+",
+    },
+    Explanation {
+        code: "W0009",
+        title: "unreachable code",
+        body: "\
+`Panic` and `Exit` never return, so the statements after one, and the
+arguments or operand evaluated after one, never run.
+
+    Exit[1]
+    Print[\"done\"]          # never printed
+
+Move the code before the call, or remove it:
+
+    Print[\"done\"]
+    Exit[1]
+",
+    },
+    Explanation {
+        code: "W0010",
+        title: "shadowed binding",
+        body: "\
+A Let, lambda parameter, pattern or For binding has the same name as a
+binding in an enclosing scope, which it hides. Code inside that means the
+outer value silently gets the inner one, which is easy to miss when the code
+is edited later.
+
+    Let[total, 10]
+    Print[Map[Function[{total}, total * 2], [1, 2]]]
+
+Give the inner binding its own name, or start it with `_` if it is unused:
+
+    Print[Map[Function[{n}, n * 2], [1, 2]]]
+
+`--allow-warning W0010` turns the warning off.
+",
+    },
+];
+
+/// The explanation for `code` (case-insensitive, e.g. `e0001`)
+pub fn explain(code: &str) -> Option<&'static Explanation> {
+    EXPLANATIONS.iter().find(|explanation| explanation.code.eq_ignore_ascii_case(code))
+}
@@ -0,0 +1,65 @@
+//! Source Migration
+//!
+//! `w fix` rewrites constructs that have a newer spelling, leaving the rest
+//! of the file as it was written. Each fix is located by the parser, so only
+//! code that parses is rewritten, and a fixed file parses to the same program
+//! as before. Running it again finds nothing more to do.
+//!
+//! Fixes applied:
+//! - A Cond default written as a lone bracketed expression, `[default]`,
+//!   becomes `Else[default]`, which cannot be mistaken for a condition.
+
+use crate::lexer::Span;
+use crate::parser::Parser;
+use std::fmt;
+
+/// What a fix rewrote
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FixKind {
+    /// `Cond[... [default]]` to `Cond[... Else[default]]`
+    CondDefaultElse,
+}
+
+/// A rewrite made to the source, at its position in the original
+#[derive(Debug, Clone, PartialEq)]
+pub struct AppliedFix {
+    pub kind: FixKind,
+    pub span: Span,
+}
+
+impl fmt::Display for AppliedFix {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            FixKind::CondDefaultElse => write!(f, "{}: Cond default `[...]` rewritten as `Else[...]`", self.span),
+        }
+    }
+}
+
+/// The migrated source and the fixes that produced it, in source order
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fixed {
+    pub source: String,
+    pub fixes: Vec<AppliedFix>,
+}
+
+/// Apply every fix to `source`; `None` if it does not parse
+pub fn fix_source(source: &str) -> Option<Fixed> {
+    let mut parser = Parser::new(source.to_string());
+    parser.parse()?;
+    let fixes: Vec<AppliedFix> = parser
+        .bracket_defaults()
+        .iter()
+        .map(|&span| AppliedFix { kind: FixKind::CondDefaultElse, span })
+        .collect();
+
+    // Spans count characters; insert from the end so earlier ones stay valid
+    let mut chars: Vec<char> = source.chars().collect();
+    for fix in fixes.iter().rev() {
+        match fix.kind {
+            FixKind::CondDefaultElse => {
+                chars.splice(fix.span.start..fix.span.start, "Else".chars());
+            }
+        }
+    }
+    Some(Fixed { source: chars.into_iter().collect(), fixes })
+}
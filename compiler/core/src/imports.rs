@@ -0,0 +1,197 @@
+//! Import Resolution
+//!
+//! Follows `Import["file.w"]` statements from the entry file, loading and
+//! parsing each imported file once. Every file becomes a module named after
+//! its file stem (`geometry.w` → `Geometry`, `shape_utils.w` → `ShapeUtils`),
+//! listed in dependency order so a module only relies on the ones before it.
+
+use crate::ast::Expression;
+use crate::parser::Parser;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The entry program and the modules it imports, directly or indirectly
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoadedProgram {
+    /// Entry program with its Import statements removed
+    pub entry: Expression,
+    /// `(module name, program)` pairs in dependency order, as expected by
+    /// `RustCodeGenerator::generate_modules`
+    pub modules: Vec<(String, Expression)>,
+    /// Canonical path each module was loaded from, parallel to `modules`
+    pub module_paths: Vec<PathBuf>,
+    /// Source text of each module, parallel to `modules`
+    pub module_sources: Vec<String>,
+}
+
+/// Import resolution errors
+#[derive(Debug, Clone, PartialEq)]
+pub enum ImportError {
+    /// The imported file does not exist or cannot be read
+    MissingFile {
+        path: PathBuf,
+        imported_from: PathBuf,
+        reason: String,
+    },
+    /// The imported file does not parse (`message` starts with the location)
+    Parse { path: PathBuf, message: String },
+    /// Files importing each other, starting and ending with the same file
+    Cycle(Vec<PathBuf>),
+    /// Two different files that would both become module `name`
+    DuplicateModule {
+        name: String,
+        first: PathBuf,
+        second: PathBuf,
+    },
+    /// A file name that cannot be turned into a module name
+    InvalidModuleName(PathBuf),
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ImportError::MissingFile { path, imported_from, reason } => {
+                write!(f, "{}: cannot import {}: {}", imported_from.display(), path.display(), reason)
+            }
+            ImportError::Parse { path, message } => write!(f, "{}:{}", path.display(), message),
+            ImportError::Cycle(paths) => {
+                let chain: Vec<String> = paths.iter().map(|path| path.display().to_string()).collect();
+                write!(f, "Import cycle: {}", chain.join(" -> "))
+            }
+            ImportError::DuplicateModule { name, first, second } => {
+                write!(
+                    f,
+                    "{} and {} would both be imported as module {}; rename one of them",
+                    first.display(), second.display(), name
+                )
+            }
+            ImportError::InvalidModuleName(path) => {
+                write!(
+                    f,
+                    "{}: file name is not a valid module name (use letters, digits and underscores)",
+                    path.display()
+                )
+            }
+        }
+    }
+}
+
+/// Load every file imported from `entry` (parsed from `entry_path`).
+///
+/// Import paths are relative to the file containing the Import. A file
+/// imported from several places is loaded once; an entry without imports is
+/// returned unchanged.
+pub fn load_imports(entry_path: &Path, entry: Expression) -> Result<LoadedProgram, ImportError> {
+    let entry_path = entry_path.canonicalize().unwrap_or_else(|_| entry_path.to_path_buf());
+    let mut loader = Loader {
+        modules: Vec::new(),
+        paths: Vec::new(),
+        sources: Vec::new(),
+        stack: vec![entry_path.clone()],
+    };
+    let entry = loader.resolve(&entry_path, entry)?;
+    Ok(LoadedProgram { entry, modules: loader.modules, module_paths: loader.paths, module_sources: loader.sources })
+}
+
+/// Module name for a W file: its stem in PascalCase
+pub fn module_name(path: &Path) -> Option<String> {
+    let stem = path.file_stem()?.to_str()?;
+    if !stem.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    let mut name = String::new();
+    for segment in stem.split(['_', '-']) {
+        if segment.is_empty() || !segment.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return None;
+        }
+        let mut chars = segment.chars();
+        if let Some(first) = chars.next() {
+            name.push(first.to_ascii_uppercase());
+            name.extend(chars);
+        }
+    }
+    Some(name)
+}
+
+struct Loader {
+    /// Modules loaded so far, in dependency order
+    modules: Vec<(String, Expression)>,
+    /// Canonical path each module was loaded from, parallel to `modules`
+    paths: Vec<PathBuf>,
+    /// Source text of each module, parallel to `modules`
+    sources: Vec<String>,
+    /// Files currently being loaded, outermost first (for cycle detection)
+    stack: Vec<PathBuf>,
+}
+
+impl Loader {
+    /// Load everything `program` (read from `path`) imports and return it
+    /// without its Import statements
+    fn resolve(&mut self, path: &Path, program: Expression) -> Result<Expression, ImportError> {
+        let expressions = match program {
+            Expression::Program(expressions) => expressions,
+            Expression::Import(import) => vec![Expression::Import(import)],
+            other => return Ok(other),
+        };
+        if !expressions.iter().any(|expr| matches!(expr, Expression::Import(_))) {
+            return Ok(Expression::Program(expressions));
+        }
+
+        let directory = path.parent().unwrap_or(Path::new(""));
+        let mut remaining = Vec::new();
+        for expr in expressions {
+            match expr {
+                Expression::Import(import) => self.load(&directory.join(import), path)?,
+                other => remaining.push(other),
+            }
+        }
+        Ok(Expression::Program(remaining))
+    }
+
+    fn load(&mut self, path: &Path, imported_from: &Path) -> Result<(), ImportError> {
+        let missing = |error: std::io::Error| ImportError::MissingFile {
+            path: path.to_path_buf(),
+            imported_from: imported_from.to_path_buf(),
+            reason: error.to_string(),
+        };
+        let canonical = path.canonicalize().map_err(missing)?;
+
+        if let Some(start) = self.stack.iter().position(|open| *open == canonical) {
+            let mut cycle = self.stack[start..].to_vec();
+            cycle.push(canonical);
+            return Err(ImportError::Cycle(cycle));
+        }
+        if self.paths.contains(&canonical) {
+            return Ok(());
+        }
+
+        let name = module_name(&canonical).ok_or_else(|| ImportError::InvalidModuleName(path.to_path_buf()))?;
+        if let Some(index) = self.modules.iter().position(|(existing, _)| *existing == name) {
+            return Err(ImportError::DuplicateModule {
+                name,
+                first: self.paths[index].clone(),
+                second: canonical,
+            });
+        }
+
+        let source = fs::read_to_string(&canonical).map_err(missing)?;
+        let mut parser = Parser::new(source.clone());
+        let program = parser.parse().ok_or_else(|| ImportError::Parse {
+            path: path.to_path_buf(),
+            message: match parser.lex_error() {
+                Some(error) => error.to_string(),
+                None => format!("{}: failed to parse expression", parser.current_span()),
+            },
+        })?;
+
+        self.stack.push(canonical.clone());
+        let program = self.resolve(&canonical, program)?;
+        self.stack.pop();
+
+        self.modules.push((name, program));
+        self.paths.push(canonical);
+        self.sources.push(source);
+        Ok(())
+    }
+}
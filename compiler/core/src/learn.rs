@@ -0,0 +1,252 @@
+//! Interactive Tutorial
+//!
+//! `w learn` walks through a fixed sequence of exercises. Each answer is
+//! parsed, type checked, compiled to Rust and run with rustc; the program's
+//! output is compared with what the exercise asks for. Failures are reported
+//! with the same error codes as the compiler, so `w --explain` applies.
+
+use crate::const_eval::ConstError;
+use crate::driver::{self, CompileError, NoCallbacks};
+use crate::explain;
+use crate::rust_codegen::CodegenOptions;
+use crate::type_inference::LocatedTypeError;
+use std::fmt;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Distinguishes the scratch directories of answers checked at the same time
+static SCRATCH_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// One exercise of the tutorial
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lesson {
+    pub title: &'static str,
+    /// What the learner is asked to write
+    pub task: &'static str,
+    /// Exact stdout a correct answer prints
+    pub expected_output: &'static str,
+    /// Shown on request, or after a wrong answer
+    pub hint: &'static str,
+    /// A correct answer
+    pub solution: &'static str,
+}
+
+/// The exercises, in the order they are taught
+pub const LESSONS: &[Lesson] = &[
+    Lesson {
+        title: "Print",
+        task: "Every operation in W is a call written with square brackets.\n\
+               Print the text Hello, W! on its own line.",
+        expected_output: "Hello, W!\n",
+        hint: "Strings go in double quotes: Print[\"...\"]",
+        solution: "Print[\"Hello, W!\"]",
+    },
+    Lesson {
+        title: "Arithmetic",
+        task: "Infix operators follow the usual precedence.\n\
+               Print the result of 6 times 7, plus 3.",
+        expected_output: "45\n",
+        hint: "Multiplication binds tighter than addition: Print[6 * 7 + 3]",
+        solution: "Print[6 * 7 + 3]",
+    },
+    Lesson {
+        title: "Functions",
+        task: "Functions are defined with `Name[parameter: Type] := body`.\n\
+               Define Double, taking an Int32 x and returning twice x, then print Double[21].",
+        expected_output: "42\n",
+        hint: "Double[x: Int32] := x * 2, then Print[Double[21]] on the next line",
+        solution: "Double[x: Int32] := x * 2\nPrint[Double[21]]",
+    },
+    Lesson {
+        title: "Match",
+        task: "Match[value, [pattern, result], ...] picks the first arm whose pattern fits.\n\
+               Bind n to 0 with Let, then print \"zero\" if n is 0 and \"other\" otherwise.",
+        expected_output: "zero\n",
+        hint: "`_` matches anything: Print[Match[n, [0, \"zero\"], [_, \"other\"]]]",
+        solution: "Let[n, 0]\nPrint[Match[n, [0, \"zero\"], [_, \"other\"]]]",
+    },
+    Lesson {
+        title: "Structs",
+        task: "Struct[Name, [field: Type, ...]] defines a record; fields are read with `.field`.\n\
+               Define a Point with Int32 fields x and y, bind p to Point[3, 4] and print p.x + p.y.",
+        expected_output: "7\n",
+        hint: "Struct[Point, [x: Int32, y: Int32]], then Let[p, Point[3, 4]] and Print[p.x + p.y]",
+        solution: "Struct[Point, [x: Int32, y: Int32]]\nLet[p, Point[3, 4]]\nPrint[p.x + p.y]",
+    },
+];
+
+/// Why an answer was not accepted
+#[derive(Debug, Clone, PartialEq)]
+pub enum Feedback {
+    /// The answer does not parse; `code` is the lexing or parsing error code
+    Parse { code: &'static str, message: String },
+    /// The answer parses but does not type check
+    Type(Box<LocatedTypeError>),
+    /// The answer's arithmetic on constants always fails
+    Const(Box<ConstError>),
+    /// The answer uses something the code generator cannot translate yet
+    Unsupported,
+    /// rustc rejected the generated code (holds its diagnostics)
+    Rust(String),
+    /// The program ran but printed something else
+    WrongOutput { expected: String, actual: String },
+}
+
+impl Feedback {
+    /// Error code the learner can look up with `w --explain`, if any
+    pub fn code(&self) -> Option<&'static str> {
+        match self {
+            Feedback::Parse { code, .. } => Some(code),
+            Feedback::Type(error) => Some(error.code()),
+            Feedback::Const(error) => Some(error.code()),
+            _ => None,
+        }
+    }
+}
+
+impl From<CompileError> for Feedback {
+    fn from(error: CompileError) -> Self {
+        match error {
+            CompileError::Parse { code, message } => Feedback::Parse { code, message },
+            // One thing to fix at a time
+            CompileError::Type(errors) => Feedback::Type(Box::new(errors.into_iter().next().expect("a type error"))),
+            CompileError::Const(errors) => Feedback::Const(Box::new(errors.into_iter().next().expect("a constant error"))),
+            CompileError::Unsupported => Feedback::Unsupported,
+            CompileError::NoStd(_) | CompileError::Profile(_) => {
+                unreachable!("the tutorial compiles with the default options")
+            }
+            CompileError::Import(_) | CompileError::ModuleType(_) => {
+                unreachable!("the tutorial compiles a single source without imports")
+            }
+            CompileError::Cancelled => unreachable!("the tutorial never cancels a compilation"),
+        }
+    }
+}
+
+impl fmt::Display for Feedback {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Feedback::Parse { code, message } => write!(f, "error[{}]: {}", code, message),
+            Feedback::Type(error) => write!(f, "error[{}]: {}", error.code(), error),
+            Feedback::Const(error) => write!(f, "error[{}]: {}", error.code(), error),
+            Feedback::Unsupported => write!(f, "This answer uses something W cannot compile yet; try a simpler form"),
+            Feedback::Rust(diagnostics) => write!(f, "The generated Rust did not compile:\n{}", diagnostics),
+            Feedback::WrongOutput { expected, actual } => {
+                write!(f, "Your program printed {:?}, but the exercise expects {:?}", actual, expected)
+            }
+        }
+    }
+}
+
+/// Check an answer to `lesson` by compiling and running it
+pub fn check_answer(lesson: &Lesson, source: &str) -> Result<(), Feedback> {
+    let rust_code = driver::compile(source, &CodegenOptions::default(), &mut NoCallbacks)?;
+    let actual = compile_and_run(&rust_code).map_err(Feedback::Rust)?;
+    if actual != lesson.expected_output {
+        return Err(Feedback::WrongOutput { expected: lesson.expected_output.to_string(), actual });
+    }
+    Ok(())
+}
+
+/// Compile `rust_code` with rustc in a scratch directory and return what it prints
+fn compile_and_run(rust_code: &str) -> Result<String, String> {
+    let scratch = SCRATCH_COUNTER.fetch_add(1, Ordering::Relaxed);
+    let dir = std::env::temp_dir().join(format!("w_learn_{}_{}", std::process::id(), scratch));
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let source = dir.join("main.rs");
+    let binary = dir.join("main");
+    fs::write(&source, rust_code).map_err(|e| e.to_string())?;
+
+    let result = Command::new("rustc")
+        .arg(&source)
+        .arg("-o")
+        .arg(&binary)
+        .arg("-A")
+        .arg("warnings")
+        .output()
+        .map_err(|e| format!("cannot run rustc: {}", e))
+        .and_then(|compiled| {
+            if !compiled.status.success() {
+                return Err(String::from_utf8_lossy(&compiled.stderr).into_owned());
+            }
+            let ran = Command::new(&binary).output().map_err(|e| e.to_string())?;
+            Ok(String::from_utf8_lossy(&ran.stdout).into_owned())
+        });
+    fs::remove_dir_all(&dir).ok();
+    result
+}
+
+/// Run the tutorial, reading answers from `input` and writing to `output`.
+///
+/// An answer is every line up to the next empty line. `hint` shows the
+/// lesson's hint, `skip` moves on and `quit` stops. Returns how many lessons
+/// were solved.
+pub fn run(mut input: impl BufRead, mut output: impl Write) -> io::Result<usize> {
+    writeln!(output, "Welcome to W! Write each answer, then an empty line to check it.")?;
+    writeln!(output, "Type `hint` for a hint, `skip` to move on or `quit` to stop.")?;
+
+    let mut solved = 0;
+    'lessons: for (number, lesson) in LESSONS.iter().enumerate() {
+        writeln!(output, "\nLesson {}/{}: {}", number + 1, LESSONS.len(), lesson.title)?;
+        writeln!(output, "{}", lesson.task)?;
+
+        loop {
+            let Some(answer) = read_answer(&mut input, &mut output)? else {
+                break 'lessons;
+            };
+            match answer.trim() {
+                "" => continue,
+                "quit" => break 'lessons,
+                "skip" => {
+                    writeln!(output, "Skipped. One solution is:\n{}", lesson.solution)?;
+                    continue 'lessons;
+                }
+                "hint" => {
+                    writeln!(output, "Hint: {}", lesson.hint)?;
+                    continue;
+                }
+                _ => {}
+            }
+
+            match check_answer(lesson, &answer) {
+                Ok(()) => {
+                    writeln!(output, "Correct!")?;
+                    solved += 1;
+                    continue 'lessons;
+                }
+                Err(feedback) => {
+                    writeln!(output, "{}", feedback)?;
+                    if let Some(explanation) = feedback.code().and_then(explain::explain) {
+                        writeln!(output, "({}: {}; run `w --explain {}` for more)", explanation.code, explanation.title, explanation.code)?;
+                    }
+                    writeln!(output, "Hint: {}", lesson.hint)?;
+                }
+            }
+        }
+    }
+
+    writeln!(output, "\nYou solved {} of {} lessons.", solved, LESSONS.len())?;
+    Ok(solved)
+}
+
+/// Read lines up to an empty line. None at the end of input with nothing read
+fn read_answer(input: &mut impl BufRead, output: &mut impl Write) -> io::Result<Option<String>> {
+    let mut answer = String::new();
+    loop {
+        write!(output, "{} ", if answer.is_empty() { ">" } else { "." })?;
+        output.flush()?;
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(if answer.is_empty() { None } else { Some(answer) });
+        }
+        let line = line.trim_end();
+        // A command or an empty line ends the answer
+        if line.is_empty() || (answer.is_empty() && matches!(line, "hint" | "skip" | "quit")) {
+            return Ok(Some(if answer.is_empty() { line.to_string() } else { answer }));
+        }
+        answer.push_str(line);
+        answer.push('\n');
+    }
+}
@@ -0,0 +1,757 @@
+use crate::ast::Radix;
+
+/// Represents the different types of tokens recognized by the lexer.
+///
+/// Each variant corresponds to a specific syntactic element in the language,
+/// such as literals, operators, punctuation, and special keywords.
+///
+/// # Token Types
+/// - Identifiers: Variable and function names
+/// - Brackets and Braces: For function calls, lists, and maps
+/// - Literals: Numbers, Strings, Booleans
+/// - Operators: Arithmetic and comparison operations
+/// - Log Levels: Debugging and logging keywords
+///
+/// # Variants
+/// Some variants carry additional data (e.g., `Identifier`, `Number`) to preserve
+/// the original lexeme's value during parsing.
+#[allow(dead_code)]
+#[derive(Debug, PartialEq, Clone)]
+pub enum Token {
+    /// Represents a user-defined or language identifier
+    Identifier(String),
+
+    /// Left square bracket `[`
+    LeftBracket,
+    /// Right square bracket `]`
+    RightBracket,
+    /// Left curly brace `{`
+    LeftBrace,
+    /// Right curly brace `}`
+    RightBrace,
+    /// Left parenthesis `(`
+    LeftParen,
+    /// Right parenthesis `)`
+    RightParen,
+
+    /// Comma `,` used for separating elements
+    Comma,
+    /// Colon `:` used for type annotations
+    Colon,
+    /// Define token `:=` for function definitions
+    Define,
+    /// Dot `.` separating the segments of a qualified name (`Math.Sqrt`)
+    Dot,
+    /// Rest `..` in struct and list patterns
+    DotDot,
+    /// Path separator `::`, an alternative to `.` in qualified names
+    PathSeparator,
+
+    /// Integer literal up to `i64::MAX`, written in decimal or with a
+    /// `0x` / `0o` / `0b` prefix; `_` separators are allowed between digits
+    Number(i64, Radix),
+    /// 64-bit floating-point literal (Rust's default), e.g. `2.5` or `1.5e10`
+    Float(f64),
+    /// String literal, with escape sequences already processed
+    String(String),
+    /// Boolean literal (true/false)
+    Boolean(bool),
+
+    /// Addition operator `+`
+    Plus,
+    /// Subtraction operator `-`
+    Minus,
+    /// Multiplication operator `*`
+    Multiply,
+    /// Division operator `/`
+    Divide,
+    /// Exponentiation operator `^`
+    Power,
+
+    /// Equality comparison `==`
+    Equals,
+    /// Inequality comparison `!=`
+    NotEquals,
+    /// Less than comparison `<`
+    LessThan,
+    /// Greater than comparison `>`
+    GreaterThan,
+    /// Less than or equal comparison `<=`
+    LessEqual,
+    /// Greater than or equal comparison `>=`
+    GreaterEqual,
+
+    /// Logical and `&&`
+    And,
+    /// Logical or `||`
+    Or,
+    /// Logical negation `!`
+    Not,
+
+    /// Bitwise and `&`
+    BitAnd,
+    /// Bitwise or `|`
+    BitOr,
+    /// Bitwise exclusive or `^^` (`^` is exponentiation)
+    BitXor,
+    /// Left shift `<<`
+    ShiftLeft,
+    /// Right shift `>>`
+    ShiftRight,
+
+    /// Pipe operator `|>`
+    Pipe,
+
+    /// Arrow operator `->` for lambda shorthand
+    Arrow,
+
+    /// Question mark `?` for error propagation
+    Question,
+
+    /// Single `=` giving a parameter its default value, e.g. `greeting: String = "Hello"`
+    Assign,
+
+    /// At sign `@` binding a whole value in a pattern, e.g. `whole @ Some[x]`
+    At,
+
+    /// Logging level tokens for different verbosity levels
+    LogDebug,   // Debug log level
+    LogInfo,    // Info log level
+    LogWarn,    // Warning log level
+    LogError,   // Error log level
+
+    // Error handling keywords (Rust's safety model)
+    /// None - represents empty Option value
+    None,
+    /// Some - represents present Option value
+    Some,
+    /// Ok - represents success Result value
+    Ok,
+    /// Err - represents error Result value
+    Err,
+
+    // Pattern matching
+    /// Underscore `_` - wildcard pattern
+    Underscore,
+}
+
+/// Location of a token in the source.
+///
+/// `start` and `end` are character offsets (end exclusive); `line` and
+/// `column` are 1-based and refer to the token's first character.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl std::fmt::Display for Span {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// A token together with where it appeared in the source
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpannedToken {
+    pub token: Token,
+    pub span: Span,
+}
+
+/// Errors produced while tokenizing
+#[derive(Debug, Clone, PartialEq)]
+pub enum LexError {
+    /// A character that cannot start any token (e.g. `#` or `~`)
+    UnexpectedChar { ch: char, span: Span },
+    /// A string literal missing its closing `"`; the span covers the opening quote
+    UnterminatedString { span: Span },
+    /// An unsupported escape such as `\q` inside a string literal
+    InvalidEscape { escape: char, span: Span },
+    /// An integer literal with a digit its radix lacks (`0b102`), no digits
+    /// (`0x`), a `_` not between two digits (`1__0`) or a value too large for
+    /// Int64; the span covers the literal
+    InvalidNumber { reason: String, span: Span },
+}
+
+impl LexError {
+    /// Where in the source the error occurred
+    pub fn span(&self) -> Span {
+        match self {
+            LexError::UnexpectedChar { span, .. }
+            | LexError::UnterminatedString { span }
+            | LexError::InvalidEscape { span, .. }
+            | LexError::InvalidNumber { span, .. } => *span,
+        }
+    }
+
+    /// Stable code identifying the kind of error, explained by `w --explain <code>`
+    pub fn code(&self) -> &'static str {
+        match self {
+            LexError::UnexpectedChar { .. } => "E0100",
+            LexError::UnterminatedString { .. } => "E0101",
+            LexError::InvalidEscape { .. } => "E0102",
+            LexError::InvalidNumber { .. } => "E0104",
+        }
+    }
+}
+
+impl std::fmt::Display for LexError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            LexError::UnexpectedChar { ch, span } => {
+                write!(f, "{}: unexpected character {:?}", span, ch)
+            }
+            LexError::UnterminatedString { span } => {
+                write!(f, "{}: unterminated string literal (missing closing \")", span)
+            }
+            LexError::InvalidEscape { escape, span } => {
+                write!(f, "{}: invalid escape sequence \\{} (expected one of \\\" \\\\ \\n \\t \\r)", span, escape)
+            }
+            LexError::InvalidNumber { reason, span } => {
+                write!(f, "{}: invalid number literal: {}", span, reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for LexError {}
+
+/// Represents the lexical analyzer (tokenizer) for the language.
+///
+/// # Purpose
+/// The Lexer breaks down the input source code into a sequence of tokens
+/// that can be further processed by the parser.
+///
+/// # Components
+/// - `input`: A vector of characters representing the entire input source
+/// - `position`: Current position in the input stream during tokenization
+/// - `line_starts`: Offset of the first character of every line, for spans
+///
+/// # Tokenization Process
+/// 1. Convert input string to a character vector
+/// 2. Iterate through characters
+/// 3. Recognize and generate appropriate tokens
+/// 4. Skip whitespace and handle different token types
+pub struct Lexer {
+    /// The entire input source code as a vector of characters
+    input: Vec<char>,
+    /// Current reading position in the input stream
+    position: usize,
+    /// Character offset at which each line begins (always starts with 0)
+    line_starts: Vec<usize>,
+}
+
+impl Lexer {
+    /// Creates a new Lexer instance from an input string.
+    ///
+    /// # Arguments
+    /// * `input` - The source code to be tokenized
+    ///
+    /// # Returns
+    /// A new Lexer with the input converted to a character vector
+    ///
+    /// # Details
+    /// - Converts the input string to a vector of characters
+    /// - Initializes the reading position to the start of the input
+    pub fn new(input: String) -> Self {
+        let input: Vec<char> = input.chars().collect();
+        let line_starts = std::iter::once(0)
+            .chain(input.iter().enumerate().filter(|(_, &c)| c == '\n').map(|(i, _)| i + 1))
+            .collect();
+        Lexer {
+            input,
+            position: 0,
+            line_starts,
+        }
+    }
+
+    /// Peek at the next token without consuming it
+    ///
+    /// # Returns
+    /// - `Some(Token)` if a valid token is found
+    /// - `None` if no more tokens are available, or the next token is
+    ///   malformed (the error is reported once it is actually consumed)
+    pub fn peek_token(&self) -> Option<Token> {
+        // Create a temporary clone to peek ahead; its spans are never used
+        let mut temp_lexer = Lexer {
+            input: self.input.clone(),
+            position: self.position,
+            line_starts: vec![0],
+        };
+        temp_lexer.next_token().ok().flatten()
+    }
+
+    /// 1-based line and column of a character offset
+    pub fn line_column(&self, offset: usize) -> (usize, usize) {
+        let line = self.line_starts.partition_point(|&start| start <= offset);
+        (line, offset - self.line_starts[line - 1] + 1)
+    }
+
+    /// Span covering the characters from `start` up to the current position
+    fn span_from(&self, start: usize) -> Span {
+        let (line, column) = self.line_column(start);
+        Span { start, end: self.position, line, column }
+    }
+
+    /// Generates the next token along with its source span.
+    ///
+    /// # Returns
+    /// - `Ok(Some(SpannedToken))` if a valid token is found
+    /// - `Ok(None)` if no more tokens are available
+    /// - `Err(LexError)` if the input at this point is not a valid token
+    pub fn next_spanned_token(&mut self) -> Result<Option<SpannedToken>, LexError> {
+        self.skip_whitespace();
+        let start = self.position;
+        let token = self.next_token()?;
+        Ok(token.map(|token| SpannedToken { token, span: self.span_from(start) }))
+    }
+
+    /// Span of the position the lexer has reached, as an empty range
+    pub fn current_span(&self) -> Span {
+        self.span_from(self.position)
+    }
+
+    /// Generates the next token from the input stream.
+    ///
+    /// # Returns
+    /// - `Ok(Some(Token))` if a valid token is found
+    /// - `Ok(None)` if no more tokens are available
+    /// - `Err(LexError)` for an unexpected character, an unterminated string
+    ///   or an invalid escape sequence
+    ///
+    /// # Token Recognition
+    /// Recognizes various token types:
+    /// - Punctuation (brackets, braces, commas)
+    /// - Operators (arithmetic, comparison)
+    /// - Literals (numbers, strings, booleans)
+    /// - Keywords (log levels, boolean values)
+    /// - Identifiers
+    pub fn next_token(&mut self) -> Result<Option<Token>, LexError> {
+        // Skip any leading whitespace
+        self.skip_whitespace();
+        
+        // Check if we've reached the end of input
+        if self.position >= self.input.len() {
+            return Ok(None);
+        }
+        let start = self.position;
+
+        // Match and generate tokens based on current character
+        let token = match self.input[self.position] {
+            '[' => {
+                self.position += 1;
+                Some(Token::LeftBracket)
+            }
+            ']' => {
+                self.position += 1;
+                Some(Token::RightBracket)
+            }
+            '{' => {
+                self.position += 1;
+                Some(Token::LeftBrace)
+            }
+            '}' => {
+                self.position += 1;
+                Some(Token::RightBrace)
+            }
+            '(' => {
+                self.position += 1;
+                Some(Token::LeftParen)
+            }
+            ')' => {
+                self.position += 1;
+                Some(Token::RightParen)
+            }
+            ':' => {
+                self.position += 1;
+                // Check for := and ::
+                if self.position < self.input.len() && self.input[self.position] == '=' {
+                    self.position += 1;
+                    Some(Token::Define)
+                } else if self.position < self.input.len() && self.input[self.position] == ':' {
+                    self.position += 1;
+                    Some(Token::PathSeparator)
+                } else {
+                    Some(Token::Colon)
+                }
+            }
+            ',' => {
+                self.position += 1;
+                Some(Token::Comma)
+            }
+            '.' => {
+                self.position += 1;
+                // Check for ..
+                if self.position < self.input.len() && self.input[self.position] == '.' {
+                    self.position += 1;
+                    Some(Token::DotDot)
+                } else {
+                    Some(Token::Dot)
+                }
+            }
+            '+' => {
+                self.position += 1;
+                Some(Token::Plus)
+            }
+            '-' => {
+                self.position += 1;
+                // Check for ->
+                if self.position < self.input.len() && self.input[self.position] == '>' {
+                    self.position += 1;
+                    Some(Token::Arrow)
+                } else {
+                    Some(Token::Minus)
+                }
+            }
+            '*' => {
+                self.position += 1;
+                Some(Token::Multiply)
+            }
+            '/' => {
+                self.position += 1;
+                Some(Token::Divide)
+            }
+            '^' => {
+                self.position += 1;
+                // Check for ^^
+                if self.position < self.input.len() && self.input[self.position] == '^' {
+                    self.position += 1;
+                    Some(Token::BitXor)
+                } else {
+                    Some(Token::Power)
+                }
+            }
+            '=' => {
+                self.position += 1;
+                // Check for ==
+                if self.position < self.input.len() && self.input[self.position] == '=' {
+                    self.position += 1;
+                    Some(Token::Equals)
+                } else {
+                    Some(Token::Assign)
+                }
+            }
+            '!' => {
+                self.position += 1;
+                // Check for !=
+                if self.position < self.input.len() && self.input[self.position] == '=' {
+                    self.position += 1;
+                    Some(Token::NotEquals)
+                } else {
+                    Some(Token::Not)
+                }
+            }
+            '<' => {
+                self.position += 1;
+                // Check for <<
+                if self.position < self.input.len() && self.input[self.position] == '<' {
+                    self.position += 1;
+                    Some(Token::ShiftLeft)
+                } else if self.position < self.input.len() && self.input[self.position] == '=' {
+                    self.position += 1;
+                    Some(Token::LessEqual)
+                } else {
+                    Some(Token::LessThan)
+                }
+            }
+            '>' => {
+                self.position += 1;
+                // Check for >>
+                if self.position < self.input.len() && self.input[self.position] == '>' {
+                    self.position += 1;
+                    Some(Token::ShiftRight)
+                } else if self.position < self.input.len() && self.input[self.position] == '=' {
+                    self.position += 1;
+                    Some(Token::GreaterEqual)
+                } else {
+                    Some(Token::GreaterThan)
+                }
+            }
+            '|' => {
+                self.position += 1;
+                // Check for |>
+                if self.position < self.input.len() && self.input[self.position] == '>' {
+                    self.position += 1;
+                    Some(Token::Pipe)
+                } else if self.position < self.input.len() && self.input[self.position] == '|' {
+                    self.position += 1;
+                    Some(Token::Or)
+                } else {
+                    Some(Token::BitOr)
+                }
+            }
+            '?' => {
+                self.position += 1;
+                Some(Token::Question)
+            }
+            '@' => {
+                self.position += 1;
+                Some(Token::At)
+            }
+            '&' => {
+                self.position += 1;
+                // Check for &&
+                if self.position < self.input.len() && self.input[self.position] == '&' {
+                    self.position += 1;
+                    Some(Token::And)
+                } else {
+                    Some(Token::BitAnd)
+                }
+            }
+            // `_name` is a name marked as intentionally unused; `_` alone is a wildcard
+            '_' if self.input.get(self.position + 1).is_some_and(|c| c.is_alphabetic()) => {
+                Some(Token::Identifier(self.read_identifier()))
+            }
+            '_' => {
+                self.position += 1;
+                Some(Token::Underscore)
+            }
+            '"' => {
+                // Handle string literals
+                Some(Token::String(self.read_string()?))
+            }
+            c if c.is_alphabetic() => {
+                // Handle keywords, identifiers, and boolean literals
+                let identifier = self.read_identifier();
+                match identifier.as_str() {
+                    "LogDebug" => Some(Token::LogDebug),
+                    "LogInfo" => Some(Token::LogInfo),
+                    "LogWarn" => Some(Token::LogWarn),
+                    "LogError" => Some(Token::LogError),
+                    "true" => Some(Token::Boolean(true)),
+                    "false" => Some(Token::Boolean(false)),
+                    // Error handling keywords
+                    "None" => Some(Token::None),
+                    "Some" => Some(Token::Some),
+                    "Ok" => Some(Token::Ok),
+                    "Err" => Some(Token::Err),
+                    _ => Some(Token::Identifier(identifier))
+                }
+            }
+            c if c.is_ascii_digit() => {
+                // Handle numeric literals
+                Some(self.read_number()?)
+            }
+            // Unrecognized character
+            _ => {
+                self.position += 1;
+                return Err(self.unexpected_char(start));
+            }
+        };
+
+        Ok(token)
+    }
+
+    /// Error for the single character at `start`
+    fn unexpected_char(&self, start: usize) -> LexError {
+        let (line, column) = self.line_column(start);
+        LexError::UnexpectedChar {
+            ch: self.input[start],
+            span: Span { start, end: start + 1, line, column },
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        loop {
+            // Skip whitespace
+            while self.position < self.input.len() && self.input[self.position].is_whitespace() {
+                self.position += 1;
+            }
+
+            // Check for ML-style comments (* ... *)
+            if self.position + 1 < self.input.len()
+                && self.input[self.position] == '('
+                && self.input[self.position + 1] == '*' {
+                // Skip the opening (*
+                self.position += 2;
+
+                // Find the closing *)
+                while self.position + 1 < self.input.len() {
+                    if self.input[self.position] == '*' && self.input[self.position + 1] == ')' {
+                        // Skip the closing *)
+                        self.position += 2;
+                        break;
+                    }
+                    self.position += 1;
+                }
+            } else {
+                // No more whitespace or comments to skip
+                break;
+            }
+        }
+    }
+
+    fn read_identifier(&mut self) -> String {
+        let mut identifier = String::new();
+        while self.position < self.input.len() &&
+              (self.input[self.position].is_alphabetic() ||
+               self.input[self.position].is_ascii_digit() ||
+               self.input[self.position] == '_') {
+            identifier.push(self.input[self.position]);
+            self.position += 1;
+        }
+        identifier
+    }
+
+    fn read_number(&mut self) -> Result<Token, LexError> {
+        let start = self.position;
+        // Hexadecimal (0x), octal (0o) and binary (0b) literals
+        if self.input[start] == '0' {
+            let radix = match self.input.get(start + 1) {
+                Some('x') | Some('X') => Some((16, Radix::Hexadecimal, "a hexadecimal")),
+                Some('o') | Some('O') => Some((8, Radix::Octal, "an octal")),
+                Some('b') | Some('B') => Some((2, Radix::Binary, "a binary")),
+                _ => None,
+            };
+            if let Some((base, radix, name)) = radix {
+                return self.read_radix_number(base, radix, name);
+            }
+        }
+
+        let mut number = self.read_digits(10, start)?;
+
+        // A `.` followed by a digit starts the fractional part of a float
+        let has_fraction = self.input.get(self.position) == Some(&'.')
+            && self.input.get(self.position + 1).is_some_and(|c| c.is_ascii_digit());
+        if has_fraction {
+            self.position += 1;
+            number.push('.');
+            number.push_str(&self.read_digits(10, start)?);
+        }
+
+        // An exponent (`e10`, `E-3`) also makes the literal a float
+        let exponent = self.exponent_length();
+        if let Some(sign_length) = exponent {
+            number.push('e');
+            if sign_length == 1 {
+                number.push(self.input[self.position + 1]);
+            }
+            self.position += 1 + sign_length;
+            number.push_str(&self.read_digits(10, start)?);
+        }
+
+        if has_fraction || exponent.is_some() {
+            return Ok(Token::Float(number.parse().unwrap_or(0.0)));
+        }
+
+        let value = number.parse().map_err(|_| self.number_too_large(start))?;
+        Ok(Token::Number(value, Radix::Decimal))
+    }
+
+    /// If the input at the current position is an exponent marker (`e` or `E`,
+    /// an optional sign, then a digit), returns the length of the sign (0 or 1).
+    fn exponent_length(&self) -> Option<usize> {
+        if !matches!(self.input.get(self.position), Some('e') | Some('E')) {
+            return None;
+        }
+        let sign_length = match self.input.get(self.position + 1) {
+            Some('+') | Some('-') => 1,
+            _ => 0,
+        };
+        self.input.get(self.position + 1 + sign_length)
+            .filter(|c| c.is_ascii_digit())
+            .map(|_| sign_length)
+    }
+
+    /// Reads a prefixed integer literal (`0xFF`, `0o755`, `0b1010`) in
+    /// `base`, which `name`s errors
+    fn read_radix_number(&mut self, base: u32, radix: Radix, name: &str) -> Result<Token, LexError> {
+        let start = self.position;
+        // Skip the `0x` / `0o` / `0b` prefix
+        self.position += 2;
+        let digits = self.read_digits(base, start)?;
+        // A letter or digit right after the literal is a mistyped digit
+        if let Some(&c) = self.input.get(self.position).filter(|c| c.is_ascii_alphanumeric()) {
+            self.position += 1;
+            return Err(self.invalid_number(start, format!("{} is not {} digit", c, name)));
+        }
+        if digits.is_empty() {
+            let prefix: String = self.input[start..start + 2].iter().collect();
+            return Err(self.invalid_number(start, format!("{} has no digits after it", prefix)));
+        }
+        let value = i64::from_str_radix(&digits, base).map_err(|_| self.number_too_large(start))?;
+        Ok(Token::Number(value, radix))
+    }
+
+    /// Reads a run of digits in the given radix, stripping `_` separators,
+    /// each of which must be between two digits of the literal at `start`
+    fn read_digits(&mut self, radix: u32, start: usize) -> Result<String, LexError> {
+        let mut digits = String::new();
+        while self.position < self.input.len() {
+            let c = self.input[self.position];
+            if c.is_digit(radix) {
+                digits.push(c);
+                self.position += 1;
+            } else if c == '_' {
+                let separates = !digits.is_empty()
+                    && self.input.get(self.position + 1).is_some_and(|c| c.is_digit(radix));
+                self.position += 1;
+                if !separates {
+                    while self.input.get(self.position).is_some_and(|&c| c == '_' || c.is_digit(radix)) {
+                        self.position += 1;
+                    }
+                    return Err(self.invalid_number(start, "a _ separator must be between two digits".to_string()));
+                }
+            } else {
+                break;
+            }
+        }
+        Ok(digits)
+    }
+
+    /// Error for the literal from `start` to the current position
+    fn invalid_number(&self, start: usize, reason: String) -> LexError {
+        let (line, column) = self.line_column(start);
+        LexError::InvalidNumber { reason, span: Span { start, end: self.position, line, column } }
+    }
+
+    /// Error for a literal from `start` above `i64::MAX`
+    fn number_too_large(&self, start: usize) -> LexError {
+        let literal: String = self.input[start..self.position].iter().collect();
+        self.invalid_number(start, format!("{} is larger than the largest Int64, {}", literal, i64::MAX))
+    }
+
+    /// Reads a string literal, processing the `\"`, `\\`, `\n`, `\t` and
+    /// `\r` escapes.
+    fn read_string(&mut self) -> Result<String, LexError> {
+        let start = self.position;
+        // Consume opening quote
+        self.position += 1;
+        let mut string = String::new();
+        while self.position < self.input.len() && 
+              self.input[self.position] != '"' {
+            let c = self.input[self.position];
+            self.position += 1;
+            if c != '\\' {
+                string.push(c);
+                continue;
+            }
+            match self.input.get(self.position) {
+                Some('"') => string.push('"'),
+                Some('\\') => string.push('\\'),
+                Some('n') => string.push('\n'),
+                Some('t') => string.push('\t'),
+                Some('r') => string.push('\r'),
+                Some(&escape) => {
+                    let (line, column) = self.line_column(self.position - 1);
+                    let span = Span { start: self.position - 1, end: self.position + 1, line, column };
+                    return Err(LexError::InvalidEscape { escape, span });
+                }
+                // A trailing `\` leaves the string unterminated
+                None => break,
+            }
+            self.position += 1;
+        }
+        if self.position >= self.input.len() {
+            let (line, column) = self.line_column(start);
+            return Err(LexError::UnterminatedString {
+                span: Span { start, end: start + 1, line, column },
+            });
+        }
+        // Consume closing quote
+        self.position += 1;
+        Ok(string)
+    }
+}
@@ -0,0 +1,69 @@
+//! W Compiler
+//!
+//! The library behind the `w` command line: it lexes and parses W source,
+//! type checks it and generates Rust, for editors, build systems and other
+//! tools that work with W programs.
+//!
+//! The items re-exported here are the stable API, and change only as semver
+//! allows:
+//!
+//! - lexing and parsing: [`Lexer`], [`Parser`] and the syntax tree they
+//!   build, [`Expression`], [`Pattern`] and [`Type`]
+//! - type checking: [`TypeInference`] and the [`TypeError`]s it reports
+//! - code generation: [`RustCodeGenerator`] with its [`CodegenOptions`], and
+//!   the [`CodegenBackend`]s that turn a program into files to build
+//! - diagnostics: [`LocatedTypeError`]s and [`Lint`]s, rendered with
+//!   [`diagnostics::render`], and the [`Explanation`] of every error code
+//! - the whole pipeline at once: [`compile`] and a [`Session`] shared
+//!   between compilations
+//!
+//! ```
+//! let program = w_core::Parser::new("Print[1 + 2]".to_string()).parse().unwrap();
+//! let rust = w_core::RustCodeGenerator::new().generate(&program).unwrap();
+//! assert!(rust.contains("fn main()"));
+//! ```
+//!
+//! The modules are public for tools that need more than this, but what is
+//! not re-exported may change in any release. Modules hidden from the
+//! documentation are support code for generated programs and the command
+//! line.
+
+pub mod ast;
+pub mod lexer;
+pub mod parser;
+#[doc(hidden)]
+pub mod stdlib;
+pub mod builtins;
+pub mod imports;
+#[doc(hidden)]
+pub mod codegen_utils;
+pub mod optimizer;
+pub mod rust_codegen;
+pub mod resolve;
+pub mod type_inference;
+mod exhaustiveness;
+pub mod cancellation;
+pub mod driver;
+pub mod session;
+pub mod explain;
+pub mod learn;
+pub mod no_std;
+pub mod profile;
+pub mod const_eval;
+pub mod lints;
+pub mod fix;
+pub mod backend;
+pub mod build_plan;
+pub mod diagnostics;
+
+pub use ast::{Expression, Pattern, Type};
+pub use backend::{Artifacts, BackendOptions, CodegenBackend};
+pub use cancellation::CancellationToken;
+pub use driver::{compile, CompileError, CompilerCallbacks};
+pub use explain::Explanation;
+pub use lexer::{LexError, Lexer, Span, Token};
+pub use lints::{Lint, LintKind};
+pub use parser::Parser;
+pub use rust_codegen::{CodegenOptions, RustCodeGenerator};
+pub use session::Session;
+pub use type_inference::{LocatedTypeError, TypeError, TypeInference};
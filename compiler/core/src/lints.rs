@@ -0,0 +1,563 @@
+//! Lints
+//!
+//! Warnings about code that type checks but is certain to go wrong when it
+//! runs: integer division by zero, `At` past the end of a list, `?` or `Try`
+//! on a literal None or Err, and functions that call themselves on every
+//! path, so never reach a base case. Integer arithmetic on literals is folded,
+//! and the values of `Let[name, value]` bindings of integers and list literals
+//! are followed through the statements after them, so `Let[n, 2 - 2]` then
+//! `total / n` is caught too, while a Cond branch whose condition folds to
+//! False, such as the division in `If[n == 0, 0, total / n]`, is skipped.
+//!
+//! Code that can never run is reported: Cond branches after a condition that
+//! is always true, and what follows a `Panic` or `Exit` call in a statement,
+//! call or operation. (Match arms after a catch-all arm are a type error.)
+//!
+//! Names that are never used are reported too: lambda parameters, `Let`
+//! bindings and functions. A name starting with `_` is meant to be unused.
+//! Functions are only reported in a program with statements to run, or when
+//! `Private`, as a file of definitions exports its public ones.
+//!
+//! A `Let`, lambda parameter, pattern or `For` binding that hides a binding
+//! of the same name in an enclosing scope is reported with both binding
+//! sites, as later edits may mean the outer one. Rebinding a name with a
+//! second top-level `Let` does not hide anything.
+//!
+//! Lints never stop compilation, unless the command line denies warnings.
+
+use crate::ast::{Expression, Operator, Pattern, Visibility};
+use crate::lexer::Span;
+use crate::resolve::{resolve_program, BindingKind};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// What a lint found
+#[derive(Debug, Clone, PartialEq)]
+pub enum LintKind {
+    /// Integer division by a value that is always zero
+    DivisionByZero,
+    /// `At[list, index]` with an index outside a list of known length
+    IndexOutOfBounds { index: i64, length: usize },
+    /// `?` (`operator` "?") or `Try` on a literal None or Err
+    AlwaysReturnsEarly { operator: &'static str, value: &'static str },
+    /// A function that calls itself whichever Cond or Match branch is taken
+    InfiniteRecursion,
+    /// A lambda parameter its body never uses
+    UnusedParameter(String),
+    /// A top-level `Let` binding no later statement uses
+    UnusedBinding(String),
+    /// A function that is never called or passed anywhere
+    UnusedFunction(String),
+    /// Cond branches after a condition that is always true
+    UnreachableBranch,
+    /// Code after a call of `after` (`Panic` or `Exit`), which never returns
+    UnreachableCode { after: &'static str },
+    /// A binding hiding the `outer` binding of the same name; `outer_span`
+    /// is the start of the top-level expression binding it, when that is
+    /// another one
+    Shadowing { name: String, inner: BindingKind, outer: BindingKind, outer_span: Option<Span> },
+}
+
+impl LintKind {
+    /// Code explained by `w --explain`
+    pub fn code(&self) -> &'static str {
+        match self {
+            LintKind::DivisionByZero => "W0001",
+            LintKind::IndexOutOfBounds { .. } => "W0002",
+            LintKind::AlwaysReturnsEarly { .. } => "W0003",
+            LintKind::InfiniteRecursion => "W0004",
+            LintKind::UnusedParameter(_) => "W0005",
+            LintKind::UnusedBinding(_) => "W0006",
+            LintKind::UnusedFunction(_) => "W0007",
+            LintKind::UnreachableBranch => "W0008",
+            LintKind::UnreachableCode { .. } => "W0009",
+            LintKind::Shadowing { .. } => "W0010",
+        }
+    }
+}
+
+/// A warning, located at the top-level expression containing it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lint {
+    pub kind: LintKind,
+    /// Function whose body contains the problem, if any
+    pub function: Option<String>,
+    /// Start of the top-level expression, when the parser's spans were given
+    pub span: Option<Span>,
+}
+
+impl Lint {
+    pub fn code(&self) -> &'static str {
+        self.kind.code()
+    }
+}
+
+impl fmt::Display for Lint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let within = self.function.as_ref().map(|function| format!(" in {}", function)).unwrap_or_default();
+        match &self.kind {
+            LintKind::DivisionByZero => write!(f, "Division by zero{} always panics", within),
+            LintKind::IndexOutOfBounds { index, length } => write!(
+                f,
+                "Index {} is outside a {}-element list{}, so At always returns None",
+                index, length, within
+            ),
+            LintKind::AlwaysReturnsEarly { operator, value } => {
+                write!(f, "{} on a literal {}{} always returns early", operator, value, within)
+            }
+            LintKind::InfiniteRecursion => write!(
+                f,
+                "{} calls itself on every path, so it never returns and overflows the stack",
+                self.function.as_deref().unwrap_or("The function")
+            ),
+            LintKind::UnusedParameter(name) => write!(
+                f,
+                "Lambda parameter {}{} is never used; name it _{} if that is intended",
+                name, within, name
+            ),
+            LintKind::UnusedBinding(name) => {
+                write!(f, "{} is bound by Let but never used; name it _{} if that is intended", name, name)
+            }
+            LintKind::UnusedFunction(name) => write!(f, "Function {} is never called", name),
+            LintKind::UnreachableBranch => write!(
+                f,
+                "Cond branches after a condition that is always true{} never run",
+                within
+            ),
+            LintKind::UnreachableCode { after } => {
+                write!(f, "Code after {}{} never runs, as {} does not return", after, within, after)
+            }
+            LintKind::Shadowing { name, inner, outer, outer_span } => {
+                let site = outer_span.map(|span| format!(" bound at {}", span)).unwrap_or_default();
+                write!(f, "The {} {}{} shadows the {} {}{}", inner, name, within, outer, name, site)
+            }
+        }
+    }
+}
+
+/// A value known before the program runs
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Known {
+    Integer(i64),
+    /// A list literal, by length
+    List(usize),
+}
+
+/// Every lint in `program`, in source order. `spans` are the parser's
+/// `expression_spans`, one per top-level expression (empty if unknown).
+pub fn lint_program(program: &Expression, spans: &[Span]) -> Vec<Lint> {
+    let expressions = match program {
+        Expression::Program(expressions) => expressions.as_slice(),
+        other => std::slice::from_ref(other),
+    };
+    let mut lints = Vec::new();
+    let mut known = BTreeMap::new();
+    for (i, expr) in expressions.iter().enumerate() {
+        let mut context = Context { function: None, span: spans.get(i).copied(), lints: &mut lints };
+        context.walk(expr, &known);
+
+        // A top-level binding is in scope for the statements after it
+        if let Expression::LetPattern { pattern: Pattern::Variable(name), value, .. } = expr {
+            match known_value(value, &known) {
+                Some(value) => known.insert(name.clone(), value),
+                None => known.remove(name),
+            };
+        }
+
+        // Statements after one that never returns never run
+        if let Some(after) = diverging_statement(expr) {
+            if expressions[i + 1..].iter().any(|later| !is_definition(later)) {
+                context.report(LintKind::UnreachableCode { after });
+            }
+        }
+
+        if let Expression::LetPattern { pattern, .. } = expr {
+            let mut names = Vec::new();
+            bound_names(pattern, &mut names);
+            for name in names {
+                if !is_intentionally_unused(&name) && !expressions[i + 1..].iter().any(|later| mentions(later, &name)) {
+                    context.report(LintKind::UnusedBinding(name));
+                }
+            }
+        }
+    }
+
+    for shadowing in resolve_program(expressions).shadowings {
+        let span = spans.get(shadowing.statement).copied();
+        let outer_span = spans.get(shadowing.outer_statement).copied().filter(|_| shadowing.outer_statement != shadowing.statement);
+        lints.push(Lint {
+            kind: LintKind::Shadowing { name: shadowing.name, inner: shadowing.inner, outer: shadowing.outer, outer_span },
+            function: shadowing.function,
+            span,
+        });
+    }
+
+    // A program with statements to run calls its functions itself; a file of
+    // definitions exports the public ones
+    let runs = expressions.iter().any(|expr| !is_definition(expr));
+    for (i, expr) in expressions.iter().enumerate() {
+        if let Expression::FunctionDefinition { name, visibility, .. } = expr {
+            let exported = !runs && *visibility == Visibility::Public;
+            let used = expressions.iter().enumerate().any(|(j, other)| j != i && mentions(other, name));
+            if !exported && !used && !is_intentionally_unused(name) {
+                lints.push(Lint { kind: LintKind::UnusedFunction(name.clone()), function: None, span: spans.get(i).copied() });
+            }
+        }
+    }
+    lints.sort_by_key(|lint| lint.span.map(|span| (span.line, span.column)));
+    lints
+}
+
+struct Context<'a> {
+    function: Option<String>,
+    span: Option<Span>,
+    lints: &'a mut Vec<Lint>,
+}
+
+impl Context<'_> {
+    fn report(&mut self, kind: LintKind) {
+        self.lints.push(Lint { kind, function: self.function.clone(), span: self.span });
+    }
+
+    fn walk(&mut self, expr: &Expression, known: &BTreeMap<String, Known>) {
+        match expr {
+            // Parameters shadow bindings of the same name
+            Expression::FunctionDefinition { name, parameters, body, .. } => {
+                let mut inner = known.clone();
+                for parameter in parameters {
+                    inner.remove(&parameter.name);
+                }
+                let enclosing = self.function.replace(name.clone());
+                if always_recurses(body, name) {
+                    self.report(LintKind::InfiniteRecursion);
+                }
+                self.walk(body, &inner);
+                self.function = enclosing;
+                return;
+            }
+            Expression::Lambda { parameters, body } => {
+                let mut inner = known.clone();
+                for parameter in parameters {
+                    inner.remove(&parameter.name);
+                    if !is_intentionally_unused(&parameter.name) && !mentions(body, &parameter.name) {
+                        self.report(LintKind::UnusedParameter(parameter.name.clone()));
+                    }
+                }
+                self.walk(body, &inner);
+                return;
+            }
+            Expression::For { variable, .. } if known.contains_key(variable) => {
+                let mut inner = known.clone();
+                inner.remove(variable);
+                expr.for_each_child(&mut |child| self.walk(child, &inner));
+                return;
+            }
+            Expression::Match { .. } | Expression::IfLet { .. } | Expression::WhileLet { .. } => {
+                let mut inner = known.clone();
+                forget_bound_names(expr, &mut inner);
+                expr.for_each_child(&mut |child| self.walk(child, &inner));
+                return;
+            }
+            // A binding in a block is in scope for the statements after it
+            Expression::Block(statements) => {
+                let mut inner = known.clone();
+                forget_bound_names(expr, &mut inner);
+                for (i, statement) in statements.iter().enumerate() {
+                    self.walk(statement, &inner);
+                    // The statements after it, and the block's value, never run
+                    if let Some(after) = diverging_statement(statement).filter(|_| i + 1 < statements.len()) {
+                        self.report(LintKind::UnreachableCode { after });
+                    }
+                    if let Expression::LetPattern { pattern, .. } = statement {
+                        let mut names = Vec::new();
+                        bound_names(pattern, &mut names);
+                        for name in names {
+                            if !is_intentionally_unused(&name) && !statements[i + 1..].iter().any(|later| mentions(later, &name)) {
+                                self.report(LintKind::UnusedBinding(name));
+                            }
+                        }
+                    }
+                }
+                return;
+            }
+            // Branches that can never run are not linted
+            Expression::Cond { conditions, default_statements, .. } => {
+                for (i, (condition, statements)) in conditions.iter().enumerate() {
+                    self.walk(condition, known);
+                    match boolean_value(condition, known) {
+                        Some(false) => continue,
+                        Some(true) => {
+                            if i + 1 < conditions.len() || default_statements.is_some() {
+                                self.report(LintKind::UnreachableBranch);
+                            }
+                            return self.walk(statements, known);
+                        }
+                        None => self.walk(statements, known),
+                    }
+                }
+                if let Some(statements) = default_statements {
+                    self.walk(statements, known);
+                }
+                return;
+            }
+            Expression::BinaryOp { operator: Operator::Divide, right, .. } if integer_value(right, known) == Some(0) => {
+                self.report(LintKind::DivisionByZero);
+            }
+            // Operands and arguments are evaluated left to right
+            Expression::BinaryOp { left, .. } => {
+                if let Some(after) = diverging_call(left) {
+                    self.report(LintKind::UnreachableCode { after });
+                }
+            }
+            Expression::FunctionCall { function, arguments } => {
+                if let Some((_, before_last)) = arguments.split_last() {
+                    if let Some(after) = before_last.iter().find_map(diverging_call) {
+                        self.report(LintKind::UnreachableCode { after });
+                    }
+                }
+                let indexed = match (function.as_ref(), arguments.as_slice()) {
+                    (Expression::Identifier(name), [list, index]) if name == "At" => Some((list, index)),
+                    // `xs[i]`, which indexes xs if it is a list
+                    (list, [index]) => Some((list, index)),
+                    _ => None,
+                };
+                if let Some((list, index)) = indexed {
+                    if let (Some(Known::List(length)), Some(index)) = (known_value(list, known), integer_value(index, known)) {
+                        if index < 0 || index as usize >= length {
+                            self.report(LintKind::IndexOutOfBounds { index, length });
+                        }
+                    }
+                }
+            }
+            Expression::Propagate { expr: operand } | Expression::Try { expr: operand } => {
+                let operator = if matches!(expr, Expression::Try { .. }) { "Try" } else { "?" };
+                match operand.as_ref() {
+                    Expression::None => self.report(LintKind::AlwaysReturnsEarly { operator, value: "None" }),
+                    Expression::Err { .. } => self.report(LintKind::AlwaysReturnsEarly { operator, value: "Err" }),
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+        expr.for_each_child(&mut |child| self.walk(child, known));
+    }
+}
+
+/// Whether evaluating `expr` calls `function` whichever way it goes. Cond
+/// conditions and Match values that fold to constants decide their branch;
+/// anything the analysis does not follow (closures, loops, other functions)
+/// counts as a way out, so only certain recursion is reported.
+fn always_recurses(expr: &Expression, function: &str) -> bool {
+    let recurses = |expr: &Expression| always_recurses(expr, function);
+    let no_bindings = BTreeMap::new();
+    match expr {
+        Expression::FunctionCall { function: callee, arguments } => {
+            matches!(callee.as_ref(), Expression::Identifier(name) if name == function)
+                || arguments.iter().any(recurses)
+        }
+        Expression::BinaryOp { left, operator: Operator::And | Operator::Or, .. } => recurses(left),
+        Expression::BinaryOp { left, right, .. } => recurses(left) || recurses(right),
+        Expression::Cond { conditions, default_statements, .. } => {
+            for (condition, statements) in conditions {
+                if recurses(condition) {
+                    return true;
+                }
+                match boolean_value(condition, &no_bindings) {
+                    Some(false) => continue,
+                    Some(true) => return recurses(statements),
+                    None if !recurses(statements) => return false,
+                    None => {}
+                }
+            }
+            default_statements.as_deref().is_some_and(recurses)
+        }
+        Expression::Match { value, arms } => {
+            if recurses(value) {
+                return true;
+            }
+            let value = integer_value(value, &no_bindings);
+            for (pattern, result) in arms {
+                let matches = match pattern {
+                    Pattern::Wildcard | Pattern::Variable(_) => Some(true),
+                    Pattern::Literal(literal) => match (value, integer_value(literal, &no_bindings)) {
+                        (Some(value), Some(literal)) => Some(value == literal),
+                        _ => None,
+                    },
+                    _ => None,
+                };
+                match matches {
+                    Some(false) => continue,
+                    Some(true) => return recurses(result),
+                    None if !recurses(result) => return false,
+                    None => {}
+                }
+            }
+            // No arm matched for certain; a Match must be exhaustive, so one did
+            !arms.is_empty()
+        }
+        Expression::Some { value } | Expression::Ok { value } => recurses(value),
+        Expression::Err { error } => recurses(error),
+        Expression::Not { expr } | Expression::Propagate { expr } | Expression::Try { expr } => recurses(expr),
+        Expression::Convert { value, .. } => recurses(value),
+        Expression::FieldAccess { object, .. } => recurses(object),
+        _ => false,
+    }
+}
+
+/// `Panic` or `Exit`, if `expr` is a call of one of them
+fn diverging_call(expr: &Expression) -> Option<&'static str> {
+    match expr {
+        Expression::FunctionCall { function, .. } => match function.as_ref() {
+            Expression::Identifier(name) if name == "Panic" => Some("Panic"),
+            Expression::Identifier(name) if name == "Exit" => Some("Exit"),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// `Panic` or `Exit`, if the top-level or block statement `expr` always calls one
+fn diverging_statement(expr: &Expression) -> Option<&'static str> {
+    match expr {
+        Expression::LetPattern { value, .. } => diverging_call(value),
+        _ => diverging_call(expr),
+    }
+}
+
+/// Whether `expr` refers to `name` anywhere, including parameter defaults
+fn mentions(expr: &Expression, name: &str) -> bool {
+    match expr {
+        Expression::Identifier(identifier) => identifier == name,
+        Expression::FunctionDefinition { parameters, body, .. } | Expression::Lambda { parameters, body } => {
+            parameters.iter().filter_map(|p| p.default.as_deref()).any(|default| mentions(default, name))
+                || mentions(body, name)
+        }
+        _ => {
+            let mut found = false;
+            expr.for_each_child(&mut |child| found = found || mentions(child, name));
+            found
+        }
+    }
+}
+
+/// Names starting with `_` are meant to be unused
+fn is_intentionally_unused(name: &str) -> bool {
+    name.starts_with('_')
+}
+
+/// Top-level expressions that define something rather than run
+fn is_definition(expr: &Expression) -> bool {
+    matches!(
+        expr,
+        Expression::FunctionDefinition { .. }
+            | Expression::StructDefinition { .. }
+            | Expression::EnumDefinition { .. }
+            | Expression::TraitDefinition { .. }
+            | Expression::Impl { .. }
+            | Expression::Import(_)
+            | Expression::Kernel { .. }
+    )
+}
+
+/// The variables a pattern binds, in order
+fn bound_names(pattern: &Pattern, names: &mut Vec<String>) {
+    match pattern {
+        Pattern::Variable(name) => names.push(name.clone()),
+        Pattern::Binding { name, pattern } => {
+            names.push(name.clone());
+            bound_names(pattern, names);
+        }
+        Pattern::Constructor { patterns, .. } | Pattern::Tuple(patterns) | Pattern::List(patterns) => {
+            patterns.iter().for_each(|pattern| bound_names(pattern, names));
+        }
+        // Every alternative binds the same names
+        Pattern::Or(alternatives) => {
+            if let Some(first) = alternatives.first() {
+                bound_names(first, names);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Remove the names bound by the patterns of a Match, IfLet or WhileLet
+fn forget_bound_names(expr: &Expression, known: &mut BTreeMap<String, Known>) {
+    fn forget(pattern: &Pattern, known: &mut BTreeMap<String, Known>) {
+        match pattern {
+            Pattern::Variable(name) => {
+                known.remove(name);
+            }
+            Pattern::Binding { name, pattern } => {
+                known.remove(name);
+                forget(pattern, known);
+            }
+            Pattern::Constructor { patterns, .. } | Pattern::Tuple(patterns) | Pattern::List(patterns) | Pattern::Or(patterns) => {
+                patterns.iter().for_each(|pattern| forget(pattern, known));
+            }
+            _ => {}
+        }
+    }
+    match expr {
+        Expression::Match { arms, .. } => arms.iter().for_each(|(pattern, _)| forget(pattern, known)),
+        Expression::IfLet { pattern, .. } | Expression::WhileLet { pattern, .. } => forget(pattern, known),
+        Expression::Block(statements) => {
+            for statement in statements {
+                if let Expression::LetPattern { pattern, .. } = statement {
+                    forget(pattern, known);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn known_value(expr: &Expression, known: &BTreeMap<String, Known>) -> Option<Known> {
+    match expr {
+        Expression::List(elements) => Some(Known::List(elements.len())),
+        Expression::Identifier(name) => known.get(name).copied(),
+        _ => integer_value(expr, known).map(Known::Integer),
+    }
+}
+
+/// Fold comparisons of known integers
+fn boolean_value(expr: &Expression, known: &BTreeMap<String, Known>) -> Option<bool> {
+    match expr {
+        Expression::Boolean(b) => Some(*b),
+        Expression::Not { expr } => boolean_value(expr, known).map(|b| !b),
+        Expression::BinaryOp { left, operator, right } => {
+            let (left, right) = (integer_value(left, known)?, integer_value(right, known)?);
+            match operator {
+                Operator::Equals => Some(left == right),
+                Operator::NotEquals => Some(left != right),
+                Operator::LessThan => Some(left < right),
+                Operator::GreaterThan => Some(left > right),
+                Operator::LessEqual => Some(left <= right),
+                Operator::GreaterEqual => Some(left >= right),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Fold integer arithmetic on literals and known bindings
+fn integer_value(expr: &Expression, known: &BTreeMap<String, Known>) -> Option<i64> {
+    match expr {
+        Expression::Number(n, _) => Some(*n),
+        Expression::Identifier(name) => match known.get(name) {
+            Some(Known::Integer(n)) => Some(*n),
+            _ => None,
+        },
+        Expression::BinaryOp { left, operator, right } => {
+            let (left, right) = (integer_value(left, known)?, integer_value(right, known)?);
+            match operator {
+                Operator::Add => left.checked_add(right),
+                Operator::Subtract => left.checked_sub(right),
+                Operator::Multiply => left.checked_mul(right),
+                Operator::Divide => left.checked_div(right),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,136 @@
+//! no_std Checks
+//!
+//! `--no-std` generates a `#![no_std]` library for embedded targets, taking
+//! `String`, `Vec` and the B-tree collections from `alloc`. A library has no
+//! `main`, and there is no console, file system, process or OS randomness,
+//! so programs using any of those are rejected here, before code generation,
+//! with a diagnostic naming what needs the standard library.
+
+use crate::ast::{Expression, Type};
+use crate::builtins;
+use crate::lexer::Span;
+use crate::rust_codegen::type_components;
+use std::fmt;
+
+/// Error code of every `NoStdError`, explained by `w --explain`
+pub const NO_STD_ERROR_CODE: &str = "E0200";
+
+/// Something a `--no-std` program uses that needs the standard library
+#[derive(Debug, Clone, PartialEq)]
+pub struct NoStdError {
+    /// What the program uses, as written in W (e.g. `Print`)
+    pub feature: String,
+    /// Why it needs std, and what to use instead
+    pub reason: &'static str,
+    /// Start of the top-level expression, when the parser's spans were given
+    pub span: Option<Span>,
+}
+
+impl NoStdError {
+    pub fn code(&self) -> &'static str {
+        NO_STD_ERROR_CODE
+    }
+}
+
+impl fmt::Display for NoStdError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} needs the standard library, which --no-std leaves out: {}", self.feature, self.reason)
+    }
+}
+
+/// Check that `program` can be generated as a `#![no_std]` library. `spans`
+/// are the parser's `expression_spans`, one per top-level expression (empty
+/// if unknown).
+pub fn check_no_std(program: &Expression, spans: &[Span]) -> Result<(), NoStdError> {
+    let expressions = match program {
+        Expression::Program(expressions) => expressions.as_slice(),
+        other => std::slice::from_ref(other),
+    };
+    let is_item = |expr: &Expression| matches!(expr,
+        Expression::FunctionDefinition { .. } | Expression::StructDefinition { .. } | Expression::EnumDefinition { .. }
+        | Expression::TraitDefinition { .. } | Expression::Impl { .. } | Expression::Kernel { .. });
+    if let Some(i) = expressions.iter().position(|expr| !is_item(expr)) {
+        return Err(NoStdError {
+            feature: "A top-level statement".to_string(),
+            reason: "a library has no main function to run it; move the code into a function",
+            span: spans.get(i).copied(),
+        });
+    }
+    expressions.iter().enumerate().try_for_each(|(i, expr)| {
+        check_expression(expr).map_err(|error| NoStdError { span: spans.get(i).copied(), ..error })
+    })
+}
+
+fn check_expression(expr: &Expression) -> Result<(), NoStdError> {
+    match expr {
+        Expression::FunctionCall { function, .. } => {
+            if let Expression::Identifier(name) = function.as_ref() {
+                let reason = match name.as_str() {
+                    "Print" | "PrintErr" | "PrintInline" => Some("there is no console; return the value to the caller instead"),
+                    "Lines" => Some("there is no standard input"),
+                    "ReadFileBytes" => Some("there is no file system"),
+                    "Exit" => Some("there is no process to exit; use Panic"),
+                    _ => None,
+                };
+                if let Some(reason) = reason {
+                    return Err(NoStdError { feature: name.clone(), reason, span: None });
+                }
+            }
+        }
+        Expression::QualifiedName { path, name } => {
+            if let Some(builtin) = builtins::lookup(path, name).filter(|builtin| builtins::requires_std(builtin)) {
+                let reason = match builtin.module {
+                    "Math" => "float functions other than Abs, Min and Max come from the platform's libm",
+                    _ => "graphs are stored in HashMaps",
+                };
+                return Err(NoStdError { feature: format!("{}.{}", builtin.module, builtin.name), reason, span: None });
+            }
+        }
+        Expression::LogCall { level, .. } => {
+            return Err(NoStdError { feature: format!("Log{:?}", level), reason: "log messages are written to stderr, and there is no console", span: None });
+        }
+        Expression::Map(_) => return Err(hash_collection("A Map literal")),
+        Expression::Kernel { .. } => {
+            return Err(NoStdError { feature: "A kernel".to_string(), reason: "kernels run on threads, which come from the OS", span: None });
+        }
+        Expression::FunctionDefinition { parameters, .. } | Expression::Lambda { parameters, .. } => {
+            parameters.iter().try_for_each(|parameter| check_type(&parameter.type_))?;
+        }
+        Expression::StructDefinition { fields, .. } => {
+            fields.iter().try_for_each(|field| check_type(&field.type_))?;
+        }
+        Expression::EnumDefinition { variants, .. } => {
+            variants.iter().flat_map(|variant| &variant.fields).try_for_each(check_type)?;
+        }
+        Expression::TraitDefinition { methods, .. } => {
+            for method in methods {
+                method.parameters.iter().try_for_each(|parameter| check_type(&parameter.type_))?;
+                check_type(&method.return_type)?;
+            }
+        }
+        _ => {}
+    }
+
+    let mut result = Ok(());
+    expr.for_each_child(&mut |child| {
+        if result.is_ok() {
+            result = check_expression(child);
+        }
+    });
+    result
+}
+
+fn check_type(ty: &Type) -> Result<(), NoStdError> {
+    match ty {
+        Type::Map(..) | Type::HashSet(_) => Err(hash_collection(&ty.to_string())),
+        _ => type_components(ty).into_iter().try_for_each(check_type),
+    }
+}
+
+fn hash_collection(feature: &str) -> NoStdError {
+    NoStdError {
+        feature: feature.to_string(),
+        reason: "hash collections are seeded from OS randomness; use BTreeMap or BTreeSet",
+        span: None,
+    }
+}
@@ -0,0 +1,424 @@
+//! Optimizer Module
+//!
+//! AST-to-AST rewrites that run after parsing and before code generation.
+//!
+//! Currently implements common-subexpression elimination (CSE): a call to a
+//! pure user-defined function that appears more than once in the same
+//! expression, e.g. `F[x] + F[x]`, is computed once and bound to a `Let`.
+//! Repeated subtrees are found by hash-consing them in an `ExpressionInterner`.
+
+use crate::ast::{Expression, Operator, StringPart, Type};
+use crate::codegen_utils::Gensym;
+use crate::type_inference::TypeInference;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::hash::{Hash, Hasher};
+
+/// Index of an interned expression
+pub type ExprId = usize;
+
+/// Builtins that never perform side effects
+const PURE_BUILTINS: &[&str] = &["Map", "Filter", "Fold", "SortWith", "Range", "Tuple", "CharCount", "Substring", "Chars", "Bytes", "At", "Lookup"];
+
+/// Compute the structural hash of an expression
+pub fn structural_hash(expr: &Expression) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    expr.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Hash-consing table: structurally equal expressions share a single id
+#[derive(Debug, Default)]
+pub struct ExpressionInterner {
+    expressions: Vec<Expression>,
+    buckets: HashMap<u64, Vec<ExprId>>,
+}
+
+impl ExpressionInterner {
+    pub fn new() -> Self {
+        ExpressionInterner {
+            expressions: Vec::new(),
+            buckets: HashMap::new(),
+        }
+    }
+
+    /// Intern an expression, returning the id shared by all equal expressions
+    pub fn intern(&mut self, expr: &Expression) -> ExprId {
+        let bucket = self.buckets.entry(structural_hash(expr)).or_default();
+        if let Some(&id) = bucket.iter().find(|&&id| self.expressions[id] == *expr) {
+            return id;
+        }
+        let id = self.expressions.len();
+        self.expressions.push(expr.clone());
+        bucket.push(id);
+        id
+    }
+
+    /// Look up an interned expression by id
+    pub fn get(&self, id: ExprId) -> &Expression {
+        &self.expressions[id]
+    }
+
+    pub fn len(&self) -> usize {
+        self.expressions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.expressions.is_empty()
+    }
+}
+
+/// AST optimizer
+pub struct Optimizer {
+    /// User-defined functions without side effects
+    pure_functions: BTreeSet<String>,
+    /// Declared/inferred return types of user-defined functions
+    return_types: BTreeMap<String, Type>,
+    gensym: Gensym,
+}
+
+impl Default for Optimizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Optimizer {
+    pub fn new() -> Self {
+        Optimizer {
+            pure_functions: BTreeSet::new(),
+            return_types: BTreeMap::new(),
+            gensym: Gensym::new(),
+        }
+    }
+
+    /// Optimize a parsed program (or single top-level expression)
+    pub fn optimize(&mut self, expr: &Expression) -> Expression {
+        self.gensym.reset();
+        let items: Vec<&Expression> = match expr {
+            Expression::Program(expressions) => expressions.iter().collect(),
+            other => vec![other],
+        };
+        self.analyze_functions(&items);
+
+        match expr {
+            Expression::Program(expressions) => {
+                Expression::Program(expressions.iter().map(|e| self.optimize_item(e)).collect())
+            }
+            other => self.optimize_item(other),
+        }
+    }
+
+    fn optimize_item(&mut self, expr: &Expression) -> Expression {
+        match expr {
+            Expression::FunctionDefinition { name, parameters, body, visibility } => Expression::FunctionDefinition {
+                name: name.clone(),
+                parameters: parameters.clone(),
+                body: Box::new(self.eliminate_common_subexpressions(body)),
+                visibility: *visibility,
+            },
+            // Kernel bodies stay single expressions, without shared bindings
+            Expression::StructDefinition { .. } | Expression::EnumDefinition { .. }
+            | Expression::TraitDefinition { .. } | Expression::Impl { .. } | Expression::Kernel { .. } => expr.clone(),
+            other => self.eliminate_common_subexpressions(other),
+        }
+    }
+
+    /// Record return types and compute which user functions are pure
+    fn analyze_functions(&mut self, items: &[&Expression]) {
+        self.pure_functions.clear();
+        self.return_types.clear();
+
+        // Return types come from type inference; functions it cannot type are
+        // simply never considered for CSE
+        let mut inference = TypeInference::new();
+        let mut bodies = BTreeMap::new();
+        for item in items {
+            if let Ok(Type::Function(_, ret)) = inference.infer_expression(item) {
+                if let Expression::FunctionDefinition { name, .. } = item {
+                    self.return_types.insert(name.clone(), *ret);
+                }
+            }
+            if let Expression::FunctionDefinition { name, body, .. } = item {
+                bodies.insert(name.clone(), body.as_ref());
+            }
+        }
+
+        // Start by assuming every function is pure and drop any that perform
+        // side effects or call impure functions, until nothing changes
+        let mut pure: BTreeSet<String> = bodies.keys().cloned().collect();
+        loop {
+            let impure: Vec<String> = pure.iter()
+                .filter(|name| !is_side_effect_free(bodies[name.as_str()], &pure))
+                .cloned()
+                .collect();
+            if impure.is_empty() {
+                break;
+            }
+            for name in impure {
+                pure.remove(&name);
+            }
+        }
+        self.pure_functions = pure;
+    }
+
+    /// Repeatedly bind the outermost repeated pure call to a fresh `Let`
+    fn eliminate_common_subexpressions(&mut self, expr: &Expression) -> Expression {
+        let mut current = expr.clone();
+        loop {
+            let mut interner = ExpressionInterner::new();
+            let mut counts: HashMap<ExprId, usize> = HashMap::new();
+            let mut order = Vec::new();
+            self.collect_candidates(&current, &mut Vec::new(), &mut interner, &mut counts, &mut order);
+
+            let Some(&id) = order.iter().find(|id| counts[id] >= 2) else {
+                return current;
+            };
+            let common = interner.get(id).clone();
+            let name = self.gensym.fresh("cse");
+            let replacement = Expression::Identifier(name.clone());
+            let body = rewrite_eager(&current, &mut |e| (*e == common).then(|| replacement.clone()));
+            current = Expression::Let {
+                name,
+                value: Box::new(common),
+                body: Box::new(body),
+            };
+        }
+    }
+
+    /// Walk eagerly evaluated positions, counting CSE candidates in pre-order
+    fn collect_candidates(
+        &self,
+        expr: &Expression,
+        bound: &mut Vec<String>,
+        interner: &mut ExpressionInterner,
+        counts: &mut HashMap<ExprId, usize>,
+        order: &mut Vec<ExprId>,
+    ) {
+        // Candidates referring to a Let-bound name cannot be hoisted above it
+        if self.is_candidate(expr) && !bound.iter().any(|name| mentions(expr, name)) {
+            let id = interner.intern(expr);
+            let count = counts.entry(id).or_insert(0);
+            if *count == 0 {
+                order.push(id);
+            }
+            *count += 1;
+        }
+
+        if let Expression::Let { name, value, body } = expr {
+            self.collect_candidates(value, bound, interner, counts, order);
+            bound.push(name.clone());
+            self.collect_candidates(body, bound, interner, counts, order);
+            bound.pop();
+            return;
+        }
+        for child in eager_children(expr) {
+            self.collect_candidates(child, bound, interner, counts, order);
+        }
+    }
+
+    /// A call is worth sharing if it is pure and its result can be reused
+    /// without moving (i.e. it returns a `Copy` type)
+    fn is_candidate(&self, expr: &Expression) -> bool {
+        match expr {
+            Expression::FunctionCall { function, arguments } => match function.as_ref() {
+                Expression::Identifier(name) => {
+                    self.pure_functions.contains(name)
+                        && self.return_types.get(name).is_some_and(is_copy_type)
+                        && arguments.iter().all(|a| is_side_effect_free(a, &self.pure_functions))
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+}
+
+/// Replace every `Identifier(name)` in eagerly evaluated positions with `value`
+pub fn substitute_identifier(expr: &Expression, name: &str, value: &Expression) -> Expression {
+    rewrite_eager(expr, &mut |e| match e {
+        Expression::Identifier(id) if id == name => Some(value.clone()),
+        _ => None,
+    })
+}
+
+/// Children of `expr` that are always evaluated, in evaluation order.
+///
+/// Lambda bodies, Match arms and Cond branches are excluded: they may not run
+/// at all, and they can bind names that shadow the enclosing scope.
+fn eager_children(expr: &Expression) -> Vec<&Expression> {
+    match expr {
+        Expression::Tuple(elements) | Expression::List(elements) => elements.iter().collect(),
+        Expression::Map(entries) => entries.iter().flat_map(|(k, v)| [k, v]).collect(),
+        Expression::FunctionCall { arguments, .. } => arguments.iter().collect(),
+        // The right operand of `&&` / `||` only runs when the left one allows it
+        Expression::BinaryOp { left, operator: Operator::And | Operator::Or, .. } => vec![left],
+        Expression::BinaryOp { left, right, .. } => vec![left, right],
+        Expression::Not { expr } => vec![expr],
+        Expression::LogCall { message, .. } => vec![message],
+        Expression::Some { value } | Expression::Ok { value } => vec![value],
+        Expression::Err { error } => vec![error],
+        Expression::Propagate { expr } | Expression::Try { expr } => vec![expr],
+        Expression::Convert { value, .. } => vec![value],
+        Expression::FieldAccess { object, .. } => vec![object],
+        Expression::StructInstantiation { field_values, .. } => field_values.iter().collect(),
+        Expression::Let { value, body, .. } => vec![value, body],
+        Expression::LetPattern { value, .. } => vec![value],
+        Expression::IfLet { value, .. } => vec![value],
+        // The loop body may run zero times and binds the loop variable
+        Expression::For { iterable, .. } => vec![iterable],
+        // The scrutinee is re-evaluated every iteration, so nothing in a
+        // WhileLet can be shared with the surrounding code
+        Expression::WhileLet { .. } => vec![],
+        Expression::InterpolatedString(parts) => parts.iter()
+            .filter_map(|part| match part {
+                StringPart::Expression { value, .. } => Some(value),
+                StringPart::Literal(_) | StringPart::Placeholder { .. } => None,
+            })
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// Rebuild `expr`, replacing any eagerly evaluated subtree for which `f`
+/// returns a replacement
+fn rewrite_eager(expr: &Expression, f: &mut dyn FnMut(&Expression) -> Option<Expression>) -> Expression {
+    if let Some(replacement) = f(expr) {
+        return replacement;
+    }
+    let mut go = |e: &Expression| rewrite_eager(e, f);
+    match expr {
+        Expression::Tuple(elements) => Expression::Tuple(elements.iter().map(&mut go).collect()),
+        Expression::List(elements) => Expression::List(elements.iter().map(&mut go).collect()),
+        Expression::Map(entries) => Expression::Map(entries.iter().map(|(k, v)| (go(k), go(v))).collect()),
+        Expression::FunctionCall { function, arguments } => Expression::FunctionCall {
+            function: function.clone(),
+            arguments: arguments.iter().map(&mut go).collect(),
+        },
+        Expression::BinaryOp { left, operator, right } => Expression::BinaryOp {
+            left: Box::new(go(left)),
+            operator: operator.clone(),
+            right: Box::new(go(right)),
+        },
+        Expression::LogCall { level, message } => Expression::LogCall {
+            level: level.clone(),
+            message: Box::new(go(message)),
+        },
+        Expression::Some { value } => Expression::Some { value: Box::new(go(value)) },
+        Expression::Ok { value } => Expression::Ok { value: Box::new(go(value)) },
+        Expression::Err { error } => Expression::Err { error: Box::new(go(error)) },
+        Expression::Propagate { expr } => Expression::Propagate { expr: Box::new(go(expr)) },
+        Expression::Try { expr } => Expression::Try { expr: Box::new(go(expr)) },
+        Expression::Not { expr } => Expression::Not { expr: Box::new(go(expr)) },
+        Expression::Convert { value, target } => Expression::Convert {
+            value: Box::new(go(value)),
+            target: target.clone(),
+        },
+        Expression::FieldAccess { object, field } => Expression::FieldAccess {
+            object: Box::new(go(object)),
+            field: field.clone(),
+        },
+        Expression::StructInstantiation { struct_name, field_values } => Expression::StructInstantiation {
+            struct_name: struct_name.clone(),
+            field_values: field_values.iter().map(&mut go).collect(),
+        },
+        Expression::Let { name, value, body } => Expression::Let {
+            name: name.clone(),
+            value: Box::new(go(value)),
+            body: Box::new(go(body)),
+        },
+        Expression::IfLet { pattern, value, then_branch, else_branch } => Expression::IfLet {
+            pattern: pattern.clone(),
+            value: Box::new(go(value)),
+            then_branch: then_branch.clone(),
+            else_branch: else_branch.clone(),
+        },
+        Expression::For { variable, iterable, body } => Expression::For {
+            variable: variable.clone(),
+            iterable: Box::new(go(iterable)),
+            body: body.clone(),
+        },
+        Expression::LetPattern { pattern, value, fallback } => Expression::LetPattern {
+            pattern: pattern.clone(),
+            value: Box::new(go(value)),
+            fallback: fallback.clone(),
+        },
+        Expression::InterpolatedString(parts) => Expression::InterpolatedString(
+            parts.iter()
+                .map(|part| match part {
+                    StringPart::Expression { value, format_spec } => StringPart::Expression {
+                        value: go(value),
+                        format_spec: format_spec.clone(),
+                    },
+                    literal => literal.clone(),
+                })
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+/// Does `expr` contain any side effect, or a call to a function not known to be pure?
+fn is_side_effect_free(expr: &Expression, pure_functions: &BTreeSet<String>) -> bool {
+    match expr {
+        Expression::LogCall { .. } => false,
+        Expression::FunctionCall { function, arguments } => {
+            let callee_pure = match function.as_ref() {
+                Expression::Identifier(name) => {
+                    PURE_BUILTINS.contains(&name.as_str()) || pure_functions.contains(name)
+                }
+                _ => false,
+            };
+            callee_pure && arguments.iter().all(|a| is_side_effect_free(a, pure_functions))
+        }
+        Expression::Lambda { body, .. } => is_side_effect_free(body, pure_functions),
+        Expression::Match { value, arms } => {
+            is_side_effect_free(value, pure_functions)
+                && arms.iter().all(|(_, e)| is_side_effect_free(e, pure_functions))
+        }
+        Expression::Cond { conditions, default_statements, .. } => {
+            conditions.iter().all(|(c, s)| {
+                is_side_effect_free(c, pure_functions) && is_side_effect_free(s, pure_functions)
+            }) && default_statements.as_ref().is_none_or(|d| is_side_effect_free(d, pure_functions))
+        }
+        Expression::For { iterable, body, .. } => {
+            is_side_effect_free(iterable, pure_functions) && is_side_effect_free(body, pure_functions)
+        }
+        Expression::IfLet { .. } | Expression::WhileLet { .. } | Expression::Block(_) => {
+            expr.children().into_iter().all(|e| is_side_effect_free(e, pure_functions))
+        }
+        other => eager_children(other).into_iter().all(|e| is_side_effect_free(e, pure_functions)),
+    }
+}
+
+/// Does `expr` reference `name` anywhere?
+fn mentions(expr: &Expression, name: &str) -> bool {
+    match expr {
+        Expression::Identifier(id) => id == name,
+        Expression::Lambda { body, .. } => mentions(body, name),
+        Expression::Match { value, arms } => {
+            mentions(value, name) || arms.iter().any(|(_, e)| mentions(e, name))
+        }
+        Expression::Cond { conditions, default_statements, .. } => {
+            conditions.iter().any(|(c, s)| mentions(c, name) || mentions(s, name))
+                || default_statements.as_ref().is_some_and(|d| mentions(d, name))
+        }
+        Expression::For { iterable, body, .. } => mentions(iterable, name) || mentions(body, name),
+        Expression::IfLet { .. } | Expression::WhileLet { .. } | Expression::Block(_) => {
+            expr.children().into_iter().any(|e| mentions(e, name))
+        }
+        other => eager_children(other).into_iter().any(|e| mentions(e, name)),
+    }
+}
+
+/// Types whose values can be reused after being bound without moving
+fn is_copy_type(ty: &Type) -> bool {
+    match ty {
+        Type::Int8 | Type::Int16 | Type::Int32 | Type::Int64 | Type::Int128 | Type::Int |
+        Type::UInt8 | Type::UInt16 | Type::UInt32 | Type::UInt64 | Type::UInt128 | Type::UInt |
+        Type::Float32 | Type::Float64 | Type::Bool | Type::Char => true,
+        Type::Tuple(types) => types.iter().all(is_copy_type),
+        Type::Option(inner) => is_copy_type(inner),
+        _ => false,
+    }
+}
@@ -0,0 +1,2419 @@
+//! # W Language Parser
+//! 
+//! This parser is responsible for converting a stream of tokens into an Abstract Syntax Tree (AST).
+//! It implements a recursive descent parsing strategy, supporting various language constructs 
+//! such as function calls, binary operations, log statements, lists, maps, and more.
+//! 
+//! The parser works closely with the lexer to transform source code into a structured representation
+//! that can be further processed by other compiler stages like type checking or code generation.
+
+use crate::ast::{EnumVariant, Expression, Operator, Type, TypeAnnotation, LogLevel, Pattern, StringPart, TraitMethod, Visibility};
+use crate::cancellation::CancellationToken;
+use crate::lexer::{LexError, Lexer, Span, SpannedToken, Token};
+use std::collections::BTreeSet;
+
+/// Code reported when the tokens do not form an expression; see `w --explain E0103`
+pub const PARSE_ERROR_CODE: &str = "E0103";
+
+/// Helper enum to distinguish between function arguments and parameters during parsing
+enum ArgumentOrParameter {
+    Expression(Expression),
+    Parameter(TypeAnnotation),
+}
+
+/// Represents the parser state, holding a lexer and the current token being processed.
+/// 
+/// The parser maintains the context needed to parse a sequence of tokens into an Abstract Syntax Tree.
+pub struct Parser {
+    /// The lexer that provides a stream of tokens
+    lexer: Lexer,
+    /// The current token being examined during parsing
+    current_token: Option<Token>,
+    /// Source location of `current_token` (end of input once exhausted)
+    current_span: Span,
+    /// First tokenizing error; the token stream ends where it occurred
+    lex_error: Option<LexError>,
+    /// Checked before reading each token; once cancelled the token stream ends
+    cancellation: Option<CancellationToken>,
+    /// Whether `cancellation` cut parsing short
+    cancelled: bool,
+    /// Source location of the first token of each top-level expression parsed
+    expression_spans: Vec<Span>,
+    /// Opening bracket of each Cond default written `[default]` rather than `Else[default]`
+    bracket_defaults: Vec<Span>,
+    /// Single-letter names the source defines a struct, enum or trait with,
+    /// which are those types rather than type parameters
+    declared_letters: BTreeSet<String>,
+}
+
+impl Parser {
+    /// Creates a new Parser instance from an input string.
+    /// 
+    /// # Arguments
+    /// * `input` - The source code to be parsed
+    /// 
+    /// # Returns
+    /// A new Parser with the first token loaded
+    pub fn new(input: String) -> Self {
+        let declared_letters = declared_letters(&input);
+        let mut lexer = Lexer::new(input);
+        let (current_token, current_span, lex_error) = next_with_span(&mut lexer);
+        Parser {
+            lexer,
+            current_token,
+            current_span,
+            lex_error,
+            cancellation: None,
+            cancelled: false,
+            expression_spans: Vec::new(),
+            bracket_defaults: Vec::new(),
+            declared_letters,
+        }
+    }
+
+    /// Stop parsing (`parse` returns None) once `token` is cancelled
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// Whether parsing stopped because the cancellation token was cancelled
+    pub fn was_cancelled(&self) -> bool {
+        self.cancelled
+    }
+
+    /// The tokenizing error that cut parsing short, if any
+    pub fn lex_error(&self) -> Option<&LexError> {
+        self.lex_error.as_ref()
+    }
+
+    /// Source location of the token the parser is looking at.
+    ///
+    /// After a failed parse this points at the token where parsing stopped.
+    pub fn current_span(&self) -> Span {
+        self.current_span
+    }
+
+    /// Where each top-level expression of the last `parse` starts, in order
+    pub fn expression_spans(&self) -> &[Span] {
+        &self.expression_spans
+    }
+
+    /// Where each Cond default of the last `parse` written as a lone
+    /// bracketed expression, `[default]`, opens its bracket. `w fix`
+    /// rewrites these to `Else[default]`.
+    pub fn bracket_defaults(&self) -> &[Span] {
+        &self.bracket_defaults
+    }
+
+    /// Parses the entire input and returns the resulting expression.
+    ///
+    /// This method attempts to parse the full input, ensuring all tokens are consumed.
+    ///
+    /// # Returns
+    /// An optional Expression representing the parsed input, or None if parsing fails
+    pub fn parse(&mut self) -> Option<Expression> {
+        let mut expressions = Vec::new();
+        self.expression_spans.clear();
+        self.bracket_defaults.clear();
+
+        // Parse all expressions until we run out of tokens
+        while self.current_token.is_some() {
+            self.expression_spans.push(self.current_span);
+            if let Some(expr) = self.parse_expression() {
+                expressions.push(expr);
+            } else {
+                return None; // Parsing failed
+            }
+        }
+
+        // A lexing error or cancellation ends the token stream early; don't
+        // return a truncated program
+        if expressions.is_empty() || self.lex_error.is_some() || self.cancelled {
+            None
+        } else if expressions.len() == 1 {
+            Some(expressions.into_iter().next().unwrap())
+        } else {
+            Some(Expression::Program(expressions))
+        }
+    }
+
+    /// Attempts to parse a general expression, trying different expression types.
+    /// 
+    /// This method tries parsing expressions in a specific order:
+    /// 1. Function definitions
+    /// 2. Function calls
+    /// 3. Binary operations
+    /// 
+    /// # Returns
+    /// An optional Expression representing the parsed input, or None if parsing fails
+    pub fn parse_expression(&mut self) -> Option<Expression> {
+        let mut expr = self.parse_base_expression()?;
+
+        // Check for pipe operator |> (lowest precedence, left-associative)
+        while matches!(&self.current_token, Some(Token::Pipe)) {
+            self.advance();
+
+            // Parse the RHS — must be a function call or bare identifier
+            let rhs = self.parse_base_expression()?;
+
+            // Unwrap Propagate on RHS for pipe desugaring, then re-wrap
+            let (inner_rhs, propagate) = match rhs {
+                Expression::Propagate { expr: inner } => (*inner, true),
+                other => (other, false),
+            };
+
+            // Desugar: insert LHS as last argument of RHS function call
+            expr = match inner_rhs {
+                Expression::FunctionCall { function, mut arguments } => {
+                    arguments.push(expr);
+                    Expression::FunctionCall { function, arguments }
+                }
+                function @ (Expression::Identifier(_) | Expression::QualifiedName { .. }) => {
+                    Expression::FunctionCall {
+                        function: Box::new(function),
+                        arguments: vec![expr],
+                    }
+                }
+                _ => return None, // Pipe RHS must be a function call or identifier
+            };
+
+            // Re-wrap in Propagate if RHS had ? operator
+            if propagate {
+                expr = Expression::Propagate { expr: Box::new(expr) };
+            }
+        }
+
+        Some(expr)
+    }
+
+    /// Parses a single expression without pipe operator handling.
+    /// Pipe handling is in `parse_expression` which wraps this method.
+    fn parse_base_expression(&mut self) -> Option<Expression> {
+        // `@[kernel]` annotates the function definition that follows
+        if matches!(self.current_token, Some(Token::At)) {
+            self.advance();
+            return self.parse_annotation();
+        }
+
+        // Check if this might be a function (call or definition)
+        // by looking for Identifier followed by [
+        if let Some(Token::Identifier(id)) = &self.current_token {
+            // Special handling for Cond - don't treat it as a regular function call
+            if id == "Cond" {
+                self.advance();
+                return self.parse_cond_expression();
+            }
+
+            // Special handling for Match - pattern matching expression
+            if id == "Match" {
+                self.advance();
+                return self.parse_match_expression();
+            }
+
+            // Special handling for IfLet - single-pattern match
+            if id == "IfLet" {
+                self.advance();
+                return self.parse_if_let_expression();
+            }
+
+            // Special handling for WhileLet - loop while a pattern matches
+            if id == "WhileLet" {
+                self.advance();
+                return self.parse_while_let_expression();
+            }
+
+            // Special handling for For - loop over a collection
+            if id == "For" {
+                self.advance();
+                return self.parse_for_expression();
+            }
+
+            // Table[expr, {x, min, max}] - list comprehension over a range
+            if id == "Table" {
+                self.advance();
+                return self.parse_table_expression();
+            }
+
+            // Destructuring bindings: Let[pattern, value] / LetElse[pattern, value, fallback]
+            if id == "Let" || id == "LetElse" {
+                let with_fallback = id == "LetElse";
+                self.advance();
+                return self.parse_let_pattern(with_fallback);
+            }
+
+            // Block[statement, ..., value] - statements in a scope of their own
+            if id == "Block" {
+                self.advance();
+                return self.parse_block_expression();
+            }
+
+            // Special handling for Function - lambda/closure expression
+            if id == "Function" {
+                self.advance();
+                return self.parse_lambda_expression();
+            }
+
+            // Special handling for Struct - struct definition
+            if id == "Struct" {
+                self.advance();
+                return self.parse_struct_definition();
+            }
+
+            // Special handling for Enum - enum definition
+            if id == "Enum" {
+                self.advance();
+                return self.parse_enum_definition();
+            }
+
+            // Trait[Name, [signatures]] and Impl[Trait, Type, methods...]
+            if id == "Trait" {
+                self.advance();
+                return self.parse_trait_definition();
+            }
+            if id == "Impl" {
+                self.advance();
+                return self.parse_impl();
+            }
+
+            // Import["file.w"] - load another file as a module
+            if id == "Import" {
+                self.advance();
+                return self.parse_import();
+            }
+
+            // Convert[value, Type] may be the left operand of a binary operation
+            if id == "Convert" {
+                self.advance();
+                let convert = self.parse_convert_expression()?;
+                return self.parse_binary_operation_from(convert);
+            }
+
+            // Visibility modifiers wrapping a definition
+            if id == "Public" || id == "Private" {
+                let visibility = if id == "Public" { Visibility::Public } else { Visibility::Private };
+                self.advance();
+                return self.parse_visibility_modifier(visibility);
+            }
+
+            // Peek ahead to check if next token is LeftBracket
+            // We need to check this to avoid consuming tokens unnecessarily
+            let is_function_syntax = self.lexer.peek_token()
+                .map(|t| matches!(t, Token::LeftBracket))
+                .unwrap_or(false);
+
+            if is_function_syntax {
+                // Try to parse as function call or definition
+                if let Some(func_or_call) = self.parse_function_or_call() {
+                    if matches!(func_or_call, Expression::FunctionDefinition { .. }) {
+                        return Some(func_or_call);
+                    }
+                    // A call may be the left operand of a binary operation
+                    return self.parse_binary_operation_from(func_or_call);
+                }
+            }
+        }
+
+        // Try binary operations
+        let expr = self.parse_binary_operation()?;
+
+        // Check for arrow lambda shorthand: x -> body
+        // Desugars: x -> body  →  Function[{x}, body]
+        if let Expression::Identifier(param_name) = &expr {
+            if matches!(&self.current_token, Some(Token::Arrow)) {
+                self.advance(); // consume ->
+                let body = Box::new(self.parse_base_expression()?);
+                return Some(Expression::Lambda {
+                    parameters: vec![TypeAnnotation {
+                        name: param_name.clone(),
+                        type_: Type::Unknown, // Inferred from use
+                        default: None,
+                    }],
+                    body,
+                });
+            }
+        }
+
+        Some(expr)
+    }
+
+    /// Parse either a function definition or function call
+    fn parse_function_or_call(&mut self) -> Option<Expression> {
+        // Get the identifier
+        let name = match &self.current_token {
+            Some(Token::Identifier(id)) => id.clone(),
+            _ => return None,
+        };
+        self.advance();
+
+        // Expect left bracket
+        match self.current_token {
+            Some(Token::LeftBracket) => self.advance(),
+            _ => return None,
+        }
+
+        // Parse the contents of the brackets (could be parameters or arguments)
+        // We'll determine whether this is a function definition or call
+        // by checking for := after the closing bracket
+        let mut items = Vec::new();
+        // A string followed by more arguments may be a Print template
+        let template = match &self.current_token {
+            Some(Token::String(text))
+                if matches!(name.as_str(), "Print" | "PrintErr" | "PrintInline")
+                    && self.lexer.peek_token() == Some(Token::Comma) =>
+            {
+                Some(text.clone())
+            }
+            _ => None,
+        };
+        loop {
+            match &self.current_token {
+                Some(Token::RightBracket) => {
+                    self.advance();
+                    break;
+                }
+                Some(Token::Comma) => {
+                    self.advance();
+                }
+                _ => {
+                    if let Some(item) = self.parse_argument_or_parameter() {
+                        items.push(item);
+                    } else {
+                        return None;
+                    }
+                }
+            }
+        }
+
+        // Now check if next token is :=
+        match &self.current_token {
+            Some(Token::Define) => {
+                // It's a function definition
+                self.advance();
+
+                // Convert items to parameters
+                let parameters: Vec<TypeAnnotation> = items.into_iter()
+                    .filter_map(|item| {
+                        if let ArgumentOrParameter::Parameter(p) = item {
+                            Some(p)
+                        } else {
+                            None
+                        }
+                    })
+                    .collect();
+
+                // Parse body
+                let body = Box::new(self.parse_expression()?);
+
+                Some(Expression::FunctionDefinition {
+                    name,
+                    parameters,
+                    body,
+                    visibility: Visibility::default(),
+                })
+            }
+            _ => {
+                // It's a function call
+                let mut arguments: Vec<Expression> = items.into_iter()
+                    .filter_map(|item| {
+                        match item {
+                            ArgumentOrParameter::Expression(e) => Some(e),
+                            ArgumentOrParameter::Parameter(_) => None,
+                        }
+                    })
+                    .collect();
+                if let Some(text) = template {
+                    arguments[0] = parse_format_template(&text);
+                }
+
+                // Field[value, field] is the bracket form of `value.field`
+                if name == "Field" {
+                    return match <[Expression; 2]>::try_from(arguments) {
+                        Ok([object, Expression::Identifier(field)]) => Some(Expression::FieldAccess {
+                            object: Box::new(object),
+                            field,
+                        }),
+                        _ => None,
+                    };
+                }
+
+                // If[cond, then, else] is the two-branch form of Cond
+                if name == "If" {
+                    let mut arguments = arguments.into_iter();
+                    return match (arguments.next(), arguments.next(), arguments.next(), arguments.next()) {
+                        (Some(condition), Some(then_branch), Some(else_branch), None) => Some(Expression::Cond {
+                            conditions: vec![(condition, then_branch)],
+                            default_statements: Some(Box::new(else_branch)),
+                            from_if: true,
+                        }),
+                        _ => None,
+                    };
+                }
+
+                // Try[expr] is the bracket form of `expr?`, for Results only
+                if name == "Try" {
+                    return match <[Expression; 1]>::try_from(arguments) {
+                        Ok([expr]) => Some(Expression::Try { expr: Box::new(expr) }),
+                        _ => None,
+                    };
+                }
+
+                // `xs[i]` is also written this way; type checking tells it
+                // from a call by the type `xs` has
+                Some(Expression::FunctionCall {
+                    function: Box::new(Expression::Identifier(name)),
+                    arguments,
+                })
+            }
+        }
+    }
+
+    fn parse_argument_or_parameter(&mut self) -> Option<ArgumentOrParameter> {
+        // Try to parse as parameter (identifier with optional type)
+        if let Some(Token::Identifier(name)) = &self.current_token {
+            // Peek ahead to see if this is a type annotation
+            let next_is_colon = self.lexer.peek_token()
+                .map(|t| matches!(t, Token::Colon))
+                .unwrap_or(false);
+
+            if next_is_colon {
+                // Parse as parameter with type annotation
+                let param_name = name.clone();
+                self.advance(); // consume identifier
+                self.advance(); // consume colon
+
+                if let Some(ty) = self.parse_type() {
+                    // `= value` gives the parameter a default
+                    let default = if matches!(self.current_token, Some(Token::Assign)) {
+                        self.advance();
+                        Some(Box::new(self.parse_expression()?))
+                    } else {
+                        None
+                    };
+                    return Some(ArgumentOrParameter::Parameter(TypeAnnotation {
+                        name: param_name,
+                        type_: ty,
+                        default,
+                    }));
+                }
+            }
+        }
+
+        // Parse as general expression (handles identifiers, function calls, etc.)
+        self.parse_expression().map(ArgumentOrParameter::Expression)
+    }
+
+
+    fn parse_binary_operation(&mut self) -> Option<Expression> {
+        let left = self.parse_primary()?;
+        self.parse_binary_operation_from(left)
+    }
+
+    /// Continues a binary operation whose left operand has already been parsed
+    fn parse_binary_operation_from(&mut self, left: Expression) -> Option<Expression> {
+        let left = self.parse_postfix(left);
+        self.parse_binary_rhs(left, 0)
+    }
+
+    /// Precedence climbing: folds operators binding at least as tightly as
+    /// `min_precedence` into `left`
+    fn parse_binary_rhs(&mut self, mut left: Expression, min_precedence: u8) -> Option<Expression> {
+        while let Some(operator) = self.current_token.as_ref().and_then(binary_operator) {
+            let level = precedence(&operator);
+            if level < min_precedence {
+                break;
+            }
+
+            self.advance();
+            let operand = self.parse_primary()?;
+            let mut right = self.parse_postfix(operand);
+
+            // Let tighter operators (and right-associative ones at the same
+            // level) claim the right operand first
+            while let Some(next) = self.current_token.as_ref().and_then(binary_operator) {
+                let next_level = precedence(&next);
+                if next_level > level {
+                    right = self.parse_binary_rhs(right, level + 1)?;
+                } else if next_level == level && is_right_associative(&next) {
+                    right = self.parse_binary_rhs(right, level)?;
+                } else {
+                    break;
+                }
+            }
+
+            left = Expression::BinaryOp {
+                left: Box::new(left),
+                operator,
+                right: Box::new(right),
+            };
+        }
+
+        Some(left)
+    }
+
+    /// Handle postfix ? operator (highest precedence, binds before binary ops)
+    fn parse_postfix(&mut self, mut expr: Expression) -> Expression {
+        while matches!(&self.current_token, Some(Token::Question)) {
+            self.advance();
+            expr = Expression::Propagate { expr: Box::new(expr) };
+        }
+        expr
+    }
+
+    /// Parses a primary expression, which includes basic types, lists, maps, and log calls.
+    /// 
+    /// This method handles parsing of:
+    /// - Numbers (integer and float)
+    /// - Strings
+    /// - Identifiers
+    /// - Lists
+    /// - Maps
+    /// - Log calls (Debug, Info, Warn, Error)
+    /// 
+    /// # Returns
+    /// - `Some(Expression)` if a valid primary expression is found
+    /// - `None` if no valid primary expression can be parsed
+    fn parse_primary(&mut self) -> Option<Expression> {
+        match &self.current_token {
+            Some(Token::Number(n, radix)) => {
+                let expr = Expression::Number(*n, *radix);
+                self.advance();
+                Some(expr)
+            }
+            Some(Token::Float(f)) => {
+                let expr = Expression::Float(*f);
+                self.advance();
+                Some(expr)
+            }
+            Some(Token::String(s)) => {
+                let expr = parse_string_literal(s);
+                self.advance();
+                Some(expr)
+            }
+            Some(Token::Boolean(b)) => {
+                let expr = Expression::Boolean(*b);
+                self.advance();
+                Some(expr)
+            }
+            Some(Token::Identifier(id)) if id == "Cond" => {
+                self.advance();
+                self.parse_cond_expression()
+            }
+            Some(Token::Identifier(id)) if id == "Match" => {
+                self.advance();
+                self.parse_match_expression()
+            }
+            Some(Token::Identifier(id)) if id == "IfLet" => {
+                self.advance();
+                self.parse_if_let_expression()
+            }
+            Some(Token::Identifier(id)) if id == "WhileLet" => {
+                self.advance();
+                self.parse_while_let_expression()
+            }
+            Some(Token::Identifier(id)) if id == "For" => {
+                self.advance();
+                self.parse_for_expression()
+            }
+            Some(Token::Identifier(id)) if id == "Let" || id == "LetElse" => {
+                let with_fallback = id == "LetElse";
+                self.advance();
+                self.parse_let_pattern(with_fallback)
+            }
+            Some(Token::Identifier(id)) if id == "Block" => {
+                self.advance();
+                self.parse_block_expression()
+            }
+            Some(Token::Identifier(id)) if id == "Function" => {
+                self.advance();
+                self.parse_lambda_expression()
+            }
+            Some(Token::Identifier(id)) if id == "Convert" => {
+                self.advance();
+                self.parse_convert_expression()
+            }
+            // `Unit` is another spelling of the empty tuple `()`
+            Some(Token::Identifier(id)) if id == "Unit" => {
+                self.advance();
+                Some(Expression::Tuple(vec![]))
+            }
+            // Field access, e.g. `point.x`: values are lowercase, modules PascalCase
+            Some(Token::Identifier(id))
+                if id.starts_with(|c: char| c.is_ascii_lowercase()) && matches!(self.lexer.peek_token(), Some(Token::Dot)) =>
+            {
+                self.parse_field_access()
+            }
+            // Qualified name or call, e.g. `Math.Sqrt[x]` or `Geometry::Area`
+            Some(Token::Identifier(_)) if matches!(self.lexer.peek_token(), Some(Token::Dot) | Some(Token::PathSeparator)) => {
+                self.parse_qualified_name()
+            }
+            // Function call used as an operand, e.g. the right side of `x + F[x]`
+            Some(Token::Identifier(_)) if matches!(self.lexer.peek_token(), Some(Token::LeftBracket)) => {
+                self.parse_function_or_call()
+            }
+            Some(Token::Identifier(id)) => {
+                let expr = Expression::Identifier(id.clone());
+                self.advance();
+                Some(expr)
+            }
+            Some(Token::Not) => {
+                self.advance();
+                let expr = Box::new(self.parse_primary()?);
+                Some(Expression::Not { expr })
+            }
+            Some(Token::LeftParen) => self.parse_tuple(),
+            Some(Token::LeftBracket) => self.parse_list(),
+            Some(Token::LeftBrace) => self.parse_map(),
+            Some(Token::LogDebug) => {
+                self.advance();
+                self.parse_log_call(LogLevel::Debug)
+            }
+            Some(Token::LogInfo) => {
+                self.advance();
+                self.parse_log_call(LogLevel::Info)
+            }
+            Some(Token::LogWarn) => {
+                self.advance();
+                self.parse_log_call(LogLevel::Warn)
+            }
+            Some(Token::LogError) => {
+                self.advance();
+                self.parse_log_call(LogLevel::Error)
+            }
+            // Error handling expressions
+            Some(Token::None) => {
+                self.advance();
+                Some(Expression::None)
+            }
+            Some(Token::Some) => {
+                self.advance();
+                self.parse_some_expression()
+            }
+            Some(Token::Ok) => {
+                self.advance();
+                self.parse_ok_expression()
+            }
+            Some(Token::Err) => {
+                self.advance();
+                self.parse_err_expression()
+            }
+            _ => None,
+        }
+    }
+
+    /// Parses a chain of field accesses such as `line.start.x`
+    ///
+    /// # Returns
+    /// - `Some(Expression::FieldAccess)` nested once per `.field`
+    /// - `None` if a `.` is not followed by a field name
+    fn parse_field_access(&mut self) -> Option<Expression> {
+        let mut expr = match &self.current_token {
+            Some(Token::Identifier(id)) => Expression::Identifier(id.clone()),
+            _ => return None,
+        };
+        self.advance();
+
+        while matches!(self.current_token, Some(Token::Dot)) {
+            self.advance();
+            let field = match &self.current_token {
+                Some(Token::Identifier(field)) => field.clone(),
+                _ => return None,
+            };
+            self.advance();
+            expr = Expression::FieldAccess { object: Box::new(expr), field };
+        }
+        Some(expr)
+    }
+
+    /// Parses a qualified name such as `Math.Sqrt` or `Geometry::Area`,
+    /// followed by an optional argument list making it a call.
+    ///
+    /// # Returns
+    /// - `Some(Expression::QualifiedName)` for a bare name
+    /// - `Some(Expression::FunctionCall)` when `[arguments]` follow
+    /// - `None` if a separator is not followed by an identifier
+    fn parse_qualified_name(&mut self) -> Option<Expression> {
+        let mut segments = Vec::new();
+        loop {
+            match &self.current_token {
+                Some(Token::Identifier(id)) => segments.push(id.clone()),
+                _ => return None,
+            }
+            self.advance();
+
+            match &self.current_token {
+                Some(Token::Dot) | Some(Token::PathSeparator) => self.advance(),
+                _ => break,
+            }
+        }
+
+        let name = segments.pop()?;
+        let qualified = Expression::QualifiedName { path: segments, name };
+
+        if !matches!(self.current_token, Some(Token::LeftBracket)) {
+            return Some(qualified);
+        }
+        self.advance();
+
+        let mut arguments = Vec::new();
+        loop {
+            match &self.current_token {
+                Some(Token::RightBracket) => {
+                    self.advance();
+                    break;
+                }
+                Some(Token::Comma) => self.advance(),
+                _ => arguments.push(self.parse_expression()?),
+            }
+        }
+
+        Some(Expression::FunctionCall {
+            function: Box::new(qualified),
+            arguments,
+        })
+    }
+
+    /// Parses a Cond expression with the structure:
+    /// Cond[[condition1 statements1] [condition2 statements2] ... [default_statements]]
+    /// 
+    /// # Returns
+    /// - `Some(Expression::Cond)` if parsing succeeds
+    /// - `None` if parsing fails
+    fn parse_cond_expression(&mut self) -> Option<Expression> {
+        // Expect left bracket for Cond
+        match self.current_token {
+            Some(Token::LeftBracket) => self.advance(),
+            _ => return None,
+        }
+
+        let mut conditions = Vec::new();
+        let mut default_statements = None;
+
+        while let Some(token) = &self.current_token {
+            match token {
+                Token::RightBracket => break,
+                // The default may be written Else[statements]
+                Token::Identifier(id) if id == "Else" => {
+                    self.advance();
+                    self.expect_token(Token::LeftBracket)?;
+                    default_statements = Some(Box::new(self.parse_expression()?));
+                    self.expect_token(Token::RightBracket)?;
+                }
+                Token::LeftBracket => {
+                    let open = self.current_span;
+                    self.advance(); // Consume left bracket of condition pair
+
+                    // Parse first expression
+                    let first_expr = self.parse_expression()?;
+
+                    // Try to parse second expression (if it exists, this is a condition-statement pair)
+                    // If there's a RightBracket next, this is a default statement.
+                    // The two may be separated by a comma: [condition, statements]
+                    if matches!(self.current_token, Some(Token::Comma)) {
+                        self.advance();
+                    }
+                    let is_default = matches!(self.current_token, Some(Token::RightBracket));
+
+                    if is_default {
+                        // This bracket contains only one expression - it's the default
+                        self.advance(); // Consume right bracket
+                        self.bracket_defaults.push(open);
+                        default_statements = Some(Box::new(first_expr));
+                    } else {
+                        // Parse the second expression (statements for this condition)
+                        let statements = self.parse_expression()?;
+
+                        // Consume right bracket of condition pair
+                        match self.current_token {
+                            Some(Token::RightBracket) => self.advance(),
+                            _ => return None,
+                        }
+
+                        conditions.push((first_expr, statements));
+                    }
+                }
+                // Pairs may also be separated by commas
+                Token::Comma if !conditions.is_empty() || default_statements.is_some() => self.advance(),
+                _ => return None,
+            }
+        }
+
+        // Consume right bracket of Cond
+        match self.current_token {
+            Some(Token::RightBracket) => self.advance(),
+            _ => return None,
+        }
+
+        Some(Expression::Cond {
+            conditions,
+            default_statements,
+            from_if: false,
+        })
+    }
+
+    /// Parse `IfLet[pattern, value, then]` with an optional trailing `else` branch
+    fn parse_if_let_expression(&mut self) -> Option<Expression> {
+        match self.current_token {
+            Some(Token::LeftBracket) => self.advance(),
+            _ => return None,
+        }
+
+        let pattern = self.parse_pattern()?;
+
+        match self.current_token {
+            Some(Token::Comma) => self.advance(),
+            _ => return None,
+        }
+
+        let value = Box::new(self.parse_expression()?);
+
+        match self.current_token {
+            Some(Token::Comma) => self.advance(),
+            _ => return None,
+        }
+
+        let then_branch = Box::new(self.parse_expression()?);
+
+        let else_branch = if matches!(self.current_token, Some(Token::Comma)) {
+            self.advance();
+            Some(Box::new(self.parse_expression()?))
+        } else {
+            None
+        };
+
+        match self.current_token {
+            Some(Token::RightBracket) => self.advance(),
+            _ => return None,
+        }
+
+        Some(Expression::IfLet { pattern, value, then_branch, else_branch })
+    }
+
+    /// Parse `WhileLet[pattern, value, body]`
+    fn parse_while_let_expression(&mut self) -> Option<Expression> {
+        match self.current_token {
+            Some(Token::LeftBracket) => self.advance(),
+            _ => return None,
+        }
+
+        let pattern = self.parse_pattern()?;
+
+        match self.current_token {
+            Some(Token::Comma) => self.advance(),
+            _ => return None,
+        }
+
+        let value = Box::new(self.parse_expression()?);
+
+        match self.current_token {
+            Some(Token::Comma) => self.advance(),
+            _ => return None,
+        }
+
+        let body = Box::new(self.parse_expression()?);
+
+        match self.current_token {
+            Some(Token::RightBracket) => self.advance(),
+            _ => return None,
+        }
+
+        Some(Expression::WhileLet { pattern, value, body })
+    }
+
+    /// Parse `For[x, collection, body]`
+    fn parse_for_expression(&mut self) -> Option<Expression> {
+        match self.current_token {
+            Some(Token::LeftBracket) => self.advance(),
+            _ => return None,
+        }
+
+        let variable = match &self.current_token {
+            Some(Token::Identifier(name)) => name.clone(),
+            _ => return None,
+        };
+        self.advance();
+
+        match self.current_token {
+            Some(Token::Comma) => self.advance(),
+            _ => return None,
+        }
+
+        let iterable = Box::new(self.parse_expression()?);
+
+        match self.current_token {
+            Some(Token::Comma) => self.advance(),
+            _ => return None,
+        }
+
+        let body = Box::new(self.parse_expression()?);
+
+        match self.current_token {
+            Some(Token::RightBracket) => self.advance(),
+            _ => return None,
+        }
+
+        Some(Expression::For { variable, iterable, body })
+    }
+
+    /// Parse `Table[expr, {x, min, max}]` or `Table[expr, {x, min, max}, condition]`
+    ///
+    /// Desugars to `Map[Function[{x}, expr], Range[min, max]]`, with the
+    /// range wrapped in `Filter[Function[{x}, condition], ...]` when a
+    /// condition is given
+    fn parse_table_expression(&mut self) -> Option<Expression> {
+        self.expect_token(Token::LeftBracket)?;
+        let element = self.parse_expression()?;
+        self.expect_token(Token::Comma)?;
+
+        self.expect_token(Token::LeftBrace)?;
+        let variable = match &self.current_token {
+            Some(Token::Identifier(name)) => name.clone(),
+            _ => return None,
+        };
+        self.advance();
+        self.expect_token(Token::Comma)?;
+        let min = self.parse_expression()?;
+        self.expect_token(Token::Comma)?;
+        let max = self.parse_expression()?;
+        self.expect_token(Token::RightBrace)?;
+
+        let condition = if matches!(self.current_token, Some(Token::Comma)) {
+            self.advance();
+            Some(self.parse_expression()?)
+        } else {
+            None
+        };
+        self.expect_token(Token::RightBracket)?;
+
+        let lambda = |body: Expression| Expression::Lambda {
+            parameters: vec![TypeAnnotation { name: variable.clone(), type_: Type::Unknown, default: None }],
+            body: Box::new(body),
+        };
+        let call = |name: &str, arguments: Vec<Expression>| Expression::FunctionCall {
+            function: Box::new(Expression::Identifier(name.to_string())),
+            arguments,
+        };
+        let mut values = call("Range", vec![min, max]);
+        if let Some(condition) = condition {
+            values = call("Filter", vec![lambda(condition), values]);
+        }
+        Some(call("Map", vec![lambda(element), values]))
+    }
+
+    /// Parse `Import["path"]`; the path must be a plain string literal
+    fn parse_import(&mut self) -> Option<Expression> {
+        self.expect_token(Token::LeftBracket)?;
+        let path = match &self.current_token {
+            Some(Token::String(path)) => path.clone(),
+            _ => return None,
+        };
+        self.advance();
+        self.expect_token(Token::RightBracket)?;
+        Some(Expression::Import(path))
+    }
+
+    /// Parse `Let[pattern, value]`, or `LetElse[pattern, value, fallback]`
+    /// when `with_fallback` is set
+    fn parse_let_pattern(&mut self, with_fallback: bool) -> Option<Expression> {
+        match self.current_token {
+            Some(Token::LeftBracket) => self.advance(),
+            _ => return None,
+        }
+
+        let pattern = self.parse_pattern()?;
+
+        match self.current_token {
+            Some(Token::Comma) => self.advance(),
+            _ => return None,
+        }
+
+        let value = Box::new(self.parse_expression()?);
+
+        let fallback = if with_fallback {
+            match self.current_token {
+                Some(Token::Comma) => self.advance(),
+                _ => return None,
+            }
+            Some(Box::new(self.parse_expression()?))
+        } else {
+            None
+        };
+
+        match self.current_token {
+            Some(Token::RightBracket) => self.advance(),
+            _ => return None,
+        }
+
+        Some(Expression::LetPattern { pattern, value, fallback })
+    }
+
+    /// Parses a Match expression with the structure:
+    /// Match[value, [pattern1, result1], [pattern2, result2], ...]
+    ///
+    /// # Returns
+    /// - `Some(Expression::Match)` if parsing succeeds
+    /// - `None` if parsing fails
+    fn parse_match_expression(&mut self) -> Option<Expression> {
+        // Expect left bracket for Match
+        match self.current_token {
+            Some(Token::LeftBracket) => self.advance(),
+            _ => return None,
+        }
+
+        // Parse the value to match against
+        let value = Box::new(self.parse_expression()?);
+
+        // Expect comma after value
+        match self.current_token {
+            Some(Token::Comma) => self.advance(),
+            _ => return None,
+        }
+
+        let mut arms = Vec::new();
+
+        // Parse match arms
+        while let Some(token) = &self.current_token {
+            match token {
+                Token::RightBracket => break,
+                Token::LeftBracket => {
+                    self.advance(); // Consume left bracket of arm
+
+                    // Parse the pattern
+                    let pattern = self.parse_pattern()?;
+
+                    // Expect comma between pattern and result
+                    match self.current_token {
+                        Some(Token::Comma) => self.advance(),
+                        _ => return None,
+                    }
+
+                    // Parse the result expression
+                    let result = self.parse_expression()?;
+
+                    // Consume right bracket of arm
+                    match self.current_token {
+                        Some(Token::RightBracket) => self.advance(),
+                        _ => return None,
+                    }
+
+                    arms.push((pattern, result));
+
+                    // Handle optional comma between arms
+                    if matches!(self.current_token, Some(Token::Comma)) {
+                        self.advance();
+                    }
+                }
+                _ => return None,
+            }
+        }
+
+        // Consume right bracket of Match
+        match self.current_token {
+            Some(Token::RightBracket) => self.advance(),
+            _ => return None,
+        }
+
+        Some(Expression::Match { value, arms })
+    }
+
+    /// Parses a Lambda/Closure expression with the structure:
+    /// Function[{param1, param2, ...}, body]
+    /// or Function[{param1: Type1, param2: Type2}, body]
+    ///
+    /// # Returns
+    /// - `Some(Expression::Lambda)` if parsing succeeds
+    /// - `None` if parsing fails
+    fn parse_lambda_expression(&mut self) -> Option<Expression> {
+        // Expect left bracket for Function
+        match self.current_token {
+            Some(Token::LeftBracket) => self.advance(),
+            _ => return None,
+        }
+
+        // Expect left brace for parameter list
+        match self.current_token {
+            Some(Token::LeftBrace) => self.advance(),
+            _ => return None,
+        }
+
+        let mut parameters = Vec::new();
+
+        // Parse parameters
+        while let Some(token) = &self.current_token {
+            match token {
+                Token::RightBrace => break,
+                Token::Identifier(name) => {
+                    let param_name = name.clone();
+                    self.advance();
+
+                    // Check for type annotation
+                    if matches!(self.current_token, Some(Token::Colon)) {
+                        self.advance(); // Consume ':'
+
+                        let param_type = self.parse_type()?;
+                        parameters.push(TypeAnnotation {
+                            name: param_name,
+                            type_: param_type,
+                            default: None,
+                        });
+                    } else {
+                        // No type annotation - inferred from use
+                        parameters.push(TypeAnnotation {
+                            name: param_name,
+                            type_: Type::Unknown,
+                            default: None,
+                        });
+                    }
+
+                    // Handle comma between parameters
+                    if matches!(self.current_token, Some(Token::Comma)) {
+                        self.advance();
+                    }
+                }
+                _ => return None,
+            }
+        }
+
+        // Consume right brace
+        match self.current_token {
+            Some(Token::RightBrace) => self.advance(),
+            _ => return None,
+        }
+
+        // Expect comma after parameter list
+        match self.current_token {
+            Some(Token::Comma) => self.advance(),
+            _ => return None,
+        }
+
+        // Parse body expression
+        let body = Box::new(self.parse_expression()?);
+
+        // Consume right bracket of Function
+        match self.current_token {
+            Some(Token::RightBracket) => self.advance(),
+            _ => return None,
+        }
+
+        Some(Expression::Lambda { parameters, body })
+    }
+
+    /// Parses a Struct definition with the structure:
+    /// Struct[Name, [field1: Type1, field2: Type2, ...]]
+    ///
+    /// # Returns
+    /// - `Some(Expression::StructDefinition)` if parsing succeeds
+    /// - `None` if parsing fails
+    fn parse_struct_definition(&mut self) -> Option<Expression> {
+        // Expect left bracket for Struct
+        match self.current_token {
+            Some(Token::LeftBracket) => self.advance(),
+            _ => return None,
+        }
+
+        // Parse struct name
+        let struct_name = match &self.current_token {
+            Some(Token::Identifier(name)) => name.clone(),
+            _ => return None,
+        };
+        self.advance();
+
+        // Expect comma after name
+        match self.current_token {
+            Some(Token::Comma) => self.advance(),
+            _ => return None,
+        }
+
+        // Expect left bracket for field list
+        match self.current_token {
+            Some(Token::LeftBracket) => self.advance(),
+            _ => return None,
+        }
+
+        let mut fields = Vec::new();
+
+        // Parse fields
+        while let Some(token) = &self.current_token {
+            match token {
+                Token::RightBracket => break,
+                Token::Identifier(field_name) => {
+                    let name = field_name.clone();
+                    self.advance();
+
+                    // Expect colon for type annotation
+                    match self.current_token {
+                        Some(Token::Colon) => self.advance(),
+                        _ => return None,
+                    }
+
+                    // Parse field type
+                    let field_type = self.parse_type()?;
+                    fields.push(TypeAnnotation {
+                        name,
+                        type_: field_type,
+                        default: None,
+                    });
+
+                    // Handle comma between fields
+                    if matches!(self.current_token, Some(Token::Comma)) {
+                        self.advance();
+                    }
+                }
+                _ => return None,
+            }
+        }
+
+        // Consume right bracket of field list
+        match self.current_token {
+            Some(Token::RightBracket) => self.advance(),
+            _ => return None,
+        }
+
+        // Consume right bracket of Struct
+        match self.current_token {
+            Some(Token::RightBracket) => self.advance(),
+            _ => return None,
+        }
+
+        Some(Expression::StructDefinition {
+            name: struct_name,
+            fields,
+            visibility: Visibility::default(),
+        })
+    }
+
+    /// Parses an Enum definition with the structure:
+    /// Enum[Name, [Variant1[Type1, ...], Variant2, ...]]
+    ///
+    /// # Returns
+    /// - `Some(Expression::EnumDefinition)` if parsing succeeds
+    /// - `None` if parsing fails
+    fn parse_enum_definition(&mut self) -> Option<Expression> {
+        self.expect_token(Token::LeftBracket)?;
+
+        let enum_name = match &self.current_token {
+            Some(Token::Identifier(name)) => name.clone(),
+            _ => return None,
+        };
+        self.advance();
+
+        self.expect_token(Token::Comma)?;
+        self.expect_token(Token::LeftBracket)?;
+
+        let mut variants = Vec::new();
+
+        // Parse variants: a name, optionally followed by payload types in brackets
+        while let Some(token) = &self.current_token {
+            match token {
+                Token::RightBracket => break,
+                Token::Identifier(variant_name) => {
+                    let name = variant_name.clone();
+                    self.advance();
+
+                    let mut fields = Vec::new();
+                    if matches!(self.current_token, Some(Token::LeftBracket)) {
+                        self.advance();
+                        while !matches!(self.current_token, Some(Token::RightBracket)) {
+                            fields.push(self.parse_type()?);
+                            if matches!(self.current_token, Some(Token::Comma)) {
+                                self.advance();
+                            } else {
+                                break;
+                            }
+                        }
+                        self.expect_token(Token::RightBracket)?;
+                    }
+                    variants.push(EnumVariant { name, fields });
+
+                    // Handle comma between variants
+                    if matches!(self.current_token, Some(Token::Comma)) {
+                        self.advance();
+                    }
+                }
+                _ => return None,
+            }
+        }
+
+        // Consume right bracket of variant list, then of Enum
+        self.expect_token(Token::RightBracket)?;
+        self.expect_token(Token::RightBracket)?;
+
+        if variants.is_empty() {
+            return None;
+        }
+
+        Some(Expression::EnumDefinition {
+            name: enum_name,
+            variants,
+            visibility: Visibility::default(),
+        })
+    }
+
+    /// Parses a trait definition: `Trait[Name, [Method[self, x: Type] -> Return, ...]]`
+    ///
+    /// # Returns
+    /// - `Some(Expression::TraitDefinition)` if parsing succeeds
+    /// - `None` if a signature does not take `self` first or lacks a return type
+    fn parse_trait_definition(&mut self) -> Option<Expression> {
+        self.expect_token(Token::LeftBracket)?;
+        let name = match &self.current_token {
+            Some(Token::Identifier(name)) => name.clone(),
+            _ => return None,
+        };
+        self.advance();
+        self.expect_token(Token::Comma)?;
+        self.expect_token(Token::LeftBracket)?;
+
+        let mut methods = Vec::new();
+        while !matches!(self.current_token, Some(Token::RightBracket)) {
+            let (method_name, parameters) = self.parse_method_head()?;
+            self.expect_token(Token::Arrow)?;
+            let return_type = self.parse_type()?;
+            methods.push(TraitMethod { name: method_name, parameters, return_type });
+            if matches!(self.current_token, Some(Token::Comma)) {
+                self.advance();
+            }
+        }
+        self.expect_token(Token::RightBracket)?;
+        self.expect_token(Token::RightBracket)?;
+
+        Some(Expression::TraitDefinition { name, methods, visibility: Visibility::default() })
+    }
+
+    /// Parses a trait implementation: `Impl[Trait, Type, Method[self, x] := body, ...]`
+    ///
+    /// # Returns
+    /// - `Some(Expression::Impl)` with one FunctionDefinition per method
+    /// - `None` if a method does not take `self` first or has no body
+    fn parse_impl(&mut self) -> Option<Expression> {
+        self.expect_token(Token::LeftBracket)?;
+        let trait_name = match &self.current_token {
+            Some(Token::Identifier(name)) => name.clone(),
+            _ => return None,
+        };
+        self.advance();
+        self.expect_token(Token::Comma)?;
+        let type_name = match &self.current_token {
+            Some(Token::Identifier(name)) => name.clone(),
+            _ => return None,
+        };
+        self.advance();
+
+        let mut methods = Vec::new();
+        while matches!(self.current_token, Some(Token::Comma)) {
+            self.advance();
+            let (name, parameters) = self.parse_method_head()?;
+            self.expect_token(Token::Define)?;
+            let body = Box::new(self.parse_expression()?);
+            methods.push(Expression::FunctionDefinition { name, parameters, body, visibility: Visibility::default() });
+        }
+        self.expect_token(Token::RightBracket)?;
+
+        Some(Expression::Impl { trait_name, type_name, methods })
+    }
+
+    /// Parses a method name and parameter list starting with `self`, e.g.
+    /// `Scale[self, factor: Float64]`; parameters without a type are `Unknown`
+    fn parse_method_head(&mut self) -> Option<(String, Vec<TypeAnnotation>)> {
+        let name = match &self.current_token {
+            Some(Token::Identifier(name)) => name.clone(),
+            _ => return None,
+        };
+        self.advance();
+        self.expect_token(Token::LeftBracket)?;
+        match &self.current_token {
+            Some(Token::Identifier(receiver)) if receiver == "self" => self.advance(),
+            _ => return None,
+        }
+
+        let mut parameters = Vec::new();
+        while matches!(self.current_token, Some(Token::Comma)) {
+            self.advance();
+            let param_name = match &self.current_token {
+                Some(Token::Identifier(param_name)) => param_name.clone(),
+                _ => return None,
+            };
+            self.advance();
+            let type_ = if matches!(self.current_token, Some(Token::Colon)) {
+                self.advance();
+                self.parse_type()?
+            } else {
+                Type::Unknown
+            };
+            parameters.push(TypeAnnotation { name: param_name, type_, default: None });
+        }
+        self.expect_token(Token::RightBracket)?;
+        Some((name, parameters))
+    }
+
+    /// Parses the body of a visibility modifier: `Public[definition]` or
+    /// `Private[definition]`
+    ///
+    /// # Returns
+    /// - The wrapped function or struct definition with `visibility` set
+    /// - `None` if the brackets do not contain exactly one definition
+    fn parse_visibility_modifier(&mut self, visibility: Visibility) -> Option<Expression> {
+        self.expect_token(Token::LeftBracket)?;
+        let definition = match self.parse_expression()? {
+            Expression::FunctionDefinition { name, parameters, body, .. } => {
+                Expression::FunctionDefinition { name, parameters, body, visibility }
+            }
+            Expression::StructDefinition { name, fields, .. } => {
+                Expression::StructDefinition { name, fields, visibility }
+            }
+            Expression::EnumDefinition { name, variants, .. } => {
+                Expression::EnumDefinition { name, variants, visibility }
+            }
+            Expression::TraitDefinition { name, methods, .. } => {
+                Expression::TraitDefinition { name, methods, visibility }
+            }
+            _ => return None,
+        };
+        self.expect_token(Token::RightBracket)?;
+        Some(definition)
+    }
+
+    /// Parses an annotated definition after its `@`: `@[kernel] definition`
+    ///
+    /// # Returns
+    /// - A `Kernel` wrapping the function definition
+    /// - `None` for an unknown annotation, or one not followed by a function
+    ///   definition
+    fn parse_annotation(&mut self) -> Option<Expression> {
+        self.expect_token(Token::LeftBracket)?;
+        if !matches!(&self.current_token, Some(Token::Identifier(name)) if name == "kernel") {
+            return None;
+        }
+        self.advance();
+        self.expect_token(Token::RightBracket)?;
+        match self.parse_base_expression()? {
+            definition @ Expression::FunctionDefinition { .. } => Some(Expression::Kernel { definition: Box::new(definition) }),
+            _ => None,
+        }
+    }
+
+    /// Parses a pattern for use in Match expressions
+    ///
+    /// # Pattern Types
+    /// - `_` - Wildcard pattern
+    /// - Literals: `42`, `"hello"`, `true`, `false`
+    /// - Variables: `x`, `value`
+    /// - Constructors: `Some[x]`, `Ok[val]`, `None`, `Err[e]`
+    /// - Tuples: `(x, y, z)`
+    /// - Lists: `[x, y, z]`
+    /// - Alternatives: `1 | 2 | 3`
+    /// - Bindings: `whole @ Some[x]`
+    fn parse_pattern(&mut self) -> Option<Pattern> {
+        let first = self.parse_single_pattern()?;
+        if self.current_token != Some(Token::BitOr) {
+            return Some(first);
+        }
+        let mut alternatives = vec![first];
+        while self.current_token == Some(Token::BitOr) {
+            self.advance();
+            alternatives.push(self.parse_single_pattern()?);
+        }
+        Some(Pattern::Or(alternatives))
+    }
+
+    /// Parses one alternative of a pattern (everything but `|`)
+    fn parse_single_pattern(&mut self) -> Option<Pattern> {
+        match &self.current_token {
+            // Wildcard pattern
+            Some(Token::Underscore) => {
+                self.advance();
+                Some(Pattern::Wildcard)
+            }
+            // Rest pattern
+            Some(Token::DotDot) => {
+                self.advance();
+                Some(Pattern::Rest)
+            }
+            // Number literal pattern
+            Some(Token::Number(n, radix)) => {
+                let pattern = Pattern::Literal(Box::new(Expression::Number(*n, *radix)));
+                self.advance();
+                Some(pattern)
+            }
+            // String literal pattern
+            Some(Token::String(s)) => {
+                let pattern = Pattern::Literal(Box::new(Expression::String(s.clone())));
+                self.advance();
+                Some(pattern)
+            }
+            // Boolean literal pattern
+            Some(Token::Boolean(b)) => {
+                let pattern = Pattern::Literal(Box::new(Expression::Boolean(*b)));
+                self.advance();
+                Some(pattern)
+            }
+            // None constructor pattern
+            Some(Token::None) => {
+                self.advance();
+                Some(Pattern::Constructor {
+                    name: "None".to_string(),
+                    patterns: vec![],
+                })
+            }
+            // Some constructor pattern
+            Some(Token::Some) => {
+                self.advance();
+                // Expect '[' for Some[x]
+                match self.current_token {
+                    Some(Token::LeftBracket) => {
+                        self.advance();
+                        let pattern = self.parse_pattern()?;
+                        // Expect ']'
+                        match self.current_token {
+                            Some(Token::RightBracket) => self.advance(),
+                            _ => return None,
+                        }
+                        Some(Pattern::Constructor {
+                            name: "Some".to_string(),
+                            patterns: vec![pattern],
+                        })
+                    }
+                    _ => None,
+                }
+            }
+            // Ok constructor pattern
+            Some(Token::Ok) => {
+                self.advance();
+                // Expect '[' for Ok[x]
+                match self.current_token {
+                    Some(Token::LeftBracket) => {
+                        self.advance();
+                        let pattern = self.parse_pattern()?;
+                        // Expect ']'
+                        match self.current_token {
+                            Some(Token::RightBracket) => self.advance(),
+                            _ => return None,
+                        }
+                        Some(Pattern::Constructor {
+                            name: "Ok".to_string(),
+                            patterns: vec![pattern],
+                        })
+                    }
+                    _ => None,
+                }
+            }
+            // Err constructor pattern
+            Some(Token::Err) => {
+                self.advance();
+                // Expect '[' for Err[x]
+                match self.current_token {
+                    Some(Token::LeftBracket) => {
+                        self.advance();
+                        let pattern = self.parse_pattern()?;
+                        // Expect ']'
+                        match self.current_token {
+                            Some(Token::RightBracket) => self.advance(),
+                            _ => return None,
+                        }
+                        Some(Pattern::Constructor {
+                            name: "Err".to_string(),
+                            patterns: vec![pattern],
+                        })
+                    }
+                    _ => None,
+                }
+            }
+            // String shape patterns: StartsWith["prefix"], EndsWith["suffix"]
+            Some(Token::Identifier(id)) if id == "StartsWith" || id == "EndsWith" => {
+                let starts = id == "StartsWith";
+                self.advance();
+                self.expect_token(Token::LeftBracket)?;
+                let text = match &self.current_token {
+                    Some(Token::String(text)) => text.clone(),
+                    _ => return None,
+                };
+                self.advance();
+                self.expect_token(Token::RightBracket)?;
+                Some(if starts { Pattern::StartsWith(text) } else { Pattern::EndsWith(text) })
+            }
+            // Identifier - could be variable binding or constructor
+            Some(Token::Identifier(id)) => {
+                let name = id.clone();
+                self.advance();
+
+                // Check if it's a constructor (followed by '[')
+                if matches!(self.current_token, Some(Token::LeftBracket)) {
+                    self.advance(); // Consume '['
+
+                    let mut patterns = Vec::new();
+
+                    // Parse constructor arguments
+                    while !matches!(self.current_token, Some(Token::RightBracket)) {
+                        patterns.push(self.parse_pattern()?);
+
+                        // Handle comma between patterns
+                        if matches!(self.current_token, Some(Token::Comma)) {
+                            self.advance();
+                        } else {
+                            break;
+                        }
+                    }
+
+                    // Consume ']'
+                    match self.current_token {
+                        Some(Token::RightBracket) => self.advance(),
+                        _ => return None,
+                    }
+
+                    Some(Pattern::Constructor { name, patterns })
+                } else if matches!(self.current_token, Some(Token::At)) {
+                    // Bind the whole value and match it against a sub-pattern;
+                    // W has no grouping parentheses, so `n @ 1 | 2` binds n
+                    // whichever alternative matches
+                    self.advance();
+                    let pattern = self.parse_pattern()?;
+                    Some(Pattern::Binding { name, pattern: Box::new(pattern) })
+                } else {
+                    // It's a variable binding
+                    Some(Pattern::Variable(name))
+                }
+            }
+            // Tuple pattern
+            Some(Token::LeftParen) => {
+                self.advance(); // Consume '('
+
+                let mut patterns = Vec::new();
+
+                while !matches!(self.current_token, Some(Token::RightParen)) {
+                    patterns.push(self.parse_pattern()?);
+
+                    // Handle comma between patterns
+                    if matches!(self.current_token, Some(Token::Comma)) {
+                        self.advance();
+                    } else {
+                        break;
+                    }
+                }
+
+                // Consume ')'
+                match self.current_token {
+                    Some(Token::RightParen) => self.advance(),
+                    _ => return None,
+                }
+
+                Some(Pattern::Tuple(patterns))
+            }
+            // List pattern
+            Some(Token::LeftBracket) => {
+                self.advance(); // Consume '['
+
+                let mut patterns = Vec::new();
+
+                while !matches!(self.current_token, Some(Token::RightBracket)) {
+                    patterns.push(self.parse_pattern()?);
+
+                    // Handle comma between patterns
+                    if matches!(self.current_token, Some(Token::Comma)) {
+                        self.advance();
+                    } else {
+                        break;
+                    }
+                }
+
+                // Consume ']'
+                match self.current_token {
+                    Some(Token::RightBracket) => self.advance(),
+                    _ => return None,
+                }
+
+                Some(Pattern::List(patterns))
+            }
+            _ => None,
+        }
+    }
+
+    fn parse_log_call(&mut self, level: LogLevel) -> Option<Expression> {
+        // Expect left bracket
+        match self.current_token {
+            Some(Token::LeftBracket) => self.advance(),
+            _ => return None,
+        }
+
+        // Parse log message
+        let message = match self.parse_expression() {
+            Some(expr) => Box::new(expr),
+            None => return None,
+        };
+
+        // Expect right bracket
+        match self.current_token {
+            Some(Token::RightBracket) => self.advance(),
+            _ => return None,
+        }
+
+        Some(Expression::LogCall {
+            level,
+            message,
+        })
+    }
+
+    /// Parses a statement sequence with the structure:
+    /// Block[statement1, statement2, ..., value]
+    ///
+    /// # Returns
+    /// - `Some(Expression::Block)` if parsing succeeds
+    /// - `None` if parsing fails or the block is empty
+    fn parse_block_expression(&mut self) -> Option<Expression> {
+        self.expect_token(Token::LeftBracket)?;
+
+        let mut statements = vec![self.parse_expression()?];
+        while self.current_token == Some(Token::Comma) {
+            self.advance();
+            statements.push(self.parse_expression()?);
+        }
+
+        self.expect_token(Token::RightBracket)?;
+        Some(Expression::Block(statements))
+    }
+
+    /// Parses a numeric conversion with the structure: Convert[value, Type]
+    ///
+    /// # Returns
+    /// - `Some(Expression::Convert)` if parsing succeeds
+    /// - `None` if parsing fails
+    fn parse_convert_expression(&mut self) -> Option<Expression> {
+        match self.current_token {
+            Some(Token::LeftBracket) => self.advance(),
+            _ => return None,
+        }
+
+        let value = Box::new(self.parse_expression()?);
+
+        match self.current_token {
+            Some(Token::Comma) => self.advance(),
+            _ => return None,
+        }
+
+        let target = self.parse_type()?;
+
+        match self.current_token {
+            Some(Token::RightBracket) => self.advance(),
+            _ => return None,
+        }
+
+        Some(Expression::Convert { value, target })
+    }
+
+    /// Parses a Some expression with the structure: Some[value]
+    ///
+    /// # Returns
+    /// - `Some(Expression::Some)` if parsing succeeds
+    /// - `None` if parsing fails
+    fn parse_some_expression(&mut self) -> Option<Expression> {
+        // Expect left bracket
+        match self.current_token {
+            Some(Token::LeftBracket) => self.advance(),
+            _ => return None,
+        }
+
+        // Parse value
+        let value = match self.parse_expression() {
+            Some(expr) => Box::new(expr),
+            None => return None,
+        };
+
+        // Expect right bracket
+        match self.current_token {
+            Some(Token::RightBracket) => self.advance(),
+            _ => return None,
+        }
+
+        Some(Expression::Some { value })
+    }
+
+    /// Parses an Ok expression with the structure: Ok[value]
+    ///
+    /// # Returns
+    /// - `Some(Expression::Ok)` if parsing succeeds
+    /// - `None` if parsing fails
+    fn parse_ok_expression(&mut self) -> Option<Expression> {
+        // Expect left bracket
+        match self.current_token {
+            Some(Token::LeftBracket) => self.advance(),
+            _ => return None,
+        }
+
+        // Parse value
+        let value = match self.parse_expression() {
+            Some(expr) => Box::new(expr),
+            None => return None,
+        };
+
+        // Expect right bracket
+        match self.current_token {
+            Some(Token::RightBracket) => self.advance(),
+            _ => return None,
+        }
+
+        Some(Expression::Ok { value })
+    }
+
+    /// Parses an Err expression with the structure: Err[error]
+    ///
+    /// # Returns
+    /// - `Some(Expression::Err)` if parsing succeeds
+    /// - `None` if parsing fails
+    fn parse_err_expression(&mut self) -> Option<Expression> {
+        // Expect left bracket
+        match self.current_token {
+            Some(Token::LeftBracket) => self.advance(),
+            _ => return None,
+        }
+
+        // Parse error
+        let error = match self.parse_expression() {
+            Some(expr) => Box::new(expr),
+            None => return None,
+        };
+
+        // Expect right bracket
+        match self.current_token {
+            Some(Token::RightBracket) => self.advance(),
+            _ => return None,
+        }
+
+        Some(Expression::Err { error })
+    }
+
+    fn parse_map(&mut self) -> Option<Expression> {
+        // Consume left brace
+        match self.current_token {
+            Some(Token::LeftBrace) => self.advance(),
+            _ => return None,
+        }
+
+        let mut map_entries = Vec::new();
+        while let Some(token) = &self.current_token {
+            match token {
+                Token::RightBrace => break,
+                _ => {
+                    // Parse key
+                    let key = self.parse_expression()?;
+
+                    // Expect colon
+                    match self.current_token {
+                        Some(Token::Colon) => self.advance(),
+                        _ => return None,
+                    }
+
+                    // Parse value
+                    let value = self.parse_expression()?;
+                    map_entries.push((key, value));
+
+                    // Handle comma between entries
+                    match self.current_token {
+                        Some(Token::Comma) => self.advance(),
+                        Some(Token::RightBrace) => break,
+                        _ => return None,
+                    }
+                }
+            }
+        }
+        self.advance(); // Consume right brace
+
+        Some(Expression::Map(map_entries))
+    }
+
+    fn parse_tuple(&mut self) -> Option<Expression> {
+        // Consume left paren
+        match self.current_token {
+            Some(Token::LeftParen) => self.advance(),
+            _ => return None,
+        }
+
+        let mut elements = Vec::new();
+        let mut trailing_comma = false;
+        while let Some(token) = &self.current_token {
+            match token {
+                Token::RightParen => break,
+                _ => {
+                    let elem = self.parse_expression()?;
+                    elements.push(elem);
+
+                    // Handle comma between elements
+                    match self.current_token {
+                        Some(Token::Comma) => {
+                            self.advance();
+                            trailing_comma = true;
+                        }
+                        Some(Token::RightParen) => {
+                            trailing_comma = false;
+                            break;
+                        }
+                        _ => return None,
+                    }
+                }
+            }
+        }
+        self.advance(); // Consume right paren
+
+        // `(x)` only groups; a single-element tuple is written `(x,)`
+        if elements.len() == 1 && !trailing_comma {
+            return elements.pop();
+        }
+
+        Some(Expression::Tuple(elements))
+    }
+
+    fn parse_list(&mut self) -> Option<Expression> {
+        // Consume left bracket
+        match self.current_token {
+            Some(Token::LeftBracket) => self.advance(),
+            _ => return None,
+        }
+
+        let mut elements = Vec::new();
+        while let Some(token) = &self.current_token {
+            match token {
+                Token::RightBracket => break,
+                _ => {
+                    let elem = self.parse_expression()?;
+                    elements.push(elem);
+
+                    // Handle comma between elements
+                    match self.current_token {
+                        Some(Token::Comma) => self.advance(),
+                        Some(Token::RightBracket) => break,
+                        _ => return None,
+                    }
+                }
+            }
+        }
+        self.advance(); // Consume right bracket
+
+        Some(Expression::List(elements))
+    }
+
+    /// Parses a type annotation from the current token.
+    ///
+    /// Recognizes all Rust primitive types and generic container types:
+    /// - Primitives: Int8-128, UInt8-128, Float32/64, Bool, Char, String
+    /// - Containers: List[T], Array[T, N], Slice[T], Map[K,V], HashSet[T], BTreeMap[K,V], BTreeSet[T]
+    /// - Option[T], Result[T, E] and Function[P1, ..., R]
+    /// - Any other name is a user-defined type
+    ///
+    /// # Returns
+    /// - `Some(Type)` if a valid type is found
+    /// - `None` if the current token is not a recognized type identifier
+    fn parse_type(&mut self) -> Option<Type> {
+        match &self.current_token {
+            Some(Token::Identifier(id)) => {
+                let type_name = id.clone();
+                self.advance();
+
+                // Check if this is a generic type (followed by [)
+                if matches!(self.current_token, Some(Token::LeftBracket)) {
+                    return self.parse_generic_type(&type_name);
+                }
+
+                // Otherwise it's a primitive type or custom type
+                let type_ = match type_name.as_str() {
+                    // Signed integers
+                    "Int8" => Type::Int8,
+                    "Int16" => Type::Int16,
+                    "Int32" => Type::Int32,
+                    "Int64" => Type::Int64,
+                    "Int128" => Type::Int128,
+                    "Int" => Type::Int,
+
+                    // Unsigned integers
+                    "UInt8" => Type::UInt8,
+                    "UInt16" => Type::UInt16,
+                    "UInt32" => Type::UInt32,
+                    "UInt64" => Type::UInt64,
+                    "UInt128" => Type::UInt128,
+                    "UInt" => Type::UInt,
+
+                    // Floating point
+                    "Float32" => Type::Float32,
+                    "Float64" => Type::Float64,
+
+                    // Other primitives
+                    "Bool" => Type::Bool,
+                    "Char" => Type::Char,
+                    "String" => Type::String,
+                    "Bytes" => Type::Bytes,
+                    "Never" => Type::Never,
+                    "Unit" => Type::Tuple(vec![]),
+
+                    // Backward compatible (lowercase)
+                    "int" => Type::Int32,
+                    "float" => Type::Float64,
+                    "string" => Type::String,
+                    "bool" => Type::Bool,
+                    "char" => Type::Char,
+
+                    // Single capital letters are type parameters, unless the
+                    // program defines a type with that name
+                    _ if is_type_variable(&type_name) && !self.declared_letters.contains(&type_name) => {
+                        Type::TypeVar(type_name)
+                    }
+
+                    // Unknown types are treated as custom types
+                    _ => Type::Custom(type_name),
+                };
+                Some(type_)
+            }
+            _ => None,
+        }
+    }
+
+    /// Parse generic type syntax like List[Int32], Array[Int32, 10], Map[String, Int32], Tuple[Int32, String, Bool],
+    /// Option[Point] or Function[Int32, Bool]
+    fn parse_generic_type(&mut self, type_name: &str) -> Option<Type> {
+        // Consume the left bracket
+        self.advance();
+
+        match type_name {
+            "Tuple" => {
+                // Tuple[T1, T2, T3, ...]
+                let mut types = Vec::new();
+                loop {
+                    match &self.current_token {
+                        Some(Token::RightBracket) => break,
+                        Some(Token::Comma) => {
+                            self.advance();
+                        }
+                        _ => {
+                            types.push(self.parse_type()?);
+                        }
+                    }
+                }
+                self.expect_token(Token::RightBracket)?;
+                Some(Type::Tuple(types))
+            }
+            // Stacks are plain lists, pushed and popped at the end
+            "List" | "Stack" => {
+                let inner = Box::new(self.parse_type()?);
+                self.expect_token(Token::RightBracket)?;
+                Some(Type::List(inner))
+            }
+            "Queue" => {
+                let inner = Box::new(self.parse_type()?);
+                self.expect_token(Token::RightBracket)?;
+                Some(Type::Queue(inner))
+            }
+            "Heap" => {
+                let inner = Box::new(self.parse_type()?);
+                self.expect_token(Token::RightBracket)?;
+                Some(Type::Heap(inner))
+            }
+            "Stream" => {
+                let inner = Box::new(self.parse_type()?);
+                self.expect_token(Token::RightBracket)?;
+                Some(Type::Stream(inner))
+            }
+            "Array" => {
+                // Array[T, N] where T is a type and N is a number
+                let inner = Box::new(self.parse_type()?);
+                self.expect_token(Token::Comma)?;
+
+                // Parse the size as a number
+                let size = match &self.current_token {
+                    Some(Token::Number(n, _)) => {
+                        let size = *n as usize;
+                        self.advance();
+                        size
+                    }
+                    _ => return None,
+                };
+
+                self.expect_token(Token::RightBracket)?;
+                Some(Type::Array(inner, size))
+            }
+            "Slice" => {
+                let inner = Box::new(self.parse_type()?);
+                self.expect_token(Token::RightBracket)?;
+                Some(Type::Slice(inner))
+            }
+            "HashSet" => {
+                let inner = Box::new(self.parse_type()?);
+                self.expect_token(Token::RightBracket)?;
+                Some(Type::HashSet(inner))
+            }
+            "BTreeSet" => {
+                let inner = Box::new(self.parse_type()?);
+                self.expect_token(Token::RightBracket)?;
+                Some(Type::BTreeSet(inner))
+            }
+            "Map" => {
+                // Map[K, V]
+                let key = Box::new(self.parse_type()?);
+                self.expect_token(Token::Comma)?;
+                let value = Box::new(self.parse_type()?);
+                self.expect_token(Token::RightBracket)?;
+                Some(Type::Map(key, value))
+            }
+            "BTreeMap" => {
+                // BTreeMap[K, V]
+                let key = Box::new(self.parse_type()?);
+                self.expect_token(Token::Comma)?;
+                let value = Box::new(self.parse_type()?);
+                self.expect_token(Token::RightBracket)?;
+                Some(Type::BTreeMap(key, value))
+            }
+            "Option" => {
+                let inner = Box::new(self.parse_type()?);
+                self.expect_token(Token::RightBracket)?;
+                Some(Type::Option(inner))
+            }
+            "Result" => {
+                // Result[T, E]
+                let ok = Box::new(self.parse_type()?);
+                self.expect_token(Token::Comma)?;
+                let err = Box::new(self.parse_type()?);
+                self.expect_token(Token::RightBracket)?;
+                Some(Type::Result(ok, err))
+            }
+            "Function" => {
+                // Function[P1, P2, ..., R]: the parameter types, then the result type
+                let mut types = vec![self.parse_type()?];
+                while matches!(self.current_token, Some(Token::Comma)) {
+                    self.advance();
+                    types.push(self.parse_type()?);
+                }
+                self.expect_token(Token::RightBracket)?;
+                let result = Box::new(types.pop()?);
+                Some(Type::Function(types, result))
+            }
+            _ => None,
+        }
+    }
+
+    /// Helper to expect and consume a specific token
+    fn expect_token(&mut self, expected: Token) -> Option<()> {
+        if self.current_token == Some(expected) {
+            self.advance();
+            Some(())
+        } else {
+            None
+        }
+    }
+
+    /// Advances the parser to the next token in the input stream.
+    /// 
+    /// This method updates the current_token by requesting the next token from the lexer.
+    /// It is typically called after processing the current token to move parsing forward.
+    fn advance(&mut self) {
+        if self.lex_error.is_some() {
+            // Stay at the error rather than lexing past it
+            self.current_token = None;
+            return;
+        }
+        if self.cancellation.as_ref().is_some_and(CancellationToken::is_cancelled) {
+            self.cancelled = true;
+            self.current_token = None;
+            return;
+        }
+        let (token, span, error) = next_with_span(&mut self.lexer);
+        self.current_token = token;
+        self.current_span = span;
+        self.lex_error = error;
+    }
+}
+
+/// Read the next token and its span. At the end of input, or on a lexing
+/// error, there is no token and the span points at where lexing stopped.
+fn next_with_span(lexer: &mut Lexer) -> (Option<Token>, Span, Option<LexError>) {
+    match lexer.next_spanned_token() {
+        Ok(Some(SpannedToken { token, span })) => (Some(token), span, None),
+        Ok(None) => (None, lexer.current_span(), None),
+        Err(error) => (None, error.span(), Some(error)),
+    }
+}
+
+/// Lowers a string literal, turning `{expr}` placeholders into an
+/// `InterpolatedString`.
+///
+/// `{{` and `}}` stand for literal braces. A brace group whose contents do not
+/// parse as a single expression (e.g. `{}` or `{a: 1}`) is kept as text.
+fn parse_string_literal(text: &str) -> Expression {
+    lower_string(text, false)
+}
+
+/// Lowers the template of `Print[template, arguments...]` like any string
+/// literal, except that `{}` and `{:spec}` become placeholders for the
+/// arguments instead of text
+fn parse_format_template(text: &str) -> Expression {
+    lower_string(text, true)
+}
+
+fn lower_string(text: &str, template: bool) -> Expression {
+    let chars: Vec<char> = text.chars().collect();
+    let mut parts = Vec::new();
+    let mut literal = String::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        match chars[i] {
+            '{' if chars.get(i + 1) == Some(&'{') => {
+                literal.push('{');
+                i += 2;
+            }
+            '}' if chars.get(i + 1) == Some(&'}') => {
+                literal.push('}');
+                i += 2;
+            }
+            '{' => {
+                let placeholder = matching_brace(&chars, i)
+                    .and_then(|end| {
+                        let inner: String = chars[i + 1..end].iter().collect();
+                        let part = match inner.strip_prefix(':') {
+                            _ if template && inner.is_empty() => Some(StringPart::Placeholder { format_spec: None }),
+                            Some(spec) if template && is_format_spec(spec) => {
+                                Some(StringPart::Placeholder { format_spec: Some(spec.to_string()) })
+                            }
+                            _ => parse_placeholder(&inner),
+                        };
+                        part.map(|part| (part, end))
+                    });
+                match placeholder {
+                    Some((part, end)) => {
+                        if !literal.is_empty() {
+                            parts.push(StringPart::Literal(std::mem::take(&mut literal)));
+                        }
+                        parts.push(part);
+                        i = end + 1;
+                    }
+                    None => {
+                        literal.push('{');
+                        i += 1;
+                    }
+                }
+            }
+            c => {
+                literal.push(c);
+                i += 1;
+            }
+        }
+    }
+
+    if parts.is_empty() {
+        return Expression::String(literal);
+    }
+    if !literal.is_empty() {
+        parts.push(StringPart::Literal(literal));
+    }
+    Expression::InterpolatedString(parts)
+}
+
+/// Index of the `}` closing the `{` at `open`, allowing nested braces
+fn matching_brace(chars: &[char], open: usize) -> Option<usize> {
+    let mut depth = 0;
+    for (offset, c) in chars[open..].iter().enumerate() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(open + offset);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Type parameters are written as a single capital letter, e.g. `T`
+fn is_type_variable(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!((chars.next(), chars.next()), (Some(c), None) if c.is_ascii_uppercase())
+}
+
+/// Names that `source` defines a struct, enum or trait with, as in
+/// `Struct[Q, ...]`, which look like type parameters. Types can be used
+/// before their definition, so the source is scanned before parsing.
+fn declared_letters(source: &str) -> BTreeSet<String> {
+    let mut lexer = Lexer::new(source.to_string());
+    let mut letters = BTreeSet::new();
+    let mut recent: [Option<Token>; 2] = [None, None];
+    while let (Some(token), _, None) = next_with_span(&mut lexer) {
+        if let (Some(Token::Identifier(keyword)), Some(Token::LeftBracket), Token::Identifier(name)) =
+            (&recent[0], &recent[1], &token)
+        {
+            if matches!(keyword.as_str(), "Struct" | "Enum" | "Trait") && is_type_variable(name) {
+                letters.insert(name.clone());
+            }
+        }
+        recent = [recent[1].take(), Some(token)];
+    }
+    letters
+}
+
+/// The binary operator a token denotes, if any
+fn binary_operator(token: &Token) -> Option<Operator> {
+    match token {
+        Token::Plus => Some(Operator::Add),
+        Token::Minus => Some(Operator::Subtract),
+        Token::Multiply => Some(Operator::Multiply),
+        Token::Divide => Some(Operator::Divide),
+        Token::Power => Some(Operator::Power),
+        Token::Equals => Some(Operator::Equals),
+        Token::NotEquals => Some(Operator::NotEquals),
+        Token::LessThan => Some(Operator::LessThan),
+        Token::GreaterThan => Some(Operator::GreaterThan),
+        Token::LessEqual => Some(Operator::LessEqual),
+        Token::GreaterEqual => Some(Operator::GreaterEqual),
+        Token::And => Some(Operator::And),
+        Token::Or => Some(Operator::Or),
+        Token::BitAnd => Some(Operator::BitAnd),
+        Token::BitOr => Some(Operator::BitOr),
+        Token::BitXor => Some(Operator::BitXor),
+        Token::ShiftLeft => Some(Operator::ShiftLeft),
+        Token::ShiftRight => Some(Operator::ShiftRight),
+        _ => None,
+    }
+}
+
+/// Binding strength of a binary operator; higher binds tighter. Bitwise
+/// operators sit between comparisons and arithmetic, as in Rust.
+fn precedence(operator: &Operator) -> u8 {
+    match operator {
+        Operator::Or => 1,
+        Operator::And => 2,
+        Operator::Equals | Operator::NotEquals => 3,
+        Operator::LessThan | Operator::GreaterThan | Operator::LessEqual | Operator::GreaterEqual => 4,
+        Operator::BitOr => 5,
+        Operator::BitXor => 6,
+        Operator::BitAnd => 7,
+        Operator::ShiftLeft | Operator::ShiftRight => 8,
+        Operator::Add | Operator::Subtract => 9,
+        Operator::Multiply | Operator::Divide => 10,
+        Operator::Power => 11,
+    }
+}
+
+/// `2 ^ 3 ^ 2` is `2 ^ (3 ^ 2)`; every other operator groups to the left
+fn is_right_associative(operator: &Operator) -> bool {
+    matches!(operator, Operator::Power)
+}
+
+/// Parses the inside of a `{...}` placeholder: an expression with an
+/// optional `:spec` suffix such as `:?` or `:.2`
+fn parse_placeholder(inner: &str) -> Option<StringPart> {
+    let (source, format_spec) = match inner.rfind(':') {
+        Some(colon) if is_format_spec(&inner[colon + 1..]) => {
+            (&inner[..colon], Some(inner[colon + 1..].to_string()))
+        }
+        _ => (inner, None),
+    };
+
+    let mut parser = Parser::new(source.to_string());
+    let value = parser.parse_expression()?;
+    if parser.current_token.is_some() || parser.lex_error.is_some() {
+        return None;
+    }
+    Some(StringPart::Expression { value, format_spec })
+}
+
+/// Check whether text looks like a Rust format spec (`?`, `.2`, `>8`, `#x`, ...)
+fn is_format_spec(spec: &str) -> bool {
+    !spec.is_empty()
+        && spec.chars().all(|c| c.is_ascii_digit() || "?.<>^+-#xXobeE".contains(c))
+}
@@ -0,0 +1,162 @@
+//! Profile Validation
+//!
+//! `--profile embedded` restricts W to a subset that runs on a microcontroller
+//! without an allocator or FPU: values are integers, Bools, fixed-size Arrays
+//! and Tuples of those, or structs and enums built from them, and the program
+//! is a `#![no_std]` library of definitions. Unlike type errors, every
+//! violation is reported, so a port to the profile can be planned at once.
+
+use crate::ast::{Expression, Type};
+use crate::builtins;
+use crate::lexer::Span;
+use crate::rust_codegen::{type_components, Profile};
+use crate::type_inference::is_integer;
+use std::fmt;
+
+/// Error code of every `ProfileViolation`, explained by `w --explain`
+pub const PROFILE_ERROR_CODE: &str = "E0201";
+
+/// Something a program uses that its profile does not allow
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProfileViolation {
+    /// What the program uses, as written in W (e.g. `Print`)
+    pub feature: String,
+    /// Why the profile leaves it out
+    pub reason: &'static str,
+    /// Start of the top-level expression, when the parser's spans were given
+    pub span: Option<Span>,
+}
+
+impl ProfileViolation {
+    pub fn code(&self) -> &'static str {
+        PROFILE_ERROR_CODE
+    }
+}
+
+impl fmt::Display for ProfileViolation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} is not allowed by the embedded profile: {}", self.feature, self.reason)
+    }
+}
+
+const HEAP: &str = "it allocates on the heap, and there is no allocator";
+const FLOAT: &str = "only integer arithmetic is allowed";
+const IO: &str = "there is no console, file system or process";
+
+/// Every violation of `profile` in `program`, in source order. `spans` are
+/// the parser's `expression_spans`, one per top-level expression (empty if
+/// unknown).
+pub fn check_profile(program: &Expression, profile: Profile, spans: &[Span]) -> Vec<ProfileViolation> {
+    let mut violations = Vec::new();
+    if profile == Profile::Embedded {
+        let expressions = match program {
+            Expression::Program(expressions) => expressions.as_slice(),
+            other => std::slice::from_ref(other),
+        };
+        for (i, expr) in expressions.iter().enumerate() {
+            let start = violations.len();
+            if !matches!(expr,
+                Expression::FunctionDefinition { .. } | Expression::StructDefinition { .. } | Expression::EnumDefinition { .. }
+                | Expression::TraitDefinition { .. } | Expression::Impl { .. } | Expression::Kernel { .. })
+            {
+                violations.push(ProfileViolation {
+                    feature: "A top-level statement".to_string(),
+                    reason: "a library has no main function to run it; move the code into a function",
+                    span: None,
+                });
+            }
+            check_expression(expr, &mut violations);
+            for violation in &mut violations[start..] {
+                violation.span = spans.get(i).copied();
+            }
+        }
+    }
+    violations
+}
+
+fn check_expression(expr: &Expression, violations: &mut Vec<ProfileViolation>) {
+    let mut violation = |feature: String, reason| violations.push(ProfileViolation { feature, reason, span: None });
+    match expr {
+        // A literal message is panicked with as is, without allocating
+        Expression::FunctionCall { function, arguments }
+            if matches!((function.as_ref(), arguments.as_slice()), (Expression::Identifier(name), [Expression::String(_)]) if name == "Panic") =>
+        {
+            return;
+        }
+        Expression::String(_) | Expression::InterpolatedString(_) => violation("A String literal".to_string(), HEAP),
+        Expression::Float(_) => violation("A Float literal".to_string(), FLOAT),
+        Expression::List(_) => violation("A List literal".to_string(), "it allocates on the heap; take an Array parameter instead"),
+        Expression::Map(_) => violation("A Map literal".to_string(), HEAP),
+        Expression::LogCall { .. } => violation("Logging".to_string(), IO),
+        Expression::Kernel { .. } => violation("A kernel".to_string(), "it runs on threads, and there is no OS to start them"),
+        Expression::Convert { target, .. } if !is_integer(target) => violation(format!("Convert to {}", target), FLOAT),
+        Expression::QualifiedName { path, name } => {
+            if let Some(builtin) = builtins::lookup(path, name) {
+                let reason = if builtin.module == "Math" { FLOAT } else { HEAP };
+                violation(format!("{}.{}", builtin.module, builtin.name), reason);
+            }
+        }
+        Expression::FunctionCall { function, .. } => {
+            if let Expression::Identifier(name) = function.as_ref() {
+                if let Some(reason) = builtin_reason(name) {
+                    violation(name.clone(), reason);
+                }
+            }
+        }
+        Expression::FunctionDefinition { parameters, .. } | Expression::Lambda { parameters, .. } => {
+            for parameter in parameters {
+                check_type(&parameter.type_, violations);
+            }
+        }
+        Expression::StructDefinition { fields, .. } => {
+            for field in fields {
+                check_type(&field.type_, violations);
+            }
+        }
+        Expression::EnumDefinition { variants, .. } => {
+            for field_type in variants.iter().flat_map(|variant| &variant.fields) {
+                check_type(field_type, violations);
+            }
+        }
+        Expression::TraitDefinition { methods, .. } => {
+            for method in methods {
+                for parameter in &method.parameters {
+                    check_type(&parameter.type_, violations);
+                }
+                check_type(&method.return_type, violations);
+            }
+        }
+        _ => {}
+    }
+
+    expr.for_each_child(&mut |child| check_expression(child, violations));
+}
+
+/// Why the embedded profile leaves out the builtin function `name`, if it does
+fn builtin_reason(name: &str) -> Option<&'static str> {
+    match name {
+        "Print" | "PrintErr" | "PrintInline" | "Lines" | "ReadFileBytes" | "Exit" => Some(IO),
+        "Map" | "Filter" | "SortWith" | "Take" | "Range" | "Repeat" | "Iterate" | "Stack" | "Queue" | "Heap" | "Push" | "Pop"
+        | "PopFront" | "HeapPush" | "HeapPop" | "Lookup" | "Chars" | "Bytes" | "Substring" | "ToUpper" | "ToLower"
+        | "CharCount" => Some(HEAP),
+        _ => None,
+    }
+}
+
+fn check_type(ty: &Type, violations: &mut Vec<ProfileViolation>) {
+    match ty {
+        Type::Bool | Type::Array(..) | Type::Tuple(_) | Type::Custom(_) => {
+            type_components(ty).into_iter().for_each(|inner| check_type(inner, violations));
+        }
+        Type::Float32 | Type::Float64 => violations.push(ProfileViolation { feature: ty.to_string(), reason: FLOAT, span: None }),
+        // Untyped lambda parameters, and type variables, take the types of
+        // checked values
+        Type::Unknown | Type::TypeVar(_) => {}
+        ty if is_integer(ty) => {}
+        _ => violations.push(ProfileViolation {
+            feature: ty.to_string(),
+            reason: "only integers, Bools, Arrays, Tuples and structs or enums of them are allowed",
+            span: None,
+        }),
+    }
+}
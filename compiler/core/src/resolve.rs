@@ -0,0 +1,391 @@
+//! Name Resolution
+//!
+//! Resolves every identifier in a program to what defines it, before the
+//! program is typed: a builtin function, a top-level function, struct, enum
+//! variant or trait method, a parameter, or a local bound by `Let`, a pattern
+//! or `For`. Top-level definitions are in scope throughout the program
+//! (typing then checks the statements in order). Other names are in scope
+//! from where they are bound to the end of their scope, hiding any outer
+//! definition of the same name.
+//!
+//! Names with no definition are reported as undefined, and names with
+//! several different top-level definitions, such as a variant of two enums,
+//! as ambiguous. A program with an `Import` is not checked for undefined
+//! names, which the imported file may define.
+//!
+//! Local bindings that hide a binding of the same name in an enclosing scope
+//! are recorded too, for the shadowing lint.
+
+use crate::ast::{Expression, Pattern, TypeAnnotation};
+use crate::type_inference::TypeError;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Functions the type checker knows by name, without a definition in the
+/// program
+pub const BUILTIN_FUNCTIONS: &[&str] = &[
+    "Print", "PrintErr", "PrintInline", "Tuple", "Map", "Filter", "Fold", "SortWith", "Repeat", "Iterate", "Take", "Range",
+    "Lines", "CharCount", "ToUpper", "ToLower", "Discard", "Panic", "Exit", "Substring", "Chars", "Bytes", "ReadFileBytes",
+    "At", "Lookup", "Stack", "Queue", "Heap", "HeapPush", "HeapPop", "Push", "Pop", "PopFront",
+];
+
+/// What a name refers to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Definition {
+    /// One of `BUILTIN_FUNCTIONS`
+    Builtin,
+    /// A top-level function or kernel
+    Function,
+    /// A struct, called as its constructor
+    Struct,
+    /// A variant of the enum `enum_name`
+    Variant { enum_name: String },
+    /// A method declared by the trait `trait_name`
+    TraitMethod { trait_name: String },
+    /// A parameter of a function, lambda or `Impl` method (including `self`)
+    Parameter,
+    /// A name bound by `Let`, a pattern or `For`
+    Local,
+    /// A name defined before the program, by an earlier program checked with
+    /// the same `TypeInference`
+    Outer,
+}
+
+impl fmt::Display for Definition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Definition::Builtin => write!(f, "a builtin function"),
+            Definition::Function => write!(f, "a function"),
+            Definition::Struct => write!(f, "a struct"),
+            Definition::Variant { enum_name } => write!(f, "a variant of {}", enum_name),
+            Definition::TraitMethod { trait_name } => write!(f, "a method of trait {}", trait_name),
+            Definition::Parameter => write!(f, "a parameter"),
+            Definition::Local => write!(f, "a local binding"),
+            Definition::Outer => write!(f, "an earlier definition"),
+        }
+    }
+}
+
+/// What bound a local name
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BindingKind {
+    /// `Let`, `LetElse`, or a `Let` the optimizer introduced
+    Let,
+    /// A parameter of a function or `Impl` method
+    Parameter,
+    /// A parameter of a lambda
+    LambdaParameter,
+    /// A variable in a Match, IfLet or WhileLet pattern
+    Pattern,
+    /// The variable of a `For` loop
+    For,
+}
+
+impl fmt::Display for BindingKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BindingKind::Let => write!(f, "Let binding"),
+            BindingKind::Parameter => write!(f, "parameter"),
+            BindingKind::LambdaParameter => write!(f, "lambda parameter"),
+            BindingKind::Pattern => write!(f, "pattern binding"),
+            BindingKind::For => write!(f, "For variable"),
+        }
+    }
+}
+
+/// A binding hiding another of the same name in an enclosing scope
+#[derive(Debug, Clone, PartialEq)]
+pub struct Shadowing {
+    pub name: String,
+    pub inner: BindingKind,
+    /// Index of the top-level expression with the inner binding
+    pub statement: usize,
+    /// Function whose body has the inner binding, if any
+    pub function: Option<String>,
+    pub outer: BindingKind,
+    /// Index of the top-level expression with the outer binding
+    pub outer_statement: usize,
+}
+
+/// One identifier and the definition it resolved to
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reference {
+    pub name: String,
+    pub definition: Definition,
+    /// Index of the top-level expression the identifier is in
+    pub statement: usize,
+}
+
+/// The result of resolving a program
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Resolution {
+    /// Every identifier that resolved, in the order they appear
+    pub references: Vec<Reference>,
+    /// `UndefinedIdentifier` and `AmbiguousName` errors in the order they
+    /// appear, each with the index of its top-level expression
+    pub errors: Vec<(usize, TypeError)>,
+    /// Let, lambda parameter, pattern and For bindings hiding an outer
+    /// binding, in the order they appear. Names starting with `_` and
+    /// function parameters are left out
+    pub shadowings: Vec<Shadowing>,
+}
+
+impl Resolution {
+    /// The errors found in the top-level expression at `statement`
+    pub fn errors_in(&self, statement: usize) -> impl Iterator<Item = &TypeError> {
+        self.errors.iter().filter(move |(index, _)| *index == statement).map(|(_, error)| error)
+    }
+}
+
+/// Resolve the names in a program's top-level expressions
+pub fn resolve_program(expressions: &[Expression]) -> Resolution {
+    resolve_program_with(expressions, &|_| false)
+}
+
+/// Like `resolve_program`, resolving names the program does not define to
+/// `Definition::Outer` when `is_outer` returns true for them
+pub fn resolve_program_with(expressions: &[Expression], is_outer: &dyn Fn(&str) -> bool) -> Resolution {
+    let mut resolver = Resolver {
+        globals: BTreeMap::new(),
+        scopes: vec![BTreeMap::new()],
+        has_imports: expressions.iter().any(|expr| matches!(expr, Expression::Import(_))),
+        is_outer,
+        statement: 0,
+        function: None,
+        resolution: Resolution::default(),
+    };
+    for expr in expressions {
+        resolver.declare(expr);
+    }
+    for (index, expr) in expressions.iter().enumerate() {
+        resolver.statement = index;
+        resolver.resolve(expr);
+    }
+    resolver.resolution
+}
+
+struct Resolver<'a> {
+    /// Each top-level name with the distinct things it defines
+    globals: BTreeMap<String, Vec<Definition>>,
+    /// Innermost scope last; the first holds top-level `Let` bindings
+    scopes: Vec<BTreeMap<String, Binding>>,
+    has_imports: bool,
+    is_outer: &'a dyn Fn(&str) -> bool,
+    /// Index of the top-level expression being resolved
+    statement: usize,
+    /// Function whose body is being resolved
+    function: Option<String>,
+    resolution: Resolution,
+}
+
+/// A local name in scope
+#[derive(Debug, Clone, Copy)]
+struct Binding {
+    kind: BindingKind,
+    /// Index of the top-level expression binding it
+    statement: usize,
+}
+
+impl Resolver<'_> {
+    /// Record the names a top-level expression defines for the whole program
+    fn declare(&mut self, expr: &Expression) {
+        match expr {
+            Expression::FunctionDefinition { name, .. } => self.define_global(name, Definition::Function),
+            Expression::Kernel { definition } => self.declare(definition),
+            Expression::StructDefinition { name, .. } => self.define_global(name, Definition::Struct),
+            Expression::EnumDefinition { name, variants, .. } => {
+                for variant in variants {
+                    self.define_global(&variant.name, Definition::Variant { enum_name: name.clone() });
+                }
+            }
+            Expression::TraitDefinition { name, methods, .. } => {
+                for method in methods {
+                    self.define_global(&method.name, Definition::TraitMethod { trait_name: name.clone() });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// A second definition of the same kind replaces the first, as a
+    /// function defined again does
+    fn define_global(&mut self, name: &str, definition: Definition) {
+        let definitions = self.globals.entry(name.to_string()).or_default();
+        if !definitions.contains(&definition) {
+            definitions.push(definition);
+        }
+    }
+
+    fn resolve(&mut self, expr: &Expression) {
+        match expr {
+            Expression::Identifier(name) => self.lookup(name),
+            Expression::FunctionDefinition { name, parameters, body, .. } => {
+                let enclosing = self.function.replace(name.clone());
+                self.resolve_function(parameters, body, BindingKind::Parameter, false);
+                self.function = enclosing;
+            }
+            Expression::Lambda { parameters, body } => {
+                self.resolve_function(parameters, body, BindingKind::LambdaParameter, false)
+            }
+            Expression::Impl { methods, .. } => {
+                for method in methods {
+                    match method {
+                        Expression::FunctionDefinition { name, parameters, body, .. } => {
+                            let enclosing = self.function.replace(name.clone());
+                            self.resolve_function(parameters, body, BindingKind::Parameter, true);
+                            self.function = enclosing;
+                        }
+                        other => self.resolve(other),
+                    }
+                }
+            }
+            Expression::Match { value, arms } => {
+                self.resolve(value);
+                for (pattern, result) in arms {
+                    self.scopes.push(BTreeMap::new());
+                    self.bind_pattern(pattern, BindingKind::Pattern);
+                    self.resolve(result);
+                    self.scopes.pop();
+                }
+            }
+            Expression::IfLet { pattern, value, then_branch, else_branch } => {
+                self.resolve(value);
+                self.scopes.push(BTreeMap::new());
+                self.bind_pattern(pattern, BindingKind::Pattern);
+                self.resolve(then_branch);
+                self.scopes.pop();
+                if let Some(else_branch) = else_branch {
+                    self.resolve(else_branch);
+                }
+            }
+            Expression::WhileLet { pattern, value, body } => {
+                self.resolve(value);
+                self.scopes.push(BTreeMap::new());
+                self.bind_pattern(pattern, BindingKind::Pattern);
+                self.resolve(body);
+                self.scopes.pop();
+            }
+            Expression::For { variable, iterable, body } => {
+                self.resolve(iterable);
+                self.scopes.push(BTreeMap::new());
+                self.bind(variable, BindingKind::For);
+                self.resolve(body);
+                self.scopes.pop();
+            }
+            Expression::Let { name, value, body } => {
+                self.resolve(value);
+                self.scopes.push(BTreeMap::new());
+                self.bind(name, BindingKind::Let);
+                self.resolve(body);
+                self.scopes.pop();
+            }
+            Expression::Block(statements) => {
+                self.scopes.push(BTreeMap::new());
+                statements.iter().for_each(|statement| self.resolve(statement));
+                self.scopes.pop();
+            }
+            // The bindings stay in scope for the rest of the enclosing block
+            Expression::LetPattern { pattern, value, fallback } => {
+                self.resolve(value);
+                if let Some(fallback) = fallback {
+                    self.resolve(fallback);
+                }
+                self.bind_pattern(pattern, BindingKind::Let);
+            }
+            _ => expr.for_each_child(&mut |child| self.resolve(child)),
+        }
+    }
+
+    /// Default values are evaluated where the function is called, so they
+    /// cannot use the parameters
+    fn resolve_function(&mut self, parameters: &[TypeAnnotation], body: &Expression, kind: BindingKind, is_method: bool) {
+        for default in parameters.iter().filter_map(|parameter| parameter.default.as_ref()) {
+            self.resolve(default);
+        }
+        self.scopes.push(BTreeMap::new());
+        if is_method {
+            self.bind("self", kind);
+        }
+        for parameter in parameters {
+            self.bind(&parameter.name, kind);
+        }
+        self.resolve(body);
+        self.scopes.pop();
+    }
+
+    /// Bind the variables of `pattern` in the innermost scope. A bare name
+    /// that is an enum variant matches the variant rather than binding
+    fn bind_pattern(&mut self, pattern: &Pattern, kind: BindingKind) {
+        match pattern {
+            Pattern::Variable(name) => {
+                let is_variant = self.globals.get(name).is_some_and(|definitions| {
+                    definitions.iter().any(|definition| matches!(definition, Definition::Variant { .. }))
+                });
+                if !is_variant {
+                    self.bind(name, kind);
+                }
+            }
+            Pattern::Binding { name, pattern } => {
+                self.bind(name, kind);
+                self.bind_pattern(pattern, kind);
+            }
+            Pattern::Constructor { patterns, .. } | Pattern::Tuple(patterns) | Pattern::List(patterns) => {
+                patterns.iter().for_each(|pattern| self.bind_pattern(pattern, kind));
+            }
+            // Every alternative binds the same names
+            Pattern::Or(alternatives) => {
+                if let Some(first) = alternatives.first() {
+                    self.bind_pattern(first, kind);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Bind `name` in the innermost scope, recording the binding it hides
+    /// in an enclosing scope, if any
+    fn bind(&mut self, name: &str, kind: BindingKind) {
+        let Some((scope, enclosing)) = self.scopes.split_last_mut() else {
+            return;
+        };
+        let hidden = enclosing.iter().rev().find_map(|scope| scope.get(name));
+        if let Some(outer) = hidden.filter(|_| kind != BindingKind::Parameter && !name.starts_with('_')) {
+            self.resolution.shadowings.push(Shadowing {
+                name: name.to_string(),
+                inner: kind,
+                statement: self.statement,
+                function: self.function.clone(),
+                outer: outer.kind,
+                outer_statement: outer.statement,
+            });
+        }
+        scope.insert(name.to_string(), Binding { kind, statement: self.statement });
+    }
+
+    /// Resolve `name` in the innermost scope defining it, then among the
+    /// top-level definitions, the builtins and the outer names
+    fn lookup(&mut self, name: &str) {
+        let definition = match self.scopes.iter().rev().find_map(|scope| scope.get(name)) {
+            Some(binding) => match binding.kind {
+                BindingKind::Parameter | BindingKind::LambdaParameter => Definition::Parameter,
+                _ => Definition::Local,
+            },
+            None => match self.globals.get(name).map(Vec::as_slice) {
+                Some([definition]) => definition.clone(),
+                Some(definitions) => {
+                    let definitions = definitions.iter().map(|definition| definition.to_string()).collect();
+                    return self.error(TypeError::AmbiguousName { name: name.to_string(), definitions });
+                }
+                None if BUILTIN_FUNCTIONS.contains(&name) => Definition::Builtin,
+                None if (self.is_outer)(name) => Definition::Outer,
+                None if self.has_imports => return,
+                None => return self.error(TypeError::UndefinedIdentifier(name.to_string())),
+            },
+        };
+        self.resolution.references.push(Reference { name: name.to_string(), definition, statement: self.statement });
+    }
+
+    fn error(&mut self, error: TypeError) {
+        self.resolution.errors.push((self.statement, error));
+    }
+}
@@ -0,0 +1,3419 @@
+//! Rust Code Generation Module
+//!
+//! Translates the W language AST into idiomatic Rust source code
+
+use crate::ast::{EnumVariant, Expression, Operator, LogLevel, Type, TypeAnnotation, Pattern, StringPart, TraitMethod, Visibility};
+use crate::builtins;
+use crate::codegen_utils::{escape_format_braces, rust_string_literal, to_snake_case, wrap_long_lines, Gensym, Provenance};
+use crate::const_eval::symbol;
+use crate::type_inference::{is_integer, is_numeric_literal, number_nodes, NodeId, TypeInference, TypeTable};
+use std::cell::RefCell;
+use std::fmt::Write;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// Shape of the generated `main` function
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CodegenStyle {
+    /// Emit each top-level expression as a bare statement (minimal output)
+    #[default]
+    Expression,
+    /// Bind each top-level result to a named `let` so it can be inspected
+    /// in a debugger
+    Statement,
+}
+
+/// Post-processing applied to the generated source
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Formatter {
+    /// Emit the code exactly as generated
+    #[default]
+    None,
+    /// Pipe the output through `rustfmt` (left unformatted if it is unavailable)
+    Rustfmt,
+}
+
+/// Subset of W a program is restricted to, checked by `profile::check_profile`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Profile {
+    /// The whole language
+    #[default]
+    Standard,
+    /// Integers, Bools, Arrays and Tuples only, generated as a `#![no_std]`
+    /// library that does not need an allocator
+    Embedded,
+}
+
+/// What integer `+ - * / ^ << >>` do when the result does not fit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Arithmetic {
+    /// Rust's operators: panic in debug builds, wrap in release builds
+    #[default]
+    Unchecked,
+    /// Return an Err from the enclosing function, with the `checked_*`
+    /// functions of the `__w_arithmetic` support module and `?`. Type
+    /// checking requires that function to return `Result[T, String]`, and
+    /// rejects checked operations anywhere else.
+    Checked,
+    /// Always wrap around, with the `wrapping_*` functions
+    Wrapping,
+}
+
+/// Lints silenced by the `#![allow(...)]` header when allow-attribute
+/// emission is enabled
+const ALLOWED_LINTS: &[&str] = &["unused_parens", "unused_variables", "dead_code", "unused_braces"];
+
+/// Options controlling code generation
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CodegenOptions {
+    /// Spaces per indentation level
+    pub indent_width: usize,
+    /// Traits derived on every generated struct
+    pub derives: Vec<String>,
+    /// Post-processing of the generated source
+    pub formatter: Formatter,
+    /// Emit `#![allow(...)]` for lints the generated code commonly trips
+    pub emit_allow_attributes: bool,
+    /// Text emitted verbatim at the top of the generated file
+    pub module_header: Option<String>,
+    /// Header identifying the W file each generated file comes from,
+    /// emitted before `module_header`
+    pub provenance: Option<Provenance>,
+    /// How top-level statements in `main` are emitted
+    pub style: CodegenStyle,
+    /// Wrap lines longer than this many characters (`None` leaves them as
+    /// generated)
+    pub max_width: Option<usize>,
+    /// Import the `std::collections` types each file uses in one `use` at
+    /// its top instead of spelling out their paths
+    pub hoist_imports: bool,
+    /// Generate a `#![no_std]` library that takes `String`, `Vec` and the
+    /// B-tree collections from `alloc`, with every public item exported
+    pub no_std: bool,
+    /// Subset of W the program must stay within; `Embedded` also generates
+    /// `no_std` code, without `alloc`
+    pub profile: Profile,
+    /// Overflow behavior of integer arithmetic
+    pub arithmetic: Arithmetic,
+}
+
+impl Default for CodegenOptions {
+    fn default() -> Self {
+        CodegenOptions {
+            indent_width: 4,
+            derives: vec!["Debug".to_string(), "Clone".to_string(), "PartialEq".to_string()],
+            formatter: Formatter::default(),
+            emit_allow_attributes: false,
+            module_header: None,
+            provenance: None,
+            style: CodegenStyle::default(),
+            max_width: None,
+            hoist_imports: false,
+            no_std: false,
+            profile: Profile::default(),
+            arithmetic: Arithmetic::default(),
+        }
+    }
+}
+
+impl CodegenOptions {
+    /// Whether the generated code is a `#![no_std]` library, as asked for
+    /// directly or by the embedded profile
+    pub fn is_no_std(&self) -> bool {
+        self.no_std || self.profile == Profile::Embedded
+    }
+}
+
+/// Builder for `RustCodeGenerator`
+///
+/// ```
+/// use w_core::rust_codegen::{CodegenStyle, RustCodeGenerator};
+///
+/// let codegen = RustCodeGenerator::builder()
+///     .indent_width(2)
+///     .style(CodegenStyle::Statement)
+///     .build();
+/// # let _ = codegen;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RustCodeGeneratorBuilder {
+    options: CodegenOptions,
+    types: Option<TypeTable>,
+}
+
+impl RustCodeGeneratorBuilder {
+    /// Replace all options at once
+    pub fn options(mut self, options: CodegenOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Types from type checking the program (see
+    /// `TypeInference::type_table`), used for function return types, `Print`
+    /// placeholders and String concatenation. Without them the generator
+    /// type checks the program itself.
+    pub fn types(mut self, types: TypeTable) -> Self {
+        self.types = Some(types);
+        self
+    }
+
+    pub fn indent_width(mut self, width: usize) -> Self {
+        self.options.indent_width = width;
+        self
+    }
+
+    pub fn derives<I, S>(mut self, derives: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.options.derives = derives.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn formatter(mut self, formatter: Formatter) -> Self {
+        self.options.formatter = formatter;
+        self
+    }
+
+    pub fn emit_allow_attributes(mut self, emit: bool) -> Self {
+        self.options.emit_allow_attributes = emit;
+        self
+    }
+
+    pub fn module_header(mut self, header: impl Into<String>) -> Self {
+        self.options.module_header = Some(header.into());
+        self
+    }
+
+    pub fn provenance(mut self, provenance: Provenance) -> Self {
+        self.options.provenance = Some(provenance);
+        self
+    }
+
+    pub fn style(mut self, style: CodegenStyle) -> Self {
+        self.options.style = style;
+        self
+    }
+
+    pub fn max_width(mut self, width: usize) -> Self {
+        self.options.max_width = Some(width);
+        self
+    }
+
+    pub fn hoist_imports(mut self, hoist: bool) -> Self {
+        self.options.hoist_imports = hoist;
+        self
+    }
+
+    pub fn no_std(mut self, no_std: bool) -> Self {
+        self.options.no_std = no_std;
+        self
+    }
+
+    pub fn profile(mut self, profile: Profile) -> Self {
+        self.options.profile = profile;
+        self
+    }
+
+    pub fn arithmetic(mut self, arithmetic: Arithmetic) -> Self {
+        self.options.arithmetic = arithmetic;
+        self
+    }
+
+    pub fn build(self) -> RustCodeGenerator {
+        RustCodeGenerator {
+            output: String::new(),
+            indent_level: 0,
+            in_function: false,
+            in_closure: false,
+            struct_definitions: BTreeMap::new(),
+            enum_variants: BTreeMap::new(),
+            mutable_bindings: BTreeSet::new(),
+            gensym: Gensym::new(),
+            options: self.options,
+            given_types: self.types.is_some(),
+            types: self.types.unwrap_or_default(),
+            node_ids: HashMap::new(),
+            public_items: false,
+            module_declarations: Vec::new(),
+            module_items: BTreeMap::new(),
+            current_module: None,
+            hash_key_structs: BTreeSet::new(),
+            stream_bindings: BTreeSet::new(),
+            string_bindings: BTreeSet::new(),
+            parameter_defaults: BTreeMap::new(),
+            traits: BTreeMap::new(),
+            imports: RefCell::new(BTreeSet::new()),
+            imports_at: 0,
+            function_return_types: BTreeMap::new(),
+        }
+    }
+}
+
+/// A generated Rust source file
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneratedFile {
+    /// File name relative to the output directory, e.g. `geometry.rs`
+    pub path: String,
+    pub contents: String,
+}
+
+pub struct RustCodeGenerator {
+    output: String,
+    indent_level: usize,
+    /// Track if we're inside a function definition (to avoid wrapping in main)
+    in_function: bool,
+    /// Whether the code being generated is in a closure (a lambda or a
+    /// kernel's loop), which cannot return from the function around it
+    in_closure: bool,
+    /// Track defined struct names and their fields
+    struct_definitions: BTreeMap<String, Vec<String>>,
+    /// Track defined enum variants: variant name -> (enum name, payload arity)
+    enum_variants: BTreeMap<String, (String, usize)>,
+    /// Names mutated in place (by Push, Pop or PopFront) in the function or
+    /// `main` being generated; these are bound with `mut`
+    mutable_bindings: BTreeSet<String>,
+    /// Source of hygienic names for compiler-introduced temporaries
+    gensym: Gensym,
+    /// User-selected codegen options
+    options: CodegenOptions,
+    /// Types of the program and its modules from type checking
+    types: TypeTable,
+    /// Whether `types` was given rather than worked out by the generator
+    given_types: bool,
+    /// Id in `types` of each expression being generated, keyed by address
+    node_ids: HashMap<usize, NodeId>,
+    /// Emit functions as `pub` (set while generating a non-root module)
+    public_items: bool,
+    /// `mod` declarations emitted at the top of the next generated root file
+    module_declarations: Vec<String>,
+    /// Items defined by each module being generated (keyed by W module name),
+    /// for resolving qualified names
+    module_items: BTreeMap<String, BTreeMap<String, Visibility>>,
+    /// W name of the module currently being generated, if not the root
+    current_module: Option<String>,
+    /// Structs and enums used (directly or through a field) as Map keys or
+    /// HashSet elements; these also derive Eq and Hash
+    hash_key_structs: BTreeSet<String>,
+    /// Parameters of the functions with default values, filled in at call
+    /// sites that leave them out
+    parameter_defaults: BTreeMap<String, Vec<TypeAnnotation>>,
+    /// Defined traits and their methods; parameters typed as a trait become
+    /// `impl Trait`, and calls to a method name become method calls
+    traits: BTreeMap<String, Vec<TraitMethod>>,
+    /// Names bound to lazy streams in the function or `main` being generated;
+    /// Map and Filter over these stay lazy instead of collecting
+    stream_bindings: BTreeSet<String>,
+    /// Names bound to Strings in the function or `main` being generated;
+    /// `+` on these concatenates
+    string_bindings: BTreeSet<String>,
+    /// `std::collections` types named in the file being generated, imported
+    /// at `imports_at` when imports are hoisted. Recorded while rendering
+    /// types, which only borrows the generator.
+    imports: RefCell<BTreeSet<&'static str>>,
+    imports_at: usize,
+    /// Rust return types of the program's functions (keyed by Rust name),
+    /// worked out before generating any of them, for calls to them
+    function_return_types: BTreeMap<String, String>,
+}
+
+impl Default for RustCodeGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RustCodeGenerator {
+    /// Create a generator with default options
+    pub fn new() -> Self {
+        Self::builder().build()
+    }
+
+    /// Start configuring a generator
+    pub fn builder() -> RustCodeGeneratorBuilder {
+        RustCodeGeneratorBuilder::default()
+    }
+
+    /// The options this generator was built with
+    pub fn options(&self) -> &CodegenOptions {
+        &self.options
+    }
+
+    fn indent(&self) -> String {
+        " ".repeat(self.options.indent_width * self.indent_level)
+    }
+
+    /// A single level of indentation
+    fn indent_unit(&self) -> String {
+        " ".repeat(self.options.indent_width)
+    }
+
+    /// Path to the `std::collections` type `name`; just `name` when imports
+    /// are hoisted, recording it for the file's `use`
+    fn collection(&self, name: &'static str) -> String {
+        if self.options.hoist_imports {
+            self.imports.borrow_mut().insert(name);
+            name.to_string()
+        } else {
+            format!("{}::collections::{}", self.collections_crate(), name)
+        }
+    }
+
+    /// Whether the file is a `#![no_std]` library
+    fn is_no_std(&self) -> bool {
+        self.options.is_no_std()
+    }
+
+    /// Crate the collection types come from
+    fn collections_crate(&self) -> &'static str {
+        if self.is_no_std() { "alloc" } else { "std" }
+    }
+
+    /// Crate of language items available with or without std, e.g. `iter`
+    fn core_crate(&self) -> &'static str {
+        if self.is_no_std() { "core" } else { "std" }
+    }
+
+    /// The file generated so far, with its hoisted `use` inserted
+    fn output_with_imports(&self) -> String {
+        let imports = self.imports.borrow();
+        if imports.is_empty() {
+            return self.output.clone();
+        }
+        let names: Vec<&str> = imports.iter().copied().collect();
+        let import = match names.as_slice() {
+            [name] => format!("use {}::collections::{};\n\n", self.collections_crate(), name),
+            _ => format!("use {}::collections::{{{}}};\n\n", self.collections_crate(), names.join(", ")),
+        };
+        let mut output = self.output.clone();
+        output.insert_str(self.imports_at, &import);
+        output
+    }
+
+    /// Generate one Rust module per W source file.
+    ///
+    /// `modules` holds `(name, program)` pairs for the imported files, in
+    /// dependency order. Each becomes `<name>.rs` containing its definitions,
+    /// `pub` unless wrapped in `Private[...]`; `main.rs` is generated from
+    /// `entry` and declares every module with `mod <name>;`, so rustc/cargo can
+    /// rebuild files separately. Imported files may only contain definitions,
+    /// and private items cannot be referenced from other modules.
+    pub fn generate_modules(
+        &mut self,
+        entry: &Expression,
+        modules: &[(String, Expression)],
+    ) -> Result<Vec<GeneratedFile>, std::fmt::Error> {
+        self.generate_module_tree(entry, modules, false)
+    }
+
+    /// Generate the entry program and its modules as one file.
+    ///
+    /// Same as `generate_modules`, except each module is emitted inline as
+    /// `mod <name> { ... }` in the root file, so a plain `rustc` invocation
+    /// can compile the result.
+    pub fn generate_single_file(
+        &mut self,
+        entry: &Expression,
+        modules: &[(String, Expression)],
+    ) -> Result<String, std::fmt::Error> {
+        let mut files = self.generate_module_tree(entry, modules, true)?;
+        Ok(files.remove(0).contents)
+    }
+
+    fn generate_module_tree(
+        &mut self,
+        entry: &Expression,
+        modules: &[(String, Expression)],
+        inline: bool,
+    ) -> Result<Vec<GeneratedFile>, std::fmt::Error> {
+        let mut files = Vec::new();
+        let mut module_names = Vec::new();
+        let programs: Vec<&Expression> = std::iter::once(entry)
+            .chain(modules.iter().map(|(_, program)| program))
+            .collect();
+        self.hash_key_structs = hash_key_structs(&programs);
+        self.prepare_types(entry, modules);
+        self.module_items = modules.iter()
+            .map(|(name, program)| (name.clone(), definition_visibilities(program)))
+            .collect();
+
+        let result = self.generate_module_files(entry, modules, inline, &mut files, &mut module_names);
+        self.public_items = false;
+        self.current_module = None;
+        self.module_items.clear();
+        self.module_declarations.clear();
+        self.hash_key_structs.clear();
+        result?;
+        Ok(files)
+    }
+
+    fn generate_module_files(
+        &mut self,
+        entry: &Expression,
+        modules: &[(String, Expression)],
+        inline: bool,
+        files: &mut Vec<GeneratedFile>,
+        module_names: &mut Vec<String>,
+    ) -> Result<(), std::fmt::Error> {
+        let mut inline_modules = Vec::new();
+        for (i, (name, program)) in modules.iter().enumerate() {
+            let module_name = to_snake_case(name);
+            let items: Vec<&Expression> = match program {
+                Expression::Program(expressions) => expressions.iter().collect(),
+                other => vec![other],
+            };
+            self.current_module = Some(name.clone());
+
+            // Inline modules share the root file's header
+            if inline {
+                self.reset_file();
+            } else {
+                self.begin_file(i + 1)?;
+            }
+            // Earlier modules are visible through the crate root's glob imports
+            writeln!(self.output, "#[allow(unused_imports)]")?;
+            writeln!(self.output, "use crate::*;")?;
+            self.emit_support_modules(program)?;
+            self.register_function_return_types(program);
+            self.public_items = true;
+            for item in items {
+                if !matches!(item, Expression::FunctionDefinition { .. } | Expression::StructDefinition { .. } | Expression::EnumDefinition { .. }
+                    | Expression::TraitDefinition { .. } | Expression::Impl { .. } | Expression::Kernel { .. }) {
+                    return Err(std::fmt::Error);
+                }
+                writeln!(self.output)?;
+                self.generate_top_level_item(item)?;
+            }
+            self.public_items = false;
+
+            if inline {
+                let mut declaration = format!("mod {} {{\n", module_name);
+                for line in self.output_with_imports().lines() {
+                    if !line.is_empty() {
+                        declaration.push_str(&self.indent_unit());
+                        declaration.push_str(line);
+                    }
+                    declaration.push('\n');
+                }
+                declaration.push_str(&format!("}}\nuse {}::*;", module_name));
+                inline_modules.push(declaration);
+            } else {
+                files.push(GeneratedFile {
+                    path: format!("{}.rs", module_name),
+                    contents: self.finish_file(),
+                });
+            }
+            module_names.push(module_name);
+        }
+
+        self.current_module = None;
+        self.module_declarations = if inline {
+            inline_modules
+        } else {
+            module_names.iter()
+                .map(|name| format!("mod {};\nuse {}::*;", name, name))
+                .collect()
+        };
+        let main = self.generate(entry)?;
+
+        files.insert(0, GeneratedFile {
+            path: "main.rs".to_string(),
+            contents: main,
+        });
+        Ok(())
+    }
+
+    /// Number the expressions of `modules` and `entry` as type checking them
+    /// did, and type check them unless the types were given
+    fn prepare_types(&mut self, entry: &Expression, modules: &[(String, Expression)]) {
+        self.node_ids.clear();
+        for (_, program) in modules {
+            number_nodes(top_level(program), &mut self.node_ids);
+        }
+        number_nodes(top_level(entry), &mut self.node_ids);
+        if !self.given_types {
+            // A program that does not type check is still generated, with
+            // the types of the parts that do
+            let mut inference = TypeInference::new();
+            if self.options.arithmetic == Arithmetic::Checked {
+                inference = inference.with_checked_arithmetic();
+            }
+            for (name, program) in modules {
+                inference.check_module(name, top_level(program));
+            }
+            inference.check_program_errors(top_level(entry));
+            self.types = inference.type_table(top_level(entry));
+        }
+    }
+
+    /// Type of `expr` from type checking, if it settled on one
+    fn node_type(&self, expr: &Expression) -> Option<&Type> {
+        let id = self.node_ids.get(&(expr as *const Expression as usize))?;
+        self.types.node(*id)
+    }
+
+    /// Whether type checking found `expr` to be a List, Queue or Bytes, which
+    /// `expr[i]` indexes rather than calls
+    fn is_indexable(&self, expr: &Expression) -> bool {
+        matches!(self.node_type(expr), Some(Type::List(_) | Type::Queue(_) | Type::Bytes))
+    }
+
+    /// `At[collection, index]`: an Option, None when out of bounds
+    fn generate_at(&mut self, collection: &Expression, index: &Expression) -> Result<String, std::fmt::Error> {
+        let collection = self.generate_expression_value(collection)?;
+        let index = self.generate_expression_value(index)?;
+        Ok(format!("{}.get({} as usize).cloned()", collection, index))
+    }
+
+    /// Reset per-file state
+    fn reset_file(&mut self) {
+        self.output.clear();
+        self.indent_level = 0;
+        self.gensym.reset();
+        self.imports.borrow_mut().clear();
+        self.imports_at = 0;
+    }
+
+    /// Reset per-file state and emit the configured header of the file
+    /// generated from provenance input `input` (0 for the entry program)
+    fn begin_file(&mut self, input: usize) -> Result<(), std::fmt::Error> {
+        self.reset_file();
+
+        if let Some(provenance) = &self.options.provenance {
+            writeln!(self.output, "{}", provenance.header(input))?;
+            writeln!(self.output)?;
+        }
+        if let Some(header) = &self.options.module_header {
+            writeln!(self.output, "{}", header.trim_end())?;
+            writeln!(self.output)?;
+        }
+        if self.options.emit_allow_attributes {
+            writeln!(self.output, "#![allow({})]", ALLOWED_LINTS.join(", "))?;
+            writeln!(self.output)?;
+        }
+        self.imports_at = self.output.len();
+        Ok(())
+    }
+
+    /// Declare a `#![no_std]` crate and import the `alloc` items generated
+    /// code uses unqualified, as std's prelude would (the embedded profile
+    /// allocates nothing)
+    fn begin_no_std_crate(&mut self) -> Result<(), std::fmt::Error> {
+        writeln!(self.output, "#![no_std]")?;
+        writeln!(self.output)?;
+        if self.options.profile == Profile::Embedded {
+            self.imports_at = self.output.len();
+            return Ok(());
+        }
+        writeln!(self.output, "extern crate alloc;")?;
+        writeln!(self.output)?;
+        writeln!(self.output, "#[allow(unused_imports)]")?;
+        writeln!(self.output, "use alloc::{{borrow::ToOwned, boxed::Box, format, string::{{String, ToString}}, vec, vec::Vec}};")?;
+        writeln!(self.output)?;
+        self.imports_at = self.output.len();
+        Ok(())
+    }
+
+    /// Apply the configured formatter and line width to the finished file
+    fn finish_file(&self) -> String {
+        let output = self.output_with_imports();
+        if self.options.formatter == Formatter::Rustfmt {
+            if let Some(formatted) = run_rustfmt(&output, self.options.max_width) {
+                return formatted;
+            }
+        }
+        match self.options.max_width {
+            Some(width) => wrap_long_lines(&output, width, self.options.indent_width),
+            None => output,
+        }
+    }
+
+    pub fn generate(&mut self, expr: &Expression) -> Result<String, std::fmt::Error> {
+        // Reset output for each generation
+        self.begin_file(0)?;
+        if self.is_no_std() {
+            self.begin_no_std_crate()?;
+        }
+
+        // When generating a module tree, key usage was already collected
+        // across every module
+        if self.module_declarations.is_empty() {
+            self.hash_key_structs = hash_key_structs(&[expr]);
+            self.prepare_types(expr, &[]);
+        }
+
+        if !self.module_declarations.is_empty() {
+            for declaration in &self.module_declarations {
+                writeln!(self.output, "{}", declaration)?;
+            }
+            writeln!(self.output)?;
+        }
+
+        self.emit_support_modules(expr)?;
+        self.register_traits(expr);
+        self.register_defaults(expr);
+        self.register_function_return_types(expr);
+
+        // Check if this is a program with multiple expressions
+        match expr {
+            Expression::Program(expressions) => {
+                // Separate top-level items (structs, functions) from statements
+                let mut top_level_items = Vec::new();
+                let mut statements = Vec::new();
+
+                for e in expressions {
+                    match e {
+                        Expression::FunctionDefinition { .. } | Expression::StructDefinition { .. }
+                        | Expression::EnumDefinition { .. } | Expression::TraitDefinition { .. }
+                        | Expression::Impl { .. } | Expression::Kernel { .. } => top_level_items.push(e),
+                        _ => statements.push(e),
+                    }
+                }
+
+                // Generate all top-level items first (structs, then functions)
+                for item in &top_level_items {
+                    self.generate_top_level_item(item)?;
+                    writeln!(self.output)?;
+                }
+
+                // Generate main function with statements
+                if self.is_no_std() {
+                    // A library has no main to run statements in
+                    if !statements.is_empty() {
+                        return Err(std::fmt::Error);
+                    }
+                } else if statements.is_empty() {
+                    // Just top-level definitions, add stub main
+                    writeln!(self.output, "fn main() {{")?;
+                    writeln!(self.output, "{}// Stub main function for compilation", self.indent_unit())?;
+                    writeln!(self.output, "}}")?;
+                } else {
+                    // Generate main with statements
+                    writeln!(self.output, "fn main() {{")?;
+                    self.indent_level += 1;
+                    self.begin_logging(expr)?;
+                    self.mutable_bindings = mutated_bindings(&statements);
+                    self.stream_bindings = stream_bindings(&statements);
+                    self.string_bindings = self.string_bindings(&statements, BTreeSet::new());
+                    for stmt in &statements {
+                        self.generate_main_statement(stmt)?;
+                    }
+                    self.indent_level -= 1;
+                    writeln!(self.output, "}}")?;
+                }
+            }
+            Expression::FunctionDefinition { .. } | Expression::StructDefinition { .. } | Expression::EnumDefinition { .. }
+                    | Expression::TraitDefinition { .. } | Expression::Impl { .. } | Expression::Kernel { .. } => {
+                // Single top-level definition
+                self.generate_top_level_item(expr)?;
+                if self.is_no_std() {
+                    return Ok(self.finish_file());
+                }
+                // Add a stub main function to make it compilable
+                writeln!(self.output)?;
+                writeln!(self.output, "fn main() {{")?;
+                writeln!(self.output, "{}// Stub main function for compilation", self.indent_unit())?;
+                writeln!(self.output, "}}")?;
+            }
+            _ if self.is_no_std() => return Err(std::fmt::Error),
+            _ => {
+                // Single expression, wrap in main function
+                writeln!(self.output, "fn main() {{")?;
+                self.indent_level += 1;
+                self.begin_logging(expr)?;
+                self.mutable_bindings = mutated_bindings(&[expr]);
+                self.stream_bindings = stream_bindings(&[expr]);
+                self.string_bindings = self.string_bindings(&[expr], BTreeSet::new());
+                self.generate_main_statement(expr)?;
+                self.indent_level -= 1;
+                writeln!(self.output, "}}")?;
+            }
+        }
+
+        Ok(self.finish_file())
+    }
+
+    /// Read the log level at the start of `main`, if `expr` logs anything
+    fn begin_logging(&mut self, expr: &Expression) -> Result<(), std::fmt::Error> {
+        if used_support_modules(expr).iter().any(|(module_name, _)| *module_name == "__w_log") {
+            writeln!(self.output, "{}__w_log::init();", self.indent())?;
+        }
+        Ok(())
+    }
+
+    /// Copy in the Rust support module of each builtin namespace `expr` uses
+    fn emit_support_modules(&mut self, expr: &Expression) -> Result<(), std::fmt::Error> {
+        let mut modules = used_support_modules(expr);
+        if self.options.arithmetic != Arithmetic::Unchecked && uses_arithmetic(expr) {
+            modules.extend(builtins::support_module("Arithmetic"));
+        }
+        for (module_name, source) in modules {
+            writeln!(self.output, "#[allow(dead_code)]")?;
+            writeln!(self.output, "mod {} {{", module_name)?;
+            for line in source.lines() {
+                if line.is_empty() {
+                    writeln!(self.output)?;
+                } else {
+                    writeln!(self.output, "{}{}", self.indent_unit(), line)?;
+                }
+            }
+            writeln!(self.output, "}}")?;
+            writeln!(self.output)?;
+        }
+        Ok(())
+    }
+
+    /// Generate top-level items (functions, structs, etc.)
+    fn generate_top_level_item(&mut self, expr: &Expression) -> Result<(), std::fmt::Error> {
+        match expr {
+            Expression::FunctionDefinition { name, parameters, body, visibility } => {
+                self.generate_function_definition(name, parameters, body, *visibility)?;
+            }
+            Expression::StructDefinition { name, fields, visibility } => {
+                self.generate_struct_definition(name, fields, *visibility)?;
+            }
+            Expression::EnumDefinition { name, variants, visibility } => {
+                self.generate_enum_definition(name, variants, *visibility)?;
+            }
+            Expression::TraitDefinition { name, methods, visibility } => {
+                self.generate_trait_definition(name, methods, *visibility)?;
+            }
+            Expression::Impl { trait_name, type_name, methods } => {
+                self.generate_impl(trait_name, type_name, methods)?;
+            }
+            Expression::Kernel { definition } => match definition.as_ref() {
+                Expression::FunctionDefinition { name, parameters, body, visibility } => {
+                    self.generate_kernel(name, parameters, body, *visibility)?;
+                }
+                _ => return Err(std::fmt::Error),
+            },
+            _ => {
+                // For other top-level items, generate as statement
+                self.generate_statement(expr)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Generate a function definition
+    fn generate_function_definition(
+        &mut self,
+        name: &str,
+        parameters: &[TypeAnnotation],
+        body: &Expression,
+        visibility: Visibility,
+    ) -> Result<(), std::fmt::Error> {
+        // Convert function name to snake_case (Rust convention)
+        let rust_name = to_snake_case(name);
+
+        // Module functions are exported unless Private, root functions only
+        // from a no_std library
+        let exported = (self.public_items || self.is_no_std()) && visibility == Visibility::Public;
+        let visibility = if exported { "pub " } else { "" };
+        let return_type = self.checked_return_type(name);
+        self.generate_fn(visibility, &rust_name, None, parameters, return_type.as_ref(), body)
+    }
+
+    /// Return type type checking settled on for the function `name` of the
+    /// program or module being generated, unless it is one Rust cannot name
+    /// (a closure or `!`)
+    fn checked_return_type(&self, name: &str) -> Option<Type> {
+        fn nameable(ty: &Type) -> bool {
+            !matches!(ty, Type::Function(..) | Type::Never) && type_components(ty).into_iter().all(nameable)
+        }
+        let function = match &self.current_module {
+            Some(module) => self.types.module_item(module, name),
+            None => self.types.lookup(name),
+        };
+        match function {
+            Some(Type::Function(_, return_type)) if nameable(return_type) => Some(return_type.as_ref().clone()),
+            _ => None,
+        }
+    }
+
+    /// Rust return type of a function taking `parameters`, from the type
+    /// checked for its `body`: `()` unless it is a type the function can
+    /// name, one whose generic parameters are the function's own
+    fn body_return_type(&self, parameters: &[TypeAnnotation], body: &Expression) -> String {
+        fn nameable(ty: &Type, generics: &[String]) -> bool {
+            match ty {
+                Type::Function(..) => false,
+                Type::TypeVar(name) => generics.contains(name),
+                _ => type_components(ty).into_iter().all(|ty| nameable(ty, generics)),
+            }
+        }
+        let mut generics = Vec::new();
+        for param in parameters {
+            collect_type_variables(&param.type_, &mut generics);
+        }
+        match self.node_type(body) {
+            Some(ty) if nameable(ty, &generics) => self.type_to_rust(ty),
+            _ => "()".to_string(),
+        }
+    }
+
+    /// Generate a kernel as a function that splits the index range of its
+    /// List and Array parameters into one chunk per available thread, and
+    /// fills each chunk of the result on a scoped thread
+    fn generate_kernel(
+        &mut self,
+        name: &str,
+        parameters: &[TypeAnnotation],
+        body: &Expression,
+        visibility: Visibility,
+    ) -> Result<(), std::fmt::Error> {
+        let rust_name = to_snake_case(name);
+        let exported = (self.public_items || self.is_no_std()) && visibility == Visibility::Public;
+        let visibility = if exported { "pub " } else { "" };
+
+        // Inside the loop each List or Array parameter is one of its elements
+        let lists: Vec<String> = parameters.iter()
+            .filter(|param| matches!(param.type_, Type::List(_) | Type::Array(..)))
+            .map(|param| to_snake_case(&param.name))
+            .collect();
+        let element_type = self.body_return_type(parameters, body);
+        if lists.is_empty() || element_type == "()" {
+            return Err(std::fmt::Error);
+        }
+        let return_type = format!("Vec<{}>", element_type);
+        self.function_return_types.insert(rust_name.clone(), return_type.clone());
+
+        let params: Vec<String> = parameters.iter()
+            .map(|param| format!("{}: {}", to_snake_case(&param.name), self.parameter_type_to_rust(&param.type_)))
+            .collect();
+        writeln!(self.output, "{}{}fn {}({}) -> {} {{", self.indent(), visibility, rust_name, params.join(", "), return_type)?;
+        self.indent_level += 1;
+        self.in_function = true;
+
+        let len = self.gensym.fresh("len");
+        let out = self.gensym.fresh("out");
+        let chunk_len = self.gensym.fresh("chunk_len");
+        let scope = self.gensym.fresh("scope");
+        let chunk = self.gensym.fresh("chunk");
+        let part = self.gensym.fresh("part");
+        let start = self.gensym.fresh("start");
+        let index = self.gensym.fresh("i");
+        let slot = self.gensym.fresh("slot");
+        let indent = self.indent();
+        let unit = self.indent_unit();
+        writeln!(self.output, "{}let {} = {}.len();", indent, len, lists[0])?;
+        for list in &lists[1..] {
+            writeln!(
+                self.output,
+                "{}assert_eq!({}.len(), {}, \"{}: kernel inputs differ in length\");",
+                indent, list, len, rust_name
+            )?;
+        }
+        writeln!(self.output, "{}let mut {}: {} = vec![Default::default(); {}];", indent, out, return_type, len)?;
+        writeln!(
+            self.output,
+            "{}let {} = {}.div_ceil(std::thread::available_parallelism().map_or(1, |n| n.get())).max(1);",
+            indent, chunk_len, len
+        )?;
+        // Threads share the inputs by reference
+        for list in &lists {
+            writeln!(self.output, "{}let {} = &{};", indent, list, list)?;
+        }
+        writeln!(self.output, "{}std::thread::scope(|{}| {{", indent, scope)?;
+        writeln!(
+            self.output,
+            "{}{}for ({}, {}) in {}.chunks_mut({}).enumerate() {{",
+            indent, unit, chunk, part, out, chunk_len
+        )?;
+        writeln!(self.output, "{}{}{}let {} = {} * {};", indent, unit, unit, start, chunk, chunk_len)?;
+        writeln!(self.output, "{}{}{}{}.spawn(move || {{", indent, unit, unit, scope)?;
+        let loop_indent = format!("{}{}{}{}", indent, unit, unit, unit);
+        writeln!(
+            self.output,
+            "{}for ({}, {}) in ({}..).zip({}.iter_mut()) {{",
+            loop_indent, index, slot, start, part
+        )?;
+        for list in &lists {
+            writeln!(self.output, "{}{}let {} = {}[{}];", loop_indent, unit, list, list, index)?;
+        }
+        // The body is four blocks deeper than the function's statements
+        self.indent_level += 4;
+        let body_code = self.generate_closure_body(body)?;
+        self.indent_level -= 4;
+        writeln!(self.output, "{}{}*{} = {};", loop_indent, unit, slot, body_code)?;
+        writeln!(self.output, "{}}}", loop_indent)?;
+        writeln!(self.output, "{}{}{}}});", indent, unit, unit)?;
+        writeln!(self.output, "{}{}}}", indent, unit)?;
+        writeln!(self.output, "{}}});", indent)?;
+        writeln!(self.output, "{}{}", indent, out)?;
+
+        self.in_function = false;
+        self.indent_level -= 1;
+        writeln!(self.output, "{}}}", self.indent())?;
+        Ok(())
+    }
+
+    /// Emit `fn name(parameters) -> return_type { body }`. Methods pass their
+    /// receiver (`&self`) and the return type their trait declares; other
+    /// functions have it inferred from the body.
+    fn generate_fn(
+        &mut self,
+        visibility: &str,
+        rust_name: &str,
+        receiver: Option<&str>,
+        parameters: &[TypeAnnotation],
+        return_type: Option<&Type>,
+        body: &Expression,
+    ) -> Result<(), std::fmt::Error> {
+        write!(self.output, "{}{}fn {}", self.indent(), visibility, rust_name)?;
+
+        // Declare type variables in order of first appearance
+        let mut type_variables = Vec::new();
+        for param in parameters {
+            collect_type_variables(&param.type_, &mut type_variables);
+        }
+        if !type_variables.is_empty() {
+            write!(self.output, "<{}>", type_variables.join(", "))?;
+        }
+        write!(self.output, "(")?;
+        if let Some(receiver) = receiver {
+            write!(self.output, "{}", receiver)?;
+            if !parameters.is_empty() {
+                write!(self.output, ", ")?;
+            }
+        }
+
+        // Generate parameters
+        let enclosing_mutable_bindings = std::mem::replace(&mut self.mutable_bindings, mutated_bindings(&[body]));
+        let mut streams: BTreeSet<String> = parameters.iter()
+            .filter(|param| matches!(param.type_, Type::Stream(_)))
+            .map(|param| param.name.clone())
+            .collect();
+        streams.extend(stream_bindings(&[body]));
+        let enclosing_stream_bindings = std::mem::replace(&mut self.stream_bindings, streams);
+        let strings = self.string_bindings(&[body], string_parameters(parameters));
+        let enclosing_string_bindings = std::mem::replace(&mut self.string_bindings, strings);
+        for (i, param) in parameters.iter().enumerate() {
+            if i > 0 {
+                write!(self.output, ", ")?;
+            }
+            let param_name = to_snake_case(&param.name);
+            let param_type = self.parameter_type_to_rust(&param.type_);
+            let mutability = if self.mutable_bindings.contains(&param.name) { "mut " } else { "" };
+            write!(self.output, "{}{}: {}", mutability, param_name, param_type)?;
+        }
+
+        write!(self.output, ")")?;
+
+        let return_type = match return_type {
+            Some(declared) => self.type_to_rust(declared),
+            None => self.body_return_type(parameters, body),
+        };
+        if receiver.is_none() {
+            self.function_return_types.insert(rust_name.to_string(), return_type.clone());
+        }
+        if return_type != "()" {
+            write!(self.output, " -> {}", return_type)?;
+        }
+
+        writeln!(self.output, " {{")?;
+        self.indent_level += 1;
+        self.in_function = true;
+
+        // Emit Let bindings at the top of the body as plain statements
+        let mut body = body;
+        while let Expression::Let { name, value, body: inner } = body {
+            let value_code = self.generate_expression_value(value)?;
+            writeln!(self.output, "{}let {} = {};", self.indent(), to_snake_case(name), value_code)?;
+            body = inner;
+        }
+
+        // A block's statements are the function's, and a LetElse fallback
+        // returns its value from it
+        let body = match body {
+            Expression::Block(statements) => {
+                let (statements, value) = split_block(statements);
+                self.generate_block_statements(statements, "return", true)?;
+                value
+            }
+            _ => Some(body),
+        };
+
+        // Generate function body as an expression (no trailing semicolon for return)
+        if let Some(body) = body {
+            let body_code = self.generate_expression_value(body)?;
+            // Write without newline from writeln to keep it as an expression
+            writeln!(self.output, "{}{}", self.indent(), body_code)?;
+        }
+
+        self.in_function = false;
+        self.mutable_bindings = enclosing_mutable_bindings;
+        self.stream_bindings = enclosing_stream_bindings;
+        self.string_bindings = enclosing_string_bindings;
+        self.indent_level -= 1;
+        writeln!(self.output, "{}}}", self.indent())?;
+
+        Ok(())
+    }
+
+    /// Generate a trait with one required method per signature; every method
+    /// borrows its receiver
+    fn generate_trait_definition(
+        &mut self,
+        name: &str,
+        methods: &[TraitMethod],
+        visibility: Visibility,
+    ) -> Result<(), std::fmt::Error> {
+        let exported = (self.public_items || self.is_no_std()) && visibility == Visibility::Public;
+        let visibility = if exported { "pub " } else { "" };
+        writeln!(self.output, "{}{}trait {} {{", self.indent(), visibility, name)?;
+        self.indent_level += 1;
+        for method in methods {
+            write!(self.output, "{}fn {}(&self", self.indent(), to_snake_case(&method.name))?;
+            for param in &method.parameters {
+                write!(self.output, ", {}: {}", to_snake_case(&param.name), self.parameter_type_to_rust(&param.type_))?;
+            }
+            write!(self.output, ")")?;
+            let return_type = self.type_to_rust(&method.return_type);
+            if return_type != "()" {
+                write!(self.output, " -> {}", return_type)?;
+            }
+            writeln!(self.output, ";")?;
+        }
+        self.indent_level -= 1;
+        writeln!(self.output, "{}}}", self.indent())?;
+        Ok(())
+    }
+
+    /// Generate `impl Trait for Type`; parameter and return types come from
+    /// the trait's signatures
+    fn generate_impl(&mut self, trait_name: &str, type_name: &str, methods: &[Expression]) -> Result<(), std::fmt::Error> {
+        let signatures = self.traits.get(trait_name).cloned().ok_or(std::fmt::Error)?;
+        writeln!(self.output, "{}impl {} for {} {{", self.indent(), trait_name, type_name)?;
+        self.indent_level += 1;
+        for (i, method) in methods.iter().enumerate() {
+            let Expression::FunctionDefinition { name, parameters, body, .. } = method else {
+                return Err(std::fmt::Error);
+            };
+            let signature = signatures.iter().find(|m| m.name == *name).ok_or(std::fmt::Error)?;
+            if signature.parameters.len() != parameters.len() {
+                return Err(std::fmt::Error);
+            }
+            let parameters: Vec<TypeAnnotation> = parameters.iter()
+                .zip(&signature.parameters)
+                .map(|(param, declared)| TypeAnnotation { name: param.name.clone(), type_: declared.type_.clone(), default: None })
+                .collect();
+            if i > 0 {
+                writeln!(self.output)?;
+            }
+            self.generate_fn("", &to_snake_case(name), Some("&self"), &parameters, Some(&signature.return_type), body)?;
+        }
+        self.indent_level -= 1;
+        writeln!(self.output, "{}}}", self.indent())?;
+        Ok(())
+    }
+
+    /// Record the traits defined by `program` before any item is generated,
+    /// so functions and impls can refer to traits defined after them
+    fn register_traits(&mut self, program: &Expression) {
+        let items = match program {
+            Expression::Program(expressions) => expressions.as_slice(),
+            other => std::slice::from_ref(other),
+        };
+        for item in items {
+            if let Expression::TraitDefinition { name, methods, .. } = item {
+                self.traits.insert(name.clone(), methods.clone());
+            }
+        }
+    }
+
+    /// Record the parameters of each function with default values
+    fn register_defaults(&mut self, program: &Expression) {
+        let items = match program {
+            Expression::Program(expressions) => expressions.as_slice(),
+            other => std::slice::from_ref(other),
+        };
+        for item in items {
+            let definition = match item {
+                Expression::Kernel { definition } => definition.as_ref(),
+                other => other,
+            };
+            if let Expression::FunctionDefinition { name, parameters, .. } = definition {
+                if parameters.iter().any(|param| param.default.is_some()) {
+                    self.parameter_defaults.insert(name.clone(), parameters.clone());
+                }
+            }
+        }
+    }
+
+    /// Work out the return type of every function `program` defines before
+    /// generating any, so calls to the function itself or to one defined
+    /// after the caller have a type too
+    fn register_function_return_types(&mut self, program: &Expression) {
+        let items = match program {
+            Expression::Program(expressions) => expressions.as_slice(),
+            other => std::slice::from_ref(other),
+        };
+        let functions: Vec<(&String, &[TypeAnnotation], &Expression)> = items.iter()
+            .filter_map(|item| match item {
+                Expression::FunctionDefinition { name, parameters, body, .. } => Some((name, parameters.as_slice(), body.as_ref())),
+                _ => None,
+            })
+            .collect();
+        for (name, parameters, body) in functions {
+            let return_type = match self.checked_return_type(name) {
+                Some(checked) => self.type_to_rust(&checked),
+                None => self.body_return_type(parameters, body),
+            };
+            self.function_return_types.insert(to_snake_case(name), return_type);
+        }
+    }
+
+    /// Trait declaring `method`, if any
+    fn trait_method(&self, method: &str) -> Option<&TraitMethod> {
+        self.traits.values().flatten().find(|m| m.name == method)
+    }
+
+    /// Generate a struct definition
+    fn generate_struct_definition(
+        &mut self,
+        name: &str,
+        fields: &[TypeAnnotation],
+        visibility: Visibility,
+    ) -> Result<(), std::fmt::Error> {
+        // Track this struct's field names for constructor detection
+        let field_names: Vec<String> = fields.iter()
+            .map(|f| to_snake_case(&f.name))
+            .collect();
+        self.struct_definitions.insert(name.to_string(), field_names);
+
+        // Generate: #[derive(Debug, Clone, PartialEq)]
+        //           pub struct Name {
+        //               field1: Type1,
+        //               field2: Type2,
+        //           }
+        let mut derives = self.options.derives.clone();
+        if self.hash_key_structs.contains(name) {
+            // f32/f64 implement neither Eq nor Hash, so the derive cannot compile
+            if fields.iter().any(|f| contains_float(&f.type_)) {
+                return Err(std::fmt::Error);
+            }
+            for required in ["PartialEq", "Eq", "Hash"] {
+                if !derives.iter().any(|d| d == required) {
+                    derives.push(required.to_string());
+                }
+            }
+        }
+        if !derives.is_empty() {
+            writeln!(self.output, "{}#[derive({})]", self.indent(), derives.join(", "))?;
+        }
+        let visibility = if visibility == Visibility::Public { "pub " } else { "" };
+        writeln!(self.output, "{}{}struct {} {{", self.indent(), visibility, name)?;
+
+        self.indent_level += 1;
+        for field in fields {
+            let field_name = to_snake_case(&field.name);
+            let field_type = self.type_to_rust(&field.type_);
+            writeln!(self.output, "{}pub {}: {},", self.indent(), field_name, field_type)?;
+        }
+        self.indent_level -= 1;
+
+        writeln!(self.output, "{}}}", self.indent())?;
+
+        Ok(())
+    }
+
+    /// Generate an enum definition
+    fn generate_enum_definition(
+        &mut self,
+        name: &str,
+        variants: &[EnumVariant],
+        visibility: Visibility,
+    ) -> Result<(), std::fmt::Error> {
+        // Track variants so constructor calls and patterns can be qualified
+        for variant in variants {
+            self.enum_variants.insert(variant.name.clone(), (name.to_string(), variant.fields.len()));
+        }
+
+        let mut derives = self.options.derives.clone();
+        if self.hash_key_structs.contains(name) {
+            // f32/f64 implement neither Eq nor Hash, so the derive cannot compile
+            if variants.iter().flat_map(|v| &v.fields).any(contains_float) {
+                return Err(std::fmt::Error);
+            }
+            for required in ["PartialEq", "Eq", "Hash"] {
+                if !derives.iter().any(|d| d == required) {
+                    derives.push(required.to_string());
+                }
+            }
+        }
+        if !derives.is_empty() {
+            writeln!(self.output, "{}#[derive({})]", self.indent(), derives.join(", "))?;
+        }
+        let visibility = if visibility == Visibility::Public { "pub " } else { "" };
+        writeln!(self.output, "{}{}enum {} {{", self.indent(), visibility, name)?;
+
+        self.indent_level += 1;
+        for variant in variants {
+            if variant.fields.is_empty() {
+                writeln!(self.output, "{}{},", self.indent(), variant.name)?;
+            } else {
+                let fields: Vec<String> = variant.fields.iter().map(|t| self.type_to_rust(t)).collect();
+                writeln!(self.output, "{}{}({}),", self.indent(), variant.name, fields.join(", "))?;
+            }
+        }
+        self.indent_level -= 1;
+
+        writeln!(self.output, "{}}}", self.indent())?;
+
+        Ok(())
+    }
+
+    /// Convert W type to Rust type
+    fn type_to_rust(&self, ty: &Type) -> String {
+        match ty {
+            // Signed integers
+            Type::Int8 => "i8".to_string(),
+            Type::Int16 => "i16".to_string(),
+            Type::Int32 => "i32".to_string(),
+            Type::Int64 => "i64".to_string(),
+            Type::Int128 => "i128".to_string(),
+            Type::Int => "isize".to_string(),
+
+            // Unsigned integers
+            Type::UInt8 => "u8".to_string(),
+            Type::UInt16 => "u16".to_string(),
+            Type::UInt32 => "u32".to_string(),
+            Type::UInt64 => "u64".to_string(),
+            Type::UInt128 => "u128".to_string(),
+            Type::UInt => "usize".to_string(),
+
+            // Floating point
+            Type::Float32 => "f32".to_string(),
+            Type::Float64 => "f64".to_string(),
+
+            // Other primitives
+            Type::Bool => "bool".to_string(),
+            Type::Char => "char".to_string(),
+            Type::String => "String".to_string(),
+            Type::Bytes => "Vec<u8>".to_string(),
+
+            // Composite types
+            Type::Tuple(types) => {
+                if types.is_empty() {
+                    "()".to_string()
+                } else {
+                    let type_strs: Vec<String> = types.iter()
+                        .map(|t| self.type_to_rust(t))
+                        .collect();
+                    format!("({})", type_strs.join(", "))
+                }
+            }
+
+            // Complex types
+            Type::List(inner) => format!("Vec<{}>", self.type_to_rust(inner)),
+            Type::Queue(inner) => format!("{}<{}>", self.collection("VecDeque"), self.type_to_rust(inner)),
+            Type::Heap(inner) => format!("{}<{}>", self.collection("BinaryHeap"), self.type_to_rust(inner)),
+            Type::Stream(inner) => format!("impl Iterator<Item = {}>", self.type_to_rust(inner)),
+            Type::Array(inner, size) => format!("[{}; {}]", self.type_to_rust(inner), size),
+            Type::Slice(inner) => format!("&[{}]", self.type_to_rust(inner)),
+            Type::Map(key, value) => {
+                format!("{}<{}, {}>",
+                    self.collection("HashMap"),
+                    self.type_to_rust(key),
+                    self.type_to_rust(value))
+            }
+            Type::HashSet(inner) => format!("{}<{}>", self.collection("HashSet"), self.type_to_rust(inner)),
+            Type::BTreeMap(key, value) => {
+                format!("{}<{}, {}>",
+                    self.collection("BTreeMap"),
+                    self.type_to_rust(key),
+                    self.type_to_rust(value))
+            }
+            Type::BTreeSet(inner) => format!("{}<{}>", self.collection("BTreeSet"), self.type_to_rust(inner)),
+            Type::Function(params, ret) => {
+                let param_types: Vec<String> = params.iter()
+                    .map(|p| self.type_to_rust(p))
+                    .collect();
+                format!("fn({}) -> {}", param_types.join(", "), self.type_to_rust(ret))
+            }
+
+            // Error handling types (Rust's safety model)
+            Type::Option(inner) => format!("Option<{}>", self.type_to_rust(inner)),
+            Type::Result(ok_type, err_type) => {
+                format!("Result<{}, {}>",
+                    self.type_to_rust(ok_type),
+                    self.type_to_rust(err_type))
+            }
+
+            // Special types
+            Type::LogLevel => "LogLevel".to_string(),
+            // Left for rustc to infer
+            Type::Unknown => "_".to_string(),
+            Type::Never => "!".to_string(),
+
+            // User-defined types
+            // A trait-typed value is any type implementing it
+            Type::Custom(name) if self.traits.contains_key(name) => format!("impl {}", name),
+            Type::Custom(name) => name.clone(),
+            // Declared on the function as `fn name<T>`
+            Type::TypeVar(name) => name.clone(),
+        }
+    }
+
+    /// `: T` giving a `let` binding `value` the type type checking found,
+    /// when `value` holds a `None` Rust cannot type from the value alone
+    fn binding_annotation(&self, value: &Expression) -> String {
+        fn holds_none(expr: &Expression) -> bool {
+            let mut found = matches!(expr, Expression::None);
+            if !matches!(expr, Expression::Lambda { .. }) {
+                expr.for_each_child(&mut |child| found |= holds_none(child));
+            }
+            found
+        }
+        match self.node_type(value).and_then(|ty| self.annotation_type(ty)) {
+            Some(ty) if holds_none(value) => format!(": {}", ty),
+            _ => String::new(),
+        }
+    }
+
+    /// Rust type to annotate a value of type `ty` with, unless a part of it
+    /// is one Rust cannot name there: a closure, `!`, a trait or one left to
+    /// rustc
+    fn annotation_type(&self, ty: &Type) -> Option<String> {
+        fn nameable(ty: &Type, traits: &BTreeMap<String, Vec<TraitMethod>>) -> bool {
+            match ty {
+                Type::Function(..) | Type::Never | Type::Unknown => false,
+                Type::Custom(name) => !traits.contains_key(name),
+                _ => type_components(ty).into_iter().all(|ty| nameable(ty, traits)),
+            }
+        }
+        nameable(ty, &self.traits).then(|| self.type_to_rust(ty))
+    }
+
+    /// Rust type of a function parameter: a function-typed one takes any
+    /// closure or function with that signature, not just a `fn` pointer
+    fn parameter_type_to_rust(&self, type_: &Type) -> String {
+        match type_ {
+            Type::Function(params, ret) => {
+                let param_types: Vec<String> = params.iter()
+                    .map(|p| self.type_to_rust(p))
+                    .collect();
+                match self.type_to_rust(ret).as_str() {
+                    "()" => format!("impl Fn({})", param_types.join(", ")),
+                    ret => format!("impl Fn({}) -> {}", param_types.join(", "), ret),
+                }
+            }
+            _ => self.type_to_rust(type_),
+        }
+    }
+
+    /// Choose the `format!` placeholder for a value: `{:?}` for types that
+    /// don't implement Display (collections, structs, Options and Results),
+    /// `{}` otherwise
+    fn format_placeholder(&self, expr: &Expression) -> &'static str {
+        if let Some(ty) = self.node_type(expr) {
+            return if is_display(ty) { "{}" } else { "{:?}" };
+        }
+        match expr {
+            Expression::List(_) | Expression::Map(_) | Expression::Tuple(_) => "{:?}",
+            Expression::Some { .. } | Expression::None | Expression::Ok { .. } | Expression::Err { .. } => "{:?}",
+            Expression::Identifier(name) if self.enum_variants.contains_key(name) => "{:?}",
+            // Also check for Map/Filter function calls that return Vec
+            Expression::FunctionCall { function, .. } => match function.as_ref() {
+                // Check if it's Map/Filter or a struct constructor
+                Expression::Identifier(name)
+                    if matches!(name.as_str(),
+                        "Map" | "Filter" | "SortWith" | "Take" | "Range" | "Chars" | "Bytes" | "ReadFileBytes" | "At" | "Lookup" | "Stack" | "Queue" | "Pop" | "PopFront"
+                        | "Heap" | "HeapPop")
+                        || self.struct_definitions.contains_key(name)
+                        || self.enum_variants.contains_key(name)
+                        || self.function_return_types.get(&to_snake_case(name)).is_some_and(|ty| {
+                            ty.starts_with("Vec<") || ty.starts_with("Option<") || ty.starts_with("Result<")
+                        }) => "{:?}",
+                Expression::QualifiedName { name, .. } if self.struct_definitions.contains_key(name) => "{:?}",
+                Expression::QualifiedName { path, name } => match builtins::lookup(path, name) {
+                    Some(builtin) if matches!(builtin.return_type, Type::List(_) | Type::Map(..) | Type::Option(_)) => "{:?}",
+                    _ => "{}",
+                },
+                _ => "{}",
+            },
+            _ => "{}",
+        }
+    }
+
+    /// Generate `format!(...)` for an interpolated string literal
+    /// Names bound by `Let` to a String anywhere in `exprs`, added to `names`
+    fn string_bindings(&self, exprs: &[&Expression], mut names: BTreeSet<String>) -> BTreeSet<String> {
+        fn collect(generator: &RustCodeGenerator, expr: &Expression, names: &mut BTreeSet<String>) {
+            match expr {
+                Expression::Let { name, value, .. } if generator.is_string(value, names) => {
+                    names.insert(name.clone());
+                }
+                Expression::LetPattern { pattern: Pattern::Variable(name), value, .. } if generator.is_string(value, names) => {
+                    names.insert(name.clone());
+                }
+                _ => {}
+            }
+            expr.for_each_child(&mut |child| collect(generator, child, names));
+        }
+
+        for expr in exprs {
+            collect(self, expr, &mut names);
+        }
+        names
+    }
+
+    /// The `__w_arithmetic` function `expr`, which applies `operator`, calls,
+    /// unless the configured arithmetic uses Rust's operators. Checked
+    /// arithmetic leaves literals alone to constant checking, and floats,
+    /// which cannot fail.
+    fn arithmetic_function(&self, expr: &Expression, operator: &Operator) -> Option<String> {
+        let name = match operator {
+            Operator::Add => "add",
+            Operator::Subtract => "sub",
+            Operator::Multiply => "mul",
+            Operator::Divide => "div",
+            Operator::Power => "pow",
+            Operator::ShiftLeft => "shl",
+            Operator::ShiftRight => "shr",
+            _ => return None,
+        };
+        match self.options.arithmetic {
+            Arithmetic::Unchecked => None,
+            Arithmetic::Checked => {
+                let is_float = matches!(self.node_type(expr), Some(Type::Float32 | Type::Float64));
+                (!is_float && !is_numeric_literal(expr)).then(|| format!("checked_{}", name))
+            }
+            Arithmetic::Wrapping => Some(format!("wrapping_{}", name)),
+        }
+    }
+
+    /// Generate the body of a closure, where checked arithmetic panics
+    /// rather than return an Err from the enclosing function
+    fn generate_closure_body(&mut self, body: &Expression) -> Result<String, std::fmt::Error> {
+        let enclosing = std::mem::replace(&mut self.in_closure, true);
+        let body_code = self.generate_expression_value(body);
+        self.in_closure = enclosing;
+        body_code
+    }
+
+    /// Does `expr` produce a String: a string literal, a name bound to one, a
+    /// String builtin or function, or a `+` with a String operand?
+    fn is_string(&self, expr: &Expression, strings: &BTreeSet<String>) -> bool {
+        if let Some(ty) = self.node_type(expr) {
+            return *ty == Type::String;
+        }
+        match expr {
+            Expression::String(_) | Expression::InterpolatedString(_) => true,
+            Expression::Identifier(name) => strings.contains(name),
+            Expression::BinaryOp { left, operator: Operator::Add, right } => {
+                self.is_string(left, strings) || self.is_string(right, strings)
+            }
+            Expression::FunctionCall { function, .. } => match function.as_ref() {
+                Expression::Identifier(name) => {
+                    matches!(name.as_str(), "Substring" | "ToUpper" | "ToLower")
+                        || self.function_return_types.get(&to_snake_case(name)).is_some_and(|ty| ty == "String")
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    fn generate_interpolated_string(&mut self, parts: &[StringPart]) -> Result<String, std::fmt::Error> {
+        let mut template = String::new();
+        let mut arguments = Vec::new();
+        self.append_interpolated_parts(parts, &mut template, &mut arguments)?;
+        Ok(format_macro_call("format!", &template, &arguments))
+    }
+
+    /// Concatenate the operands of a chain of String `+`s with one `format!`,
+    /// so no operand is moved and no intermediate String is built
+    fn generate_concatenation(&mut self, expr: &Expression) -> Result<String, std::fmt::Error> {
+        fn operands<'a>(expr: &'a Expression, into: &mut Vec<&'a Expression>) {
+            match expr {
+                Expression::BinaryOp { left, operator: Operator::Add, right } => {
+                    operands(left, into);
+                    operands(right, into);
+                }
+                _ => into.push(expr),
+            }
+        }
+
+        let mut parts = Vec::new();
+        operands(expr, &mut parts);
+        let mut template = String::new();
+        let mut arguments = Vec::new();
+        for part in parts {
+            match part {
+                Expression::String(text) => template.push_str(&escape_format_braces(text)),
+                Expression::InterpolatedString(parts) => self.append_interpolated_parts(parts, &mut template, &mut arguments)?,
+                _ => {
+                    template.push_str("{}");
+                    arguments.push(self.generate_expression_value(part)?);
+                }
+            }
+        }
+        Ok(format_macro_call("format!", &template, &arguments))
+    }
+
+    /// Append interpolated string parts to a `format!` template and its arguments
+    fn append_interpolated_parts(
+        &mut self,
+        parts: &[StringPart],
+        template: &mut String,
+        arguments: &mut Vec<String>,
+    ) -> Result<(), std::fmt::Error> {
+        for part in parts {
+            match part {
+                StringPart::Literal(text) => {
+                    template.push_str(&escape_format_braces(text));
+                }
+                StringPart::Expression { value, format_spec } => {
+                    match format_spec {
+                        Some(spec) => write!(template, "{{:{}}}", spec)?,
+                        None => template.push_str(self.format_placeholder(value)),
+                    }
+                    arguments.push(self.generate_expression_value(value)?);
+                }
+                // Only a Print template fills placeholders
+                StringPart::Placeholder { format_spec } => {
+                    let spec = format_spec.as_ref().map(|spec| format!(":{}", spec)).unwrap_or_default();
+                    template.push_str(&escape_format_braces(&format!("{{{}}}", spec)));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Generate `println!(...)` for `Print[arguments]`, space-separated, or
+    /// `eprintln!(...)` or `print!(...)` for PrintErr and PrintInline.
+    ///
+    /// Literal arguments are folded into the format string and interpolated
+    /// strings are spliced in, so `Print["hi"]` becomes `println!("hi")`
+    /// rather than formatting a freshly allocated `String`.
+    fn generate_print(&mut self, name: &str, arguments: &[Expression]) -> Result<String, std::fmt::Error> {
+        let macro_name = print_macro(name).ok_or(std::fmt::Error)?;
+        if arguments.is_empty() && macro_name != "print!" {
+            return Ok(format!("{}()", macro_name));
+        }
+        if let Some(Expression::InterpolatedString(parts)) = arguments.first() {
+            if parts.iter().any(|part| matches!(part, StringPart::Placeholder { .. })) {
+                return self.generate_print_template(macro_name, parts, &arguments[1..]);
+            }
+        }
+
+        let mut template = String::new();
+        let mut values = Vec::new();
+        for (i, arg) in arguments.iter().enumerate() {
+            if i > 0 {
+                template.push(' ');
+            }
+            match arg {
+                // Formatted here exactly as Rust's Display would at runtime
+                Expression::String(s) => template.push_str(&escape_format_braces(s)),
+                Expression::Number(n, _) => template.push_str(&n.to_string()),
+                Expression::Float(f) => template.push_str(&f.to_string()),
+                Expression::Boolean(b) => template.push_str(&b.to_string()),
+                Expression::InterpolatedString(parts) => {
+                    self.append_interpolated_parts(parts, &mut template, &mut values)?;
+                }
+                other => {
+                    template.push_str(self.format_placeholder(other));
+                    values.push(self.generate_expression_value(other)?);
+                }
+            }
+        }
+        Ok(format_macro_call(macro_name, &template, &values))
+    }
+
+    /// Generate `println!(...)` for `Print["x = {}", x]`, whose placeholders
+    /// take the arguments in order; there must be one argument per placeholder
+    fn generate_print_template(
+        &mut self,
+        macro_name: &str,
+        parts: &[StringPart],
+        arguments: &[Expression],
+    ) -> Result<String, std::fmt::Error> {
+        let mut template = String::new();
+        let mut values = Vec::new();
+        let mut arguments = arguments.iter();
+        for part in parts {
+            match part {
+                StringPart::Placeholder { format_spec } => {
+                    let argument = arguments.next().ok_or(std::fmt::Error)?;
+                    match format_spec {
+                        Some(spec) => write!(template, "{{:{}}}", spec)?,
+                        None => template.push_str(self.format_placeholder(argument)),
+                    }
+                    values.push(self.generate_expression_value(argument)?);
+                }
+                other => self.append_interpolated_parts(std::slice::from_ref(other), &mut template, &mut values)?,
+            }
+        }
+        if arguments.next().is_some() {
+            return Err(std::fmt::Error);
+        }
+        Ok(format_macro_call(macro_name, &template, &values))
+    }
+
+    /// Resolve a qualified name to a Rust path.
+    ///
+    /// Builtins (`Math.Sqrt` → `f64::sqrt`) take priority; otherwise the first
+    /// segment must name a module passed to `generate_modules` that defines
+    /// the item, and the item must be visible from the current module.
+    fn resolve_qualified_name(&self, path: &[String], name: &str) -> Result<String, std::fmt::Error> {
+        if let Some(builtin) = builtins::lookup(path, name) {
+            return Ok(builtin.rust_path.to_string());
+        }
+        let items = path.first().and_then(|module| self.module_items.get(module));
+        match items.and_then(|items| items.get(name)) {
+            // Private items are only reachable from inside their own module
+            Some(Visibility::Private) if self.current_module.as_ref() != path.first() => Err(std::fmt::Error),
+            Some(_) => {
+                let mut segments: Vec<String> = path.iter().map(|segment| to_snake_case(segment)).collect();
+                if self.struct_definitions.contains_key(name) {
+                    segments.push(name.to_string());
+                } else {
+                    segments.push(to_snake_case(name));
+                }
+                Ok(format!("crate::{}", segments.join("::")))
+            }
+            _ => Err(std::fmt::Error),
+        }
+    }
+
+    /// Generate a statement directly inside `main`, honouring the codegen style
+    fn generate_main_statement(&mut self, expr: &Expression) -> Result<(), std::fmt::Error> {
+        match expr {
+            Expression::Let { name, value, body } => {
+                let value_code = self.generate_expression_value(value)?;
+                let annotation = self.binding_annotation(value);
+                writeln!(self.output, "{}let {}{} = {};", self.indent(), to_snake_case(name), annotation, value_code)?;
+                self.generate_main_statement(body)
+            }
+            Expression::LetPattern { pattern, value, fallback } => {
+                self.generate_let_statement(pattern, value, fallback.as_deref(), "return", false)
+            }
+            _ if self.options.style == CodegenStyle::Statement && !is_unit_statement(expr) => {
+                let result_var = self.gensym.fresh("result");
+                let value = self.generate_expression_value(expr)?;
+                writeln!(self.output, "{}let {} = {};", self.indent(), result_var, value)?;
+                Ok(())
+            }
+            _ => self.generate_statement(expr),
+        }
+    }
+
+    /// Write `let pattern = value;`, or for a refutable pattern `let pattern
+    /// = value else { ... };`, whose else branch runs `fallback` and leaves
+    /// the enclosing block with `leave` (`return` or `break 'label`), passing
+    /// it the fallback's value when `with_value`
+    fn generate_let_statement(
+        &mut self,
+        pattern: &Pattern,
+        value: &Expression,
+        fallback: Option<&Expression>,
+        leave: &str,
+        with_value: bool,
+    ) -> Result<(), std::fmt::Error> {
+        // String literal, prefix and suffix patterns become match guards, which `let` cannot express
+        if pattern_has_guard(pattern) {
+            return Err(std::fmt::Error);
+        }
+        let pattern_code = self.generate_pattern(pattern)?;
+        let value_code = self.generate_expression_value(value)?;
+        match fallback {
+            _ if pattern.is_irrefutable_with(&|name| self.enum_variants.contains_key(name)) => {
+                let annotation = match pattern {
+                    Pattern::Variable(_) => self.binding_annotation(value),
+                    _ => String::new(),
+                };
+                writeln!(self.output, "{}let {}{} = {};", self.indent(), pattern_code, annotation, value_code)?;
+            }
+            // A refutable pattern needs a fallback
+            None => return Err(std::fmt::Error),
+            Some(fallback) => {
+                // `let ... else` does not accept an initializer ending in `}`
+                let value_code = if value_code.ends_with('}') { format!("({})", value_code) } else { value_code };
+                writeln!(self.output, "{}let {} = {} else {{", self.indent(), pattern_code, value_code)?;
+                self.indent_level += 1;
+                if with_value {
+                    let fallback_code = self.generate_expression_value(fallback)?;
+                    writeln!(self.output, "{}{} {};", self.indent(), leave, fallback_code)?;
+                } else {
+                    self.generate_statement(fallback)?;
+                    writeln!(self.output, "{}{};", self.indent(), leave)?;
+                }
+                self.indent_level -= 1;
+                writeln!(self.output, "{}}};", self.indent())?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Write the statements of a Block that come before its value, binding
+    /// `Let`s for the rest of the block; see `generate_let_statement` for
+    /// `leave` and `with_value`
+    fn generate_block_statements(&mut self, statements: &[Expression], leave: &str, with_value: bool) -> Result<(), std::fmt::Error> {
+        for statement in statements {
+            match statement {
+                Expression::LetPattern { pattern, value, fallback } => {
+                    self.generate_let_statement(pattern, value, fallback.as_deref(), leave, with_value)?
+                }
+                _ => self.generate_statement(statement)?,
+            }
+        }
+        Ok(())
+    }
+
+    /// A label for a Block that a LetElse fallback must be able to leave,
+    /// with the `break` leaving it
+    fn block_label(&mut self, statements: &[Expression]) -> (Option<String>, String) {
+        let leaves = statements.iter().any(|statement| matches!(statement, Expression::LetPattern { fallback: Some(_), .. }));
+        if !leaves {
+            return (None, String::new());
+        }
+        let label = format!("'{}", self.gensym.fresh("block"));
+        let leave = format!("break {}", label);
+        (Some(label), leave)
+    }
+
+    /// Generate a statement (expression with side effects, like println or assignments)
+    fn generate_statement(&mut self, expr: &Expression) -> Result<(), std::fmt::Error> {
+        match expr {
+            Expression::Let { name, value, body } => {
+                // Statement-level bindings share the enclosing block's scope
+                let value_code = self.generate_expression_value(value)?;
+                writeln!(self.output, "{}let {} = {};", self.indent(), to_snake_case(name), value_code)?;
+                self.generate_statement(body)?;
+            }
+            // The value is a statement too, so the block is `()`
+            Expression::Block(statements) => {
+                let (label, leave) = self.block_label(statements);
+                let label = label.map(|label| format!("{}: ", label)).unwrap_or_default();
+                writeln!(self.output, "{}{}{{", self.indent(), label)?;
+                self.indent_level += 1;
+                self.generate_block_statements(statements, &leave, false)?;
+                self.indent_level -= 1;
+                writeln!(self.output, "{}}}", self.indent())?;
+            }
+            Expression::IfLet { pattern, value, then_branch, else_branch } => {
+                let (pattern_code, value_code) = self.generate_if_let_head(pattern, value)?;
+                writeln!(self.output, "{}if let {} = {} {{", self.indent(), pattern_code, value_code)?;
+                self.indent_level += 1;
+                self.generate_statement(then_branch)?;
+                self.indent_level -= 1;
+                if let Some(else_branch) = else_branch {
+                    writeln!(self.output, "{}}} else {{", self.indent())?;
+                    self.indent_level += 1;
+                    self.generate_statement(else_branch)?;
+                    self.indent_level -= 1;
+                }
+                writeln!(self.output, "{}}}", self.indent())?;
+            }
+            Expression::WhileLet { pattern, value, body } => {
+                let (pattern_code, value_code) = self.generate_if_let_head(pattern, value)?;
+                writeln!(self.output, "{}while let {} = {} {{", self.indent(), pattern_code, value_code)?;
+                self.indent_level += 1;
+                self.generate_statement(body)?;
+                self.indent_level -= 1;
+                writeln!(self.output, "{}}}", self.indent())?;
+            }
+            Expression::For { variable, iterable, body } => {
+                let iterable_code = self.generate_expression_value(iterable)?;
+                writeln!(self.output, "{}for {} in {} {{", self.indent(), to_snake_case(variable), iterable_code)?;
+                self.indent_level += 1;
+                self.generate_statement(body)?;
+                self.indent_level -= 1;
+                writeln!(self.output, "{}}}", self.indent())?;
+            }
+            Expression::FunctionCall { function, arguments } => {
+                match function.as_ref() {
+                    Expression::Identifier(name) if print_macro(name).is_some() => {
+                        let print_call = self.generate_print(name, arguments)?;
+                        writeln!(self.output, "{}{};", self.indent(), print_call)?;
+                    }
+                    Expression::Identifier(name) if name == "Discard" && arguments.len() == 1 => {
+                        let value = self.generate_expression_value(&arguments[0])?;
+                        writeln!(self.output, "{}let _ = {};", self.indent(), value)?;
+                    }
+                    _ => {
+                        // Generic function call
+                        let call_expr = self.generate_expression_value(expr)?;
+                        writeln!(self.output, "{}{};", self.indent(), call_expr)?;
+                    }
+                }
+            }
+            _ => {
+                // For other expressions, generate as value and discard
+                let value = self.generate_expression_value(expr)?;
+                writeln!(self.output, "{}{};", self.indent(), value)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Generate an expression that returns a value (not a statement)
+    fn generate_expression_value(&mut self, expr: &Expression) -> Result<String, std::fmt::Error> {
+        let mut out = String::new();
+        self.write_expression_value(&mut out, expr)?;
+        Ok(out)
+    }
+
+    /// Write the value of `expr` to the end of `out`.
+    ///
+    /// Literals, names, field accesses, tuples, lists and operators, which
+    /// make up most of a large program, are written straight into `out`, so a
+    /// deeply nested arithmetic expression fills one buffer instead of
+    /// formatting a new String at every level. Other expressions come from
+    /// `generate_compound_value` and are appended.
+    fn write_expression_value(&mut self, out: &mut String, expr: &Expression) -> Result<(), std::fmt::Error> {
+        match expr {
+            // Methods borrow `self`, so its fields are cloned out
+            Expression::FieldAccess { object, field } if matches!(object.as_ref(), Expression::Identifier(name) if name == "self") => {
+                write!(out, "self.{}.clone()", to_snake_case(field))
+            }
+            Expression::FieldAccess { object, field } => {
+                self.write_expression_value(out, object)?;
+                write!(out, ".{}", to_snake_case(field))
+            }
+            Expression::Number(n, radix) => {
+                write!(out, "{}", radix.format(*n))?;
+                // Rust takes an unsuffixed literal for an i32 unless its
+                // context decides otherwise, so a larger one names its type
+                if i32::try_from(*n).is_err() {
+                    let ty = self.node_type(expr).filter(|ty| is_integer(ty)).cloned().unwrap_or(Type::Int64);
+                    write!(out, "{}", self.type_to_rust(&ty))?;
+                }
+                Ok(())
+            }
+
+            // Debug formatting always keeps a `.` or exponent, so `2.0` stays a float literal
+            Expression::Float(f) => write!(out, "{:?}", f),
+
+            Expression::String(s) => write!(out, "{}.to_string()", rust_string_literal(s)),
+
+            Expression::Boolean(b) => write!(out, "{}", b),
+
+            Expression::Identifier(name) => {
+                // Variants are qualified with their enum; everything else is snake_case
+                match self.enum_variants.get(name) {
+                    Some((enum_name, _)) => write!(out, "{}::{}", enum_name, name),
+                    None => write!(out, "{}", to_snake_case(name)),
+                }
+            }
+
+            Expression::Tuple(elements) => {
+                // Generate tuple: (elem1, elem2, ...); no elements is the unit type
+                out.push('(');
+                for (i, elem) in elements.iter().enumerate() {
+                    if i > 0 {
+                        out.push_str(", ");
+                    }
+                    self.write_expression_value(out, elem)?;
+                }
+                // Add trailing comma for single-element tuples (Rust requirement)
+                if elements.len() == 1 {
+                    out.push(',');
+                }
+                out.push(')');
+                Ok(())
+            }
+
+            // An empty list names its element type, which Rust cannot
+            // infer from the uses W can
+            Expression::List(elements) if elements.is_empty() => {
+                match self.node_type(expr).and_then(|ty| self.annotation_type(ty)) {
+                    Some(ty) => out.push_str(&format!("{}::new()", ty.replacen('<', "::<", 1))),
+                    None => out.push_str("vec![]"),
+                }
+                Ok(())
+            }
+
+            Expression::List(elements) => {
+                // Generate vec![...]
+                out.push_str("vec![");
+                for (i, elem) in elements.iter().enumerate() {
+                    if i > 0 {
+                        out.push_str(", ");
+                    }
+                    self.write_expression_value(out, elem)?;
+                }
+                out.push(']');
+                Ok(())
+            }
+
+            Expression::BinaryOp { left, operator, right } => {
+                // HashMap has no PartialOrd, so `<` on map literals cannot compile
+                let is_ordering = matches!(operator,
+                    Operator::LessThan | Operator::GreaterThan | Operator::LessEqual | Operator::GreaterEqual);
+                if is_ordering && (matches!(left.as_ref(), Expression::Map(_)) || matches!(right.as_ref(), Expression::Map(_))) {
+                    return Err(std::fmt::Error);
+                }
+                if matches!(operator, Operator::Add) && self.is_string(expr, &self.string_bindings) {
+                    let concatenation = self.generate_concatenation(expr)?;
+                    out.push_str(&concatenation);
+                    return Ok(());
+                }
+
+                if let Some(function) = self.arithmetic_function(expr, operator) {
+                    // Shift amounts and exponents are u32, and `^` computes in i32
+                    let (left_cast, right_cast) = match operator {
+                        Operator::Power => (" as i32", " as u32"),
+                        Operator::ShiftLeft | Operator::ShiftRight => ("", " as u32"),
+                        _ => ("", ""),
+                    };
+                    write!(out, "__w_arithmetic::{}(", function)?;
+                    self.write_expression_value(out, left)?;
+                    write!(out, "{}, ", left_cast)?;
+                    self.write_expression_value(out, right)?;
+                    write!(out, "{})", right_cast)?;
+                    if self.options.arithmetic == Arithmetic::Checked && self.in_function && !self.in_closure {
+                        // The enclosing function returns Result[T, String]
+                        write!(out, ".map_err(|error| format!(\"Arithmetic: {} {{}}\", error))?", symbol(operator))?;
+                    } else if self.options.arithmetic == Arithmetic::Checked {
+                        // Top-level code and closures have no Result to return the failure in
+                        write!(out, ".unwrap_or_else(|error| panic!(\"Arithmetic: {} {{}}\", error))", symbol(operator))?;
+                    }
+                    return Ok(());
+                }
+
+                let symbol = match operator {
+                    Operator::Power => {
+                        // Use pow for integer exponentiation
+                        // Add type suffix to avoid ambiguity
+                        out.push_str("((");
+                        self.write_expression_value(out, left)?;
+                        out.push_str(" as i32).pow(");
+                        self.write_expression_value(out, right)?;
+                        out.push_str(" as u32))");
+                        return Ok(());
+                    }
+                    Operator::Add => "+",
+                    Operator::Subtract => "-",
+                    Operator::Multiply => "*",
+                    Operator::Divide => "/",
+                    Operator::Equals => "==",
+                    Operator::NotEquals => "!=",
+                    Operator::LessThan => "<",
+                    Operator::LessEqual => "<=",
+                    Operator::GreaterEqual => ">=",
+                    Operator::GreaterThan => ">",
+                    Operator::And => "&&",
+                    Operator::Or => "||",
+                    Operator::BitAnd => "&",
+                    Operator::BitOr => "|",
+                    Operator::BitXor => "^",
+                    Operator::ShiftLeft => "<<",
+                    Operator::ShiftRight => ">>",
+                };
+                out.push('(');
+                self.write_expression_value(out, left)?;
+                write!(out, " {} ", symbol)?;
+                self.write_expression_value(out, right)?;
+                out.push(')');
+                Ok(())
+            }
+
+            _ => {
+                let code = self.generate_compound_value(expr)?;
+                out.push_str(&code);
+                Ok(())
+            }
+        }
+    }
+
+    /// Generate an expression not written in place by `write_expression_value`
+    fn generate_compound_value(&mut self, expr: &Expression) -> Result<String, std::fmt::Error> {
+        match expr {
+            Expression::Program(_) => {
+                // Program nodes should not appear in expression contexts
+                Err(std::fmt::Error)
+            }
+            // Destructuring bindings scope over the following statements of `main`
+            Expression::LetPattern { .. } => Err(std::fmt::Error),
+            // Imports are resolved into modules before code generation
+            Expression::Import(_) => Err(std::fmt::Error),
+            // Written in place by `write_expression_value`
+            Expression::FieldAccess { .. } | Expression::Number(..) | Expression::Float(_) | Expression::String(_)
+            | Expression::Boolean(_) | Expression::Identifier(_) | Expression::Tuple(_) | Expression::List(_)
+            | Expression::BinaryOp { .. } => self.generate_expression_value(expr),
+
+            Expression::InterpolatedString(parts) => self.generate_interpolated_string(parts),
+
+            Expression::QualifiedName { path, name } => self.resolve_qualified_name(path, name),
+
+            Expression::Map(entries) => {
+                // Generate HashMap initialization
+                let map_var = self.gensym.fresh("map");
+                let mut result = String::from("{\n");
+                self.indent_level += 1;
+                let map_type = match self.node_type(expr).and_then(|ty| self.annotation_type(ty)) {
+                    Some(ty) if entries.is_empty() => format!(": {}", ty),
+                    _ => String::new(),
+                };
+                result.push_str(&format!("{}let mut {}{} = {}::new();\n", self.indent(), map_var, map_type, self.collection("HashMap")));
+
+                for (key, value) in entries {
+                    let key_val = self.generate_expression_value(key)?;
+                    let value_val = self.generate_expression_value(value)?;
+                    result.push_str(&format!("{}{}.insert({}, {});\n", self.indent(), map_var, key_val, value_val));
+                }
+
+                result.push_str(&format!("{}{}\n", self.indent(), map_var));
+                self.indent_level -= 1;
+                result.push_str(&format!("{}}}", self.indent()));
+                Ok(result)
+            }
+
+            // `xs[i]` indexes xs if type checking found it to be a List
+            Expression::FunctionCall { function, arguments } if arguments.len() == 1 && self.is_indexable(function) => {
+                self.generate_at(function, &arguments[0])
+            }
+
+            Expression::FunctionCall { function, arguments } => {
+                match function.as_ref() {
+                    Expression::Identifier(name) => {
+                        // Check for built-in functions
+                        match name.as_str() {
+                            "Tuple" => {
+                                // Generate tuple from explicit Tuple[...] constructor
+                                if arguments.is_empty() {
+                                    Ok("()".to_string())
+                                } else {
+                                    let mut result = String::from("(");
+                                    for (i, arg) in arguments.iter().enumerate() {
+                                        if i > 0 {
+                                            result.push_str(", ");
+                                        }
+                                        result.push_str(&self.generate_expression_value(arg)?);
+                                    }
+                                    // Add trailing comma for single-element tuples
+                                    if arguments.len() == 1 {
+                                        result.push(',');
+                                    }
+                                    result.push(')');
+                                    Ok(result)
+                                }
+                            }
+                            "Map" => {
+                                // Map[function, list] -> list.into_iter().map(|x| function(x)).collect::<Vec<_>>()
+                                // Over a stream the collect is left to Take
+                                if arguments.len() != 2 {
+                                    return Err(std::fmt::Error);
+                                }
+                                let list = self.generate_expression_value(&arguments[1])?;
+                                let (list, collect) = if is_stream(&arguments[1], &self.stream_bindings) {
+                                    (list, "")
+                                } else {
+                                    (format!("{}.into_iter()", list), ".collect::<Vec<_>>()")
+                                };
+                                // Extract lambda body directly for better code generation
+                                match &arguments[0] {
+                                    Expression::Lambda { parameters, body } => {
+                                        if parameters.len() == 1 {
+                                            let param = &to_snake_case(&parameters[0].name);
+                                            let body_str = self.generate_closure_body(body)?;
+                                            Ok(format!("{}.map(|{}| {}){}",
+                                                list, param, body_str, collect))
+                                        } else {
+                                            Err(std::fmt::Error)
+                                        }
+                                    }
+                                    Expression::Identifier(name) => {
+                                        // Builtins are not Rust functions, so apply the name in a closure
+                                        let param = self.gensym.fresh("x");
+                                        let call = Expression::FunctionCall {
+                                            function: Box::new(Expression::Identifier(name.clone())),
+                                            arguments: vec![Expression::Identifier(param.clone())],
+                                        };
+                                        let body_str = self.generate_expression_value(&call)?;
+                                        Ok(format!("{}.map(|{}| {}){}", list, param, body_str, collect))
+                                    }
+                                    _ => {
+                                        let func = self.generate_expression_value(&arguments[0])?;
+                                        Ok(format!("{}.map({}){}", list, func, collect))
+                                    }
+                                }
+                            }
+                            "Filter" => {
+                                // Filter[predicate, list] -> list.into_iter().filter(|&x| predicate(x)).collect::<Vec<_>>()
+                                // Use pattern matching to get owned values from iterator
+                                if arguments.len() != 2 {
+                                    return Err(std::fmt::Error);
+                                }
+                                let func = self.generate_expression_value(&arguments[0])?;
+                                let list = self.generate_expression_value(&arguments[1])?;
+                                let stream = is_stream(&arguments[1], &self.stream_bindings);
+                                let (list, collect) = if stream {
+                                    (list, "")
+                                } else {
+                                    (format!("{}.into_iter()", list), ".collect::<Vec<_>>()")
+                                };
+                                // Extract parameter name from lambda if possible
+                                match &arguments[0] {
+                                    Expression::Lambda { parameters, body } => {
+                                        if parameters.len() == 1 {
+                                            let param = &to_snake_case(&parameters[0].name);
+                                            let body_str = self.generate_closure_body(body)?;
+                                            if stream {
+                                                // Stream elements (like stdin lines) are often not
+                                                // Copy, so the predicate sees a clone
+                                                return Ok(format!("{list}.filter(|{p}| {{ let {p} = {p}.clone(); {body} }})",
+                                                    list = list, p = param, body = body_str));
+                                            }
+                                            // Use |&param| to pattern match and get owned value
+                                            Ok(format!("{}.filter(|&{}| {}){}",
+                                                list, param, body_str, collect))
+                                        } else {
+                                            Err(std::fmt::Error)
+                                        }
+                                    }
+                                    _ => {
+                                        // For non-lambda functions, use the function directly
+                                        Ok(format!("{}.filter({}){}", list, func, collect))
+                                    }
+                                }
+                            }
+                            "Fold" => {
+                                // Fold[function, init, list] -> list.into_iter().fold(init, |acc, x| function(acc, x))
+                                if arguments.len() != 3 {
+                                    return Err(std::fmt::Error);
+                                }
+                                let init = self.generate_expression_value(&arguments[1])?;
+                                let list = self.generate_expression_value(&arguments[2])?;
+                                // Extract lambda body directly
+                                match &arguments[0] {
+                                    Expression::Lambda { parameters, body } => {
+                                        if parameters.len() == 2 {
+                                            let param1 = &to_snake_case(&parameters[0].name);
+                                            let param2 = &to_snake_case(&parameters[1].name);
+                                            let body_str = self.generate_closure_body(body)?;
+                                            Ok(format!("{}.into_iter().fold({}, |{}, {}| {})",
+                                                list, init, param1, param2, body_str))
+                                        } else {
+                                            Err(std::fmt::Error)
+                                        }
+                                    }
+                                    _ => {
+                                        let func = self.generate_expression_value(&arguments[0])?;
+                                        Ok(format!("{}.into_iter().fold({}, {})", list, init, func))
+                                    }
+                                }
+                            }
+                            "SortWith" => {
+                                // SortWith[cmp, list] -> stable sort_by on the list, ordering
+                                // each pair by the sign of cmp's Int32 result. The closure gets
+                                // references, so the lambda's parameters are rebound to clones.
+                                if arguments.len() != 2 {
+                                    return Err(std::fmt::Error);
+                                }
+                                let list = self.generate_expression_value(&arguments[1])?;
+                                let sorted = self.gensym.fresh("sorted");
+                                let comparator = match &arguments[0] {
+                                    Expression::Lambda { parameters, body } => {
+                                        if parameters.len() != 2 {
+                                            return Err(std::fmt::Error);
+                                        }
+                                        let a = to_snake_case(&parameters[0].name);
+                                        let b = to_snake_case(&parameters[1].name);
+                                        let body_str = self.generate_closure_body(body)?;
+                                        format!("|{a}, {b}| {{ let ({a}, {b}) = ({a}.clone(), {b}.clone()); ({body}).cmp(&0) }}",
+                                            a = a, b = b, body = body_str)
+                                    }
+                                    _ => {
+                                        let func = self.generate_expression_value(&arguments[0])?;
+                                        let a = self.gensym.fresh("a");
+                                        let b = self.gensym.fresh("b");
+                                        format!("|{a}, {b}| {func}({a}.clone(), {b}.clone()).cmp(&0)", a = a, b = b, func = func)
+                                    }
+                                };
+                                Ok(format!("{{ let mut {s} = {list}; {s}.sort_by({cmp}); {s} }}",
+                                    s = sorted, list = list, cmp = comparator))
+                            }
+                            // Streams are lazy iterators; only Take collects them
+                            "Repeat" => {
+                                if arguments.len() != 1 {
+                                    return Err(std::fmt::Error);
+                                }
+                                let value = self.generate_expression_value(&arguments[0])?;
+                                Ok(format!("{}::iter::repeat({})", self.core_crate(), value))
+                            }
+                            "Iterate" => {
+                                // Iterate[f, seed] -> seed, f[seed], f[f[seed]], ... Each element is
+                                // computed only when requested, so Take[n, ...] applies f n - 1 times.
+                                if arguments.len() != 2 {
+                                    return Err(std::fmt::Error);
+                                }
+                                let seed = self.generate_expression_value(&arguments[1])?;
+                                let (param, step) = match &arguments[0] {
+                                    Expression::Lambda { parameters, body } => {
+                                        if parameters.len() != 1 {
+                                            return Err(std::fmt::Error);
+                                        }
+                                        (to_snake_case(&parameters[0].name), self.generate_closure_body(body)?)
+                                    }
+                                    _ => {
+                                        let func = self.generate_expression_value(&arguments[0])?;
+                                        let param = self.gensym.fresh("previous");
+                                        let step = format!("{}({})", func, param);
+                                        (param, step)
+                                    }
+                                };
+                                let next = self.gensym.fresh("next");
+                                Ok(format!(
+                                    "{{ let mut {next} = None; {core}::iter::from_fn(move || {{ {next} = Some(match {next}.take() {{ None => {seed}, Some({param}) => {step} }}); {next}.clone() }}) }}",
+                                    core = self.core_crate(), next = next, seed = seed, param = param, step = step))
+                            }
+                            "Lines" => {
+                                // Lines[] -> stdin lines, stopping at EOF or the first read error
+                                if !arguments.is_empty() {
+                                    return Err(std::fmt::Error);
+                                }
+                                Ok("std::io::BufRead::lines(std::io::stdin().lock()).map_while(Result::ok)".to_string())
+                            }
+                            "Take" => {
+                                // Take[n, stream] -> the first n elements as a Vec
+                                if arguments.len() != 2 {
+                                    return Err(std::fmt::Error);
+                                }
+                                let count = self.generate_expression_value(&arguments[0])?;
+                                let stream = self.generate_expression_value(&arguments[1])?;
+                                let stream = if is_stream(&arguments[1], &self.stream_bindings) {
+                                    stream
+                                } else {
+                                    format!("{}.into_iter()", stream)
+                                };
+                                Ok(format!("{}.take({} as usize).collect::<Vec<_>>()", stream, count))
+                            }
+                            "Range" => {
+                                // Range[min, max] -> (min..=max).collect::<Vec<_>>()
+                                if arguments.len() != 2 {
+                                    return Err(std::fmt::Error);
+                                }
+                                let min = self.generate_expression_value(&arguments[0])?;
+                                let max = self.generate_expression_value(&arguments[1])?;
+                                Ok(format!("({}..={}).collect::<Vec<_>>()", min, max))
+                            }
+                            // String builtins count and slice by char, never by byte
+                            "CharCount" => {
+                                if arguments.len() != 1 {
+                                    return Err(std::fmt::Error);
+                                }
+                                let s = self.generate_expression_value(&arguments[0])?;
+                                Ok(format!("({}.chars().count() as i32)", s))
+                            }
+                            "Substring" => {
+                                // Substring[s, start, len] -> chars in [start, start + len), clamped to the end
+                                if arguments.len() != 3 {
+                                    return Err(std::fmt::Error);
+                                }
+                                let s = self.generate_expression_value(&arguments[0])?;
+                                let start = self.generate_expression_value(&arguments[1])?;
+                                let len = self.generate_expression_value(&arguments[2])?;
+                                Ok(format!("{}.chars().skip({} as usize).take({} as usize).collect::<String>()",
+                                    s, start, len))
+                            }
+                            "ToUpper" | "ToLower" => {
+                                if arguments.len() != 1 {
+                                    return Err(std::fmt::Error);
+                                }
+                                let s = self.generate_expression_value(&arguments[0])?;
+                                let method = if name == "ToUpper" { "to_uppercase" } else { "to_lowercase" };
+                                Ok(format!("{}.{}()", s, method))
+                            }
+                            "Discard" => {
+                                // Discard[x] in value position is a block evaluating to ()
+                                if arguments.len() != 1 {
+                                    return Err(std::fmt::Error);
+                                }
+                                let value = self.generate_expression_value(&arguments[0])?;
+                                Ok(format!("{{ let _ = {}; }}", value))
+                            }
+                            "Panic" => {
+                                if arguments.len() != 1 {
+                                    return Err(std::fmt::Error);
+                                }
+                                // A literal message is used as is, without allocating a String
+                                let message = match &arguments[0] {
+                                    Expression::String(message) => rust_string_literal(message),
+                                    other => self.generate_expression_value(other)?,
+                                };
+                                Ok(format!("panic!(\"{{}}\", {})", message))
+                            }
+                            "Exit" => {
+                                if arguments.len() != 1 {
+                                    return Err(std::fmt::Error);
+                                }
+                                let code = self.generate_expression_value(&arguments[0])?;
+                                Ok(format!("std::process::exit({})", code))
+                            }
+                            "Chars" => {
+                                if arguments.len() != 1 {
+                                    return Err(std::fmt::Error);
+                                }
+                                let s = self.generate_expression_value(&arguments[0])?;
+                                Ok(format!("{}.chars().collect::<Vec<char>>()", s))
+                            }
+                            "Bytes" => {
+                                // Bytes[0x01, 0x02] -> vec![0x01u8, 0x02u8]
+                                let mut bytes = Vec::new();
+                                for arg in arguments {
+                                    match arg {
+                                        Expression::Number(n, _) => {
+                                            let byte = u8::try_from(*n).map_err(|_| std::fmt::Error)?;
+                                            bytes.push(format!("{:#04x}u8", byte));
+                                        }
+                                        _ => bytes.push(self.generate_expression_value(arg)?),
+                                    }
+                                }
+                                Ok(format!("vec![{}]", bytes.join(", ")))
+                            }
+                            "ReadFileBytes" => {
+                                // I/O errors become Strings so `?` works in W functions returning Result
+                                if arguments.len() != 1 {
+                                    return Err(std::fmt::Error);
+                                }
+                                let path = self.generate_expression_value(&arguments[0])?;
+                                Ok(format!("std::fs::read({}).map_err(|e| e.to_string())", path))
+                            }
+                            "At" => {
+                                if arguments.len() != 2 {
+                                    return Err(std::fmt::Error);
+                                }
+                                self.generate_at(&arguments[0], &arguments[1])
+                            }
+                            "Lookup" => {
+                                // Lookup[map, key] -> Option<V>, None when the key is absent
+                                if arguments.len() != 2 {
+                                    return Err(std::fmt::Error);
+                                }
+                                let map = self.generate_expression_value(&arguments[0])?;
+                                let key = self.generate_expression_value(&arguments[1])?;
+                                Ok(format!("{}.get(&{}).cloned()", map, key))
+                            }
+                            "Stack" | "Queue" => {
+                                // Stack[a, b] -> vec![a, b]; Queue[a, b] -> VecDeque::from(vec![a, b])
+                                let mut elements = Vec::new();
+                                for arg in arguments {
+                                    elements.push(self.generate_expression_value(arg)?);
+                                }
+                                Ok(match (name.as_str(), elements.is_empty()) {
+                                    ("Stack", true) => "Vec::new()".to_string(),
+                                    ("Stack", false) => format!("vec![{}]", elements.join(", ")),
+                                    (_, true) => format!("{}::new()", self.collection("VecDeque")),
+                                    (_, false) => format!("{}::from(vec![{}])", self.collection("VecDeque"), elements.join(", ")),
+                                })
+                            }
+                            "Heap" => {
+                                // Heap[a, b] -> BinaryHeap::from(vec![a, b])
+                                let mut elements = Vec::new();
+                                for arg in arguments {
+                                    elements.push(self.generate_expression_value(arg)?);
+                                }
+                                Ok(if elements.is_empty() {
+                                    format!("{}::new()", self.collection("BinaryHeap"))
+                                } else {
+                                    format!("{}::from(vec![{}])", self.collection("BinaryHeap"), elements.join(", "))
+                                })
+                            }
+                            "HeapPush" => {
+                                if arguments.len() != 2 {
+                                    return Err(std::fmt::Error);
+                                }
+                                let heap = self.generate_expression_value(&arguments[0])?;
+                                let value = self.generate_expression_value(&arguments[1])?;
+                                Ok(format!("{}.push({})", heap, value))
+                            }
+                            "HeapPop" => {
+                                if arguments.len() != 1 {
+                                    return Err(std::fmt::Error);
+                                }
+                                let heap = self.generate_expression_value(&arguments[0])?;
+                                Ok(format!("{}.pop()", heap))
+                            }
+                            "Push" => {
+                                // Push[c, x] appends in place; `extend` is shared by Vec and
+                                // VecDeque, so the collection's type is not needed here
+                                if arguments.len() != 2 {
+                                    return Err(std::fmt::Error);
+                                }
+                                let collection = self.generate_expression_value(&arguments[0])?;
+                                let value = self.generate_expression_value(&arguments[1])?;
+                                Ok(format!("{}.extend([{}])", collection, value))
+                            }
+                            "Pop" | "PopFront" => {
+                                // Pop[stack] -> stack.pop(); PopFront[queue] -> queue.pop_front()
+                                if arguments.len() != 1 {
+                                    return Err(std::fmt::Error);
+                                }
+                                let collection = self.generate_expression_value(&arguments[0])?;
+                                let method = if name == "Pop" { "pop" } else { "pop_front" };
+                                Ok(format!("{}.{}()", collection, method))
+                            }
+                            "Print" | "PrintErr" | "PrintInline" => {
+                                // Print returns (), so we generate a block
+                                let mut result = String::from("{\n");
+                                self.indent_level += 1;
+
+                                let print_call = self.generate_print(name, arguments)?;
+                                writeln!(&mut result, "{}{};", self.indent(), print_call)?;
+
+                                self.indent_level -= 1;
+                                result.push_str(&format!("{}}}", self.indent()));
+                                Ok(result)
+                            }
+                            _ if self.enum_variants.contains_key(name) => {
+                                // Enum variant constructor: Enum::Variant(value1, value2)
+                                let (enum_name, arity) = self.enum_variants[name].clone();
+                                if arity != arguments.len() {
+                                    return Err(std::fmt::Error);
+                                }
+                                let mut values = Vec::new();
+                                for arg in arguments {
+                                    values.push(self.generate_expression_value(arg)?);
+                                }
+                                Ok(format!("{}::{}({})", enum_name, name, values.join(", ")))
+                            }
+                            _ => {
+                                // Check if this is a struct constructor
+                                if let Some(field_names) = self.struct_definitions.get(name).cloned() {
+                                    // Generate struct instantiation: StructName { field1: value1, field2: value2 }
+                                    if field_names.len() != arguments.len() {
+                                        return Err(std::fmt::Error);
+                                    }
+
+                                    let mut result = format!("{} {{ ", name);
+                                    for (i, (field_name, arg)) in field_names.iter().zip(arguments.iter()).enumerate() {
+                                        if i > 0 {
+                                            result.push_str(", ");
+                                        }
+                                        let arg_val = self.generate_expression_value(arg)?;
+                                        result.push_str(&format!("{}: {}", field_name, arg_val));
+                                    }
+                                    result.push_str(" }");
+                                    Ok(result)
+                                } else if self.trait_method(name).is_some() {
+                                    // Trait method: Method[receiver, args...] -> receiver.method(args...)
+                                    let (receiver, rest) = arguments.split_first().ok_or(std::fmt::Error)?;
+                                    let receiver = self.generate_expression_value(receiver)?;
+                                    let mut values = Vec::new();
+                                    for arg in rest {
+                                        values.push(self.generate_expression_value(arg)?);
+                                    }
+                                    Ok(format!("{}.{}({})", receiver, to_snake_case(name), values.join(", ")))
+                                } else {
+                                    // Generic function call; parameters left out take their defaults
+                                    let func_name = to_snake_case(name);
+                                    let mut result = format!("{}(", func_name);
+                                    let defaults: Vec<TypeAnnotation> = self.parameter_defaults.get(name)
+                                        .map(|parameters| parameters.iter().skip(arguments.len()).cloned().collect())
+                                        .unwrap_or_default();
+
+                                    let mut values: Vec<&Expression> = arguments.iter().collect();
+                                    for param in &defaults {
+                                        values.push(param.default.as_deref().ok_or(std::fmt::Error)?);
+                                    }
+                                    for (i, arg) in values.into_iter().enumerate() {
+                                        if i > 0 {
+                                            result.push_str(", ");
+                                        }
+                                        result.push_str(&self.generate_expression_value(arg)?);
+                                    }
+
+                                    result.push(')');
+                                    Ok(result)
+                                }
+                            }
+                        }
+                    }
+                    Expression::QualifiedName { path, name } => {
+                        let rust_path = self.resolve_qualified_name(path, name)?;
+                        let builtin = builtins::lookup(path, name);
+                        if builtin.is_some_and(|b| b.arity() != arguments.len()) {
+                            return Err(std::fmt::Error);
+                        }
+
+                        let parameter_types = builtin.map(|b| b.parameter_types.as_slice()).unwrap_or(&[]);
+                        let mut argument_values = Vec::new();
+                        for (i, arg) in arguments.iter().enumerate() {
+                            match (arg, parameter_types.get(i)) {
+                                // Integer literals become float literals for f64 parameters
+                                (Expression::Number(n, _), Some(Type::Float64)) => {
+                                    argument_values.push(format!("{:?}", *n as f64));
+                                }
+                                // Collections are lent to support modules, not moved
+                                (_, Some(Type::List(_) | Type::Map(..))) => {
+                                    argument_values.push(format!("&{}", self.generate_expression_value(arg)?));
+                                }
+                                _ => argument_values.push(self.generate_expression_value(arg)?),
+                            }
+                        }
+
+                        // Struct constructors from another module
+                        if let Some(field_names) = self.struct_definitions.get(name).cloned() {
+                            if builtin.is_none() {
+                                if field_names.len() != arguments.len() {
+                                    return Err(std::fmt::Error);
+                                }
+                                let fields: Vec<String> = field_names.iter().zip(argument_values)
+                                    .map(|(field_name, value)| format!("{}: {}", field_name, value))
+                                    .collect();
+                                return Ok(format!("{} {{ {} }}", rust_path, fields.join(", ")));
+                            }
+                        }
+
+                        Ok(format!("{}({})", rust_path, argument_values.join(", ")))
+                    }
+                    _ => Ok("/* unsupported function call */".to_string()),
+                }
+            }
+
+            Expression::Cond { conditions, default_statements, .. } => {
+                // Generate if-else chain
+                let mut result = String::new();
+
+                for (i, (condition, statements)) in conditions.iter().enumerate() {
+                    if i > 0 {
+                        result.push_str(" else ");
+                    }
+
+                    // W has no truthiness: `if 42 {` would not compile, so reject
+                    // conditions that are evidently not bool
+                    if !is_possibly_bool(condition) {
+                        return Err(std::fmt::Error);
+                    }
+                    let cond_val = self.generate_expression_value(condition)?;
+                    writeln!(&mut result, "if {} {{", cond_val)?;
+
+                    self.indent_level += 1;
+                    let stmt_val = self.generate_expression_value(statements)?;
+                    writeln!(&mut result, "{}{}", self.indent(), stmt_val)?;
+                    self.indent_level -= 1;
+
+                    write!(&mut result, "{}}}", self.indent())?;
+                }
+
+                // Generate default case if present
+                if let Some(default_expr) = default_statements {
+                    writeln!(&mut result, " else {{")?;
+                    self.indent_level += 1;
+                    let default_val = self.generate_expression_value(default_expr)?;
+                    writeln!(&mut result, "{}{}", self.indent(), default_val)?;
+                    self.indent_level -= 1;
+                    write!(&mut result, "{}}}", self.indent())?;
+                }
+
+                Ok(result)
+            }
+
+            Expression::LogCall { level, message } => {
+                let function = match level {
+                    LogLevel::Debug => "debug",
+                    LogLevel::Info => "info",
+                    LogLevel::Warn => "warn",
+                    LogLevel::Error => "error",
+                };
+
+                // The message is an argument, never a format string
+                let message_val = self.generate_expression_value(message)?;
+                Ok(format!("__w_log::{}({})", function, message_val))
+            }
+
+            Expression::FunctionDefinition { .. } => {
+                Ok("/* function definitions not supported as values */".to_string())
+            }
+
+            // Error handling expressions (Rust's safety model)
+            Expression::None => Ok("None".to_string()),
+
+            Expression::Some { value } => {
+                let value_str = self.generate_expression_value(value)?;
+                Ok(format!("Some({})", value_str))
+            }
+
+            Expression::Ok { value } => {
+                let value_str = self.generate_expression_value(value)?;
+                Ok(format!("Ok({})", value_str))
+            }
+
+            Expression::Err { error } => {
+                let error_str = self.generate_expression_value(error)?;
+                Ok(format!("Err({})", error_str))
+            }
+
+            Expression::IfLet { pattern, value, then_branch, else_branch } => {
+                let (pattern_str, value_str) = self.generate_if_let_head(pattern, value)?;
+                let then_str = self.generate_expression_value(then_branch)?;
+                match else_branch {
+                    Some(else_branch) => {
+                        let else_str = self.generate_expression_value(else_branch)?;
+                        Ok(format!("if let {} = {} {{\n{}{}\n}} else {{\n{}{}\n}}",
+                            pattern_str, value_str, self.indent_unit(), then_str, self.indent_unit(), else_str))
+                    }
+                    // Without an else branch the result is discarded
+                    None => Ok(format!("if let {} = {} {{\n{}{};\n}}",
+                        pattern_str, value_str, self.indent_unit(), then_str)),
+                }
+            }
+
+            Expression::WhileLet { pattern, value, body } => {
+                let (pattern_str, value_str) = self.generate_if_let_head(pattern, value)?;
+                let body_str = self.generate_expression_value(body)?;
+                Ok(format!("while let {} = {} {{\n{}{};\n}}",
+                    pattern_str, value_str, self.indent_unit(), body_str))
+            }
+
+            Expression::For { variable, iterable, body } => {
+                let iterable_str = self.generate_expression_value(iterable)?;
+                let body_str = self.generate_expression_value(body)?;
+                Ok(format!("for {} in {} {{\n{}{};\n}}",
+                    to_snake_case(variable), iterable_str, self.indent_unit(), body_str))
+            }
+
+            Expression::Match { value, arms } => {
+                let value_str = self.generate_expression_value(value)?;
+                // Rust only has slice patterns, so list arms match the Vec as a
+                // slice and each arm takes owned copies of what it binds
+                let on_slice = arms.iter().any(|(pattern, _)| is_list_pattern(pattern));
+                let mut result = match on_slice {
+                    true if is_rust_identifier(&value_str) => format!("match {}.as_slice() {{\n", value_str),
+                    true => format!("match ({}).as_slice() {{\n", value_str),
+                    false => format!("match {} {{\n", value_str),
+                };
+
+                for (pattern, expr) in arms {
+                    let pattern_str = self.generate_pattern(pattern)?;
+                    let mut expr_str = self.generate_expression_value(expr)?;
+                    if on_slice {
+                        let mut owned = Vec::new();
+                        self.slice_bindings(pattern, true, &mut owned);
+                        if !owned.is_empty() {
+                            expr_str = format!("{{ {} {} }}", owned.join(" "), expr_str);
+                        }
+                    }
+                    result.push_str(&format!("{}{} => {},\n", self.indent_unit(), pattern_str, expr_str));
+                }
+
+                result.push('}');
+                Ok(result)
+            }
+
+            Expression::Lambda { parameters, body } => {
+                // Generate Rust closure: |param1, param2, ...| body
+                let mut result = String::from("|");
+
+                for (i, param) in parameters.iter().enumerate() {
+                    if i > 0 {
+                        result.push_str(", ");
+                    }
+                    result.push_str(&to_snake_case(&param.name));
+                    // Parameter types are left for rustc to infer from use
+                }
+
+                result.push_str("| ");
+                result.push_str(&self.generate_closure_body(body)?);
+
+                Ok(result)
+            }
+
+            Expression::StructDefinition { .. } | Expression::EnumDefinition { .. }
+            | Expression::TraitDefinition { .. } | Expression::Impl { .. } | Expression::Kernel { .. } => {
+                // Type definitions should not appear in expression contexts
+                Err(std::fmt::Error)
+            }
+
+            Expression::Propagate { expr } => {
+                let inner = self.generate_expression_value(expr)?;
+                Ok(format!("({})?", inner))
+            }
+
+            // `main` returns (), so there is nothing to propagate an Err to
+            Expression::Try { .. } if !self.in_function => Err(std::fmt::Error),
+            Expression::Try { expr } => {
+                let inner = self.generate_expression_value(expr)?;
+                Ok(format!("({})?", inner))
+            }
+
+            Expression::Not { expr } => {
+                let inner = self.generate_expression_value(expr)?;
+                Ok(format!("!{}", inner))
+            }
+
+            // Floats approximate any number; integer targets are range checked
+            // by the `__w_convert` support module
+            Expression::Convert { value, target } => {
+                let value = self.generate_expression_value(value)?;
+                let target = self.type_to_rust(target);
+                if target == "f32" || target == "f64" {
+                    Ok(format!("({} as {})", value, target))
+                } else {
+                    Ok(format!("__w_convert::convert::<{}, _>({})", target, value))
+                }
+            }
+
+            Expression::Let { name, value, body } => {
+                // Generate a block: { let name = value; body }
+                let mut result = String::from("{\n");
+                self.indent_level += 1;
+                let value_str = self.generate_expression_value(value)?;
+                writeln!(&mut result, "{}let {} = {};", self.indent(), to_snake_case(name), value_str)?;
+                let body_str = self.generate_expression_value(body)?;
+                writeln!(&mut result, "{}{}", self.indent(), body_str)?;
+                self.indent_level -= 1;
+                write!(&mut result, "{}}}", self.indent())?;
+                Ok(result)
+            }
+
+            Expression::Block(statements) => {
+                let (statements, value) = split_block(statements);
+                let (label, leave) = self.block_label(statements);
+                let label = label.map(|label| format!("{}: ", label)).unwrap_or_default();
+                // Statements are written to the output, so the block is
+                // written to a fresh one and taken back
+                let enclosing = std::mem::take(&mut self.output);
+                self.indent_level += 1;
+                let written = self.generate_block_statements(statements, &leave, true).and_then(|()| match value {
+                    Some(value) => {
+                        let value_code = self.generate_expression_value(value)?;
+                        writeln!(self.output, "{}{}", self.indent(), value_code)
+                    }
+                    None => Ok(()),
+                });
+                self.indent_level -= 1;
+                let block = std::mem::replace(&mut self.output, enclosing);
+                written?;
+                Ok(format!("{}{{\n{}{}}}", label, block, self.indent()))
+            }
+
+            Expression::StructInstantiation { struct_name, field_values } => {
+                // Generate: StructName { field1: value1, field2: value2 }
+                // Look up the field names from the struct definition
+                let field_names = self.struct_definitions.get(struct_name)
+                    .cloned()
+                    .ok_or(std::fmt::Error)?;
+
+                if field_names.len() != field_values.len() {
+                    // Mismatch between number of fields and values
+                    return Err(std::fmt::Error);
+                }
+
+                let mut result = format!("{} {{ ", struct_name);
+
+                // Generate field: value pairs
+                for (i, (field_name, value)) in field_names.iter().zip(field_values.iter()).enumerate() {
+                    if i > 0 {
+                        result.push_str(", ");
+                    }
+                    let value_str = self.generate_expression_value(value)?;
+                    result.push_str(&format!("{}: {}", field_name, value_str));
+                }
+
+                result.push_str(" }");
+                Ok(result)
+            }
+        }
+    }
+
+    /// Generate `Point { x, y: _, .. }` from `Point[x, _, ..]`: sub-patterns
+    /// match fields in declaration order, and a trailing `..` skips the rest
+    fn generate_struct_pattern(&self, name: &str, patterns: &[Pattern]) -> Result<String, std::fmt::Error> {
+        let field_names = &self.struct_definitions[name];
+        let (patterns, has_rest) = match patterns.split_last() {
+            Some((Pattern::Rest, init)) => (init, true),
+            _ => (patterns, false),
+        };
+        // Every field must be covered unless the pattern ends in `..`
+        let count_ok = if has_rest { patterns.len() <= field_names.len() } else { patterns.len() == field_names.len() };
+        if !count_ok || patterns.contains(&Pattern::Rest) {
+            return Err(std::fmt::Error);
+        }
+
+        let mut fields = Vec::new();
+        for (field_name, pattern) in field_names.iter().zip(patterns) {
+            let pattern = self.generate_pattern(pattern)?;
+            if &pattern == field_name {
+                fields.push(pattern);
+            } else {
+                fields.push(format!("{}: {}", field_name, pattern));
+            }
+        }
+        if has_rest {
+            fields.push("..".to_string());
+        }
+        if fields.is_empty() {
+            return Ok(format!("{} {{}}", name));
+        }
+        Ok(format!("{} {{ {} }}", name, fields.join(", ")))
+    }
+
+    /// `Circle[r]` -> `Shape::Circle(r)`; a trailing `..` skips the remaining payload
+    fn generate_variant_pattern(&self, name: &str, patterns: &[Pattern]) -> Result<String, std::fmt::Error> {
+        let (enum_name, arity) = &self.enum_variants[name];
+        let has_rest = patterns.last() == Some(&Pattern::Rest);
+        let fixed = patterns.len() - usize::from(has_rest);
+        if fixed > *arity || (!has_rest && fixed != *arity) || patterns[..fixed].contains(&Pattern::Rest) {
+            return Err(std::fmt::Error);
+        }
+        if *arity == 0 {
+            return Ok(format!("{}::{}", enum_name, name));
+        }
+        let mut parts = Vec::new();
+        for pattern in patterns {
+            parts.push(self.generate_pattern(pattern)?);
+        }
+        Ok(format!("{}::{}({})", enum_name, name, parts.join(", ")))
+    }
+
+    /// `let` statements turning what `pattern` binds in a match on a slice into
+    /// owned values: the whole slice (`whole`, or a `..` rest) with `to_vec`,
+    /// elements with `clone`
+    fn slice_bindings(&self, pattern: &Pattern, whole: bool, out: &mut Vec<String>) {
+        let owned = |name: &str, whole: bool| {
+            let name = to_snake_case(name);
+            match whole {
+                true => format!("let {} = {}.to_vec();", name, name),
+                false => format!("let {} = {}.clone();", name, name),
+            }
+        };
+        match pattern {
+            Pattern::Variable(name) if !self.enum_variants.contains_key(name) => out.push(owned(name, whole)),
+            Pattern::Binding { name, pattern } => {
+                out.push(owned(name, whole || **pattern == Pattern::Rest));
+                self.slice_bindings(pattern, whole, out);
+            }
+            Pattern::List(patterns) => {
+                for pattern in patterns {
+                    self.slice_bindings(pattern, false, out);
+                }
+            }
+            Pattern::Constructor { patterns, .. } | Pattern::Tuple(patterns) => {
+                for pattern in patterns {
+                    self.slice_bindings(pattern, whole, out);
+                }
+            }
+            // Every alternative binds the same names
+            Pattern::Or(alternatives) => {
+                if let Some(first) = alternatives.first() {
+                    self.slice_bindings(first, whole, out);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Pattern and scrutinee of an `if let` or `while let`
+    fn generate_if_let_head(&mut self, pattern: &Pattern, value: &Expression) -> Result<(String, String), std::fmt::Error> {
+        // String literal, prefix and suffix patterns become match guards, which `if let`/`while let` cannot express
+        if pattern_has_guard(pattern) {
+            return Err(std::fmt::Error);
+        }
+        Ok((self.generate_pattern(pattern)?, self.generate_expression_value(value)?))
+    }
+
+    /// Generate Rust pattern syntax from Pattern AST
+    fn generate_pattern(&self, pattern: &Pattern) -> Result<String, std::fmt::Error> {
+        match pattern {
+            Pattern::Wildcard => Ok("_".to_string()),
+
+            Pattern::Literal(expr) => {
+                match expr.as_ref() {
+                    Expression::Number(n, radix) => Ok(radix.format(*n)),
+                    // String patterns match against &str in Rust
+                    Expression::String(s) => Ok(format!("s if s == {}", rust_string_literal(s))),
+                    Expression::Boolean(b) => Ok(b.to_string()),
+                    _ => Err(std::fmt::Error),
+                }
+            }
+
+            // Like string literals, prefixes and suffixes are tested in a guard
+            Pattern::StartsWith(prefix) => Ok(format!("s if s.starts_with({})", rust_string_literal(prefix))),
+            Pattern::EndsWith(suffix) => Ok(format!("s if s.ends_with({})", rust_string_literal(suffix))),
+
+            // A bare unit variant matches that variant rather than binding a name
+            Pattern::Variable(name) => match self.enum_variants.get(name) {
+                Some((enum_name, 0)) => Ok(format!("{}::{}", enum_name, name)),
+                Some(_) => Err(std::fmt::Error),
+                None if self.mutable_bindings.contains(name) => Ok(format!("mut {}", to_snake_case(name))),
+                None => Ok(to_snake_case(name)),
+            },
+
+            Pattern::Constructor { name, patterns } => {
+                match name.as_str() {
+                    "Some" => {
+                        if patterns.len() == 1 {
+                            let inner = self.generate_pattern(&patterns[0])?;
+                            Ok(format!("Some({})", inner))
+                        } else {
+                            Err(std::fmt::Error)
+                        }
+                    }
+                    "None" => Ok("None".to_string()),
+                    "Ok" => {
+                        if patterns.len() == 1 {
+                            let inner = self.generate_pattern(&patterns[0])?;
+                            Ok(format!("Ok({})", inner))
+                        } else {
+                            Err(std::fmt::Error)
+                        }
+                    }
+                    "Err" => {
+                        if patterns.len() == 1 {
+                            let inner = self.generate_pattern(&patterns[0])?;
+                            Ok(format!("Err({})", inner))
+                        } else {
+                            Err(std::fmt::Error)
+                        }
+                    }
+                    _ if self.enum_variants.contains_key(name) => self.generate_variant_pattern(name, patterns),
+                    _ if self.struct_definitions.contains_key(name) => self.generate_struct_pattern(name, patterns),
+                    _ => {
+                        // Generic constructor - could be custom type
+                        let mut result = format!("{}(", name);
+                        for (i, p) in patterns.iter().enumerate() {
+                            if i > 0 {
+                                result.push_str(", ");
+                            }
+                            result.push_str(&self.generate_pattern(p)?);
+                        }
+                        result.push(')');
+                        Ok(result)
+                    }
+                }
+            }
+
+            Pattern::Tuple(patterns) => {
+                if patterns.is_empty() {
+                    Ok("()".to_string())
+                } else {
+                    let mut result = String::from("(");
+                    for (i, p) in patterns.iter().enumerate() {
+                        if i > 0 {
+                            result.push_str(", ");
+                        }
+                        result.push_str(&self.generate_pattern(p)?);
+                    }
+                    // Add trailing comma for single-element tuples
+                    if patterns.len() == 1 {
+                        result.push(',');
+                    }
+                    result.push(')');
+                    Ok(result)
+                }
+            }
+
+            Pattern::Rest => Ok("..".to_string()),
+
+            Pattern::List(patterns) => {
+                // In Rust, list patterns are represented as slices
+                let mut result = String::from("[");
+                for (i, p) in patterns.iter().enumerate() {
+                    if i > 0 {
+                        result.push_str(", ");
+                    }
+                    result.push_str(&self.generate_pattern(p)?);
+                }
+                result.push(']');
+                Ok(result)
+            }
+
+            // String alternatives are all guards, so their conditions are
+            // or-ed in a single guard; guards cannot be mixed with plain patterns
+            Pattern::Or(alternatives) if alternatives.iter().all(is_string_test) => {
+                let conditions = alternatives
+                    .iter()
+                    .map(|p| Ok(self.generate_pattern(p)?.trim_start_matches("s if ").to_string()))
+                    .collect::<Result<Vec<_>, std::fmt::Error>>()?;
+                Ok(format!("s if {}", conditions.join(" || ")))
+            }
+            // Guards test a fresh `s`, which cannot also be bound with `@`
+            Pattern::Binding { pattern, .. } if pattern_has_guard(pattern) => Err(std::fmt::Error),
+            Pattern::Binding { name, pattern } => {
+                let binding = if self.mutable_bindings.contains(name) {
+                    format!("mut {}", to_snake_case(name))
+                } else {
+                    to_snake_case(name)
+                };
+                match pattern.as_ref() {
+                    Pattern::Or(_) => Ok(format!("{} @ ({})", binding, self.generate_pattern(pattern)?)),
+                    _ => Ok(format!("{} @ {}", binding, self.generate_pattern(pattern)?)),
+                }
+            }
+            Pattern::Or(alternatives) => {
+                if alternatives.iter().any(pattern_has_guard) {
+                    return Err(std::fmt::Error);
+                }
+                let alternatives = alternatives.iter().map(|p| self.generate_pattern(p)).collect::<Result<Vec<_>, _>>()?;
+                Ok(alternatives.join(" | "))
+            }
+        }
+    }
+}
+
+/// The macro the print builtin `name` generates, if it is one
+fn print_macro(name: &str) -> Option<&'static str> {
+    match name {
+        "Print" => Some("println!"),
+        "PrintErr" => Some("eprintln!"),
+        "PrintInline" => Some("print!"),
+        _ => None,
+    }
+}
+
+/// `name!("template", arg1, arg2, ...)`
+fn format_macro_call(name: &str, template: &str, arguments: &[String]) -> String {
+    let mut call = format!("{}({}", name, rust_string_literal(template));
+    for argument in arguments {
+        call.push_str(", ");
+        call.push_str(argument);
+    }
+    call.push(')');
+    call
+}
+
+/// The top-level expressions of a program
+fn top_level(program: &Expression) -> &[Expression] {
+    match program {
+        Expression::Program(expressions) => expressions,
+        other => std::slice::from_ref(other),
+    }
+}
+
+/// Visibility of every function and struct defined by a module's program
+fn definition_visibilities(program: &Expression) -> BTreeMap<String, Visibility> {
+    let items = match program {
+        Expression::Program(expressions) => expressions.as_slice(),
+        other => std::slice::from_ref(other),
+    };
+    items.iter()
+        .map(|item| match item {
+            Expression::Kernel { definition } => definition.as_ref(),
+            other => other,
+        })
+        .filter_map(|item| match item {
+            Expression::FunctionDefinition { name, visibility, .. }
+            | Expression::StructDefinition { name, visibility, .. }
+            | Expression::EnumDefinition { name, visibility, .. }
+            | Expression::TraitDefinition { name, visibility, .. } => Some((name.clone(), *visibility)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Names of structs and enums that must implement Hash and Eq because they are used,
+/// directly or through fields, as a Map key or HashSet element in a type
+/// annotation or as a key in a Map literal
+fn hash_key_structs(programs: &[&Expression]) -> BTreeSet<String> {
+    fn collect(expr: &Expression, struct_fields: &mut BTreeMap<String, Vec<Type>>, key_types: &mut Vec<Type>) {
+        match expr {
+            Expression::StructDefinition { name, fields, .. } => {
+                struct_fields.insert(name.clone(), fields.iter().map(|f| f.type_.clone()).collect());
+                fields.iter().for_each(|f| key_types_in(&f.type_, key_types));
+            }
+            Expression::EnumDefinition { name, variants, .. } => {
+                let payloads: Vec<Type> = variants.iter().flat_map(|v| v.fields.iter().cloned()).collect();
+                payloads.iter().for_each(|t| key_types_in(t, key_types));
+                struct_fields.insert(name.clone(), payloads);
+                // A variant used as a key stands for its enum
+                for variant in variants {
+                    struct_fields.insert(variant.name.clone(), vec![Type::Custom(name.clone())]);
+                }
+            }
+            Expression::FunctionDefinition { parameters, .. } | Expression::Lambda { parameters, .. } => {
+                parameters.iter().for_each(|p| key_types_in(&p.type_, key_types));
+            }
+            Expression::Map(entries) => {
+                for (key, _) in entries {
+                    match key {
+                        Expression::FunctionCall { function, .. } => {
+                            if let Expression::Identifier(name) | Expression::QualifiedName { name, .. } = function.as_ref() {
+                                key_types.push(Type::Custom(name.clone()));
+                            }
+                        }
+                        Expression::StructInstantiation { struct_name, .. } => {
+                            key_types.push(Type::Custom(struct_name.clone()));
+                        }
+                        Expression::Identifier(name) => key_types.push(Type::Custom(name.clone())),
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+        expr.for_each_child(&mut |child| collect(child, struct_fields, key_types));
+    }
+
+    fn key_types_in(ty: &Type, out: &mut Vec<Type>) {
+        if let Type::Map(key, _) | Type::HashSet(key) = ty {
+            out.push((**key).clone());
+        }
+        for inner in type_components(ty) {
+            key_types_in(inner, out);
+        }
+    }
+
+    let mut struct_fields = BTreeMap::new();
+    let mut pending = Vec::new();
+    for program in programs {
+        collect(program, &mut struct_fields, &mut pending);
+    }
+
+    // A key struct's fields must be hashable too
+    let mut structs = BTreeSet::new();
+    while let Some(ty) = pending.pop() {
+        if let Type::Custom(name) = &ty {
+            if let Some(fields) = struct_fields.get(name) {
+                if structs.insert(name.clone()) {
+                    pending.extend(fields.iter().cloned());
+                }
+            }
+        }
+        pending.extend(type_components(&ty).into_iter().cloned());
+    }
+    structs
+}
+
+fn contains_float(ty: &Type) -> bool {
+    matches!(ty, Type::Float32 | Type::Float64) || type_components(ty).into_iter().any(contains_float)
+}
+
+/// Whether values of `ty` are printed with `{}` rather than `{:?}`
+fn is_display(ty: &Type) -> bool {
+    matches!(ty,
+        Type::Int8 | Type::Int16 | Type::Int32 | Type::Int64 | Type::Int128 | Type::Int
+        | Type::UInt8 | Type::UInt16 | Type::UInt32 | Type::UInt64 | Type::UInt128 | Type::UInt
+        | Type::Float32 | Type::Float64 | Type::Bool | Type::Char | Type::String)
+}
+
+/// Types directly nested inside `ty`
+pub(crate) fn type_components(ty: &Type) -> Vec<&Type> {
+    match ty {
+        Type::Tuple(types) => types.iter().collect(),
+        Type::Function(params, ret) => params.iter().chain(std::iter::once(ret.as_ref())).collect(),
+        Type::Map(a, b) | Type::BTreeMap(a, b) | Type::Result(a, b) => vec![a, b],
+        Type::List(inner) | Type::Queue(inner) | Type::Heap(inner) | Type::Stream(inner) | Type::Array(inner, _) | Type::Slice(inner)
+        | Type::HashSet(inner) | Type::BTreeSet(inner) | Type::Option(inner) => vec![inner],
+        _ => vec![],
+    }
+}
+
+/// Support modules of the builtin namespaces called anywhere in `expr`,
+/// each listed once in order of first use
+fn used_support_modules(expr: &Expression) -> Vec<(&'static str, &'static str)> {
+    fn collect(expr: &Expression, modules: &mut Vec<(&'static str, &'static str)>) {
+        let support = match expr {
+            Expression::QualifiedName { path, name } => {
+                builtins::lookup(path, name).and_then(|builtin| builtins::support_module(builtin.module))
+            }
+            Expression::Convert { target, .. } if !matches!(target, Type::Float32 | Type::Float64) => {
+                builtins::support_module("Convert")
+            }
+            Expression::LogCall { .. } => builtins::support_module("Log"),
+            _ => None,
+        };
+        if let Some(support) = support {
+            if !modules.contains(&support) {
+                modules.push(support);
+            }
+        }
+        expr.for_each_child(&mut |child| collect(child, modules));
+    }
+
+    let mut modules = Vec::new();
+    collect(expr, &mut modules);
+    modules
+}
+
+/// Whether `expr` applies an operator `Arithmetic` decides the overflow
+/// behavior of
+fn uses_arithmetic(expr: &Expression) -> bool {
+    fn visit(expr: &Expression, found: &mut bool) {
+        if let Expression::BinaryOp { operator, .. } = expr {
+            *found |= matches!(operator,
+                Operator::Add | Operator::Subtract | Operator::Multiply | Operator::Divide | Operator::Power
+                | Operator::ShiftLeft | Operator::ShiftRight);
+        }
+        expr.for_each_child(&mut |child| visit(child, found));
+    }
+
+    let mut found = false;
+    visit(expr, &mut found);
+    found
+}
+
+/// Type variables mentioned in `ty`, appended to `names` if not already there
+fn collect_type_variables(ty: &Type, names: &mut Vec<String>) {
+    match ty {
+        Type::TypeVar(name) if !names.contains(name) => names.push(name.clone()),
+        _ => {
+            for component in type_components(ty) {
+                collect_type_variables(component, names);
+            }
+        }
+    }
+}
+
+/// False for conditions that can never be bool (literals, collections,
+/// arithmetic); anything else is left for rustc to check
+fn is_possibly_bool(expr: &Expression) -> bool {
+    match expr {
+        Expression::Number(..) | Expression::Float(_) | Expression::String(_)
+        | Expression::InterpolatedString(_) | Expression::List(_) | Expression::Map(_)
+        | Expression::Tuple(_) => false,
+        Expression::BinaryOp { operator, .. } => !matches!(operator,
+            Operator::Add | Operator::Subtract | Operator::Multiply | Operator::Divide | Operator::Power |
+            Operator::BitAnd | Operator::BitOr | Operator::BitXor | Operator::ShiftLeft | Operator::ShiftRight),
+        _ => true,
+    }
+}
+
+/// Names passed directly as the collection argument of Push, Pop, PopFront,
+/// HeapPush or HeapPop anywhere in `exprs`
+fn mutated_bindings(exprs: &[&Expression]) -> BTreeSet<String> {
+    fn collect(expr: &Expression, names: &mut BTreeSet<String>) {
+        if let Expression::FunctionCall { function, arguments } = expr {
+            if let (Expression::Identifier(callee), Some(Expression::Identifier(target))) =
+                (function.as_ref(), arguments.first())
+            {
+                if matches!(callee.as_str(), "Push" | "Pop" | "PopFront" | "HeapPush" | "HeapPop") {
+                    names.insert(target.clone());
+                }
+            }
+        }
+        expr.for_each_child(&mut |child| collect(child, names));
+    }
+
+    let mut names = BTreeSet::new();
+    for expr in exprs {
+        collect(expr, &mut names);
+    }
+    names
+}
+
+/// A Block's statements and its value, which is `()` when it ends with a
+/// `Let`
+fn split_block(statements: &[Expression]) -> (&[Expression], Option<&Expression>) {
+    match statements.split_last() {
+        Some((Expression::LetPattern { .. }, _)) | None => (statements, None),
+        Some((value, statements)) => (statements, Some(value)),
+    }
+}
+
+/// Names bound by `Let` to a lazy stream anywhere in `exprs`
+fn stream_bindings(exprs: &[&Expression]) -> BTreeSet<String> {
+    fn collect(expr: &Expression, names: &mut BTreeSet<String>) {
+        match expr {
+            Expression::Let { name, value, .. } if is_stream(value, names) => {
+                names.insert(name.clone());
+            }
+            Expression::LetPattern { pattern: Pattern::Variable(name), value, .. } if is_stream(value, names) => {
+                names.insert(name.clone());
+            }
+            _ => {}
+        }
+        expr.for_each_child(&mut |child| collect(child, names));
+    }
+
+    let mut names = BTreeSet::new();
+    for expr in exprs {
+        collect(expr, &mut names);
+    }
+    names
+}
+
+/// Names of the String-typed `parameters`
+fn string_parameters(parameters: &[TypeAnnotation]) -> BTreeSet<String> {
+    parameters.iter()
+        .filter(|param| param.type_ == Type::String)
+        .map(|param| param.name.clone())
+        .collect()
+}
+
+/// Does `expr` produce a lazy stream: Iterate, Repeat, Lines, Map or Filter over a
+/// stream, or a name bound to one?
+fn is_stream(expr: &Expression, streams: &BTreeSet<String>) -> bool {
+    match expr {
+        Expression::Identifier(name) => streams.contains(name),
+        Expression::FunctionCall { function, arguments } => match function.as_ref() {
+            Expression::Identifier(name) => match name.as_str() {
+                "Iterate" | "Repeat" | "Lines" => true,
+                "Map" | "Filter" => arguments.get(1).is_some_and(|list| is_stream(list, streams)),
+                _ => false,
+            },
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Does `pattern` contain a string literal, prefix or suffix (generated as a
+/// match guard)?
+pub(crate) fn pattern_has_guard(pattern: &Pattern) -> bool {
+    match pattern {
+        Pattern::Literal(literal) => matches!(literal.as_ref(), Expression::String(_)),
+        Pattern::StartsWith(_) | Pattern::EndsWith(_) => true,
+        Pattern::Constructor { patterns, .. } | Pattern::Tuple(patterns) | Pattern::List(patterns) => {
+            patterns.iter().any(pattern_has_guard)
+        }
+        Pattern::Or(alternatives) => alternatives.iter().any(pattern_has_guard),
+        Pattern::Binding { pattern, .. } => pattern_has_guard(pattern),
+        Pattern::Wildcard | Pattern::Variable(_) | Pattern::Rest => false,
+    }
+}
+
+/// Does `pattern` match a list by its elements (`[x, ..]`, `whole @ []`, ...)?
+fn is_list_pattern(pattern: &Pattern) -> bool {
+    match pattern {
+        Pattern::List(_) => true,
+        Pattern::Binding { pattern, .. } => is_list_pattern(pattern),
+        Pattern::Or(alternatives) => alternatives.iter().any(is_list_pattern),
+        _ => false,
+    }
+}
+
+/// Is `code` a plain Rust name, safe to call a method on without parentheses?
+fn is_rust_identifier(code: &str) -> bool {
+    !code.is_empty() && code.chars().all(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// Is `pattern` itself a string literal, prefix or suffix (not just containing one)?
+fn is_string_test(pattern: &Pattern) -> bool {
+    match pattern {
+        Pattern::Literal(literal) => matches!(literal.as_ref(), Expression::String(_)),
+        Pattern::StartsWith(_) | Pattern::EndsWith(_) => true,
+        _ => false,
+    }
+}
+
+/// Statements that only exist for their side effects and produce `()`
+fn is_unit_statement(expr: &Expression) -> bool {
+    match expr {
+        Expression::LogCall { .. } | Expression::For { .. } | Expression::WhileLet { .. } => true,
+        Expression::IfLet { else_branch, .. } => else_branch.is_none(),
+        Expression::FunctionCall { function, .. } => {
+            matches!(function.as_ref(), Expression::Identifier(name) if matches!(name.as_str(), "Print" | "PrintErr" | "PrintInline" | "Push" | "HeapPush"))
+        }
+        _ => false,
+    }
+}
+
+/// Format Rust source with `rustfmt`, returning `None` if it is unavailable or fails
+fn run_rustfmt(source: &str, max_width: Option<usize>) -> Option<String> {
+    use std::io::Write as _;
+    use std::process::{Command, Stdio};
+
+    let mut command = Command::new("rustfmt");
+    command.args(["--emit", "stdout", "--edition", "2021"]);
+    if let Some(width) = max_width {
+        command.args(["--config", &format!("max_width={}", width)]);
+    }
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+    child.stdin.take()?.write_all(source.as_bytes()).ok()?;
+    let output = child.wait_with_output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout).ok()
+}
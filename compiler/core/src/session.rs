@@ -0,0 +1,71 @@
+//! Compiler Session
+//!
+//! State worth keeping between compilations: the builtin registry and the
+//! generated code of sources already compiled. A language server or watch
+//! mode creates one `Session` and shares it (it is `Send` and `Sync`)
+//! instead of starting from nothing on every keystroke.
+
+use crate::builtins::{self, BuiltinFunction, BUILTIN_FUNCTIONS};
+use crate::cancellation::CancellationToken;
+use crate::driver::{self, CompileError, CompilerCallbacks};
+use crate::rust_codegen::CodegenOptions;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Shared state for any number of compilations, possibly on several threads
+#[derive(Debug, Default)]
+pub struct Session {
+    /// Result of compiling each source with each set of options
+    compiled: Mutex<HashMap<(String, CodegenOptions), Result<String, CompileError>>>,
+}
+
+impl Session {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Every builtin function, grouped by namespace
+    pub fn builtins(&self) -> &'static [BuiltinFunction] {
+        &BUILTIN_FUNCTIONS
+    }
+
+    /// Look up a builtin by its qualified name (`path` excludes the item name)
+    pub fn lookup_builtin(&self, path: &[String], name: &str) -> Option<&'static BuiltinFunction> {
+        builtins::lookup(path, name)
+    }
+
+    /// Compile `source` like `driver::compile_with_cancellation`, reusing the
+    /// result of an earlier compilation of the same source and options.
+    ///
+    /// A cached result runs no phases, so `callbacks` hear nothing. Cancelled
+    /// compilations are not cached. The cache is not locked while compiling,
+    /// so other threads can compile at the same time.
+    pub fn compile(
+        &self,
+        source: &str,
+        options: &CodegenOptions,
+        callbacks: &mut dyn CompilerCallbacks,
+        token: &CancellationToken,
+    ) -> Result<String, CompileError> {
+        let key = (source.to_string(), options.clone());
+        if let Some(result) = self.compiled.lock().unwrap().get(&key) {
+            return result.clone();
+        }
+
+        let result = driver::compile_with_cancellation(source, options, callbacks, token);
+        if result != Err(CompileError::Cancelled) {
+            self.compiled.lock().unwrap().insert(key, result.clone());
+        }
+        result
+    }
+
+    /// Number of compilations whose results are cached
+    pub fn cached_count(&self) -> usize {
+        self.compiled.lock().unwrap().len()
+    }
+
+    /// Forget cached compilations, e.g. when a watch mode has seen many edits
+    pub fn clear_cache(&self) {
+        self.compiled.lock().unwrap().clear();
+    }
+}
@@ -0,0 +1,145 @@
+//! Checked and wrapping arithmetic for the standard library
+//!
+//! With `--arithmetic checked`, `a + b` compiles to `checked_add(a, b)?`
+//! (with the error mapped to a String), which returns an Err from the
+//! enclosing function when the result does not fit its type, in release
+//! builds too. With `--arithmetic wrapping` it compiles to
+//! `wrapping_add(a, b)`, which wraps around in debug builds too.
+//! Floats have nothing to check, and use the plain operators. This file is
+//! also copied verbatim into generated programs that use either mode, so it
+//! must stay self-contained and use only `core` (for `--no-std`).
+
+/// Why a checked operation has no result
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArithmeticError {
+    /// The result does not fit the operands' type (or a shift amount is
+    /// not less than its width)
+    Overflow { type_name: &'static str },
+    /// An integer was divided by zero
+    DivisionByZero,
+}
+
+impl core::fmt::Display for ArithmeticError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            ArithmeticError::Overflow { type_name } => write!(f, "overflows {}", type_name),
+            ArithmeticError::DivisionByZero => write!(f, "divides by zero"),
+        }
+    }
+}
+
+/// A type `+`, `-`, `*` and `/` apply to
+pub trait Number: Copy + core::fmt::Display {
+    fn checked(self, operator: char, rhs: Self) -> Result<Self, ArithmeticError>;
+    fn wrapping(self, operator: char, rhs: Self) -> Self;
+}
+
+/// A type that can also be shifted
+pub trait Integer: Number {
+    fn checked_shift(self, operator: char, amount: u32) -> Result<Self, ArithmeticError>;
+    fn wrapping_shift(self, operator: char, amount: u32) -> Self;
+}
+
+macro_rules! integers {
+    ($($type_:ty),*) => {
+        $(impl Number for $type_ {
+            fn checked(self, operator: char, rhs: Self) -> Result<Self, ArithmeticError> {
+                let result = match operator {
+                    '+' => self.checked_add(rhs),
+                    '-' => self.checked_sub(rhs),
+                    '*' => self.checked_mul(rhs),
+                    _ if rhs == 0 => return Err(ArithmeticError::DivisionByZero),
+                    _ => self.checked_div(rhs),
+                };
+                result.ok_or(ArithmeticError::Overflow { type_name: stringify!($type_) })
+            }
+
+            fn wrapping(self, operator: char, rhs: Self) -> Self {
+                match operator {
+                    '+' => self.wrapping_add(rhs),
+                    '-' => self.wrapping_sub(rhs),
+                    '*' => self.wrapping_mul(rhs),
+                    // Still panics on a zero divisor
+                    _ => self.wrapping_div(rhs),
+                }
+            }
+        }
+
+        impl Integer for $type_ {
+            fn checked_shift(self, operator: char, amount: u32) -> Result<Self, ArithmeticError> {
+                let result = if operator == '<' { self.checked_shl(amount) } else { self.checked_shr(amount) };
+                result.ok_or(ArithmeticError::Overflow { type_name: stringify!($type_) })
+            }
+
+            fn wrapping_shift(self, operator: char, amount: u32) -> Self {
+                if operator == '<' { self.wrapping_shl(amount) } else { self.wrapping_shr(amount) }
+            }
+        })*
+    };
+}
+
+macro_rules! floats {
+    ($($type_:ty),*) => {
+        $(impl Number for $type_ {
+            fn checked(self, operator: char, rhs: Self) -> Result<Self, ArithmeticError> {
+                Ok(self.wrapping(operator, rhs))
+            }
+
+            fn wrapping(self, operator: char, rhs: Self) -> Self {
+                match operator {
+                    '+' => self + rhs,
+                    '-' => self - rhs,
+                    '*' => self * rhs,
+                    _ => self / rhs,
+                }
+            }
+        })*
+    };
+}
+
+integers!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+floats!(f32, f64);
+
+macro_rules! operations {
+    ($($operator:literal => $checked:ident, $wrapping:ident;)*) => {
+        $(pub fn $checked<T: Number>(left: T, right: T) -> Result<T, ArithmeticError> {
+            left.checked($operator, right)
+        }
+
+        pub fn $wrapping<T: Number>(left: T, right: T) -> T {
+            left.wrapping($operator, right)
+        })*
+    };
+}
+
+operations! {
+    '+' => checked_add, wrapping_add;
+    '-' => checked_sub, wrapping_sub;
+    '*' => checked_mul, wrapping_mul;
+    '/' => checked_div, wrapping_div;
+}
+
+pub fn checked_shl<T: Integer>(value: T, amount: u32) -> Result<T, ArithmeticError> {
+    value.checked_shift('<', amount)
+}
+
+pub fn wrapping_shl<T: Integer>(value: T, amount: u32) -> T {
+    value.wrapping_shift('<', amount)
+}
+
+pub fn checked_shr<T: Integer>(value: T, amount: u32) -> Result<T, ArithmeticError> {
+    value.checked_shift('>', amount)
+}
+
+pub fn wrapping_shr<T: Integer>(value: T, amount: u32) -> T {
+    value.wrapping_shift('>', amount)
+}
+
+/// `^`, which always computes in i32
+pub fn checked_pow(base: i32, exponent: u32) -> Result<i32, ArithmeticError> {
+    base.checked_pow(exponent).ok_or(ArithmeticError::Overflow { type_name: "i32" })
+}
+
+pub fn wrapping_pow(base: i32, exponent: u32) -> i32 {
+    base.wrapping_pow(exponent)
+}
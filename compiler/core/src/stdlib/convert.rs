@@ -0,0 +1,59 @@
+//! Checked numeric conversions for the standard library
+//!
+//! `Convert[x, T]` to an integer type compiles to `convert::<T, _>(x)`,
+//! which panics instead of wrapping when `x` does not fit in `T`. Floats are
+//! truncated toward zero first, like Rust's `as`. This file is also copied
+//! verbatim into generated programs that convert to an integer type, so it
+//! must stay self-contained and use only `core` (for `--no-std`).
+
+/// Conversion of `Self` into the numeric type `T`
+pub trait ConvertTo<T> {
+    fn convert_to(self) -> T;
+}
+
+/// `value` as a `T`, panicking if it is out of range (or NaN)
+pub fn convert<T, S: ConvertTo<T>>(value: S) -> T {
+    value.convert_to()
+}
+
+macro_rules! integer_target {
+    ($target:ty; $($source:ty),*) => {
+        $(impl ConvertTo<$target> for $source {
+            // Spelled out: TryFrom is not in the 2015 edition prelude
+            #[allow(clippy::unnecessary_fallible_conversions)]
+            fn convert_to(self) -> $target {
+                <$target as core::convert::TryFrom<$source>>::try_from(self)
+                    .unwrap_or_else(|_| panic!("Convert: {} is out of range for {}", self, stringify!($target)))
+            }
+        })*
+
+        impl ConvertTo<$target> for f64 {
+            fn convert_to(self) -> $target {
+                // `as` truncates toward zero, so anything strictly between
+                // MIN - 1 and MAX + 1 fits. Up to 32 bits both bounds are
+                // exact; for wider targets MIN - 1 rounds to MIN (hence the
+                // `== min`) and MAX + 1 to 2^bits.
+                let min = <$target>::MIN as f64;
+                if (self > min - 1.0 || self == min) && self < <$target>::MAX as f64 + 1.0 {
+                    self as $target
+                } else {
+                    panic!("Convert: {} is out of range for {}", self, stringify!($target))
+                }
+            }
+        }
+
+        impl ConvertTo<$target> for f32 {
+            fn convert_to(self) -> $target {
+                (self as f64).convert_to()
+            }
+        }
+    };
+}
+
+macro_rules! integer_targets {
+    ($($target:ty),*) => {
+        $(integer_target!($target; i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);)*
+    };
+}
+
+integer_targets!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
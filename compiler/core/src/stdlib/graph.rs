@@ -0,0 +1,91 @@
+//! Graph algorithms for the standard library
+//!
+//! Graphs are adjacency lists keyed by node id. Neighbours keep the order
+//! their edges were given in, so traversals are deterministic. This file is
+//! also copied verbatim into generated programs that call `Graph.*`
+//! builtins, so it must stay self-contained.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Build a directed graph from `(from, to)` edges; every endpoint gets an entry
+pub fn from_edges(edges: &[(i32, i32)]) -> HashMap<i32, Vec<i32>> {
+    let mut graph: HashMap<i32, Vec<i32>> = HashMap::new();
+    for &(from, to) in edges {
+        graph.entry(from).or_default().push(to);
+        graph.entry(to).or_default();
+    }
+    graph
+}
+
+/// Build an undirected graph: each edge is added in both directions
+pub fn undirected(edges: &[(i32, i32)]) -> HashMap<i32, Vec<i32>> {
+    let mut graph: HashMap<i32, Vec<i32>> = HashMap::new();
+    for &(a, b) in edges {
+        graph.entry(a).or_default().push(b);
+        graph.entry(b).or_default().push(a);
+    }
+    graph
+}
+
+/// Nodes reachable from `start` in breadth-first order
+pub fn bfs(graph: &HashMap<i32, Vec<i32>>, start: i32) -> Vec<i32> {
+    let mut order = vec![start];
+    let mut seen = HashSet::from([start]);
+    let mut next = 0;
+    while next < order.len() {
+        let node = order[next];
+        next += 1;
+        for &neighbor in graph.get(&node).into_iter().flatten() {
+            if seen.insert(neighbor) {
+                order.push(neighbor);
+            }
+        }
+    }
+    order
+}
+
+/// Nodes reachable from `start` in depth-first preorder
+pub fn dfs(graph: &HashMap<i32, Vec<i32>>, start: i32) -> Vec<i32> {
+    let mut order = Vec::new();
+    let mut seen = HashSet::new();
+    let mut stack = vec![start];
+    while let Some(node) = stack.pop() {
+        if !seen.insert(node) {
+            continue;
+        }
+        order.push(node);
+        // Reversed so the first neighbour is visited first
+        for &neighbor in graph.get(&node).into_iter().flatten().rev() {
+            if !seen.contains(&neighbor) {
+                stack.push(neighbor);
+            }
+        }
+    }
+    order
+}
+
+/// A path with the fewest edges from `from` to `to` (both included), or
+/// None if `to` is unreachable
+pub fn shortest_path(graph: &HashMap<i32, Vec<i32>>, from: i32, to: i32) -> Option<Vec<i32>> {
+    let mut previous = HashMap::from([(from, from)]);
+    let mut queue = VecDeque::from([from]);
+    while let Some(node) = queue.pop_front() {
+        if node == to {
+            let mut path = vec![to];
+            let mut current = to;
+            while current != from {
+                current = previous[&current];
+                path.push(current);
+            }
+            path.reverse();
+            return Some(path);
+        }
+        for &neighbor in graph.get(&node).into_iter().flatten() {
+            if let std::collections::hash_map::Entry::Vacant(entry) = previous.entry(neighbor) {
+                entry.insert(node);
+                queue.push_back(neighbor);
+            }
+        }
+    }
+    None
+}
@@ -1,4 +1,4 @@
-/// Basic input/output functions for the standard library
+//! Basic input/output functions for the standard library
 
 /// Print a message to the console
 pub fn print<T: std::fmt::Display>(message: T) {
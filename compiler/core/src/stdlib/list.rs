@@ -1,4 +1,4 @@
-/// List manipulation functions for the standard library
+//! List manipulation functions for the standard library
 
 /// Compute the length of a list
 pub fn len<T>(list: &[T]) -> usize {
@@ -0,0 +1,72 @@
+//! Logging for the standard library
+//!
+//! `LogInfo[message]` compiles to `info(message)`, which writes
+//! `[INFO] message` to stderr if the level is enabled. As with `env_logger`,
+//! the `RUST_LOG` environment variable sets the most detailed level written:
+//! `off`, `error`, `warn`, `info` or `debug`; without it, every level but
+//! debug is. Generated programs call `init` at the start of `main`, and a
+//! copy that was not initialized reads `RUST_LOG` on first use. This file is
+//! also copied verbatim into generated programs that log, so it must stay
+//! self-contained.
+
+use std::fmt::Display;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// How much detail a message is; each level enables the ones before it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error = 1,
+    Warn = 2,
+    Info = 3,
+    Debug = 4,
+}
+
+/// One more than the most detailed level enabled; 0 before `init`
+static ENABLED: AtomicUsize = AtomicUsize::new(0);
+
+/// The most detailed level a `RUST_LOG` setting enables, 0 for none
+pub fn max_level(setting: Option<&str>) -> usize {
+    match setting.map(|setting| setting.trim().to_ascii_lowercase()).as_deref() {
+        Some("off") => 0,
+        Some("error") => Level::Error as usize,
+        Some("warn") => Level::Warn as usize,
+        Some("debug") | Some("trace") => Level::Debug as usize,
+        _ => Level::Info as usize,
+    }
+}
+
+/// Enable the levels `RUST_LOG` asks for
+pub fn init() {
+    let level = max_level(std::env::var("RUST_LOG").ok().as_deref());
+    ENABLED.store(level + 1, Ordering::Relaxed);
+}
+
+/// Whether messages at `level` are written
+pub fn enabled(level: Level) -> bool {
+    if ENABLED.load(Ordering::Relaxed) == 0 {
+        init();
+    }
+    (level as usize) < ENABLED.load(Ordering::Relaxed)
+}
+
+fn log<T: Display>(level: Level, label: &str, message: T) {
+    if enabled(level) {
+        eprintln!("[{}] {}", label, message);
+    }
+}
+
+pub fn error<T: Display>(message: T) {
+    log(Level::Error, "ERROR", message)
+}
+
+pub fn warn<T: Display>(message: T) {
+    log(Level::Warn, "WARN", message)
+}
+
+pub fn info<T: Display>(message: T) {
+    log(Level::Info, "INFO", message)
+}
+
+pub fn debug<T: Display>(message: T) {
+    log(Level::Debug, "DEBUG", message)
+}
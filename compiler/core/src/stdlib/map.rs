@@ -1,4 +1,4 @@
-/// Map manipulation functions for the standard library
+//! Map manipulation functions for the standard library
 
 use std::collections::HashMap;
 
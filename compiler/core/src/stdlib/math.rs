@@ -1,4 +1,4 @@
-/// Basic mathematical functions for the standard library
+//! Basic mathematical functions for the standard library
 
 /// Compute the factorial of a number
 pub fn factorial(n: u64) -> u64 {
@@ -3,3 +3,7 @@ pub mod math;
 pub mod string;
 pub mod list;
 pub mod map;
+pub mod graph;
+pub mod convert;
+pub mod arithmetic;
+pub mod log;
@@ -0,0 +1,8 @@
+pub mod io;
+pub mod math;
+pub mod string;
+pub mod list;
+pub mod map;
+// The Graph support module is the one the compiler embeds in generated code
+#[path = "../graph.rs"]
+pub mod graph;
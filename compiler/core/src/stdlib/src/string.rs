@@ -0,0 +1,42 @@
+/// String manipulation functions
+
+/// Convert a string to uppercase
+pub fn to_uppercase(s: &str) -> String {
+    s.to_uppercase()
+}
+
+/// Convert a string to lowercase
+pub fn to_lowercase(s: &str) -> String {
+    s.to_lowercase()
+}
+
+/// Get the length of a string in bytes (see `char_count` for characters)
+pub fn length(s: &str) -> usize {
+    s.len()
+}
+
+/// Check if a string contains a substring
+pub fn contains(s: &str, substring: &str) -> bool {
+    s.contains(substring)
+}
+
+/// Trim whitespace from start and end of a string
+pub fn trim(s: &str) -> String {
+    s.trim().to_string()
+}
+
+/// Count the characters (Unicode scalar values) in a string
+pub fn char_count(s: &str) -> usize {
+    s.chars().count()
+}
+
+/// Take `len` characters starting at character index `start`; never splits a
+/// multi-byte character and stops early at the end of the string
+pub fn substring(s: &str, start: usize, len: usize) -> String {
+    s.chars().skip(start).take(len).collect()
+}
+
+/// Split a string into its characters
+pub fn chars(s: &str) -> Vec<char> {
+    s.chars().collect()
+}
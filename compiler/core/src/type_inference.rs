@@ -0,0 +1,3823 @@
+//! Type Inference Module
+//!
+//! Performs type inference and type checking on the W language AST.
+//! This runs after parsing and before code generation.
+
+use crate::ast::{EnumVariant, Expression, Type, TypeAnnotation, Operator, Pattern, StringPart, TraitMethod, Visibility};
+use crate::builtins;
+use crate::cancellation::CancellationToken;
+use crate::diagnostics;
+use crate::exhaustiveness;
+use crate::lexer::Span;
+use crate::resolve;
+use crate::rust_codegen::{pattern_has_guard, type_components};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+use std::rc::Rc;
+
+/// Type inference errors
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeError {
+    /// Type mismatch between expected and actual types
+    TypeMismatch {
+        expected: Type,
+        actual: Type,
+        context: String,
+    },
+    /// Undefined variable or function
+    UndefinedIdentifier(String),
+    /// Wrong number of arguments in function call
+    ArityMismatch {
+        function: String,
+        expected: usize,
+        actual: usize,
+    },
+    /// Cannot infer type (insufficient information)
+    CannotInfer(String),
+    /// Struct not defined
+    UndefinedStruct(String),
+    /// Field count mismatch in struct instantiation
+    FieldCountMismatch {
+        struct_name: String,
+        expected: usize,
+        actual: usize,
+    },
+    /// Trait not defined
+    UndefinedTrait(String),
+    /// `Impl` that leaves out a method its trait requires
+    MissingTraitMethod {
+        trait_name: String,
+        type_name: String,
+        method: String,
+    },
+    /// `Impl` defining a method its trait does not declare
+    UnknownTraitMethod {
+        trait_name: String,
+        method: String,
+    },
+    /// Trait method called on (or trait-typed argument given) a type without an `Impl`
+    TraitNotImplemented {
+        trait_name: String,
+        type_: Type,
+    },
+    /// Field access on a type that has no such field
+    UndefinedField {
+        type_: Type,
+        field: String,
+    },
+    /// Cond condition that is not Bool (there is no implicit truthiness)
+    NonBoolCondition(Type),
+    /// `<`, `>`, `<=` or `>=` on a type without an ordering
+    NotOrdered {
+        type_: Type,
+        reason: String,
+    },
+    /// Map key or HashSet element type that cannot implement Hash and Eq
+    UnhashableKey {
+        key_type: Type,
+        reason: String,
+    },
+    /// Heap element type without the total order (Ord) BinaryHeap needs
+    UnorderedHeapElement {
+        element_type: Type,
+        reason: String,
+    },
+    /// `Let` with a pattern that can fail to match (holds the pattern as W source)
+    RefutablePattern(String),
+    /// A possibly infinite stream consumed whole (holds what consumed it)
+    UnboundedStream(String),
+    /// Branches returning numeric types that no type holds without loss
+    /// (e.g. Int64 and Float64); `suggestion` is a type to convert one to
+    NoCommonNumericType {
+        first: Type,
+        second: Type,
+        context: String,
+        suggestion: Type,
+    },
+    /// An or-pattern whose alternatives do not all bind `name` (holds the
+    /// pattern as W source)
+    OrPatternBindings {
+        pattern: String,
+        name: String,
+    },
+    /// Inference was stopped by its cancellation token; not a problem with
+    /// the program
+    Cancelled,
+    /// `Convert` from or to a type that is not numeric
+    InvalidConversion {
+        from: Type,
+        to: Type,
+    },
+    /// `Try` outside the body of a function that returns Result; holds the
+    /// function and what it returns, or `None` outside any function
+    TryWithoutResult(Option<(String, Type)>),
+    /// An `@[kernel]` function outside the data-parallel numeric subset
+    InvalidKernel {
+        kernel: String,
+        reason: String,
+    },
+    /// A parameter without a default value after one with a default
+    DefaultBeforeRequired {
+        function: String,
+        parameter: String,
+    },
+    /// A Match whose arms leave some values unmatched; `missing` is one of
+    /// them as a W pattern
+    NonExhaustiveMatch {
+        type_: Type,
+        missing: String,
+    },
+    /// A Match arm that only matches values earlier arms already match
+    /// (holds its pattern as W source)
+    UnreachableArm(String),
+    /// A name with several different top-level definitions, such as a
+    /// variant of two enums; `definitions` describes each
+    AmbiguousName {
+        name: String,
+        definitions: Vec<String>,
+    },
+    /// A struct or enum holding itself inline, directly or through other
+    /// types, so it would have infinite size; holds the chain of type
+    /// names, starting and ending with the same one
+    RecursiveType(Vec<String>),
+    /// A Print template whose `{}` placeholders are not as many as the
+    /// arguments after it
+    FormatArguments {
+        function: String,
+        placeholders: usize,
+        arguments: usize,
+    },
+    /// A `Private` definition of an imported module used outside it
+    PrivateItem { name: String, module: String },
+    /// Integer arithmetic that checked arithmetic can fail in, in a function
+    /// that does not return Result, so the failure has nowhere to go
+    UncheckedArithmetic { operator: String, function: String, returns: Type },
+    /// `?` on an Option or Result outside a function (`None`), or in one
+    /// (with its name and return type) that does not return the same kind
+    /// of value, so the early return has nowhere to go
+    PropagateMismatch { operand: Type, function: Option<(String, Type)> },
+    /// A string literal, StartsWith or EndsWith pattern in LetElse, IfLet
+    /// or WhileLet (the construct), which only Match can test
+    StringPatternOutsideMatch { pattern: String, construct: &'static str },
+}
+
+impl TypeError {
+    /// Stable code identifying the kind of error, explained by `w --explain <code>`
+    pub fn code(&self) -> &'static str {
+        match self {
+            TypeError::TypeMismatch { .. } => "E0001",
+            TypeError::UndefinedIdentifier(_) => "E0002",
+            TypeError::ArityMismatch { .. } => "E0003",
+            TypeError::CannotInfer(_) => "E0004",
+            TypeError::UndefinedStruct(_) => "E0005",
+            TypeError::FieldCountMismatch { .. } => "E0006",
+            TypeError::UndefinedTrait(_) => "E0007",
+            TypeError::MissingTraitMethod { .. } => "E0008",
+            TypeError::UnknownTraitMethod { .. } => "E0009",
+            TypeError::TraitNotImplemented { .. } => "E0010",
+            TypeError::UndefinedField { .. } => "E0011",
+            TypeError::NonBoolCondition(_) => "E0012",
+            TypeError::NotOrdered { .. } => "E0013",
+            TypeError::UnhashableKey { .. } => "E0014",
+            TypeError::UnorderedHeapElement { .. } => "E0015",
+            TypeError::RefutablePattern(_) => "E0016",
+            TypeError::UnboundedStream(_) => "E0017",
+            TypeError::NoCommonNumericType { .. } => "E0018",
+            TypeError::OrPatternBindings { .. } => "E0019",
+            TypeError::Cancelled => "E0020",
+            TypeError::InvalidConversion { .. } => "E0021",
+            TypeError::TryWithoutResult(_) => "E0022",
+            TypeError::InvalidKernel { .. } => "E0023",
+            TypeError::DefaultBeforeRequired { .. } => "E0024",
+            TypeError::NonExhaustiveMatch { .. } => "E0025",
+            TypeError::UnreachableArm(_) => "E0026",
+            TypeError::AmbiguousName { .. } => "E0027",
+            TypeError::RecursiveType(_) => "E0028",
+            TypeError::FormatArguments { .. } => "E0029",
+            TypeError::PrivateItem { .. } => "E0030",
+            TypeError::UncheckedArithmetic { .. } => "E0031",
+            TypeError::PropagateMismatch { .. } => "E0032",
+            TypeError::StringPatternOutsideMatch { .. } => "E0033",
+        }
+    }
+}
+
+impl fmt::Display for TypeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TypeError::TypeMismatch { expected, actual, context } => {
+                write!(f, "Type mismatch in {}: expected {}, got {}", context, expected, actual)
+            }
+            TypeError::UndefinedIdentifier(name) => {
+                write!(f, "Undefined identifier: {}", name)
+            }
+            TypeError::ArityMismatch { function, expected, actual } => {
+                write!(f, "Function {} expects {} arguments, got {}", function, expected, actual)
+            }
+            TypeError::CannotInfer(context) => {
+                write!(f, "Cannot infer type for: {}", context)
+            }
+            TypeError::UndefinedStruct(name) => {
+                write!(f, "Undefined struct: {}", name)
+            }
+            TypeError::FieldCountMismatch { struct_name, expected, actual } => {
+                write!(f, "Struct {} expects {} fields, got {}", struct_name, expected, actual)
+            }
+            TypeError::UndefinedTrait(name) => {
+                write!(f, "Undefined trait: {}", name)
+            }
+            TypeError::MissingTraitMethod { trait_name, type_name, method } => {
+                write!(f, "Impl of {} for {} is missing method {}", trait_name, type_name, method)
+            }
+            TypeError::UnknownTraitMethod { trait_name, method } => {
+                write!(f, "Method {} is not declared by trait {}", method, trait_name)
+            }
+            TypeError::TraitNotImplemented { trait_name, type_ } => {
+                write!(f, "{} does not implement {}", type_, trait_name)
+            }
+            TypeError::UndefinedField { type_, field } => {
+                write!(f, "{} has no field {}", type_, field)
+            }
+            TypeError::NonBoolCondition(actual) => {
+                write!(f, "Condition must be Bool, got {}", actual)?;
+                match actual {
+                    t if is_numeric(t) => write!(f, "; compare explicitly, e.g. `x != 0`"),
+                    Type::String => write!(f, "; compare explicitly, e.g. `s != \"\"`"),
+                    _ => Ok(()),
+                }
+            }
+            TypeError::NotOrdered { type_, reason } => {
+                write!(f, "Values of type {} cannot be compared with < > <= >=: {}", type_, reason)
+            }
+            TypeError::UnhashableKey { key_type, reason } => {
+                write!(f, "{} cannot be used as a Map key or HashSet element: {}", key_type, reason)
+            }
+            TypeError::UnorderedHeapElement { element_type, reason } => {
+                write!(f, "{} cannot be stored in a Heap: {}", element_type, reason)
+            }
+            TypeError::RefutablePattern(pattern) => {
+                write!(
+                    f,
+                    "Refutable pattern `{}` in Let; use LetElse[{}, value, fallback] or Match to handle the other cases",
+                    pattern, pattern
+                )
+            }
+            TypeError::NoCommonNumericType { first, second, context, suggestion } => write!(
+                f,
+                "No lossless common type for {} and {} in {}; convert one branch explicitly, e.g. Convert[value, {}]",
+                first, second, context, suggestion
+            ),
+            TypeError::UnboundedStream(context) => {
+                write!(f, "Unbounded stream used in {}; bound it with Take[n, stream] first", context)
+            }
+            TypeError::OrPatternBindings { pattern, name } => {
+                write!(f, "Variable {} is not bound in every alternative of `{}`", name, pattern)
+            }
+            TypeError::Cancelled => write!(f, "Type checking was cancelled"),
+            TypeError::InvalidConversion { from, to } => {
+                write!(f, "Cannot Convert {} to {}: only numeric types convert to each other", from, to)
+            }
+            TypeError::TryWithoutResult(None) => {
+                write!(f, "Try can only be used in the body of a function that returns Result")
+            }
+            TypeError::TryWithoutResult(Some((function, returns))) => write!(
+                f,
+                "{} uses Try but returns {}; Try returns its Err early, so {} must return Result (e.g. Ok[value])",
+                function, returns, function
+            ),
+            TypeError::InvalidKernel { kernel, reason } => write!(f, "Kernel {} {}", kernel, reason),
+            TypeError::DefaultBeforeRequired { function, parameter } => write!(
+                f,
+                "Parameter {} of {} has no default value but follows one that does; parameters with defaults must come last",
+                parameter, function
+            ),
+            TypeError::NonExhaustiveMatch { type_, missing } if missing == "_" => write!(
+                f,
+                "Match on {} does not cover every value; add a `_` arm for the values no arm matches",
+                type_
+            ),
+            TypeError::NonExhaustiveMatch { type_, missing } => write!(
+                f,
+                "Match on {} does not cover `{}`; add an arm for it or a `_` arm",
+                type_, missing
+            ),
+            TypeError::UnreachableArm(pattern) => write!(
+                f,
+                "Match arm `{}` is unreachable: the arms before it match every value it does",
+                pattern
+            ),
+            TypeError::AmbiguousName { name, definitions } => {
+                write!(f, "Ambiguous name {}: it is {}", name, definitions.join(" and "))
+            }
+            TypeError::RecursiveType(cycle) => {
+                let name = cycle.first().map(String::as_str).unwrap_or_default();
+                write!(
+                    f,
+                    "Type {} contains itself ({}), so it would have infinite size; hold the recursive value in a List, e.g. List[{}]",
+                    name,
+                    cycle.join(" -> "),
+                    cycle.get(1).map(String::as_str).unwrap_or(name)
+                )
+            }
+            TypeError::FormatArguments { function, placeholders, arguments } => write!(
+                f,
+                "The {} template has {} `{{}}` placeholder{} but {} argument{} after it; give one argument per placeholder",
+                function,
+                placeholders,
+                if *placeholders == 1 { "" } else { "s" },
+                arguments,
+                if *arguments == 1 { "" } else { "s" }
+            ),
+            TypeError::PrivateItem { name, module } => write!(
+                f,
+                "{} is private to module {}; mark it Public[...] there to use it here",
+                name, module
+            ),
+            TypeError::UncheckedArithmetic { operator, function, returns } => write!(
+                f,
+                "{} returns {}, but checked arithmetic returns an Err from it when its {} overflows; wrap its value in Ok[...] so it returns Result[{}, String], or build with --arithmetic unchecked or wrapping",
+                function, returns, operator, returns
+            ),
+            TypeError::PropagateMismatch { operand, function: None } => write!(
+                f,
+                "? on {} returns early from the enclosing function, so it can only be used in the body of a function that returns {}",
+                operand, propagated_kind(operand)
+            ),
+            TypeError::PropagateMismatch { operand, function: Some((function, returns)) } => write!(
+                f,
+                "{} uses ? on {} but returns {}; ? returns the {} early, so {} must return {}",
+                function, operand, returns,
+                if matches!(operand, Type::Option(_)) { "None" } else { "Err" },
+                function, propagated_kind(operand)
+            ),
+            TypeError::StringPatternOutsideMatch { pattern, construct } => write!(
+                f,
+                "{} cannot test the string pattern `{}`; use Match instead, e.g. Match[value, [{}, ...], [_, ...]]",
+                construct, pattern, pattern
+            ),
+        }
+    }
+}
+
+/// Reject a string literal, StartsWith or EndsWith in `pattern`, which Rust
+/// tests in a match guard, in `construct`, which is generated without one
+fn check_no_string_pattern(pattern: &Pattern, construct: &'static str) -> Result<(), TypeError> {
+    if pattern_has_guard(pattern) {
+        return Err(TypeError::StringPatternOutsideMatch { pattern: pattern.to_string(), construct });
+    }
+    Ok(())
+}
+
+/// What a function using `?` on `operand` must return: an Option or a Result
+fn propagated_kind(operand: &Type) -> &'static str {
+    if matches!(operand, Type::Option(_)) { "an Option (e.g. Some[value])" } else { "a Result (e.g. Ok[value])" }
+}
+
+/// A type error and where it was found
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocatedTypeError {
+    pub error: TypeError,
+    /// Start of the top-level expression it was found in, when the parser's
+    /// spans were given
+    pub span: Option<Span>,
+}
+
+impl LocatedTypeError {
+    pub fn code(&self) -> &'static str {
+        self.error.code()
+    }
+
+    /// The error with its line and column in `file` and the line of
+    /// `source` it is on, rustc style
+    pub fn render(&self, file: &str, source: &str) -> String {
+        diagnostics::render("error", self.code(), &self.error.to_string(), file, source, self.span)
+    }
+}
+
+/// `line:column: message`, or just the message without a span
+impl fmt::Display for LocatedTypeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.span {
+            Some(span) => write!(f, "{}: {}", span, self.error),
+            None => write!(f, "{}", self.error),
+        }
+    }
+}
+
+/// Type environment tracks variable and function types
+#[derive(Debug, Clone)]
+pub struct TypeEnvironment {
+    /// Maps variable/function names to their types
+    bindings: BTreeMap<String, Type>,
+    /// Maps struct names to their field types
+    structs: BTreeMap<String, Vec<TypeAnnotation>>,
+    /// Maps enum names to their variants
+    enums: BTreeMap<String, Vec<EnumVariant>>,
+    /// Maps trait names to the methods they require
+    traits: BTreeMap<String, Vec<TraitMethod>>,
+    /// (trait, type) pairs with an `Impl`
+    impls: BTreeSet<(String, String)>,
+    /// Number of trailing parameters with default values, for functions
+    /// that have any
+    defaults: BTreeMap<String, usize>,
+    /// Bindings whose type variables each use instantiates afresh: function
+    /// definitions and lambdas bound with `Let`
+    generalized: BTreeSet<String>,
+    /// Imported modules, whose definitions are also reachable as
+    /// `Module.Name`
+    modules: BTreeSet<String>,
+    /// `Private` definitions of imported modules, with the module each is
+    /// private to; binding the name again hides the module's definition
+    private: BTreeMap<String, String>,
+}
+
+impl Default for TypeEnvironment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TypeEnvironment {
+    pub fn new() -> Self {
+        TypeEnvironment {
+            bindings: BTreeMap::new(),
+            structs: BTreeMap::new(),
+            enums: BTreeMap::new(),
+            traits: BTreeMap::new(),
+            impls: BTreeSet::new(),
+            defaults: BTreeMap::new(),
+            generalized: BTreeSet::new(),
+            modules: BTreeSet::new(),
+            private: BTreeMap::new(),
+        }
+    }
+
+    /// Add a variable or function binding
+    pub fn bind(&mut self, name: String, ty: Type) {
+        self.generalized.remove(&name);
+        self.private.remove(&name);
+        self.bindings.insert(name, ty);
+    }
+
+    /// Remove a variable or function binding
+    pub fn unbind(&mut self, name: &str) {
+        self.bindings.remove(name);
+        self.generalized.remove(name);
+    }
+
+    /// Add a binding whose type variables are instantiated at each use
+    pub fn bind_generalized(&mut self, name: String, ty: Type) {
+        self.private.remove(&name);
+        self.generalized.insert(name.clone());
+        self.bindings.insert(name, ty);
+    }
+
+    /// Whether `name` is bound with `bind_generalized`
+    pub fn is_generalized(&self, name: &str) -> bool {
+        self.generalized.contains(name)
+    }
+
+    /// Look up a variable or function type
+    pub fn lookup(&self, name: &str) -> Option<&Type> {
+        self.bindings.get(name)
+    }
+
+    /// Iterate over all variable/function bindings in name order
+    pub fn bindings(&self) -> impl Iterator<Item = (&String, &Type)> {
+        self.bindings.iter()
+    }
+
+    /// Record that the definitions in scope include those of module `name`
+    pub fn define_module(&mut self, name: String) {
+        self.modules.insert(name);
+    }
+
+    /// Whether `path` names an imported module
+    pub fn is_module(&self, path: &[String]) -> bool {
+        matches!(path, [module] if self.modules.contains(module))
+    }
+
+    /// Record that `name` is a `Private` definition of module `module`
+    pub fn define_private(&mut self, name: String, module: String) {
+        self.private.insert(name, module);
+    }
+
+    /// The module `name` is private to, if it is a `Private` definition of
+    /// an imported module
+    pub fn private_module(&self, name: &str) -> Option<&String> {
+        self.private.get(name)
+    }
+
+    /// Add a struct definition
+    pub fn define_struct(&mut self, name: String, fields: Vec<TypeAnnotation>) {
+        self.private.remove(&name);
+        self.structs.insert(name, fields);
+    }
+
+    /// Look up a struct definition
+    pub fn lookup_struct(&self, name: &str) -> Option<&Vec<TypeAnnotation>> {
+        self.structs.get(name)
+    }
+
+    /// Add an enum definition
+    pub fn define_enum(&mut self, name: String, variants: Vec<EnumVariant>) {
+        for variant in &variants {
+            self.private.remove(&variant.name);
+        }
+        self.enums.insert(name, variants);
+    }
+
+    /// Look up an enum definition
+    pub fn lookup_enum(&self, name: &str) -> Option<&Vec<EnumVariant>> {
+        self.enums.get(name)
+    }
+
+    /// Find the enum defining a variant, returning the enum name and variant
+    pub fn lookup_variant(&self, variant: &str) -> Option<(&String, &EnumVariant)> {
+        self.enums.iter().find_map(|(name, variants)| {
+            variants.iter().find(|v| v.name == variant).map(|v| (name, v))
+        })
+    }
+
+    /// The chain of struct and enum names from `name` back to itself, each
+    /// holding the next inline rather than in a collection, if there is one
+    pub fn recursive_cycle(&self, name: &str) -> Option<Vec<String>> {
+        fn search(env: &TypeEnvironment, path: &mut Vec<String>, visited: &mut BTreeSet<String>) -> bool {
+            let current = &path[path.len() - 1];
+            let field_types: Vec<&Type> = match (env.structs.get(current), env.enums.get(current)) {
+                (Some(fields), _) => fields.iter().map(|field| &field.type_).collect(),
+                (None, Some(variants)) => variants.iter().flat_map(|variant| &variant.fields).collect(),
+                (None, None) => return false,
+            };
+            let mut contained = Vec::new();
+            for field_type in field_types {
+                inline_type_names(field_type, &mut contained);
+            }
+            for next in contained {
+                let closes = next == path[0];
+                if closes || visited.insert(next.clone()) {
+                    path.push(next);
+                    if closes || search(env, path, visited) {
+                        return true;
+                    }
+                    path.pop();
+                }
+            }
+            false
+        }
+        let mut path = vec![name.to_string()];
+        search(self, &mut path, &mut BTreeSet::new()).then_some(path)
+    }
+
+    /// Record that the last `count` parameters of `function` have defaults
+    pub fn define_defaults(&mut self, function: String, count: usize) {
+        if count == 0 {
+            self.defaults.remove(&function);
+        } else {
+            self.defaults.insert(function, count);
+        }
+    }
+
+    /// Number of trailing parameters of `function` that callers may leave out
+    pub fn lookup_defaults(&self, function: &str) -> usize {
+        self.defaults.get(function).copied().unwrap_or(0)
+    }
+
+    /// Add a trait definition
+    pub fn define_trait(&mut self, name: String, methods: Vec<TraitMethod>) {
+        self.traits.insert(name, methods);
+    }
+
+    /// Look up a trait definition
+    pub fn lookup_trait(&self, name: &str) -> Option<&Vec<TraitMethod>> {
+        self.traits.get(name)
+    }
+
+    /// Find the trait declaring a method, returning the trait name and signature
+    pub fn lookup_trait_method(&self, method: &str) -> Option<(&String, &TraitMethod)> {
+        self.traits.iter().find_map(|(name, methods)| {
+            methods.iter().find(|m| m.name == method).map(|m| (name, m))
+        })
+    }
+
+    /// Record that `type_name` implements `trait_name`
+    pub fn define_impl(&mut self, trait_name: String, type_name: String) {
+        self.impls.insert((trait_name, type_name));
+    }
+
+    /// Whether values of `ty` can be used where `trait_name` is expected:
+    /// types with an `Impl`, and values already typed as the trait
+    pub fn implements(&self, ty: &Type, trait_name: &str) -> bool {
+        match ty {
+            Type::Custom(name) => {
+                name == trait_name || self.impls.contains(&(trait_name.to_string(), name.clone()))
+            }
+            _ => false,
+        }
+    }
+
+    /// Create a child environment (for nested scopes)
+    pub fn child(&self) -> Self {
+        TypeEnvironment {
+            bindings: self.bindings.clone(),
+            structs: self.structs.clone(),
+            enums: self.enums.clone(),
+            traits: self.traits.clone(),
+            impls: self.impls.clone(),
+            defaults: self.defaults.clone(),
+            generalized: self.generalized.clone(),
+            modules: self.modules.clone(),
+            private: self.private.clone(),
+        }
+    }
+}
+
+/// What the type variables introduced during inference have been unified
+/// with. Inference variables are `Type::TypeVar`s named `?1`, `?2`, ...,
+/// which W source cannot write; single capital letters are the generic
+/// parameters of definitions.
+#[derive(Debug, Default)]
+struct Substitution {
+    bindings: BTreeMap<String, Type>,
+    next: usize,
+}
+
+/// Whether `name` is an inference variable rather than a generic parameter
+fn is_inference_variable(name: &str) -> bool {
+    name.starts_with('?')
+}
+
+/// Check that a Print template in `arguments` has a placeholder for each
+/// argument after it
+fn check_format_arguments(function: &str, arguments: &[Expression]) -> Result<(), TypeError> {
+    let Some(Expression::InterpolatedString(parts)) = arguments.first() else {
+        return Ok(());
+    };
+    let placeholders = parts.iter().filter(|part| matches!(part, StringPart::Placeholder { .. })).count();
+    if placeholders == 0 || placeholders == arguments.len() - 1 {
+        return Ok(());
+    }
+    Err(TypeError::FormatArguments { function: function.to_string(), placeholders, arguments: arguments.len() - 1 })
+}
+
+/// Whether `ty`, already resolved, is an inference variable nothing has fixed
+fn is_unresolved(ty: &Type) -> bool {
+    matches!(ty, Type::TypeVar(name) if is_inference_variable(name))
+}
+
+/// Whether some part of `ty`, already resolved, is an inference variable
+/// nothing has fixed
+fn has_unresolved(ty: &Type) -> bool {
+    is_unresolved(ty) || type_components(ty).into_iter().any(has_unresolved)
+}
+
+/// Identity of an expression: its position in a pre-order walk (see
+/// `Expression::for_each_child`) of the programs checked, imported modules
+/// first. Two parses of the same source number their nodes alike.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NodeId(usize);
+
+/// Number every expression in `expressions` after those already in `ids`,
+/// which maps each node's address to its id
+pub fn number_nodes(expressions: &[Expression], ids: &mut HashMap<usize, NodeId>) {
+    fn number(expr: &Expression, ids: &mut HashMap<usize, NodeId>) {
+        let id = NodeId(ids.len());
+        ids.insert(expr as *const Expression as usize, id);
+        expr.for_each_child(&mut |child| number(child, ids));
+    }
+    for expr in expressions {
+        number(expr, ids);
+    }
+}
+
+/// The types inference settled on for a program: those of the names it
+/// defines at top level (its functions, as `Type::Function`, and `Let`
+/// bindings), of the functions of the modules it imports, and of each of
+/// its expressions. Code generation uses them instead of guessing from the
+/// syntax. Names defined more than once, names whose type has a part
+/// nothing fixed (`Unknown` or a type variable), and expressions whose type
+/// has a part nothing fixed (`Unknown`) are left out.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TypeTable {
+    bindings: BTreeMap<String, Type>,
+    modules: BTreeMap<String, BTreeMap<String, Type>>,
+    nodes: BTreeMap<NodeId, Type>,
+}
+
+impl TypeTable {
+    /// Type of the top-level function or binding `name`
+    pub fn lookup(&self, name: &str) -> Option<&Type> {
+        self.bindings.get(name)
+    }
+
+    /// Return type of the top-level function `name`
+    pub fn return_type(&self, name: &str) -> Option<&Type> {
+        match self.bindings.get(name) {
+            Some(Type::Function(_, return_type)) => Some(return_type),
+            _ => None,
+        }
+    }
+
+    /// Type of the function `name` of the imported module `module`
+    pub fn module_item(&self, module: &str, name: &str) -> Option<&Type> {
+        self.modules.get(module)?.get(name)
+    }
+
+    /// Type of the expression `id`
+    pub fn node(&self, id: NodeId) -> Option<&Type> {
+        self.nodes.get(&id)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bindings.is_empty() && self.modules.is_empty() && self.nodes.is_empty()
+    }
+}
+
+/// Types inferred for the numbered expressions of the programs checked
+#[derive(Debug, Default)]
+struct NodeTypes {
+    /// Id of each numbered expression, keyed by its address
+    ids: HashMap<usize, NodeId>,
+    /// Inferred type of each expression, resolved when the table is built
+    types: BTreeMap<NodeId, Type>,
+}
+
+/// Whether every part of `ty` is known
+fn is_settled(ty: &Type) -> bool {
+    match ty {
+        Type::Unknown | Type::TypeVar(_) => false,
+        _ => type_components(ty).into_iter().all(is_settled),
+    }
+}
+
+/// Whether no part of `ty`, zonked, is `Unknown`; generic parameters are known
+fn is_known(ty: &Type) -> bool {
+    *ty != Type::Unknown && type_components(ty).into_iter().all(is_known)
+}
+
+/// What a function body returns early, which its return type must fit
+#[derive(Default)]
+struct EarlyReturns {
+    /// Error types of its `Try`s
+    try_errors: Vec<Type>,
+    /// Option or Result types `?` is used on
+    propagated: Vec<Type>,
+    /// The first operator checked arithmetic returns an Err from
+    checked_operator: Option<&'static str>,
+}
+
+/// Type inference engine
+///
+/// Types are inferred by unification: an unannotated lambda or function
+/// parameter, an empty list or the payload of `None` starts as a fresh type
+/// variable, and each use (an argument, an operand, a branch) unifies it with
+/// what is required there. Functions are generalized over the variables left
+/// in their parameter types, so each use instantiates them afresh. Lambdas
+/// bound with `Let` are not: they are generated as Rust closures, which have
+/// one type, so every use of one must agree on its parameter types.
+pub struct TypeInference {
+    env: TypeEnvironment,
+    /// Shared by every nested scope of one inference
+    substitution: Rc<RefCell<Substitution>>,
+    /// Checked before inferring each expression
+    cancellation: Option<CancellationToken>,
+    /// What the function body being inferred returns early, shared with its
+    /// nested scopes; `None` where nothing can (outside functions and inside
+    /// lambdas and kernels)
+    early_returns: Option<Rc<RefCell<EarlyReturns>>>,
+    /// Whether integer arithmetic returns an Err when it overflows or divides
+    /// by zero, as `--arithmetic checked` generates it
+    checked_arithmetic: bool,
+    /// Errors inference continued past, in the order found; shared by every
+    /// nested scope
+    errors: Rc<RefCell<Vec<TypeError>>>,
+    /// Types of the program's functions not yet defined, for calls before
+    /// their definition; each definition unifies with and removes its own
+    declared: BTreeMap<String, Type>,
+    /// Types of the expressions of the programs checked; shared by every
+    /// nested scope
+    nodes: Rc<RefCell<NodeTypes>>,
+    /// Types of the functions of each module checked
+    module_items: BTreeMap<String, BTreeMap<String, Type>>,
+}
+
+impl Default for TypeInference {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TypeInference {
+    pub fn new() -> Self {
+        TypeInference {
+            env: TypeEnvironment::new(),
+            substitution: Rc::new(RefCell::new(Substitution::default())),
+            cancellation: None,
+            early_returns: None,
+            checked_arithmetic: false,
+            errors: Rc::new(RefCell::new(Vec::new())),
+            declared: BTreeMap::new(),
+            nodes: Rc::new(RefCell::new(NodeTypes::default())),
+            module_items: BTreeMap::new(),
+        }
+    }
+
+    /// Stop with `TypeError::Cancelled` once `token` is cancelled
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// Check arithmetic as `--arithmetic checked` generates it: the function
+    /// around integer arithmetic must return a Result its failure can be
+    /// returned in
+    pub fn with_checked_arithmetic(mut self) -> Self {
+        self.checked_arithmetic = true;
+        self
+    }
+
+    /// Inference over `env` for a nested scope, sharing the cancellation token
+    /// and enclosing function
+    fn with_env(&self, env: TypeEnvironment) -> TypeInference {
+        TypeInference {
+            env,
+            substitution: Rc::clone(&self.substitution),
+            cancellation: self.cancellation.clone(),
+            early_returns: self.early_returns.clone(),
+            checked_arithmetic: self.checked_arithmetic,
+            errors: Rc::clone(&self.errors),
+            declared: BTreeMap::new(),
+            nodes: Rc::clone(&self.nodes),
+            module_items: BTreeMap::new(),
+        }
+    }
+
+    /// Inference over `env` for the body of a function, collecting what it
+    /// returns early
+    fn for_function_body(&self, env: TypeEnvironment) -> (TypeInference, Rc<RefCell<EarlyReturns>>) {
+        let early_returns = Rc::new(RefCell::new(EarlyReturns::default()));
+        let inference = TypeInference {
+            env,
+            substitution: Rc::clone(&self.substitution),
+            cancellation: self.cancellation.clone(),
+            early_returns: Some(Rc::clone(&early_returns)),
+            checked_arithmetic: self.checked_arithmetic,
+            errors: Rc::clone(&self.errors),
+            declared: BTreeMap::new(),
+            nodes: Rc::clone(&self.nodes),
+            module_items: BTreeMap::new(),
+        };
+        (inference, early_returns)
+    }
+
+    /// The type of a function's body, with its parameters bound to
+    /// `param_types` and the function itself bound as returning
+    /// `assumed_return`, so it can call itself
+    fn infer_function_body(
+        &self,
+        name: &str,
+        param_types: &[Type],
+        parameters: &[TypeAnnotation],
+        defaults: usize,
+        body: &Expression,
+        assumed_return: Type,
+    ) -> Result<Type, TypeError> {
+        let mut child_env = self.env.child();
+        child_env.bind(name.to_string(), Type::Function(param_types.to_vec(), Box::new(assumed_return)));
+        child_env.define_defaults(name.to_string(), defaults);
+        for (param, param_type) in parameters.iter().zip(param_types) {
+            child_env.bind(param.name.clone(), param_type.clone());
+        }
+
+        let (mut child_inference, early_returns) = self.for_function_body(child_env);
+        let return_type = child_inference.infer(body)?;
+        let early_returns = early_returns.borrow();
+        self.with_early_returns(name, return_type, &early_returns)
+    }
+
+    /// A type variable nothing is known about yet
+    fn fresh(&self) -> Type {
+        let mut substitution = self.substitution.borrow_mut();
+        substitution.next += 1;
+        Type::TypeVar(format!("?{}", substitution.next))
+    }
+
+    /// `ty` with every inference variable unified with something replaced by it
+    fn resolve(&self, ty: &Type) -> Type {
+        map_type(ty, &mut |t| match t {
+            Type::TypeVar(name) if is_inference_variable(name) => {
+                let bound = self.substitution.borrow().bindings.get(name).cloned();
+                bound.map(|bound| self.resolve(&bound))
+            }
+            _ => None,
+        })
+    }
+
+    /// `ty` resolved, with the inference variables still free as `Unknown`
+    fn zonk(&self, ty: &Type) -> Type {
+        map_type(&self.resolve(ty), &mut |t| match t {
+            Type::TypeVar(name) if is_inference_variable(name) => Some(Type::Unknown),
+            _ => None,
+        })
+    }
+
+    /// Make `a` and `b` the same type, binding inference variables in either,
+    /// and return it; `None` if they cannot be. `Unknown` (such as an
+    /// unannotated parameter) and `Never` (a branch that does not return)
+    /// fit anything without fixing a variable.
+    fn unify(&self, a: &Type, b: &Type) -> Option<Type> {
+        let (a, b) = (self.resolve(a), self.resolve(b));
+        let both = |x: &Type, y: &Type| self.unify(x, y).map(Box::new);
+        match (&a, &b) {
+            (Type::TypeVar(x), Type::TypeVar(y)) if x == y => Some(a.clone()),
+            (Type::TypeVar(name), other) | (other, Type::TypeVar(name)) if is_inference_variable(name) => {
+                if matches!(other, Type::Unknown | Type::Never) {
+                    return Some(Type::TypeVar(name.clone()));
+                }
+                if type_variables(other).contains(name) {
+                    return None;
+                }
+                self.substitution.borrow_mut().bindings.insert(name.clone(), other.clone());
+                Some(other.clone())
+            }
+            (Type::Unknown, other) | (other, Type::Unknown) => Some(other.clone()),
+            (Type::Never, other) | (other, Type::Never) => Some(other.clone()),
+            (Type::Option(x), Type::Option(y)) => Some(Type::Option(both(x, y)?)),
+            (Type::List(x), Type::List(y)) => Some(Type::List(both(x, y)?)),
+            (Type::Queue(x), Type::Queue(y)) => Some(Type::Queue(both(x, y)?)),
+            (Type::Heap(x), Type::Heap(y)) => Some(Type::Heap(both(x, y)?)),
+            (Type::Stream(x), Type::Stream(y)) => Some(Type::Stream(both(x, y)?)),
+            (Type::Slice(x), Type::Slice(y)) => Some(Type::Slice(both(x, y)?)),
+            (Type::HashSet(x), Type::HashSet(y)) => Some(Type::HashSet(both(x, y)?)),
+            (Type::BTreeSet(x), Type::BTreeSet(y)) => Some(Type::BTreeSet(both(x, y)?)),
+            (Type::Array(x, n), Type::Array(y, m)) if n == m => Some(Type::Array(both(x, y)?, *n)),
+            (Type::Result(ok1, err1), Type::Result(ok2, err2)) => Some(Type::Result(both(ok1, ok2)?, both(err1, err2)?)),
+            (Type::Map(k1, v1), Type::Map(k2, v2)) => Some(Type::Map(both(k1, k2)?, both(v1, v2)?)),
+            (Type::BTreeMap(k1, v1), Type::BTreeMap(k2, v2)) => Some(Type::BTreeMap(both(k1, k2)?, both(v1, v2)?)),
+            (Type::Tuple(xs), Type::Tuple(ys)) if xs.len() == ys.len() => {
+                xs.iter().zip(ys).map(|(x, y)| self.unify(x, y)).collect::<Option<Vec<_>>>().map(Type::Tuple)
+            }
+            (Type::Function(xs, r1), Type::Function(ys, r2)) if xs.len() == ys.len() => {
+                let params = xs.iter().zip(ys).map(|(x, y)| self.unify(x, y)).collect::<Option<Vec<_>>>()?;
+                Some(Type::Function(params, both(r1, r2)?))
+            }
+            _ if a == b => Some(a.clone()),
+            _ => None,
+        }
+    }
+
+    /// Whether `ty` is `expected`, fixing whichever is not known yet
+    fn is_exactly(&self, ty: &Type, expected: &Type) -> bool {
+        ty == expected || ((is_unresolved(ty) || is_unresolved(expected)) && self.unify(ty, expected).is_some())
+    }
+
+    /// `ty`, or a list of a fresh element type if `ty` is not known yet and
+    /// is used as a collection
+    fn as_list(&self, ty: Type) -> Type {
+        if !is_unresolved(&ty) {
+            return ty;
+        }
+        let list = Type::List(Box::new(self.fresh()));
+        self.unify(&ty, &list);
+        list
+    }
+
+    /// The type of `At[container, key]` (`name` "At") or `Lookup[container,
+    /// key]`: an Option of the element or value found
+    fn infer_lookup(&mut self, name: &str, container: &Expression, key: &Expression) -> Result<Type, TypeError> {
+        let container_type = self.infer(container)?;
+        let container_type = if name == "At" { self.as_list(container_type) } else { container_type };
+        let (key_type, value) = match (name, &container_type) {
+            ("At", Type::Bytes) => (Type::Int32, Type::UInt8),
+            ("At", Type::List(element) | Type::Queue(element)) => (Type::Int32, (**element).clone()),
+            ("Lookup", Type::Map(key, value) | Type::BTreeMap(key, value)) => ((**key).clone(), (**value).clone()),
+            _ => {
+                let expected = if name == "At" {
+                    Type::List(Box::new(Type::Unknown))
+                } else {
+                    Type::Map(Box::new(Type::Unknown), Box::new(Type::Unknown))
+                };
+                return Err(TypeError::TypeMismatch {
+                    expected,
+                    actual: container_type,
+                    context: format!("first argument to {}", name),
+                });
+            }
+        };
+        let index_type = self.infer(key)?;
+        // Any integer can index; widening to usize happens in the generated code
+        let index_ok = if name == "At" {
+            integer_width(&index_type).is_some()
+        } else {
+            self.unify(&key_type, &index_type).is_some()
+        };
+        if !index_ok {
+            return Err(TypeError::TypeMismatch {
+                expected: key_type,
+                actual: index_type,
+                context: format!("second argument to {}", name),
+            });
+        }
+        Ok(Type::Option(Box::new(value)))
+    }
+
+    /// A use of a generalized binding: its generic parameters (and the type
+    /// variables generalized from its parameters) replaced by fresh variables
+    fn instantiate(&self, ty: &Type) -> Type {
+        let fresh: BTreeMap<String, Type> = type_variables(ty)
+            .into_iter()
+            .filter(|name| !is_inference_variable(name))
+            .map(|name| (name, self.fresh()))
+            .collect();
+        map_type(ty, &mut |t| match t {
+            Type::TypeVar(name) => fresh.get(name).cloned(),
+            _ => None,
+        })
+    }
+
+    /// The type of a function or lambda taking `params`, generalized over the
+    /// inference variables in its parameter types that the enclosing scopes
+    /// do not mention; they become the generic parameters `T1`, `T2`, ...
+    /// Variables only in the result stay as they are.
+    fn generalize(&self, params: &[Type], result: &Type) -> Type {
+        let ty = self.resolve(&Type::Function(params.to_vec(), Box::new(result.clone())));
+        let in_scope: BTreeSet<String> = self.env.bindings.values()
+            .flat_map(|bound| type_variables(&self.resolve(bound)))
+            .collect();
+        let mut generic = BTreeMap::new();
+        for param in params {
+            for name in type_variables(&self.resolve(param)) {
+                if is_inference_variable(&name) && !in_scope.contains(&name) && !generic.contains_key(&name) {
+                    let parameter = format!("T{}", generic.len() + 1);
+                    generic.insert(name, Type::TypeVar(parameter));
+                }
+            }
+        }
+        map_type(&ty, &mut |t| match t {
+            Type::TypeVar(name) => generic.get(name).cloned(),
+            _ => None,
+        })
+    }
+
+    /// The return type of `function`, whose body has type `body_type` and
+    /// returns `early_returns`
+    fn with_early_returns(&self, function: &str, body_type: Type, early_returns: &EarlyReturns) -> Result<Type, TypeError> {
+        let mut return_type = self.with_try_errors(function, body_type, &early_returns.try_errors)?;
+        for operand in &early_returns.propagated {
+            return_type = self.with_propagated(function, return_type, operand)?;
+        }
+        let Some(operator) = early_returns.checked_operator else {
+            return Ok(return_type);
+        };
+        // Checked arithmetic returns its failure as a String Err, as Try would
+        match self.with_try_errors(function, return_type, &[Type::String]) {
+            Err(TypeError::TryWithoutResult(Some((function, returns)))) => {
+                Err(TypeError::UncheckedArithmetic { operator: operator.to_string(), function, returns })
+            }
+            Err(TypeError::TypeMismatch { expected, actual, .. }) => Err(TypeError::TypeMismatch {
+                expected,
+                actual,
+                context: format!("error type of checked {} in {}", operator, function),
+            }),
+            return_type => return_type,
+        }
+    }
+
+    /// The return type of `function`, whose body has type `body_type` and uses
+    /// `Try` on Results with `try_errors`: a Result whose error type fits them all
+    fn with_try_errors(&self, function: &str, body_type: Type, try_errors: &[Type]) -> Result<Type, TypeError> {
+        try_errors.iter().try_fold(body_type, |return_type, try_error| match return_type {
+            Type::Result(ok_type, err_type) => match self.unify(&err_type, try_error) {
+                Some(err_type) => Ok(Type::Result(ok_type, Box::new(err_type))),
+                None => Err(TypeError::TypeMismatch {
+                    expected: *err_type,
+                    actual: try_error.clone(),
+                    context: format!("error type of Try in {}", function),
+                }),
+            },
+            Type::Unknown => Ok(Type::Result(Box::new(Type::Unknown), Box::new(try_error.clone()))),
+            Type::TypeVar(name) if is_inference_variable(&name) => {
+                let result = Type::Result(Box::new(self.fresh()), Box::new(try_error.clone()));
+                Ok(self.unify(&Type::TypeVar(name), &result).unwrap_or(result))
+            }
+            other => Err(TypeError::TryWithoutResult(Some((function.to_string(), self.zonk(&other))))),
+        })
+    }
+
+    /// The return type of `function`, of type `return_type` so far, that
+    /// uses `?` on `operand`: an Option if it is one, or like `Try` a Result
+    /// whose error type fits the operand's
+    fn with_propagated(&self, function: &str, return_type: Type, operand: &Type) -> Result<Type, TypeError> {
+        let mismatch = |returns: Type| TypeError::PropagateMismatch {
+            operand: self.zonk(operand),
+            function: Some((function.to_string(), self.zonk(&returns))),
+        };
+        match (operand, return_type) {
+            (Type::Result(_, err_type), return_type) => {
+                self.with_try_errors(function, return_type, &[err_type.as_ref().clone()]).map_err(|error| match error {
+                    TypeError::TryWithoutResult(Some((_, returns))) => mismatch(returns),
+                    TypeError::TypeMismatch { expected, actual, .. } => TypeError::TypeMismatch {
+                        expected,
+                        actual,
+                        context: format!("error type of ? in {}", function),
+                    },
+                    error => error,
+                })
+            }
+            (_, Type::Option(inner)) => Ok(Type::Option(inner)),
+            (_, Type::Unknown) => Ok(Type::Option(Box::new(Type::Unknown))),
+            (_, Type::TypeVar(name)) if is_inference_variable(&name) => {
+                let option = Type::Option(Box::new(self.fresh()));
+                Ok(self.unify(&Type::TypeVar(name), &option).unwrap_or(option))
+            }
+            (_, other) => Err(mismatch(other)),
+        }
+    }
+
+    /// Combine the result type of another branch into the type seen so far.
+    /// Numeric branches widen to their least upper bound, e.g. Int32 and Int64
+    /// give Int64.
+    fn merge_branch_types(&self, so_far: Option<Type>, branch: Type, context: &str) -> Result<Type, TypeError> {
+        let expected = match so_far {
+            None => return Ok(branch),
+            Some(expected) => expected,
+        };
+        if let Some(merged) = self.unify(&expected, &branch) {
+            return Ok(merged);
+        }
+        if is_numeric(&expected) && is_numeric(&branch) {
+            return numeric_lub(&expected, &branch).ok_or_else(|| TypeError::NoCommonNumericType {
+                suggestion: conversion_target(&expected, &branch),
+                first: expected,
+                second: branch,
+                context: context.to_string(),
+            });
+        }
+        Err(TypeError::TypeMismatch {
+            expected,
+            actual: branch,
+            context: context.to_string(),
+        })
+    }
+
+    /// Reject a use of a `Private` definition of an imported module; the
+    /// module's own uses were checked before it was recorded as private
+    fn check_visible(&self, name: &str) -> Result<(), TypeError> {
+        match self.env.private_module(name) {
+            Some(module) => Err(TypeError::PrivateItem { name: name.to_string(), module: module.clone() }),
+            None => Ok(()),
+        }
+    }
+
+    /// The type of a use of `name`, instantiated if it is generalized
+    fn lookup_instance(&self, name: &str) -> Option<Type> {
+        let ty = self.resolve(self.env.lookup(name)?);
+        Some(if self.env.is_generalized(name) { self.instantiate(&ty) } else { ty })
+    }
+
+    /// Access the environment built up by previously inferred expressions
+    pub fn env(&self) -> &TypeEnvironment {
+        &self.env
+    }
+
+    /// The types of the names `expressions`, a program checked with this
+    /// inference, defines at top level, of the functions of the modules
+    /// checked before it, and of the expressions of all of them
+    pub fn type_table(&self, expressions: &[Expression]) -> TypeTable {
+        let mut definitions: BTreeMap<String, usize> = BTreeMap::new();
+        for expr in expressions {
+            let mut names = BTreeSet::new();
+            match expr {
+                Expression::FunctionDefinition { name, .. } => {
+                    names.insert(name.clone());
+                }
+                Expression::LetPattern { pattern, .. } => self.pattern_bindings(pattern, &mut names),
+                _ => {}
+            }
+            for name in names {
+                *definitions.entry(name).or_default() += 1;
+            }
+        }
+        let bindings = definitions.into_iter()
+            .filter(|(_, count)| *count == 1)
+            .filter_map(|(name, _)| {
+                let ty = self.resolve(self.env.lookup(&name)?);
+                is_settled(&ty).then_some((name, ty))
+            })
+            .collect();
+        let modules = self.module_items.iter()
+            .map(|(module, items)| {
+                let items = items.iter()
+                    .map(|(name, ty)| (name.clone(), self.resolve(ty)))
+                    .filter(|(_, ty)| is_settled(ty))
+                    .collect();
+                (module.clone(), items)
+            })
+            .collect();
+        let nodes = self.nodes.borrow().types.iter()
+            .map(|(id, ty)| (*id, self.zonk(ty)))
+            .filter(|(_, ty)| is_known(ty))
+            .collect();
+        TypeTable { bindings, modules, nodes }
+    }
+
+    /// Remember that `expr`, if it belongs to a program being checked, has
+    /// type `ty`
+    fn record(&self, expr: &Expression, ty: &Type) {
+        let mut nodes = self.nodes.borrow_mut();
+        if let Some(&id) = nodes.ids.get(&(expr as *const Expression as usize)) {
+            nodes.types.insert(id, ty.clone());
+        }
+    }
+
+    /// Infer the type of an expression. Type variables nothing has fixed
+    /// yet, such as the payload of a lone `None`, come back as `Unknown`.
+    pub fn infer_expression(&mut self, expr: &Expression) -> Result<Type, TypeError> {
+        let ty = self.infer(expr);
+        self.first_recovered_error()?;
+        Ok(self.zonk(&ty?))
+    }
+
+    /// Check `expr` where a value of type `expected` is wanted, such as a
+    /// parameter's type, and return its type. An empty list, `None` or a
+    /// lambda gets its type from `expected`, and a mismatch inside a literal
+    /// is reported at the element rather than for the whole value.
+    pub fn check_expression(&mut self, expr: &Expression, expected: &Type) -> Result<Type, TypeError> {
+        let actual = self.infer_expecting(expr, expected, "expression");
+        self.first_recovered_error()?;
+        let actual = actual?;
+        let ty = self.unify(expected, &actual).ok_or_else(|| TypeError::TypeMismatch {
+            expected: self.zonk(expected),
+            actual: self.zonk(&actual),
+            context: "expression".to_string(),
+        })?;
+        Ok(self.zonk(&ty))
+    }
+
+    /// The type of `expr`, inferred where a value of type `expected` is
+    /// wanted. List, Option and Result literals check their contents against
+    /// the element or payload type, naming them in errors as part of
+    /// `context`, and a lambda's parameters take the parameter types of an
+    /// expected function before its body is inferred. Comparing the result
+    /// with `expected` is left to the caller.
+    fn infer_expecting(&mut self, expr: &Expression, expected: &Type, context: &str) -> Result<Type, TypeError> {
+        let ty = self.infer_expecting_node(expr, expected, context)?;
+        self.record(expr, &ty);
+        Ok(ty)
+    }
+
+    fn infer_expecting_node(&mut self, expr: &Expression, expected: &Type, context: &str) -> Result<Type, TypeError> {
+        let (value, inner, what) = match (expr, self.resolve(expected)) {
+            (Expression::List(elements), Type::List(element)) if !elements.is_empty() => {
+                let context = format!("element of {}", context);
+                for element_expr in elements {
+                    let actual = self.infer_expecting(element_expr, &element, &context)?;
+                    if self.unify(&element, &actual).is_none() {
+                        return Err(TypeError::TypeMismatch {
+                            expected: self.zonk(&element),
+                            actual: self.zonk(&actual),
+                            context,
+                        });
+                    }
+                }
+                return Ok(Type::List(Box::new(self.resolve(&element))));
+            }
+            (Expression::Lambda { parameters, .. }, Type::Function(params, _)) if parameters.len() == params.len() => {
+                let ret = self.infer_application(expr, &params, context)?;
+                let params = params.iter().map(|param| self.resolve(param)).collect();
+                return Ok(Type::Function(params, Box::new(ret)));
+            }
+            // Literals take the numeric type wanted, as in Rust: an integer
+            // literal any integer type it fits in, a float literal either
+            // float type
+            (Expression::Number(n, _), ty) if is_integer(&ty) => {
+                if !literal_fits(*n, &ty) {
+                    return Err(TypeError::TypeMismatch {
+                        expected: ty.clone(),
+                        actual: literal_type(*n),
+                        context: format!("{} (literal {} is outside the range of {})", context, n, ty),
+                    });
+                }
+                return Ok(ty);
+            }
+            (Expression::Number(n, _), ty @ (Type::Float32 | Type::Float64)) => {
+                return Err(TypeError::TypeMismatch {
+                    expected: ty,
+                    actual: literal_type(*n),
+                    context: format!("{} (write the literal as {}.0)", context, n),
+                });
+            }
+            (Expression::Float(_), ty @ (Type::Float32 | Type::Float64)) => return Ok(ty),
+            // Arithmetic on literals alone, such as `60 * 60`, likewise
+            (Expression::BinaryOp { left, operator, right }, ty)
+                if is_numeric(&ty) && is_arithmetic(operator) && is_numeric_literal(expr) =>
+            {
+                self.infer_expecting(left, &ty, context)?;
+                self.infer_expecting(right, &ty, context)?;
+                return Ok(ty);
+            }
+            (Expression::Some { value }, Type::Option(inner)) => (value, *inner, "Some value"),
+            (Expression::Ok { value }, Type::Result(ok, _)) => (value, *ok, "Ok value"),
+            (Expression::Err { error }, Type::Result(_, err)) => (error, *err, "Err value"),
+            _ => return self.infer(expr),
+        };
+        let actual = self.infer_expecting(value, &inner, &format!("{} of {}", what, context))?;
+        let Some(payload) = self.unify(&inner, &actual) else {
+            return Err(TypeError::TypeMismatch {
+                expected: self.zonk(&inner),
+                actual: self.zonk(&actual),
+                context: format!("{} of {}", what, context),
+            });
+        };
+        Ok(match (expr, self.resolve(expected)) {
+            (Expression::Ok { .. }, Type::Result(_, err)) => Type::Result(Box::new(payload), err),
+            (Expression::Err { .. }, Type::Result(ok, _)) => Type::Result(ok, Box::new(payload)),
+            _ => Type::Option(Box::new(payload)),
+        })
+    }
+
+    /// The type of `expr` with what is known so far about its type variables
+    /// filled in
+    fn infer(&mut self, expr: &Expression) -> Result<Type, TypeError> {
+        let ty = self.infer_node(expr)?;
+        let ty = self.resolve(&ty);
+        // Keys only annotations name are checked where they are written; this
+        // catches the ones inferred from a literal or an operation's result
+        self.check_hashable_keys(&ty)?;
+        if self.checked_arithmetic {
+            self.note_arithmetic(expr, &ty);
+        }
+        self.record(expr, &ty);
+        Ok(ty)
+    }
+
+    /// Under checked arithmetic, note that `expr`, of type `ty`, returns an
+    /// Err from the function around it if it is integer arithmetic that can
+    /// fail. At top level and in closures and kernels, which have no
+    /// function to return from, it panics instead. Arithmetic on literals
+    /// alone is left to constant checking, and floats have nothing to check.
+    fn note_arithmetic(&self, expr: &Expression, ty: &Type) {
+        let Expression::BinaryOp { operator, .. } = expr else {
+            return;
+        };
+        let Some(operator) = checked_operator(operator) else {
+            return;
+        };
+        if is_numeric_literal(expr) || matches!(ty, Type::Float32 | Type::Float64 | Type::String) {
+            return;
+        }
+        if let Some(early_returns) = &self.early_returns {
+            early_returns.borrow_mut().checked_operator.get_or_insert(operator);
+        }
+    }
+
+    fn infer_node(&mut self, expr: &Expression) -> Result<Type, TypeError> {
+        if self.cancellation.as_ref().is_some_and(CancellationToken::is_cancelled) {
+            return Err(TypeError::Cancelled);
+        }
+        match expr {
+            // Literals have known types
+            Expression::Number(n, _) => Ok(literal_type(*n)),
+            Expression::Float(_) => Ok(Type::Float64),
+            Expression::String(_) => Ok(Type::String),
+            Expression::InterpolatedString(parts) => {
+                for part in parts {
+                    if let StringPart::Expression { value, .. } = part {
+                        self.infer(value)?;
+                    }
+                }
+                Ok(Type::String)
+            }
+            Expression::Boolean(_) => Ok(Type::Bool),
+
+            // Tuples
+            Expression::Tuple(elements) => {
+                let mut types = Vec::new();
+                for elem in elements {
+                    types.push(self.infer(elem)?);
+                }
+                Ok(Type::Tuple(types))
+            }
+
+            // Lists
+            Expression::List(elements) => {
+                // All elements have one type; an empty list's is fixed by
+                // how the list is used
+                let mut element_type = self.fresh();
+                for elem in elements {
+                    let elem_type = self.infer(elem)?;
+                    element_type = self.unify(&element_type, &elem_type).ok_or_else(|| TypeError::TypeMismatch {
+                        expected: self.zonk(&element_type),
+                        actual: elem_type,
+                        context: "list elements".to_string(),
+                    })?;
+                }
+                Ok(Type::List(Box::new(element_type)))
+            }
+
+            Expression::Map(entries) => {
+                // Like a list, every key has one type and every value another
+                let (mut key_type, mut value_type) = (self.fresh(), self.fresh());
+                for (key, value) in entries {
+                    let entry_key = self.infer(key)?;
+                    key_type = self.unify(&key_type, &entry_key).ok_or_else(|| TypeError::TypeMismatch {
+                        expected: self.zonk(&key_type),
+                        actual: entry_key,
+                        context: "map keys".to_string(),
+                    })?;
+                    let entry_value = self.infer(value)?;
+                    value_type = self.unify(&value_type, &entry_value).ok_or_else(|| TypeError::TypeMismatch {
+                        expected: self.zonk(&value_type),
+                        actual: entry_value,
+                        context: "map values".to_string(),
+                    })?;
+                }
+                Ok(Type::Map(Box::new(key_type), Box::new(value_type)))
+            }
+
+            // Identifiers look up in environment
+            Expression::Identifier(name) => {
+                self.check_visible(name)?;
+                if let Some(ty) = self.lookup_instance(name) {
+                    return Ok(ty);
+                }
+                // A bare variant is a value, or a constructor function if it has a payload
+                match self.env.lookup_variant(name) {
+                    Some((enum_name, variant)) if variant.fields.is_empty() => Ok(Type::Custom(enum_name.clone())),
+                    Some((enum_name, variant)) => Ok(Type::Function(
+                        variant.fields.clone(),
+                        Box::new(Type::Custom(enum_name.clone())),
+                    )),
+                    None => builtin_function_type(name).ok_or_else(|| TypeError::UndefinedIdentifier(name.clone())),
+                }
+            }
+
+            // A module's definitions are in scope unqualified as well
+            Expression::QualifiedName { path, name } if self.env.is_module(path) => {
+                self.infer(&Expression::Identifier(name.clone())).map_err(|error| qualify_undefined(error, path, name))
+            }
+            // Other qualified names resolve against the builtin registry
+            Expression::QualifiedName { path, name } => {
+                builtins::lookup(path, name)
+                    .map(|builtin| Type::Function(
+                        builtin.parameter_types.clone(),
+                        Box::new(builtin.return_type.clone()),
+                    ))
+                    .ok_or_else(|| TypeError::UndefinedIdentifier(qualified_name(path, name)))
+            }
+
+            // Binary operations
+            Expression::BinaryOp { left, operator, right } => {
+                // A literal operand takes the type of the other, so `x + 1`
+                // adds to an Int64 `x`, for the operators whose operands have
+                // one type
+                let same_types = !matches!(operator, Operator::ShiftLeft | Operator::ShiftRight | Operator::And | Operator::Or);
+                let context = match operator {
+                    Operator::BitAnd | Operator::BitOr | Operator::BitXor => "bitwise operation",
+                    operator if is_arithmetic(operator) => "arithmetic operation",
+                    _ => "comparison operation",
+                };
+                let (mut left_type, mut right_type) = match (is_numeric_literal(left), is_numeric_literal(right)) {
+                    (true, false) if same_types => {
+                        let right_type = self.infer(right)?;
+                        (self.infer_expecting(left, &right_type, context)?, right_type)
+                    }
+                    (false, true) if same_types => {
+                        let left_type = self.infer(left)?;
+                        let right_type = self.infer_expecting(right, &left_type, context)?;
+                        (left_type, right_type)
+                    }
+                    _ => (self.infer(left)?, self.infer(right)?),
+                };
+
+                // An operand whose type is not known yet takes the other's
+                if same_types && (is_unresolved(&left_type) || is_unresolved(&right_type)) {
+                    if let Some(ty) = self.unify(&left_type, &right_type) {
+                        left_type = ty.clone();
+                        right_type = ty;
+                    }
+                }
+
+                match operator {
+                    // Arithmetic operations
+                    Operator::Add | Operator::Subtract | Operator::Multiply | Operator::Divide | Operator::Power => {
+                        // Both operands should be numeric and same type, or
+                        // both Strings for concatenation
+                        // A diverging operand, such as a Panic, takes the other's type
+                        let (left_type, right_type) = match (left_type, right_type) {
+                            (Type::Never, Type::Never) => return Ok(Type::Never),
+                            (Type::Never, other) | (other, Type::Never) => (other.clone(), other),
+                            // The placeholder for a value whose definition did
+                            // not type check fits either way
+                            (Type::Unknown, other) | (other, Type::Unknown) => return Ok(other),
+                            pair => pair,
+                        };
+                        // Numbers or Strings, as later uses decide
+                        if is_unresolved(&left_type) {
+                            return Ok(left_type);
+                        }
+                        let is_concatenation = matches!(operator, Operator::Add) && left_type == Type::String;
+                        if !is_numeric(&left_type) && !is_concatenation {
+                            return Err(TypeError::TypeMismatch {
+                                expected: Type::Int32,
+                                actual: left_type,
+                                context: "arithmetic operation".to_string(),
+                            });
+                        }
+                        if left_type != right_type {
+                            return Err(TypeError::TypeMismatch {
+                                expected: left_type.clone(),
+                                actual: right_type,
+                                context: "arithmetic operation".to_string(),
+                            });
+                        }
+                        Ok(left_type)
+                    }
+
+                    // Comparison operations return bool
+                    Operator::Equals | Operator::NotEquals | Operator::LessThan | Operator::GreaterThan |
+                    Operator::LessEqual | Operator::GreaterEqual => {
+                        // Both operands should have the same type, up to the
+                        // unknown payloads of None / Ok / Err
+                        let Some(left_type) = self.unify(&left_type, &right_type) else {
+                            return Err(TypeError::TypeMismatch {
+                                expected: left_type,
+                                actual: right_type,
+                                context: "comparison operation".to_string(),
+                            });
+                        };
+                        // Ordering additionally needs PartialOrd in the generated Rust
+                        let is_ordering = !matches!(operator, Operator::Equals | Operator::NotEquals);
+                        if is_ordering && !is_unresolved(&left_type) {
+                            if let Some(reason) = unordered_reason(&left_type) {
+                                return Err(TypeError::NotOrdered { type_: left_type, reason });
+                            }
+                        }
+                        Ok(Type::Bool)
+                    }
+
+                    // Bitwise operations are restricted to integers of one type
+                    Operator::BitAnd | Operator::BitOr | Operator::BitXor => {
+                        if is_unresolved(&left_type) {
+                            return Ok(left_type);
+                        }
+                        if !is_integer(&left_type) {
+                            return Err(TypeError::TypeMismatch {
+                                expected: Type::Int32,
+                                actual: left_type,
+                                context: "bitwise operation".to_string(),
+                            });
+                        }
+                        if left_type != right_type {
+                            return Err(TypeError::TypeMismatch {
+                                expected: left_type.clone(),
+                                actual: right_type,
+                                context: "bitwise operation".to_string(),
+                            });
+                        }
+                        Ok(left_type)
+                    }
+
+                    // Shifts keep the left operand's type; the amount may be any integer
+                    Operator::ShiftLeft | Operator::ShiftRight => {
+                        for operand_type in [&left_type, &right_type] {
+                            if !is_integer(operand_type) && !is_unresolved(operand_type) {
+                                return Err(TypeError::TypeMismatch {
+                                    expected: Type::Int32,
+                                    actual: operand_type.clone(),
+                                    context: "shift operation".to_string(),
+                                });
+                            }
+                        }
+                        Ok(left_type)
+                    }
+
+                    // Logical operations take and return bool
+                    Operator::And | Operator::Or => {
+                        for operand_type in [left_type, right_type] {
+                            if !self.is_exactly(&operand_type, &Type::Bool) {
+                                return Err(TypeError::TypeMismatch {
+                                    expected: Type::Bool,
+                                    actual: operand_type,
+                                    context: "logical operation".to_string(),
+                                });
+                            }
+                        }
+                        Ok(Type::Bool)
+                    }
+                }
+            }
+
+            // Function definitions
+            Expression::FunctionDefinition { name, parameters, body, .. } => {
+                for param in parameters {
+                    self.check_hashable_keys(&param.type_)?;
+                }
+                let defaults = self.check_defaults(name, parameters)?;
+
+                // Unannotated parameters start as type variables for the body
+                // to fix, and a recursive function's calls to itself return
+                // one that is unified with what the body returns. A function
+                // called before its definition keeps the types those calls
+                // were checked against
+                let (param_types, return_variable) = match self.declared.remove(name) {
+                    Some(Type::Function(param_types, return_variable)) => {
+                        self.env.unbind(name);
+                        (param_types, *return_variable)
+                    }
+                    _ => (
+                        parameters.iter()
+                            .map(|p| if p.type_ == Type::Unknown { self.fresh() } else { p.type_.clone() })
+                            .collect(),
+                        self.fresh(),
+                    ),
+                };
+                // A body that does not type check is reported, and the
+                // function still bound so that calls to it are checked
+                let body_type = self.infer_function_body(name, &param_types, parameters, defaults, body, return_variable.clone());
+                let body_type = self.recover(body_type)?;
+                let mut return_type = self.unify(&return_variable, &body_type).ok_or_else(|| TypeError::TypeMismatch {
+                    expected: self.zonk(&return_variable),
+                    actual: self.zonk(&body_type),
+                    context: format!("return type of recursive function {}", name),
+                })?;
+                // Still unfixed: every path diverges or calls the function again
+                if return_type == return_variable && body_type != Type::Unknown {
+                    return_type = Type::Never;
+                }
+
+                // Bind the function, generic over the parameter types nothing fixed
+                let func_type = self.generalize(&param_types, &return_type);
+                self.env.bind_generalized(name.clone(), func_type.clone());
+                self.env.define_defaults(name.clone(), defaults);
+
+                Ok(func_type)
+            }
+
+            // A kernel's body is the type of one element of its result, with
+            // each List or Array parameter standing for one of its elements
+            Expression::Kernel { definition } => {
+                let Expression::FunctionDefinition { name, parameters, body, .. } = definition.as_ref() else {
+                    return Err(TypeError::CannotInfer("kernel without a function definition".to_string()));
+                };
+                let invalid = |reason: String| TypeError::InvalidKernel { kernel: name.clone(), reason };
+                let defaults = self.check_defaults(name, parameters)?;
+
+                let mut child_env = self.env.child();
+                let mut has_list = false;
+                for param in parameters {
+                    let element_type = match &param.type_ {
+                        Type::List(element_type) | Type::Array(element_type, _) => {
+                            has_list = true;
+                            element_type.as_ref()
+                        }
+                        other => other,
+                    };
+                    if !is_numeric(element_type) {
+                        return Err(invalid(format!(
+                            "takes {}: {}, but kernel parameters are numbers or Lists and Arrays of numbers",
+                            param.name, param.type_
+                        )));
+                    }
+                    child_env.bind(param.name.clone(), element_type.clone());
+                }
+                if !has_list {
+                    return Err(invalid("has no List or Array parameter to compute over".to_string()));
+                }
+                if let Some(feature) = kernel_unsupported(body) {
+                    return Err(invalid(format!(
+                        "uses {}, but a kernel body is limited to arithmetic, comparisons, If, Convert and Math functions",
+                        feature
+                    )));
+                }
+
+                // A kernel's elements are computed in a loop with no Result
+                let mut child_inference = self.with_env(child_env);
+                child_inference.early_returns = None;
+                let element_type = child_inference.infer(body)?;
+                if !is_numeric(&element_type) {
+                    return Err(invalid(format!("computes {} elements, but kernels compute numbers", element_type)));
+                }
+                let param_types = parameters.iter().map(|p| p.type_.clone()).collect();
+                let func_type = Type::Function(param_types, Box::new(Type::List(Box::new(element_type))));
+                self.env.bind(name.clone(), func_type.clone());
+                self.env.define_defaults(name.clone(), defaults);
+                Ok(func_type)
+            }
+
+            // Function calls
+            Expression::FunctionCall { function, arguments } => {
+                match function.as_ref() {
+                    Expression::Identifier(name) => {
+                        self.check_visible(name)?;
+                        // Check for built-in functions
+                        match name.as_str() {
+                            "Print" | "PrintErr" | "PrintInline" => {
+                                check_format_arguments(name, arguments)?;
+                                // Any argument that type checks can be printed,
+                                // except a stream, which would never finish
+                                for arg in arguments {
+                                    let arg_type = self.infer(arg)?;
+                                    reject_stream(&arg_type, name)?;
+                                }
+                                Ok(Type::Tuple(vec![])) // Unit type ()
+                            }
+                            "Tuple" => {
+                                let mut types = Vec::new();
+                                for arg in arguments {
+                                    types.push(self.infer(arg)?);
+                                }
+                                Ok(Type::Tuple(types))
+                            }
+                            "Map" | "Filter" => {
+                                if arguments.len() != 2 {
+                                    return Err(TypeError::ArityMismatch {
+                                        function: name.clone(),
+                                        expected: 2,
+                                        actual: arguments.len(),
+                                    });
+                                }
+                                // Over a stream the result stays lazy
+                                let collection = self.infer(&arguments[1])?;
+                                if let Type::Stream(element) = collection {
+                                    if name == "Filter" {
+                                        return Ok(Type::Stream(element));
+                                    }
+                                    let result = self.infer_application(&arguments[0], &[*element], "Map function")?;
+                                    return Ok(Type::Stream(Box::new(result)));
+                                }
+                                let element = match collection {
+                                    Type::List(element)
+                                    | Type::Array(element, _)
+                                    | Type::Slice(element)
+                                    | Type::Queue(element)
+                                    | Type::HashSet(element)
+                                    | Type::BTreeSet(element) => *element,
+                                    Type::Unknown => Type::Unknown,
+                                    ty if is_unresolved(&ty) => {
+                                        let element = self.fresh();
+                                        self.unify(&ty, &Type::List(Box::new(element.clone())));
+                                        element
+                                    }
+                                    actual => {
+                                        return Err(TypeError::TypeMismatch {
+                                            expected: Type::List(Box::new(Type::Unknown)),
+                                            actual: self.zonk(&actual),
+                                            context: format!("list passed to {}", name),
+                                        })
+                                    }
+                                };
+                                if name == "Filter" {
+                                    let keep = self.infer_application(&arguments[0], std::slice::from_ref(&element), "Filter predicate")?;
+                                    if self.unify(&keep, &Type::Bool).is_none() {
+                                        return Err(TypeError::TypeMismatch {
+                                            expected: Type::Bool,
+                                            actual: self.zonk(&keep),
+                                            context: "Filter predicate".to_string(),
+                                        });
+                                    }
+                                    return Ok(Type::List(Box::new(self.resolve(&element))));
+                                }
+                                let result = self.infer_application(&arguments[0], &[element], "Map function")?;
+                                Ok(Type::List(Box::new(result)))
+                            }
+                            "Fold" => {
+                                // Fold returns the accumulator type
+                                if arguments.len() != 3 {
+                                    return Err(TypeError::ArityMismatch {
+                                        function: name.clone(),
+                                        expected: 3,
+                                        actual: arguments.len(),
+                                    });
+                                }
+                                if let Ok(list_type) = self.infer(&arguments[2]) {
+                                    reject_stream(&list_type, "Fold")?;
+                                }
+                                // Return type is the type of the initial value
+                                self.infer(&arguments[1])
+                            }
+                            "SortWith" => {
+                                if arguments.len() != 2 {
+                                    return Err(TypeError::ArityMismatch {
+                                        function: name.clone(),
+                                        expected: 2,
+                                        actual: arguments.len(),
+                                    });
+                                }
+                                let element = match self.infer(&arguments[1])? {
+                                    Type::List(element) => *element,
+                                    Type::Stream(_) => return Err(TypeError::UnboundedStream("SortWith".to_string())),
+                                    other => {
+                                        return Err(TypeError::TypeMismatch {
+                                            expected: Type::List(Box::new(Type::Unknown)),
+                                            actual: other,
+                                            context: "list passed to SortWith".to_string(),
+                                        })
+                                    }
+                                };
+                                self.check_comparator(&arguments[0], &element)?;
+                                Ok(Type::List(Box::new(element)))
+                            }
+                            "Repeat" => {
+                                if arguments.len() != 1 {
+                                    return Err(TypeError::ArityMismatch {
+                                        function: name.clone(),
+                                        expected: 1,
+                                        actual: arguments.len(),
+                                    });
+                                }
+                                Ok(Type::Stream(Box::new(self.infer(&arguments[0])?)))
+                            }
+                            "Iterate" => {
+                                // Iterate[f, seed]: seed, f[seed], f[f[seed]], ...
+                                if arguments.len() != 2 {
+                                    return Err(TypeError::ArityMismatch {
+                                        function: name.clone(),
+                                        expected: 2,
+                                        actual: arguments.len(),
+                                    });
+                                }
+                                let seed = self.infer(&arguments[1])?;
+                                let next = self.infer_application(&arguments[0], std::slice::from_ref(&seed), "Iterate function")?;
+                                let element = self.unify(&seed, &next).ok_or_else(|| TypeError::TypeMismatch {
+                                    expected: seed.clone(),
+                                    actual: next,
+                                    context: "Iterate function result (must match the seed)".to_string(),
+                                })?;
+                                Ok(Type::Stream(Box::new(element)))
+                            }
+                            "Take" => {
+                                if arguments.len() != 2 {
+                                    return Err(TypeError::ArityMismatch {
+                                        function: name.clone(),
+                                        expected: 2,
+                                        actual: arguments.len(),
+                                    });
+                                }
+                                let count = self.infer(&arguments[0])?;
+                                if !self.is_exactly(&count, &Type::Int32) {
+                                    return Err(TypeError::TypeMismatch {
+                                        expected: Type::Int32,
+                                        actual: count,
+                                        context: "count passed to Take".to_string(),
+                                    });
+                                }
+                                match self.infer(&arguments[1])? {
+                                    Type::Stream(element) | Type::List(element) => Ok(Type::List(element)),
+                                    other => Err(TypeError::TypeMismatch {
+                                        expected: Type::Stream(Box::new(Type::Unknown)),
+                                        actual: other,
+                                        context: "Take".to_string(),
+                                    }),
+                                }
+                            }
+                            "Range" => {
+                                // Range[min, max] is every integer from min to max, inclusive
+                                if arguments.len() != 2 {
+                                    return Err(TypeError::ArityMismatch {
+                                        function: name.clone(),
+                                        expected: 2,
+                                        actual: arguments.len(),
+                                    });
+                                }
+                                let min = self.infer(&arguments[0])?;
+                                if !is_integer(&min) {
+                                    return Err(TypeError::TypeMismatch {
+                                        expected: Type::Int32,
+                                        actual: min,
+                                        context: "Range bound".to_string(),
+                                    });
+                                }
+                                let max = self.infer(&arguments[1])?;
+                                if max != min {
+                                    return Err(TypeError::TypeMismatch {
+                                        expected: min,
+                                        actual: max,
+                                        context: "Range bound".to_string(),
+                                    });
+                                }
+                                Ok(Type::List(Box::new(min)))
+                            }
+                            "Lines" => {
+                                // Lines[] reads stdin lazily, one line per element
+                                self.check_builtin_arguments(name, arguments, &[])?;
+                                Ok(Type::Stream(Box::new(Type::String)))
+                            }
+                            "CharCount" => {
+                                self.check_builtin_arguments(name, arguments, &[Type::String])?;
+                                Ok(Type::Int32)
+                            }
+                            "ToUpper" | "ToLower" => {
+                                self.check_builtin_arguments(name, arguments, &[Type::String])?;
+                                Ok(Type::String)
+                            }
+                            "Discard" => {
+                                // The value is checked, then dropped
+                                if arguments.len() != 1 {
+                                    return Err(TypeError::ArityMismatch {
+                                        function: name.clone(),
+                                        expected: 1,
+                                        actual: arguments.len(),
+                                    });
+                                }
+                                self.infer(&arguments[0])?;
+                                Ok(Type::Tuple(vec![]))
+                            }
+                            "Panic" => {
+                                self.check_builtin_arguments(name, arguments, &[Type::String])?;
+                                Ok(Type::Never)
+                            }
+                            "Exit" => {
+                                self.check_builtin_arguments(name, arguments, &[Type::Int32])?;
+                                Ok(Type::Never)
+                            }
+                            "Substring" => {
+                                self.check_builtin_arguments(name, arguments, &[Type::String, Type::Int32, Type::Int32])?;
+                                Ok(Type::String)
+                            }
+                            "Chars" => {
+                                self.check_builtin_arguments(name, arguments, &[Type::String])?;
+                                Ok(Type::List(Box::new(Type::Char)))
+                            }
+                            "Bytes" => {
+                                // Integer literals must fit in a byte; anything else must already be UInt8
+                                for arg in arguments {
+                                    if let Expression::Number(n, _) = arg {
+                                        if u8::try_from(*n).is_err() {
+                                            return Err(TypeError::TypeMismatch {
+                                                expected: Type::UInt8,
+                                                actual: literal_type(*n),
+                                                context: format!("byte literal {} (must be 0 to 255)", n),
+                                            });
+                                        }
+                                        continue;
+                                    }
+                                    let arg_type = self.infer(arg)?;
+                                    if !self.is_exactly(&arg_type, &Type::UInt8) {
+                                        return Err(TypeError::TypeMismatch {
+                                            expected: Type::UInt8,
+                                            actual: arg_type,
+                                            context: "argument to Bytes".to_string(),
+                                        });
+                                    }
+                                }
+                                Ok(Type::Bytes)
+                            }
+                            "ReadFileBytes" => {
+                                self.check_builtin_arguments(name, arguments, &[Type::String])?;
+                                Ok(Type::Result(Box::new(Type::Bytes), Box::new(Type::String)))
+                            }
+                            "At" | "Lookup" => {
+                                if arguments.len() != 2 {
+                                    return Err(TypeError::ArityMismatch {
+                                        function: name.clone(),
+                                        expected: 2,
+                                        actual: arguments.len(),
+                                    });
+                                }
+                                self.infer_lookup(name, &arguments[0], &arguments[1])
+                            }
+                            "Stack" => {
+                                let element = self.infer_element_type(name, arguments)?;
+                                Ok(Type::List(Box::new(element)))
+                            }
+                            "Queue" => {
+                                let element = self.infer_element_type(name, arguments)?;
+                                Ok(Type::Queue(Box::new(element)))
+                            }
+                            "Heap" => {
+                                let element = self.infer_element_type(name, arguments)?;
+                                check_heap_element(&element)?;
+                                Ok(Type::Heap(Box::new(element)))
+                            }
+                            "HeapPush" => {
+                                if arguments.len() != 2 {
+                                    return Err(TypeError::ArityMismatch {
+                                        function: name.clone(),
+                                        expected: 2,
+                                        actual: arguments.len(),
+                                    });
+                                }
+                                let heap_type = self.infer(&arguments[0])?;
+                                let Type::Heap(element) = heap_type else {
+                                    return Err(TypeError::TypeMismatch {
+                                        expected: Type::Heap(Box::new(Type::Unknown)),
+                                        actual: heap_type,
+                                        context: "first argument to HeapPush".to_string(),
+                                    });
+                                };
+                                let value_type = self.infer(&arguments[1])?;
+                                let Some(element) = self.unify(&element, &value_type) else {
+                                    return Err(TypeError::TypeMismatch {
+                                        expected: *element,
+                                        actual: value_type,
+                                        context: "value pushed".to_string(),
+                                    });
+                                };
+                                check_heap_element(&element)?;
+                                Ok(Type::Tuple(vec![]))
+                            }
+                            "HeapPop" => {
+                                if arguments.len() != 1 {
+                                    return Err(TypeError::ArityMismatch {
+                                        function: name.clone(),
+                                        expected: 1,
+                                        actual: arguments.len(),
+                                    });
+                                }
+                                match self.infer(&arguments[0])? {
+                                    Type::Heap(element) => Ok(Type::Option(element)),
+                                    other => Err(TypeError::TypeMismatch {
+                                        expected: Type::Heap(Box::new(Type::Unknown)),
+                                        actual: other,
+                                        context: "argument to HeapPop".to_string(),
+                                    }),
+                                }
+                            }
+                            "Push" => {
+                                if arguments.len() != 2 {
+                                    return Err(TypeError::ArityMismatch {
+                                        function: name.clone(),
+                                        expected: 2,
+                                        actual: arguments.len(),
+                                    });
+                                }
+                                let collection_type = self.infer(&arguments[0])?;
+                                let collection_type = self.as_list(collection_type);
+                                let element = match &collection_type {
+                                    Type::List(element) | Type::Queue(element) => (**element).clone(),
+                                    _ => {
+                                        return Err(TypeError::TypeMismatch {
+                                            expected: Type::List(Box::new(Type::Unknown)),
+                                            actual: collection_type,
+                                            context: "first argument to Push (a Stack or Queue)".to_string(),
+                                        })
+                                    }
+                                };
+                                let value_type = self.infer_expecting(&arguments[1], &element, "value pushed")?;
+                                if self.unify(&element, &value_type).is_none() {
+                                    return Err(TypeError::TypeMismatch {
+                                        expected: element,
+                                        actual: value_type,
+                                        context: "value pushed".to_string(),
+                                    });
+                                }
+                                Ok(Type::Tuple(vec![]))
+                            }
+                            "Pop" | "PopFront" => {
+                                if arguments.len() != 1 {
+                                    return Err(TypeError::ArityMismatch {
+                                        function: name.clone(),
+                                        expected: 1,
+                                        actual: arguments.len(),
+                                    });
+                                }
+                                // Stacks pop from the back, queues from the front
+                                let collection_type = self.infer(&arguments[0])?;
+                                let collection_type = if name == "Pop" { self.as_list(collection_type) } else { collection_type };
+                                match (name.as_str(), collection_type) {
+                                    ("Pop", Type::List(element)) | ("PopFront", Type::Queue(element)) => {
+                                        Ok(Type::Option(element))
+                                    }
+                                    (_, other) => Err(TypeError::TypeMismatch {
+                                        expected: if name == "Pop" {
+                                            Type::List(Box::new(Type::Unknown))
+                                        } else {
+                                            Type::Queue(Box::new(Type::Unknown))
+                                        },
+                                        actual: other,
+                                        context: format!("argument to {}", name),
+                                    }),
+                                }
+                            }
+                            _ => {
+                                // Check if it's an enum variant constructor
+                                if let Some((enum_name, variant)) = self.env.lookup_variant(name) {
+                                    let (enum_name, fields) = (enum_name.clone(), variant.fields.clone());
+                                    if fields.len() != arguments.len() {
+                                        return Err(TypeError::ArityMismatch {
+                                            function: name.clone(),
+                                            expected: fields.len(),
+                                            actual: arguments.len(),
+                                        });
+                                    }
+                                    let context = format!("argument to {}", name);
+                                    for (arg, field_type) in arguments.iter().zip(&fields) {
+                                        let arg_type = self.infer_expecting(arg, field_type, &context)?;
+                                        if self.unify(&arg_type, field_type).is_none() {
+                                            return Err(TypeError::TypeMismatch {
+                                                expected: field_type.clone(),
+                                                actual: self.zonk(&arg_type),
+                                                context,
+                                            });
+                                        }
+                                    }
+                                    return Ok(Type::Custom(enum_name));
+                                }
+
+                                // Check if it's a struct constructor
+                                if self.env.lookup_struct(name).is_some() {
+                                    return self.infer_struct_instantiation(name, arguments);
+                                }
+
+                                // Trait methods are called with the receiver first: ToText[p]
+                                if self.env.lookup(name).is_none() {
+                                    if let Some((trait_name, method)) = self.env.lookup_trait_method(name) {
+                                        let (trait_name, method) = (trait_name.clone(), method.clone());
+                                        return self.check_method_call(&trait_name, &method, arguments);
+                                    }
+                                }
+
+                                // Look up user-defined function
+                                if let Some(func_type) = self.lookup_instance(name) {
+                                    match func_type {
+                                        Type::Function(param_types, return_type) => {
+                                            // Trailing parameters with defaults may be left out
+                                            let required = param_types.len() - self.env.lookup_defaults(name);
+                                            if arguments.len() < required || arguments.len() > param_types.len() {
+                                                return Err(TypeError::ArityMismatch {
+                                                    function: name.clone(),
+                                                    expected: param_types.len(),
+                                                    actual: arguments.len(),
+                                                });
+                                            }
+                                            // Check argument types; a generic function's type
+                                            // variables were instantiated by the lookup
+                                            let context = format!("argument to {}", name);
+                                            for (arg, expected_type) in arguments.iter().zip(param_types.iter()) {
+                                                let arg_type = self.infer_expecting(arg, expected_type, &context)?;
+                                                if self.unify(expected_type, &arg_type).is_none()
+                                                    && !self.satisfies_trait(expected_type, &arg_type)
+                                                {
+                                                    return Err(TypeError::TypeMismatch {
+                                                        expected: self.zonk(expected_type),
+                                                        actual: self.zonk(&arg_type),
+                                                        context,
+                                                    });
+                                                }
+                                            }
+                                            Ok(self.resolve(&return_type))
+                                        }
+                                        // Calling a value whose type is not known yet, such as
+                                        // an unannotated lambda parameter, makes it a function
+                                        Type::TypeVar(variable) if is_inference_variable(&variable) => {
+                                            let argument_types =
+                                                arguments.iter().map(|arg| self.infer(arg)).collect::<Result<Vec<_>, _>>()?;
+                                            let return_type = self.fresh();
+                                            let function_type = Type::Function(argument_types, Box::new(return_type.clone()));
+                                            self.unify(&Type::TypeVar(variable), &function_type);
+                                            Ok(self.resolve(&return_type))
+                                        }
+                                        // A definition that did not type check
+                                        Type::Unknown => {
+                                            for arg in arguments {
+                                                self.infer(arg)?;
+                                            }
+                                            Ok(Type::Unknown)
+                                        }
+                                        // `xs[i]` on a value that can be indexed is At[xs, i]
+                                        Type::List(_) | Type::Queue(_) | Type::Bytes if arguments.len() == 1 => {
+                                            self.infer_lookup("At", function, &arguments[0])
+                                        }
+                                        _ => Err(TypeError::TypeMismatch {
+                                            expected: Type::Function(vec![], Box::new(Type::Int32)),
+                                            actual: func_type.clone(),
+                                            context: format!("{} is not a function", name),
+                                        }),
+                                    }
+                                } else {
+                                    Err(TypeError::UndefinedIdentifier(name.clone()))
+                                }
+                            }
+                        }
+                    }
+                    Expression::QualifiedName { path, name } if self.env.is_module(path) => {
+                        let function = Box::new(Expression::Identifier(name.clone()));
+                        self.infer(&Expression::FunctionCall { function, arguments: arguments.clone() })
+                            .map_err(|error| qualify_undefined(error, path, name))
+                    }
+                    Expression::QualifiedName { path, name } => {
+                        let qualified = qualified_name(path, name);
+                        let builtin = builtins::lookup(path, name)
+                            .ok_or_else(|| TypeError::UndefinedIdentifier(qualified.clone()))?;
+                        if builtin.arity() != arguments.len() {
+                            return Err(TypeError::ArityMismatch {
+                                function: qualified,
+                                expected: builtin.arity(),
+                                actual: arguments.len(),
+                            });
+                        }
+                        for (arg, parameter_type) in arguments.iter().zip(&builtin.parameter_types) {
+                            // Integer literals are emitted as floats for float parameters
+                            let literal_coerces = matches!(arg, Expression::Number(..))
+                                && *parameter_type == Type::Float64;
+                            let arg_type = self.infer(arg)?;
+                            if self.unify(&arg_type, parameter_type).is_none() && !literal_coerces {
+                                return Err(TypeError::TypeMismatch {
+                                    expected: parameter_type.clone(),
+                                    actual: arg_type,
+                                    context: format!("argument to {}", qualified),
+                                });
+                            }
+                        }
+                        Ok(builtin.return_type.clone())
+                    }
+                    _ => Err(TypeError::CannotInfer("complex function expression".to_string())),
+                }
+            }
+
+            // Struct definitions
+            Expression::StructDefinition { name, fields, .. } => {
+                self.env.define_struct(name.clone(), fields.clone());
+                if let Some(cycle) = self.env.recursive_cycle(name) {
+                    return Err(TypeError::RecursiveType(cycle));
+                }
+                for field in fields {
+                    self.check_hashable_keys(&field.type_)?;
+                }
+                Ok(Type::Tuple(vec![])) // Struct definitions return unit type
+            }
+
+            Expression::TraitDefinition { name, methods, .. } => {
+                self.env.define_trait(name.clone(), methods.clone());
+                Ok(Type::Tuple(vec![]))
+            }
+
+            Expression::Impl { trait_name, type_name, methods } => {
+                self.check_impl(trait_name, type_name, methods)?;
+                Ok(Type::Tuple(vec![]))
+            }
+
+            Expression::FieldAccess { object, field } => {
+                let object_type = self.infer(object)?;
+                let fields = match &object_type {
+                    Type::Custom(name) => self.env.lookup_struct(name),
+                    _ => None,
+                };
+                fields
+                    .and_then(|fields| fields.iter().find(|f| f.name == *field))
+                    .map(|f| f.type_.clone())
+                    .ok_or_else(|| TypeError::UndefinedField { type_: object_type, field: field.clone() })
+            }
+
+            // Enum definitions
+            // Imported definitions are checked with their own file
+            Expression::Import(_) => Ok(Type::Tuple(vec![])),
+
+            Expression::EnumDefinition { name, variants, .. } => {
+                self.env.define_enum(name.clone(), variants.clone());
+                if let Some(cycle) = self.env.recursive_cycle(name) {
+                    return Err(TypeError::RecursiveType(cycle));
+                }
+                for field_type in variants.iter().flat_map(|v| &v.fields) {
+                    self.check_hashable_keys(field_type)?;
+                }
+                Ok(Type::Tuple(vec![]))
+            }
+
+            // Other expressions
+            Expression::None => Ok(Type::Option(Box::new(self.fresh()))),
+            Expression::Some { value } => {
+                let inner_type = self.infer(value)?;
+                Ok(Type::Option(Box::new(inner_type)))
+            }
+            Expression::Ok { value } => {
+                let ok_type = self.infer(value)?;
+                Ok(Type::Result(Box::new(ok_type), Box::new(Type::String)))
+            }
+            Expression::Err { error } => {
+                let err_type = self.infer(error)?;
+                Ok(Type::Result(Box::new(self.fresh()), Box::new(err_type)))
+            }
+
+            // Match expression with pattern matching
+            Expression::Match { value, arms } => {
+                // Infer the type of the value being matched
+                let value_type = self.infer(value)?;
+
+                if arms.is_empty() {
+                    return Err(TypeError::CannotInfer("match with no arms".to_string()));
+                }
+
+                // Check each arm and collect result types
+                let mut result_type: Option<Type> = None;
+
+                for (pattern, result_expr) in arms {
+                    // Create child environment for pattern bindings
+                    let mut child_env = self.env.child();
+
+                    // Check pattern against value type and collect bindings
+                    self.check_pattern(pattern, &value_type, &mut child_env)?;
+
+                    // Infer result type in the child environment
+                    let mut child_inference = self.with_env(child_env);
+                    let arm_result_type = child_inference.infer(result_expr)?;
+
+                    // Ensure all arms return the same type
+                    result_type = Some(self.merge_branch_types(result_type, arm_result_type, "match arm result")?);
+                }
+
+                // rustc rejects a match that misses a case, so report it here
+                // in terms of the W patterns
+                let patterns: Vec<&Pattern> = arms.iter().map(|(pattern, _)| pattern).collect();
+                exhaustiveness::check_match(&self.env, &self.zonk(&value_type), &patterns)?;
+
+                Ok(result_type.unwrap())
+            }
+
+            // Pattern bindings are visible in the then branch only
+            Expression::IfLet { pattern, value, then_branch, else_branch } => {
+                check_no_string_pattern(pattern, "IfLet")?;
+                let value_type = self.infer(value)?;
+                let mut child_env = self.env.child();
+                self.check_pattern(pattern, &value_type, &mut child_env)?;
+                let mut child_inference = self.with_env(child_env);
+                let then_type = child_inference.infer(then_branch)?;
+                match else_branch {
+                    Some(else_branch) => {
+                        let else_type = self.infer(else_branch)?;
+                        self.merge_branch_types(Some(then_type), else_type, "IfLet branch")
+                    }
+                    None => Ok(Type::Tuple(vec![])),
+                }
+            }
+
+            // Pattern bindings are visible in the loop body only
+            Expression::WhileLet { pattern, value, body } => {
+                check_no_string_pattern(pattern, "WhileLet")?;
+                let value_type = self.infer(value)?;
+                let mut child_env = self.env.child();
+                self.check_pattern(pattern, &value_type, &mut child_env)?;
+                let mut child_inference = self.with_env(child_env);
+                child_inference.infer(body)?;
+                Ok(Type::Tuple(vec![]))
+            }
+
+            // The loop variable is bound to each element inside the body only
+            Expression::For { variable, iterable, body } => {
+                let iterable_type = self.infer(iterable)?;
+                let iterable_type = self.as_list(iterable_type);
+                // A stream is consumed one element at a time, so it may be
+                // unbounded (like the lines of stdin)
+                let element_type = match iterable_type {
+                    Type::List(element) | Type::Queue(element) | Type::Heap(element) | Type::Stream(element)
+                    | Type::Array(element, _) | Type::Slice(element) | Type::HashSet(element) | Type::BTreeSet(element) => {
+                        *element
+                    }
+                    Type::Bytes => Type::UInt8,
+                    Type::Map(key, value) | Type::BTreeMap(key, value) => Type::Tuple(vec![*key, *value]),
+                    other => {
+                        return Err(TypeError::TypeMismatch {
+                            expected: Type::List(Box::new(Type::Unknown)),
+                            actual: other,
+                            context: "For loop collection".to_string(),
+                        })
+                    }
+                };
+                let mut child_env = self.env.child();
+                child_env.bind(variable.clone(), element_type);
+                let mut child_inference = self.with_env(child_env);
+                child_inference.infer(body)?;
+                Ok(Type::Tuple(vec![]))
+            }
+
+            // Conditional expression
+            Expression::Cond { conditions, default_statements, from_if } => {
+                let mut result_type: Option<Type> = None;
+
+                // Check each condition
+                for (condition, statements) in conditions {
+                    let cond_type = self.infer(condition)?;
+                    if !self.is_exactly(&cond_type, &Type::Bool) {
+                        return Err(TypeError::NonBoolCondition(cond_type));
+                    }
+
+                    let stmt_type = self.infer(statements)?;
+                    result_type = Some(self.merge_branch_types(result_type, stmt_type, "cond branch")?);
+                }
+
+                // Check default branch if present
+                if let Some(default) = default_statements {
+                    let default_type = self.infer(default)?;
+                    result_type = Some(self.merge_branch_types(result_type, default_type, if *from_if { "If else branch" } else { "cond default branch" })?);
+                }
+
+                Ok(result_type.unwrap_or(Type::Tuple(vec![])))
+            }
+
+            Expression::Not { expr } => {
+                let operand_type = self.infer(expr)?;
+                if !self.is_exactly(&operand_type, &Type::Bool) {
+                    return Err(TypeError::TypeMismatch {
+                        expected: Type::Bool,
+                        actual: operand_type,
+                        context: "! operator".to_string(),
+                    });
+                }
+                Ok(Type::Bool)
+            }
+
+            // Error propagation operator ?; like Try, the enclosing
+            // function's return type is checked once its body is inferred
+            Expression::Propagate { expr } => {
+                let inner_type = self.infer(expr)?;
+                if matches!(inner_type, Type::Option(_) | Type::Result(..)) {
+                    let Some(early_returns) = &self.early_returns else {
+                        return Err(TypeError::PropagateMismatch { operand: self.zonk(&inner_type), function: None });
+                    };
+                    early_returns.borrow_mut().propagated.push(inner_type.clone());
+                }
+                match inner_type {
+                    Type::Option(inner) => Ok(*inner),
+                    Type::Result(ok_type, _) => Ok(*ok_type),
+                    _ => Err(TypeError::TypeMismatch {
+                        expected: Type::Option(Box::new(Type::Int32)),
+                        actual: inner_type,
+                        context: "? operator requires Option or Result type".to_string(),
+                    }),
+                }
+            }
+
+            // Result propagation; the enclosing function's return type is
+            // checked against the error types once its body is inferred
+            Expression::Try { expr } => {
+                let Some(early_returns) = self.early_returns.clone() else {
+                    return Err(TypeError::TryWithoutResult(None));
+                };
+                match self.infer(expr)? {
+                    Type::Result(ok_type, err_type) => {
+                        early_returns.borrow_mut().try_errors.push(*err_type);
+                        Ok(*ok_type)
+                    }
+                    Type::Unknown => Ok(Type::Unknown),
+                    other => Err(TypeError::TypeMismatch {
+                        expected: Type::Result(Box::new(Type::Unknown), Box::new(Type::Unknown)),
+                        actual: other,
+                        context: "Try".to_string(),
+                    }),
+                }
+            }
+
+            // Any numeric type converts to any other; range is checked at run time
+            Expression::Convert { value, target } => {
+                let from = self.infer(value)?;
+                if (is_numeric(&from) || from == Type::Unknown || is_unresolved(&from)) && is_numeric(target) {
+                    Ok(target.clone())
+                } else {
+                    Err(TypeError::InvalidConversion { from, to: target.clone() })
+                }
+            }
+
+            // Local bindings are visible only in the body
+            Expression::Let { name, value, body } => {
+                let value_type = self.infer(value)?;
+                let mut child_env = self.env.child();
+                child_env.bind(name.clone(), value_type);
+                let mut child_inference = self.with_env(child_env);
+                child_inference.infer(body)
+            }
+
+            // Destructuring bindings stay visible to the following statements
+            Expression::LetPattern { pattern, value, fallback } => {
+                self.infer_let_pattern(pattern, value, fallback.as_deref())?;
+                Ok(Type::Tuple(vec![]))
+            }
+
+            // Statements run in a scope of their own. A LetElse fallback
+            // leaves the block with its value, so it has the block's type.
+            Expression::Block(statements) => {
+                let mut block = self.with_env(self.env.child());
+                let mut value_type = Type::Tuple(vec![]);
+                let mut fallback_types = Vec::new();
+                for statement in statements {
+                    value_type = match statement {
+                        Expression::LetPattern { pattern, value, fallback } => {
+                            fallback_types.extend(block.infer_let_pattern(pattern, value, fallback.as_deref())?);
+                            Type::Tuple(vec![])
+                        }
+                        _ => block.infer(statement)?,
+                    };
+                }
+                for fallback_type in fallback_types {
+                    value_type = self.unify(&value_type, &fallback_type).ok_or_else(|| TypeError::TypeMismatch {
+                        expected: self.resolve(&value_type),
+                        actual: self.resolve(&fallback_type),
+                        context: "LetElse fallback in Block".to_string(),
+                    })?;
+                }
+                Ok(value_type)
+            }
+
+            // Unannotated parameters start as type variables for the body to fix
+            Expression::Lambda { parameters, body } => {
+                let param_types: Vec<Type> = parameters.iter()
+                    .map(|p| if p.type_ == Type::Unknown { self.fresh() } else { p.type_.clone() })
+                    .collect();
+                let mut child_env = self.env.child();
+                for (param, param_type) in parameters.iter().zip(&param_types) {
+                    child_env.bind(param.name.clone(), param_type.clone());
+                }
+                // A closure cannot return early from the function around it
+                let mut child_inference = self.with_env(child_env);
+                child_inference.early_returns = None;
+                let return_type = child_inference.infer(body)?;
+                Ok(Type::Function(param_types, Box::new(return_type)))
+            }
+
+            // Not yet implemented
+            Expression::Program(_) => Err(TypeError::CannotInfer("program".to_string())),
+            Expression::LogCall { .. } => Ok(Type::Tuple(vec![])),
+            Expression::StructInstantiation { struct_name, field_values } => {
+                self.infer_struct_instantiation(struct_name, field_values)
+            }
+        }
+    }
+
+    /// Check that every Map key and HashSet element type inside `ty` is hashable
+    fn check_hashable_keys(&self, ty: &Type) -> Result<(), TypeError> {
+        match ty {
+            Type::Map(key, value) => {
+                if let Some(reason) = self.unhashable_reason(key, &mut BTreeSet::new()) {
+                    return Err(TypeError::UnhashableKey { key_type: (**key).clone(), reason });
+                }
+                self.check_hashable_keys(key)?;
+                self.check_hashable_keys(value)
+            }
+            Type::HashSet(element) => {
+                if let Some(reason) = self.unhashable_reason(element, &mut BTreeSet::new()) {
+                    return Err(TypeError::UnhashableKey { key_type: (**element).clone(), reason });
+                }
+                self.check_hashable_keys(element)
+            }
+            Type::Tuple(types) => types.iter().try_for_each(|t| self.check_hashable_keys(t)),
+            Type::Function(params, ret) => {
+                params.iter().try_for_each(|t| self.check_hashable_keys(t))?;
+                self.check_hashable_keys(ret)
+            }
+            Type::Result(ok, err) | Type::BTreeMap(ok, err) => {
+                self.check_hashable_keys(ok)?;
+                self.check_hashable_keys(err)
+            }
+            Type::List(inner) | Type::Queue(inner) | Type::Heap(inner) | Type::Stream(inner) | Type::Array(inner, _)
+            | Type::Slice(inner) | Type::BTreeSet(inner) | Type::Option(inner) => self.check_hashable_keys(inner),
+            _ => Ok(()),
+        }
+    }
+
+    /// Why values of `ty` cannot implement Hash and Eq, or None if they can.
+    /// Structs are hashable when all their fields are (Hash/Eq are derived
+    /// for them as needed); structs not yet defined are given the benefit of
+    /// the doubt.
+    fn unhashable_reason(&self, ty: &Type, visiting: &mut BTreeSet<String>) -> Option<String> {
+        match ty {
+            Type::Float32 | Type::Float64 => {
+                Some("floats do not implement Hash or Eq; use an integer or String key instead".to_string())
+            }
+            Type::Map(..) | Type::HashSet(_) => Some("hash collections do not implement Hash".to_string()),
+            Type::Function(..) => Some("functions do not implement Hash or Eq".to_string()),
+            Type::Heap(_) => Some("heaps do not implement Hash or Eq".to_string()),
+            Type::Stream(_) => Some("streams do not implement Hash or Eq".to_string()),
+            Type::TypeVar(name) if !is_inference_variable(name) => {
+                Some(format!("generic type {} has no Hash bound; use a concrete key type", name))
+            }
+            Type::Tuple(types) => types.iter().find_map(|t| self.unhashable_reason(t, visiting)),
+            Type::Result(ok, err) | Type::BTreeMap(ok, err) => self.unhashable_reason(ok, visiting)
+                .or_else(|| self.unhashable_reason(err, visiting)),
+            Type::List(inner) | Type::Queue(inner) | Type::Array(inner, _) | Type::Slice(inner) | Type::BTreeSet(inner)
+            | Type::Option(inner) => self.unhashable_reason(inner, visiting),
+            Type::Custom(name) => {
+                if !visiting.insert(name.clone()) {
+                    return None;
+                }
+                if let Some(variants) = self.env.lookup_enum(name) {
+                    return variants.iter().find_map(|variant| {
+                        variant.fields.iter().find_map(|field_type| self.unhashable_reason(field_type, visiting))
+                            .map(|reason| format!("variant {} of enum {}: {}", variant.name, name, reason))
+                    });
+                }
+                let fields = self.env.lookup_struct(name)?;
+                fields.iter().find_map(|field| {
+                    self.unhashable_reason(&field.type_, visiting)
+                        .map(|reason| format!("field `{}` of struct {}: {}", field.name, name, reason))
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Check a SortWith comparator: it takes two elements and returns an
+    /// Int32 that is negative, zero or positive (like `a - b`)
+    fn check_comparator(&mut self, comparator: &Expression, element: &Type) -> Result<(), TypeError> {
+        let result_type = self.infer_application(comparator, &[element.clone(), element.clone()], "SortWith comparator")?;
+        if self.unify(&result_type, &Type::Int32).is_none() {
+            return Err(TypeError::TypeMismatch {
+                expected: Type::Int32,
+                actual: result_type,
+                context: "SortWith comparator result (negative, zero or positive)".to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Check an `Impl` block against its trait and record it. Method bodies
+    /// see `self` as the implementing type and parameters as the trait declares them.
+    fn check_impl(&mut self, trait_name: &str, type_name: &str, methods: &[Expression]) -> Result<(), TypeError> {
+        let required = self.env.lookup_trait(trait_name)
+            .cloned()
+            .ok_or_else(|| TypeError::UndefinedTrait(trait_name.to_string()))?;
+        if self.env.lookup_struct(type_name).is_none() && self.env.lookup_enum(type_name).is_none() {
+            return Err(TypeError::UndefinedStruct(type_name.to_string()));
+        }
+        // Recorded first so methods can call each other on `self`
+        self.env.define_impl(trait_name.to_string(), type_name.to_string());
+
+        for signature in &required {
+            let implemented = methods.iter().any(|m| {
+                matches!(m, Expression::FunctionDefinition { name, .. } if *name == signature.name)
+            });
+            if !implemented {
+                return Err(TypeError::MissingTraitMethod {
+                    trait_name: trait_name.to_string(),
+                    type_name: type_name.to_string(),
+                    method: signature.name.clone(),
+                });
+            }
+        }
+
+        for method in methods {
+            let Expression::FunctionDefinition { name, parameters, body, .. } = method else {
+                continue;
+            };
+            let signature = required.iter().find(|m| m.name == *name).ok_or_else(|| {
+                TypeError::UnknownTraitMethod { trait_name: trait_name.to_string(), method: name.clone() }
+            })?;
+            let qualified = format!("{}.{}", trait_name, name);
+            if parameters.len() != signature.parameters.len() {
+                return Err(TypeError::ArityMismatch {
+                    function: qualified,
+                    expected: signature.parameters.len(),
+                    actual: parameters.len(),
+                });
+            }
+
+            let mut child_env = self.env.child();
+            child_env.bind("self".to_string(), Type::Custom(type_name.to_string()));
+            for (param, declared) in parameters.iter().zip(&signature.parameters) {
+                let param_type = self.unify(&param.type_, &declared.type_).ok_or_else(|| TypeError::TypeMismatch {
+                    expected: declared.type_.clone(),
+                    actual: param.type_.clone(),
+                    context: format!("parameter {} of {}", param.name, qualified),
+                })?;
+                child_env.bind(param.name.clone(), param_type);
+            }
+            let (mut child_inference, early_returns) = self.for_function_body(child_env);
+            let body_type = child_inference.infer(body)?;
+            let body_type = self.with_early_returns(&qualified, body_type, &early_returns.borrow())?;
+            if self.unify(&body_type, &signature.return_type).is_none() {
+                return Err(TypeError::TypeMismatch {
+                    expected: signature.return_type.clone(),
+                    actual: body_type,
+                    context: format!("result of {} for {}", qualified, type_name),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Bind the names of `Let[pattern, value]` or `LetElse[pattern, value,
+    /// fallback]` in the current scope, returning the fallback's type
+    fn infer_let_pattern(
+        &mut self,
+        pattern: &Pattern,
+        value: &Expression,
+        fallback: Option<&Expression>,
+    ) -> Result<Option<Type>, TypeError> {
+        if fallback.is_none() && !pattern.is_irrefutable_with(&|name| self.env.lookup_variant(name).is_some()) {
+            return Err(TypeError::RefutablePattern(pattern.to_string()));
+        }
+        check_no_string_pattern(pattern, "LetElse")?;
+        let value_type = self.infer(value)?;
+        let fallback_type = fallback.map(|fallback| self.infer(fallback)).transpose()?;
+        let mut env = self.env.clone();
+        self.check_pattern(pattern, &value_type, &mut env)?;
+        self.env = env;
+        Ok(fallback_type)
+    }
+
+    /// Type of `name[field_values...]`: one value per field of struct `name`,
+    /// in order, each checked against its field's type
+    fn infer_struct_instantiation(&mut self, name: &str, field_values: &[Expression]) -> Result<Type, TypeError> {
+        self.check_visible(name)?;
+        let fields = self.env.lookup_struct(name)
+            .cloned()
+            .ok_or_else(|| TypeError::UndefinedStruct(name.to_string()))?;
+        if fields.len() != field_values.len() {
+            return Err(TypeError::FieldCountMismatch {
+                struct_name: name.to_string(),
+                expected: fields.len(),
+                actual: field_values.len(),
+            });
+        }
+        for (value, field) in field_values.iter().zip(&fields) {
+            let context = format!("field {}", field.name);
+            let value_type = self.infer_expecting(value, &field.type_, &context)?;
+            if self.unify(&value_type, &field.type_).is_none() {
+                return Err(TypeError::TypeMismatch {
+                    expected: field.type_.clone(),
+                    actual: value_type,
+                    context,
+                });
+            }
+        }
+        Ok(Type::Custom(name.to_string()))
+    }
+
+    /// Check a trait method call `Method[receiver, args...]`
+    fn check_method_call(
+        &mut self,
+        trait_name: &str,
+        method: &TraitMethod,
+        arguments: &[Expression],
+    ) -> Result<Type, TypeError> {
+        if arguments.len() != method.parameters.len() + 1 {
+            return Err(TypeError::ArityMismatch {
+                function: method.name.clone(),
+                expected: method.parameters.len() + 1,
+                actual: arguments.len(),
+            });
+        }
+        let receiver = self.infer(&arguments[0])?;
+        if !self.env.implements(&receiver, trait_name) {
+            return Err(TypeError::TraitNotImplemented { trait_name: trait_name.to_string(), type_: receiver });
+        }
+        for (arg, param) in arguments[1..].iter().zip(&method.parameters) {
+            let arg_type = self.infer(arg)?;
+            if self.unify(&arg_type, &param.type_).is_none() {
+                return Err(TypeError::TypeMismatch {
+                    expected: param.type_.clone(),
+                    actual: arg_type,
+                    context: format!("argument {} to {}", param.name, method.name),
+                });
+            }
+        }
+        Ok(method.return_type.clone())
+    }
+
+    /// Whether `actual` can be passed for a parameter typed as a trait
+    fn satisfies_trait(&self, expected: &Type, actual: &Type) -> bool {
+        match expected {
+            Type::Custom(trait_name) if self.env.lookup_trait(trait_name).is_some() => {
+                self.env.implements(actual, trait_name)
+            }
+            _ => false,
+        }
+    }
+
+    /// Result type of applying `function` (a lambda or a function value) to
+    /// arguments of `argument_types`; `context` names it in errors
+    fn infer_application(
+        &mut self,
+        function: &Expression,
+        argument_types: &[Type],
+        context: &str,
+    ) -> Result<Type, TypeError> {
+        let check_arity = |actual: usize| {
+            if actual == argument_types.len() {
+                Ok(())
+            } else {
+                Err(TypeError::ArityMismatch {
+                    function: context.to_string(),
+                    expected: argument_types.len(),
+                    actual,
+                })
+            }
+        };
+        let parameter_mismatch = |expected: &Type, actual: &Type| TypeError::TypeMismatch {
+            expected: expected.clone(),
+            actual: actual.clone(),
+            context: format!("{} parameter", context),
+        };
+
+        match function {
+            Expression::Lambda { parameters, body } => {
+                check_arity(parameters.len())?;
+                let mut child_env = self.env.child();
+                for (param, argument_type) in parameters.iter().zip(argument_types) {
+                    let param_type = self.unify(&param.type_, argument_type)
+                        .ok_or_else(|| parameter_mismatch(argument_type, &param.type_))?;
+                    child_env.bind(param.name.clone(), param_type);
+                }
+                // A closure cannot return early from the function around it
+                let mut child_inference = self.with_env(child_env);
+                child_inference.early_returns = None;
+                child_inference.infer(body)
+            }
+            other => match self.infer(other)? {
+                Type::Function(params, ret) => {
+                    check_arity(params.len())?;
+                    for (param, argument_type) in params.iter().zip(argument_types) {
+                        if self.unify(param, argument_type).is_none() {
+                            return Err(parameter_mismatch(argument_type, param));
+                        }
+                    }
+                    Ok(self.resolve(&ret))
+                }
+                // A function value not yet known to be one takes the shape of this call
+                ty if is_unresolved(&ty) => {
+                    let ret = self.fresh();
+                    self.unify(&ty, &Type::Function(argument_types.to_vec(), Box::new(ret.clone())));
+                    Ok(self.resolve(&ret))
+                }
+                actual => Err(TypeError::TypeMismatch {
+                    expected: Type::Function(argument_types.to_vec(), Box::new(Type::Unknown)),
+                    actual,
+                    context: context.to_string(),
+                }),
+            },
+        }
+    }
+
+    /// Check a call to a builtin with fixed parameter types
+    /// Check that the default values of `function`'s parameters have their
+    /// parameters' types and come last, returning how many there are.
+    /// Defaults are evaluated where the function is called, so they cannot
+    /// use the other parameters.
+    fn check_defaults(&mut self, function: &str, parameters: &[TypeAnnotation]) -> Result<usize, TypeError> {
+        let mut count = 0;
+        for param in parameters {
+            let Some(default) = &param.default else {
+                if count > 0 {
+                    return Err(TypeError::DefaultBeforeRequired {
+                        function: function.to_string(),
+                        parameter: param.name.clone(),
+                    });
+                }
+                continue;
+            };
+            let default_type = self.infer(default)?;
+            if self.unify(&default_type, &param.type_).is_none() {
+                return Err(TypeError::TypeMismatch {
+                    expected: param.type_.clone(),
+                    actual: default_type,
+                    context: format!("default value of {} in {}", param.name, function),
+                });
+            }
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    fn check_builtin_arguments(
+        &mut self,
+        name: &str,
+        arguments: &[Expression],
+        parameter_types: &[Type],
+    ) -> Result<(), TypeError> {
+        if arguments.len() != parameter_types.len() {
+            return Err(TypeError::ArityMismatch {
+                function: name.to_string(),
+                expected: parameter_types.len(),
+                actual: arguments.len(),
+            });
+        }
+        for (arg, expected_type) in arguments.iter().zip(parameter_types) {
+            let arg_type = self.infer(arg)?;
+            if !self.is_exactly(&arg_type, expected_type) {
+                return Err(TypeError::TypeMismatch {
+                    expected: expected_type.clone(),
+                    actual: arg_type,
+                    context: format!("argument to {}", name),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Common type of the elements passed to a collection constructor such as
+    /// `Stack[1, 2]`; a type variable when there are none
+    fn infer_element_type(&mut self, name: &str, arguments: &[Expression]) -> Result<Type, TypeError> {
+        let mut element = self.fresh();
+        for arg in arguments {
+            let arg_type = self.infer(arg)?;
+            element = self.unify(&element, &arg_type).ok_or_else(|| TypeError::TypeMismatch {
+                expected: self.zonk(&element),
+                actual: arg_type,
+                context: format!("element of {}", name),
+            })?;
+        }
+        Ok(element)
+    }
+
+    /// The most a pattern says about the type of the values it matches, with
+    /// type variables for its parts; `None` if it matches values of any type
+    fn pattern_shape(&self, pattern: &Pattern) -> Option<Type> {
+        match pattern {
+            Pattern::Constructor { name, .. } => match name.as_str() {
+                "Some" | "None" => Some(Type::Option(Box::new(self.fresh()))),
+                "Ok" | "Err" => Some(Type::Result(Box::new(self.fresh()), Box::new(self.fresh()))),
+                _ => match self.env.lookup_variant(name) {
+                    Some((enum_name, _)) => Some(Type::Custom(enum_name.clone())),
+                    None => self.env.lookup_struct(name).map(|_| Type::Custom(name.clone())),
+                },
+            },
+            Pattern::Variable(name) => self.env.lookup_variant(name).map(|(enum_name, _)| Type::Custom(enum_name.clone())),
+            Pattern::Tuple(patterns) => Some(Type::Tuple(patterns.iter().map(|_| self.fresh()).collect())),
+            Pattern::List(_) => Some(Type::List(Box::new(self.fresh()))),
+            Pattern::StartsWith(_) | Pattern::EndsWith(_) => Some(Type::String),
+            Pattern::Binding { pattern, .. } => self.pattern_shape(pattern),
+            Pattern::Or(patterns) => patterns.iter().find_map(|pattern| self.pattern_shape(pattern)),
+            Pattern::Wildcard | Pattern::Literal(_) | Pattern::Rest => None,
+        }
+    }
+
+    /// Check that a pattern matches the expected type and collect variable bindings
+    fn check_pattern(
+        &self,
+        pattern: &Pattern,
+        expected_type: &Type,
+        env: &mut TypeEnvironment,
+    ) -> Result<(), TypeError> {
+        // A value whose type is not known yet takes the shape of its pattern
+        let shaped;
+        let expected_type = match self.pattern_shape(pattern) {
+            Some(shape) if is_unresolved(expected_type) => {
+                shaped = self.unify(expected_type, &shape).unwrap_or(shape);
+                &shaped
+            }
+            _ => expected_type,
+        };
+        match pattern {
+            // Wildcard matches anything
+            Pattern::Wildcard => Ok(()),
+
+            // Literal patterns must match exactly
+            Pattern::Literal(expr) => {
+                // Create a temporary inference context to check the literal
+                let mut temp_inference = self.with_env(self.env.clone());
+                let literal_type = temp_inference.infer(expr)?;
+
+                if !self.is_exactly(&literal_type, expected_type) {
+                    return Err(TypeError::TypeMismatch {
+                        expected: expected_type.clone(),
+                        actual: literal_type,
+                        context: "pattern literal".to_string(),
+                    });
+                }
+                Ok(())
+            }
+
+            // `..` is only meaningful inside struct and list patterns, which
+            // handle it themselves
+            Pattern::Rest => Ok(()),
+
+            Pattern::StartsWith(_) | Pattern::EndsWith(_) => {
+                if self.unify(expected_type, &Type::String).is_none() {
+                    return Err(TypeError::TypeMismatch {
+                        expected: Type::String,
+                        actual: expected_type.clone(),
+                        context: format!("{} pattern", pattern),
+                    });
+                }
+                Ok(())
+            }
+
+            // Variable patterns bind to the expected type
+            Pattern::Variable(name) => {
+                // A bare name that is a variant matches that variant instead of binding
+                if self.env.lookup_variant(name).is_some() {
+                    return self.check_variant_pattern(name, &[], expected_type, env);
+                }
+                env.bind(name.clone(), expected_type.clone());
+                Ok(())
+            }
+
+            // Constructor patterns (Some, Ok, Err, None)
+            Pattern::Constructor { name, patterns } => {
+                match name.as_str() {
+                    "Some" => {
+                        match expected_type {
+                            Type::Option(inner_type) => {
+                                if patterns.len() != 1 {
+                                    return Err(TypeError::CannotInfer(
+                                        "Some pattern must have exactly one argument".to_string()
+                                    ));
+                                }
+                                self.check_pattern(&patterns[0], inner_type, env)
+                            }
+                            _ => Err(TypeError::TypeMismatch {
+                                expected: Type::Option(Box::new(Type::Int32)),
+                                actual: expected_type.clone(),
+                                context: "Some pattern".to_string(),
+                            }),
+                        }
+                    }
+                    "None" => {
+                        match expected_type {
+                            Type::Option(_) => {
+                                if !patterns.is_empty() {
+                                    return Err(TypeError::CannotInfer(
+                                        "None pattern should have no arguments".to_string()
+                                    ));
+                                }
+                                Ok(())
+                            }
+                            _ => Err(TypeError::TypeMismatch {
+                                expected: Type::Option(Box::new(Type::Int32)),
+                                actual: expected_type.clone(),
+                                context: "None pattern".to_string(),
+                            }),
+                        }
+                    }
+                    "Ok" => {
+                        match expected_type {
+                            Type::Result(ok_type, _) => {
+                                if patterns.len() != 1 {
+                                    return Err(TypeError::CannotInfer(
+                                        "Ok pattern must have exactly one argument".to_string()
+                                    ));
+                                }
+                                self.check_pattern(&patterns[0], ok_type, env)
+                            }
+                            _ => Err(TypeError::TypeMismatch {
+                                expected: Type::Result(Box::new(Type::Int32), Box::new(Type::String)),
+                                actual: expected_type.clone(),
+                                context: "Ok pattern".to_string(),
+                            }),
+                        }
+                    }
+                    "Err" => {
+                        match expected_type {
+                            Type::Result(_, err_type) => {
+                                if patterns.len() != 1 {
+                                    return Err(TypeError::CannotInfer(
+                                        "Err pattern must have exactly one argument".to_string()
+                                    ));
+                                }
+                                self.check_pattern(&patterns[0], err_type, env)
+                            }
+                            _ => Err(TypeError::TypeMismatch {
+                                expected: Type::Result(Box::new(Type::Int32), Box::new(Type::String)),
+                                actual: expected_type.clone(),
+                                context: "Err pattern".to_string(),
+                            }),
+                        }
+                    }
+                    _ if self.env.lookup_variant(name).is_some() => {
+                        self.check_variant_pattern(name, patterns, expected_type, env)
+                    }
+                    _ => match self.env.lookup_struct(name) {
+                        Some(fields) => self.check_struct_pattern(name, fields, patterns, expected_type, env),
+                        None => Err(TypeError::CannotInfer(format!("Unknown constructor: {}", name))),
+                    },
+                }
+            }
+
+            // Tuple patterns
+            Pattern::Tuple(patterns) => {
+                match expected_type {
+                    Type::Tuple(types) => {
+                        if patterns.len() != types.len() {
+                            return Err(TypeError::TypeMismatch {
+                                expected: expected_type.clone(),
+                                actual: Type::Tuple(vec![]), // Placeholder
+                                context: format!(
+                                    "tuple pattern length mismatch: expected {}, got {}",
+                                    types.len(),
+                                    patterns.len()
+                                ),
+                            });
+                        }
+
+                        for (pattern, ty) in patterns.iter().zip(types.iter()) {
+                            self.check_pattern(pattern, ty, env)?;
+                        }
+                        Ok(())
+                    }
+                    _ => Err(TypeError::TypeMismatch {
+                        expected: Type::Tuple(vec![]),
+                        actual: expected_type.clone(),
+                        context: "tuple pattern".to_string(),
+                    }),
+                }
+            }
+
+            // List patterns
+            Pattern::List(patterns) => {
+                match expected_type {
+                    Type::List(element_type) => {
+                        // All patterns in the list must match the element type
+                        if patterns.iter().filter(|p| p.is_rest()).count() > 1 {
+                            return Err(TypeError::CannotInfer(
+                                "list pattern can contain at most one `..`".to_string()
+                            ));
+                        }
+                        for pattern in patterns {
+                            // `rest @ ..` is the list of the elements left over
+                            match pattern {
+                                Pattern::Binding { name, pattern } if **pattern == Pattern::Rest => {
+                                    env.bind(name.clone(), expected_type.clone());
+                                }
+                                _ => self.check_pattern(pattern, element_type, env)?,
+                            }
+                        }
+                        Ok(())
+                    }
+                    _ => Err(TypeError::TypeMismatch {
+                        expected: Type::List(Box::new(Type::Int32)),
+                        actual: expected_type.clone(),
+                        context: "list pattern".to_string(),
+                    }),
+                }
+            }
+
+            // The name takes the whole value's type
+            Pattern::Binding { name, pattern } => {
+                self.check_pattern(pattern, expected_type, env)?;
+                env.bind(name.clone(), expected_type.clone());
+                Ok(())
+            }
+
+            // Every alternative must bind the same variables, at the same types
+            Pattern::Or(alternatives) => {
+                let mut first: Option<BTreeMap<String, Type>> = None;
+                for alternative in alternatives {
+                    let mut alternative_env = env.child();
+                    self.check_pattern(alternative, expected_type, &mut alternative_env)?;
+                    let mut names = BTreeSet::new();
+                    self.pattern_bindings(alternative, &mut names);
+                    let bound: BTreeMap<String, Type> = names
+                        .into_iter()
+                        .map(|name| {
+                            let ty = alternative_env.lookup(&name).cloned().unwrap_or(Type::Unknown);
+                            (name, ty)
+                        })
+                        .collect();
+
+                    let Some(first) = &first else {
+                        first = Some(bound);
+                        continue;
+                    };
+                    let missing = first.keys().find(|name| !bound.contains_key(*name))
+                        .or_else(|| bound.keys().find(|name| !first.contains_key(*name)));
+                    if let Some(name) = missing {
+                        return Err(TypeError::OrPatternBindings { pattern: pattern.to_string(), name: name.clone() });
+                    }
+                    for (name, ty) in &bound {
+                        if &first[name] != ty {
+                            return Err(TypeError::TypeMismatch {
+                                expected: first[name].clone(),
+                                actual: ty.clone(),
+                                context: format!("{} in `{}`", name, pattern),
+                            });
+                        }
+                    }
+                }
+                for (name, ty) in first.unwrap_or_default() {
+                    env.bind(name, ty);
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Collect the variables `pattern` binds (bare variant names bind nothing)
+    fn pattern_bindings(&self, pattern: &Pattern, names: &mut BTreeSet<String>) {
+        match pattern {
+            Pattern::Variable(name) if self.env.lookup_variant(name).is_none() => {
+                names.insert(name.clone());
+            }
+            Pattern::Constructor { patterns, .. } | Pattern::Tuple(patterns) | Pattern::List(patterns) => {
+                for pattern in patterns {
+                    self.pattern_bindings(pattern, names);
+                }
+            }
+            Pattern::Binding { name, pattern } => {
+                names.insert(name.clone());
+                self.pattern_bindings(pattern, names);
+            }
+            // Nested alternatives are checked to agree, so the first is enough
+            Pattern::Or(alternatives) => {
+                if let Some(first) = alternatives.first() {
+                    self.pattern_bindings(first, names);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Check `Circle[r]` against an enum: the value must have the variant's
+    /// enum type, and sub-patterns bind the payload in order (a trailing `..`
+    /// skips the rest)
+    fn check_variant_pattern(
+        &self,
+        name: &str,
+        patterns: &[Pattern],
+        expected_type: &Type,
+        env: &mut TypeEnvironment,
+    ) -> Result<(), TypeError> {
+        let (enum_name, variant) = self.env.lookup_variant(name)
+            .ok_or_else(|| TypeError::CannotInfer(format!("Unknown constructor: {}", name)))?;
+        let enum_type = Type::Custom(enum_name.clone());
+        if self.unify(expected_type, &enum_type).is_none() {
+            return Err(TypeError::TypeMismatch {
+                expected: enum_type,
+                actual: expected_type.clone(),
+                context: format!("{} pattern", name),
+            });
+        }
+
+        let (patterns, has_rest) = match patterns.split_last() {
+            Some((Pattern::Rest, init)) => (init, true),
+            _ => (patterns, false),
+        };
+        let arity_ok = if has_rest { patterns.len() <= variant.fields.len() } else { patterns.len() == variant.fields.len() };
+        if !arity_ok || patterns.contains(&Pattern::Rest) {
+            return Err(TypeError::ArityMismatch {
+                function: name.to_string(),
+                expected: variant.fields.len(),
+                actual: patterns.len(),
+            });
+        }
+        for (pattern, field_type) in patterns.iter().zip(&variant.fields) {
+            self.check_pattern(pattern, field_type, env)?;
+        }
+        Ok(())
+    }
+
+    /// Check `Point[x, _, ..]` against a struct: sub-patterns bind fields in
+    /// declaration order, and all fields must be covered unless the pattern
+    /// ends in `..`
+    fn check_struct_pattern(
+        &self,
+        name: &str,
+        fields: &[TypeAnnotation],
+        patterns: &[Pattern],
+        expected_type: &Type,
+        env: &mut TypeEnvironment,
+    ) -> Result<(), TypeError> {
+        if expected_type != &Type::Custom(name.to_string()) {
+            return Err(TypeError::TypeMismatch {
+                expected: Type::Custom(name.to_string()),
+                actual: expected_type.clone(),
+                context: format!("{} pattern", name),
+            });
+        }
+
+        let (patterns, has_rest) = match patterns.split_last() {
+            Some((Pattern::Rest, init)) => (init, true),
+            _ => (patterns, false),
+        };
+        if patterns.contains(&Pattern::Rest) {
+            return Err(TypeError::CannotInfer(format!("`..` must be the last field in a {} pattern", name)));
+        }
+        let count_ok = if has_rest { patterns.len() <= fields.len() } else { patterns.len() == fields.len() };
+        if !count_ok {
+            return Err(TypeError::FieldCountMismatch {
+                struct_name: name.to_string(),
+                expected: fields.len(),
+                actual: patterns.len(),
+            });
+        }
+
+        for (pattern, field) in patterns.iter().zip(fields) {
+            self.check_pattern(pattern, &field.type_, env)?;
+        }
+        Ok(())
+    }
+
+    /// Type check a program (multiple expressions), stopping at its first
+    /// error
+    pub fn check_program(&mut self, expressions: &[Expression]) -> Result<(), TypeError> {
+        match self.check_program_errors(expressions).into_iter().next() {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    /// Type check a program and return every error in it, in source order.
+    /// Names are resolved first (see `resolve`): a statement using a name
+    /// that is undefined or ambiguous reports only that.
+    /// A statement that does not type check is reported and the names it
+    /// defines are bound to `Unknown`, which the statements after it may
+    /// use as anything, so one mistake is not reported again at each use.
+    /// A function whose body does not type check is still bound with its
+    /// parameter types. Cancellation ends the check with `Cancelled` as the
+    /// last error.
+    pub fn check_program_errors(&mut self, expressions: &[Expression]) -> Vec<TypeError> {
+        self.check_program_located(expressions, &[]).into_iter().map(|located| located.error).collect()
+    }
+
+    /// Type check the program of an imported module, which the importing
+    /// files call `name` (see `imports`), so its definitions are in scope for
+    /// the programs checked after it, both unqualified and as `name.Item`
+    pub fn check_module(&mut self, name: &str, expressions: &[Expression]) -> Vec<TypeError> {
+        // Its definitions may name each other as `name.Item` too
+        self.env.define_module(name.to_string());
+        let errors = self.check_program_errors(expressions);
+        let mut items = BTreeMap::new();
+        for expr in expressions {
+            if let Expression::FunctionDefinition { name: item, .. } = expr {
+                if let Some(ty) = self.env.lookup(item) {
+                    items.insert(item.clone(), ty.clone());
+                }
+            }
+            match expr {
+                Expression::FunctionDefinition { name: item, visibility: Visibility::Private, .. }
+                | Expression::StructDefinition { name: item, visibility: Visibility::Private, .. } => {
+                    self.env.define_private(item.clone(), name.to_string());
+                }
+                // An enum's variants are how it is used
+                Expression::EnumDefinition { variants, visibility: Visibility::Private, .. } => {
+                    for variant in variants {
+                        self.env.define_private(variant.name.clone(), name.to_string());
+                    }
+                }
+                _ => {}
+            }
+        }
+        self.module_items.insert(name.to_string(), items);
+        errors
+    }
+
+    /// Like `check_program_errors`, locating each error at the top-level
+    /// expression it was found in. `spans` are the parser's
+    /// `expression_spans`, one per expression (empty if unknown).
+    pub fn check_program_located(&mut self, expressions: &[Expression], spans: &[Span]) -> Vec<LocatedTypeError> {
+        number_nodes(expressions, &mut self.nodes.borrow_mut().ids);
+        let resolution = resolve::resolve_program_with(expressions, &|name| self.is_defined(name));
+        self.declare_functions(expressions);
+        let mut located = Vec::new();
+        for (index, expr) in expressions.iter().enumerate() {
+            let result = self.infer(expr);
+            let cancelled = result == Err(TypeError::Cancelled);
+            if let Err(error) = result {
+                self.errors.borrow_mut().push(error);
+                if !cancelled {
+                    self.bind_placeholders(expr);
+                }
+            }
+            let mut errors = self.errors.take();
+            // Names that do not resolve explain what typing found wrong with
+            // their statement, so only they are reported
+            let unresolved: Vec<TypeError> = resolution.errors_in(index).cloned().collect();
+            if !unresolved.is_empty() && !cancelled {
+                errors = unresolved;
+            }
+            let span = spans.get(index).copied();
+            located.extend(errors.into_iter().map(|error| LocatedTypeError { error, span }));
+            if cancelled {
+                break;
+            }
+        }
+        // Bindings show what later statements learned, such as the element
+        // type of a list that started empty
+        let resolved: Vec<(String, Type)> =
+            self.env.bindings.iter().map(|(name, ty)| (name.clone(), self.resolve(ty))).collect();
+        self.env.bindings.extend(resolved);
+        if located.is_empty() {
+            for (index, expr) in expressions.iter().enumerate() {
+                if let Some(error) = self.uninferred(expr) {
+                    located.push(LocatedTypeError { error, span: spans.get(index).copied() });
+                }
+            }
+        }
+        located
+    }
+
+    /// What the whole program left the type of unfixed in `expr`, one of
+    /// its top-level statements, where Rust cannot fix it either: a name it
+    /// binds, or else a `None` or empty literal in it. Functions are left
+    /// out, as their type variables are their generic parameters.
+    fn uninferred(&self, expr: &Expression) -> Option<TypeError> {
+        fn literals<'a>(expr: &'a Expression, found: &mut Vec<&'a Expression>) {
+            match expr {
+                Expression::None => found.push(expr),
+                Expression::List(elements) if elements.is_empty() => found.push(expr),
+                Expression::Map(entries) if entries.is_empty() => found.push(expr),
+                _ => expr.for_each_child(&mut |child| literals(child, found)),
+            }
+        }
+        if matches!(expr,
+            Expression::FunctionDefinition { .. } | Expression::Kernel { .. } | Expression::Impl { .. }
+            | Expression::TraitDefinition { .. } | Expression::StructDefinition { .. }
+            | Expression::EnumDefinition { .. } | Expression::Import(_)) {
+            return None;
+        }
+        let mut names = BTreeSet::new();
+        if let Expression::LetPattern { pattern, .. } = expr {
+            self.pattern_bindings(pattern, &mut names);
+        }
+        let binding = names.into_iter().find_map(|name| {
+            let ty = self.env.lookup(&name)?;
+            (!matches!(ty, Type::Function(..)) && has_unresolved(ty)).then(|| TypeError::CannotInfer(format!(
+                "{}, bound to {}; use it where its type is known, or bind it to a value of that type",
+                name, ty
+            )))
+        });
+        let mut found = Vec::new();
+        literals(expr, &mut found);
+        binding.or_else(|| found.into_iter().find_map(|literal| {
+            let ty = self.resolve(&self.recorded(literal)?);
+            let shown = match literal {
+                Expression::None => "None",
+                Expression::List(_) => "[]",
+                _ => "{}",
+            };
+            has_unresolved(&ty).then(|| TypeError::CannotInfer(format!(
+                "{} ({}); use it where its type is known, e.g. as the argument of an annotated parameter",
+                shown, ty
+            )))
+        }))
+    }
+
+    /// Type inferred for `expr`, if it belongs to a program being checked
+    fn recorded(&self, expr: &Expression) -> Option<Type> {
+        let nodes = self.nodes.borrow();
+        let id = nodes.ids.get(&(expr as *const Expression as usize))?;
+        nodes.types.get(id).cloned()
+    }
+
+    /// Bind each function `expressions` defines exactly once, and nothing
+    /// earlier defined, so that statements before its definition can call
+    /// it: its parameter types are instantiated at each call, and its return
+    /// type is a variable its definition fixes
+    fn declare_functions(&mut self, expressions: &[Expression]) {
+        let mut definitions: BTreeMap<&str, usize> = BTreeMap::new();
+        for expr in expressions {
+            if let Expression::FunctionDefinition { name, .. } = expr {
+                *definitions.entry(name.as_str()).or_default() += 1;
+            }
+        }
+        for expr in expressions {
+            let Expression::FunctionDefinition { name, parameters, .. } = expr else {
+                continue;
+            };
+            if definitions[name.as_str()] > 1 || self.is_defined(name) {
+                continue;
+            }
+            let param_types = parameters.iter()
+                .map(|p| if p.type_ == Type::Unknown { self.fresh() } else { p.type_.clone() })
+                .collect();
+            let ty = Type::Function(param_types, Box::new(self.fresh()));
+            self.env.bind_generalized(name.clone(), ty.clone());
+            self.env.define_defaults(name.clone(), parameters.iter().filter(|p| p.default.is_some()).count());
+            self.declared.insert(name.clone(), ty);
+        }
+    }
+
+    /// Whether an earlier program checked with this inference defined `name`
+    fn is_defined(&self, name: &str) -> bool {
+        self.env.lookup(name).is_some()
+            || self.env.lookup_struct(name).is_some()
+            || self.env.lookup_variant(name).is_some()
+            || self.env.lookup_trait_method(name).is_some()
+    }
+
+    /// Bind the names the failed statement `expr` would have defined, that
+    /// it has not, to `Unknown`
+    fn bind_placeholders(&mut self, expr: &Expression) {
+        let mut names = BTreeSet::new();
+        match expr {
+            Expression::FunctionDefinition { name, .. } => {
+                names.insert(name.clone());
+            }
+            Expression::LetPattern { pattern, .. } => self.pattern_bindings(pattern, &mut names),
+            _ => {}
+        }
+        for name in names {
+            // A declaration is for calls to check against, not a definition
+            if self.declared.remove(&name).is_some() {
+                self.env.unbind(&name);
+            }
+            if self.env.lookup(&name).is_none() {
+                self.env.bind(name, Type::Unknown);
+            }
+        }
+    }
+
+    /// Record `result`'s error, if any, and continue with `Unknown` as the
+    /// type that could not be inferred. Cancellation is not recovered from
+    fn recover(&self, result: Result<Type, TypeError>) -> Result<Type, TypeError> {
+        match result {
+            Err(TypeError::Cancelled) => Err(TypeError::Cancelled),
+            Err(error) => {
+                self.errors.borrow_mut().push(error);
+                Ok(Type::Unknown)
+            }
+            ok => ok,
+        }
+    }
+
+    /// The first error inference recovered from since the last call, if
+    /// any, as an `Err`
+    fn first_recovered_error(&self) -> Result<(), TypeError> {
+        match self.errors.take().into_iter().next() {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+}
+
+/// `ty` with each part for which `f` gives a replacement replaced
+fn map_type(ty: &Type, f: &mut impl FnMut(&Type) -> Option<Type>) -> Type {
+    if let Some(replacement) = f(ty) {
+        return replacement;
+    }
+    let mut sub = |inner: &Type| Box::new(map_type(inner, f));
+    match ty {
+        Type::List(inner) => Type::List(sub(inner)),
+        Type::Queue(inner) => Type::Queue(sub(inner)),
+        Type::Heap(inner) => Type::Heap(sub(inner)),
+        Type::Stream(inner) => Type::Stream(sub(inner)),
+        Type::Slice(inner) => Type::Slice(sub(inner)),
+        Type::HashSet(inner) => Type::HashSet(sub(inner)),
+        Type::BTreeSet(inner) => Type::BTreeSet(sub(inner)),
+        Type::Option(inner) => Type::Option(sub(inner)),
+        Type::Array(inner, size) => Type::Array(sub(inner), *size),
+        Type::Map(key, value) => Type::Map(sub(key), sub(value)),
+        Type::BTreeMap(key, value) => Type::BTreeMap(sub(key), sub(value)),
+        Type::Result(ok, err) => Type::Result(sub(ok), sub(err)),
+        Type::Tuple(types) => Type::Tuple(types.iter().map(|t| map_type(t, f)).collect()),
+        Type::Function(params, ret) => {
+            let params = params.iter().map(|t| map_type(t, f)).collect();
+            Type::Function(params, Box::new(map_type(ret, f)))
+        }
+        other => other.clone(),
+    }
+}
+
+/// Names of the type variables in `ty`, generic parameters and inference
+/// variables alike
+fn type_variables(ty: &Type) -> BTreeSet<String> {
+    let mut names = BTreeSet::new();
+    map_type(ty, &mut |t| {
+        if let Type::TypeVar(name) = t {
+            names.insert(name.clone());
+        }
+        None
+    });
+    names
+}
+
+/// The narrowest numeric type both `a` and `b` convert to without loss
+fn numeric_lub(a: &Type, b: &Type) -> Option<Type> {
+    if widens_to(a, b) {
+        return Some(b.clone());
+    }
+    if widens_to(b, a) {
+        return Some(a.clone());
+    }
+    // Neither holds the other (e.g. Int32 and UInt32), but a wider type may
+    [Type::Int16, Type::Int32, Type::Int64, Type::Int128, Type::Float32, Type::Float64]
+        .into_iter()
+        .find(|wider| widens_to(a, wider) && widens_to(b, wider))
+}
+
+/// Whether every value of `from` is exactly representable in `to`; these
+/// are the conversions Rust provides `From` for
+fn widens_to(from: &Type, to: &Type) -> bool {
+    if from == to {
+        return true;
+    }
+    match (from, to) {
+        // Platform-sized integers have no lossless conversion to anything else
+        (Type::Int | Type::UInt, _) => false,
+        (_, Type::Int) => matches!(from, Type::Int8 | Type::Int16 | Type::UInt8),
+        (_, Type::UInt) => matches!(from, Type::UInt8 | Type::UInt16),
+        (Type::Float32, Type::Float64) => true,
+        _ => match (integer_width(from), integer_width(to)) {
+            (Some((from_signed, from_bits)), Some((to_signed, to_bits))) => {
+                if from_signed == to_signed {
+                    from_bits <= to_bits
+                } else {
+                    // Only unsigned to a strictly wider signed type
+                    !from_signed && to_bits > from_bits
+                }
+            }
+            // Floats hold integers up to their mantissa width
+            (Some((_, bits)), None) => match to {
+                Type::Float32 => bits <= 16,
+                Type::Float64 => bits <= 32,
+                _ => false,
+            },
+            _ => false,
+        },
+    }
+}
+
+/// Signedness and bit width of a fixed-size integer type
+fn integer_width(ty: &Type) -> Option<(bool, u32)> {
+    match ty {
+        Type::Int8 => Some((true, 8)),
+        Type::Int16 => Some((true, 16)),
+        Type::Int32 => Some((true, 32)),
+        Type::Int64 => Some((true, 64)),
+        Type::Int128 => Some((true, 128)),
+        Type::UInt8 => Some((false, 8)),
+        Type::UInt16 => Some((false, 16)),
+        Type::UInt32 => Some((false, 32)),
+        Type::UInt64 => Some((false, 64)),
+        Type::UInt128 => Some((false, 128)),
+        _ => None,
+    }
+}
+
+/// Type to suggest converting to when two numeric types have no lossless
+/// common type: Float64 if either is a float, otherwise the wider integer
+fn conversion_target(a: &Type, b: &Type) -> Type {
+    if matches!(a, Type::Float32 | Type::Float64) || matches!(b, Type::Float32 | Type::Float64) {
+        return Type::Float64;
+    }
+    let bits = |ty: &Type| integer_width(ty).map_or(64, |(_, bits)| bits);
+    if bits(b) > bits(a) { b.clone() } else { a.clone() }
+}
+
+/// Why `<`/`>` cannot be used on values of `ty`, or None if it can.
+/// Numbers, Bool, Char, String and Bytes are ordered, as are tuples, lists,
+/// Options and Results of ordered types (lexicographically).
+fn unordered_reason(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Map(..) | Type::HashSet(_) => Some("hash collections have no ordering".to_string()),
+        Type::Function(..) => Some("functions have no ordering".to_string()),
+        Type::Heap(_) => Some("heaps have no ordering".to_string()),
+        Type::Stream(_) => Some("streams have no ordering".to_string()),
+        Type::TypeVar(name) if !is_inference_variable(name) => {
+            Some(format!("generic type {} has no ordering bound", name))
+        }
+        Type::Custom(name) => Some(format!("{} does not derive PartialOrd; compare a field instead", name)),
+        Type::Tuple(types) => types.iter().find_map(unordered_reason),
+        Type::BTreeMap(a, b) | Type::Result(a, b) => unordered_reason(a).or_else(|| unordered_reason(b)),
+        Type::List(inner) | Type::Queue(inner) | Type::Array(inner, _) | Type::Slice(inner) | Type::BTreeSet(inner)
+        | Type::Option(inner) => unordered_reason(inner),
+        _ => None,
+    }
+}
+
+/// Heaps need Ord, not just PartialOrd: besides what `unordered_reason`
+/// rules out, floats (which have NaN) cannot be heap elements
+fn check_heap_element(element: &Type) -> Result<(), TypeError> {
+    fn float_reason(ty: &Type) -> Option<String> {
+        match ty {
+            Type::Float32 | Type::Float64 => {
+                Some("floats are not totally ordered (NaN); scale to an integer priority instead".to_string())
+            }
+            Type::Tuple(types) => types.iter().find_map(float_reason),
+            Type::BTreeMap(a, b) | Type::Result(a, b) => float_reason(a).or_else(|| float_reason(b)),
+            Type::List(inner) | Type::Queue(inner) | Type::Array(inner, _) | Type::Slice(inner)
+            | Type::BTreeSet(inner) | Type::Option(inner) => float_reason(inner),
+            _ => None,
+        }
+    }
+
+    match unordered_reason(element).or_else(|| float_reason(element)) {
+        Some(reason) => Err(TypeError::UnorderedHeapElement { element_type: element.clone(), reason }),
+        None => Ok(()),
+    }
+}
+
+/// Type of a unary builtin passed by name as a function value, e.g. `Map[ToUpper, lines]`
+fn builtin_function_type(name: &str) -> Option<Type> {
+    let (param, result) = match name {
+        "ToUpper" | "ToLower" => (Type::String, Type::String),
+        "CharCount" => (Type::String, Type::Int32),
+        _ => return None,
+    };
+    Some(Type::Function(vec![param], Box::new(result)))
+}
+
+/// The struct and enum names `ty` holds inline: itself, or inside an Option,
+/// Result, tuple or array. Other collections keep their elements on the heap
+fn inline_type_names(ty: &Type, names: &mut Vec<String>) {
+    match ty {
+        Type::Custom(name) => names.push(name.clone()),
+        Type::Option(inner) | Type::Array(inner, _) => inline_type_names(inner, names),
+        Type::Result(ok, err) => {
+            inline_type_names(ok, names);
+            inline_type_names(err, names);
+        }
+        Type::Tuple(types) => types.iter().for_each(|ty| inline_type_names(ty, names)),
+        _ => {}
+    }
+}
+
+/// Streams may be infinite, so anything consuming one whole needs Take first
+fn reject_stream(ty: &Type, context: &str) -> Result<(), TypeError> {
+    match ty {
+        Type::Stream(_) => Err(TypeError::UnboundedStream(context.to_string())),
+        _ => Ok(()),
+    }
+}
+
+/// Render a qualified name the way it is written in W source
+/// The first part of a kernel body that cannot run once per element on
+/// plain numbers, as W source (e.g. `Print`), if any
+fn kernel_unsupported(body: &Expression) -> Option<String> {
+    let unsupported = match body {
+        Expression::Number(..) | Expression::Float(_) | Expression::Boolean(_) | Expression::Identifier(_)
+        | Expression::BinaryOp { .. } | Expression::Not { .. } | Expression::Cond { .. } | Expression::Convert { .. } => None,
+        Expression::QualifiedName { path, .. } if path.len() == 1 && path[0] == "Math" => None,
+        Expression::FunctionCall { function, .. } => match function.as_ref() {
+            Expression::QualifiedName { path, .. } if path.len() == 1 && path[0] == "Math" => None,
+            Expression::QualifiedName { path, name } => Some(qualified_name(path, name)),
+            Expression::Identifier(name) => Some(name.clone()),
+            _ => Some("a computed function".to_string()),
+        },
+        Expression::String(_) | Expression::InterpolatedString(_) => Some("a String".to_string()),
+        Expression::List(_) | Expression::Map(_) | Expression::Tuple(_) => Some("a collection literal".to_string()),
+        Expression::Let { .. } | Expression::LetPattern { .. } => Some("Let".to_string()),
+        Expression::Match { .. } => Some("Match".to_string()),
+        Expression::Lambda { .. } => Some("a Function".to_string()),
+        _ => Some("an expression other than arithmetic".to_string()),
+    };
+    if unsupported.is_some() {
+        return unsupported;
+    }
+    let mut found = None;
+    body.for_each_child(&mut |child| {
+        if found.is_none() {
+            found = kernel_unsupported(child);
+        }
+    });
+    found
+}
+
+fn qualified_name(path: &[String], name: &str) -> String {
+    format!("{}.{}", path.join("."), name)
+}
+
+/// `error`, naming `name` as it was written when it is the one undefined
+fn qualify_undefined(error: TypeError, path: &[String], name: &str) -> TypeError {
+    match error {
+        TypeError::UndefinedIdentifier(undefined) if undefined == name => {
+            TypeError::UndefinedIdentifier(qualified_name(path, name))
+        }
+        other => other,
+    }
+}
+
+/// Check if a type is a signed or unsigned integer
+pub(crate) fn is_integer(ty: &Type) -> bool {
+    matches!(ty,
+        Type::Int8 | Type::Int16 | Type::Int32 | Type::Int64 | Type::Int128 | Type::Int |
+        Type::UInt8 | Type::UInt16 | Type::UInt32 | Type::UInt64 | Type::UInt128 | Type::UInt
+    )
+}
+
+/// Whether the integer literal `n` is a value of the integer type `ty`
+fn literal_fits(n: i64, ty: &Type) -> bool {
+    match ty {
+        Type::Int8 => i8::try_from(n).is_ok(),
+        Type::Int16 => i16::try_from(n).is_ok(),
+        Type::Int32 => i32::try_from(n).is_ok(),
+        Type::UInt8 => u8::try_from(n).is_ok(),
+        Type::UInt16 => u16::try_from(n).is_ok(),
+        Type::UInt32 => u32::try_from(n).is_ok(),
+        Type::UInt64 | Type::UInt128 | Type::UInt => n >= 0,
+        _ => true,
+    }
+}
+
+/// The type of integer literal `n` where nothing else decides it: Int32,
+/// Rust's default, or Int64 for values beyond it
+pub(crate) fn literal_type(n: i64) -> Type {
+    if i32::try_from(n).is_ok() { Type::Int32 } else { Type::Int64 }
+}
+
+fn is_arithmetic(operator: &Operator) -> bool {
+    matches!(operator, Operator::Add | Operator::Subtract | Operator::Multiply | Operator::Divide | Operator::Power)
+}
+
+/// The symbol of an operator checked arithmetic can fail in
+fn checked_operator(operator: &Operator) -> Option<&'static str> {
+    match operator {
+        Operator::Add => Some("+"),
+        Operator::Subtract => Some("-"),
+        Operator::Multiply => Some("*"),
+        Operator::Divide => Some("/"),
+        Operator::Power => Some("^"),
+        Operator::ShiftLeft => Some("<<"),
+        Operator::ShiftRight => Some(">>"),
+        _ => None,
+    }
+}
+
+/// A number literal, or arithmetic on number literals only
+pub(crate) fn is_numeric_literal(expr: &Expression) -> bool {
+    match expr {
+        Expression::Number(..) | Expression::Float(_) => true,
+        Expression::BinaryOp { left, operator, right } => {
+            is_arithmetic(operator) && is_numeric_literal(left) && is_numeric_literal(right)
+        }
+        _ => false,
+    }
+}
+
+/// Check if a type is numeric
+fn is_numeric(ty: &Type) -> bool {
+    matches!(ty,
+        Type::Int8 | Type::Int16 | Type::Int32 | Type::Int64 | Type::Int128 | Type::Int |
+        Type::UInt8 | Type::UInt16 | Type::UInt32 | Type::UInt64 | Type::UInt128 | Type::UInt |
+        Type::Float32 | Type::Float64
+    )
+}
@@ -1,13 +1,22 @@
 (* Error propagation with the ? operator *)
 
 (* Basic ? on a Result value *)
-GetValue[x: Int32] := Ok[x * 2]
+GetValue[x: Int32] := If[x > 0, Ok[x * 2], Err["not positive"]]
 
 (* ? on Option types *)
-FindItem[x: Int32] := Some[x + 1]
+FindItem[x: Int32] := If[x < 100, Some[x + 1], None]
 
-(* Chaining multiple ? operations *)
-Process[x: Int32] := GetValue[x]?
+(* ? returns the Err early, so a function using it on a Result returns a Result *)
+Process[x: Int32] := Ok[GetValue[x]? + 1]
+
+(* and one using it on an Option returns an Option *)
+Scaled[x: Int32] := Some[FindItem[x]? * 10]
 
 (* ? combined with the pipe operator *)
-Pipeline[x: Int32] := GetValue[x]? |> FindItem?
+Pipeline[x: Int32] := Some[x |> FindItem? |> FindItem?]
+
+Print[Process[5]]
+Print[Process[0]]
+Print[Scaled[5]]
+Print[Pipeline[98]]
+Print[Pipeline[99]]
@@ -1,6 +0,0 @@
-pub mod ast;
-pub mod lexer;
-pub mod parser;
-pub mod stdlib;
-pub mod rust_codegen;
-pub mod type_inference;
@@ -1,23 +1,188 @@
-mod ast;
-mod lexer;
-mod parser;
-mod rust_codegen;
+use w::backend::{self, BackendOptions};
+use w::build_plan::BuildPlan;
+use w::codegen_utils::Provenance;
+use w::driver::{self, CompilerCallbacks};
+use w::explain;
+use w::fix;
+use w::learn;
+use w::lints::Lint;
+use w::parser;
+use w::rust_codegen::{Arithmetic, CodegenStyle, Formatter, Profile, RustCodeGenerator};
 
 use std::fs;
 use std::fs::File;
 use std::io::Write;
+use std::path::Path;
 use std::process::Command;
 
 fn main() {
     // Use command-line argument for input file
     let args: Vec<String> = std::env::args().collect();
-    
-    // Check if an input file is provided
-    let input_file = if args.len() > 1 {
-        &args[1]
-    } else {
-        "hello_world.w"  // Default file
-    };
+
+    // `w learn` runs the interactive tutorial instead of compiling a file
+    if args.get(1).map(String::as_str) == Some("learn") {
+        if let Err(e) = learn::run(std::io::stdin().lock(), std::io::stdout()) {
+            eprintln!("Tutorial stopped: {}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    // `w fix <file>` migrates the file in place to current syntax
+    if args.get(1).map(String::as_str) == Some("fix") {
+        let Some(file) = args.get(2) else {
+            eprintln!("Usage: w fix <file.w>");
+            std::process::exit(1);
+        };
+        run_fix(file);
+        return;
+    }
+
+    // Parse flags; the first non-flag argument is the input file
+    let mut input_file = "hello_world.w";  // Default file
+    let mut codegen_builder = RustCodeGenerator::builder();
+    // Flags that affect the generated code, recorded in its header
+    let mut codegen_flags: Vec<String> = Vec::new();
+    let mut frozen = false;
+    let mut split_modules = false;
+    let mut emit_build_plan = false;
+    let mut deny_warnings = false;
+    // Warning codes not to report, e.g. W0010
+    let mut allowed_warnings: Vec<String> = Vec::new();
+    let mut backend = backend::backend(backend::DEFAULT_BACKEND).expect("the default backend exists");
+    let mut rest = args.iter().skip(1);
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--frozen" => frozen = true,
+            "--split-modules" => {
+                split_modules = true;
+                codegen_flags.push(arg.clone());
+            }
+            "--style" => {
+                let style = match rest.next().map(String::as_str) {
+                    Some("expression") => CodegenStyle::Expression,
+                    Some("statement") => CodegenStyle::Statement,
+                    other => {
+                        eprintln!("Invalid --style {:?}: expected `expression` or `statement`", other.unwrap_or(""));
+                        std::process::exit(1);
+                    }
+                };
+                codegen_builder = codegen_builder.style(style);
+                codegen_flags.push(format!("--style {:?}", style).to_lowercase());
+            }
+            "--indent" => {
+                let width = match rest.next().and_then(|w| w.parse().ok()) {
+                    Some(width) => width,
+                    None => {
+                        eprintln!("--indent expects a number of spaces");
+                        std::process::exit(1);
+                    }
+                };
+                codegen_builder = codegen_builder.indent_width(width);
+                codegen_flags.push(format!("--indent {}", width));
+            }
+            "--max-width" => {
+                let width = match rest.next().and_then(|w| w.parse().ok()) {
+                    Some(width) => width,
+                    None => {
+                        eprintln!("--max-width expects a number of characters");
+                        std::process::exit(1);
+                    }
+                };
+                codegen_builder = codegen_builder.max_width(width);
+                codegen_flags.push(format!("--max-width {}", width));
+            }
+            "--rustfmt" => {
+                codegen_builder = codegen_builder.formatter(Formatter::Rustfmt);
+                codegen_flags.push(arg.clone());
+            }
+            "--explain" => {
+                // Print the explanation of an error code and stop
+                let code = rest.next().map(String::as_str).unwrap_or("");
+                match explain::explain(code) {
+                    Some(explanation) => {
+                        println!("{}: {}\n", explanation.code, explanation.title);
+                        print!("{}", explanation.body);
+                        std::process::exit(0);
+                    }
+                    None => {
+                        eprintln!("No explanation for error code {:?}", code);
+                        std::process::exit(1);
+                    }
+                }
+            }
+            "--hoist-imports" => {
+                codegen_builder = codegen_builder.hoist_imports(true);
+                codegen_flags.push(arg.clone());
+            }
+            "--profile" => {
+                let profile = match rest.next().map(String::as_str) {
+                    Some("standard") => Profile::Standard,
+                    Some("embedded") => Profile::Embedded,
+                    other => {
+                        eprintln!("Invalid --profile {:?}: expected `standard` or `embedded`", other.unwrap_or(""));
+                        std::process::exit(1);
+                    }
+                };
+                codegen_builder = codegen_builder.profile(profile);
+                codegen_flags.push(format!("--profile {:?}", profile).to_lowercase());
+            }
+            "--arithmetic" => {
+                let arithmetic = match rest.next().map(String::as_str) {
+                    Some("unchecked") => Arithmetic::Unchecked,
+                    Some("checked") => Arithmetic::Checked,
+                    Some("wrapping") => Arithmetic::Wrapping,
+                    other => {
+                        eprintln!("Invalid --arithmetic {:?}: expected `unchecked`, `checked` or `wrapping`", other.unwrap_or(""));
+                        std::process::exit(1);
+                    }
+                };
+                codegen_builder = codegen_builder.arithmetic(arithmetic);
+                codegen_flags.push(format!("--arithmetic {:?}", arithmetic).to_lowercase());
+            }
+            "--no-std" => {
+                codegen_builder = codegen_builder.no_std(true);
+                codegen_flags.push(arg.clone());
+            }
+            "--backend" => {
+                let name = rest.next().map(String::as_str).unwrap_or("");
+                backend = match backend::backend(name) {
+                    Some(backend) => backend,
+                    None => {
+                        eprintln!("Invalid --backend {:?}: expected one of {}", name, backend::BACKENDS.join(", "));
+                        std::process::exit(1);
+                    }
+                };
+                codegen_flags.push(format!("--backend {}", name));
+            }
+            "--emit" => match rest.next().map(String::as_str) {
+                Some("build-plan") => emit_build_plan = true,
+                other => {
+                    eprintln!("Invalid --emit {:?}: expected `build-plan`", other.unwrap_or(""));
+                    std::process::exit(1);
+                }
+            },
+            "--deny-warnings" => deny_warnings = true,
+            "--allow-warning" => match rest.next() {
+                Some(code) if code.to_uppercase().starts_with('W') && explain::explain(code).is_some() => {
+                    allowed_warnings.push(code.to_uppercase());
+                }
+                other => {
+                    eprintln!("Invalid --allow-warning {:?}: expected a warning code such as W0010", other.map(String::as_str).unwrap_or(""));
+                    std::process::exit(1);
+                }
+            },
+            "--allow-lints" => {
+                codegen_builder = codegen_builder.emit_allow_attributes(true);
+                codegen_flags.push(arg.clone());
+            }
+            flag if flag.starts_with("--") => {
+                eprintln!("Unknown option: {}", flag);
+                std::process::exit(1);
+            }
+            file => input_file = file,
+        }
+    }
 
     // Read the contents of the file
     let input = match fs::read_to_string(input_file) {
@@ -28,28 +193,126 @@ fn main() {
         }
     };
     
-    let mut parser = parser::Parser::new(input);
-    let expr = parser.parse().expect("Failed to parse expression");
+    // Record compiler version, source hash and flags for reproducible output;
+    // the driver adds the imported files to the inputs
+    codegen_builder = codegen_builder.provenance(Provenance {
+        compiler_version: env!("CARGO_PKG_VERSION").to_string(),
+        flags: codegen_flags,
+        inputs: vec![(input_file.to_string(), input.clone())],
+    });
 
-    // Use Rust code generation instead of assembly
-    let mut rust_codegen = rust_codegen::RustCodeGenerator::new();
-    let rust_code = rust_codegen.generate(&expr).expect("Failed to generate Rust code");
-    
-    // Write Rust code to file
-    let output_file = "generated.rs";
-    let mut file = File::create(output_file).expect("Failed to create file");
-    file.write_all(rust_code.as_bytes()).expect("Failed to write to file");
-    
-    // Compile the generated Rust code
-    let rustc_status = Command::new("rustc")
-        .args(&[output_file, "-o", "output"])
+    // Load the imported files, check everything and generate the backend's
+    // files; the driver stops at the first phase that fails
+    let options = BackendOptions { codegen: codegen_builder.build().options().clone(), split_modules, ..BackendOptions::default() };
+    let mut warnings = WarningPrinter { file: input_file, allowed: &allowed_warnings, printed: 0 };
+    let compiled = match driver::compile_file(Path::new(input_file), &input, backend.as_mut(), &options, &mut warnings) {
+        Ok(compiled) => compiled,
+        Err(error) => {
+            eprint!("{}", error.render(input_file, &input));
+            if let Some(code) = error.code() {
+                eprintln!("For more information about this error, try `w --explain {}`.", code);
+            }
+            std::process::exit(1);
+        }
+    };
+    if deny_warnings && warnings.printed > 0 {
+        let plural = if warnings.printed == 1 { "" } else { "s" };
+        eprintln!("error: {} warning{} denied by --deny-warnings", warnings.printed, plural);
+        std::process::exit(1);
+    }
+    let (loaded, artifacts) = (compiled.program, compiled.artifacts);
+
+    // --emit build-plan: describe the build instead of carrying it out
+    if emit_build_plan {
+        let working_dir = std::env::current_dir().expect("Failed to read the working directory");
+        print!("{}", BuildPlan::new(backend.name(), Path::new(input_file), &loaded, &artifacts, &working_dir).to_json());
+        return;
+    }
+
+    for output_file in &artifacts.files {
+        if frozen {
+            // --frozen: the committed artifact must already match exactly
+            match fs::read_to_string(&output_file.path) {
+                Ok(existing) if existing == output_file.contents => {}
+                Ok(_) => {
+                    eprintln!("{} is out of date with {} (--frozen); regenerate it without --frozen", output_file.path, input_file);
+                    std::process::exit(1);
+                }
+                Err(e) => {
+                    eprintln!("Cannot read {} (--frozen): {}", output_file.path, e);
+                    std::process::exit(1);
+                }
+            }
+        } else {
+            // Write the generated code to file
+            if let Some(parent) = Path::new(&output_file.path).parent() {
+                fs::create_dir_all(parent).expect("Failed to create output directory");
+            }
+            let mut file = File::create(&output_file.path).expect("Failed to create file");
+            file.write_all(output_file.contents.as_bytes()).expect("Failed to write to file");
+        }
+    }
+
+    let Some(build) = artifacts.build else {
+        println!("Compilation of {} complete.", input_file);
+        return;
+    };
+    let status = Command::new(&build.command[0])
+        .args(&build.command[1..])
         .status()
-        .expect("Failed to run rustc");
-    
-    if !rustc_status.success() {
-        eprintln!("Rust compiler (rustc) failed");
+        .unwrap_or_else(|e| panic!("Failed to run {}: {}", build.command[0], e));
+    if !status.success() {
+        eprintln!("{} failed on the code generated by the {} backend", build.command[0], backend.name());
         std::process::exit(1);
     }
-    
-    println!("Compilation of {} complete. Run ./output to see the result.", input_file);
+    println!("Compilation of {} complete. {}", input_file, build.next_step);
+}
+
+/// Prints each lint the driver reports as a warning, unless `--allow-warning`
+/// silences its code
+struct WarningPrinter<'a> {
+    file: &'a str,
+    allowed: &'a [String],
+    printed: usize,
+}
+
+impl CompilerCallbacks for WarningPrinter<'_> {
+    fn on_lint(&mut self, lint: &Lint) {
+        if self.allowed.iter().any(|code| code == lint.code()) {
+            return;
+        }
+        match lint.span {
+            Some(span) => eprintln!("warning[{}]: {}:{}: {}", lint.code(), self.file, span, lint),
+            None => eprintln!("warning[{}]: {}: {}", lint.code(), self.file, lint),
+        }
+        self.printed += 1;
+    }
+}
+
+/// Rewrite `file` with every fix applied and list what changed
+fn run_fix(file: &str) {
+    let source = match fs::read_to_string(file) {
+        Ok(contents) => contents,
+        Err(e) => {
+            eprintln!("Error reading file {}: {}", file, e);
+            std::process::exit(1);
+        }
+    };
+    let Some(fixed) = fix::fix_source(&source) else {
+        eprintln!("error[{}]: {}: cannot fix a file that does not parse; run `w {}` to see why", parser::PARSE_ERROR_CODE, file, file);
+        std::process::exit(1);
+    };
+    if fixed.fixes.is_empty() {
+        println!("{} needs no fixes", file);
+        return;
+    }
+    if let Err(e) = fs::write(file, &fixed.source) {
+        eprintln!("Error writing file {}: {}", file, e);
+        std::process::exit(1);
+    }
+    for applied in &fixed.fixes {
+        println!("{}:{}", file, applied);
+    }
+    let plural = if fixed.fixes.len() == 1 { "" } else { "es" };
+    println!("Applied {} fix{} to {}", fixed.fixes.len(), plural, file);
 }
@@ -1,5 +0,0 @@
-pub mod io;
-pub mod math;
-pub mod string;
-pub mod list;
-pub mod map;
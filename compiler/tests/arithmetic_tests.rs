@@ -4,7 +4,7 @@ fn evaluate(input: &str) -> i64 {
     let mut lexer = Lexer::new(input.to_string());
     let mut tokens = Vec::new();
     
-    while let Some(token) = lexer.next_token() {
+    while let Some(token) = lexer.next_token().unwrap() {
         tokens.push(token);
     }
     
@@ -15,7 +15,7 @@ fn evaluate(input: &str) -> i64 {
                 let mut numbers = Vec::new();
                 for token in tokens.iter().skip(2) {
                     match token {
-                        Token::Number(n) => numbers.push(*n),
+                        Token::Number(n, _) => numbers.push(*n as i32),
                         Token::Comma => continue,
                         Token::RightBracket => break,
                         _ => panic!("Invalid token in arithmetic expression"),
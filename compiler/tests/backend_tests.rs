@@ -0,0 +1,100 @@
+mod common;
+
+use common::parse;
+use w::backend::{backend, BackendOptions, CodegenBackend, RustBackend, BACKENDS, DEFAULT_BACKEND};
+use w::imports::LoadedProgram;
+use w::rust_codegen::{CodegenOptions, RustCodeGenerator};
+
+use std::process::Command;
+
+fn program(entry: &str, modules: &[(&str, &str)]) -> LoadedProgram {
+    LoadedProgram {
+        entry: parse(entry),
+        modules: modules.iter().map(|(name, source)| (name.to_string(), parse(source))).collect(),
+        module_paths: modules.iter().map(|(name, _)| format!("{}.w", name).into()).collect(),
+        module_sources: modules.iter().map(|(_, source)| source.to_string()).collect(),
+    }
+}
+
+// ============================================================================
+// Selection Tests
+// ============================================================================
+
+#[test]
+fn test_every_listed_backend_exists() {
+    assert!(BACKENDS.contains(&DEFAULT_BACKEND));
+    for name in BACKENDS {
+        assert_eq!(backend(name).map(|backend| backend.name()), Some(*name));
+    }
+}
+
+#[test]
+fn test_unknown_backend() {
+    assert!(backend("wasm").is_none());
+    assert!(backend("").is_none());
+}
+
+// ============================================================================
+// Rust Backend Tests
+// ============================================================================
+
+#[test]
+fn test_rust_backend_matches_codegen() {
+    let source = "Add[a: Int32, b: Int32] := a + b\nPrint[Add[1, 2]]";
+    let artifacts = RustBackend.generate(&program(source, &[]), &BackendOptions::default()).unwrap();
+    assert_eq!(artifacts.files.len(), 1);
+    assert_eq!(artifacts.files[0].path, "generated.rs");
+    assert_eq!(artifacts.files[0].contents, RustCodeGenerator::new().generate(&parse(source)).unwrap());
+
+    let build = artifacts.build.unwrap();
+    assert_eq!(build.command, ["rustc", "generated.rs", "-o", "output"]);
+    assert_eq!(build.outputs, ["output"]);
+    assert_eq!(build.next_step, "Run ./output to see the result.");
+}
+
+#[test]
+fn test_rust_backend_split_modules() {
+    let loaded = program("Print[Square[3]]", &[("math", "Square[n: Int32] := n * n")]);
+    let single = RustBackend.generate(&loaded, &BackendOptions::default()).unwrap();
+    assert_eq!(single.files.len(), 1);
+
+    let options = BackendOptions { split_modules: true, ..BackendOptions::default() };
+    let split = RustBackend.generate(&loaded, &options).unwrap();
+    let paths: Vec<&str> = split.files.iter().map(|file| file.path.as_str()).collect();
+    assert!(paths.len() > 1, "got: {:?}", paths);
+    assert!(paths.iter().all(|path| path.starts_with("generated_modules/")), "got: {:?}", paths);
+    assert_eq!(split.build.unwrap().command[1], paths[0]);
+}
+
+#[test]
+fn test_rust_backend_no_std_builds_library() {
+    let options = BackendOptions {
+        codegen: CodegenOptions { no_std: true, ..CodegenOptions::default() },
+        ..BackendOptions::default()
+    };
+    let build = RustBackend.generate(&program("Add[a: Int32, b: Int32] := a + b", &[]), &options).unwrap().build.unwrap();
+    assert!(build.command.ends_with(&["-o".to_string(), "libgenerated.rlib".to_string()]), "got: {:?}", build.command);
+    assert!(build.command.contains(&"lib".to_string()));
+}
+
+// ============================================================================
+// Command-Line Tests
+// ============================================================================
+
+#[test]
+fn test_cli_selects_backend() {
+    let dir = std::env::temp_dir().join(format!("w_backend_cli_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("main.w"), "Print[6 * 7]\n").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_w")).args(["--backend", "rust", "main.w"]).current_dir(&dir).output().unwrap();
+    let run = Command::new(dir.join("output")).output();
+    let unknown = Command::new(env!("CARGO_BIN_EXE_w")).args(["--backend", "wasm", "main.w"]).current_dir(&dir).output().unwrap();
+    std::fs::remove_dir_all(&dir).ok();
+
+    assert!(output.status.success(), "got: {}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(String::from_utf8(run.unwrap().stdout).unwrap(), "42\n");
+    assert_eq!(unknown.status.code(), Some(1));
+    let stderr = String::from_utf8(unknown.stderr).unwrap();
+    assert!(stderr.contains("Invalid --backend \"wasm\": expected one of rust"), "got: {}", stderr);
+}
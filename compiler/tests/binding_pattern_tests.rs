@@ -0,0 +1,129 @@
+mod common;
+
+use common::{compile_and_run, generate, parse};
+use w::ast::{Expression, Pattern, Type};
+use w::lexer::{Lexer, Token};
+use w::rust_codegen::RustCodeGenerator;
+use w::type_inference::{TypeError, TypeInference};
+
+fn infer(input: &str) -> Result<Type, TypeError> {
+    TypeInference::new().infer_expression(&parse(input))
+}
+
+/// The pattern of the first arm of a Match
+fn first_pattern(input: &str) -> Pattern {
+    match parse(input) {
+        Expression::Match { arms, .. } => arms[0].0.clone(),
+        other => panic!("expected Match, got: {:?}", other),
+    }
+}
+
+// ============================================================================
+// Parser Tests
+// ============================================================================
+
+#[test]
+fn test_lex_at_sign() {
+    let mut lexer = Lexer::new("whole @ x".to_string());
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Identifier("whole".to_string())));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::At));
+}
+
+#[test]
+fn test_parse_binding_pattern() {
+    let pattern = first_pattern("Match[o, [whole @ Some[x], x], [None, 0]]");
+    assert_eq!(
+        pattern,
+        Pattern::Binding {
+            name: "whole".to_string(),
+            pattern: Box::new(Pattern::Constructor {
+                name: "Some".to_string(),
+                patterns: vec![Pattern::Variable("x".to_string())],
+            }),
+        }
+    );
+}
+
+#[test]
+fn test_binding_covers_every_alternative() {
+    let pattern = first_pattern("Match[n, [small @ 1 | 2 | 3, small], [_, 0]]");
+    match pattern {
+        Pattern::Binding { name, pattern } => {
+            assert_eq!(name, "small");
+            assert!(matches!(*pattern, Pattern::Or(ref alternatives) if alternatives.len() == 3), "got: {:?}", pattern);
+        }
+        other => panic!("expected Binding, got: {:?}", other),
+    }
+}
+
+#[test]
+fn test_display_binding_pattern() {
+    let pattern = first_pattern("Match[p, [(a, inner @ Some[_]), a], [_, 0]]");
+    assert_eq!(pattern.to_string(), "(a, inner @ Some[_])");
+}
+
+// ============================================================================
+// Type Inference Tests
+// ============================================================================
+
+#[test]
+fn test_binding_has_whole_value_type() {
+    assert_eq!(infer("Match[Some[1], [whole @ Some[_], whole], [None, None]]").unwrap(), Type::Option(Box::new(Type::Int32)));
+}
+
+#[test]
+fn test_binding_and_inner_variable_both_bound() {
+    assert_eq!(infer("Match[Some[1], [whole @ Some[x], x], [None, 0]]").unwrap(), Type::Int32);
+}
+
+#[test]
+fn test_binding_sub_pattern_is_checked() {
+    assert!(matches!(
+        infer("Match[1, [n @ \"one\", n], [_, 0]]"),
+        Err(TypeError::TypeMismatch { .. })
+    ));
+}
+
+#[test]
+fn test_binding_in_every_alternative() {
+    assert_eq!(infer("Match[2, [n @ 1 | 2, n], [_, 0]]").unwrap(), Type::Int32);
+}
+
+// ============================================================================
+// Code Generation Tests
+// ============================================================================
+
+#[test]
+fn test_generate_binding_pattern() {
+    let code = generate("Match[o, [whole @ Some[x], x], [None, 0]]");
+    assert!(code.contains("whole @ Some(x) =>"), "got: {}", code);
+}
+
+#[test]
+fn test_generate_binding_over_alternatives() {
+    let code = generate("Match[n, [small @ 1 | 2 | 3, small], [_, 0]]");
+    assert!(code.contains("small @ (1 | 2 | 3) =>"), "got: {}", code);
+}
+
+#[test]
+fn test_binding_string_guard_is_unsupported() {
+    let program = parse("Match[s, [word @ \"hi\", word], [_, \"\"]]");
+    assert!(RustCodeGenerator::new().generate(&program).is_err());
+}
+
+// ============================================================================
+// End-to-End Tests
+// ============================================================================
+
+#[test]
+fn test_binding_patterns_run() {
+    let code = generate(
+        "Let[o, Some[5]]
+Print[Match[o, [whole @ Some[x], x + 1], [None, 0]]]
+Let[n, 3]
+Print[Match[n, [small @ 1 | 2 | 3, small * 10], [other, other]]]
+Let[p, (1, Some[2])]
+Print[Match[p, [(a, inner @ Some[_]), a], [_, 0]]]",
+    );
+    assert_eq!(compile_and_run(&code, "run"), "6\n30\n1\n");
+}
@@ -0,0 +1,99 @@
+mod common;
+
+use common::{generate, parse};
+use w::ast::{Expression, Operator, Type};
+use w::lexer::{Lexer, Token};
+use w::type_inference::{TypeError, TypeInference};
+
+fn infer(input: &str) -> Result<Type, TypeError> {
+    TypeInference::new().infer_expression(&parse(input))
+}
+
+fn tokens(input: &str) -> Vec<Token> {
+    let mut lexer = Lexer::new(input.to_string());
+    let mut tokens = Vec::new();
+    while let Some(token) = lexer.next_token().unwrap() {
+        tokens.push(token);
+    }
+    tokens
+}
+
+// ============================================================================
+// Lexer Tests
+// ============================================================================
+
+#[test]
+fn test_lex_bitwise_operators() {
+    assert_eq!(tokens("& | ^^ << >>"), vec![
+        Token::BitAnd, Token::BitOr, Token::BitXor, Token::ShiftLeft, Token::ShiftRight,
+    ]);
+}
+
+#[test]
+fn test_lex_keeps_longer_operators() {
+    assert_eq!(tokens("&& || |> ^ < >"), vec![
+        Token::And, Token::Or, Token::Pipe, Token::Power, Token::LessThan, Token::GreaterThan,
+    ]);
+}
+
+// ============================================================================
+// Precedence Tests
+// ============================================================================
+
+#[test]
+fn test_shift_binds_looser_than_addition() {
+    match parse("1 << 2 + 3") {
+        Expression::BinaryOp { operator: Operator::ShiftLeft, right, .. } => {
+            assert!(matches!(*right, Expression::BinaryOp { operator: Operator::Add, .. }), "got: {:?}", right);
+        }
+        other => panic!("Expected shift at the root, got: {:?}", other),
+    }
+}
+
+#[test]
+fn test_bitwise_precedence_matches_rust() {
+    // a | b ^^ c & d == a | (b ^ (c & d))
+    let rust_code = generate("Mix[a: UInt32, b: UInt32, c: UInt32, d: UInt32] := a | b ^^ c & d");
+    assert!(rust_code.contains("(a | (b ^ (c & d)))"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_bitwise_binds_tighter_than_comparison() {
+    let rust_code = generate("IsOdd[x: UInt32] := x & 1 == 1");
+    assert!(rust_code.contains("((x & 1) == 1)"), "got: {}", rust_code);
+    assert!(rust_code.contains("-> bool"), "got: {}", rust_code);
+}
+
+// ============================================================================
+// Codegen Tests
+// ============================================================================
+
+#[test]
+fn test_generate_shifts() {
+    let rust_code = generate("Pack[hi: UInt16, lo: UInt16] := hi << 8 | lo");
+    assert!(rust_code.contains("fn pack(hi: u16, lo: u16) -> u16"), "got: {}", rust_code);
+    assert!(rust_code.contains("((hi << 8) | lo)"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_power_is_still_exponentiation() {
+    let rust_code = generate("Cube[x: Int32] := x ^ 3");
+    assert!(rust_code.contains(".pow("), "got: {}", rust_code);
+}
+
+// ============================================================================
+// Type Inference Tests
+// ============================================================================
+
+#[test]
+fn test_bitwise_on_integers() {
+    assert_eq!(infer("6 & 3").unwrap(), Type::Int32);
+    assert_eq!(infer("1 << 4").unwrap(), Type::Int32);
+}
+
+#[test]
+fn test_bitwise_rejects_non_integers() {
+    assert!(matches!(infer("1.5 & 2.5"), Err(TypeError::TypeMismatch { .. })));
+    assert!(matches!(infer("true | false"), Err(TypeError::TypeMismatch { .. })));
+    assert!(matches!(infer("1 >> 2.0"), Err(TypeError::TypeMismatch { .. })));
+}
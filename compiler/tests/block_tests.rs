@@ -0,0 +1,143 @@
+mod common;
+
+use common::{compile_and_run, generate, parse};
+use w::ast::{Expression, Pattern, Type};
+use w::lints::{lint_program, LintKind};
+use w::parser::Parser;
+use w::type_inference::{TypeError, TypeInference};
+
+fn check(input: &str) -> Vec<TypeError> {
+    let expressions = match parse(input) {
+        Expression::Program(expressions) => expressions,
+        other => vec![other],
+    };
+    TypeInference::new().check_program_errors(&expressions)
+}
+
+/// Type of the block `input`, bound to a name and checked
+fn infer(input: &str) -> Result<Type, TypeError> {
+    let expressions = vec![Expression::LetPattern {
+        pattern: Pattern::Variable("value".to_string()),
+        value: Box::new(parse(input)),
+        fallback: None,
+    }];
+    let mut inference = TypeInference::new();
+    match inference.check_program_errors(&expressions).into_iter().next() {
+        Some(error) => Err(error),
+        None => Ok(inference.type_table(&expressions).lookup("value").cloned().expect("a settled type")),
+    }
+}
+
+const AREA: &str = "Area[w: Int32, h: Int32] := Block[Let[a, w * h], Print[a], a + 1]";
+
+// ============================================================================
+// Parser Tests
+// ============================================================================
+
+#[test]
+fn test_parse_block() {
+    match parse("Block[Let[x, 1], x]") {
+        Expression::Block(statements) => {
+            assert_eq!(statements.len(), 2);
+            assert!(matches!(&statements[0], Expression::LetPattern { pattern: Pattern::Variable(name), .. } if name == "x"));
+            assert_eq!(statements[1], Expression::Identifier("x".to_string()));
+        }
+        other => panic!("Expected Block, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_empty_block_does_not_parse() {
+    assert!(Parser::new("Block[]".to_string()).parse().is_none());
+}
+
+// ============================================================================
+// Type Inference Tests
+// ============================================================================
+
+#[test]
+fn test_block_has_type_of_its_value() {
+    assert_eq!(infer("Block[Let[x, 2], Let[y, \"s\"], x * 3]"), Ok(Type::Int32));
+    assert_eq!(infer("Block[Print[1], Let[x, 2]]"), Ok(Type::Tuple(vec![])));
+}
+
+#[test]
+fn test_block_bindings_stay_in_block() {
+    assert_eq!(check(&format!("{}\nLet[n, Area[2, 3]]", AREA)), vec![]);
+    assert_eq!(check("Let[n, Block[Let[x, 1], x]]\nPrint[n + x]").len(), 1);
+    assert_eq!(
+        check("Let[n, Block[Let[x, 1], x]]\nLet[m, x]"),
+        vec![TypeError::UndefinedIdentifier("x".to_string())]
+    );
+}
+
+#[test]
+fn test_fallback_has_type_of_block() {
+    assert_eq!(infer("Block[LetElse[Some[n], Some[5], 0], n * 2]"), Ok(Type::Int32));
+    match infer("Block[LetElse[Some[n], Some[5], \"none\"], n * 2]") {
+        Err(TypeError::TypeMismatch { expected, actual, context }) => {
+            assert_eq!((expected, actual), (Type::Int32, Type::String));
+            assert_eq!(context, "LetElse fallback in Block");
+        }
+        other => panic!("Expected TypeMismatch, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_unused_block_binding_is_reported() {
+    let mut parser = Parser::new("Let[n, Block[Let[x, 1], Let[y, 2], y]]\nPrint[n]".to_string());
+    let program = parser.parse().unwrap();
+    let kinds: Vec<LintKind> = lint_program(&program, parser.expression_spans()).into_iter().map(|lint| lint.kind).collect();
+    assert_eq!(kinds, vec![LintKind::UnusedBinding("x".to_string())]);
+}
+
+// ============================================================================
+// Code Generation Tests
+// ============================================================================
+
+#[test]
+fn test_function_body_block_is_statements() {
+    let rust_code = generate(AREA);
+    assert!(
+        rust_code.contains("fn area(w: i32, h: i32) -> i32 {\n    let a = (w * h);\n    println!(\"{}\", a);\n    (a + 1)\n}"),
+        "got: {}",
+        rust_code
+    );
+}
+
+#[test]
+fn test_value_block_is_scoped() {
+    let rust_code = generate("Let[total, Block[Let[x, 2], x + 1]]");
+    assert!(rust_code.contains("let total = {\n        let x = 2;\n        (x + 1)\n    };"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_fallback_returns_or_breaks() {
+    let rust_code = generate("FirstOr[xs: List[Int32]] := Block[LetElse[Some[first], At[xs, 0], 0], first]");
+    assert!(rust_code.contains("else {\n        return 0;\n    };"), "got: {}", rust_code);
+
+    let rust_code = generate("Let[n, Block[LetElse[Some[x], Some[1], 0], x]]");
+    assert!(rust_code.contains("let n = '__w_0_block: {"), "got: {}", rust_code);
+    assert!(rust_code.contains("break '__w_0_block 0;"), "got: {}", rust_code);
+}
+
+// ============================================================================
+// Runtime Tests
+// ============================================================================
+
+#[test]
+fn test_blocks_run() {
+    let source = format!(
+        "{}\n\
+         FirstOr[xs: List[Int32]] := Block[LetElse[Some[first], At[xs, 0], 0], first * 10]\n\
+         Let[total, Block[Let[x, 2], Let[y, x * 3], x + y]]\n\
+         Print[total]\n\
+         Print[Area[2, 3]]\n\
+         Print[FirstOr[[4, 5]]]\n\
+         Print[FirstOr[[]]]\n\
+         Block[LetElse[Some[n], At[[1], 3], Print[\"none\"]], Print[n]]\n\
+         Print[Map[Function[{{v}}, Block[Let[w, v * v], w + 1]], [1, 2]]]",
+        AREA
+    );
+    assert_eq!(compile_and_run(&generate(&source), "run"), "8\n6\n7\n40\n0\nnone\n[2, 5]\n");
+}
@@ -0,0 +1,102 @@
+use w::backend::{Artifacts, BuildStep};
+use w::build_plan::{BuildPlan, PlannedImport};
+use w::imports::load_imports;
+use w::parser::Parser;
+use w::rust_codegen::GeneratedFile;
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// A directory holding `files`, removed by the caller
+fn project(name: &str, files: &[(&str, &str)]) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("w_build_plan_{}_{}", name, std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    for (file, contents) in files {
+        std::fs::write(dir.join(file), contents).unwrap();
+    }
+    dir
+}
+
+fn artifacts() -> Artifacts {
+    Artifacts {
+        files: vec![GeneratedFile { path: "generated.rs".to_string(), contents: String::new() }],
+        build: Some(BuildStep {
+            command: vec!["rustc".to_string(), "generated.rs".to_string(), "-o".to_string(), "output".to_string()],
+            outputs: vec!["output".to_string()],
+            next_step: String::new(),
+        }),
+    }
+}
+
+// ============================================================================
+// Plan Tests
+// ============================================================================
+
+#[test]
+fn test_plan_lists_imports_and_outputs() {
+    let dir = project("imports", &[("main.w", "Import[\"geometry.w\"]\nPrint[Area[2]]"), ("geometry.w", "Area[n: Int32] := n * n")]);
+    let entry = dir.join("main.w");
+    let mut parser = Parser::new(std::fs::read_to_string(&entry).unwrap());
+    let loaded = load_imports(&entry, parser.parse().unwrap()).unwrap();
+    let plan = BuildPlan::new("rust", &entry, &loaded, &artifacts(), &dir);
+    std::fs::remove_dir_all(&dir).ok();
+
+    assert_eq!(plan.inputs, ["main.w", "geometry.w"]);
+    assert_eq!(plan.imports, [PlannedImport { module: "Geometry".to_string(), path: "geometry.w".to_string() }]);
+    assert_eq!(plan.outputs, ["generated.rs", "output"]);
+    assert_eq!(plan.commands, [["rustc", "generated.rs", "-o", "output"]]);
+}
+
+#[test]
+fn test_paths_outside_working_directory_are_absolute() {
+    let dir = project("outside", &[("main.w", "Print[1]")]);
+    let entry = dir.join("main.w");
+    let mut parser = Parser::new("Print[1]".to_string());
+    let loaded = load_imports(&entry, parser.parse().unwrap()).unwrap();
+    let plan = BuildPlan::new("rust", &entry, &loaded, &artifacts(), Path::new("/nonexistent"));
+    std::fs::remove_dir_all(&dir).ok();
+
+    assert!(Path::new(&plan.inputs[0]).is_absolute(), "got: {:?}", plan.inputs);
+}
+
+#[test]
+fn test_plan_json() {
+    let plan = BuildPlan {
+        backend: "rust".to_string(),
+        inputs: vec!["my \"main\".w".to_string()],
+        imports: vec![],
+        outputs: vec!["generated.rs".to_string()],
+        commands: vec![vec!["rustc".to_string(), "a\\b".to_string()]],
+    };
+    assert_eq!(
+        plan.to_json(),
+        "{\n  \"backend\": \"rust\",\n  \"inputs\": [\"my \\\"main\\\".w\"],\n  \"imports\": [],\n  \
+         \"outputs\": [\"generated.rs\"],\n  \"commands\": [[\"rustc\", \"a\\\\b\"]]\n}\n"
+    );
+}
+
+// ============================================================================
+// Command-Line Tests
+// ============================================================================
+
+#[test]
+fn test_cli_emit_build_plan() {
+    let dir = project("cli", &[("main.w", "Import[\"shape_utils.w\"]\nPrint[Square[3]]"), ("shape_utils.w", "Square[n: Int32] := n * n")]);
+    let output = Command::new(env!("CARGO_BIN_EXE_w")).args(["--emit", "build-plan", "main.w"]).current_dir(&dir).output().unwrap();
+    let wrote_nothing = !dir.join("generated.rs").exists() && !dir.join("output").exists();
+    std::fs::remove_dir_all(&dir).ok();
+
+    assert!(output.status.success(), "got: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(wrote_nothing);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("\"inputs\": [\"main.w\", \"shape_utils.w\"],"), "got: {}", stdout);
+    assert!(stdout.contains("\"imports\": [{\"module\": \"ShapeUtils\", \"path\": \"shape_utils.w\"}],"), "got: {}", stdout);
+    assert!(stdout.contains("\"commands\": [[\"rustc\", \"generated.rs\", \"-o\", \"output\"]]"), "got: {}", stdout);
+}
+
+#[test]
+fn test_cli_rejects_unknown_emit() {
+    let output = Command::new(env!("CARGO_BIN_EXE_w")).args(["--emit", "graph", "main.w"]).output().unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8(output.stderr).unwrap().contains("Invalid --emit \"graph\""));
+}
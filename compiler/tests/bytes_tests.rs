@@ -0,0 +1,72 @@
+mod common;
+
+use common::{compile_and_run, parse, scratch_dir, try_generate};
+use w::ast::Type;
+use w::type_inference::{TypeError, TypeInference};
+
+fn infer(input: &str) -> Result<Type, TypeError> {
+    TypeInference::new().infer_expression(&parse(input))
+}
+
+// ============================================================================
+// Parsing / Type Tests
+// ============================================================================
+
+#[test]
+fn test_parse_bytes_type_annotation() {
+    let rust_code = try_generate("Checksum[data: Bytes] := data").unwrap();
+    assert!(rust_code.contains("fn checksum(data: Vec<u8>) -> Vec<u8>"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_infer_bytes_builtins() {
+    assert_eq!(infer("Bytes[0x01, 0xFF]").unwrap(), Type::Bytes);
+    assert_eq!(infer("ReadFileBytes[\"data.bin\"]").unwrap(),
+        Type::Result(Box::new(Type::Bytes), Box::new(Type::String)));
+    assert_eq!(infer("At[Bytes[1, 2], 0]").unwrap(), Type::Option(Box::new(Type::UInt8)));
+}
+
+#[test]
+fn test_byte_literal_out_of_range_is_rejected() {
+    let err = infer("Bytes[0x01, 256]").unwrap_err();
+    assert!(err.to_string().contains("256"), "got: {}", err);
+    assert!(try_generate("Print[Bytes[256]]").is_err());
+}
+
+#[test]
+fn test_at_requires_bytes() {
+    assert!(matches!(infer("At[\"abc\", 0]"), Err(TypeError::TypeMismatch { .. })));
+}
+
+// ============================================================================
+// Codegen Tests
+// ============================================================================
+
+#[test]
+fn test_generate_bytes_literal() {
+    let rust_code = try_generate("Print[Bytes[0x01, 0xFF]]").unwrap();
+    assert!(rust_code.contains("vec![0x01u8, 0xffu8]"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_literal_runs_and_at_is_bounds_checked() {
+    let rust_code = try_generate("Print[Bytes[0x01, 0xFF]]\nPrint[At[Bytes[7, 8], 1]]\nPrint[At[Bytes[7, 8], 5]]").unwrap();
+    assert_eq!(compile_and_run(&rust_code, "at"), "[1, 255]\nSome(8)\nNone\n");
+}
+
+#[test]
+fn test_read_file_bytes() {
+    let dir = scratch_dir("read");
+    let data = dir.join("data.bin");
+    std::fs::write(&data, [0x00, 0x10, 0xFF]).unwrap();
+
+    let rust_code = try_generate(&format!("Print[ReadFileBytes[\"{}\"]]", data.display())).unwrap();
+    assert!(rust_code.contains("std::fs::read("), "got: {}", rust_code);
+    assert_eq!(compile_and_run(&rust_code, "read"), "Ok([0, 16, 255])\n");
+}
+
+#[test]
+fn test_read_file_bytes_return_type() {
+    let rust_code = try_generate("Load[path: String] := ReadFileBytes[path]").unwrap();
+    assert!(rust_code.contains("fn load(path: String) -> Result<Vec<u8>, String>"), "got: {}", rust_code);
+}
@@ -0,0 +1,121 @@
+use w::cancellation::CancellationToken;
+use w::driver::{compile_with_cancellation, CompileError, CompilerCallbacks, NoCallbacks, ParseStats, TypecheckStats};
+use w::parser::Parser;
+use w::rust_codegen::CodegenOptions;
+use w::type_inference::{TypeError, TypeInference};
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+fn cancelled_token() -> CancellationToken {
+    let token = CancellationToken::new();
+    token.cancel();
+    token
+}
+
+/// Cancels the token as soon as parsing finishes, like an editor receiving
+/// a keystroke mid-compilation
+struct CancelAfterParse {
+    token: CancellationToken,
+    typechecked: bool,
+}
+
+impl CompilerCallbacks for CancelAfterParse {
+    fn on_parse_complete(&mut self, _stats: &ParseStats) {
+        self.token.cancel();
+    }
+
+    fn on_typecheck_complete(&mut self, _stats: &TypecheckStats) {
+        self.typechecked = true;
+    }
+}
+
+// ============================================================================
+// Token Tests
+// ============================================================================
+
+#[test]
+fn test_clones_share_the_flag() {
+    let token = CancellationToken::new();
+    let clone = token.clone();
+    assert!(!clone.is_cancelled());
+    token.cancel();
+    assert!(clone.is_cancelled());
+}
+
+#[test]
+fn test_token_from_atomic_bool() {
+    let flag = Arc::new(AtomicBool::new(false));
+    let token = CancellationToken::from(flag.clone());
+    flag.store(true, Ordering::Relaxed);
+    assert!(token.is_cancelled());
+}
+
+// ============================================================================
+// Pass Tests
+// ============================================================================
+
+#[test]
+fn test_cancelled_parse_returns_none() {
+    let mut parser = Parser::new("Print[1]\nPrint[2]".to_string()).with_cancellation(cancelled_token());
+    assert_eq!(parser.parse(), None);
+    assert!(parser.was_cancelled());
+    assert!(parser.lex_error().is_none());
+}
+
+#[test]
+fn test_uncancelled_parse_succeeds() {
+    let mut parser = Parser::new("Print[1]".to_string()).with_cancellation(CancellationToken::new());
+    assert!(parser.parse().is_some());
+    assert!(!parser.was_cancelled());
+}
+
+#[test]
+fn test_parse_error_is_not_cancellation() {
+    let mut parser = Parser::new("Print[1".to_string());
+    assert_eq!(parser.parse(), None);
+    assert!(!parser.was_cancelled());
+}
+
+#[test]
+fn test_cancelled_inference() {
+    let expr = Parser::new("1 + 2".to_string()).parse().unwrap();
+    let result = TypeInference::new().with_cancellation(cancelled_token()).infer_expression(&expr);
+    assert_eq!(result, Err(TypeError::Cancelled));
+}
+
+#[test]
+fn test_cancellation_reaches_nested_scopes() {
+    // The token is checked inside function bodies, not only at the top level
+    let expr = Parser::new("Double[x: Int32] := x * 2".to_string()).parse().unwrap();
+    let token = CancellationToken::new();
+    let mut inference = TypeInference::new().with_cancellation(token.clone());
+    assert!(inference.infer_expression(&expr).is_ok());
+    token.cancel();
+    assert_eq!(inference.infer_expression(&expr), Err(TypeError::Cancelled));
+}
+
+// ============================================================================
+// Driver Tests
+// ============================================================================
+
+#[test]
+fn test_compile_cancelled_before_start() {
+    let result = compile_with_cancellation("Print[1]", &CodegenOptions::default(), &mut NoCallbacks, &cancelled_token());
+    assert_eq!(result, Err(CompileError::Cancelled));
+}
+
+#[test]
+fn test_compile_cancelled_between_phases() {
+    let token = CancellationToken::new();
+    let mut callbacks = CancelAfterParse { token: token.clone(), typechecked: false };
+    let result = compile_with_cancellation("Print[1]", &CodegenOptions::default(), &mut callbacks, &token);
+    assert_eq!(result, Err(CompileError::Cancelled));
+    assert!(!callbacks.typechecked);
+}
+
+#[test]
+fn test_cancelled_has_no_code() {
+    assert_eq!(CompileError::Cancelled.code(), None);
+    assert_eq!(TypeError::Cancelled.code(), "E0020");
+}
@@ -0,0 +1,197 @@
+mod common;
+
+use common::{compile_and_run_output, parse};
+use w::ast::{Expression, Type};
+use w::rust_codegen::{Arithmetic, RustCodeGenerator};
+use w::stdlib::arithmetic::{self, ArithmeticError};
+use w::type_inference::{TypeError, TypeInference};
+
+use std::process::Command;
+
+fn check(input: &str) -> Result<(), TypeError> {
+    let expressions = match parse(input) {
+        Expression::Program(expressions) => expressions,
+        other => vec![other],
+    };
+    TypeInference::new().with_checked_arithmetic().check_program(&expressions)
+}
+
+fn generate(input: &str, arithmetic: Arithmetic) -> String {
+    let mut codegen = RustCodeGenerator::builder().arithmetic(arithmetic).build();
+    codegen.generate(&parse(input)).unwrap()
+}
+
+const SCALE: &str = "Scale[n: Int32, k: Int32] := n * k + 1";
+
+/// `SCALE` as checked arithmetic allows it, returning its overflow
+const CHECKED_SCALE: &str = "Scale[n: Int32, k: Int32] := Ok[n * k + 1]";
+
+// ============================================================================
+// Code Generation Tests
+// ============================================================================
+
+#[test]
+fn test_unchecked_uses_operators() {
+    let rust_code = generate(SCALE, Arithmetic::Unchecked);
+    assert!(rust_code.contains("((n * k) + 1)"), "got: {}", rust_code);
+    assert!(!rust_code.contains("__w_arithmetic"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_checked_returns_errors() {
+    let rust_code = generate(CHECKED_SCALE, Arithmetic::Checked);
+    let mul = "__w_arithmetic::checked_mul(n, k).map_err(|error| format!(\"Arithmetic: * {}\", error))?";
+    let add = format!("__w_arithmetic::checked_add({}, 1).map_err(|error| format!(\"Arithmetic: + {{}}\", error))?", mul);
+    assert!(rust_code.contains(&add), "got: {}", rust_code);
+    assert!(rust_code.contains("-> Result<i32, String>"), "got: {}", rust_code);
+    assert_eq!(rust_code.matches("mod __w_arithmetic").count(), 1, "got: {}", rust_code);
+}
+
+#[test]
+fn test_checked_leaves_literals_and_floats() {
+    let rust_code = generate("Half[x: Float64] := x / 2.0
+Print[60 * 60]", Arithmetic::Checked);
+    assert!(rust_code.contains("(x / 2.0)"), "got: {}", rust_code);
+    assert!(rust_code.contains("(60 * 60)"), "got: {}", rust_code);
+    assert!(!rust_code.contains("__w_arithmetic::checked_"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_wrapping_calls_wrapping_functions() {
+    let rust_code = generate(SCALE, Arithmetic::Wrapping);
+    assert!(rust_code.contains("__w_arithmetic::wrapping_add(__w_arithmetic::wrapping_mul(n, k), 1)"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_power_and_shift_arguments() {
+    let rust_code = generate("Bits[n: Int32] := (2 ^ n) << 1", Arithmetic::Wrapping);
+    assert!(rust_code.contains("__w_arithmetic::wrapping_shl(__w_arithmetic::wrapping_pow(2 as i32, n as u32), 1 as u32)"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_comparisons_and_strings_keep_operators() {
+    let rust_code = generate("Print[1 < 2]\nPrint[\"a\" + \"b\"]", Arithmetic::Checked);
+    assert!(rust_code.contains("(1 < 2)"), "got: {}", rust_code);
+    assert!(rust_code.contains("format!(\"ab\")"), "got: {}", rust_code);
+}
+
+// ============================================================================
+// Support Module Tests
+// ============================================================================
+
+#[test]
+fn test_checked_functions_return_result() {
+    assert_eq!(arithmetic::checked_add(2, 3), Ok(5));
+    assert_eq!(arithmetic::checked_add(i32::MAX, 1), Err(ArithmeticError::Overflow { type_name: "i32" }));
+    assert_eq!(arithmetic::checked_div(7u8, 0), Err(ArithmeticError::DivisionByZero));
+    assert_eq!(arithmetic::checked_shl(1i64, 64), Err(ArithmeticError::Overflow { type_name: "i64" }));
+    assert_eq!(arithmetic::checked_div(1.0, 0.0), Ok(f64::INFINITY));
+}
+
+#[test]
+fn test_wrapping_functions_wrap() {
+    assert_eq!(arithmetic::wrapping_add(i32::MAX, 1), i32::MIN);
+    assert_eq!(arithmetic::wrapping_sub(0u8, 1), 255);
+    assert_eq!(arithmetic::wrapping_pow(2, 32), 0);
+}
+
+// ============================================================================
+// Type Checking Tests
+// ============================================================================
+
+#[test]
+fn test_checked_arithmetic_needs_result_function() {
+    assert_eq!(check(CHECKED_SCALE), Ok(()));
+    assert_eq!(check("F[x: Int32] := Block[Let[y, x + 1], Ok[y]]\nPrint[60 * 60]"), Ok(()));
+    assert_eq!(check("Half[x: Float64] := x / 2.0"), Ok(()));
+
+    let error = check(SCALE).unwrap_err();
+    assert_eq!(
+        error,
+        TypeError::UncheckedArithmetic { operator: "*".to_string(), function: "Scale".to_string(), returns: Type::Int32 }
+    );
+    assert_eq!(error.code(), "E0031");
+    assert!(error.to_string().contains("wrap its value in Ok[...] so it returns Result[Int32, String]"), "got: {}", error);
+}
+
+#[test]
+fn test_checked_arithmetic_without_function_panics() {
+    // Top-level code and closures have no function to return an Err from
+    assert_eq!(check("Let[x, 2]\nPrint[x << 1]"), Ok(()));
+    assert_eq!(check("F[xs: List[Int32]] := Map[Function[{x}, x - 1], xs]"), Ok(()));
+
+    let rust_code = generate("Let[x, 2]\nPrint[x << 1]\nF[xs: List[Int32]] := Ok[Map[Function[{x}, x - 1], xs]]", Arithmetic::Checked);
+    let panics = |call: &str, operator: &str| {
+        format!("{}.unwrap_or_else(|error| panic!(\"Arithmetic: {} {{}}\", error))", call, operator)
+    };
+    assert!(rust_code.contains(&panics("__w_arithmetic::checked_shl(x, 1 as u32)", "<<")), "got: {}", rust_code);
+    assert!(rust_code.contains(&panics("__w_arithmetic::checked_sub(x, 1)", "-")), "got: {}", rust_code);
+}
+
+#[test]
+fn test_checked_error_type_is_string() {
+    let error = check("F[x: Int32] := Block[Let[y, x * 2], Err[y]]").unwrap_err();
+    assert_eq!(
+        error,
+        TypeError::TypeMismatch { expected: Type::Int32, actual: Type::String, context: "error type of checked * in F".to_string() }
+    );
+}
+
+// ============================================================================
+// Runtime Tests
+// ============================================================================
+
+#[test]
+fn test_checked_overflow_returns_err() {
+    let source = format!(
+        "{}\nShow[result: Result[Int32, String]] := Match[result, [Ok[n], Print[n]], [Err[e], Print[e]]]\n\
+         Show[Scale[3, 4]]\nShow[Scale[2147483647, 2]]",
+        CHECKED_SCALE
+    );
+    let output = compile_and_run_output(&generate(&source, Arithmetic::Checked), "checked");
+    assert!(output.status.success(), "got: {}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "13\nArithmetic: * overflows i32\n");
+}
+
+#[test]
+fn test_checked_overflow_at_top_level_panics() {
+    let source = "Let[big, 2147483647]\nPrint[Map[Function[{x}, x + 1], [1, 2]]]\nPrint[big + 1]";
+    let output = compile_and_run_output(&generate(source, Arithmetic::Checked), "checked_main");
+    assert!(!output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "[2, 3]\n");
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("Arithmetic: + overflows i32"), "got: {}", stderr);
+}
+
+#[test]
+fn test_wrapping_overflow_wraps() {
+    let source = format!("{}\nPrint[Scale[2147483647, 2]]\nPrint[1.5 * 2.0]", SCALE);
+    let output = compile_and_run_output(&generate(&source, Arithmetic::Wrapping), "wrapping");
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "-1\n3\n");
+}
+
+#[test]
+fn test_cli_arithmetic_flag() {
+    let dir = std::env::temp_dir().join(format!("w_checked_arithmetic_cli_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("main.w"), "Print[6 * 7]").unwrap();
+    let run = |args: &[&str]| Command::new(env!("CARGO_BIN_EXE_w")).current_dir(&dir).args(args).output().unwrap();
+
+    let invalid = run(&["--arithmetic", "saturating", "main.w"]);
+    assert!(!invalid.status.success());
+    assert!(String::from_utf8(invalid.stderr).unwrap().contains("Invalid --arithmetic \"saturating\""));
+
+    let output = run(&["--arithmetic", "wrapping", "main.w"]);
+    let generated = std::fs::read_to_string(dir.join("generated.rs")).unwrap_or_default();
+    std::fs::write(dir.join("main.w"), "Scale[x: Int32] := x * 7\nPrint[Scale[6]]").unwrap();
+    let unchecked = run(&["--arithmetic", "checked", "main.w"]);
+    std::fs::remove_dir_all(&dir).ok();
+    assert!(!unchecked.status.success());
+    let stderr = String::from_utf8(unchecked.stderr).unwrap();
+    assert!(stderr.contains("E0031"), "got: {}", stderr);
+
+    assert!(output.status.success(), "got: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(generated.contains("__w_arithmetic::wrapping_mul(6, 7)"), "got: {}", generated);
+    assert!(generated.contains("--arithmetic wrapping"), "got: {}", generated);
+}
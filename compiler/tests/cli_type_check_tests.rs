@@ -0,0 +1,127 @@
+use w::ast::Expression;
+use w::parser::Parser;
+use w::type_inference::{TypeError, TypeInference};
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+fn program(input: &str) -> Vec<Expression> {
+    let mut parser = Parser::new(input.to_string());
+    match parser.parse().unwrap() {
+        Expression::Program(expressions) => expressions,
+        other => vec![other],
+    }
+}
+
+/// A directory holding `files`, for the `w` command to run in
+fn project(name: &str, files: &[(&str, &str)]) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("w_cli_type_check_{}_{}", name, std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    for (file, contents) in files {
+        std::fs::write(dir.join(file), contents).unwrap();
+    }
+    dir
+}
+
+fn run(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_w")).current_dir(dir).args(args).output().unwrap()
+}
+
+const SHAPE_UTILS: &str = "Double[n: Int32] := n * 2";
+
+// ============================================================================
+// Module Tests
+// ============================================================================
+
+#[test]
+fn test_module_definitions_are_in_scope() {
+    let mut inference = TypeInference::new();
+    assert_eq!(inference.check_module("ShapeUtils", &program(SHAPE_UTILS)), vec![]);
+    let errors = inference.check_program_errors(&program(
+        "Let[a, ShapeUtils.Double[3] + 1]\nLet[b, Double[4]]\nLet[c, ShapeUtils.Double[\"x\"]]\nLet[d, ShapeUtils.Triple[1]]",
+    ));
+    assert_eq!(errors.len(), 2, "got: {:?}", errors);
+    assert!(matches!(&errors[0], TypeError::TypeMismatch { context, .. } if context == "argument to Double"), "got: {:?}", errors);
+    assert_eq!(errors[1], TypeError::UndefinedIdentifier("ShapeUtils.Triple".to_string()));
+}
+
+#[test]
+fn test_unknown_module_is_undefined() {
+    let errors = TypeInference::new().check_program_errors(&program("Let[a, ShapeUtils.Double[3]]"));
+    assert_eq!(errors, vec![TypeError::UndefinedIdentifier("ShapeUtils.Double".to_string())]);
+}
+
+#[test]
+fn test_print_arguments_are_type_checked() {
+    for source in ["Print[1 + true]", "Print[Substring[1, 2, 3]]", "Print[Cond[[42, 1], [0]]]", "PrintErr[\"{}\", Undefined]"] {
+        let errors = TypeInference::new().check_program_errors(&program(source));
+        assert_eq!(errors.len(), 1, "{}: got: {:?}", source, errors);
+    }
+}
+
+// ============================================================================
+// Command-Line Tests
+// ============================================================================
+
+#[test]
+fn test_cli_reports_type_errors() {
+    let dir = project("entry", &[("main.w", "Print[1]\nLet[x, 1 + \"one\"]")]);
+    let output = run(&dir, &["main.w"]);
+    let generated = dir.join("generated.rs").exists();
+    std::fs::remove_dir_all(&dir).ok();
+    assert_eq!(output.status.code(), Some(1));
+    assert!(!generated, "nothing should be generated for an ill-typed program");
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.starts_with("error[E0001]: Type mismatch"), "got: {}", stderr);
+    assert!(stderr.contains(" --> main.w:2:1\n"), "got: {}", stderr);
+    assert!(stderr.contains("try `w --explain E0001`"), "got: {}", stderr);
+}
+
+#[test]
+fn test_cli_reports_module_type_errors() {
+    let dir = project("module", &[
+        ("main.w", "Import[\"shape_utils.w\"]\nPrint[1]"),
+        ("shape_utils.w", "Half[n: Int32] := n / \"2\""),
+    ]);
+    let output = run(&dir, &["--emit", "build-plan", "main.w"]);
+    std::fs::remove_dir_all(&dir).ok();
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.starts_with("error[E0001]: "), "got: {}", stderr);
+    assert!(stderr.contains("shape_utils.w: Type mismatch in arithmetic operation"), "got: {}", stderr);
+}
+
+#[test]
+fn test_cli_accepts_qualified_module_calls() {
+    let dir = project("qualified", &[
+        ("main.w", "Import[\"shape_utils.w\"]\nLet[six, ShapeUtils.Double[3]]\nPrint[six + 1]"),
+        ("shape_utils.w", SHAPE_UTILS),
+    ]);
+    let output = run(&dir, &["--emit", "build-plan", "main.w"]);
+    std::fs::remove_dir_all(&dir).ok();
+    assert!(output.status.success(), "got: {}", String::from_utf8_lossy(&output.stderr));
+}
+
+#[test]
+fn test_cli_reports_codegen_failure() {
+    let dir = project("codegen", &[("main.w", "Print[Let[x, 1]]")]);
+    let output = run(&dir, &["main.w"]);
+    std::fs::remove_dir_all(&dir).ok();
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.starts_with("error: main.w: The program uses something W cannot compile yet"), "got: {}", stderr);
+    assert!(!stderr.contains("panicked"), "got: {}", stderr);
+}
+
+#[test]
+fn test_cli_rejects_private_module_items() {
+    let dir = project("private", &[
+        ("main.w", "Import[\"geometry.w\"]\nPrint[Geometry.Secret[1]]"),
+        ("geometry.w", "Private[Secret[x: Int32] := x + 1]"),
+    ]);
+    let output = run(&dir, &["--emit", "build-plan", "main.w"]);
+    std::fs::remove_dir_all(&dir).ok();
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.starts_with("error[E0030]: Secret is private to module Geometry"), "got: {}", stderr);
+}
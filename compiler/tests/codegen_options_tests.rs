@@ -0,0 +1,170 @@
+mod common;
+
+use common::{parse, scratch_dir, with_binary};
+use w::rust_codegen::{CodegenOptions, CodegenStyle, Formatter, RustCodeGenerator};
+
+fn generate_with(input: &str, mut codegen: RustCodeGenerator) -> String {
+    codegen.generate(&parse(input)).unwrap()
+}
+
+/// Check that rustc accepts the generated code
+fn assert_compiles(rust_code: &str, name: &str) {
+    with_binary(rust_code, &scratch_dir(name), |_| ());
+}
+
+// ============================================================================
+// Builder / Options Tests
+// ============================================================================
+
+#[test]
+fn test_new_uses_default_options() {
+    let codegen = RustCodeGenerator::new();
+    assert_eq!(codegen.options(), &CodegenOptions::default());
+}
+
+#[test]
+fn test_builder_sets_options() {
+    let codegen = RustCodeGenerator::builder()
+        .indent_width(2)
+        .derives(["Debug"])
+        .formatter(Formatter::Rustfmt)
+        .emit_allow_attributes(true)
+        .module_header("// header")
+        .style(CodegenStyle::Statement)
+        .max_width(80)
+        .hoist_imports(true)
+        .build();
+
+    let options = codegen.options();
+    assert_eq!(options.indent_width, 2);
+    assert_eq!(options.derives, vec!["Debug".to_string()]);
+    assert_eq!(options.formatter, Formatter::Rustfmt);
+    assert!(options.emit_allow_attributes);
+    assert_eq!(options.module_header.as_deref(), Some("// header"));
+    assert_eq!(options.style, CodegenStyle::Statement);
+    assert_eq!(options.max_width, Some(80));
+    assert!(options.hoist_imports);
+}
+
+#[test]
+fn test_indent_width() {
+    let codegen = RustCodeGenerator::builder().indent_width(2).build();
+    let rust_code = generate_with("Square[x: Int32] := x * x", codegen);
+
+    assert!(rust_code.contains("fn square(x: i32) -> i32 {\n  (x * x)\n}"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_custom_derive_list() {
+    let codegen = RustCodeGenerator::builder().derives(["Debug", "Clone", "Copy"]).build();
+    let rust_code = generate_with("Struct[Point, [x: Int32, y: Int32]]", codegen);
+
+    assert!(rust_code.contains("#[derive(Debug, Clone, Copy)]"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_empty_derive_list_omits_attribute() {
+    let codegen = RustCodeGenerator::builder().derives(Vec::<String>::new()).build();
+    let rust_code = generate_with("Struct[Point, [x: Int32]]", codegen);
+
+    assert!(!rust_code.contains("#[derive"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_allow_attributes_and_header() {
+    let codegen = RustCodeGenerator::builder()
+        .module_header("// Generated by w")
+        .emit_allow_attributes(true)
+        .build();
+    let rust_code = generate_with("Print[1]", codegen);
+
+    assert!(rust_code.starts_with("// Generated by w\n\n#![allow("), "got: {}", rust_code);
+    assert!(rust_code.contains("unused_parens"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_defaults_emit_no_header() {
+    let rust_code = generate_with("Print[1]", RustCodeGenerator::new());
+    assert!(rust_code.starts_with("fn main() {"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_rustfmt_formatter_keeps_code_valid() {
+    let codegen = RustCodeGenerator::builder().formatter(Formatter::Rustfmt).build();
+    let rust_code = generate_with("Square[x: Int32] := x * x", codegen);
+
+    // Formatted when rustfmt is installed, unchanged otherwise
+    assert!(rust_code.contains("fn square(x: i32) -> i32 {"), "got: {}", rust_code);
+    assert!(rust_code.contains("fn main() {"), "got: {}", rust_code);
+}
+
+// ============================================================================
+// Import Hoisting Tests
+// ============================================================================
+
+const COLLECTIONS: &str = "Unique[items: HashSet[String]] := items
+Ages[ages: Map[String, Int32]] := ages
+Ordered[ages: BTreeMap[String, Int32]] := ages
+Let[q, Queue[1, 2]]
+Print[PopFront[q]]";
+
+#[test]
+fn test_collections_are_qualified_by_default() {
+    let rust_code = generate_with(COLLECTIONS, RustCodeGenerator::new());
+
+    assert!(rust_code.contains("items: std::collections::HashSet<String>"), "got: {}", rust_code);
+    assert!(!rust_code.contains("use std::collections"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_hoisted_imports_form_one_use() {
+    let codegen = RustCodeGenerator::builder().hoist_imports(true).build();
+    let rust_code = generate_with(COLLECTIONS, codegen);
+
+    assert!(
+        rust_code.starts_with("use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};\n\n"),
+        "got: {}",
+        rust_code
+    );
+    assert!(rust_code.contains("fn unique(items: HashSet<String>) -> HashSet<String>"), "got: {}", rust_code);
+    assert!(rust_code.contains("VecDeque::from(vec![1, 2])"), "got: {}", rust_code);
+    assert_eq!(rust_code.matches("std::collections").count(), 1, "got: {}", rust_code);
+    assert_compiles(&rust_code, "hoisted");
+}
+
+#[test]
+fn test_hoisted_imports_follow_header_and_allow_attributes() {
+    let codegen = RustCodeGenerator::builder()
+        .module_header("// header")
+        .emit_allow_attributes(true)
+        .hoist_imports(true)
+        .build();
+    let rust_code = generate_with("Unique[items: HashSet[String]] := items", codegen);
+
+    let allow = rust_code.find("#![allow(").unwrap();
+    let import = rust_code.find("use std::collections::HashSet;\n").unwrap();
+    assert!(rust_code.starts_with("// header\n"), "got: {}", rust_code);
+    assert!(allow < import, "got: {}", rust_code);
+}
+
+#[test]
+fn test_no_use_without_collections() {
+    let codegen = RustCodeGenerator::builder().hoist_imports(true).build();
+    let rust_code = generate_with("Print[1]", codegen);
+    assert!(rust_code.starts_with("fn main() {"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_each_module_imports_what_it_uses() {
+    let modules = vec![("Sets".to_string(), parse("Unique[items: HashSet[String]] := items"))];
+    let entry = parse("Ages[ages: Map[String, Int32]] := ages\nPrint[1]");
+
+    let files = RustCodeGenerator::builder().hoist_imports(true).build().generate_modules(&entry, &modules).unwrap();
+    assert!(files[0].contents.contains("use std::collections::HashMap;\n"), "got: {}", files[0].contents);
+    assert!(!files[0].contents.contains("HashSet"), "got: {}", files[0].contents);
+    assert!(files[1].contents.contains("use std::collections::HashSet;\n"), "got: {}", files[1].contents);
+
+    let rust_code = RustCodeGenerator::builder().hoist_imports(true).build().generate_single_file(&entry, &modules).unwrap();
+    assert!(rust_code.contains("mod sets {\n    use std::collections::HashSet;\n"), "got: {}", rust_code);
+    assert_compiles(&rust_code, "modules");
+}
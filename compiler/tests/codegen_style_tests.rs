@@ -0,0 +1,53 @@
+use w::parser::Parser;
+use w::rust_codegen::{CodegenStyle, RustCodeGenerator};
+
+fn generate(input: &str, style: CodegenStyle) -> String {
+    let mut parser = Parser::new(input.to_string());
+    let expr = parser.parse().unwrap();
+    let mut codegen = RustCodeGenerator::builder().style(style).build();
+    codegen.generate(&expr).unwrap()
+}
+
+// ============================================================================
+// Codegen Style Tests
+// ============================================================================
+
+#[test]
+fn test_default_style_is_expression() {
+    assert_eq!(CodegenStyle::default(), CodegenStyle::Expression);
+}
+
+#[test]
+fn test_expression_style_emits_bare_statements() {
+    let rust_code = generate("Square[x: Int32] := x * x\nSquare[4]", CodegenStyle::Expression);
+
+    assert!(rust_code.contains("    square(4);"), "got: {}", rust_code);
+    assert!(!rust_code.contains("let "), "got: {}", rust_code);
+}
+
+#[test]
+fn test_statement_style_binds_each_result() {
+    let rust_code = generate(
+        "Square[x: Int32] := x * x\nSquare[4]\nSquare[5]",
+        CodegenStyle::Statement,
+    );
+
+    assert!(rust_code.contains("let __w_0_result = square(4);"), "got: {}", rust_code);
+    assert!(rust_code.contains("let __w_1_result = square(5);"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_statement_style_leaves_print_alone() {
+    let rust_code = generate("Print[\"hi\"]\n1 + 2", CodegenStyle::Statement);
+
+    assert!(rust_code.contains("println!(\"hi\");"), "got: {}", rust_code);
+    assert!(!rust_code.contains("let __w_0_result = {"), "got: {}", rust_code);
+    assert!(rust_code.contains("let __w_0_result = (1 + 2);"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_statement_style_single_expression() {
+    let rust_code = generate("(1, 2)", CodegenStyle::Statement);
+
+    assert!(rust_code.contains("let __w_0_result = (1, 2);"), "got: {}", rust_code);
+}
@@ -0,0 +1,157 @@
+mod common;
+
+use common::compile_and_run;
+use w::codegen_utils::{is_gensym, provenance_header, source_hash, sources_hash, to_snake_case, Gensym, Provenance, GENSYM_PREFIX};
+use w::driver::{compile, NoCallbacks};
+use w::parser::Parser;
+use w::rust_codegen::{CodegenOptions, RustCodeGenerator};
+
+// ============================================================================
+// Gensym Tests
+// ============================================================================
+
+#[test]
+fn test_gensym_names_are_unique() {
+    let mut gensym = Gensym::new();
+    let first = gensym.fresh("map");
+    let second = gensym.fresh("map");
+
+    assert_ne!(first, second);
+    assert!(is_gensym(&first));
+    assert!(is_gensym(&second));
+}
+
+#[test]
+fn test_gensym_reset_restarts_numbering() {
+    let mut gensym = Gensym::new();
+    let first = gensym.fresh("tmp");
+    gensym.fresh("tmp");
+    gensym.reset();
+
+    assert_eq!(gensym.fresh("tmp"), first);
+}
+
+#[test]
+fn test_snake_case_never_produces_gensym_prefix() {
+    for name in ["Map", "map", "W", "MyValue", "x1", "HTTPServer", "_W_0_cse", "_Map"] {
+        let mangled = to_snake_case(name);
+        assert!(!mangled.starts_with(GENSYM_PREFIX), "{} mangled to {}", name, mangled);
+    }
+}
+
+// ============================================================================
+// Codegen Hygiene Tests
+// ============================================================================
+
+#[test]
+fn test_map_literal_uses_gensym_temporary() {
+    let mut parser = Parser::new(r#"Print[{"a": 1}]"#.to_string());
+    let expr = parser.parse().unwrap();
+
+    let mut codegen = RustCodeGenerator::new();
+    let rust_code = codegen.generate(&expr).unwrap();
+
+    assert!(rust_code.contains("let mut __w_0_map = std::collections::HashMap::new();"),
+        "Map literal should bind a gensym temporary, got: {}", rust_code);
+    assert!(!rust_code.contains("let mut map "),
+        "Map literal should not use a hard-coded name, got: {}", rust_code);
+}
+
+#[test]
+fn test_nested_map_literals_get_distinct_temporaries() {
+    let mut parser = Parser::new(r#"Print[[{"a": 1}, {"b": 2}]]"#.to_string());
+    let expr = parser.parse().unwrap();
+
+    let mut codegen = RustCodeGenerator::new();
+    let rust_code = codegen.generate(&expr).unwrap();
+
+    assert!(rust_code.contains("__w_0_map"), "got: {}", rust_code);
+    assert!(rust_code.contains("__w_1_map"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_user_identifier_named_map_does_not_collide() {
+    let input = r#"UseMap[map: Map[String, Int32]] := {"k": map}"#;
+    let mut parser = Parser::new(input.to_string());
+    let expr = parser.parse().unwrap();
+
+    let mut codegen = RustCodeGenerator::new();
+    let rust_code = codegen.generate(&expr).unwrap();
+
+    assert!(rust_code.contains("__w_0_map.insert(\"k\".to_string(), map);"),
+        "User `map` must stay distinct from the temporary, got: {}", rust_code);
+}
+
+#[test]
+fn test_underscore_identifier_does_not_capture_cse_temporary() {
+    // `_W_0_cse` once mangled to `__w_0_cse`, the name of the temporary the
+    // optimizer binds `Sq[x]` to, so the sum came out as 9 + 9 + 9
+    let source = "Sq[n: Int32] := n * n\nF[x: Int32, _W_0_cse: Int32] := Sq[x] + Sq[x] + _W_0_cse\nPrint[F[3, 2]]";
+    let rust_code = compile(source, &CodegenOptions::default(), &mut NoCallbacks).unwrap();
+    assert!(rust_code.contains("_w_0_cse: i32"), "got: {}", rust_code);
+    assert_eq!(compile_and_run(&rust_code, "cse"), "20\n");
+}
+
+#[test]
+fn test_gensym_output_is_stable_across_generate_calls() {
+    let mut parser = Parser::new(r#"Print[{"a": 1}]"#.to_string());
+    let expr = parser.parse().unwrap();
+
+    let mut codegen = RustCodeGenerator::new();
+    let first = codegen.generate(&expr).unwrap();
+    let second = codegen.generate(&expr).unwrap();
+
+    assert_eq!(first, second);
+}
+
+// ============================================================================
+// Provenance Header Tests
+// ============================================================================
+
+#[test]
+fn test_source_hash_matches_fnv1a_reference_values() {
+    assert_eq!(source_hash(""), 0xcbf29ce484222325);
+    assert_eq!(source_hash("a"), 0xaf63dc4c8601ec8c);
+}
+
+#[test]
+fn test_source_hash_changes_with_source() {
+    assert_ne!(source_hash("Print[1]"), source_hash("Print[2]"));
+}
+
+#[test]
+fn test_provenance_header_contents() {
+    let flags = vec!["--style statement".to_string()];
+    let header = provenance_header("0.1.0", "main.w", &["Print[1]"], &flags);
+
+    assert!(header.starts_with("// Generated by w 0.1.0\n"), "got: {}", header);
+    assert!(header.contains(&format!("// Source: main.w (fnv1a64: {:016x})", source_hash("Print[1]"))),
+        "got: {}", header);
+    assert!(header.contains("// Flags: --style statement"), "got: {}", header);
+    assert!(header.lines().all(|line| line.starts_with("//")), "got: {}", header);
+}
+
+#[test]
+fn test_provenance_header_without_flags() {
+    let header = provenance_header("0.1.0", "main.w", &[""], &[]);
+    assert!(header.contains("// Flags: (none)"), "got: {}", header);
+}
+
+#[test]
+fn test_sources_hash_separates_sources() {
+    assert_eq!(sources_hash(&["Print[1]"]), source_hash("Print[1]"));
+    assert_ne!(sources_hash(&["Print[1]", "A := 1"]), source_hash("Print[1]A := 1"));
+    assert_ne!(sources_hash(&["Print[1]", "A := 1"]), sources_hash(&["Print[1]A", " := 1"]));
+}
+
+#[test]
+fn test_provenance_header_of_each_input() {
+    let provenance = Provenance {
+        compiler_version: "0.1.0".to_string(),
+        flags: Vec::new(),
+        inputs: vec![("main.w".to_string(), "Print[1]".to_string()), ("util.w".to_string(), "A := 1".to_string())],
+    };
+    let hash = sources_hash(&["Print[1]", "A := 1"]);
+    assert!(provenance.header(0).contains(&format!("// Source: main.w (2 inputs, fnv1a64: {:016x})", hash)));
+    assert!(provenance.header(1).contains(&format!("// Source: util.w (2 inputs, fnv1a64: {:016x})", hash)));
+}
@@ -0,0 +1,132 @@
+mod common;
+
+use common::{compile_and_run, generate, parse};
+use w::ast::{Expression, Type};
+use w::type_inference::{TypeError, TypeInference};
+
+fn infer(input: &str) -> Result<Type, TypeError> {
+    TypeInference::new().infer_expression(&parse(input))
+}
+
+fn check(input: &str) -> Result<(), TypeError> {
+    let expressions = match parse(input) {
+        Expression::Program(expressions) => expressions,
+        other => vec![other],
+    };
+    TypeInference::new().check_program(&expressions)
+}
+
+// ============================================================================
+// Type Tests
+// ============================================================================
+
+#[test]
+fn test_parse_stack_and_queue_types() {
+    match parse("F[s: Stack[Int32], q: Queue[String]] := s") {
+        Expression::FunctionDefinition { parameters, .. } => {
+            assert_eq!(parameters[0].type_, Type::List(Box::new(Type::Int32)));
+            assert_eq!(parameters[1].type_, Type::Queue(Box::new(Type::String)));
+        }
+        other => panic!("Expected FunctionDefinition, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_constructor_types() {
+    assert_eq!(infer("Stack[1, 2]"), Ok(Type::List(Box::new(Type::Int32))));
+    assert_eq!(infer("Queue[\"a\"]"), Ok(Type::Queue(Box::new(Type::String))));
+    assert_eq!(infer("Queue[]"), Ok(Type::Queue(Box::new(Type::Unknown))));
+}
+
+#[test]
+fn test_constructor_elements_must_agree() {
+    let result = infer("Stack[1, \"two\"]");
+    assert!(matches!(result, Err(TypeError::TypeMismatch { .. })), "got: {:?}", result);
+}
+
+#[test]
+fn test_pops_are_option_typed() {
+    assert_eq!(infer("Pop[Stack[1]]"), Ok(Type::Option(Box::new(Type::Int32))));
+    assert_eq!(infer("PopFront[Queue[1.5]]"), Ok(Type::Option(Box::new(Type::Float64))));
+}
+
+#[test]
+fn test_pop_kind_must_match_collection() {
+    let result = infer("PopFront[Stack[1]]");
+    assert!(matches!(result, Err(TypeError::TypeMismatch { .. })), "got: {:?}", result);
+
+    let result = infer("Pop[Queue[1]]");
+    assert!(matches!(result, Err(TypeError::TypeMismatch { .. })), "got: {:?}", result);
+}
+
+#[test]
+fn test_push_checks_element_type() {
+    assert!(check("Let[s, Stack[1]]\nPush[s, 2]").is_ok());
+    assert!(check("Let[q, Queue[]]\nPush[q, \"a\"]").is_ok());
+
+    let result = check("Let[s, Stack[1]]\nPush[s, \"two\"]");
+    assert!(matches!(result, Err(TypeError::TypeMismatch { .. })), "got: {:?}", result);
+
+    let result = check("Push[5, 1]");
+    assert!(matches!(result, Err(TypeError::TypeMismatch { .. })), "got: {:?}", result);
+}
+
+#[test]
+fn test_empty_literal_of_unfixed_type_is_rejected() {
+    for (input, shown) in [("Print[[]]", "[] (List[_])"), ("Print[{}]", "{} (Map[_, _])")] {
+        match check(input) {
+            Err(TypeError::CannotInfer(message)) => assert!(message.starts_with(shown), "got: {}", message),
+            other => panic!("Expected CannotInfer for {}, got {:?}", input, other),
+        }
+    }
+}
+
+// ============================================================================
+// Codegen Tests
+// ============================================================================
+
+#[test]
+fn test_codegen_constructors() {
+    let rust_code = generate("Print[Stack[]]\nPrint[Stack[1, 2]]\nPrint[Queue[]]\nPrint[Queue[1]]");
+    assert!(rust_code.contains("println!(\"{:?}\", Vec::new());"), "got: {}", rust_code);
+    assert!(rust_code.contains("vec![1, 2]"), "got: {}", rust_code);
+    assert!(rust_code.contains("std::collections::VecDeque::new()"), "got: {}", rust_code);
+    assert!(rust_code.contains("std::collections::VecDeque::from(vec![1])"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_codegen_mutated_bindings_are_mut() {
+    let rust_code = generate("Let[s, Stack[1]]\nLet[t, Stack[2]]\nPush[s, 3]\nPrint[Pop[s]]");
+    assert!(rust_code.contains("let mut s = vec![1];"), "got: {}", rust_code);
+    assert!(rust_code.contains("let t = vec![2];"), "got: {}", rust_code);
+    assert!(rust_code.contains("s.extend([3]);"), "got: {}", rust_code);
+    assert!(rust_code.contains("println!(\"{:?}\", s.pop());"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_codegen_mutated_parameter_is_mut() {
+    let rust_code = generate("Next[q: Queue[Int32]] := PopFront[q]");
+    assert!(rust_code.contains("fn next(mut q: std::collections::VecDeque<i32>)"), "got: {}", rust_code);
+    assert!(rust_code.contains("q.pop_front()"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_stack_and_queue_order() {
+    let rust_code = generate(
+        "Drain[s: Stack[Int32]] := WhileLet[Some[x], Pop[s], Print[x]]\n\
+         Let[s, Stack[1, 2]]\nPush[s, 3]\nDrain[s]\n\
+         Let[q, Queue[]]\nPush[q, 10]\nPush[q, 20]\nPrint[PopFront[q]]\nPrint[PopFront[q]]\nPrint[PopFront[q]]",
+    );
+    assert_eq!(compile_and_run(&rust_code, "order"), "3\n2\n1\nSome(10)\nSome(20)\nNone\n");
+}
+
+#[test]
+fn test_empty_literals_name_their_type() {
+    let rust_code = generate(
+        "Inc[x: Int32] := x + 1\nSize[m: Map[String, Int32]] := m\n\
+         Print[Map[Inc, []]]\nPrint[Size[{}]]",
+    );
+    assert!(rust_code.contains("Vec::<i32>::new().into_iter()"), "got: {}", rust_code);
+    assert!(rust_code.contains(": std::collections::HashMap<String, i32> = std::collections::HashMap::new();"), "got: {}", rust_code);
+    assert_eq!(compile_and_run(&rust_code, "empty_literals"), "[]\n{}\n");
+}
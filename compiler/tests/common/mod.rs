@@ -0,0 +1,107 @@
+//! Helpers shared by the integration tests
+//!
+//! Each file in tests/ is its own crate and declares `mod common;`; most use
+//! only a few of these.
+#![allow(dead_code)]
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+
+use w::ast::Expression;
+use w::parser::Parser;
+use w::rust_codegen::RustCodeGenerator;
+
+pub fn parse(input: &str) -> Expression {
+    let mut parser = Parser::new(input.to_string());
+    parser.parse().unwrap()
+}
+
+/// The top-level expressions of `input`
+pub fn program(input: &str) -> Vec<Expression> {
+    match parse(input) {
+        Expression::Program(expressions) => expressions,
+        other => vec![other],
+    }
+}
+
+pub fn generate(input: &str) -> String {
+    try_generate(input).unwrap()
+}
+
+/// Like `generate`, for tests that expect the generator to fail
+pub fn try_generate(input: &str) -> Result<String, std::fmt::Error> {
+    let mut codegen = RustCodeGenerator::new();
+    codegen.generate(&parse(input))
+}
+
+/// A directory for the test `name` to build in, separate for each test file
+/// and process
+pub fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("w_{}_{}_{}", env!("CARGO_CRATE_NAME"), name, std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Compile generated code with rustc inside `dir`, hand the binary to `run`,
+/// then remove `dir`
+pub fn with_binary<T>(rust_code: &str, dir: &Path, run: impl FnOnce(&Path) -> T) -> T {
+    let source = dir.join("main.rs");
+    let binary = dir.join("main");
+    std::fs::write(&source, rust_code).unwrap();
+
+    let status = Command::new("rustc")
+        .arg(&source)
+        .arg("-o")
+        .arg(&binary)
+        .arg("-A")
+        .arg("warnings")
+        .status()
+        .unwrap();
+    if !status.success() {
+        std::fs::remove_dir_all(dir).ok();
+        panic!("rustc rejected generated code: {}", rust_code);
+    }
+
+    let result = run(&binary);
+    std::fs::remove_dir_all(dir).ok();
+    result
+}
+
+/// Compile generated code with rustc and return the finished process
+pub fn compile_and_run_output(rust_code: &str, name: &str) -> Output {
+    with_binary(rust_code, &scratch_dir(name), |binary| Command::new(binary).output().unwrap())
+}
+
+/// Compile generated code with rustc and return the program's stdout
+pub fn compile_and_run(rust_code: &str, name: &str) -> String {
+    String::from_utf8(compile_and_run_output(rust_code, name).stdout).unwrap()
+}
+
+/// Like `compile_and_run`, feeding `stdin` to the program
+pub fn compile_and_run_with_stdin(rust_code: &str, name: &str, stdin: &str) -> String {
+    let output = with_binary(rust_code, &scratch_dir(name), |binary| {
+        let mut child = Command::new(binary).stdin(Stdio::piped()).stdout(Stdio::piped()).spawn().unwrap();
+        child.stdin.take().unwrap().write_all(stdin.as_bytes()).unwrap();
+        child.wait_with_output().unwrap()
+    });
+    String::from_utf8(output.stdout).unwrap()
+}
+
+/// Compile generated code as a library with rustc
+pub fn compile_library(rust_code: &str, name: &str) -> bool {
+    let dir = scratch_dir(name);
+    let source = dir.join("lib.rs");
+    std::fs::write(&source, rust_code).unwrap();
+
+    let status = Command::new("rustc")
+        .arg(&source)
+        .args(["--crate-type", "lib", "--edition", "2021", "--out-dir"])
+        .arg(&dir)
+        .arg("-A")
+        .arg("warnings")
+        .status()
+        .unwrap();
+    std::fs::remove_dir_all(&dir).ok();
+    status.success()
+}
@@ -0,0 +1,106 @@
+mod common;
+
+use common::{compile_and_run, generate, parse};
+use w::ast::{Expression, Operator, Type};
+use w::lexer::{Lexer, Token};
+use w::rust_codegen::RustCodeGenerator;
+use w::type_inference::{TypeError, TypeInference};
+
+fn infer(input: &str) -> Result<Type, TypeError> {
+    TypeInference::new().infer_expression(&parse(input))
+}
+
+fn tokens(input: &str) -> Vec<Token> {
+    let mut lexer = Lexer::new(input.to_string());
+    let mut tokens = Vec::new();
+    while let Some(token) = lexer.next_token().unwrap() {
+        tokens.push(token);
+    }
+    tokens
+}
+
+// ============================================================================
+// Less-or-Equal / Greater-or-Equal Tests
+// ============================================================================
+
+#[test]
+fn test_lex_inclusive_comparisons() {
+    assert_eq!(tokens("<= >= < > << >>"), vec![
+        Token::LessEqual, Token::GreaterEqual, Token::LessThan, Token::GreaterThan,
+        Token::ShiftLeft, Token::ShiftRight,
+    ]);
+}
+
+#[test]
+fn test_parse_inclusive_comparison_below_arithmetic() {
+    match parse("x + 1 >= 10") {
+        Expression::BinaryOp { operator: Operator::GreaterEqual, left, .. } => {
+            assert!(matches!(*left, Expression::BinaryOp { operator: Operator::Add, .. }), "got: {:?}", left);
+        }
+        other => panic!("Expected >= at the root, got: {:?}", other),
+    }
+}
+
+#[test]
+fn test_generate_inclusive_comparisons() {
+    let rust_code = generate("InRange[x: Int32] := x >= 0 && x <= 9");
+    assert!(rust_code.contains("((x >= 0) && (x <= 9))"), "got: {}", rust_code);
+    assert!(rust_code.contains("fn in_range(x: i32) -> bool"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_infer_inclusive_comparisons() {
+    assert_eq!(infer("1 <= 2").unwrap(), Type::Bool);
+    assert_eq!(infer("2.5 >= 1.0").unwrap(), Type::Bool);
+    assert!(matches!(infer("1 <= 2.0"), Err(TypeError::TypeMismatch { .. })));
+}
+
+// ============================================================================
+// Ord-Based Comparison Tests
+// ============================================================================
+
+#[test]
+fn test_strings_and_tuples_are_ordered() {
+    assert_eq!(infer("\"apple\" < \"banana\"").unwrap(), Type::Bool);
+    assert_eq!(infer("(1, \"a\") >= (1, \"b\")").unwrap(), Type::Bool);
+    assert_eq!(infer("[1, 2] < [1, 3]").unwrap(), Type::Bool);
+}
+
+#[test]
+fn test_generated_string_and_tuple_comparisons_run() {
+    let rust_code = generate("Print[\"apple\" < \"banana\"]\nPrint[(2, \"a\") <= (1, \"z\")]");
+    assert_eq!(compile_and_run(&rust_code, "ord"), "true\nfalse\n");
+}
+
+#[test]
+fn test_ordering_structs_is_rejected() {
+    let expressions = match parse("Struct[Point, [x: Int32]]\nLess[a: Point, b: Point] := a < b") {
+        Expression::Program(expressions) => expressions,
+        other => vec![other],
+    };
+    let err = TypeInference::new().check_program(&expressions).unwrap_err();
+    assert!(matches!(err, TypeError::NotOrdered { .. }), "got: {:?}", err);
+    assert!(err.to_string().contains("compare a field instead"), "got: {}", err);
+}
+
+#[test]
+fn test_ordering_maps_is_rejected() {
+    let expressions = match parse("Less[a: Map[String, Int32], b: Map[String, Int32]] := a < b") {
+        Expression::Program(expressions) => expressions,
+        other => vec![other],
+    };
+    let err = TypeInference::new().check_program(&expressions).unwrap_err();
+    assert!(err.to_string().contains("hash collections have no ordering"), "got: {}", err);
+
+    let mut codegen = RustCodeGenerator::new();
+    assert!(codegen.generate(&parse("Print[{\"a\": 1} < {\"b\": 2}]")).is_err());
+}
+
+#[test]
+fn test_equality_on_maps_is_still_allowed() {
+    let expressions = match parse("Same[a: Map[String, Int32], b: Map[String, Int32]] := a == b") {
+        Expression::Program(expressions) => expressions,
+        other => vec![other],
+    };
+    assert!(TypeInference::new().check_program(&expressions).is_ok());
+}
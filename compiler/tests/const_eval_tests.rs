@@ -0,0 +1,135 @@
+use w::ast::{Expression, Type};
+use w::const_eval::{check_constants, ConstError, ConstErrorKind};
+use w::driver::{compile, CompileError, NoCallbacks};
+use w::parser::Parser;
+use w::rust_codegen::CodegenOptions;
+
+use std::process::Command;
+
+/// Constant evaluation errors in `input`, located by the parser's spans
+fn errors(input: &str) -> Vec<ConstError> {
+    let mut parser = Parser::new(input.to_string());
+    let program: Expression = parser.parse().unwrap();
+    check_constants(&program, parser.expression_spans())
+}
+
+fn kinds(input: &str) -> Vec<ConstErrorKind> {
+    errors(input).into_iter().map(|error| error.kind).collect()
+}
+
+fn overflow(left: i128, operator: &'static str, right: i128) -> ConstErrorKind {
+    ConstErrorKind::Overflow { left, operator, right, type_: Type::Int32 }
+}
+
+// ============================================================================
+// Division Tests
+// ============================================================================
+
+#[test]
+fn test_division_by_literal_zero() {
+    assert_eq!(kinds("Print[10 / 0]"), vec![ConstErrorKind::DivisionByZero]);
+}
+
+#[test]
+fn test_divisor_arithmetic_is_zero() {
+    let errors = errors("Print[1]\nHalf[n: Int32] := n / (3 - 3)");
+    assert_eq!(errors.len(), 1, "got: {:?}", errors);
+    assert_eq!(errors[0].code(), "E0202");
+    assert_eq!(errors[0].function.as_deref(), Some("Half"));
+    assert_eq!(errors[0].span.map(|span| (span.line, span.column)), Some((2, 1)));
+    assert_eq!(errors[0].to_string(), "Division by zero in Half: the divisor is always 0");
+}
+
+#[test]
+fn test_nonzero_divisor_is_accepted() {
+    assert_eq!(kinds("Half[n: Int32] := n / (3 - 1)\nPrint[Half[10] / 5]"), vec![]);
+}
+
+// ============================================================================
+// Overflow Tests
+// ============================================================================
+
+#[test]
+fn test_int32_overflow() {
+    assert_eq!(kinds("Print[2147483647 + 1]"), vec![overflow(2147483647, "+", 1)]);
+    assert_eq!(kinds("Let[big, 65536 * 65536]"), vec![overflow(65536, "*", 65536)]);
+    assert_eq!(kinds("Print[0 - 2147483647 - 2]"), vec![overflow(-2147483647, "-", 2)]);
+}
+
+#[test]
+fn test_power_and_shift_overflow() {
+    assert_eq!(kinds("Print[2 ^ 31]"), vec![overflow(2, "^", 31)]);
+    assert_eq!(kinds("Print[1 << 40]"), vec![overflow(1, "<<", 40)]);
+    assert_eq!(kinds("Print[2 ^ 30]\nPrint[1 << 31]"), vec![]);
+}
+
+#[test]
+fn test_overflow_message() {
+    let errors = errors("Limit[] := 2147483647 * 2");
+    assert_eq!(errors[0].code(), "E0203");
+    assert_eq!(errors[0].to_string(), "2147483647 * 2 in Limit overflows Int32, so it always panics");
+}
+
+#[test]
+fn test_wider_context_is_not_int32() {
+    assert_eq!(kinds("Widen[n: Int64] := n\nPrint[Widen[2147483647 + 1]]"), vec![]);
+    assert_eq!(kinds("Add[n: Int64] := n + 2147483647 * 2"), vec![]);
+}
+
+// ============================================================================
+// Array Size Tests
+// ============================================================================
+
+#[test]
+fn test_array_too_large() {
+    let errors = errors("Grid[cells: Array[Array[Int64, 2000000000], 2000000000]] := 0");
+    assert_eq!(errors.len(), 1, "got: {:?}", errors);
+    assert_eq!(errors[0].code(), "E0204");
+    assert!(matches!(errors[0].kind, ConstErrorKind::ArrayTooLarge { bytes: 32000000000000000000, .. }), "got: {:?}", errors);
+}
+
+#[test]
+fn test_array_size_limit_is_what_rustc_accepts() {
+    // 2^61 - 1 bytes still compile; 2.4e18 bytes is below i64::MAX but not
+    // something rustc accepts
+    assert_eq!(kinds("Buffer[bytes: Array[Int8, 2305843009213693951]] := 0"), vec![]);
+    let errors = errors("Buffer[words: Array[Int64, 300000000000000000]] := 0");
+    assert_eq!(errors.len(), 1, "got: {:?}", errors);
+    assert!(matches!(errors[0].kind, ConstErrorKind::ArrayTooLarge { bytes: 2400000000000000000, .. }), "got: {:?}", errors);
+}
+
+#[test]
+fn test_array_sizes_in_type_definitions() {
+    assert_eq!(kinds("Struct[Image, [pixels: Array[Int32, 1000]]]"), vec![]);
+    assert_eq!(kinds("Struct[Image, [pixels: Array[Array[Int128, 2000000000], 2000000000]]]").len(), 1);
+    assert_eq!(kinds("Enum[Frame, [Empty, Full[Array[Array[Int64, 2000000000], 2000000000]]]]").len(), 1);
+}
+
+// ============================================================================
+// Pipeline Tests
+// ============================================================================
+
+#[test]
+fn test_driver_fails_before_codegen() {
+    let result = compile("Print[1]\nPrint[10 / (2 - 2)]", &CodegenOptions::default(), &mut NoCallbacks);
+    match result {
+        Err(error @ CompileError::Const(_)) => {
+            assert_eq!(error.code(), Some("E0202"));
+            assert_eq!(error.to_string(), "error[E0202]: 2:1: Division by zero: the divisor is always 0");
+        }
+        other => panic!("Expected CompileError::Const, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_cli_reports_constant_errors() {
+    let dir = std::env::temp_dir().join(format!("w_const_eval_cli_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("main.w"), "Print[2147483647 + 1]").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_w")).current_dir(&dir).args(["--emit", "build-plan", "main.w"]).output().unwrap();
+    std::fs::remove_dir_all(&dir).ok();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.starts_with("error[E0203]: main.w:1:1: 2147483647 + 1 overflows Int32, so it always panics"), "got: {}", stderr);
+}
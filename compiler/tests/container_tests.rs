@@ -29,7 +29,7 @@ fn test_parse_list_with_numbers() {
             assert_eq!(elements.len(), 3);
             for (i, elem) in elements.iter().enumerate() {
                 match elem {
-                    Expression::Number(n) => assert_eq!(*n, (i + 1) as i32),
+                    Expression::Number(n, _) => assert_eq!(*n, (i + 1) as i64),
                     _ => panic!("Expected number in list"),
                 }
             }
@@ -46,7 +46,7 @@ fn test_parse_list_with_strings() {
     match expr {
         Expression::List(elements) => {
             assert_eq!(elements.len(), 3);
-            let expected = vec!["a", "b", "c"];
+            let expected = ["a", "b", "c"];
             for (i, elem) in elements.iter().enumerate() {
                 match elem {
                     Expression::String(s) => assert_eq!(s, expected[i]),
@@ -126,7 +126,7 @@ fn test_parse_map_with_number_values() {
         Expression::Map(entries) => {
             assert_eq!(entries.len(), 2);
             match &entries[0] {
-                (Expression::String(k), Expression::Number(v)) => {
+                (Expression::String(k), Expression::Number(v, _)) => {
                     assert_eq!(k, "age");
                     assert_eq!(*v, 30);
                 }
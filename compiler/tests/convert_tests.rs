@@ -0,0 +1,137 @@
+mod common;
+
+use common::{compile_and_run_output, generate, parse};
+use w::ast::{Expression, Type};
+use w::stdlib::convert::convert;
+use w::type_inference::{TypeError, TypeInference};
+
+fn infer(input: &str) -> Result<Type, TypeError> {
+    TypeInference::new().infer_expression(&parse(input))
+}
+
+// ============================================================================
+// Parser Tests
+// ============================================================================
+
+#[test]
+fn test_parse_convert() {
+    assert_eq!(
+        parse("Convert[x, Float64]"),
+        Expression::Convert { value: Box::new(Expression::Identifier("x".to_string())), target: Type::Float64 }
+    );
+}
+
+#[test]
+fn test_convert_is_an_operand() {
+    match parse("Convert[x, Float64] / 2.0") {
+        Expression::BinaryOp { left, .. } => assert!(matches!(*left, Expression::Convert { .. }), "got: {:?}", left),
+        other => panic!("expected BinaryOp, got: {:?}", other),
+    }
+}
+
+// ============================================================================
+// Type Inference Tests
+// ============================================================================
+
+#[test]
+fn test_convert_has_target_type() {
+    assert_eq!(infer("Convert[7, Float64]"), Ok(Type::Float64));
+    assert_eq!(infer("Convert[2.5, UInt8]"), Ok(Type::UInt8));
+}
+
+#[test]
+fn test_convert_allows_mixed_arithmetic() {
+    assert_eq!(infer("Convert[7, Float64] / 2.0"), Ok(Type::Float64));
+    assert!(infer("7 / 2.0").is_err());
+}
+
+#[test]
+fn test_convert_rejects_non_numeric_types() {
+    assert_eq!(
+        infer("Convert[\"42\", Int32]"),
+        Err(TypeError::InvalidConversion { from: Type::String, to: Type::Int32 })
+    );
+    assert_eq!(
+        infer("Convert[1, Bool]"),
+        Err(TypeError::InvalidConversion { from: Type::Int32, to: Type::Bool })
+    );
+}
+
+#[test]
+fn test_invalid_conversion_message() {
+    let error = infer("Convert[\"42\", Int32]").unwrap_err();
+    assert_eq!(error.code(), "E0021");
+    assert_eq!(error.to_string(), "Cannot Convert String to Int32: only numeric types convert to each other");
+}
+
+// ============================================================================
+// Codegen Tests
+// ============================================================================
+
+#[test]
+fn test_float_target_is_a_cast() {
+    let rust_code = generate("Let[x, 7]\nPrint[Convert[x, Float64]]");
+    assert!(rust_code.contains("(x as f64)"), "got: {}", rust_code);
+    assert!(!rust_code.contains("mod __w_convert"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_integer_target_is_checked() {
+    let rust_code = generate("Let[x, 7]\nPrint[Convert[x, UInt8]]");
+    assert!(rust_code.contains("__w_convert::convert::<u8, _>(x)"), "got: {}", rust_code);
+    assert_eq!(rust_code.matches("mod __w_convert").count(), 1, "got: {}", rust_code);
+}
+
+#[test]
+fn test_function_returns_target_type() {
+    let rust_code = generate("Half[n: Int32] := Convert[n, Float64] / 2.0");
+    assert!(rust_code.contains("fn half(n: i32) -> f64"), "got: {}", rust_code);
+}
+
+// ============================================================================
+// Runtime Tests
+// ============================================================================
+
+#[test]
+fn test_conversion_helper() {
+    assert_eq!(convert::<u8, _>(255i32), 255);
+    assert_eq!(convert::<i32, _>(-2.9f64), -2);
+    assert_eq!(convert::<i64, _>(2.5f32), 2);
+    assert_eq!(convert::<u64, _>(u32::MAX), u32::MAX as u64);
+}
+
+#[test]
+#[should_panic(expected = "Convert: 256 is out of range for u8")]
+fn test_conversion_helper_checks_integer_range() {
+    convert::<u8, _>(256i32);
+}
+
+#[test]
+#[should_panic(expected = "out of range for i32")]
+fn test_conversion_helper_rejects_nan() {
+    convert::<i32, _>(f64::NAN);
+}
+
+#[test]
+#[should_panic(expected = "out of range for i64")]
+fn test_conversion_helper_checks_float_range() {
+    convert::<i64, _>(9.3e18f64);
+}
+
+#[test]
+fn test_mixed_arithmetic_runs() {
+    let rust_code = generate("Let[x, 7]\nPrint[Convert[x, Float64] / 2.0]\nPrint[Convert[2.9, Int64] + Convert[x, Int64]]");
+    let output = compile_and_run_output(&rust_code, "mixed");
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "3.5\n9\n");
+}
+
+#[test]
+fn test_narrowing_out_of_range_panics() {
+    let rust_code = generate("Print[Convert[300, UInt8]]");
+    let output = compile_and_run_output(&rust_code, "narrowing");
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8(output.stderr).unwrap().contains("Convert: 300 is out of range for u8"),
+        "program did not panic with the range message"
+    );
+}
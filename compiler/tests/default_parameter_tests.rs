@@ -0,0 +1,107 @@
+mod common;
+
+use common::{compile_and_run, generate, parse};
+use w::ast::{Expression, Type};
+use w::parser::Parser;
+use w::type_inference::{TypeError, TypeInference};
+
+const GREET: &str = "Greet[name: String, greeting: String = \"Hello\"] := greeting + \", \" + name\n";
+
+fn check(input: &str) -> Result<(), TypeError> {
+    let expressions = match parse(input) {
+        Expression::Program(expressions) => expressions,
+        other => vec![other],
+    };
+    TypeInference::new().check_program(&expressions)
+}
+
+// ============================================================================
+// Parser Tests
+// ============================================================================
+
+#[test]
+fn test_parse_default() {
+    match parse(GREET) {
+        Expression::FunctionDefinition { parameters, .. } => {
+            assert_eq!(parameters[0].default, None);
+            assert_eq!(parameters[1].default.as_deref(), Some(&Expression::String("Hello".to_string())));
+        }
+        other => panic!("expected FunctionDefinition, got: {:?}", other),
+    }
+}
+
+#[test]
+fn test_default_needs_value() {
+    let mut parser = Parser::new("F[x: Int32 = ] := x".to_string());
+    assert_eq!(parser.parse(), None);
+}
+
+// ============================================================================
+// Type Inference Tests
+// ============================================================================
+
+#[test]
+fn test_trailing_arguments_may_be_left_out() {
+    assert_eq!(check(&format!("{}Let[a, Greet[\"W\"]]\nLet[b, Greet[\"W\", \"Hi\"]]", GREET)), Ok(()));
+}
+
+#[test]
+fn test_required_arguments_are_still_required() {
+    assert_eq!(
+        check(&format!("{}Let[a, Greet[]]", GREET)),
+        Err(TypeError::ArityMismatch { function: "Greet".to_string(), expected: 2, actual: 0 })
+    );
+    assert!(matches!(check(&format!("{}Let[a, Greet[\"a\", \"b\", \"c\"]]", GREET)), Err(TypeError::ArityMismatch { .. })));
+}
+
+#[test]
+fn test_default_must_have_parameter_type() {
+    match check("F[x: Int32, label: String = 1] := x") {
+        Err(TypeError::TypeMismatch { expected, actual, context }) => {
+            assert_eq!((expected, actual), (Type::String, Type::Int32));
+            assert_eq!(context, "default value of label in F");
+        }
+        other => panic!("expected TypeMismatch, got: {:?}", other),
+    }
+}
+
+#[test]
+fn test_default_cannot_use_parameters() {
+    assert_eq!(check("F[x: Int32, y: Int32 = x] := x + y"), Err(TypeError::UndefinedIdentifier("x".to_string())));
+}
+
+#[test]
+fn test_defaults_come_last() {
+    let error = check("Greet[greeting: String = \"Hello\", name: String] := greeting + name").unwrap_err();
+    assert_eq!(error, TypeError::DefaultBeforeRequired { function: "Greet".to_string(), parameter: "name".to_string() });
+    assert_eq!(error.code(), "E0024");
+    assert_eq!(
+        error.to_string(),
+        "Parameter name of Greet has no default value but follows one that does; parameters with defaults must come last"
+    );
+}
+
+// ============================================================================
+// Codegen Tests
+// ============================================================================
+
+#[test]
+fn test_call_site_fills_defaults() {
+    let rust_code = generate(&format!("{}Print[Greet[\"W\"]]", GREET));
+    assert!(rust_code.contains("fn greet(name: String, greeting: String) -> String"), "got: {}", rust_code);
+    assert!(rust_code.contains("greet(\"W\".to_string(), \"Hello\".to_string())"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_defaults_run() {
+    let input = format!(
+        "{}Scale[x: Int32, factor: Int32 = 2, offset: Int32 = 0] := x * factor + offset\n\
+         Print[Greet[\"W\"]]\n\
+         Print[Greet[\"W\", \"Hi\"]]\n\
+         Print[Scale[5]]\n\
+         Print[Scale[5, 3]]\n\
+         Print[Scale[5, 3, 1]]",
+        GREET
+    );
+    assert_eq!(compile_and_run(&generate(&input), "run"), "Hello, W\nHi, W\n10\n15\n16\n");
+}
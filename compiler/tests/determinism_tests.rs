@@ -0,0 +1,69 @@
+use w::driver::{compile as compile_program, NoCallbacks};
+use w::parser::Parser;
+use w::rust_codegen::CodegenOptions;
+use w::type_inference::TypeInference;
+
+const PROGRAM: &str = r#"
+Struct[Point, [x: Int32, y: Int32]]
+Struct[Person, [name: String, age: Int32]]
+Struct[Circle, [radius: Int32]]
+Sq[x: Int32] := x * x
+Dist[p: Point] := Sq[p.x] + Sq[p.y]
+Older[p: Person] := p.age + 1
+Area[c: Circle] := c.radius * c.radius * 3
+Print[Point[1, 2], Person["w", 3]]
+Print[Dist[Point[3, 4]], Older[Person["w", 3]], Area[Circle[5]]]
+Print[{"a": 1, "b": 2, "c": 3}]
+"#;
+
+/// Run the whole pipeline from scratch, type checking included, as a
+/// separate compiler invocation would
+fn compile(input: &str) -> String {
+    compile_program(input, &CodegenOptions::default(), &mut NoCallbacks).unwrap()
+}
+
+#[test]
+fn test_compiling_twice_produces_identical_output() {
+    let first = compile(PROGRAM);
+    let second = compile(PROGRAM);
+
+    assert!(first.contains("fn dist(p: Point) -> i32"), "got: {}", first);
+    assert_eq!(first, second, "Generated code must be reproducible");
+}
+
+#[test]
+fn test_repeated_compilations_are_identical() {
+    let reference = compile(PROGRAM);
+    for _ in 0..10 {
+        assert_eq!(compile(PROGRAM), reference);
+    }
+}
+
+#[test]
+fn test_type_errors_are_reproducible() {
+    let input = "F[x: Int32] := x\nF[\"a\"]\nG[y: Bool] := y + 1";
+    let check = || {
+        let mut parser = Parser::new(input.to_string());
+        let expressions = match parser.parse().unwrap() {
+            w::ast::Expression::Program(expressions) => expressions,
+            other => vec![other],
+        };
+        TypeInference::new().check_program(&expressions).unwrap_err().to_string()
+    };
+
+    assert_eq!(check(), check());
+}
+
+#[test]
+fn test_environment_iterates_in_name_order() {
+    let mut parser = Parser::new("Zeta[x: Int32] := x\nAlpha[x: Int32] := x\nMid[x: Int32] := x".to_string());
+    let expressions = match parser.parse().unwrap() {
+        w::ast::Expression::Program(expressions) => expressions,
+        other => vec![other],
+    };
+    let mut inference = TypeInference::new();
+    inference.check_program(&expressions).unwrap();
+
+    let names: Vec<&String> = inference.env().bindings().map(|(name, _)| name).collect();
+    assert_eq!(names, ["Alpha", "Mid", "Zeta"]);
+}
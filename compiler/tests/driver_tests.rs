@@ -0,0 +1,235 @@
+use w::backend::{BackendOptions, RustBackend};
+use w::codegen_utils::Provenance;
+use w::driver::{compile, compile_file, CodegenStats, CompiledProgram, CompileError, CompilerCallbacks, NoCallbacks, ParseStats, TypecheckStats};
+use w::parser::PARSE_ERROR_CODE;
+use w::rust_codegen::CodegenOptions;
+use w::type_inference::{LocatedTypeError, TypeError};
+
+use std::path::PathBuf;
+
+/// Records which phases finished, in order, with their stats
+#[derive(Default)]
+struct Recorder {
+    phases: Vec<&'static str>,
+    parse: Option<ParseStats>,
+    typecheck: Option<TypecheckStats>,
+    codegen: Option<CodegenStats>,
+}
+
+impl CompilerCallbacks for Recorder {
+    fn on_parse_complete(&mut self, stats: &ParseStats) {
+        self.phases.push("parse");
+        self.parse = Some(stats.clone());
+    }
+
+    fn on_typecheck_complete(&mut self, stats: &TypecheckStats) {
+        self.phases.push("typecheck");
+        self.typecheck = Some(stats.clone());
+    }
+
+    fn on_codegen_complete(&mut self, stats: &CodegenStats) {
+        self.phases.push("codegen");
+        self.codegen = Some(stats.clone());
+    }
+}
+
+fn compile_recorded(source: &str) -> (Result<String, CompileError>, Recorder) {
+    let mut recorder = Recorder::default();
+    let result = compile(source, &CodegenOptions::default(), &mut recorder);
+    (result, recorder)
+}
+
+// ============================================================================
+// Callback Tests
+// ============================================================================
+
+#[test]
+fn test_phases_reported_in_order() {
+    let (result, recorder) = compile_recorded("Print[1 + 2]");
+    assert!(result.is_ok(), "got: {:?}", result);
+    assert_eq!(recorder.phases, vec!["parse", "typecheck", "codegen"]);
+}
+
+#[test]
+fn test_parse_stats() {
+    let source = "Double[x: Int32] := x * 2\nPrint[Double[21]]";
+    let (_, recorder) = compile_recorded(source);
+    let stats = recorder.parse.unwrap();
+    assert_eq!(stats.source_bytes, source.len());
+    assert_eq!(stats.expressions, 2);
+}
+
+#[test]
+fn test_typecheck_stats() {
+    let (_, recorder) = compile_recorded("Let[x, 1]\nPrint[x]");
+    assert_eq!(recorder.typecheck.unwrap().expressions, 2);
+}
+
+#[test]
+fn test_codegen_stats_describe_output() {
+    let (result, recorder) = compile_recorded("Print[\"hi\"]");
+    let rust_code = result.unwrap();
+    let stats = recorder.codegen.unwrap();
+    assert_eq!(stats.bytes, rust_code.len());
+    assert_eq!(stats.lines, rust_code.lines().count());
+}
+
+#[test]
+fn test_failed_phase_reports_nothing() {
+    let (result, recorder) = compile_recorded("missing + 1");
+    match &result {
+        Err(CompileError::Type(errors)) => {
+            assert!(matches!(errors[..], [LocatedTypeError { error: TypeError::UndefinedIdentifier(_), .. }]), "got: {:?}", errors)
+        }
+        other => panic!("Expected a type error, got: {:?}", other),
+    }
+    assert_eq!(recorder.phases, vec!["parse"]);
+
+    let (result, recorder) = compile_recorded("Print[Add[1, 2]");
+    assert!(matches!(result, Err(CompileError::Parse { code: PARSE_ERROR_CODE, .. })), "got: {:?}", result);
+    assert!(recorder.phases.is_empty());
+}
+
+#[test]
+fn test_default_callbacks_do_nothing() {
+    /// Overrides only the hook it cares about
+    struct CodegenOnly(usize);
+    impl CompilerCallbacks for CodegenOnly {
+        fn on_codegen_complete(&mut self, stats: &CodegenStats) {
+            self.0 = stats.lines;
+        }
+    }
+
+    let mut callbacks = CodegenOnly(0);
+    compile("Print[1]", &CodegenOptions::default(), &mut callbacks).unwrap();
+    assert!(callbacks.0 > 0);
+}
+
+// ============================================================================
+// Compile Tests
+// ============================================================================
+
+#[test]
+fn test_compile_generates_rust() {
+    let rust_code = compile("Print[1 + 2]", &CodegenOptions::default(), &mut NoCallbacks).unwrap();
+    assert!(rust_code.contains("fn main()"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_compile_uses_options() {
+    let options = CodegenOptions { indent_width: 2, ..CodegenOptions::default() };
+    let rust_code = compile("Print[1]", &options, &mut NoCallbacks).unwrap();
+    assert!(rust_code.contains("\n  println!"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_compile_error_codes() {
+    let error = compile("1 + \"a\"", &CodegenOptions::default(), &mut NoCallbacks).unwrap_err();
+    assert_eq!(error.code(), Some("E0001"));
+    assert!(error.to_string().starts_with("error[E0001]: "), "got: {}", error);
+    assert_eq!(CompileError::Unsupported.code(), None);
+}
+
+// ============================================================================
+// File Tests
+// ============================================================================
+
+/// A directory holding `files`; the entry is the first
+fn project(name: &str, files: &[(&str, &str)]) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("w_driver_{}_{}", name, std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    for (file, contents) in files {
+        std::fs::write(dir.join(file), contents).unwrap();
+    }
+    dir
+}
+
+fn compile_project(name: &str, files: &[(&str, &str)]) -> Result<CompiledProgram, CompileError> {
+    let dir = project(name, files);
+    let (entry, source) = files[0];
+    let mut recorder = Recorder::default();
+    let result = compile_file(&dir.join(entry), source, &mut RustBackend, &BackendOptions::default(), &mut recorder);
+    std::fs::remove_dir_all(&dir).ok();
+    if result.is_ok() {
+        assert_eq!(recorder.phases, vec!["parse", "typecheck", "codegen"]);
+    }
+    result
+}
+
+#[test]
+fn test_compile_file_loads_imports() {
+    let compiled = compile_project("imports", &[
+        ("main.w", "Import[\"shape_utils.w\"]\nPrint[ShapeUtils.Double[3]]"),
+        ("shape_utils.w", "Double[n: Int32] := n * 2"),
+    ])
+    .unwrap();
+    assert_eq!(compiled.program.modules.len(), 1);
+    assert_eq!(compiled.program.modules[0].0, "ShapeUtils");
+    let rust_code = &compiled.artifacts.files[0].contents;
+    assert!(rust_code.contains("mod shape_utils"), "got: {}", rust_code);
+    assert!(compiled.artifacts.build.is_some());
+}
+
+#[test]
+fn test_compile_file_reports_module_errors() {
+    let error = compile_project("module_errors", &[
+        ("main.w", "Import[\"shape_utils.w\"]\nPrint[1]"),
+        ("shape_utils.w", "Half[n: Int32] := n / \"2\""),
+    ])
+    .unwrap_err();
+    assert_eq!(error.code(), Some("E0001"));
+    let rendered = error.render("main.w", "");
+    assert!(rendered.starts_with("error[E0001]: "), "got: {}", rendered);
+    assert!(rendered.contains("shape_utils.w: Type mismatch"), "got: {}", rendered);
+}
+
+#[test]
+fn test_compile_file_reports_missing_import() {
+    let error = compile_project("missing", &[("main.w", "Import[\"nowhere.w\"]\nPrint[1]")]).unwrap_err();
+    assert!(matches!(error, CompileError::Import(_)), "got: {:?}", error);
+    assert_eq!(error.code(), None);
+    assert!(error.to_string().contains("cannot import"), "got: {}", error);
+}
+
+/// The header of each file generated from `files` with split modules
+fn split_headers(name: &str, files: &[(&str, &str)]) -> Vec<(String, String)> {
+    let dir = project(name, files);
+    let (entry, source) = files[0];
+    let provenance = Provenance {
+        compiler_version: "0.1.0".to_string(),
+        flags: Vec::new(),
+        inputs: vec![(entry.to_string(), source.to_string())],
+    };
+    let codegen = CodegenOptions { provenance: Some(provenance), ..CodegenOptions::default() };
+    let options = BackendOptions { codegen, split_modules: true, ..BackendOptions::default() };
+    let result = compile_file(&dir.join(entry), source, &mut RustBackend, &options, &mut NoCallbacks);
+    std::fs::remove_dir_all(&dir).ok();
+    result.unwrap().artifacts.files.into_iter()
+        .map(|file| (file.path, file.contents.lines().take(4).collect::<Vec<_>>().join("\n")))
+        .collect()
+}
+
+#[test]
+fn test_split_module_headers_name_their_own_source() {
+    let headers = split_headers("headers", &[
+        ("main.w", "Import[\"shape_utils.w\"]\nPrint[ShapeUtils.Double[3]]"),
+        ("shape_utils.w", "Double[n: Int32] := n * 2"),
+    ]);
+    let (main, module) = (&headers[0], &headers[1]);
+    assert_eq!(main.0, "generated_modules/main.rs");
+    assert!(main.1.contains("// Source: main.w (2 inputs, fnv1a64: "), "got: {}", main.1);
+    assert_eq!(module.0, "generated_modules/shape_utils.rs");
+    // Imported files are named from the entry file's directory
+    let source = module.1.lines().nth(1).unwrap();
+    assert!(source.contains("/shape_utils.w (2 inputs, fnv1a64: "), "got: {}", source);
+    assert!(!source.contains("main.w"), "got: {}", source);
+}
+
+#[test]
+fn test_header_hash_covers_imported_files() {
+    let entry = ("main.w", "Import[\"shape_utils.w\"]\nPrint[ShapeUtils.Double[3]]");
+    let before = split_headers("hash_before", &[entry, ("shape_utils.w", "Double[n: Int32] := n * 2")]);
+    let after = split_headers("hash_after", &[entry, ("shape_utils.w", "Double[n: Int32] := n + n")]);
+    assert_ne!(before[0].1, after[0].1);
+    assert_ne!(before[1].1, after[1].1);
+}
@@ -0,0 +1,157 @@
+mod common;
+
+use common::{compile_and_run, parse, try_generate};
+use w::ast::{EnumVariant, Expression, Type, Visibility};
+use w::parser::Parser;
+use w::type_inference::{TypeError, TypeInference};
+
+fn check(input: &str) -> Result<(), TypeError> {
+    let expressions = match parse(input) {
+        Expression::Program(expressions) => expressions,
+        other => vec![other],
+    };
+    TypeInference::new().check_program(&expressions)
+}
+
+const SHAPE: &str = "Enum[Shape, [Circle[Float64], Rect[Float64, Float64], Empty]]\n";
+
+// ============================================================================
+// Parser Tests
+// ============================================================================
+
+#[test]
+fn test_parse_enum_definition() {
+    match parse("Enum[Shape, [Circle[Float64], Rect[Float64, Float64], Empty]]") {
+        Expression::EnumDefinition { name, variants, visibility } => {
+            assert_eq!(name, "Shape");
+            assert_eq!(variants, vec![
+                EnumVariant { name: "Circle".to_string(), fields: vec![Type::Float64] },
+                EnumVariant { name: "Rect".to_string(), fields: vec![Type::Float64, Type::Float64] },
+                EnumVariant { name: "Empty".to_string(), fields: vec![] },
+            ]);
+            assert_eq!(visibility, Visibility::Public);
+        }
+        other => panic!("Expected EnumDefinition, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_private_enum() {
+    match parse("Private[Enum[Color, [Red, Green]]]") {
+        Expression::EnumDefinition { visibility, .. } => assert_eq!(visibility, Visibility::Private),
+        other => panic!("Expected EnumDefinition, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_enum_requires_variants() {
+    let mut parser = Parser::new("Enum[Shape, []]".to_string());
+    assert!(parser.parse().is_none());
+}
+
+// ============================================================================
+// Type Inference Tests
+// ============================================================================
+
+#[test]
+fn test_variant_constructor_has_enum_type() {
+    let mut inference = TypeInference::new();
+    inference.infer_expression(&parse(SHAPE.trim())).unwrap();
+    assert_eq!(inference.infer_expression(&parse("Circle[1.0]")), Ok(Type::Custom("Shape".to_string())));
+    assert_eq!(inference.infer_expression(&parse("Empty")), Ok(Type::Custom("Shape".to_string())));
+}
+
+#[test]
+fn test_variant_constructor_checks_payload() {
+    let result = check(&format!("{}Circle[\"big\"]", SHAPE));
+    assert!(matches!(result, Err(TypeError::TypeMismatch { expected: Type::Float64, .. })), "got: {:?}", result);
+
+    let result = check(&format!("{}Rect[1.0]", SHAPE));
+    assert!(matches!(result, Err(TypeError::ArityMismatch { expected: 2, actual: 1, .. })), "got: {:?}", result);
+}
+
+#[test]
+fn test_variant_patterns_bind_payload() {
+    let source = format!("{}Area[s: Shape] := Match[s, [Circle[r], r], [Rect[w, h], w * h], [Empty, 0.0]]", SHAPE);
+    assert!(check(&source).is_ok(), "got: {:?}", check(&source));
+}
+
+#[test]
+fn test_variant_pattern_must_match_enum() {
+    let source = format!("{}Enum[Color, [Red, Green]]\nF[c: Color] := Match[c, [Circle[r], r], [_, 0.0]]", SHAPE);
+    let result = check(&source);
+    assert!(matches!(result, Err(TypeError::TypeMismatch { .. })), "got: {:?}", result);
+}
+
+#[test]
+fn test_variant_pattern_arity() {
+    let source = format!("{}F[s: Shape] := Match[s, [Rect[w], w], [_, 0.0]]", SHAPE);
+    let result = check(&source);
+    assert!(matches!(result, Err(TypeError::ArityMismatch { .. })), "got: {:?}", result);
+
+    let source = format!("{}F[s: Shape] := Match[s, [Rect[w, ..], w], [_, 0.0]]", SHAPE);
+    assert!(check(&source).is_ok());
+}
+
+#[test]
+fn test_let_with_variant_pattern_is_refutable() {
+    let result = check(&format!("{}Let[Circle[r], Circle[1.0]]", SHAPE));
+    assert_eq!(result, Err(TypeError::RefutablePattern("Circle[r]".to_string())));
+
+    assert!(check(&format!("{}LetElse[Circle[r], Circle[1.0], Print[0]]", SHAPE)).is_ok());
+}
+
+#[test]
+fn test_float_payload_enum_is_not_hashable() {
+    let result = check(&format!("{}F[m: Map[Shape, Int32]] := m", SHAPE));
+    assert!(matches!(result, Err(TypeError::UnhashableKey { .. })), "got: {:?}", result);
+}
+
+// ============================================================================
+// Codegen Tests
+// ============================================================================
+
+#[test]
+fn test_codegen_enum_definition() {
+    let rust_code = try_generate(SHAPE.trim()).unwrap();
+    assert!(rust_code.contains("pub enum Shape {\n    Circle(f64),\n    Rect(f64, f64),\n    Empty,\n}"),
+        "got: {}", rust_code);
+    assert!(rust_code.contains("#[derive(Debug, Clone, PartialEq)]"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_codegen_variant_constructors() {
+    let rust_code = try_generate(&format!("{}MakeCircle[r: Float64] := Circle[r]\nPrint[Empty]", SHAPE)).unwrap();
+    assert!(rust_code.contains("fn make_circle(r: f64) -> Shape {"), "got: {}", rust_code);
+    assert!(rust_code.contains("Shape::Circle(r)"), "got: {}", rust_code);
+    assert!(rust_code.contains("println!(\"{:?}\", Shape::Empty);"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_codegen_variant_patterns() {
+    let rust_code = try_generate(&format!("{}F[s: Shape] := Match[s, [Rect[w, ..], Print[w]], [Empty, Print[0]], [_, Print[1]]]", SHAPE)).unwrap();
+    assert!(rust_code.contains("Shape::Rect(w, ..) =>"), "got: {}", rust_code);
+    assert!(rust_code.contains("Shape::Empty =>"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_codegen_rejects_wrong_variant_arity() {
+    assert!(try_generate(&format!("{}Print[Rect[1.0]]", SHAPE)).is_err());
+}
+
+#[test]
+fn test_key_enum_derives_hash() {
+    let rust_code = try_generate("Enum[Color, [Red, Green]]\nPrint[{Red: 1}]").unwrap();
+    assert!(rust_code.contains("#[derive(Debug, Clone, PartialEq, Eq, Hash)]"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_enum_program_runs() {
+    let rust_code = try_generate(&format!(
+        "{}Describe[s: Shape] := Match[s, [Circle[r], Print[r]], [Rect[w, h], Print[w * h]], [Empty, Print[\"empty\"]]]\n\
+         Describe[Circle[1.5]]\nDescribe[Rect[2.0, 3.0]]\nDescribe[Empty]\n\
+         LetElse[Circle[radius], Rect[1.0, 1.0], Print[\"not a circle\"]]\nPrint[radius]",
+        SHAPE,
+    )).unwrap();
+    assert_eq!(compile_and_run(&rust_code, "describe"), "1.5\n6\nempty\nnot a circle\n");
+}
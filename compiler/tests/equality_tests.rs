@@ -0,0 +1,74 @@
+mod common;
+
+use common::{compile_and_run, generate, parse};
+use w::ast::{Expression, Type};
+use w::type_inference::{TypeError, TypeInference};
+
+fn check(input: &str) -> Result<(), TypeError> {
+    let expressions = match parse(input) {
+        Expression::Program(expressions) => expressions,
+        other => vec![other],
+    };
+    TypeInference::new().check_program(&expressions)
+}
+
+fn infer(input: &str) -> Result<Type, TypeError> {
+    TypeInference::new().infer_expression(&parse(input))
+}
+
+// ============================================================================
+// Option / Result Unification Tests
+// ============================================================================
+
+#[test]
+fn test_none_unifies_with_any_option() {
+    assert_eq!(infer("Some[1] == Some[1]").unwrap(), Type::Bool);
+    assert_eq!(infer("Some[\"a\"] == None").unwrap(), Type::Bool);
+    assert_eq!(infer("None != Some[2.5]").unwrap(), Type::Bool);
+}
+
+#[test]
+fn test_ok_and_err_unify() {
+    assert_eq!(infer("Ok[1] == Err[\"failed\"]").unwrap(), Type::Bool);
+}
+
+#[test]
+fn test_mismatched_payloads_are_still_rejected() {
+    assert!(matches!(infer("Some[1] == Some[\"a\"]"), Err(TypeError::TypeMismatch { .. })));
+    assert!(matches!(infer("Ok[1] == Err[2]"), Err(TypeError::TypeMismatch { .. })));
+}
+
+#[test]
+fn test_none_payload_is_unknown() {
+    assert_eq!(infer("None").unwrap(), Type::Option(Box::new(Type::Unknown)));
+}
+
+#[test]
+fn test_branches_unify_with_none() {
+    assert!(check("Find[x: Int32] := Cond[[x > 0 Some[x]] [None]]").is_ok());
+}
+
+// ============================================================================
+// Struct Equality Tests
+// ============================================================================
+
+#[test]
+fn test_struct_equality_type_checks() {
+    assert!(check("Struct[Point, [x: Int32, y: Int32]]\nSame[a: Point, b: Point] := a == b").is_ok());
+}
+
+// ============================================================================
+// Codegen Tests
+// ============================================================================
+
+#[test]
+fn test_generated_equality_runs() {
+    let rust_code = generate(
+        "Struct[Point, [x: Int32, y: Int32]]\n\
+         Print[Some[1] == Some[1]]\n\
+         Print[Some[1] == None]\n\
+         Print[Ok[1] == Err[\"failed\"]]\n\
+         Print[Point[1, 2] == Point[1, 2]]",
+    );
+    assert_eq!(compile_and_run(&rust_code, "run"), "true\nfalse\nfalse\ntrue\n");
+}
@@ -0,0 +1,136 @@
+mod common;
+
+use common::{compile_and_run, generate, parse, program};
+use w::ast::Type;
+use w::type_inference::{TypeError, TypeInference};
+
+fn infer(input: &str) -> Result<Type, TypeError> {
+    TypeInference::new().infer_expression(&parse(input))
+}
+
+/// Check `definitions`, then infer the type of `expr` in the resulting scope
+fn infer_with(definitions: &str, expr: &str) -> Result<Type, TypeError> {
+    let mut inference = TypeInference::new();
+    inference.check_program(&program(definitions))?;
+    inference.infer_expression(&parse(expr))
+}
+
+/// The missing case reported for a non-exhaustive Match
+fn missing(result: Result<Type, TypeError>) -> String {
+    match result {
+        Err(TypeError::NonExhaustiveMatch { missing, .. }) => missing,
+        other => panic!("Expected a non-exhaustive Match, got: {:?}", other),
+    }
+}
+
+const SHAPES: &str = "Enum[Shape, [Circle[Float64], Square[Float64], Empty]]";
+
+// ============================================================================
+// Exhaustiveness Tests
+// ============================================================================
+
+#[test]
+fn test_option_without_none() {
+    let result = infer("Match[Some[1], [Some[x], x]]");
+    assert_eq!(result, Err(TypeError::NonExhaustiveMatch {
+        type_: Type::Option(Box::new(Type::Int32)),
+        missing: "None".to_string(),
+    }));
+    assert_eq!(result.unwrap_err().code(), "E0025");
+    assert_eq!(infer("Match[Some[1], [Some[x], x], [None, 0]]").unwrap(), Type::Int32);
+}
+
+#[test]
+fn test_nested_missing_case() {
+    let input = "Match[Some[Some[true]], [Some[Some[true]], 1], [Some[None], 2], [None, 3]]";
+    assert_eq!(missing(infer(input)), "Some[Some[false]]");
+    assert_eq!(missing(infer("Match[Ok[1], [Ok[x], x]]")), "Err[_]");
+}
+
+#[test]
+fn test_enum_variants() {
+    assert_eq!(missing(infer_with(SHAPES, "Match[Empty, [Circle[r], r], [Empty, 0.0]]")), "Square[_]");
+    assert_eq!(infer_with(SHAPES, "Match[Empty, [Circle[r], r], [Square[s], s], [Empty, 0.0]]").unwrap(), Type::Float64);
+}
+
+#[test]
+fn test_literals_need_wildcard() {
+    assert_eq!(missing(infer("Match[5, [1, \"one\"], [2, \"two\"]]")), "_");
+    assert_eq!(missing(infer("Match[\"a\", [StartsWith[\"#\"], 1]]")), "_");
+    assert_eq!(infer("Match[5, [1, \"one\"], [_, \"other\"]]").unwrap(), Type::String);
+    assert_eq!(missing(infer("Match[true, [true, 1]]")), "false");
+}
+
+#[test]
+fn test_tuples() {
+    assert_eq!(missing(infer("Match[(1, true), [(1, true), 1], [(_, false), 2]]")), "(_, true)");
+    assert_eq!(infer("Match[(1, true), [(_, true), 1], [(_, false), 2]]").unwrap(), Type::Int32);
+}
+
+#[test]
+fn test_list_lengths() {
+    assert_eq!(missing(infer("Match[[1], [[], 0], [[x], x]]")), "[_, _, ..]");
+    assert_eq!(missing(infer("Match[[1], [[x, ..], x]]")), "[]");
+    assert_eq!(infer("Match[[1], [[], 0], [[x, ..], x]]").unwrap(), Type::Int32);
+    assert_eq!(infer("Match[[1], [[], 0], [[x], x], [[x, .., y], x + y]]").unwrap(), Type::Int32);
+}
+
+// ============================================================================
+// Unreachable Arm Tests
+// ============================================================================
+
+#[test]
+fn test_arm_after_wildcard() {
+    let result = infer("Match[5, [_, \"other\"], [0, \"zero\"]]");
+    assert_eq!(result, Err(TypeError::UnreachableArm("0".to_string())));
+    assert_eq!(result.unwrap_err().code(), "E0026");
+}
+
+#[test]
+fn test_arm_covered_by_earlier_arms() {
+    assert_eq!(infer("Match[true, [true, 1], [false, 2], [_, 3]]"), Err(TypeError::UnreachableArm("_".to_string())));
+    assert_eq!(infer("Match[3, [1 | 2, 1], [2, 2], [_, 3]]"), Err(TypeError::UnreachableArm("2".to_string())));
+    assert_eq!(
+        infer("Match[[1], [[x, ..], x], [[a, b], a], [[], 0]]"),
+        Err(TypeError::UnreachableArm("[a, b]".to_string()))
+    );
+}
+
+#[test]
+fn test_struct_pattern_with_rest() {
+    let definitions = "Struct[Point, [x: Int32, y: Int32]]";
+    assert_eq!(infer_with(definitions, "Match[Point[1, 2], [Point[0, y, ..], y], [Point[x, ..], x]]").unwrap(), Type::Int32);
+    assert_eq!(
+        infer_with(definitions, "Match[Point[1, 2], [Point[x, ..], x], [Point[0, y], y]]"),
+        Err(TypeError::UnreachableArm("Point[0, y]".to_string()))
+    );
+}
+
+// ============================================================================
+// List Pattern Tests
+// ============================================================================
+
+#[test]
+fn test_rest_binding_is_a_list() {
+    assert_eq!(infer("Match[[1, 2], [[x, rest @ ..], rest], [[], []]]").unwrap(), Type::List(Box::new(Type::Int32)));
+    // A named rest still leaves shorter lists to later arms
+    assert_eq!(infer("Match[[1], [[a, b], a], [[x, rest @ ..], x], [[], 0]]").unwrap(), Type::Int32);
+}
+
+#[test]
+fn test_list_arms_match_a_slice() {
+    let rust_code = generate("Head[xs: List[Int32]] := Match[xs, [[], 0], [[x, ..], x]]");
+    assert!(rust_code.contains("match xs.as_slice() {"), "got: {}", rust_code);
+    assert!(rust_code.contains("[x, ..] => { let x = x.clone(); x },"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_list_arms_run() {
+    let rust_code = generate(
+        "Head[xs: List[Int32]] := Match[xs, [[], 0], [[x, ..], x]]\n\
+         Describe[xs: List[String]] := Match[xs, [[a, b], a + b], [[first, rest @ ..], Describe[rest]], [whole, \"empty\"]]\n\
+         Print[Head[[7, 8]]]\nPrint[Head[[]]]\n\
+         Print[Describe[[\"a\", \"b\"]]]\nPrint[Describe[[\"a\", \"b\", \"c\"]]]\nPrint[Describe[[]]]",
+    );
+    assert_eq!(compile_and_run(&rust_code, "list_arms"), "7\n0\nab\nbc\nempty\n");
+}
@@ -0,0 +1,119 @@
+mod common;
+
+use common::parse;
+use w::ast::{Expression, Type};
+use w::type_inference::{TypeError, TypeInference};
+
+fn program(input: &str) -> Vec<Expression> {
+    if input.is_empty() {
+        return Vec::new();
+    }
+    match parse(input) {
+        Expression::Program(expressions) => expressions,
+        other => vec![other],
+    }
+}
+
+/// Check `definitions`, then infer the type of `expr` in the resulting scope
+fn infer_with(definitions: &str, expr: &str) -> Result<Type, TypeError> {
+    let mut inference = TypeInference::new();
+    inference.check_program(&program(definitions))?;
+    inference.infer_expression(&parse(expr))
+}
+
+/// Check `definitions`, then check `expr` against `expected`
+fn check_with(definitions: &str, expr: &str, expected: Type) -> Result<Type, TypeError> {
+    let mut inference = TypeInference::new();
+    inference.check_program(&program(definitions))?;
+    inference.check_expression(&parse(expr), &expected)
+}
+
+fn list(element: Type) -> Type {
+    Type::List(Box::new(element))
+}
+
+fn option(inner: Type) -> Type {
+    Type::Option(Box::new(inner))
+}
+
+fn mismatch(result: Result<Type, TypeError>) -> (Type, Type, String) {
+    match result {
+        Err(TypeError::TypeMismatch { expected, actual, context }) => (expected, actual, context),
+        other => panic!("Expected a type mismatch, got: {:?}", other),
+    }
+}
+
+// ============================================================================
+// Checking Mode Tests
+// ============================================================================
+
+#[test]
+fn test_empty_literals_take_expected_type() {
+    assert_eq!(check_with("", "[]", list(Type::String)).unwrap(), list(Type::String));
+    assert_eq!(check_with("", "None", option(Type::Bool)).unwrap(), option(Type::Bool));
+    assert_eq!(check_with("", "[[], [None]]", list(list(option(Type::Int32)))).unwrap(), list(list(option(Type::Int32))));
+}
+
+#[test]
+fn test_lambda_parameters_take_expected_types() {
+    let point = Type::Custom("Point".to_string());
+    let expected = Type::Function(vec![point.clone()], Box::new(Type::Int32));
+    let definitions = "Struct[Point, [x: Int32, y: Int32]]";
+    assert_eq!(check_with(definitions, "Function[{p}, p.x]", expected.clone()).unwrap(), expected);
+    assert!(matches!(infer_with(definitions, "Function[{p}, p.x]"), Err(TypeError::UndefinedField { .. })));
+}
+
+#[test]
+fn test_mismatch_reported_at_element() {
+    let (expected, actual, context) = mismatch(check_with("", "[1, \"a\"]", list(Type::Int32)));
+    assert_eq!((expected, actual), (Type::Int32, Type::String));
+    assert_eq!(context, "element of expression");
+
+    let (expected, actual, context) = mismatch(check_with("", "Some[[\"a\"]]", option(list(Type::Int32))));
+    assert_eq!((expected, actual), (Type::Int32, Type::String));
+    assert_eq!(context, "element of Some value of expression");
+}
+
+// ============================================================================
+// Signature Tests
+// ============================================================================
+
+#[test]
+fn test_function_signature_types_empty_list() {
+    assert_eq!(infer_with("Total[xs: List[Int32]] := xs", "Total[[]]").unwrap(), list(Type::Int32));
+    assert_eq!(infer_with("Rows[xs: List[List[Int32]]] := xs", "Rows[[[], [1]]]").unwrap(), list(list(Type::Int32)));
+}
+
+#[test]
+fn test_argument_mismatch_names_element() {
+    let (expected, actual, context) = mismatch(infer_with("Total[xs: List[Int32]] := xs", "Total[[1, \"a\"]]"));
+    assert_eq!((expected, actual), (Type::Int32, Type::String));
+    assert_eq!(context, "element of argument to Total");
+}
+
+#[test]
+fn test_struct_field_types_empty_list() {
+    let definitions = "Struct[Bag, [items: List[String]]]";
+    assert_eq!(infer_with(definitions, "Bag[[]]").unwrap(), Type::Custom("Bag".to_string()));
+    let (_, _, context) = mismatch(infer_with(definitions, "Bag[[1]]"));
+    assert_eq!(context, "element of field items");
+}
+
+#[test]
+fn test_push_types_none() {
+    assert_eq!(infer_with("Let[xs, [Some[1]]]\nPush[xs, None]", "xs").unwrap(), list(option(Type::Int32)));
+}
+
+// ============================================================================
+// Scrutinee Tests
+// ============================================================================
+
+#[test]
+fn test_match_patterns_type_none_scrutinee() {
+    assert_eq!(infer_with("", "Match[None, [Some[x], x + 1], [None, 0]]").unwrap(), Type::Int32);
+}
+
+#[test]
+fn test_match_patterns_type_empty_list_scrutinee() {
+    assert_eq!(infer_with("", "Match[[], [[], \"none\"], [[x, ..], x]]").unwrap(), Type::String);
+}
@@ -0,0 +1,163 @@
+use w::ast::Type;
+use w::const_eval::ConstErrorKind;
+use w::explain::{explain, EXPLANATIONS};
+use w::lexer::{LexError, Lexer};
+use w::lints::LintKind;
+use w::no_std::NO_STD_ERROR_CODE;
+use w::profile::PROFILE_ERROR_CODE;
+use w::resolve::BindingKind;
+use w::parser::{Parser, PARSE_ERROR_CODE};
+use w::type_inference::{TypeError, TypeInference};
+
+use std::collections::BTreeSet;
+use std::process::Command;
+
+fn type_error(input: &str) -> TypeError {
+    let mut parser = Parser::new(input.to_string());
+    TypeInference::new().infer_expression(&parser.parse().unwrap()).unwrap_err()
+}
+
+fn lex_error(input: &str) -> LexError {
+    let mut lexer = Lexer::new(input.to_string());
+    loop {
+        match lexer.next_token() {
+            Ok(Some(_)) => continue,
+            Ok(None) => panic!("Expected a lex error in: {}", input),
+            Err(error) => return error,
+        }
+    }
+}
+
+// ============================================================================
+// Code Tests
+// ============================================================================
+
+#[test]
+fn test_type_error_codes() {
+    assert_eq!(type_error("1 + \"a\"").code(), "E0001");
+    assert_eq!(type_error("missing + 1").code(), "E0002");
+    assert_eq!(type_error("CharCount[\"a\", \"b\"]").code(), "E0003");
+    assert_eq!(type_error("Cond[[1 2]]").code(), "E0012");
+    assert_eq!(type_error("Heap[1.5]").code(), "E0015");
+    assert_eq!(type_error("Print[Repeat[1]]").code(), "E0017");
+}
+
+#[test]
+fn test_codes_are_stable_across_payloads() {
+    let a = TypeError::NonBoolCondition(Type::Int32);
+    let b = TypeError::NonBoolCondition(Type::String);
+    assert_eq!(a.code(), b.code());
+}
+
+#[test]
+fn test_lex_error_codes() {
+    assert_eq!(lex_error("x # y").code(), "E0100");
+    assert_eq!(lex_error("\"open").code(), "E0101");
+    assert_eq!(lex_error(r#""\q""#).code(), "E0102");
+    assert_eq!(lex_error("0b102").code(), "E0104");
+    assert_eq!(PARSE_ERROR_CODE, "E0103");
+}
+
+// ============================================================================
+// Explanation Tests
+// ============================================================================
+
+#[test]
+fn test_every_code_has_an_explanation() {
+    let errors = [
+        type_error("1 + \"a\""),
+        type_error("missing + 1"),
+        type_error("CharCount[\"a\", \"b\"]"),
+        TypeError::CannotInfer("map literal".to_string()),
+        TypeError::UndefinedStruct("Point".to_string()),
+        TypeError::FieldCountMismatch { struct_name: "Point".to_string(), expected: 2, actual: 1 },
+        TypeError::UndefinedTrait("Shape".to_string()),
+        TypeError::MissingTraitMethod { trait_name: "Shape".to_string(), type_name: "Point".to_string(), method: "Area".to_string() },
+        TypeError::UnknownTraitMethod { trait_name: "Shape".to_string(), method: "Area".to_string() },
+        TypeError::TraitNotImplemented { trait_name: "Shape".to_string(), type_: Type::Int32 },
+        TypeError::UndefinedField { type_: Type::Int32, field: "x".to_string() },
+        TypeError::NonBoolCondition(Type::Int32),
+        TypeError::NotOrdered { type_: Type::Int32, reason: String::new() },
+        TypeError::UnhashableKey { key_type: Type::Float64, reason: String::new() },
+        TypeError::UnorderedHeapElement { element_type: Type::Float64, reason: String::new() },
+        TypeError::RefutablePattern("Some[x]".to_string()),
+        TypeError::UnboundedStream("Print".to_string()),
+        TypeError::NoCommonNumericType { first: Type::Int64, second: Type::Float64, context: String::new(), suggestion: Type::Float64 },
+        TypeError::OrPatternBindings { pattern: "Some[x] | None".to_string(), name: "x".to_string() },
+        TypeError::Cancelled,
+        type_error("Convert[\"42\", Int32]"),
+        TypeError::TryWithoutResult(None),
+        TypeError::InvalidKernel { kernel: "Shout".to_string(), reason: String::new() },
+        TypeError::DefaultBeforeRequired { function: "Greet".to_string(), parameter: "name".to_string() },
+        TypeError::NonExhaustiveMatch { type_: Type::Bool, missing: "false".to_string() },
+        TypeError::UnreachableArm("_".to_string()),
+        TypeError::AmbiguousName { name: String::new(), definitions: vec![] },
+        TypeError::RecursiveType(vec![]),
+        type_error("Print[\"{}\", 1, 2]"),
+        TypeError::PrivateItem { name: "Helper".to_string(), module: "Geometry".to_string() },
+        TypeError::UncheckedArithmetic { operator: "*".to_string(), function: "Scale".to_string(), returns: Type::Int32 },
+        TypeError::PropagateMismatch { operand: Type::Option(Box::new(Type::Int32)), function: None },
+        TypeError::StringPatternOutsideMatch { pattern: "\"a\"".to_string(), construct: "IfLet" },
+    ];
+    let mut codes: Vec<&str> = errors.iter().map(|e| e.code()).collect();
+    codes.extend([lex_error("#").code(), lex_error("\"").code(), lex_error(r#""\q""#).code(), lex_error("0x").code(), PARSE_ERROR_CODE, NO_STD_ERROR_CODE, PROFILE_ERROR_CODE]);
+    codes.extend([ConstErrorKind::DivisionByZero, ConstErrorKind::Overflow { left: 0, operator: "+", right: 0, type_: Type::Int32 }, ConstErrorKind::ArrayTooLarge { type_: Type::Int32, bytes: 0 }].iter().map(ConstErrorKind::code));
+    codes.extend([LintKind::DivisionByZero, LintKind::IndexOutOfBounds { index: 0, length: 0 }, LintKind::AlwaysReturnsEarly { operator: "?", value: "None" }, LintKind::InfiniteRecursion, LintKind::UnusedParameter(String::new()), LintKind::UnusedBinding(String::new()), LintKind::UnusedFunction(String::new()), LintKind::UnreachableBranch, LintKind::UnreachableCode { after: "Panic" }, LintKind::Shadowing { name: String::new(), inner: BindingKind::Let, outer: BindingKind::Let, outer_span: None }].iter().map(LintKind::code));
+
+    for code in &codes {
+        assert!(explain(code).is_some(), "no explanation for {}", code);
+    }
+    let distinct: BTreeSet<&str> = codes.iter().copied().collect();
+    assert_eq!(distinct.len(), codes.len(), "codes are reused: {:?}", codes);
+    assert_eq!(EXPLANATIONS.len(), codes.len(), "explanations without an error: {:?}", EXPLANATIONS);
+}
+
+#[test]
+fn test_explain_is_case_insensitive() {
+    assert_eq!(explain("e0012").unwrap().code, "E0012");
+    assert!(explain("E9999").is_none());
+    assert!(explain("").is_none());
+}
+
+#[test]
+fn test_explanations_show_an_example() {
+    for explanation in EXPLANATIONS {
+        assert!(explanation.body.contains("\n    "), "{} has no indented example", explanation.code);
+        assert!(explanation.body.ends_with('\n'), "{} does not end with a newline", explanation.code);
+    }
+}
+
+// ============================================================================
+// Command-Line Tests
+// ============================================================================
+
+#[test]
+fn test_cli_explain() {
+    let output = Command::new(env!("CARGO_BIN_EXE_w")).args(["--explain", "E0012"]).output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.starts_with("E0012: condition is not Bool\n\n"), "got: {}", stdout);
+    assert!(stdout.contains("If[count != 0, \"some\", \"none\"]"), "got: {}", stdout);
+}
+
+#[test]
+fn test_cli_explain_unknown_code() {
+    let output = Command::new(env!("CARGO_BIN_EXE_w")).args(["--explain", "E4242"]).output().unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8(output.stderr).unwrap().contains("E4242"));
+}
+
+#[test]
+fn test_cli_parse_error_reports_code() {
+    let dir = std::env::temp_dir().join(format!("w_explain_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let source = dir.join("bad.w");
+    std::fs::write(&source, "Print[Add[1, 2]").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_w")).arg(&source).current_dir(&dir).output().unwrap();
+    std::fs::remove_dir_all(&dir).ok();
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.starts_with("error[E0103]: "), "got: {}", stderr);
+    assert!(stderr.contains("try `w --explain E0103`"), "got: {}", stderr);
+}
@@ -0,0 +1,160 @@
+mod common;
+
+use common::{compile_and_run, generate, parse};
+use w::ast::{Expression, Type};
+use w::parser::Parser;
+use w::type_inference::{TypeError, TypeInference};
+
+/// Type of the last statement of `input`, after checking the ones before it
+fn infer_last(input: &str) -> Result<Type, TypeError> {
+    let mut expressions = match parse(input) {
+        Expression::Program(expressions) => expressions,
+        other => vec![other],
+    };
+    let last = expressions.pop().unwrap();
+    let mut inference = TypeInference::new();
+    inference.check_program(&expressions)?;
+    inference.infer_expression(&last)
+}
+
+const SHAPES: &str = "Struct[Point, [x: Int32, y: Int32]]\n\
+                      Struct[Line, [start: Point, label: String]]\n";
+
+// ============================================================================
+// Parser Tests
+// ============================================================================
+
+#[test]
+fn test_parse_dot_field_access() {
+    let expected = Expression::FieldAccess {
+        object: Box::new(Expression::Identifier("point".to_string())),
+        field: "x".to_string(),
+    };
+    assert_eq!(parse("point.x"), expected);
+}
+
+#[test]
+fn test_parse_field_builtin_matches_dot_syntax() {
+    assert_eq!(parse("Field[point, x]"), parse("point.x"));
+}
+
+#[test]
+fn test_parse_chained_field_access() {
+    match parse("line.start.x") {
+        Expression::FieldAccess { object, field } => {
+            assert_eq!(field, "x");
+            assert!(matches!(*object, Expression::FieldAccess { .. }), "got: {:?}", object);
+        }
+        other => panic!("Expected field access, got: {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_field_access_in_arithmetic() {
+    match parse("p.x + p.y") {
+        Expression::BinaryOp { left, right, .. } => {
+            assert!(matches!(*left, Expression::FieldAccess { .. }), "got: {:?}", left);
+            assert!(matches!(*right, Expression::FieldAccess { .. }), "got: {:?}", right);
+        }
+        other => panic!("Expected binary operation, got: {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_capitalized_dotted_name_is_still_qualified() {
+    assert!(matches!(parse("Math.Pi"), Expression::QualifiedName { .. }));
+}
+
+#[test]
+fn test_parse_field_requires_a_field_name() {
+    let mut parser = Parser::new("Field[point, \"x\"]".to_string());
+    assert!(parser.parse().is_none());
+}
+
+// ============================================================================
+// Type Inference Tests
+// ============================================================================
+
+#[test]
+fn test_infer_field_type_from_struct_definition() {
+    let result = infer_last(&format!("{}Let[p, Point[3, 4]]\np.x", SHAPES));
+    assert_eq!(result, Ok(Type::Int32));
+}
+
+#[test]
+fn test_infer_nested_field_type() {
+    let result = infer_last(&format!("{}Let[l, Line[Point[1, 2], \"diag\"]]\nl.start", SHAPES));
+    assert_eq!(result, Ok(Type::Custom("Point".to_string())));
+}
+
+#[test]
+fn test_infer_field_builtin() {
+    let result = infer_last(&format!("{}Let[l, Line[Point[1, 2], \"diag\"]]\nField[l, label]", SHAPES));
+    assert_eq!(result, Ok(Type::String));
+}
+
+#[test]
+fn test_unknown_field_is_rejected() {
+    let result = infer_last(&format!("{}Let[p, Point[3, 4]]\np.z", SHAPES));
+    assert_eq!(
+        result,
+        Err(TypeError::UndefinedField { type_: Type::Custom("Point".to_string()), field: "z".to_string() })
+    );
+}
+
+#[test]
+fn test_field_access_on_non_struct_is_rejected() {
+    let result = infer_last("Let[n, 5]\nn.x");
+    assert!(matches!(result, Err(TypeError::UndefinedField { type_: Type::Int32, .. })), "got: {:?}", result);
+}
+
+#[test]
+fn test_infer_field_of_parameter() {
+    let result = infer_last(&format!("{}Norm[p: Point] := p.x * p.x + p.y * p.y\nNorm[Point[1, 2]]", SHAPES));
+    assert_eq!(result, Ok(Type::Int32));
+    let result = infer_last(&format!("{}Map[Function[{{l}}, l.start.y], [Line[Point[1, 2], \"a\"]]]", SHAPES));
+    assert_eq!(result, Ok(Type::List(Box::new(Type::Int32))));
+}
+
+/// Type of a `StructInstantiation` node of `struct_name`, with SHAPES defined
+fn infer_instantiation(struct_name: &str, field_values: &str) -> Result<Type, TypeError> {
+    let field_values = match parse(&format!("[{}]", field_values)) {
+        Expression::List(values) => values,
+        other => panic!("Expected list, got {:?}", other),
+    };
+    let mut inference = TypeInference::new();
+    let Expression::Program(definitions) = parse(SHAPES) else { panic!("Expected program") };
+    inference.check_program(&definitions)?;
+    inference.infer_expression(&Expression::StructInstantiation { struct_name: struct_name.to_string(), field_values })
+}
+
+#[test]
+fn test_infer_struct_instantiation() {
+    assert_eq!(infer_instantiation("Line", "Point[1, 2], \"a\""), Ok(Type::Custom("Line".to_string())));
+    assert_eq!(infer_instantiation("Circle", "1"), Err(TypeError::UndefinedStruct("Circle".to_string())));
+    assert_eq!(
+        infer_instantiation("Point", "1"),
+        Err(TypeError::FieldCountMismatch { struct_name: "Point".to_string(), expected: 2, actual: 1 })
+    );
+    let result = infer_instantiation("Line", "Point[1, 2], 3");
+    assert!(matches!(result, Err(TypeError::TypeMismatch { ref context, .. }) if context == "field label"), "got: {:?}", result);
+}
+
+// ============================================================================
+// Code Generation Tests
+// ============================================================================
+
+#[test]
+fn test_codegen_field_access() {
+    let code = generate(&format!("{}Let[p, Point[3, 4]]\nPrint[p.x]", SHAPES));
+    assert!(code.contains("println!(\"{}\", p.x);"), "got: {}", code);
+}
+
+#[test]
+fn test_run_field_access() {
+    let code = generate(&format!(
+        "{}Let[p, Point[3, 4]]\nLet[l, Line[Point[1, 2], \"diag\"]]\nPrint[p.x + Field[p, y]]\nPrint[l.start.y]\nPrint[l.label]",
+        SHAPES
+    ));
+    assert_eq!(compile_and_run(&code, "run"), "7\n2\ndiag\n");
+}
@@ -0,0 +1,101 @@
+mod common;
+
+use common::parse;
+use w::fix::{fix_source, FixKind};
+use w::parser::Parser;
+
+use std::process::Command;
+
+fn fixed(input: &str) -> String {
+    fix_source(input).unwrap().source
+}
+
+// ============================================================================
+// Else Syntax Tests
+// ============================================================================
+
+#[test]
+fn test_else_matches_bracket_default() {
+    assert_eq!(parse("Cond[[x > 1, 1], Else[2]]"), parse("Cond[[x > 1, 1], [2]]"));
+    assert_eq!(parse("Cond[[x > 1 1] Else[2]]"), parse("Cond[[x > 1 1] [2]]"));
+}
+
+#[test]
+fn test_else_records_no_bracket_default() {
+    let mut parser = Parser::new("Cond[[x > 1, 1], Else[2]]".to_string());
+    parser.parse().unwrap();
+    assert!(parser.bracket_defaults().is_empty());
+}
+
+// ============================================================================
+// Fix Tests
+// ============================================================================
+
+#[test]
+fn test_fix_cond_default() {
+    let result = fix_source("Sign[n: Int32] := Cond[[n > 0, 1], [n < 0, 0 - 1], [0]]").unwrap();
+    assert_eq!(result.source, "Sign[n: Int32] := Cond[[n > 0, 1], [n < 0, 0 - 1], Else[0]]");
+    assert_eq!(result.fixes.len(), 1);
+    assert_eq!(result.fixes[0].kind, FixKind::CondDefaultElse);
+    assert_eq!((result.fixes[0].span.line, result.fixes[0].span.column), (1, 52));
+    assert_eq!(result.fixes[0].to_string(), "1:52: Cond default `[...]` rewritten as `Else[...]`");
+}
+
+#[test]
+fn test_fix_nested_conds() {
+    let source = "Let[x, Cond[[a, Cond[[b 1] [2]]], [3]]]\nPrint[\"é\"]\nPrint[Cond[[c 4] [5]]]";
+    assert_eq!(
+        fixed(source),
+        "Let[x, Cond[[a, Cond[[b 1] Else[2]]], Else[3]]]\nPrint[\"é\"]\nPrint[Cond[[c 4] Else[5]]]"
+    );
+}
+
+#[test]
+fn test_fix_keeps_program() {
+    let source = "Abs[n: Int32] := Cond[[n < 0 0 - n] [n]]\nPrint[Abs[0 - 3]]";
+    assert_eq!(parse(&fixed(source)), parse(source));
+}
+
+#[test]
+fn test_fix_is_idempotent() {
+    let once = fixed("Cond[[x > 1, 1], [2]]");
+    let again = fix_source(&once).unwrap();
+    assert_eq!(again.source, once);
+    assert!(again.fixes.is_empty());
+}
+
+#[test]
+fn test_fix_leaves_if_and_comments_alone() {
+    let source = "(* [1] is not a default *)\nPrint[If[x > 1, 1, 2]]";
+    let result = fix_source(source).unwrap();
+    assert_eq!(result.source, source);
+    assert!(result.fixes.is_empty());
+}
+
+#[test]
+fn test_fix_rejects_unparseable_source() {
+    assert!(fix_source("Cond[[x > 1, 1], [2]").is_none());
+}
+
+// ============================================================================
+// Command-Line Tests
+// ============================================================================
+
+#[test]
+fn test_cli_fix_rewrites_file() {
+    let dir = std::env::temp_dir().join(format!("w_fix_cli_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let source = dir.join("old.w");
+    std::fs::write(&source, "Print[Cond[[1 > 2, 1], [2]]]\n").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_w")).args(["fix", "old.w"]).current_dir(&dir).output().unwrap();
+    let rewritten = std::fs::read_to_string(&source).unwrap();
+    let again = Command::new(env!("CARGO_BIN_EXE_w")).args(["fix", "old.w"]).current_dir(&dir).output().unwrap();
+    std::fs::remove_dir_all(&dir).ok();
+
+    assert!(output.status.success());
+    assert_eq!(rewritten, "Print[Cond[[1 > 2, 1], Else[2]]]\n");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout, "old.w:1:24: Cond default `[...]` rewritten as `Else[...]`\nApplied 1 fix to old.w\n");
+    assert_eq!(String::from_utf8(again.stdout).unwrap(), "old.w needs no fixes\n");
+}
@@ -0,0 +1,95 @@
+mod common;
+
+use common::{compile_and_run, generate, parse};
+use w::ast::{Expression, Radix, Type};
+use w::parser::Parser;
+use w::type_inference::{TypeError, TypeInference};
+
+fn infer(input: &str) -> Result<Type, TypeError> {
+    TypeInference::new().infer_expression(&parse(input))
+}
+
+// ============================================================================
+// Parser Tests
+// ============================================================================
+
+#[test]
+fn test_parse_for() {
+    match parse("For[x, [1, 2], Print[x]]") {
+        Expression::For { variable, iterable, .. } => {
+            assert_eq!(variable, "x");
+            assert_eq!(*iterable, Expression::List(vec![Expression::Number(1, Radix::Decimal), Expression::Number(2, Radix::Decimal)]));
+        }
+        other => panic!("Expected For, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_for_requires_identifier_binding() {
+    let mut parser = Parser::new("For[1, [1, 2], Print[1]]".to_string());
+    assert!(parser.parse().is_none());
+}
+
+// ============================================================================
+// Type Inference Tests
+// ============================================================================
+
+#[test]
+fn test_for_is_unit() {
+    assert_eq!(infer("For[x, [1, 2], Print[x]]"), Ok(Type::Tuple(vec![])));
+}
+
+#[test]
+fn test_for_binds_element_type() {
+    let result = infer("For[x, [\"a\", \"b\"], !x]");
+    assert!(matches!(result, Err(TypeError::TypeMismatch { actual: Type::String, .. })), "got: {:?}", result);
+
+    assert!(infer("For[x, [true, false], !x]").is_ok());
+}
+
+#[test]
+fn test_for_over_map_binds_tuples() {
+    let result = TypeInference::new().check_program(&[parse("F[m: Map[String, Int32]] := For[entry, m, !entry]")]);
+    assert!(matches!(
+        result,
+        Err(TypeError::TypeMismatch { actual: Type::Tuple(ref types), .. }) if types == &vec![Type::String, Type::Int32]
+    ), "got: {:?}", result);
+}
+
+#[test]
+fn test_for_rejects_non_collection() {
+    let result = infer("For[x, 5, Print[x]]");
+    assert!(matches!(result, Err(TypeError::TypeMismatch { actual: Type::Int32, .. })), "got: {:?}", result);
+}
+
+#[test]
+fn test_for_variable_does_not_escape_body() {
+    let result = TypeInference::new().check_program(&[parse("For[x, [1], Print[x]]"), parse("x")]);
+    assert_eq!(result, Err(TypeError::UndefinedIdentifier("x".to_string())));
+}
+
+// ============================================================================
+// Codegen Tests
+// ============================================================================
+
+#[test]
+fn test_codegen_for_statement() {
+    let rust_code = generate("For[x, [1, 2], Print[x]]");
+    assert!(rust_code.contains("for x in vec![1, 2] {"), "got: {}", rust_code);
+    assert!(rust_code.contains("println!(\"{}\", x);"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_codegen_for_in_function_body() {
+    let rust_code = generate("Show[xs: List[Int32]] := For[x, xs, Print[x]]");
+    assert!(rust_code.contains("fn show(xs: Vec<i32>) {"), "got: {}", rust_code);
+    assert!(rust_code.contains("for x in xs {"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_for_runs_body_per_element() {
+    let rust_code = generate(
+        "Show[xs: List[Int32]] := For[x, xs, Print[x * 10]]\nFor[n, [1, 2, 3], Print[n + 1]]\nShow[[4, 5]]",
+    );
+    assert_eq!(compile_and_run(&rust_code, "per_element"), "2\n3\n4\n40\n50\n");
+}
@@ -0,0 +1,89 @@
+mod common;
+
+use common::{compile_and_run, generate, parse, program};
+use w::ast::{Expression, Type};
+use w::type_inference::{TypeError, TypeInference};
+
+/// Check `definitions`, then infer the type of `expr` in the resulting scope
+fn infer_with(definitions: &str, expr: &str) -> Result<Type, TypeError> {
+    let mut inference = TypeInference::new();
+    inference.check_program(&program(definitions))?;
+    inference.infer_expression(&parse(expr))
+}
+
+const APPLY: &str = "Apply[f: Function[Int32, Int32], x: Int32] := f[x]";
+
+// ============================================================================
+// Parser Tests
+// ============================================================================
+
+#[test]
+fn test_function_parameter_is_called() {
+    match parse(APPLY) {
+        Expression::FunctionDefinition { body, .. } => assert_eq!(*body, Expression::FunctionCall {
+            function: Box::new(Expression::Identifier("f".to_string())),
+            arguments: vec![Expression::Identifier("x".to_string())],
+        }),
+        other => panic!("Expected FunctionDefinition, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_other_parameters_still_index() {
+    let rust_code = generate("Second[f: List[Int32]] := f[1]");
+    assert!(rust_code.contains("f.get(1 as usize).cloned()"), "got: {}", rust_code);
+}
+
+// ============================================================================
+// Type Inference Tests
+// ============================================================================
+
+#[test]
+fn test_call_site_infers_lambda() {
+    assert_eq!(infer_with(APPLY, "Apply[Function[{n}, n * 2], 21]"), Ok(Type::Int32));
+    assert_eq!(infer_with(&format!("Double[n: Int32] := n * 2\n{}", APPLY), "Apply[Double, 4]"), Ok(Type::Int32));
+}
+
+#[test]
+fn test_argument_with_wrong_signature() {
+    let result = infer_with(APPLY, "Apply[Function[{n}, n > 2], 1]");
+    assert_eq!(result, Err(TypeError::TypeMismatch {
+        expected: Type::Function(vec![Type::Int32], Box::new(Type::Int32)),
+        actual: Type::Function(vec![Type::Int32], Box::new(Type::Bool)),
+        context: "argument to Apply".to_string(),
+    }));
+}
+
+#[test]
+fn test_body_call_is_checked() {
+    let result = infer_with("Apply[f: Function[Int32, Int32]] := f[\"s\"]", "Apply");
+    assert!(matches!(result, Err(TypeError::TypeMismatch { ref context, .. }) if context == "argument to f"), "got: {:?}", result);
+}
+
+// ============================================================================
+// Code Generation Tests
+// ============================================================================
+
+#[test]
+fn test_parameter_generates_impl_fn() {
+    let rust_code = generate(APPLY);
+    assert!(rust_code.contains("fn apply(f: impl Fn(i32) -> i32, x: i32) -> i32 {"), "got: {}", rust_code);
+    assert!(rust_code.contains("f(x)"), "got: {}", rust_code);
+
+    let rust_code = generate("Each[f: Function[Int32, String, Unit], n: Int32] := f[n, \"a\"]");
+    assert!(rust_code.contains("fn each(f: impl Fn(i32, String), n: i32) {"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_function_parameters_roundtrip() {
+    let source = format!(
+        "Double[n: Int32] := n * 2\n{}\n\
+         Twice[f: Function[Int32, Int32], x: Int32] := f[f[x]]\n\
+         Let[offset, 10]\n\
+         Print[Apply[Function[{{n}}, n + 1], 41]]\n\
+         Print[Twice[Double, 5]]\n\
+         Print[Apply[Function[{{n}}, n + offset], 1]]",
+        APPLY
+    );
+    assert_eq!(compile_and_run(&generate(&source), "roundtrip"), "42\n20\n11\n");
+}
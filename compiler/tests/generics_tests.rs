@@ -0,0 +1,131 @@
+mod common;
+
+use common::{compile_and_run, generate, parse, program};
+use w::ast::{Expression, Type};
+use w::type_inference::{TypeError, TypeInference};
+
+/// Check `definitions`, then infer the type of `expr` in the resulting scope
+fn infer_with(definitions: &str, expr: &str) -> Result<Type, TypeError> {
+    let mut inference = TypeInference::new();
+    inference.check_program(&program(definitions))?;
+    inference.infer_expression(&parse(expr))
+}
+
+// ============================================================================
+// Parser Tests
+// ============================================================================
+
+#[test]
+fn test_parse_type_variables() {
+    match parse("First[list: List[T], fallback: U] := fallback") {
+        Expression::FunctionDefinition { parameters, .. } => {
+            assert_eq!(parameters[0].type_, Type::List(Box::new(Type::TypeVar("T".to_string()))));
+            assert_eq!(parameters[1].type_, Type::TypeVar("U".to_string()));
+        }
+        other => panic!("Expected FunctionDefinition, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_longer_names_stay_custom_types() {
+    match parse("F[p: Point] := p") {
+        Expression::FunctionDefinition { parameters, .. } => {
+            assert_eq!(parameters[0].type_, Type::Custom("Point".to_string()));
+        }
+        other => panic!("Expected FunctionDefinition, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_declared_single_letter_types_are_not_variables() {
+    // The definition can come after the use
+    let expressions = program("F[q: Q, m: Map[K, T]] := q\nStruct[Q, [n: Int32]]\nEnum[K, [A, B]]");
+    match &expressions[0] {
+        Expression::FunctionDefinition { parameters, .. } => {
+            assert_eq!(parameters[0].type_, Type::Custom("Q".to_string()));
+            assert_eq!(
+                parameters[1].type_,
+                Type::Map(Box::new(Type::Custom("K".to_string())), Box::new(Type::TypeVar("T".to_string())))
+            );
+        }
+        other => panic!("Expected FunctionDefinition, got {:?}", other),
+    }
+}
+
+// ============================================================================
+// Type Inference Tests
+// ============================================================================
+
+#[test]
+fn test_identity_instantiates_per_call() {
+    let definitions = "Identity[x: T] := x";
+    assert_eq!(infer_with(definitions, "Identity[1]"), Ok(Type::Int32));
+    assert_eq!(infer_with(definitions, "Identity[\"s\"]"), Ok(Type::String));
+}
+
+#[test]
+fn test_type_variable_inside_container() {
+    let result = infer_with("Last[s: Stack[T]] := Pop[s]", "Last[[\"a\", \"b\"]]");
+    assert_eq!(result, Ok(Type::Option(Box::new(Type::String))));
+}
+
+#[test]
+fn test_conflicting_instantiation_is_rejected() {
+    let result = infer_with("Pair[a: T, b: T] := (a, b)", "Pair[1, \"s\"]");
+    assert!(matches!(result, Err(TypeError::TypeMismatch { .. })), "got: {:?}", result);
+}
+
+#[test]
+fn test_container_shape_must_match() {
+    let result = infer_with("Last[s: Stack[T]] := Pop[s]", "Last[1]");
+    assert!(matches!(result, Err(TypeError::TypeMismatch { .. })), "got: {:?}", result);
+}
+
+#[test]
+fn test_generic_values_are_not_map_keys() {
+    let result = TypeInference::new().check_program(&program("Counts[m: Map[K, Int32]] := m"));
+    match result {
+        Err(error) => assert!(error.to_string().contains("generic type K"), "got: {}", error),
+        Ok(()) => panic!("Expected a hashability error"),
+    }
+}
+
+// ============================================================================
+// Codegen Tests
+// ============================================================================
+
+#[test]
+fn test_codegen_generic_function() {
+    let rust_code = generate("Identity[x: T] := x");
+    assert!(rust_code.contains("fn identity<T>(x: T) -> T"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_codegen_declares_each_variable_once() {
+    let rust_code = generate("Choose[s: Stack[T], f: U, t: T] := t");
+    assert!(rust_code.contains("fn choose<T, U>(s: Vec<T>, f: U, t: T) -> T"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_codegen_generic_pop() {
+    let rust_code = generate("Last[s: Stack[T]] := Pop[s]");
+    assert!(rust_code.contains("fn last<T>(mut s: Vec<T>) -> Option<T>"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_generic_function_runs_with_several_types() {
+    let rust_code = generate(
+        "Last[s: Stack[T]] := Pop[s]\nIfLet[Some[n], Last[Stack[1, 2]], Print[n]]\nIfLet[Some[w], Last[Stack[\"a\", \"b\"]], Print[w]]",
+    );
+    assert_eq!(compile_and_run(&rust_code, "last"), "2\nb\n");
+}
+
+#[test]
+fn test_single_letter_struct_runs() {
+    let input = "Struct[Q, [n: Int32]]\nEnum[K, [A, B]]\nStruct[N, [next: N]]\n\
+                 F[q: Q, m: Map[K, Int32]] := q.n\nPrint[F[Q[3], {A: 1}]]";
+    let expressions = program(input);
+    assert_eq!(TypeInference::new().check_program(&expressions), Err(TypeError::RecursiveType(vec!["N".to_string(), "N".to_string()])));
+    let rust_code = generate(&input.replace("Struct[N, [next: N]]\n", ""));
+    assert_eq!(compile_and_run(&rust_code, "single_letter_struct"), "3\n");
+}
@@ -0,0 +1,121 @@
+mod common;
+
+use common::{compile_and_run, generate, parse};
+use w::ast::{Expression, Type};
+use w::stdlib::graph;
+use w::type_inference::{TypeError, TypeInference};
+
+fn infer(input: &str) -> Result<Type, TypeError> {
+    TypeInference::new().infer_expression(&parse(input))
+}
+
+fn check(input: &str) -> Result<(), TypeError> {
+    let expressions = match parse(input) {
+        Expression::Program(expressions) => expressions,
+        other => vec![other],
+    };
+    TypeInference::new().check_program(&expressions)
+}
+
+// ============================================================================
+// Stdlib Tests
+// ============================================================================
+
+#[test]
+fn test_from_edges_is_directed() {
+    let g = graph::from_edges(&[(1, 2), (1, 3), (2, 3)]);
+    assert_eq!(g[&1], vec![2, 3]);
+    assert_eq!(g[&3], Vec::<i32>::new());
+}
+
+#[test]
+fn test_undirected_adds_both_directions() {
+    let g = graph::undirected(&[(1, 2), (2, 3)]);
+    assert_eq!(g[&2], vec![1, 3]);
+}
+
+#[test]
+fn test_bfs_and_dfs_orders() {
+    let g = graph::undirected(&[(1, 2), (1, 3), (2, 4), (3, 4), (4, 5)]);
+    assert_eq!(graph::bfs(&g, 1), vec![1, 2, 3, 4, 5]);
+    assert_eq!(graph::dfs(&g, 1), vec![1, 2, 4, 3, 5]);
+}
+
+#[test]
+fn test_traversal_from_unknown_node() {
+    let g = graph::from_edges(&[(1, 2)]);
+    assert_eq!(graph::bfs(&g, 9), vec![9]);
+    assert_eq!(graph::dfs(&g, 9), vec![9]);
+}
+
+#[test]
+fn test_shortest_path() {
+    let g = graph::from_edges(&[(1, 2), (2, 3), (3, 4), (1, 3)]);
+    assert_eq!(graph::shortest_path(&g, 1, 4), Some(vec![1, 3, 4]));
+    assert_eq!(graph::shortest_path(&g, 2, 2), Some(vec![2]));
+    assert_eq!(graph::shortest_path(&g, 4, 1), None);
+}
+
+// ============================================================================
+// Type Inference Tests
+// ============================================================================
+
+#[test]
+fn test_graph_builtin_types() {
+    let graph_type = Type::Map(Box::new(Type::Int32), Box::new(Type::List(Box::new(Type::Int32))));
+    assert_eq!(infer("Graph.FromEdges[[(1, 2)]]"), Ok(graph_type));
+    assert_eq!(
+        infer("Graph.ShortestPath[Graph.FromEdges[[(1, 2)]], 1, 2]"),
+        Ok(Type::Option(Box::new(Type::List(Box::new(Type::Int32))))),
+    );
+}
+
+#[test]
+fn test_graph_parameter_in_user_function() {
+    assert!(check("Reachable[g: Map[Int32, List[Int32]], from: Int32] := Graph.Bfs[g, from]").is_ok());
+}
+
+#[test]
+fn test_graph_builtin_argument_checks() {
+    let result = infer("Graph.Bfs[[(1, 2)], 1]");
+    assert!(matches!(result, Err(TypeError::TypeMismatch { .. })), "got: {:?}", result);
+
+    let result = infer("Graph.Bfs[Graph.FromEdges[[(1, 2)]]]");
+    assert!(matches!(result, Err(TypeError::ArityMismatch { expected: 2, actual: 1, .. })), "got: {:?}", result);
+}
+
+// ============================================================================
+// Codegen Tests
+// ============================================================================
+
+#[test]
+fn test_codegen_borrows_collection_arguments() {
+    let rust_code = generate("Let[g, Graph.FromEdges[[(1, 2)]]]\nPrint[Graph.Bfs[g, 1]]");
+    assert!(rust_code.contains("__w_graph::from_edges(&vec![(1, 2)])"), "got: {}", rust_code);
+    assert!(rust_code.contains("__w_graph::bfs(&g, 1)"), "got: {}", rust_code);
+    assert!(rust_code.contains("println!(\"{:?}\""), "got: {}", rust_code);
+}
+
+#[test]
+fn test_support_module_emitted_once_and_only_when_used() {
+    let rust_code = generate("Print[Graph.Bfs[Graph.FromEdges[[(1, 2)]], 1]]");
+    assert_eq!(rust_code.matches("mod __w_graph {").count(), 1, "got: {}", rust_code);
+
+    let rust_code = generate("Print[Math.Sqrt[4]]");
+    assert!(!rust_code.contains("__w_graph"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_graph_program_runs() {
+    let rust_code = generate(
+        "Route[g: Map[Int32, List[Int32]], from: Int32, to: Int32] := \
+         Match[Graph.ShortestPath[g, from, to], [Some[path], For[node, path, Print[node]]], [None, Print[\"unreachable\"]]]\n\
+         Let[g, Graph.Undirected[[(1, 2), (1, 3), (2, 4), (3, 4), (4, 5)]]]\n\
+         Print[Graph.Bfs[g, 1]]\nPrint[Graph.Dfs[g, 1]]\n\
+         Route[g, 1, 5]\nRoute[Graph.FromEdges[[(1, 2)]], 2, 1]",
+    );
+    assert_eq!(
+        compile_and_run(&rust_code, "route"),
+        "[1, 2, 3, 4, 5]\n[1, 2, 4, 3, 5]\n1\n2\n4\n5\nunreachable\n",
+    );
+}
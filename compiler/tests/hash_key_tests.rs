@@ -0,0 +1,96 @@
+mod common;
+
+use common::{compile_and_run, parse, try_generate};
+use w::ast::{Expression, Type};
+use w::type_inference::{TypeError, TypeInference};
+
+fn check(input: &str) -> Result<(), TypeError> {
+    let expressions = match parse(input) {
+        Expression::Program(expressions) => expressions,
+        other => vec![other],
+    };
+    TypeInference::new().check_program(&expressions)
+}
+
+// ============================================================================
+// Derive Tests
+// ============================================================================
+
+#[test]
+fn test_struct_used_as_map_key_derives_hash_and_eq() {
+    let rust_code = try_generate(
+        "Struct[Point, [x: Int32, y: Int32]]\nCount[m: Map[Point, Int32]] := m",
+    ).unwrap();
+    assert!(rust_code.contains("#[derive(Debug, Clone, PartialEq, Eq, Hash)]\npub struct Point"),
+        "got: {}", rust_code);
+}
+
+#[test]
+fn test_struct_not_used_as_key_keeps_default_derives() {
+    let rust_code = try_generate("Struct[Point, [x: Int32, y: Int32]]\nOrigin[p: Point] := p").unwrap();
+    assert!(rust_code.contains("#[derive(Debug, Clone, PartialEq)]\npub struct Point"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_key_struct_fields_also_derive_hash() {
+    let rust_code = try_generate(
+        "Struct[Inner, [a: Int32]]\nStruct[Outer, [inner: Inner]]\nUse[s: HashSet[Outer]] := s",
+    ).unwrap();
+    assert!(rust_code.contains("#[derive(Debug, Clone, PartialEq, Eq, Hash)]\npub struct Inner"),
+        "got: {}", rust_code);
+    assert!(rust_code.contains("#[derive(Debug, Clone, PartialEq, Eq, Hash)]\npub struct Outer"),
+        "got: {}", rust_code);
+}
+
+#[test]
+fn test_struct_key_in_map_literal_compiles() {
+    let rust_code = try_generate(
+        "Struct[Point, [x: Int32, y: Int32]]\nPrint[{Point[1, 2]: \"a\"}]",
+    ).unwrap();
+    assert_eq!(compile_and_run(&rust_code, "literal"), "{Point { x: 1, y: 2 }: \"a\"}\n");
+}
+
+#[test]
+fn test_float_struct_key_is_not_generated() {
+    assert!(try_generate("Struct[Reading, [value: Float64]]\nPrint[{Reading[1.5]: 1}]").is_err());
+}
+
+// ============================================================================
+// Type Checker Tests
+// ============================================================================
+
+#[test]
+fn test_float_map_key_is_rejected() {
+    let err = check("Lookup[m: Map[Float64, Int32]] := m").unwrap_err();
+    assert!(matches!(err, TypeError::UnhashableKey { .. }), "got: {:?}", err);
+    assert!(err.to_string().contains("use an integer or String key"), "got: {}", err);
+}
+
+#[test]
+fn test_struct_with_float_field_key_is_rejected() {
+    let err = check("Struct[Reading, [value: Float64]]\nIndex[m: Map[Reading, Int32]] := m").unwrap_err();
+    assert!(err.to_string().contains("field `value` of struct Reading"), "got: {}", err);
+}
+
+#[test]
+fn test_nested_unhashable_key_is_rejected() {
+    assert!(matches!(check("Tags[s: List[HashSet[Float32]]] := s"), Err(TypeError::UnhashableKey { .. })));
+    assert!(matches!(check("Struct[Index, [by_ratio: Map[Tuple[Int32, Float64], String]]]"),
+        Err(TypeError::UnhashableKey { .. })));
+}
+
+#[test]
+fn test_inferred_unhashable_key_is_rejected() {
+    let err = check("Print[{1.5: \"a\"}]").unwrap_err();
+    assert!(matches!(err, TypeError::UnhashableKey { key_type: Type::Float64, .. }), "got: {:?}", err);
+    let err = check("Struct[Reading, [value: Float64]]\nLet[m, {Reading[1.5]: 1}]").unwrap_err();
+    assert!(err.to_string().contains("field `value` of struct Reading"), "got: {}", err);
+    let err = check("Keys[] := {{1: 2}: \"a\"}").unwrap_err();
+    assert!(err.to_string().contains("hash collections do not implement Hash"), "got: {}", err);
+}
+
+#[test]
+fn test_hashable_keys_are_accepted() {
+    assert!(check("Struct[Point, [x: Int32, y: Int32]]\nCount[m: Map[Point, Int32], s: HashSet[String]] := m").is_ok());
+    assert!(check("Struct[Point, [x: Int32, y: Int32]]\nLet[m, {Point[1, 2]: \"a\", Point[3, 4]: \"b\"}]").is_ok());
+}
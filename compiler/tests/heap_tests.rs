@@ -0,0 +1,116 @@
+mod common;
+
+use common::{compile_and_run, generate, parse};
+use w::ast::{Expression, Type};
+use w::type_inference::{TypeError, TypeInference};
+
+fn infer(input: &str) -> Result<Type, TypeError> {
+    TypeInference::new().infer_expression(&parse(input))
+}
+
+fn check(input: &str) -> Result<(), TypeError> {
+    let expressions = match parse(input) {
+        Expression::Program(expressions) => expressions,
+        other => vec![other],
+    };
+    TypeInference::new().check_program(&expressions)
+}
+
+// ============================================================================
+// Parser Tests
+// ============================================================================
+
+#[test]
+fn test_parse_heap_type() {
+    match parse("F[h: Heap[Tuple[Int32, String]]] := h") {
+        Expression::FunctionDefinition { parameters, .. } => {
+            assert_eq!(parameters[0].type_, Type::Heap(Box::new(Type::Tuple(vec![Type::Int32, Type::String]))));
+        }
+        other => panic!("Expected FunctionDefinition, got {:?}", other),
+    }
+}
+
+// ============================================================================
+// Type Inference Tests
+// ============================================================================
+
+#[test]
+fn test_heap_literal_type() {
+    assert_eq!(infer("Heap[3, 1, 2]"), Ok(Type::Heap(Box::new(Type::Int32))));
+}
+
+#[test]
+fn test_heap_pop_returns_option() {
+    assert!(check("Top[h: Heap[Int32]] := HeapPop[h]").is_ok());
+    assert_eq!(infer("HeapPop[Heap[1]]"), Ok(Type::Option(Box::new(Type::Int32))));
+}
+
+#[test]
+fn test_heap_push_checks_element_type() {
+    let result = check("Add[h: Heap[Int32]] := HeapPush[h, \"s\"]");
+    assert!(matches!(result, Err(TypeError::TypeMismatch { .. })), "got: {:?}", result);
+}
+
+#[test]
+fn test_heap_operations_reject_other_collections() {
+    let result = infer("HeapPop[Stack[1]]");
+    assert!(matches!(result, Err(TypeError::TypeMismatch { .. })), "got: {:?}", result);
+}
+
+#[test]
+fn test_float_heap_is_rejected() {
+    let result = infer("Heap[1.5, 2.5]");
+    match result {
+        Err(TypeError::UnorderedHeapElement { ref reason, .. }) => {
+            assert!(reason.contains("floats"), "got: {}", reason)
+        }
+        other => panic!("Expected UnorderedHeapElement, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_float_priority_in_tuple_is_rejected() {
+    let result = check("Add[h: Heap[Tuple[Int32, String]], d: Float64] := HeapPush[h, (d, \"a\")]");
+    assert!(result.is_err(), "got: {:?}", result);
+
+    let result = infer("Heap[(1.5, 1)]");
+    assert!(matches!(result, Err(TypeError::UnorderedHeapElement { .. })), "got: {:?}", result);
+}
+
+#[test]
+fn test_struct_heap_is_rejected() {
+    let result = check("Struct[Point, [x: Int32]]\nAdd[h: Heap[Point], p: Point] := HeapPush[h, p]");
+    match result {
+        Err(error) => assert!(error.to_string().contains("cannot be stored in a Heap"), "got: {}", error),
+        Ok(()) => panic!("Expected UnorderedHeapElement"),
+    }
+}
+
+// ============================================================================
+// Codegen Tests
+// ============================================================================
+
+#[test]
+fn test_codegen_heap_builtins() {
+    assert!(generate("Print[Heap[]]").contains("std::collections::BinaryHeap::new()"));
+    assert!(generate("Print[Heap[1, 2]]").contains("std::collections::BinaryHeap::from(vec![1, 2])"));
+
+    let rust_code = generate("Add[h: Heap[Int32]] := HeapPush[h, 1]\nTop[h: Heap[Int32]] := HeapPop[h]");
+    assert!(rust_code.contains("fn add(mut h: std::collections::BinaryHeap<i32>)"), "got: {}", rust_code);
+    assert!(rust_code.contains("h.push(1)"), "got: {}", rust_code);
+    assert!(rust_code.contains("fn top(mut h: std::collections::BinaryHeap<i32>) -> Option<i32>"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_heap_pops_largest_first() {
+    // The second heap is a Dijkstra-style frontier: negated distances make
+    // the smallest come out first
+    let rust_code = generate(
+        "Drain[h: Heap[Int32]] := WhileLet[Some[x], HeapPop[h], Print[x]]\n\
+         Visit[h: Heap[Tuple[Int32, String]]] := WhileLet[Some[(_, node)], HeapPop[h], Print[node]]\n\
+         Let[h, Heap[2, 7]]\nHeapPush[h, 5]\nDrain[h]\n\
+         Let[frontier, Heap[]]\nHeapPush[frontier, (0 - 4, \"c\")]\nHeapPush[frontier, (0, \"a\")]\n\
+         HeapPush[frontier, (0 - 1, \"b\")]\nVisit[frontier]",
+    );
+    assert_eq!(compile_and_run(&rust_code, "order"), "7\n5\n2\na\nb\nc\n");
+}
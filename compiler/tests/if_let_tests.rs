@@ -0,0 +1,97 @@
+mod common;
+
+use common::{compile_and_run, parse, try_generate};
+use w::ast::{Expression, Pattern, Radix, Type};
+use w::type_inference::{TypeError, TypeInference};
+
+fn infer(input: &str) -> Result<Type, TypeError> {
+    TypeInference::new().infer_expression(&parse(input))
+}
+
+// ============================================================================
+// Parser Tests
+// ============================================================================
+
+#[test]
+fn test_parse_if_let_with_else() {
+    match parse("IfLet[Some[x], Some[1], x, 0]") {
+        Expression::IfLet { pattern, else_branch, .. } => {
+            assert_eq!(pattern, Pattern::Constructor {
+                name: "Some".to_string(),
+                patterns: vec![Pattern::Variable("x".to_string())],
+            });
+            assert_eq!(else_branch.as_deref(), Some(&Expression::Number(0, Radix::Decimal)));
+        }
+        other => panic!("Expected IfLet, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_if_let_without_else() {
+    match parse("IfLet[Some[x], Some[1], Print[x]]") {
+        Expression::IfLet { else_branch, .. } => assert!(else_branch.is_none()),
+        other => panic!("Expected IfLet, got {:?}", other),
+    }
+}
+
+// ============================================================================
+// Type Inference Tests
+// ============================================================================
+
+#[test]
+fn test_if_let_type_is_branch_type() {
+    assert_eq!(infer("IfLet[Some[x], Some[1], x + 1, 0]"), Ok(Type::Int32));
+}
+
+#[test]
+fn test_if_let_branches_must_agree() {
+    let result = infer("IfLet[Some[x], Some[1], x, \"none\"]");
+    assert!(matches!(result, Err(TypeError::TypeMismatch { .. })), "got: {:?}", result);
+}
+
+#[test]
+fn test_if_let_without_else_is_unit() {
+    assert_eq!(infer("IfLet[Some[x], Some[1], Print[x]]"), Ok(Type::Tuple(vec![])));
+}
+
+#[test]
+fn test_if_let_binding_not_visible_in_else() {
+    let result = infer("IfLet[Some[x], Some[1], x, x]");
+    assert_eq!(result, Err(TypeError::UndefinedIdentifier("x".to_string())));
+}
+
+#[test]
+fn test_if_let_pattern_must_match_value_type() {
+    let result = infer("IfLet[Ok[x], Some[1], x, 0]");
+    assert!(result.is_err(), "got: {:?}", result);
+}
+
+// ============================================================================
+// Codegen Tests
+// ============================================================================
+
+#[test]
+fn test_codegen_if_let_statement() {
+    let rust_code = try_generate("IfLet[Some[x], Some[1], Print[x], Print[0]]").unwrap();
+    assert!(rust_code.contains("if let Some(x) = Some(1) {"), "got: {}", rust_code);
+    assert!(rust_code.contains("} else {"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_codegen_if_let_value() {
+    let rust_code = try_generate("Wrap[n: Int32] := IfLet[Some[x], Some[n], x, 0]").unwrap();
+    assert!(rust_code.contains("if let Some(x) = Some(n) {\n    x\n} else {\n    0\n}"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_codegen_rejects_string_literal_pattern() {
+    assert!(try_generate("IfLet[(\"a\", x), (\"a\", 1), Print[x]]").is_err());
+}
+
+#[test]
+fn test_if_let_runs_matching_branch() {
+    let rust_code = try_generate(
+        "IfLet[Some[x], Some[41], Print[x + 1], Print[0]]\nIfLet[(a, b), (1, 2), Print[a + b]]\nIfLet[(1, y), (2, 3), Print[y], Print[\"no match\"]]",
+    ).unwrap();
+    assert_eq!(compile_and_run(&rust_code, "branches"), "42\n3\nno match\n");
+}
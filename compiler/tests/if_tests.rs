@@ -0,0 +1,106 @@
+mod common;
+
+use common::{compile_and_run, generate, parse};
+use w::ast::{Expression, Type};
+use w::parser::Parser;
+use w::type_inference::{TypeError, TypeInference};
+
+fn infer(input: &str) -> Result<Type, TypeError> {
+    TypeInference::new().infer_expression(&parse(input))
+}
+
+// ============================================================================
+// Parser Tests
+// ============================================================================
+
+#[test]
+fn test_parse_if_is_two_branch_cond() {
+    match parse("If[x > 0, 1, 2]") {
+        Expression::Cond { conditions, default_statements, from_if } => {
+            assert!(from_if);
+            assert_eq!(Expression::Cond { conditions, default_statements, from_if: false }, parse("Cond[[x > 0 1] [2]]"));
+        }
+        other => panic!("Expected Cond, got: {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_if_requires_three_arguments() {
+    for source in ["If[x > 0, 1]", "If[x > 0, 1, 2, 3]", "If[]"] {
+        let mut parser = Parser::new(source.to_string());
+        assert!(parser.parse().is_none(), "parsed: {}", source);
+    }
+}
+
+#[test]
+fn test_parse_nested_if() {
+    match parse("If[a, If[b, 1, 2], 3]") {
+        Expression::Cond { conditions, .. } => {
+            assert!(matches!(conditions[0].1, Expression::Cond { .. }), "got: {:?}", conditions[0].1);
+        }
+        other => panic!("Expected Cond, got: {:?}", other),
+    }
+}
+
+// ============================================================================
+// Type Inference Tests
+// ============================================================================
+
+#[test]
+fn test_if_branches_share_a_type() {
+    assert_eq!(infer("If[true, \"yes\", \"no\"]").unwrap(), Type::String);
+}
+
+#[test]
+fn test_if_branch_mismatch_is_rejected() {
+    assert!(matches!(infer("If[true, 1, \"one\"]"), Err(TypeError::TypeMismatch { .. })));
+}
+
+#[test]
+fn test_if_branch_mismatch_names_if() {
+    let message = infer("If[true, [1, 2], Some[\"x\"]]").unwrap_err().to_string();
+    assert_eq!(message, "Type mismatch in If else branch: expected List[Int32], got Option[String]");
+}
+
+#[test]
+fn test_if_condition_must_be_bool() {
+    assert_eq!(infer("If[1, 2, 3]"), Err(TypeError::NonBoolCondition(Type::Int32)));
+}
+
+#[test]
+fn test_if_with_diverging_else() {
+    assert_eq!(infer("If[true, 2.5, Panic[\"no\"]]").unwrap(), Type::Float64);
+}
+
+// ============================================================================
+// Code Generation Tests
+// ============================================================================
+
+#[test]
+fn test_codegen_if_else_expression() {
+    let rust_code = generate("Abs[x: Int32] := If[x < 0, 0 - x, x]");
+    assert!(
+        rust_code.contains("fn abs(x: i32) -> i32 {\n    if (x < 0) {\n        (0 - x)\n    } else {\n        x\n    }\n}"),
+        "got: {}",
+        rust_code
+    );
+}
+
+#[test]
+fn test_codegen_if_return_type_skips_diverging_branch() {
+    let rust_code = generate("Check[x: Int32] := If[x < 0, Panic[\"negative\"], x]");
+    assert!(rust_code.contains("fn check(x: i32) -> i32 {"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_run_if() {
+    let rust_code = generate(
+        "Abs[x: Int32] := If[x < 0, 0 - x, x]\n\
+         Print[Abs[0 - 4]]\n\
+         Let[n, 7]\n\
+         Print[If[n > 5, \"big\", \"small\"]]\n\
+         If[n == 7, Print[\"seven\"], Print[\"other\"]]\n\
+         Print[If[n > 5, If[n > 6, 2, 1], 0]]",
+    );
+    assert_eq!(compile_and_run(&rust_code, "run"), "4\nbig\nseven\n2\n");
+}
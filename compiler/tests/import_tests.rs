@@ -0,0 +1,193 @@
+mod common;
+
+use common::{compile_and_run, parse};
+use w::ast::Expression;
+use w::imports::{load_imports, module_name, ImportError, LoadedProgram};
+use w::parser::Parser;
+use w::rust_codegen::RustCodeGenerator;
+
+use std::path::{Path, PathBuf};
+
+/// A fresh directory holding the given `(relative path, source)` files
+fn project(name: &str, files: &[(&str, &str)]) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("w_imports_{}_{}", name, std::process::id()));
+    std::fs::remove_dir_all(&dir).ok();
+    for (path, source) in files {
+        let path = dir.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, source).unwrap();
+    }
+    dir
+}
+
+/// Parse and resolve `main.w` in `dir`
+fn load(dir: &Path) -> Result<LoadedProgram, ImportError> {
+    let entry_path = dir.join("main.w");
+    let source = std::fs::read_to_string(&entry_path).unwrap();
+    load_imports(&entry_path, parse(&source))
+}
+
+fn module_names(program: &LoadedProgram) -> Vec<&str> {
+    program.modules.iter().map(|(name, _)| name.as_str()).collect()
+}
+
+// ============================================================================
+// Parser Tests
+// ============================================================================
+
+#[test]
+fn test_parse_import() {
+    assert_eq!(parse("Import[\"geometry.w\"]"), Expression::Import("geometry.w".to_string()));
+}
+
+#[test]
+fn test_import_requires_string_path() {
+    let mut parser = Parser::new("Import[geometry]".to_string());
+    assert!(parser.parse().is_none());
+}
+
+#[test]
+fn test_module_names_follow_file_stem() {
+    assert_eq!(module_name(Path::new("lib/geometry.w")), Some("Geometry".to_string()));
+    assert_eq!(module_name(Path::new("shape_utils.w")), Some("ShapeUtils".to_string()));
+    assert_eq!(module_name(Path::new("2d.w")), None);
+    assert_eq!(module_name(Path::new("my file.w")), None);
+}
+
+// ============================================================================
+// Loader Tests
+// ============================================================================
+
+#[test]
+fn test_entry_without_imports_is_unchanged() {
+    let dir = project("none", &[("main.w", "Print[1]")]);
+    let program = load(&dir).unwrap();
+    assert_eq!(program.entry, parse("Print[1]"));
+    assert!(program.modules.is_empty());
+}
+
+#[test]
+fn test_imports_are_removed_and_loaded() {
+    let dir = project("basic", &[
+        ("main.w", "Import[\"lib/geometry.w\"]\nPrint[Square[2]]"),
+        ("lib/geometry.w", "Square[x: Int32] := x * x"),
+    ]);
+    let program = load(&dir).unwrap();
+    assert_eq!(program.entry, Expression::Program(vec![parse("Print[Square[2]]")]));
+    assert_eq!(module_names(&program), vec!["Geometry"]);
+}
+
+#[test]
+fn test_modules_in_dependency_order_and_loaded_once() {
+    // main imports both; shapes also imports geometry, relative to lib/
+    let dir = project("diamond", &[
+        ("main.w", "Import[\"lib/shapes.w\"]\nImport[\"lib/geometry.w\"]\nPrint[1]"),
+        ("lib/shapes.w", "Import[\"geometry.w\"]\nArea[x: Int32] := Square[x]"),
+        ("lib/geometry.w", "Square[x: Int32] := x * x"),
+    ]);
+    let program = load(&dir).unwrap();
+    assert_eq!(module_names(&program), vec!["Geometry", "Shapes"]);
+}
+
+#[test]
+fn test_missing_file_is_reported() {
+    let dir = project("missing", &[("main.w", "Import[\"nope.w\"]\nPrint[1]")]);
+    match load(&dir) {
+        Err(error @ ImportError::MissingFile { .. }) => {
+            let message = error.to_string();
+            assert!(message.contains("main.w: cannot import"), "got: {}", message);
+            assert!(message.contains("nope.w"), "got: {}", message);
+        }
+        other => panic!("Expected MissingFile, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_import_cycle_is_reported() {
+    let dir = project("cycle", &[
+        ("main.w", "Import[\"a.w\"]\nPrint[1]"),
+        ("a.w", "Import[\"b.w\"]\nF[x: Int32] := x"),
+        ("b.w", "Import[\"a.w\"]\nG[x: Int32] := x"),
+    ]);
+    match load(&dir) {
+        Err(ImportError::Cycle(paths)) => {
+            let names: Vec<_> = paths.iter().map(|p| p.file_name().unwrap().to_str().unwrap()).collect();
+            assert_eq!(names, vec!["a.w", "b.w", "a.w"]);
+        }
+        other => panic!("Expected Cycle, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_error_names_the_imported_file() {
+    let dir = project("parse", &[
+        ("main.w", "Import[\"broken.w\"]\nPrint[1]"),
+        ("broken.w", "F[x := "),
+    ]);
+    match load(&dir) {
+        Err(error @ ImportError::Parse { .. }) => {
+            assert!(error.to_string().contains("broken.w:"), "got: {}", error);
+        }
+        other => panic!("Expected Parse, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_same_module_name_from_two_directories() {
+    let dir = project("duplicate", &[
+        ("main.w", "Import[\"a/util.w\"]\nImport[\"b/util.w\"]\nPrint[1]"),
+        ("a/util.w", "F[x: Int32] := x"),
+        ("b/util.w", "G[x: Int32] := x"),
+    ]);
+    let result = load(&dir);
+    assert!(matches!(result, Err(ImportError::DuplicateModule { ref name, .. }) if name == "Util"), "got: {:?}", result);
+}
+
+// ============================================================================
+// Codegen Tests
+// ============================================================================
+
+#[test]
+fn test_single_file_inlines_modules() {
+    let modules = vec![("Geometry".to_string(), parse("Square[x: Int32] := x * x"))];
+    let entry = parse("Print[Square[4]]");
+
+    let rust_code = RustCodeGenerator::new().generate_single_file(&entry, &modules).unwrap();
+    assert!(rust_code.contains("mod geometry {\n"), "got: {}", rust_code);
+    assert!(rust_code.contains("    pub fn square(x: i32) -> i32 {"), "got: {}", rust_code);
+    assert!(rust_code.contains("}\nuse geometry::*;"), "got: {}", rust_code);
+    assert!(!rust_code.contains("mod geometry;"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_single_file_header_appears_once() {
+    let modules = vec![("Geometry".to_string(), parse("Square[x: Int32] := x * x"))];
+    let entry = parse("Print[Square[4]]");
+
+    let mut codegen = RustCodeGenerator::builder().module_header("// header").build();
+    let rust_code = codegen.generate_single_file(&entry, &modules).unwrap();
+    assert_eq!(rust_code.matches("// header").count(), 1, "got: {}", rust_code);
+}
+
+#[test]
+fn test_module_files_include_support_modules() {
+    let modules = vec![(
+        "Routes".to_string(),
+        parse("Reach[g: Map[Int32, List[Int32]]] := Graph.Bfs[g, 1]"),
+    )];
+    let files = RustCodeGenerator::new().generate_modules(&parse("Print[1]"), &modules).unwrap();
+    assert!(files[1].contents.contains("mod __w_graph {"), "got: {}", files[1].contents);
+    assert!(!files[0].contents.contains("mod __w_graph {"), "got: {}", files[0].contents);
+}
+
+#[test]
+fn test_imported_program_runs() {
+    let dir = project("run", &[
+        ("main.w", "Import[\"shape_utils.w\"]\nPrint[Square[4]]\nPrint[ShapeUtils.Double[3]]"),
+        ("shape_utils.w", "Import[\"lib/geometry.w\"]\nDouble[x: Int32] := Geometry.Square[x] - x * x + x * 2"),
+        ("lib/geometry.w", "Square[x: Int32] := x * x"),
+    ]);
+    let program = load(&dir).unwrap();
+    let rust_code = RustCodeGenerator::new().generate_single_file(&program.entry, &program.modules).unwrap();
+    assert_eq!(compile_and_run(&rust_code, "shapes"), "16\n6\n");
+}
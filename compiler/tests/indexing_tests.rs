@@ -0,0 +1,174 @@
+mod common;
+
+use common::{compile_and_run, generate, parse};
+use w::ast::{Expression, Radix, Type};
+use w::type_inference::{TypeError, TypeInference};
+
+fn infer(input: &str) -> Result<Type, TypeError> {
+    TypeInference::new().infer_expression(&parse(input))
+}
+
+/// Infer the return type of the single function defined in `input`
+fn infer_body(input: &str) -> Result<Type, TypeError> {
+    match infer(input)? {
+        Type::Function(_, return_type) => Ok(*return_type),
+        other => panic!("Expected function type, got: {:?}", other),
+    }
+}
+
+/// `name[argument]`, which type checking decides is a call or an index
+fn apply(name: &str, argument: Expression) -> Expression {
+    Expression::FunctionCall {
+        function: Box::new(Expression::Identifier(name.to_string())),
+        arguments: vec![argument],
+    }
+}
+
+// ============================================================================
+// Parser Tests
+// ============================================================================
+
+#[test]
+fn test_parse_bracket_index_as_application() {
+    assert_eq!(parse("xs[0]"), apply("xs", Expression::Number(0, Radix::Decimal)));
+}
+
+#[test]
+fn test_parse_bracket_index_in_expression() {
+    match parse("xs[i] + 1") {
+        Expression::BinaryOp { left, .. } => assert_eq!(*left, apply("xs", Expression::Identifier("i".to_string()))),
+        other => panic!("Expected binary operation, got: {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_lowercase_function_call_is_not_an_index() {
+    match parse("Print[g[2]]\ng[x] := x * 2") {
+        Expression::Program(expressions) => match &expressions[0] {
+            Expression::FunctionCall { arguments, .. } => match &arguments[0] {
+                Expression::FunctionCall { function, .. } => {
+                    assert_eq!(**function, Expression::Identifier("g".to_string()));
+                }
+                other => panic!("Expected call to g, got: {:?}", other),
+            },
+            other => panic!("Expected Print call, got: {:?}", other),
+        },
+        other => panic!("Expected program, got: {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_pascal_case_call_is_not_an_index() {
+    match parse("Square[4]") {
+        Expression::FunctionCall { function, .. } => assert_eq!(*function, Expression::Identifier("Square".to_string())),
+        other => panic!("Expected call to Square, got: {:?}", other),
+    }
+}
+
+// ============================================================================
+// Type Inference Tests
+// ============================================================================
+
+#[test]
+fn test_at_list_is_option_of_element() {
+    assert_eq!(infer("At[[\"a\", \"b\"], 1]").unwrap(), Type::Option(Box::new(Type::String)));
+}
+
+#[test]
+fn test_bracket_index_on_list_parameter() {
+    assert_eq!(
+        infer_body("Second[xs: List[Float64]] := xs[1]").unwrap(),
+        Type::Option(Box::new(Type::Float64))
+    );
+}
+
+#[test]
+fn test_bracket_application_follows_binding_type() {
+    assert_eq!(infer("Block[Let[xs, [1, 2]], xs[0]]").unwrap(), Type::Option(Box::new(Type::Int32)));
+    assert_eq!(
+        infer_body("Double[x: Int32] := Block[Let[g, Function[{y}, y * 2]], g[x]]").unwrap(),
+        Type::Int32
+    );
+}
+
+#[test]
+fn test_at_accepts_any_integer_index() {
+    assert_eq!(
+        infer_body("Get[xs: List[Int32], i: UInt64] := At[xs, i]").unwrap(),
+        Type::Option(Box::new(Type::Int32))
+    );
+}
+
+#[test]
+fn test_at_rejects_non_integer_index() {
+    assert!(matches!(infer("At[[1, 2], \"one\"]"), Err(TypeError::TypeMismatch { .. })));
+}
+
+#[test]
+fn test_lookup_map_is_option_of_value() {
+    assert_eq!(
+        infer_body("Age[ages: Map[String, Int32]] := Lookup[ages, \"ann\"]").unwrap(),
+        Type::Option(Box::new(Type::Int32))
+    );
+    assert_eq!(
+        infer_body("Name[names: BTreeMap[Int32, String]] := Lookup[names, 7]").unwrap(),
+        Type::Option(Box::new(Type::String))
+    );
+}
+
+#[test]
+fn test_lookup_rejects_wrong_key_type() {
+    assert!(matches!(
+        infer_body("Age[ages: Map[String, Int32]] := Lookup[ages, 1]"),
+        Err(TypeError::TypeMismatch { .. })
+    ));
+}
+
+#[test]
+fn test_lookup_rejects_list() {
+    assert!(matches!(infer("Lookup[[1, 2], 0]"), Err(TypeError::TypeMismatch { .. })));
+}
+
+// ============================================================================
+// Code Generation Tests
+// ============================================================================
+
+#[test]
+fn test_codegen_index_is_bounds_safe() {
+    let rust_code = generate("Second[xs: List[Int32]] := xs[1]");
+    assert!(
+        rust_code.contains("fn second(xs: Vec<i32>) -> Option<i32> {\n    xs.get(1 as usize).cloned()\n}"),
+        "got: {}",
+        rust_code
+    );
+}
+
+#[test]
+fn test_codegen_let_bound_lambda_is_called() {
+    let rust_code = generate("Double[x: Int32] := Block[Let[g, Function[{y}, y * 2]], g[x]]");
+    assert!(rust_code.contains("g(x)"), "got: {}", rust_code);
+    assert!(!rust_code.contains(".get("), "got: {}", rust_code);
+}
+
+#[test]
+fn test_codegen_lookup_borrows_key() {
+    let rust_code = generate("Age[ages: Map[String, Int32], name: String] := Lookup[ages, name]");
+    assert!(
+        rust_code.contains("fn age(ages: std::collections::HashMap<String, i32>, name: String) -> Option<i32> {\n    ages.get(&name).cloned()\n}"),
+        "got: {}",
+        rust_code
+    );
+}
+
+#[test]
+fn test_run_indexing() {
+    let rust_code = generate(
+        "Let[xs, [10, 20, 30]]\n\
+         Print[xs[1]]\n\
+         Print[At[xs, 3]]\n\
+         Let[ages, {\"ann\": 31, \"bob\": 42}]\n\
+         Print[Lookup[ages, \"bob\"]]\n\
+         Print[Lookup[ages, \"cy\"]]",
+    );
+    assert_eq!(compile_and_run(&rust_code, "run"), "Some(20)\nNone\nSome(42)\nNone\n");
+}
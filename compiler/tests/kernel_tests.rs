@@ -0,0 +1,126 @@
+mod common;
+
+use common::{compile_and_run, generate, parse};
+use w::ast::{Expression, Type};
+use w::no_std::check_no_std;
+use w::parser::Parser;
+use w::profile::check_profile;
+use w::rust_codegen::Profile;
+use w::type_inference::{TypeError, TypeInference};
+
+const SAXPY: &str = "@[kernel] Saxpy[a: Float64, xs: List[Float64], ys: List[Float64]] := a * xs + ys\n";
+
+fn infer(input: &str) -> Result<Type, TypeError> {
+    TypeInference::new().infer_expression(&parse(input))
+}
+
+// ============================================================================
+// Parser Tests
+// ============================================================================
+
+#[test]
+fn test_parse_kernel() {
+    match parse(SAXPY) {
+        Expression::Kernel { definition } => {
+            assert!(matches!(*definition, Expression::FunctionDefinition { ref name, .. } if name == "Saxpy"), "got: {:?}", definition);
+        }
+        other => panic!("expected Kernel, got: {:?}", other),
+    }
+}
+
+#[test]
+fn test_unknown_annotation_is_rejected() {
+    let mut parser = Parser::new("@[inline] F[x: Int32] := x".to_string());
+    assert_eq!(parser.parse(), None);
+}
+
+#[test]
+fn test_kernel_needs_definition() {
+    let mut parser = Parser::new("@[kernel] F[1]".to_string());
+    assert_eq!(parser.parse(), None);
+}
+
+// ============================================================================
+// Type Inference Tests
+// ============================================================================
+
+#[test]
+fn test_kernel_returns_list_of_body_type() {
+    assert_eq!(
+        infer(SAXPY),
+        Ok(Type::Function(
+            vec![Type::Float64, Type::List(Box::new(Type::Float64)), Type::List(Box::new(Type::Float64))],
+            Box::new(Type::List(Box::new(Type::Float64)))
+        ))
+    );
+}
+
+#[test]
+fn test_kernel_needs_a_list() {
+    let error = infer("@[kernel] Twice[n: Int32] := n * 2").unwrap_err();
+    assert_eq!(error.code(), "E0023");
+    assert_eq!(error.to_string(), "Kernel Twice has no List or Array parameter to compute over");
+}
+
+#[test]
+fn test_kernel_parameters_are_numeric() {
+    let error = infer("@[kernel] F[names: List[String]] := 1").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Kernel F takes names: List[String], but kernel parameters are numbers or Lists and Arrays of numbers"
+    );
+}
+
+#[test]
+fn test_kernel_body_is_arithmetic() {
+    let error = infer("@[kernel] Shout[xs: List[Int32]] := Print[xs]").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Kernel Shout uses Print, but a kernel body is limited to arithmetic, comparisons, If, Convert and Math functions"
+    );
+    assert_eq!(infer("@[kernel] Root[xs: List[Float64]] := Math.Sqrt[xs]").map(|_| ()), Ok(()));
+}
+
+#[test]
+fn test_kernel_computes_numbers() {
+    assert!(matches!(infer("@[kernel] Positive[xs: List[Int32]] := xs > 0"), Err(TypeError::InvalidKernel { .. })));
+}
+
+#[test]
+fn test_kernels_need_threads() {
+    assert_eq!(check_no_std(&parse(SAXPY), &[]).unwrap_err().feature, "A kernel");
+    let features: Vec<String> = check_profile(&parse("@[kernel] F[xs: Array[Int32, 4]] := xs + 1"), Profile::Embedded, &[])
+        .into_iter()
+        .map(|violation| violation.feature)
+        .collect();
+    assert_eq!(features, vec!["A kernel"]);
+}
+
+// ============================================================================
+// Codegen Tests
+// ============================================================================
+
+#[test]
+fn test_kernel_signature() {
+    let rust_code = generate(SAXPY);
+    assert!(rust_code.contains("fn saxpy(a: f64, xs: Vec<f64>, ys: Vec<f64>) -> Vec<f64>"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_kernel_runs_chunks_on_scoped_threads() {
+    let rust_code = generate(SAXPY);
+    assert!(rust_code.contains("std::thread::scope"), "got: {}", rust_code);
+    assert!(rust_code.contains(".chunks_mut("), "got: {}", rust_code);
+    assert!(rust_code.contains("assert_eq!(ys.len(), __w_0_len, \"saxpy: kernel inputs differ in length\");"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_kernel_runs() {
+    let input = format!(
+        "{}@[kernel] Clamp[xs: List[Int32], limit: Int32] := If[xs > limit, limit, xs]\n\
+         Print[Saxpy[2.0, [1.0, 2.0, 3.0], [10.0, 20.0, 30.0]]]\n\
+         Print[Clamp[[1, 5, 9, 3], 4]]",
+        SAXPY
+    );
+    assert_eq!(compile_and_run(&generate(&input), "runs"), "[12.0, 24.0, 36.0]\n[1, 4, 4, 3]\n");
+}
@@ -1,5 +1,5 @@
 use w::lexer::{Lexer, Token};
-use w::ast::Expression;
+use w::ast::{Expression, Radix};
 use w::parser::Parser;
 use w::rust_codegen::RustCodeGenerator;
 
@@ -11,32 +11,32 @@ use w::rust_codegen::RustCodeGenerator;
 fn test_arrow_token() {
     let mut lexer = Lexer::new("x -> x + 1".to_string());
 
-    assert_eq!(lexer.next_token(), Some(Token::Identifier("x".to_string())));
-    assert_eq!(lexer.next_token(), Some(Token::Arrow));
-    assert_eq!(lexer.next_token(), Some(Token::Identifier("x".to_string())));
-    assert_eq!(lexer.next_token(), Some(Token::Plus));
-    assert_eq!(lexer.next_token(), Some(Token::Number(1)));
-    assert_eq!(lexer.next_token(), None);
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Identifier("x".to_string())));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Arrow));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Identifier("x".to_string())));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Plus));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Number(1, Radix::Decimal)));
+    assert_eq!(lexer.next_token().unwrap(), None);
 }
 
 #[test]
 fn test_arrow_token_no_spaces() {
     let mut lexer = Lexer::new("x->y".to_string());
 
-    assert_eq!(lexer.next_token(), Some(Token::Identifier("x".to_string())));
-    assert_eq!(lexer.next_token(), Some(Token::Arrow));
-    assert_eq!(lexer.next_token(), Some(Token::Identifier("y".to_string())));
-    assert_eq!(lexer.next_token(), None);
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Identifier("x".to_string())));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Arrow));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Identifier("y".to_string())));
+    assert_eq!(lexer.next_token().unwrap(), None);
 }
 
 #[test]
 fn test_minus_not_confused_with_arrow() {
     let mut lexer = Lexer::new("x - y".to_string());
 
-    assert_eq!(lexer.next_token(), Some(Token::Identifier("x".to_string())));
-    assert_eq!(lexer.next_token(), Some(Token::Minus));
-    assert_eq!(lexer.next_token(), Some(Token::Identifier("y".to_string())));
-    assert_eq!(lexer.next_token(), None);
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Identifier("x".to_string())));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Minus));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Identifier("y".to_string())));
+    assert_eq!(lexer.next_token().unwrap(), None);
 }
 
 // ============================================
@@ -73,7 +73,7 @@ fn test_parse_arrow_lambda_comparison() {
                 Expression::BinaryOp { left, operator, right } => {
                     assert_eq!(*left, Expression::Identifier("x".to_string()));
                     assert_eq!(operator, w::ast::Operator::GreaterThan);
-                    assert_eq!(*right, Expression::Number(100));
+                    assert_eq!(*right, Expression::Number(100, Radix::Decimal));
                 }
                 _ => panic!("Expected BinaryOp body, got {:?}", body),
             }
@@ -0,0 +1,111 @@
+use w::learn::{check_answer, run, Feedback, LESSONS};
+use w::type_inference::TypeError;
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Run the tutorial on scripted input, returning the lessons solved and the transcript
+fn session(input: &str) -> (usize, String) {
+    let mut output = Vec::new();
+    let solved = run(input.as_bytes(), &mut output).unwrap();
+    (solved, String::from_utf8(output).unwrap())
+}
+
+// ============================================================================
+// Answer Checking Tests
+// ============================================================================
+
+#[test]
+fn test_every_solution_is_accepted() {
+    for lesson in LESSONS {
+        assert_eq!(check_answer(lesson, lesson.solution), Ok(()), "lesson {}", lesson.title);
+    }
+}
+
+#[test]
+fn test_wrong_output_is_reported() {
+    assert_eq!(
+        check_answer(&LESSONS[1], "Print[6 * (7 + 3)]"),
+        Err(Feedback::WrongOutput { expected: "45\n".to_string(), actual: "60\n".to_string() })
+    );
+}
+
+#[test]
+fn test_parse_errors_carry_codes() {
+    let feedback = check_answer(&LESSONS[0], "Print[\"Hello, W!\"").unwrap_err();
+    assert_eq!(feedback.code(), Some("E0103"));
+    let feedback = check_answer(&LESSONS[0], "Print[\"Hello, W!\" # comment]").unwrap_err();
+    assert_eq!(feedback.code(), Some("E0100"));
+    assert!(feedback.to_string().starts_with("error[E0100]: 1:"), "got: {}", feedback);
+}
+
+#[test]
+fn test_type_errors_are_reported_before_compiling() {
+    let feedback = check_answer(&LESSONS[2], "Double[x: Int32] := x * \"2\"\nPrint[Double[21]]").unwrap_err();
+    assert!(matches!(&feedback, Feedback::Type(located) if matches!(located.error, TypeError::TypeMismatch { .. })), "got: {:?}", feedback);
+    assert_eq!(feedback.code(), Some("E0001"));
+}
+
+// ============================================================================
+// Session Tests
+// ============================================================================
+
+#[test]
+fn test_session_solves_lessons_in_order() {
+    let (solved, transcript) = session("Print[\"Hello, W!\"]\n\nPrint[6 * 7 + 3]\n\nquit\n");
+    assert_eq!(solved, 2);
+    assert!(transcript.contains("Lesson 1/5: Print"), "got: {}", transcript);
+    assert!(transcript.contains("Lesson 3/5: Functions"), "got: {}", transcript);
+    assert!(!transcript.contains("Lesson 4/5"), "got: {}", transcript);
+    assert!(transcript.ends_with("You solved 2 of 5 lessons.\n"), "got: {}", transcript);
+}
+
+#[test]
+fn test_session_hint_and_skip() {
+    let (solved, transcript) = session("hint\nskip\nquit\n");
+    assert_eq!(solved, 0);
+    assert!(transcript.contains(&format!("Hint: {}", LESSONS[0].hint)), "got: {}", transcript);
+    assert!(transcript.contains(&format!("Skipped. One solution is:\n{}", LESSONS[0].solution)), "got: {}", transcript);
+    assert!(transcript.contains("Lesson 2/5"), "got: {}", transcript);
+}
+
+#[test]
+fn test_session_explains_error_codes() {
+    let (_, transcript) = session("Print[\"Hello, W!\"\n\nquit\n");
+    assert!(transcript.contains("error[E0103]"), "got: {}", transcript);
+    assert!(transcript.contains("run `w --explain E0103` for more"), "got: {}", transcript);
+    assert!(transcript.contains(&format!("Hint: {}", LESSONS[0].hint)), "got: {}", transcript);
+}
+
+#[test]
+fn test_session_multi_line_answer() {
+    let (solved, _) = session("skip\nskip\nDouble[x: Int32] := x * 2\nPrint[Double[21]]\n\nquit\n");
+    assert_eq!(solved, 1);
+}
+
+#[test]
+fn test_session_ends_at_end_of_input() {
+    let (solved, transcript) = session("");
+    assert_eq!(solved, 0);
+    assert!(transcript.ends_with("You solved 0 of 5 lessons.\n"), "got: {}", transcript);
+}
+
+// ============================================================================
+// Command-Line Tests
+// ============================================================================
+
+#[test]
+fn test_cli_learn() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_w"))
+        .arg("learn")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(b"Print[\"Hello, W!\"]\n\nquit\n").unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("Correct!"), "got: {}", stdout);
+    assert!(stdout.contains("You solved 1 of 5 lessons."), "got: {}", stdout);
+}
@@ -0,0 +1,183 @@
+mod common;
+
+use common::{compile_and_run, parse, try_generate};
+use w::ast::{Expression, Pattern};
+use w::parser::Parser;
+use w::type_inference::{TypeError, TypeInference};
+
+fn check(input: &str) -> Result<(), TypeError> {
+    let expressions = match parse(input) {
+        Expression::Program(expressions) => expressions,
+        other => vec![other],
+    };
+    TypeInference::new().check_program(&expressions)
+}
+
+// ============================================================================
+// Parser Tests
+// ============================================================================
+
+#[test]
+fn test_parse_let_pattern() {
+    match parse("Let[(a, b), (1, 2)]") {
+        Expression::LetPattern { pattern, fallback, .. } => {
+            assert_eq!(pattern, Pattern::Tuple(vec![
+                Pattern::Variable("a".to_string()),
+                Pattern::Variable("b".to_string()),
+            ]));
+            assert!(fallback.is_none());
+        }
+        other => panic!("Expected LetPattern, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_let_else() {
+    match parse("LetElse[Some[x], Some[1], Print[\"none\"]]") {
+        Expression::LetPattern { fallback, .. } => assert!(fallback.is_some()),
+        other => panic!("Expected LetPattern, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_let_requires_pattern_and_value() {
+    let mut parser = Parser::new("Let[x]".to_string());
+    assert!(parser.parse().is_none());
+}
+
+// ============================================================================
+// Refutability Tests
+// ============================================================================
+
+#[test]
+fn test_irrefutable_patterns() {
+    for source in ["Let[x, 1]", "Let[_, 1]", "Let[(a, (b, _)), (1, (2, 3))]", "Let[[..], [1]]"] {
+        match parse(source) {
+            Expression::LetPattern { pattern, .. } => {
+                assert!(pattern.is_irrefutable(), "{} should be irrefutable", source);
+            }
+            other => panic!("Expected LetPattern, got {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn test_refutable_patterns() {
+    for source in ["Let[Some[x], v]", "Let[None, v]", "Let[Ok[x], v]", "Let[(1, x), v]", "Let[[a, b], v]"] {
+        match parse(source) {
+            Expression::LetPattern { pattern, .. } => {
+                assert!(!pattern.is_irrefutable(), "{} should be refutable", source);
+            }
+            other => panic!("Expected LetPattern, got {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn test_struct_pattern_refutability_follows_fields() {
+    match parse("Let[Point[x, Some[y]], p]") {
+        Expression::LetPattern { pattern, .. } => assert!(!pattern.is_irrefutable()),
+        other => panic!("Expected LetPattern, got {:?}", other),
+    }
+    match parse("Let[Point[x, ..], p]") {
+        Expression::LetPattern { pattern, .. } => assert!(pattern.is_irrefutable()),
+        other => panic!("Expected LetPattern, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_pattern_display_uses_w_syntax() {
+    match parse("LetElse[Some[(x, _)], v, Print[1]]") {
+        Expression::LetPattern { pattern, .. } => assert_eq!(pattern.to_string(), "Some[(x, _)]"),
+        other => panic!("Expected LetPattern, got {:?}", other),
+    }
+}
+
+// ============================================================================
+// Type Inference Tests
+// ============================================================================
+
+#[test]
+fn test_let_binds_for_following_statements() {
+    assert!(check("Let[(a, b), (1, \"s\")]\nPrint[a + 1]").is_ok());
+}
+
+#[test]
+fn test_let_bound_types_are_checked() {
+    let result = check("Let[(a, b), (1, \"s\")]\n!b");
+    assert!(matches!(result, Err(TypeError::TypeMismatch { .. })), "got: {:?}", result);
+}
+
+#[test]
+fn test_refutable_let_is_rejected() {
+    let result = check("Let[Some[x], Some[1]]");
+    assert_eq!(result, Err(TypeError::RefutablePattern("Some[x]".to_string())));
+
+    let message = result.unwrap_err().to_string();
+    assert!(message.contains("LetElse[Some[x], value, fallback]"), "got: {}", message);
+}
+
+#[test]
+fn test_let_else_accepts_refutable_pattern() {
+    assert!(check("LetElse[Some[x], Some[1], Print[\"none\"]]\nPrint[x * 2]").is_ok());
+}
+
+#[test]
+fn test_let_of_unfixed_type_is_rejected() {
+    let result = check("Let[o, None]");
+    let message = match result {
+        Err(TypeError::CannotInfer(message)) => message,
+        other => panic!("Expected CannotInfer, got {:?}", other),
+    };
+    assert!(message.contains("o, bound to Option[_]"), "got: {}", message);
+    // A later statement can fix it
+    assert!(check("Let[o, None]\nPrint[o == Some[2]]").is_ok());
+}
+
+#[test]
+fn test_let_pattern_must_match_value_type() {
+    let result = check("Let[(a, b), (1, 2, 3)]");
+    assert!(result.is_err(), "got: {:?}", result);
+}
+
+// ============================================================================
+// Codegen Tests
+// ============================================================================
+
+#[test]
+fn test_codegen_irrefutable_let() {
+    let rust_code = try_generate("Let[(a, _), (1, 2)]\nPrint[a]").unwrap();
+    assert!(rust_code.contains("let (a, _) = (1, 2);"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_codegen_let_else() {
+    let rust_code = try_generate("LetElse[Some[x], Some[1], Print[\"none\"]]\nPrint[x]").unwrap();
+    assert!(rust_code.contains("let Some(x) = Some(1) else {"), "got: {}", rust_code);
+    assert!(rust_code.contains("return;"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_codegen_annotates_let_of_none() {
+    let rust_code = try_generate("Let[o, None]\nPrint[o == Some[2]]").unwrap();
+    assert!(rust_code.contains("let o: Option<i32> = None;"), "got: {}", rust_code);
+    assert_eq!(compile_and_run(&rust_code, "let_of_none"), "false\n");
+}
+
+#[test]
+fn test_codegen_rejects_refutable_let() {
+    assert!(try_generate("Let[Some[x], Some[1]]\nPrint[x]").is_err());
+}
+
+#[test]
+fn test_codegen_rejects_string_literal_in_let_else() {
+    assert!(try_generate("LetElse[(\"a\", x), (\"a\", 1), Print[0]]\nPrint[x]").is_err());
+}
+
+#[test]
+fn test_let_else_runs_fallback_and_exits() {
+    let rust_code = try_generate(
+        "LetElse[Some[x], Some[20], Print[\"none\"]]\nPrint[x + 1]\nLetElse[(1, y), (2, 5), Print[\"fallback\"]]\nPrint[y]",
+    ).unwrap();
+    assert_eq!(compile_and_run(&rust_code, "let_else"), "21\nfallback\n");
+}
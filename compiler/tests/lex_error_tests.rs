@@ -0,0 +1,129 @@
+use w::lexer::{LexError, Lexer, Span, Token};
+use w::parser::Parser;
+
+fn first_error(input: &str) -> LexError {
+    let mut lexer = Lexer::new(input.to_string());
+    loop {
+        match lexer.next_token() {
+            Ok(Some(_)) => continue,
+            Ok(None) => panic!("expected a lex error in {:?}", input),
+            Err(error) => return error,
+        }
+    }
+}
+
+// ============================================================================
+// LexError Variant Tests
+// ============================================================================
+
+#[test]
+fn test_unexpected_character() {
+    assert_eq!(first_error("x # y"), LexError::UnexpectedChar {
+        ch: '#',
+        span: Span { start: 2, end: 3, line: 1, column: 3 },
+    });
+}
+
+#[test]
+fn test_lone_operator_characters_are_unexpected() {
+    for (input, ch) in [("a ~ b", '~'), ("a $ b", '$'), ("a ` b", '`')] {
+        match first_error(input) {
+            LexError::UnexpectedChar { ch: found, .. } => assert_eq!(found, ch, "in {:?}", input),
+            other => panic!("expected UnexpectedChar for {:?}, got: {:?}", input, other),
+        }
+    }
+}
+
+#[test]
+fn test_unterminated_string_points_at_opening_quote() {
+    assert_eq!(first_error("Print[\n  \"hello]"), LexError::UnterminatedString {
+        span: Span { start: 9, end: 10, line: 2, column: 3 },
+    });
+}
+
+#[test]
+fn test_trailing_backslash_is_unterminated() {
+    assert!(matches!(first_error(r#""abc\"#), LexError::UnterminatedString { .. }));
+}
+
+#[test]
+fn test_invalid_escape() {
+    assert_eq!(first_error(r#""a\qb""#), LexError::InvalidEscape {
+        escape: 'q',
+        span: Span { start: 2, end: 4, line: 1, column: 3 },
+    });
+}
+
+#[test]
+fn test_invalid_number_literals() {
+    let invalid = |input: &str| match first_error(input) {
+        LexError::InvalidNumber { reason, span } => (reason, span.start, span.end),
+        other => panic!("expected InvalidNumber for {:?}, got: {:?}", input, other),
+    };
+    assert_eq!(invalid("x + 0b102"), ("2 is not a binary digit".to_string(), 4, 9));
+    assert_eq!(invalid("0o78"), ("8 is not an octal digit".to_string(), 0, 4));
+    assert_eq!(invalid("[0x]"), ("0x has no digits after it".to_string(), 1, 3));
+    assert_eq!(invalid("1__0"), ("a _ separator must be between two digits".to_string(), 0, 4));
+    assert_eq!(invalid("1_ _"), ("a _ separator must be between two digits".to_string(), 0, 2));
+    assert_eq!(invalid("0x_1"), ("a _ separator must be between two digits".to_string(), 0, 4));
+    assert_eq!(
+        invalid("0x8000000000000000"),
+        ("0x8000000000000000 is larger than the largest Int64, 9223372036854775807".to_string(), 0, 18)
+    );
+    assert_eq!(invalid("99999999999999999999").0, "99999999999999999999 is larger than the largest Int64, 9223372036854775807");
+}
+
+#[test]
+fn test_lexing_continues_after_valid_tokens() {
+    let mut lexer = Lexer::new("x #".to_string());
+    assert_eq!(lexer.next_token(), Ok(Some(Token::Identifier("x".to_string()))));
+    assert!(lexer.next_token().is_err());
+}
+
+// ============================================================================
+// Error Message Tests
+// ============================================================================
+
+#[test]
+fn test_error_messages_include_location() {
+    assert_eq!(first_error("x # y").to_string(), "1:3: unexpected character '#'");
+    assert_eq!(
+        first_error("\"open").to_string(),
+        "1:1: unterminated string literal (missing closing \")"
+    );
+    assert_eq!(
+        first_error(r#""\q""#).to_string(),
+        r#"1:2: invalid escape sequence \q (expected one of \" \\ \n \t \r)"#
+    );
+    assert_eq!(first_error("\n 0b12").to_string(), "2:2: invalid number literal: 2 is not a binary digit");
+}
+
+// ============================================================================
+// Parser Integration Tests
+// ============================================================================
+
+#[test]
+fn test_parser_exposes_lex_error() {
+    let mut parser = Parser::new("Print[1]\nPrint[2 ; 3]".to_string());
+
+    assert!(parser.parse().is_none());
+    let error = parser.lex_error().expect("expected a lex error");
+    assert!(matches!(error, LexError::UnexpectedChar { ch: ';', .. }), "got: {:?}", error);
+    assert_eq!(parser.current_span(), error.span());
+}
+
+#[test]
+fn test_parser_without_lex_error() {
+    let mut parser = Parser::new("Print[1]".to_string());
+
+    assert!(parser.parse().is_some());
+    assert!(parser.lex_error().is_none());
+}
+
+#[test]
+fn test_lex_error_between_statements_fails_the_parse() {
+    let mut parser = Parser::new("Print[1]\n#\nPrint[2]".to_string());
+
+    assert!(parser.parse().is_none(), "a truncated program must not be returned");
+    assert!(parser.lex_error().is_some());
+}
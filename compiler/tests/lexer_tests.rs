@@ -1,32 +1,157 @@
+use w::ast::Radix;
 use w::lexer::{Lexer, Token};
 
 #[test]
 fn test_function_call() {
     let mut lexer = Lexer::new("Print[123, hello]".to_string());
     
-    assert_eq!(lexer.next_token(), Some(Token::Identifier("Print".to_string())));
-    assert_eq!(lexer.next_token(), Some(Token::LeftBracket));
-    assert_eq!(lexer.next_token(), Some(Token::Number(123)));
-    assert_eq!(lexer.next_token(), Some(Token::Comma));
-    assert_eq!(lexer.next_token(), Some(Token::Identifier("hello".to_string())));
-    assert_eq!(lexer.next_token(), Some(Token::RightBracket));
-    assert_eq!(lexer.next_token(), None);
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Identifier("Print".to_string())));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::LeftBracket));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Number(123, Radix::Decimal)));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Comma));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Identifier("hello".to_string())));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::RightBracket));
+    assert_eq!(lexer.next_token().unwrap(), None);
 }
 
 #[test]
 fn test_nested_function_calls() {
     let mut lexer = Lexer::new("Add[Multiply[2, 3], 4]".to_string());
     
-    assert_eq!(lexer.next_token(), Some(Token::Identifier("Add".to_string())));
-    assert_eq!(lexer.next_token(), Some(Token::LeftBracket));
-    assert_eq!(lexer.next_token(), Some(Token::Identifier("Multiply".to_string())));
-    assert_eq!(lexer.next_token(), Some(Token::LeftBracket));
-    assert_eq!(lexer.next_token(), Some(Token::Number(2)));
-    assert_eq!(lexer.next_token(), Some(Token::Comma));
-    assert_eq!(lexer.next_token(), Some(Token::Number(3)));
-    assert_eq!(lexer.next_token(), Some(Token::RightBracket));
-    assert_eq!(lexer.next_token(), Some(Token::Comma));
-    assert_eq!(lexer.next_token(), Some(Token::Number(4)));
-    assert_eq!(lexer.next_token(), Some(Token::RightBracket));
-    assert_eq!(lexer.next_token(), None);
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Identifier("Add".to_string())));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::LeftBracket));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Identifier("Multiply".to_string())));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::LeftBracket));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Number(2, Radix::Decimal)));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Comma));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Number(3, Radix::Decimal)));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::RightBracket));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Comma));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Number(4, Radix::Decimal)));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::RightBracket));
+    assert_eq!(lexer.next_token().unwrap(), None);
+}
+
+#[test]
+fn test_prefixed_integer_literals() {
+    let mut lexer = Lexer::new("0xFF 0o755 0b1010 0XfF 0B1".to_string());
+
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Number(255, Radix::Hexadecimal)));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Number(0o755, Radix::Octal)));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Number(10, Radix::Binary)));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Number(255, Radix::Hexadecimal)));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Number(1, Radix::Binary)));
+    assert_eq!(lexer.next_token().unwrap(), None);
+}
+
+#[test]
+fn test_literals_beyond_int32_keep_their_value() {
+    let mut lexer = Lexer::new("0xFFFFFFFF 9223372036854775807".to_string());
+
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Number(0xFFFF_FFFF, Radix::Hexadecimal)));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Number(i64::MAX, Radix::Decimal)));
+}
+
+#[test]
+fn test_prefixed_literals_in_generated_rust() {
+    use w::parser::Parser;
+    use w::rust_codegen::RustCodeGenerator;
+
+    let mut parser = Parser::new("Mask[x: Int32] := x * 0xFF + 0b10".to_string());
+    let expr = parser.parse().unwrap();
+    let mut codegen = RustCodeGenerator::new();
+    let rust_code = codegen.generate(&expr).unwrap();
+
+    assert!(rust_code.contains("((x * 0xFF) + 0b10)"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_literals_beyond_int32_generate_suffix() {
+    use w::parser::Parser;
+    use w::rust_codegen::RustCodeGenerator;
+
+    let mut parser = Parser::new("Let[mask, 0xFFFFFFFF]\nWiden[x: UInt64] := x\nPrint[Widen[0o77777777777]]".to_string());
+    let expr = parser.parse().unwrap();
+    let mut codegen = RustCodeGenerator::new();
+    let rust_code = codegen.generate(&expr).unwrap();
+
+    assert!(rust_code.contains("let mask = 0xFFFFFFFFi64;"), "got: {}", rust_code);
+    assert!(rust_code.contains("widen(0o77777777777u64)"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_numeric_separators_are_stripped() {
+    let mut lexer = Lexer::new("1_000_000 0xFF_FF 0b1010_1010".to_string());
+
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Number(1_000_000, Radix::Decimal)));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Number(0xFFFF, Radix::Hexadecimal)));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Number(0b1010_1010, Radix::Binary)));
+    assert_eq!(lexer.next_token().unwrap(), None);
+}
+
+#[test]
+fn test_float_literals_with_separators() {
+    let mut lexer = Lexer::new("2.5 1_000.000_5 2.0".to_string());
+
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Float(2.5)));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Float(1000.0005)));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Float(2.0)));
+    assert_eq!(lexer.next_token().unwrap(), None);
+}
+
+#[test]
+fn test_float_literal_codegen_keeps_decimal_point() {
+    use w::parser::Parser;
+    use w::rust_codegen::RustCodeGenerator;
+
+    let mut parser = Parser::new("Scale[x: Float64] := x * 2.0 + 1_000.5".to_string());
+    let expr = parser.parse().unwrap();
+    let mut codegen = RustCodeGenerator::new();
+    let rust_code = codegen.generate(&expr).unwrap();
+
+    assert!(rust_code.contains("((x * 2.0) + 1000.5)"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_scientific_notation_floats() {
+    let mut lexer = Lexer::new("1.5e10 2E-3 6e+2 1_0e1_0".to_string());
+
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Float(1.5e10)));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Float(2e-3)));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Float(600.0)));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Float(1e11)));
+    assert_eq!(lexer.next_token().unwrap(), None);
+}
+
+#[test]
+fn test_exponent_marker_without_digits_is_not_consumed() {
+    let mut lexer = Lexer::new("2e x".to_string());
+
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Number(2, Radix::Decimal)));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Identifier("e".to_string())));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Identifier("x".to_string())));
+}
+
+#[test]
+fn test_scientific_notation_codegen() {
+    use w::parser::Parser;
+    use w::rust_codegen::RustCodeGenerator;
+
+    let mut parser = Parser::new("Scale[x: Float64] := x * 1.5e10 + 2E-3".to_string());
+    let expr = parser.parse().unwrap();
+    let mut codegen = RustCodeGenerator::new();
+    let rust_code = codegen.generate(&expr).unwrap();
+
+    assert!(rust_code.contains("((x * 15000000000.0) + 0.002)"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_underscore_prefixed_name_is_identifier() {
+    let mut lexer = Lexer::new("{_index, _}".to_string());
+
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::LeftBrace));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Identifier("_index".to_string())));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Comma));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Underscore));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::RightBrace));
 }
@@ -0,0 +1,92 @@
+mod common;
+
+use common::compile_and_run;
+use w::codegen_utils::wrap_long_lines;
+use w::parser::Parser;
+use w::rust_codegen::RustCodeGenerator;
+
+const PIPELINE: &str = "Let[xs, [1, 2, 3, 4, 5, 6, 7, 8, 9, 10]]
+Print[Fold[Function[{acc, x}, acc + x], 0, Filter[Function[{x}, x > 2], Map[Function[{x}, x * x + 1], Map[Function[{x}, x * 3], xs]]]]]";
+
+fn generate_with_width(input: &str, width: usize) -> String {
+    let mut parser = Parser::new(input.to_string());
+    let expr = parser.parse().unwrap();
+    RustCodeGenerator::builder().max_width(width).build().generate(&expr).unwrap()
+}
+
+// ============================================================================
+// Wrapping Tests
+// ============================================================================
+
+#[test]
+fn test_short_lines_are_unchanged() {
+    let source = "fn main() {\n    println!(\"{}\", f(1, 2));\n}\n";
+    assert_eq!(wrap_long_lines(source, 40, 4), source);
+}
+
+#[test]
+fn test_method_chain_gets_one_call_per_line() {
+    let wrapped = wrap_long_lines("    let v = xs.into_iter().map(|x| (x * 3)).collect::<Vec<_>>();", 40, 4);
+    assert_eq!(
+        wrapped,
+        "    let v = xs.into_iter()\n        .map(|x| (x * 3))\n        .collect::<Vec<_>>();"
+    );
+}
+
+#[test]
+fn test_arguments_get_one_per_line() {
+    let wrapped = wrap_long_lines("    total(first_argument, second_argument, third_argument);", 40, 4);
+    assert_eq!(
+        wrapped,
+        "    total(\n        first_argument,\n        second_argument,\n        third_argument\n    );"
+    );
+}
+
+#[test]
+fn test_literals_are_never_split() {
+    let line = "    f(\"a, b, (c)\", 'x', ',', r#\"d, \"e\"\"#, \"\\\", f\", g(1, 2));";
+    let wrapped = wrap_long_lines(line, 20, 4);
+
+    assert!(wrapped.contains("\n        \"a, b, (c)\",\n"), "got: {}", wrapped);
+    assert!(wrapped.contains("\n        r#\"d, \"e\"\"#,\n"), "got: {}", wrapped);
+    assert!(wrapped.contains("\n        \"\\\", f\",\n"), "got: {}", wrapped);
+}
+
+#[test]
+fn test_generic_arguments_are_not_split() {
+    let wrapped = wrap_long_lines("fn f(map: HashMap<String, i32>, set: HashSet<i64>) {", 30, 4);
+    assert!(wrapped.contains("\n    map: HashMap<String, i32>,\n"), "got: {}", wrapped);
+}
+
+#[test]
+fn test_comments_are_left_alone() {
+    let line = "// Source: a/very/long/path/to/the/input/file.w (fnv1a64: 0123456789abcdef)";
+    assert_eq!(wrap_long_lines(line, 20, 4), line);
+}
+
+// ============================================================================
+// Codegen Tests
+// ============================================================================
+
+#[test]
+fn test_no_wrapping_by_default() {
+    let mut parser = Parser::new(PIPELINE.to_string());
+    let expr = parser.parse().unwrap();
+    let rust_code = RustCodeGenerator::new().generate(&expr).unwrap();
+
+    assert!(rust_code.lines().any(|line| line.len() > 200), "got: {}", rust_code);
+}
+
+#[test]
+fn test_pipeline_fits_max_width() {
+    let rust_code = generate_with_width(PIPELINE, 60);
+
+    assert!(rust_code.lines().all(|line| line.len() <= 60), "got: {}", rust_code);
+    assert!(rust_code.contains("        xs.into_iter()\n            .map(|x| (x * 3))\n"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_wrapped_pipeline_runs() {
+    let rust_code = generate_with_width(PIPELINE, 30);
+    assert_eq!(compile_and_run(&rust_code, "pipeline").trim(), "3475");
+}
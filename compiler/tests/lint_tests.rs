@@ -0,0 +1,386 @@
+use w::driver::{compile, CompilerCallbacks};
+use w::lints::{lint_program, Lint, LintKind};
+use w::parser::Parser;
+use w::resolve::BindingKind;
+use w::rust_codegen::CodegenOptions;
+
+use std::process::Command;
+
+fn lints(input: &str) -> Vec<Lint> {
+    let mut parser = Parser::new(input.to_string());
+    let program = parser.parse().unwrap();
+    lint_program(&program, parser.expression_spans())
+}
+
+fn kinds(input: &str) -> Vec<LintKind> {
+    lints(input).into_iter().map(|lint| lint.kind).collect()
+}
+
+// ============================================================================
+// Division by Zero
+// ============================================================================
+
+#[test]
+fn test_literal_division_by_zero() {
+    assert_eq!(kinds("Print[10 / 0]"), vec![LintKind::DivisionByZero]);
+}
+
+#[test]
+fn test_folded_division_by_zero() {
+    assert_eq!(kinds("Print[10 / (3 - 3)]"), vec![LintKind::DivisionByZero]);
+    assert_eq!(kinds("Let[n, 2 * 2 - 4]\nPrint[10 / n]"), vec![LintKind::DivisionByZero]);
+}
+
+#[test]
+fn test_nonzero_and_unknown_divisors_not_flagged() {
+    assert!(kinds("Print[10 / 2]").is_empty());
+    assert!(kinds("Half[n: Int32] := n / 2\nPrint[Half[3]]").is_empty());
+    assert!(kinds("Div[a: Int32, b: Int32] := a / b").is_empty());
+}
+
+#[test]
+fn test_parameter_shadows_binding() {
+    assert!(kinds("Let[n, 0]\nDiv[a: Int32, n: Int32] := a / n\nPrint[Div[4, 2]]").is_empty());
+}
+
+#[test]
+fn test_rebinding_forgets_value() {
+    assert!(kinds("Let[n, 0]\nLet[n, ReadLine[]]\nPrint[10 / n]").is_empty());
+}
+
+#[test]
+fn test_unreachable_branch_not_flagged() {
+    assert_eq!(kinds("Let[n, 0]\nPrint[If[n == 0, 0, 10 / n]]"), vec![LintKind::UnreachableBranch]);
+    assert_eq!(kinds("Let[n, 0]\nPrint[If[n != 0, 0, 10 / n]]"), vec![LintKind::DivisionByZero]);
+}
+
+// ============================================================================
+// Index Out of Bounds
+// ============================================================================
+
+#[test]
+fn test_index_past_list_literal() {
+    assert_eq!(
+        kinds("Print[At[[1, 2, 3], 3]]"),
+        vec![LintKind::IndexOutOfBounds { index: 3, length: 3 }]
+    );
+}
+
+#[test]
+fn test_index_through_binding() {
+    assert_eq!(
+        kinds("Let[xs, [1, 2]]\nPrint[xs[1 + 1]]"),
+        vec![LintKind::IndexOutOfBounds { index: 2, length: 2 }]
+    );
+    assert!(kinds("Let[xs, [1, 2]]\nPrint[xs[1]]").is_empty());
+}
+
+// ============================================================================
+// Propagating Literals
+// ============================================================================
+
+#[test]
+fn test_propagate_literal_none() {
+    assert_eq!(
+        kinds("Half[n: Int32] := Some[None? / 2]"),
+        vec![LintKind::AlwaysReturnsEarly { operator: "?", value: "None" }]
+    );
+}
+
+#[test]
+fn test_try_literal_err() {
+    assert_eq!(
+        kinds("Double[n: Int32] := Ok[Try[Err[\"todo\"]] * 2]"),
+        vec![LintKind::AlwaysReturnsEarly { operator: "Try", value: "Err" }]
+    );
+}
+
+// ============================================================================
+// Infinite Recursion
+// ============================================================================
+
+#[test]
+fn test_recursion_without_base_case() {
+    assert_eq!(kinds("Loop[n: Int32] := Loop[n]"), vec![LintKind::InfiniteRecursion]);
+    assert_eq!(kinds("Spin[n: Int32] := 1 + Spin[n - 1]"), vec![LintKind::InfiniteRecursion]);
+}
+
+#[test]
+fn test_recursion_in_every_branch() {
+    assert_eq!(
+        kinds("Both[n: Int32] := If[n > 0, Both[n - 1], Both[n + 1]]"),
+        vec![LintKind::InfiniteRecursion]
+    );
+}
+
+#[test]
+fn test_base_case_not_flagged() {
+    assert!(kinds("Fact[n: Int32] := Cond[[n == 0, 1], [n * Fact[n - 1]]]").is_empty());
+    assert!(kinds("Count[n: Int32] := Match[n, [0, 0], [_, Count[n - 1]]]").is_empty());
+    assert!(kinds("Defer[n: Int32] := Function[{x}, Defer[x]]").is_empty());
+}
+
+#[test]
+fn test_constant_branches_decide() {
+    assert_eq!(
+        kinds("Stuck[n: Int32] := Cond[[1 == 2, 0], [Stuck[n]]]"),
+        vec![LintKind::InfiniteRecursion]
+    );
+    assert_eq!(
+        kinds("Pick[n: Int32] := Match[3, [3, Pick[n]], [_, 0]]"),
+        vec![LintKind::InfiniteRecursion]
+    );
+    assert!(kinds("Pick[n: Int32] := Match[4, [3, Pick[n]], [_, 0]]").is_empty());
+}
+
+#[test]
+fn test_infinite_recursion_message() {
+    let lints = lints("Loop[n: Int32] := Loop[n]");
+    assert_eq!(lints[0].code(), "W0004");
+    assert_eq!(lints[0].to_string(), "Loop calls itself on every path, so it never returns and overflows the stack");
+}
+
+// ============================================================================
+// Unreachable Code
+// ============================================================================
+
+#[test]
+fn test_branches_after_always_true_condition() {
+    assert_eq!(
+        kinds("Print[Cond[[2 > 1, \"a\"], [3 > 1, \"b\"], Else[\"c\"]]]"),
+        vec![LintKind::UnreachableBranch]
+    );
+    assert!(kinds("Print[Cond[[1 > 2, \"a\"], [3 > 1, \"b\"]]]").is_empty());
+    assert!(kinds("Sign[n: Int32] := Cond[[n > 0, 1], [n < 0, 0 - 1], Else[0]]\nPrint[Sign[2]]").is_empty());
+}
+
+#[test]
+fn test_statements_after_exit() {
+    assert_eq!(kinds("Exit[1]\nPrint[\"done\"]"), vec![LintKind::UnreachableCode { after: "Exit" }]);
+    assert_eq!(
+        kinds("Let[x, Panic[\"stop\"]]\nPrint[x]"),
+        vec![LintKind::UnreachableCode { after: "Panic" }]
+    );
+    // Definitions after it are not code that runs
+    assert!(kinds("Print[1]\nExit[0]\nDouble[n: Int32] := n * 2").iter().all(|kind| !matches!(kind, LintKind::UnreachableCode { .. })));
+}
+
+#[test]
+fn test_block_statements_after_panic() {
+    assert_eq!(
+        kinds("G[n: Int32] := Block[Panic[\"x\"], n]\nPrint[G[1]]"),
+        vec![LintKind::UnreachableCode { after: "Panic" }]
+    );
+    assert_eq!(
+        kinds("G[n: Int32] := Block[Let[m, Exit[1]], Print[m], n]\nPrint[G[1]]"),
+        vec![LintKind::UnreachableCode { after: "Exit" }]
+    );
+    // Diverging as the block's value leaves nothing after it
+    assert!(kinds("G[n: Int32] := Block[Print[n], Panic[\"x\"]]\nPrint[G[1]]").is_empty());
+}
+
+#[test]
+fn test_arguments_after_panic() {
+    assert_eq!(
+        kinds("Print[Max[Panic[\"no\"], 2]]"),
+        vec![LintKind::UnreachableCode { after: "Panic" }]
+    );
+    assert_eq!(kinds("Print[Panic[\"no\"] + 1]"), vec![LintKind::UnreachableCode { after: "Panic" }]);
+    assert!(kinds("Print[If[true, 1, Panic[\"no\"]]]").iter().all(|kind| !matches!(kind, LintKind::UnreachableCode { .. })));
+    assert!(kinds("Half[n: Int32] := If[n < 0, Panic[\"negative\"], n / 2]\nPrint[Half[4]]").is_empty());
+}
+
+#[test]
+fn test_unreachable_code_message() {
+    let lints = lints("Stop[n: Int32] := Panic[\"no\"] * n\nPrint[Stop[1]]");
+    assert_eq!(lints.len(), 1);
+    assert_eq!(lints[0].code(), "W0009");
+    assert_eq!(lints[0].to_string(), "Code after Panic in Stop never runs, as Panic does not return");
+}
+
+// ============================================================================
+// Unused Names
+// ============================================================================
+
+#[test]
+fn test_unused_lambda_parameter() {
+    assert_eq!(
+        kinds("Print[Fold[Function[{acc, x}, acc + 1], 0, [1, 2]]]"),
+        vec![LintKind::UnusedParameter("x".to_string())]
+    );
+    assert!(kinds("Print[Fold[Function[{acc, x}, acc + x], 0, [1, 2]]]").is_empty());
+    assert!(kinds("Print[Fold[Function[{acc, _x}, acc + 1], 0, [1, 2]]]").is_empty());
+}
+
+#[test]
+fn test_unused_let_binding() {
+    assert_eq!(kinds("Let[total, 3]\nPrint[4]"), vec![LintKind::UnusedBinding("total".to_string())]);
+    assert!(kinds("Let[total, 3]\nPrint[total]").is_empty());
+    assert_eq!(kinds("Let[(a, b), (1, 2)]\nPrint[b]"), vec![LintKind::UnusedBinding("a".to_string())]);
+    assert!(kinds("Let[_total, 3]\nPrint[4]").is_empty());
+}
+
+#[test]
+fn test_unused_function() {
+    assert_eq!(kinds("Double[n: Int32] := n * 2\nPrint[4]"), vec![LintKind::UnusedFunction("Double".to_string())]);
+    assert!(kinds("Double[n: Int32] := n * 2\nPrint[Double[2]]").is_empty());
+    assert!(kinds("Double[n: Int32] := n * 2\nPrint[Map[Double, [1, 2]]]").is_empty());
+    // Calls from its own body don't count
+    assert_eq!(
+        kinds("Down[n: Int32] := If[n > 0, Down[n - 1], 0]\nPrint[1]"),
+        vec![LintKind::UnusedFunction("Down".to_string())]
+    );
+}
+
+#[test]
+fn test_definitions_only_file_exports_public_functions() {
+    assert!(kinds("Double[n: Int32] := n * 2").is_empty());
+    assert_eq!(
+        kinds("Private[Helper[n: Int32] := n + 1]\nDouble[n: Int32] := n * 2"),
+        vec![LintKind::UnusedFunction("Helper".to_string())]
+    );
+}
+
+#[test]
+fn test_unused_name_messages() {
+    let lints = lints("Print[Map[Function[{x}, 1], [1]]]\nLet[total, 3]\nDouble[n: Int32] := n * 2");
+    let messages: Vec<(String, Option<usize>)> = lints.iter()
+        .map(|lint| (format!("{}: {}", lint.code(), lint), lint.span.map(|span| span.line)))
+        .collect();
+    assert_eq!(messages, vec![
+        ("W0005: Lambda parameter x is never used; name it _x if that is intended".to_string(), Some(1)),
+        ("W0006: total is bound by Let but never used; name it _total if that is intended".to_string(), Some(2)),
+        ("W0007: Function Double is never called".to_string(), Some(3)),
+    ]);
+}
+
+// ============================================================================
+// Shadowing
+// ============================================================================
+
+fn shadowing(name: &str, inner: BindingKind, outer: BindingKind, outer_line: Option<usize>) -> (String, BindingKind, BindingKind, Option<usize>) {
+    (name.to_string(), inner, outer, outer_line)
+}
+
+/// Each shadowing lint's name, binding kinds and the line of the outer binding
+fn shadowings(input: &str) -> Vec<(String, BindingKind, BindingKind, Option<usize>)> {
+    lints(input).into_iter()
+        .filter_map(|lint| match lint.kind {
+            LintKind::Shadowing { name, inner, outer, outer_span } => Some((name, inner, outer, outer_span.map(|span| span.line))),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn test_lambda_parameter_shadows_let() {
+    assert_eq!(
+        shadowings("Let[total, 10]\nPrint[Map[Function[{total}, total * 2], [total]]]"),
+        vec![shadowing("total", BindingKind::LambdaParameter, BindingKind::Let, Some(1))]
+    );
+}
+
+#[test]
+fn test_pattern_shadows_parameter() {
+    let source = "Unwrap[x: Option[Int32]] := Match[x, [Some[x], x], [None, 0]]\nPrint[Unwrap[Some[1]]]";
+    assert_eq!(shadowings(source), vec![shadowing("x", BindingKind::Pattern, BindingKind::Parameter, None)]);
+    assert_eq!(lints(source)[0].function.as_deref(), Some("Unwrap"));
+}
+
+#[test]
+fn test_nested_bindings_shadow() {
+    let source = "Print[Map[Function[{n}, Map[Function[{n}, n], [n]]], [[1]]]]\nFor[n, [1], Print[IfLet[Some[n], Some[n], n, 0]]]";
+    assert_eq!(shadowings(source), vec![
+        shadowing("n", BindingKind::LambdaParameter, BindingKind::LambdaParameter, None),
+        shadowing("n", BindingKind::Pattern, BindingKind::For, None),
+    ]);
+}
+
+#[test]
+fn test_rebinding_and_parameters_not_flagged() {
+    assert!(shadowings("Let[n, 1]\nLet[n, n + 1]\nPrint[n]").is_empty());
+    assert!(shadowings("Let[n, 1]\nDouble[n: Int32] := n * 2\nPrint[Double[n]]").is_empty());
+    assert!(shadowings("Let[n, 1]\nPrint[Map[Function[{_n}, 0], [n]]]").is_empty());
+    assert!(shadowings("Print[Map[Function[{a}, a], [1]]]\nPrint[Map[Function[{a}, a], [2]]]").is_empty());
+}
+
+#[test]
+fn test_shadowing_message() {
+    let lints = lints("Let[total, 10]\nScale[k: Int32] := Map[Function[{total}, total * k], [total]]\nPrint[Scale[2]]");
+    assert_eq!(lints.len(), 1, "got: {:?}", lints);
+    assert_eq!(lints[0].code(), "W0010");
+    assert_eq!(lints[0].to_string(), "The lambda parameter total in Scale shadows the Let binding total bound at 1:1");
+}
+
+// ============================================================================
+// Reporting
+// ============================================================================
+
+#[test]
+fn test_lint_location_and_message() {
+    let lints = lints("Print[1]\nShare[total: Int32] := total / 0\nPrint[Share[3]]");
+    assert_eq!(lints.len(), 1);
+    let lint = &lints[0];
+    assert_eq!(lint.code(), "W0001");
+    assert_eq!(lint.function.as_deref(), Some("Share"));
+    assert_eq!(lint.span.map(|span| (span.line, span.column)), Some((2, 1)));
+    assert_eq!(lint.to_string(), "Division by zero in Share always panics");
+}
+
+#[derive(Default)]
+struct Collector {
+    lints: Vec<Lint>,
+}
+
+impl CompilerCallbacks for Collector {
+    fn on_lint(&mut self, lint: &Lint) {
+        self.lints.push(lint.clone());
+    }
+}
+
+#[test]
+fn test_driver_reports_lints_without_failing() {
+    let mut collector = Collector::default();
+    let result = compile("Print[At[[1, 2], 5]]", &CodegenOptions::default(), &mut collector);
+    assert!(result.is_ok(), "got: {:?}", result);
+    assert_eq!(collector.lints.len(), 1);
+    assert_eq!(collector.lints[0].code(), "W0002");
+}
+
+#[test]
+fn test_cli_deny_warnings() {
+    let dir = std::env::temp_dir().join(format!("w_lint_cli_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("main.w"), "Let[total, 3]\nPrint[4]\n").unwrap();
+
+    let run = |args: &[&str]| Command::new(env!("CARGO_BIN_EXE_w")).args(args).current_dir(&dir).output().unwrap();
+    let warned = run(&["--emit", "build-plan", "main.w"]);
+    let denied = run(&["--deny-warnings", "--emit", "build-plan", "main.w"]);
+    std::fs::remove_dir_all(&dir).ok();
+
+    assert!(warned.status.success());
+    assert!(String::from_utf8(warned.stderr).unwrap().starts_with("warning[W0006]: main.w:1:1: total is bound by Let"));
+    assert_eq!(denied.status.code(), Some(1));
+    assert!(denied.stdout.is_empty());
+    let stderr = String::from_utf8(denied.stderr).unwrap();
+    assert!(stderr.ends_with("error: 1 warning denied by --deny-warnings\n"), "got: {}", stderr);
+}
+
+#[test]
+fn test_cli_allow_warning() {
+    let dir = std::env::temp_dir().join(format!("w_lint_allow_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("main.w"), "Let[n, 3]\nPrint[Map[Function[{n}, n], [n]]]\n").unwrap();
+
+    let run = |args: &[&str]| Command::new(env!("CARGO_BIN_EXE_w")).args(args).current_dir(&dir).output().unwrap();
+    let warned = run(&["--emit", "build-plan", "main.w"]);
+    let allowed = run(&["--allow-warning", "W0010", "--deny-warnings", "--emit", "build-plan", "main.w"]);
+    let invalid = run(&["--allow-warning", "E0001", "main.w"]);
+    std::fs::remove_dir_all(&dir).ok();
+
+    let stderr = String::from_utf8(warned.stderr).unwrap();
+    assert!(stderr.starts_with("warning[W0010]: main.w:2:1: The lambda parameter n shadows the Let binding n bound at 1:1"), "got: {}", stderr);
+    assert!(allowed.status.success(), "got: {}", String::from_utf8_lossy(&allowed.stderr));
+    assert!(allowed.stderr.is_empty());
+    assert_eq!(invalid.status.code(), Some(1));
+}
@@ -0,0 +1,75 @@
+mod common;
+
+use common::{parse, program};
+use w::ast::Type;
+use w::type_inference::{TypeError, TypeInference};
+
+/// Check `definitions`, then infer the type of `expr` in the resulting scope
+fn infer_with(definitions: &str, expr: &str) -> Result<Type, TypeError> {
+    let mut inference = TypeInference::new();
+    inference.check_program(&program(definitions))?;
+    inference.infer_expression(&parse(expr))
+}
+
+fn mismatch(result: Result<Type, TypeError>) -> (Type, Type, String) {
+    match result {
+        Err(TypeError::TypeMismatch { expected, actual, context }) => (expected, actual, context),
+        other => panic!("Expected a type mismatch, got: {:?}", other),
+    }
+}
+
+// ============================================================================
+// Binary Operation Tests
+// ============================================================================
+
+#[test]
+fn test_literal_takes_other_operand_type() {
+    assert_eq!(infer_with("Grow[n: Int64] := n * 2 + 1", "Grow").unwrap(), Type::Function(vec![Type::Int64], Box::new(Type::Int64)));
+    assert_eq!(infer_with("Flip[n: UInt8] := 1 - n", "Flip").unwrap(), Type::Function(vec![Type::UInt8], Box::new(Type::UInt8)));
+}
+
+#[test]
+fn test_literal_in_comparison() {
+    assert_eq!(infer_with("IsOne[n: Int64] := n == 1", "IsOne[5]").unwrap(), Type::Bool);
+}
+
+#[test]
+fn test_float_literal_takes_float32() {
+    assert_eq!(infer_with("Halve[x: Float32] := x / 2.0", "Halve").unwrap(), Type::Function(vec![Type::Float32], Box::new(Type::Float32)));
+}
+
+#[test]
+fn test_integer_literal_is_not_a_float() {
+    let (expected, actual, context) = mismatch(infer_with("Double[x: Float64] := x * 2", "Double"));
+    assert_eq!((expected, actual), (Type::Float64, Type::Int32));
+    assert_eq!(context, "arithmetic operation (write the literal as 2.0)");
+}
+
+#[test]
+fn test_literals_alone_stay_int32() {
+    assert_eq!(infer_with("Let[n, 1 + 2]", "n").unwrap(), Type::Int32);
+    assert!(matches!(infer_with("Let[n, 1.5 + 1]", "n"), Err(TypeError::TypeMismatch { .. })));
+}
+
+// ============================================================================
+// Expected Type Tests
+// ============================================================================
+
+#[test]
+fn test_literal_argument_takes_parameter_type() {
+    assert_eq!(infer_with("Id[n: Int64] := n", "Id[3]").unwrap(), Type::Int64);
+    assert_eq!(infer_with("Total[xs: List[Int64]] := xs", "Total[[1, 2 * 3]]").unwrap(), Type::List(Box::new(Type::Int64)));
+}
+
+#[test]
+fn test_literal_fields_take_field_types() {
+    let definitions = "Struct[Pixel, [offset: Int64, level: UInt8]]";
+    assert_eq!(infer_with(definitions, "Pixel[40, 255]").unwrap(), Type::Custom("Pixel".to_string()));
+}
+
+#[test]
+fn test_literal_out_of_range() {
+    let (expected, actual, context) = mismatch(infer_with("Shade[n: UInt8] := n", "Shade[300]"));
+    assert_eq!((expected, actual), (Type::UInt8, Type::Int32));
+    assert_eq!(context, "argument to Shade (literal 300 is outside the range of UInt8)");
+}
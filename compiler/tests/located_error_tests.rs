@@ -0,0 +1,90 @@
+use w::diagnostics::render;
+use w::driver::{compile, CompileError, NoCallbacks};
+use w::lexer::Span;
+use w::parser::Parser;
+use w::rust_codegen::CodegenOptions;
+use w::type_inference::{LocatedTypeError, TypeError, TypeInference};
+
+/// Type check `source`, locating errors with the parser's spans
+fn located(source: &str) -> Vec<LocatedTypeError> {
+    let mut parser = Parser::new(source.to_string());
+    let expressions = match parser.parse().unwrap() {
+        w::ast::Expression::Program(expressions) => expressions,
+        other => vec![other],
+    };
+    TypeInference::new().check_program_located(&expressions, parser.expression_spans())
+}
+
+fn lines(errors: &[LocatedTypeError]) -> Vec<usize> {
+    errors.iter().map(|error| error.span.expect("a span").line).collect()
+}
+
+// ============================================================================
+// Location Tests
+// ============================================================================
+
+#[test]
+fn test_errors_located_at_their_statement() {
+    let errors = located("Let[a, 1]\nLet[b, missing]\n\n  Let[c, a + \"s\"]");
+    assert_eq!(lines(&errors), [2, 4]);
+    assert_eq!(errors[1].span.unwrap().column, 3);
+    assert_eq!(errors[0].error, TypeError::UndefinedIdentifier("missing".to_string()));
+}
+
+#[test]
+fn test_function_body_error_located_at_definition() {
+    let errors = located("Print[1]\nTwice[n: Int32] :=\n  n + \"x\"\nLet[y, Twice[\"a\"]]");
+    assert_eq!(lines(&errors), [2, 4]);
+}
+
+#[test]
+fn test_display_prefixes_location() {
+    let error = LocatedTypeError {
+        error: TypeError::UndefinedIdentifier("x".to_string()),
+        span: Some(Span { start: 4, end: 7, line: 2, column: 5 }),
+    };
+    assert_eq!(error.to_string(), "2:5: Undefined identifier: x");
+    assert_eq!(LocatedTypeError { span: None, ..error }.to_string(), "Undefined identifier: x");
+}
+
+// ============================================================================
+// Rendering Tests
+// ============================================================================
+
+#[test]
+fn test_render_underlines_statement() {
+    let source = "Let[count, 1]\nLet[label, count + \"s\"]\n";
+    let errors = located(source);
+    assert_eq!(
+        errors[0].render("main.w", source),
+        "error[E0001]: Type mismatch in arithmetic operation: expected Int32, got String\n \
+         --> main.w:2:1\n  |\n2 | Let[label, count + \"s\"]\n  | ^^^^^^^^^^^^^^^^^^^^^^^\n"
+    );
+}
+
+#[test]
+fn test_render_widens_gutter_and_indents_caret() {
+    let source = format!("{}    Let[x, y]   \n", "Print[1]\n".repeat(9));
+    let span = Span { start: 0, end: 3, line: 10, column: 5 };
+    assert_eq!(
+        render("warning", "W0001", "message", "main.w", &source, Some(span)),
+        "warning[W0001]: message\n  --> main.w:10:5\n   |\n10 |     Let[x, y]   \n   |     ^^^^^^^^^\n"
+    );
+}
+
+#[test]
+fn test_render_without_span() {
+    assert_eq!(render("error", "E0002", "message", "main.w", "", None), "error[E0002]: message\n --> main.w\n");
+    let past_end = Span { start: 0, end: 1, line: 3, column: 1 };
+    assert_eq!(render("error", "E0002", "message", "main.w", "Print[1]", Some(past_end)), "error[E0002]: message\n --> main.w\n");
+}
+
+#[test]
+fn test_compile_error_render() {
+    let source = "Let[a, first]\nLet[b, second]";
+    let error = compile(source, &CodegenOptions::default(), &mut NoCallbacks).unwrap_err();
+    assert!(matches!(error, CompileError::Type(ref errors) if errors.len() == 2), "got: {:?}", error);
+    let rendered = error.render("main.w", source);
+    assert!(rendered.contains(" --> main.w:1:1\n"), "got: {}", rendered);
+    assert!(rendered.contains("2 | Let[b, second]\n"), "got: {}", rendered);
+}
@@ -0,0 +1,91 @@
+mod common;
+
+use common::{generate, parse, scratch_dir, with_binary};
+use w::no_std::check_no_std;
+use w::stdlib::log::{self, Level};
+
+use std::process::{Command, Output};
+
+/// Compile generated code with rustc and run it once with `RUST_LOG` set to
+/// each of `settings` (unset for `None`)
+fn run_with_log_settings(rust_code: &str, name: &str, settings: &[Option<&str>]) -> Vec<Output> {
+    with_binary(rust_code, &scratch_dir(name), |binary| {
+        settings
+            .iter()
+            .map(|setting| {
+                let mut command = Command::new(binary);
+                match setting {
+                    Some(setting) => command.env("RUST_LOG", setting),
+                    None => command.env_remove("RUST_LOG"),
+                };
+                command.output().unwrap()
+            })
+            .collect()
+    })
+}
+
+const STEP: &str = "Step[x: Int32] := Block[LogDebug[\"step {x}\"], x + 1]";
+
+// ============================================================================
+// Code Generation Tests
+// ============================================================================
+
+#[test]
+fn test_log_calls_support_module() {
+    let rust_code = generate("LogInfo[\"start\"]\nLogError[2]");
+    assert!(rust_code.contains("__w_log::info(\"start\".to_string());"), "got: {}", rust_code);
+    assert!(rust_code.contains("__w_log::error(2);"), "got: {}", rust_code);
+    assert_eq!(rust_code.matches("mod __w_log").count(), 1, "got: {}", rust_code);
+}
+
+#[test]
+fn test_main_initializes_logging() {
+    let rust_code = generate(&format!("{}\nPrint[Step[1]]", STEP));
+    assert!(rust_code.contains("fn main() {\n    __w_log::init();\n"), "got: {}", rust_code);
+
+    let rust_code = generate("LogWarn[\"once\"]");
+    assert!(rust_code.contains("fn main() {\n    __w_log::init();\n"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_programs_without_logs_have_no_logger() {
+    let rust_code = generate("Print[1]");
+    assert!(!rust_code.contains("__w_log"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_no_std_rejects_logging() {
+    let error = check_no_std(&parse("F[x: Int32] := Block[LogInfo[x], x]"), &[]).unwrap_err();
+    assert_eq!(error.feature, "LogInfo");
+}
+
+// ============================================================================
+// Support Module Tests
+// ============================================================================
+
+#[test]
+fn test_rust_log_sets_max_level() {
+    assert_eq!(log::max_level(None), Level::Info as usize);
+    assert_eq!(log::max_level(Some("debug")), Level::Debug as usize);
+    assert_eq!(log::max_level(Some(" WARN ")), Level::Warn as usize);
+    assert_eq!(log::max_level(Some("off")), 0);
+    assert_eq!(log::max_level(Some("verbose")), Level::Info as usize);
+}
+
+// ============================================================================
+// Runtime Tests
+// ============================================================================
+
+#[test]
+fn test_levels_filtered_by_rust_log() {
+    let source = format!("{}\nLogInfo[\"starting\"]\nPrint[Step[1]]\nLogError[\"done\"]", STEP);
+    let outputs = run_with_log_settings(&generate(&source), "levels", &[None, Some("debug"), Some("error"), Some("off")]);
+    let stderr: Vec<String> = outputs.iter().map(|output| String::from_utf8(output.stderr.clone()).unwrap()).collect();
+    assert_eq!(stderr[0], "[INFO] starting\n[ERROR] done\n");
+    assert_eq!(stderr[1], "[INFO] starting\n[DEBUG] step 1\n[ERROR] done\n");
+    assert_eq!(stderr[2], "[ERROR] done\n");
+    assert_eq!(stderr[3], "");
+    for output in &outputs {
+        assert_eq!(String::from_utf8(output.stdout.clone()).unwrap(), "2\n");
+    }
+}
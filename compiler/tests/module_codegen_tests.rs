@@ -0,0 +1,76 @@
+mod common;
+
+use common::parse;
+use w::rust_codegen::RustCodeGenerator;
+
+// ============================================================================
+// Multi-File Codegen Tests
+// ============================================================================
+
+#[test]
+fn test_one_file_per_module() {
+    let modules = vec![("Geometry".to_string(), parse("Square[x: Int32] := x * x"))];
+    let entry = parse("Print[Square[4]]");
+
+    let mut codegen = RustCodeGenerator::new();
+    let files = codegen.generate_modules(&entry, &modules).unwrap();
+
+    let paths: Vec<&str> = files.iter().map(|file| file.path.as_str()).collect();
+    assert_eq!(paths, vec!["main.rs", "geometry.rs"]);
+}
+
+#[test]
+fn test_root_declares_modules() {
+    let modules = vec![
+        ("Geometry".to_string(), parse("Square[x: Int32] := x * x")),
+        ("Text".to_string(), parse("Greeting[name: String] := name")),
+    ];
+    let entry = parse("Print[Square[4]]");
+
+    let mut codegen = RustCodeGenerator::new();
+    let files = codegen.generate_modules(&entry, &modules).unwrap();
+    let main = &files[0].contents;
+
+    assert!(main.contains("mod geometry;\nuse geometry::*;"), "got: {}", main);
+    assert!(main.contains("mod text;\nuse text::*;"), "got: {}", main);
+    assert!(main.contains("square(4)"), "got: {}", main);
+}
+
+#[test]
+fn test_module_items_are_public() {
+    let modules = vec![(
+        "Shapes".to_string(),
+        parse("Struct[Point, [x: Int32, y: Int32]]\nOrigin[x: Int32] := x"),
+    )];
+    let entry = parse("Print[Origin[0]]");
+
+    let mut codegen = RustCodeGenerator::new();
+    let files = codegen.generate_modules(&entry, &modules).unwrap();
+    let shapes = &files[1].contents;
+
+    assert!(shapes.contains("pub fn origin(x: i32)"), "got: {}", shapes);
+    assert!(shapes.contains("pub struct Point"), "got: {}", shapes);
+    assert!(shapes.contains("use crate::*;"), "got: {}", shapes);
+    assert!(!files[0].contents.contains("pub fn"), "got: {}", files[0].contents);
+}
+
+#[test]
+fn test_module_rejects_top_level_statements() {
+    let modules = vec![("Noisy".to_string(), parse("Print[1]"))];
+    let entry = parse("Print[2]");
+
+    let mut codegen = RustCodeGenerator::new();
+    assert!(codegen.generate_modules(&entry, &modules).is_err());
+}
+
+#[test]
+fn test_generate_after_modules_has_no_declarations() {
+    let modules = vec![("Geometry".to_string(), parse("Square[x: Int32] := x * x"))];
+    let entry = parse("Print[1]");
+
+    let mut codegen = RustCodeGenerator::new();
+    codegen.generate_modules(&entry, &modules).unwrap();
+    let rust_code = codegen.generate(&entry).unwrap();
+
+    assert!(!rust_code.contains("mod geometry;"), "got: {}", rust_code);
+}
@@ -0,0 +1,86 @@
+mod common;
+
+use common::{parse, program};
+use w::ast::Type;
+use w::driver::{compile, CompileError, NoCallbacks};
+use w::rust_codegen::CodegenOptions;
+use w::type_inference::{TypeError, TypeInference};
+
+fn errors(input: &str) -> Vec<TypeError> {
+    TypeInference::new().check_program_errors(&program(input))
+}
+
+fn mismatch(expected: Type, actual: Type, context: &str) -> TypeError {
+    TypeError::TypeMismatch { expected, actual, context: context.to_string() }
+}
+
+// ============================================================================
+// Collection Tests
+// ============================================================================
+
+#[test]
+fn test_independent_statements_all_reported() {
+    let errors = errors("Let[a, missing]\nLet[n, 1]\nLet[b, n + \"a\"]\nLet[c, other]");
+    assert_eq!(errors.len(), 3, "got: {:?}", errors);
+    assert_eq!(errors[0], TypeError::UndefinedIdentifier("missing".to_string()));
+    assert!(matches!(errors[1], TypeError::TypeMismatch { .. }), "got: {:?}", errors[1]);
+    assert_eq!(errors[2], TypeError::UndefinedIdentifier("other".to_string()));
+}
+
+#[test]
+fn test_correct_program_has_no_errors() {
+    assert_eq!(errors("Square[n: Int32] := n * n\nPrint[Square[3]]"), vec![]);
+}
+
+#[test]
+fn test_check_program_returns_first_error() {
+    let source = "Let[a, first]\nLet[b, second]";
+    let result = TypeInference::new().check_program(&program(source));
+    assert_eq!(result, Err(errors(source)[0].clone()));
+}
+
+// ============================================================================
+// Recovery Tests
+// ============================================================================
+
+#[test]
+fn test_failed_let_is_not_reported_again() {
+    let errors = errors("Let[total, missing + 1]\nLet[double, total * 2]\nLet[label, total + \"s\"]");
+    assert_eq!(errors, vec![TypeError::UndefinedIdentifier("missing".to_string())]);
+}
+
+#[test]
+fn test_function_with_bad_body_still_checks_calls() {
+    let errors = errors("Twice[n: Int32] := n + \"x\"\nLet[a, Twice[2] + 1]\nLet[b, Twice[\"a\"]]");
+    assert_eq!(errors.len(), 2, "got: {:?}", errors);
+    assert!(matches!(errors[0], TypeError::TypeMismatch { .. }), "got: {:?}", errors[0]);
+    assert_eq!(errors[1], mismatch(Type::Int32, Type::String, "argument to Twice"));
+}
+
+#[test]
+fn test_failed_definition_is_callable() {
+    let errors = errors("Scores[xs: Map[Float64, Int32]] := xs\nLet[a, Scores[1, 2]]");
+    assert_eq!(errors.len(), 1, "got: {:?}", errors);
+    assert!(matches!(errors[0], TypeError::UnhashableKey { .. }), "got: {:?}", errors[0]);
+}
+
+#[test]
+fn test_infer_expression_reports_recovered_error() {
+    let result = TypeInference::new().infer_expression(&parse("Twice[n: Int32] := n + \"x\""));
+    assert!(matches!(result, Err(TypeError::TypeMismatch { .. })), "got: {:?}", result);
+}
+
+// ============================================================================
+// Driver Tests
+// ============================================================================
+
+#[test]
+fn test_compile_reports_every_error() {
+    let result = compile("Let[a, first]\nPrint[1 + 2]\nLet[b, second]", &CodegenOptions::default(), &mut NoCallbacks);
+    let Err(CompileError::Type(errors)) = &result else {
+        panic!("Expected type errors, got: {:?}", result);
+    };
+    assert_eq!(errors.len(), 2, "got: {:?}", errors);
+    let message = result.unwrap_err().to_string();
+    assert_eq!(message, "error[E0002]: 1:1: Undefined identifier: first\nerror[E0002]: 3:1: Undefined identifier: second");
+}
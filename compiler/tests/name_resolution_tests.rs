@@ -0,0 +1,136 @@
+mod common;
+
+use common::program;
+use w::resolve::{resolve_program, Definition, Resolution};
+use w::type_inference::{TypeError, TypeInference};
+
+fn resolve(input: &str) -> Resolution {
+    resolve_program(&program(input))
+}
+
+/// Each resolved identifier with what it refers to, in order
+fn references(input: &str) -> Vec<(String, Definition)> {
+    let resolution = resolve(input);
+    assert_eq!(resolution.errors, vec![], "unexpected errors in {}", input);
+    resolution.references.into_iter().map(|reference| (reference.name, reference.definition)).collect()
+}
+
+fn errors(input: &str) -> Vec<TypeError> {
+    TypeInference::new().check_program_errors(&program(input))
+}
+
+fn undefined(name: &str) -> TypeError {
+    TypeError::UndefinedIdentifier(name.to_string())
+}
+
+fn variant(enum_name: &str) -> Definition {
+    Definition::Variant { enum_name: enum_name.to_string() }
+}
+
+// ============================================================================
+// Resolution Tests
+// ============================================================================
+
+#[test]
+fn test_identifiers_resolve_to_definitions() {
+    let references = references("Let[offset, 10]\nShift[n: Int32] := n + offset\nPrint[Shift[2]]");
+    assert_eq!(references, vec![
+        ("n".to_string(), Definition::Parameter),
+        ("offset".to_string(), Definition::Local),
+        ("Print".to_string(), Definition::Builtin),
+        ("Shift".to_string(), Definition::Function),
+    ]);
+}
+
+#[test]
+fn test_type_definitions_resolve() {
+    let source = "Enum[Shape, [Circle[Float64], Dot]]\n\
+                  Struct[Point, [x: Int32, y: Int32]]\n\
+                  Trait[Printable, [ToText[self] -> String]]\n\
+                  Let[shapes, [Circle[1.0], Dot]]\n\
+                  Let[origin, Point[0, 0]]\n\
+                  Print[ToText[origin]]";
+    let definitions: Vec<Definition> = references(source).into_iter().map(|(_, definition)| definition).collect();
+    assert_eq!(definitions, vec![
+        variant("Shape"),
+        variant("Shape"),
+        Definition::Struct,
+        Definition::Builtin,
+        Definition::TraitMethod { trait_name: "Printable".to_string() },
+        Definition::Local,
+    ]);
+}
+
+#[test]
+fn test_inner_scope_hides_outer() {
+    let references = references("Let[x, 1]\nF[x: Int32] := x\nPrint[Map[Function[{x}, x], [x]]]");
+    let x: Vec<Definition> = references.into_iter().filter(|(name, _)| name == "x").map(|(_, definition)| definition).collect();
+    assert_eq!(x, vec![Definition::Parameter, Definition::Parameter, Definition::Local]);
+}
+
+#[test]
+fn test_call_may_precede_definition() {
+    assert_eq!(resolve("Print[Double[2]]\nDouble[n: Int32] := n * 2").errors, vec![]);
+}
+
+#[test]
+fn test_let_is_visible_after_binding() {
+    assert_eq!(resolve("Print[total]\nLet[total, 1]\nPrint[total]").errors, vec![(0, undefined("total"))]);
+}
+
+#[test]
+fn test_pattern_bindings_are_scoped() {
+    let resolution = resolve("Let[o, Some[1]]\nPrint[Match[o, [Some[v], v], [None, v]]]\nPrint[IfLet[Some[w], o, w, w]]");
+    assert_eq!(resolution.errors, vec![(1, undefined("v")), (2, undefined("w"))]);
+}
+
+#[test]
+fn test_variant_in_pattern_does_not_bind() {
+    let resolution = resolve("Enum[Color, [Red, Green]]\nPick[c: Color] := Match[c, [Red, 1], [other, 2]]");
+    assert_eq!(resolution.errors, vec![]);
+    assert!(resolution.references.iter().all(|reference| reference.name != "Red"), "got: {:?}", resolution.references);
+}
+
+#[test]
+fn test_variant_of_two_enums_is_ambiguous() {
+    let resolution = resolve("Enum[Shape, [Circle[Float64], Square[Float64]]]\nEnum[Token, [Circle, Cross]]\nPrint[Circle]");
+    assert_eq!(resolution.errors, vec![(2, TypeError::AmbiguousName {
+        name: "Circle".to_string(),
+        definitions: vec!["a variant of Shape".to_string(), "a variant of Token".to_string()],
+    })]);
+}
+
+#[test]
+fn test_imported_names_are_not_undefined() {
+    assert_eq!(resolve("Import[\"geometry.w\"]\nPrint[Area[2]]").errors, vec![]);
+}
+
+// ============================================================================
+// Type Inference Tests
+// ============================================================================
+
+#[test]
+fn test_undefined_name_in_print_is_reported() {
+    assert_eq!(errors("Print[Missing[2]]"), vec![undefined("Missing")]);
+}
+
+#[test]
+fn test_unresolved_name_replaces_type_errors() {
+    assert_eq!(errors("Let[a, missing + \"s\"]\nLet[b, 1 + \"s\"]").len(), 2);
+    assert_eq!(errors("Let[a, missing + \"s\"]")[0], undefined("missing"));
+}
+
+#[test]
+fn test_ambiguous_name_is_a_type_error() {
+    let errors = errors("Struct[Circle, [r: Float64]]\nEnum[Shape, [Circle[Float64]]]\nLet[c, Circle[1.0]]");
+    assert_eq!(errors.len(), 1, "got: {:?}", errors);
+    assert_eq!(errors[0].code(), "E0027");
+    assert_eq!(errors[0].to_string(), "Ambiguous name Circle: it is a struct and a variant of Shape");
+}
+
+#[test]
+fn test_names_from_earlier_program_resolve() {
+    let mut inference = TypeInference::new();
+    assert_eq!(inference.check_program(&program("Double[n: Int32] := n * 2")), Ok(()));
+    assert_eq!(inference.check_program(&program("Let[a, Double[2]]")), Ok(()));
+}
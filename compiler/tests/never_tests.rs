@@ -0,0 +1,101 @@
+mod common;
+
+use common::{compile_and_run_output, generate, parse};
+use w::ast::{Expression, Type};
+use w::type_inference::{TypeError, TypeInference};
+
+fn infer(input: &str) -> Result<Type, TypeError> {
+    TypeInference::new().infer_expression(&parse(input))
+}
+
+/// Type check a program and return the type bound to `name`
+fn binding_type(input: &str, name: &str) -> Result<Type, TypeError> {
+    let expressions = match parse(input) {
+        Expression::Program(expressions) => expressions,
+        other => vec![other],
+    };
+    let mut inference = TypeInference::new();
+    inference.check_program(&expressions)?;
+    Ok(inference.env().lookup(name).cloned().unwrap())
+}
+
+// ============================================================================
+// Type Inference Tests
+// ============================================================================
+
+#[test]
+fn test_panic_and_exit_are_never() {
+    assert_eq!(infer("Panic[\"bad\"]").unwrap(), Type::Never);
+    assert_eq!(infer("Exit[1]").unwrap(), Type::Never);
+}
+
+#[test]
+fn test_panic_requires_string_message() {
+    assert!(matches!(infer("Panic[1]"), Err(TypeError::TypeMismatch { .. })));
+    assert!(matches!(infer("Exit[\"code\"]"), Err(TypeError::TypeMismatch { .. })));
+}
+
+#[test]
+fn test_cond_with_panic_branch_takes_other_branch_type() {
+    assert_eq!(infer("Cond[[true 1] [Panic[\"bad\"]]]").unwrap(), Type::Int32);
+    assert_eq!(infer("Cond[[true Panic[\"bad\"]] [\"ok\"]]").unwrap(), Type::String);
+}
+
+#[test]
+fn test_match_with_diverging_arm() {
+    assert_eq!(
+        infer("Match[3, [1, \"one\"], [_, Exit[2]]]").unwrap(),
+        Type::String
+    );
+}
+
+#[test]
+fn test_iflet_with_diverging_else() {
+    assert_eq!(infer("IfLet[Some[x], Some[4], x * 2, Panic[\"missing\"]]").unwrap(), Type::Int32);
+}
+
+#[test]
+fn test_only_diverging_branches_are_never() {
+    assert_eq!(infer("Cond[[true Panic[\"a\"]] [Exit[1]]]").unwrap(), Type::Never);
+}
+
+#[test]
+fn test_diverging_function_in_branch() {
+    let source = "Fail[message: String] := Panic[message]\nLet[v, Cond[[true 2.5] [Fail[\"bad\"]]]]";
+    assert_eq!(binding_type(source, "v").unwrap(), Type::Float64);
+}
+
+#[test]
+fn test_never_does_not_hide_other_mismatches() {
+    assert!(matches!(
+        infer("Cond[[true 1] [false \"one\"] [Panic[\"bad\"]]]"),
+        Err(TypeError::TypeMismatch { .. })
+    ));
+}
+
+// ============================================================================
+// Code Generation Tests
+// ============================================================================
+
+#[test]
+fn test_codegen_panic_and_exit() {
+    let rust_code = generate("Fail[message: String] := Panic[message]\nExit[3]");
+    assert!(rust_code.contains("fn fail(message: String) -> ! {\n    panic!(\"{}\", message)\n}"), "got: {}", rust_code);
+    assert!(rust_code.contains("std::process::exit(3);"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_run_exit_code() {
+    let rust_code = generate("Let[n, 5]\nLet[v, Cond[[n > 0 n * 2] [Panic[\"negative\"]]]]\nPrint[v]\nExit[3]\nPrint[\"unreachable\"]");
+    let output = compile_and_run_output(&rust_code, "exit");
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "10\n");
+    assert_eq!(output.status.code(), Some(3));
+}
+
+#[test]
+fn test_run_panic_branch() {
+    let rust_code = generate("Let[n, 0]\nLet[v, Cond[[n > 0 n * 2] [Panic[\"not positive\"]]]]\nPrint[v]");
+    let output = compile_and_run_output(&rust_code, "panic");
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr).unwrap().contains("not positive"));
+}
@@ -0,0 +1,120 @@
+mod common;
+
+use common::{compile_library, parse};
+use w::driver::{compile, CompileError, NoCallbacks};
+use w::no_std::{check_no_std, NoStdError};
+use w::rust_codegen::{CodegenOptions, RustCodeGenerator};
+
+const LIBRARY: &str = "Struct[Point, [x: Int32, y: Int32]]
+Small[n: Int32] := Convert[n, UInt8]
+Label[n: Int32] := \"n\"
+Keep[m: BTreeMap[String, Int32]] := m
+Norm[p: Point] := Math.Abs[Convert[p.x, Float64]] + Math.Abs[Convert[p.y, Float64]]";
+
+fn generate(input: &str) -> String {
+    RustCodeGenerator::builder().no_std(true).build().generate(&parse(input)).unwrap()
+}
+
+fn check(input: &str) -> Result<(), NoStdError> {
+    check_no_std(&parse(input), &[])
+}
+
+// ============================================================================
+// Check Tests
+// ============================================================================
+
+#[test]
+fn test_definitions_pass() {
+    assert_eq!(check(LIBRARY), Ok(()));
+}
+
+#[test]
+fn test_top_level_statements_are_rejected() {
+    let error = check("Area[r: Float64] := r * r\nArea[2.0]").unwrap_err();
+    assert_eq!(error.feature, "A top-level statement");
+}
+
+#[test]
+fn test_io_is_rejected() {
+    assert_eq!(check("F[x: Int32] := Print[x]").unwrap_err().feature, "Print");
+    assert_eq!(check("F[code: Int32] := Exit[code]").unwrap_err().feature, "Exit");
+}
+
+#[test]
+fn test_std_math_is_rejected() {
+    assert_eq!(check("F[x: Float64] := Math.Sqrt[x]").unwrap_err().feature, "Math.Sqrt");
+    assert_eq!(check("F[x: Float64] := Math.Abs[x]"), Ok(()));
+}
+
+#[test]
+fn test_hash_collections_are_rejected() {
+    assert_eq!(check("F[m: Map[String, Int32]] := m").unwrap_err().feature, "Map[String, Int32]");
+    assert_eq!(check("F[xs: List[HashSet[Int32]]] := xs").unwrap_err().feature, "HashSet[Int32]");
+}
+
+#[test]
+fn test_error_message() {
+    let error = check("F[x: Int32] := Print[x]").unwrap_err();
+    assert_eq!(error.code(), "E0200");
+    assert_eq!(
+        error.to_string(),
+        "Print needs the standard library, which --no-std leaves out: there is no console; return the value to the caller instead"
+    );
+}
+
+// ============================================================================
+// Codegen Tests
+// ============================================================================
+
+#[test]
+fn test_crate_header() {
+    let rust_code = generate(LIBRARY);
+    assert!(rust_code.starts_with("#![no_std]\n\nextern crate alloc;\n"), "got: {}", rust_code);
+    assert!(!rust_code.contains("fn main"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_functions_are_exported() {
+    let rust_code = generate(LIBRARY);
+    assert!(rust_code.contains("pub fn small(n: i32) -> u8"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_collections_come_from_alloc() {
+    assert!(generate(LIBRARY).contains("m: alloc::collections::BTreeMap<String, i32>"));
+
+    let options = CodegenOptions { no_std: true, hoist_imports: true, ..CodegenOptions::default() };
+    let rust_code = RustCodeGenerator::builder().options(options).build().generate(&parse(LIBRARY)).unwrap();
+    assert!(rust_code.contains("use alloc::collections::BTreeMap;"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_statements_are_unsupported() {
+    let result = RustCodeGenerator::builder().no_std(true).build().generate(&parse("Print[1]"));
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_library_compiles_without_std() {
+    assert!(compile_library(&generate(LIBRARY), "library"), "rustc rejected: {}", generate(LIBRARY));
+}
+
+// ============================================================================
+// Driver Tests
+// ============================================================================
+
+#[test]
+fn test_driver_checks_no_std() {
+    let options = CodegenOptions { no_std: true, ..CodegenOptions::default() };
+    let error = compile("F[x: Int32] := Print[x]", &options, &mut NoCallbacks).unwrap_err();
+    assert!(matches!(error, CompileError::NoStd(_)), "got: {:?}", error);
+    assert_eq!(error.code(), Some("E0200"));
+}
+
+#[test]
+fn test_driver_locates_no_std_errors() {
+    let options = CodegenOptions { no_std: true, ..CodegenOptions::default() };
+    let error = compile("Double[x: Int32] := x * 2\nShow[x: Int32] := Print[x]", &options, &mut NoCallbacks).unwrap_err();
+    let rendered = error.render("lib.w", "");
+    assert!(rendered.starts_with("error[E0200]: lib.w:2:1: Print needs the standard library"), "got: {}", rendered);
+}
@@ -0,0 +1,103 @@
+mod common;
+
+use common::parse;
+use w::ast::Type;
+use w::type_inference::{TypeError, TypeInference};
+
+fn infer(input: &str) -> Result<Type, TypeError> {
+    TypeInference::new().infer_expression(&parse(input))
+}
+
+/// Return type of a function whose Cond picks `a` or `b`
+fn cond_result(a: &str, b: &str) -> Result<Type, TypeError> {
+    let source = format!("F[a: {}, b: {}, c: Bool] := Cond[[c a] [b]]", a, b);
+    match infer(&source)? {
+        Type::Function(_, result) => Ok(*result),
+        other => panic!("Expected function type, got: {:?}", other),
+    }
+}
+
+// ============================================================================
+// Widening Tests
+// ============================================================================
+
+#[test]
+fn test_cond_widens_int32_and_int64() {
+    assert_eq!(cond_result("Int32", "Int64"), Ok(Type::Int64));
+    assert_eq!(cond_result("Int64", "Int32"), Ok(Type::Int64));
+}
+
+#[test]
+fn test_match_widens_literal_arm_to_int64() {
+    let result = infer("F[x: Int64, c: Bool] := Match[c, [true, 1], [_, x]]");
+    match result {
+        Ok(Type::Function(_, result)) => assert_eq!(*result, Type::Int64),
+        other => panic!("Expected function type, got: {:?}", other),
+    }
+}
+
+#[test]
+fn test_unsigned_widens_to_wider_signed() {
+    assert_eq!(cond_result("UInt8", "Int16"), Ok(Type::Int16));
+    assert_eq!(cond_result("UInt32", "Int64"), Ok(Type::Int64));
+}
+
+#[test]
+fn test_mixed_signedness_finds_a_wider_common_type() {
+    assert_eq!(cond_result("Int32", "UInt32"), Ok(Type::Int64));
+    assert_eq!(cond_result("Int64", "UInt64"), Ok(Type::Int128));
+}
+
+#[test]
+fn test_small_integers_widen_to_floats() {
+    assert_eq!(cond_result("Int16", "Float32"), Ok(Type::Float32));
+    assert_eq!(cond_result("Int32", "Float64"), Ok(Type::Float64));
+    assert_eq!(cond_result("Float32", "Float64"), Ok(Type::Float64));
+}
+
+#[test]
+fn test_platform_sized_integers_only_take_small_types() {
+    assert_eq!(cond_result("Int8", "Int"), Ok(Type::Int));
+    assert_eq!(cond_result("UInt16", "UInt"), Ok(Type::UInt));
+    assert!(cond_result("Int32", "Int").is_err());
+}
+
+// ============================================================================
+// Lossy Combination Tests
+// ============================================================================
+
+#[test]
+fn test_int64_and_float64_suggest_conversion() {
+    match cond_result("Int64", "Float64") {
+        Err(TypeError::NoCommonNumericType { first, second, context, suggestion }) => {
+            assert_eq!(first, Type::Int64);
+            assert_eq!(second, Type::Float64);
+            assert_eq!(context, "cond default branch");
+            assert_eq!(suggestion, Type::Float64);
+        }
+        other => panic!("Expected NoCommonNumericType, got: {:?}", other),
+    }
+}
+
+#[test]
+fn test_int_and_uint128_suggest_the_wider_integer() {
+    match cond_result("Int8", "UInt128") {
+        Err(TypeError::NoCommonNumericType { suggestion, .. }) => assert_eq!(suggestion, Type::UInt128),
+        other => panic!("Expected NoCommonNumericType, got: {:?}", other),
+    }
+}
+
+#[test]
+fn test_no_common_numeric_type_message() {
+    let message = cond_result("Int64", "Float64").unwrap_err().to_string();
+    assert_eq!(
+        message,
+        "No lossless common type for Int64 and Float64 in cond default branch; convert one branch explicitly, e.g. Convert[value, Float64]"
+    );
+}
+
+#[test]
+fn test_non_numeric_branches_still_mismatch() {
+    let result = cond_result("Int32", "String");
+    assert!(matches!(result, Err(TypeError::TypeMismatch { .. })), "got: {:?}", result);
+}
@@ -0,0 +1,118 @@
+mod common;
+
+use common::parse;
+use w::optimizer::{structural_hash, ExpressionInterner, Optimizer};
+use w::rust_codegen::RustCodeGenerator;
+
+fn optimize_and_generate(input: &str) -> String {
+    let expr = Optimizer::new().optimize(&parse(input));
+    let mut codegen = RustCodeGenerator::new();
+    codegen.generate(&expr).unwrap()
+}
+
+// ============================================================================
+// Structural Hashing / Interning Tests
+// ============================================================================
+
+#[test]
+fn test_equal_expressions_hash_equal() {
+    let a = parse("F[x] + \"s\"");
+    let b = parse("F[x] + \"s\"");
+    assert_eq!(structural_hash(&a), structural_hash(&b));
+}
+
+#[test]
+fn test_different_expressions_hash_differently() {
+    let a = parse("F[x] + 1");
+    let b = parse("F[x] - 1");
+    assert_ne!(structural_hash(&a), structural_hash(&b));
+}
+
+#[test]
+fn test_interner_shares_ids_for_equal_expressions() {
+    let mut interner = ExpressionInterner::new();
+    let first = interner.intern(&parse("F[x, [1, 2]]"));
+    let second = interner.intern(&parse("F[x, [1, 2]]"));
+    let other = interner.intern(&parse("F[y, [1, 2]]"));
+
+    assert_eq!(first, second);
+    assert_ne!(first, other);
+    assert_eq!(interner.len(), 2);
+}
+
+// ============================================================================
+// Common-Subexpression Elimination Tests
+// ============================================================================
+
+#[test]
+fn test_cse_binds_repeated_pure_call() {
+    let rust_code = optimize_and_generate(
+        "F[x: Int32] := x * x\nG[x: Int32] := F[x] + F[x]",
+    );
+
+    assert!(rust_code.contains("let __w_0_cse = f(x);"),
+        "Shared call should be bound to a let, got: {}", rust_code);
+    assert!(rust_code.contains("(__w_0_cse + __w_0_cse)"),
+        "Both uses should refer to the binding, got: {}", rust_code);
+    assert!(rust_code.contains("fn g(x: i32) -> i32"),
+        "Return type should still be inferred, got: {}", rust_code);
+}
+
+#[test]
+fn test_cse_in_main_statement() {
+    let rust_code = optimize_and_generate(
+        "Sq[x: Int32] := x * x\nPrint[Sq[3] * Sq[3]]",
+    );
+
+    assert!(rust_code.contains("let __w_0_cse = sq(3);"), "got: {}", rust_code);
+    assert!(rust_code.contains("(__w_0_cse * __w_0_cse)"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_cse_skips_impure_functions() {
+    let rust_code = optimize_and_generate(
+        "Noisy[x: Int32] := Print[x]\nUse[x: Int32] := (Noisy[x], Noisy[x])",
+    );
+
+    assert!(!rust_code.contains("__w_0_cse"),
+        "Calls with side effects must not be merged, got: {}", rust_code);
+}
+
+#[test]
+fn test_cse_skips_single_occurrence() {
+    let rust_code = optimize_and_generate(
+        "F[x: Int32] := x * x\nG[x: Int32] := F[x] + F[1]",
+    );
+
+    assert!(!rust_code.contains("__w_0_cse"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_cse_skips_non_copy_results() {
+    let rust_code = optimize_and_generate(
+        "Name[x: Int32] := \"w\"\nPair[x: Int32] := (Name[x], Name[x])",
+    );
+
+    assert!(!rust_code.contains("__w_0_cse"),
+        "Reusing a String binding would move it twice, got: {}", rust_code);
+}
+
+#[test]
+fn test_cse_does_not_hoist_out_of_lambdas() {
+    let rust_code = optimize_and_generate(
+        "F[x: Int32] := x * x\nG[x: Int32] := Fold[Function[{acc, x}, acc + F[x]], F[x], [1, 2]]",
+    );
+
+    assert!(!rust_code.contains("__w_0_cse"),
+        "F[x] inside the lambda refers to a different x, got: {}", rust_code);
+}
+
+#[test]
+fn test_cse_prefers_outermost_repeated_call() {
+    let rust_code = optimize_and_generate(
+        "F[x: Int32] := x * x\nG[x: Int32] := F[F[x]] + F[F[x]]",
+    );
+
+    assert!(rust_code.contains("let __w_0_cse = f(f(x));"), "got: {}", rust_code);
+    assert!(!rust_code.contains("__w_1_cse"), "got: {}", rust_code);
+}
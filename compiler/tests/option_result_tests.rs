@@ -1,3 +1,6 @@
+mod common;
+
+use common::{compile_and_run, generate};
 use w::lexer::Lexer;
 use w::parser::Parser;
 use w::ast::Expression;
@@ -24,7 +27,7 @@ fn test_some_parsing() {
     match expr {
         Expression::Some { value } => {
             match *value {
-                Expression::Number(n) => assert_eq!(n, 42),
+                Expression::Number(n, _) => assert_eq!(n, 42),
                 _ => panic!("Expected number in Some"),
             }
         }
@@ -118,7 +121,7 @@ fn test_nested_some() {
             match *value {
                 Expression::Some { value: inner } => {
                     match *inner {
-                        Expression::Number(n) => assert_eq!(n, 42),
+                        Expression::Number(n, _) => assert_eq!(n, 42),
                         _ => panic!("Expected nested number"),
                     }
                 }
@@ -132,27 +135,63 @@ fn test_nested_some() {
 #[test]
 fn test_lexer_none_token() {
     let mut lexer = Lexer::new("None".to_string());
-    let token = lexer.next_token().unwrap();
+    let token = lexer.next_token().unwrap().unwrap();
     assert_eq!(token, w::lexer::Token::None);
 }
 
 #[test]
 fn test_lexer_some_token() {
     let mut lexer = Lexer::new("Some".to_string());
-    let token = lexer.next_token().unwrap();
+    let token = lexer.next_token().unwrap().unwrap();
     assert_eq!(token, w::lexer::Token::Some);
 }
 
 #[test]
 fn test_lexer_ok_token() {
     let mut lexer = Lexer::new("Ok".to_string());
-    let token = lexer.next_token().unwrap();
+    let token = lexer.next_token().unwrap().unwrap();
     assert_eq!(token, w::lexer::Token::Ok);
 }
 
 #[test]
 fn test_lexer_err_token() {
     let mut lexer = Lexer::new("Err".to_string());
-    let token = lexer.next_token().unwrap();
+    let token = lexer.next_token().unwrap().unwrap();
     assert_eq!(token, w::lexer::Token::Err);
 }
+
+// ============================================================================
+// Annotation Tests
+// ============================================================================
+
+#[test]
+fn test_annotated_parameters_codegen() {
+    let rust_code = generate("Keep[o: Option[Int32]] := o\nRetry[r: Result[List[Int64], String]] := r");
+    assert!(rust_code.contains("fn keep(o: Option<i32>) -> Option<i32>"), "got: {}", rust_code);
+    assert!(rust_code.contains("fn retry(r: Result<Vec<i64>, String>) -> Result<Vec<i64>, String>"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_annotated_fields_codegen() {
+    let rust_code = generate("Struct[User, [age: Option[UInt8], score: Result[Float64, String]]]");
+    assert!(rust_code.contains("pub age: Option<u8>,"), "got: {}", rust_code);
+    assert!(rust_code.contains("pub score: Result<f64, String>,"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_annotated_parameters_and_fields_roundtrip() {
+    let source = "Struct[User, [name: String, age: Option[Int32], score: Result[Float64, String]]]\n\
+                  Keep[o: Option[Int64]] := o\n\
+                  Retry[r: Result[Int32, String]] := r\n\
+                  Print[Keep[Some[5]]]\n\
+                  Print[Keep[None]]\n\
+                  Print[Retry[Ok[1]]]\n\
+                  Print[Retry[Err[\"bad\"]]]\n\
+                  Let[u, User[\"ann\", Some[30], Ok[1.5]]]\n\
+                  Match[u.age, [Some[a], Print[a]], [None, Print[\"unknown\"]]]\n\
+                  Match[u.score, [Ok[s], Print[s]], [Err[e], Print[e]]]";
+    assert_eq!(
+        compile_and_run(&generate(source), "roundtrip"),
+        "Some(5)\nNone\nOk(1)\nErr(\"bad\")\n30\n1.5\n"
+    );
+}
@@ -0,0 +1,137 @@
+mod common;
+
+use common::{compile_and_run, generate, parse};
+use w::ast::{Expression, Pattern, Radix, Type};
+use w::rust_codegen::RustCodeGenerator;
+use w::type_inference::{TypeError, TypeInference};
+
+fn infer(input: &str) -> Result<Type, TypeError> {
+    TypeInference::new().infer_expression(&parse(input))
+}
+
+/// The pattern of the first arm of a Match
+fn first_pattern(input: &str) -> Pattern {
+    match parse(input) {
+        Expression::Match { arms, .. } => arms[0].0.clone(),
+        other => panic!("expected Match, got: {:?}", other),
+    }
+}
+
+// ============================================================================
+// Parser Tests
+// ============================================================================
+
+#[test]
+fn test_parse_literal_alternatives() {
+    let pattern = first_pattern("Match[n, [1 | 2 | 3, \"small\"], [_, \"big\"]]");
+    assert_eq!(
+        pattern,
+        Pattern::Or(vec![
+            Pattern::Literal(Box::new(Expression::Number(1, Radix::Decimal))),
+            Pattern::Literal(Box::new(Expression::Number(2, Radix::Decimal))),
+            Pattern::Literal(Box::new(Expression::Number(3, Radix::Decimal))),
+        ])
+    );
+}
+
+#[test]
+fn test_parse_single_pattern_is_not_wrapped() {
+    let pattern = first_pattern("Match[n, [1, \"one\"], [_, \"other\"]]");
+    assert_eq!(pattern, Pattern::Literal(Box::new(Expression::Number(1, Radix::Decimal))));
+}
+
+#[test]
+fn test_parse_nested_alternatives() {
+    let pattern = first_pattern("Match[o, [Some[1 | 2], \"low\"], [_, \"other\"]]");
+    assert_eq!(pattern.to_string(), "Some[1 | 2]");
+}
+
+// ============================================================================
+// Type Inference Tests
+// ============================================================================
+
+#[test]
+fn test_alternatives_checked_against_value_type() {
+    assert_eq!(infer("Match[2, [1 | 2, \"low\"], [_, \"high\"]]").unwrap(), Type::String);
+    assert!(matches!(
+        infer("Match[2, [1 | \"two\", \"low\"], [_, \"high\"]]"),
+        Err(TypeError::TypeMismatch { .. })
+    ));
+}
+
+#[test]
+fn test_alternatives_binding_same_variable() {
+    assert_eq!(infer("Match[(1, 0), [(x, 0) | (0, x), x], [_, 0]]").unwrap(), Type::Int32);
+}
+
+#[test]
+fn test_alternatives_binding_different_variables() {
+    let result = infer("Match[(1, 0), [(x, 0) | (0, y), 1], [_, 0]]");
+    match result {
+        Err(TypeError::OrPatternBindings { pattern, name }) => {
+            assert_eq!(pattern, "(x, 0) | (0, y)");
+            assert_eq!(name, "x");
+        }
+        other => panic!("expected OrPatternBindings, got: {:?}", other),
+    }
+}
+
+#[test]
+fn test_alternative_binding_nothing() {
+    assert!(matches!(
+        infer("Match[Some[1], [Some[x] | None, 1], [_, 0]]"),
+        Err(TypeError::OrPatternBindings { .. })
+    ));
+}
+
+#[test]
+fn test_or_pattern_error_code() {
+    let error = infer("Match[Some[1], [Some[x] | None, 1], [_, 0]]").unwrap_err();
+    assert_eq!(error.code(), "E0019");
+}
+
+// ============================================================================
+// Code Generation Tests
+// ============================================================================
+
+#[test]
+fn test_generate_literal_alternatives() {
+    let code = generate("Match[n, [1 | 2 | 3, \"small\"], [_, \"big\"]]");
+    assert!(code.contains("1 | 2 | 3 =>"), "got: {}", code);
+}
+
+#[test]
+fn test_generate_string_alternatives_as_one_guard() {
+    let code = generate("Match[s, [\"a\" | StartsWith[\"b\"], \"ab\"], [_, \"other\"]]");
+    assert!(code.contains("s if s == \"a\" || s.starts_with(\"b\") =>"), "got: {}", code);
+}
+
+#[test]
+fn test_generate_nested_alternatives() {
+    let code = generate("Match[o, [Some[1 | 2], \"low\"], [Some[_] | None, \"rest\"]]");
+    assert!(code.contains("Some(1 | 2) =>"), "got: {}", code);
+    assert!(code.contains("Some(_) | None =>"), "got: {}", code);
+}
+
+#[test]
+fn test_mixing_string_guard_and_plain_pattern_is_unsupported() {
+    let program = parse("Match[p, [(\"a\", _) | (_, 1), 1], [_, 0]]");
+    assert!(RustCodeGenerator::new().generate(&program).is_err());
+}
+
+// ============================================================================
+// End-to-End Tests
+// ============================================================================
+
+#[test]
+fn test_or_patterns_run() {
+    let code = generate(
+        "Let[n, 2]
+Print[Match[n, [1 | 2 | 3, \"small\"], [_, \"big\"]]]
+Let[s, \"bee\"]
+Print[Match[s, [\"a\" | StartsWith[\"b\"], \"ab\"], [_, \"other\"]]]
+Let[o, Some[5]]
+Print[Match[o, [Some[1 | 2], \"low\"], [Some[_] | None, \"rest\"]]]",
+    );
+    assert_eq!(compile_and_run(&code, "run"), "small\nab\nrest\n");
+}
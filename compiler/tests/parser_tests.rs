@@ -79,7 +79,7 @@ mod tests {
             Expression::LogCall { level, message } => {
                 assert_eq!(level, LogLevel::Info);
                 match *message {
-                    Expression::Number(num) => assert_eq!(num, 42),
+                    Expression::Number(num, _) => assert_eq!(num, 42),
                     _ => panic!("Expected number message"),
                 }
             }
@@ -94,38 +94,35 @@ mod tests {
         let expr = parser.parse_expression().unwrap();
 
         match expr {
-            Expression::Cond { conditions, default_statements } => {
+            Expression::Cond { conditions, default_statements, .. } => {
                 assert_eq!(conditions.len(), 1);
                 assert!(default_statements.is_none());
 
                 // Check the condition
-                match &conditions[0] {
-                    (condition, statements) => {
-                        match condition {
-                            Expression::BinaryOp { left, operator: _, right: _ } => {
-                                match **left {
-                                    Expression::Identifier(ref name) => assert_eq!(name, "x"),
-                                    _ => panic!("Expected x identifier"),
-                                }
-                            }
-                            _ => panic!("Expected binary operation"),
+                let (condition, statements) = &conditions[0];
+                match condition {
+                    Expression::BinaryOp { left, operator: _, right: _ } => {
+                        match **left {
+                            Expression::Identifier(ref name) => assert_eq!(name, "x"),
+                            _ => panic!("Expected x identifier"),
                         }
+                    }
+                    _ => panic!("Expected binary operation"),
+                }
 
-                        match statements {
-                            Expression::FunctionCall { function, arguments } => {
-                                match **function {
-                                    Expression::Identifier(ref name) => assert_eq!(name, "Print"),
-                                    _ => panic!("Expected Print function"),
-                                }
-                                assert_eq!(arguments.len(), 1);
-                                match arguments[0] {
-                                    Expression::String(ref msg) => assert_eq!(msg, "Greater than 10"),
-                                    _ => panic!("Expected string argument"),
-                                }
-                            }
-                            _ => panic!("Expected function call"),
+                match statements {
+                    Expression::FunctionCall { function, arguments } => {
+                        match **function {
+                            Expression::Identifier(ref name) => assert_eq!(name, "Print"),
+                            _ => panic!("Expected Print function"),
+                        }
+                        assert_eq!(arguments.len(), 1);
+                        match arguments[0] {
+                            Expression::String(ref msg) => assert_eq!(msg, "Greater than 10"),
+                            _ => panic!("Expected string argument"),
                         }
                     }
+                    _ => panic!("Expected function call"),
                 }
             }
             _ => panic!("Expected Cond expression"),
@@ -138,67 +135,61 @@ mod tests {
         let expr = parser.parse_expression().unwrap();
         
         match expr {
-            Expression::Cond { conditions, default_statements } => {
+            Expression::Cond { conditions, default_statements, .. } => {
                 assert_eq!(conditions.len(), 2);
                 
                 // Check first condition
-                match &conditions[0] {
-                    (condition, statements) => {
-                        match condition {
-                            Expression::BinaryOp { left, operator: _, right: _ } => {
-                                match **left {
-                                    Expression::Identifier(ref name) => assert_eq!(name, "x"),
-                                    _ => panic!("Expected x identifier"),
-                                }
-                            }
-                            _ => panic!("Expected binary operation"),
+                let (condition, statements) = &conditions[0];
+                match condition {
+                    Expression::BinaryOp { left, operator: _, right: _ } => {
+                        match **left {
+                            Expression::Identifier(ref name) => assert_eq!(name, "x"),
+                            _ => panic!("Expected x identifier"),
                         }
+                    }
+                    _ => panic!("Expected binary operation"),
+                }
                         
-                        match statements {
-                            Expression::FunctionCall { function, arguments } => {
-                                match **function {
-                                    Expression::Identifier(ref name) => assert_eq!(name, "Print"),
-                                    _ => panic!("Expected Print function"),
-                                }
-                                assert_eq!(arguments.len(), 1);
-                                match arguments[0] {
-                                    Expression::String(ref msg) => assert_eq!(msg, "Greater than 10"),
-                                    _ => panic!("Expected string argument"),
-                                }
-                            }
-                            _ => panic!("Expected function call"),
+                match statements {
+                    Expression::FunctionCall { function, arguments } => {
+                        match **function {
+                            Expression::Identifier(ref name) => assert_eq!(name, "Print"),
+                            _ => panic!("Expected Print function"),
+                        }
+                        assert_eq!(arguments.len(), 1);
+                        match arguments[0] {
+                            Expression::String(ref msg) => assert_eq!(msg, "Greater than 10"),
+                            _ => panic!("Expected string argument"),
                         }
                     }
+                    _ => panic!("Expected function call"),
                 }
                 
                 // Check second condition
-                match &conditions[1] {
-                    (condition, statements) => {
-                        match condition {
-                            Expression::BinaryOp { left, operator: _, right: _ } => {
-                                match **left {
-                                    Expression::Identifier(ref name) => assert_eq!(name, "x"),
-                                    _ => panic!("Expected x identifier"),
-                                }
-                            }
-                            _ => panic!("Expected binary operation"),
+                let (condition, statements) = &conditions[1];
+                match condition {
+                    Expression::BinaryOp { left, operator: _, right: _ } => {
+                        match **left {
+                            Expression::Identifier(ref name) => assert_eq!(name, "x"),
+                            _ => panic!("Expected x identifier"),
                         }
+                    }
+                    _ => panic!("Expected binary operation"),
+                }
                         
-                        match statements {
-                            Expression::FunctionCall { function, arguments } => {
-                                match **function {
-                                    Expression::Identifier(ref name) => assert_eq!(name, "Print"),
-                                    _ => panic!("Expected Print function"),
-                                }
-                                assert_eq!(arguments.len(), 1);
-                                match arguments[0] {
-                                    Expression::String(ref msg) => assert_eq!(msg, "Less than 5"),
-                                    _ => panic!("Expected string argument"),
-                                }
-                            }
-                            _ => panic!("Expected function call"),
+                match statements {
+                    Expression::FunctionCall { function, arguments } => {
+                        match **function {
+                            Expression::Identifier(ref name) => assert_eq!(name, "Print"),
+                            _ => panic!("Expected Print function"),
+                        }
+                        assert_eq!(arguments.len(), 1);
+                        match arguments[0] {
+                            Expression::String(ref msg) => assert_eq!(msg, "Less than 5"),
+                            _ => panic!("Expected string argument"),
                         }
                     }
+                    _ => panic!("Expected function call"),
                 }
                 
                 // Check default statements
@@ -228,57 +219,51 @@ mod tests {
         let expr = parser.parse_expression().unwrap();
         
         match expr {
-            Expression::Cond { conditions, default_statements } => {
+            Expression::Cond { conditions, default_statements, .. } => {
                 assert_eq!(conditions.len(), 2);
                 
                 // Check first condition
-                match &conditions[0] {
-                    (condition, statements) => {
-                        match condition {
-                            Expression::Number(num) => assert_eq!(*num, 42),
-                            _ => panic!("Expected number"),
-                        }
+                let (condition, statements) = &conditions[0];
+                match condition {
+                    Expression::Number(num, _) => assert_eq!(*num, 42),
+                    _ => panic!("Expected number"),
+                }
                         
-                        match statements {
-                            Expression::FunctionCall { function, arguments } => {
-                                match **function {
-                                    Expression::Identifier(ref name) => assert_eq!(name, "Print"),
-                                    _ => panic!("Expected Print function"),
-                                }
-                                assert_eq!(arguments.len(), 1);
-                                match arguments[0] {
-                                    Expression::String(ref msg) => assert_eq!(msg, "The answer"),
-                                    _ => panic!("Expected string argument"),
-                                }
-                            }
-                            _ => panic!("Expected function call"),
+                match statements {
+                    Expression::FunctionCall { function, arguments } => {
+                        match **function {
+                            Expression::Identifier(ref name) => assert_eq!(name, "Print"),
+                            _ => panic!("Expected Print function"),
+                        }
+                        assert_eq!(arguments.len(), 1);
+                        match arguments[0] {
+                            Expression::String(ref msg) => assert_eq!(msg, "The answer"),
+                            _ => panic!("Expected string argument"),
                         }
                     }
+                    _ => panic!("Expected function call"),
                 }
                 
                 // Check second condition
-                match &conditions[1] {
-                    (condition, statements) => {
-                        match condition {
-                            Expression::Number(num) => assert_eq!(*num, 0),
-                            _ => panic!("Expected number"),
-                        }
+                let (condition, statements) = &conditions[1];
+                match condition {
+                    Expression::Number(num, _) => assert_eq!(*num, 0),
+                    _ => panic!("Expected number"),
+                }
                         
-                        match statements {
-                            Expression::FunctionCall { function, arguments } => {
-                                match **function {
-                                    Expression::Identifier(ref name) => assert_eq!(name, "Print"),
-                                    _ => panic!("Expected Print function"),
-                                }
-                                assert_eq!(arguments.len(), 1);
-                                match arguments[0] {
-                                    Expression::String(ref msg) => assert_eq!(msg, "Zero"),
-                                    _ => panic!("Expected string argument"),
-                                }
-                            }
-                            _ => panic!("Expected function call"),
+                match statements {
+                    Expression::FunctionCall { function, arguments } => {
+                        match **function {
+                            Expression::Identifier(ref name) => assert_eq!(name, "Print"),
+                            _ => panic!("Expected Print function"),
+                        }
+                        assert_eq!(arguments.len(), 1);
+                        match arguments[0] {
+                            Expression::String(ref msg) => assert_eq!(msg, "Zero"),
+                            _ => panic!("Expected string argument"),
                         }
                     }
+                    _ => panic!("Expected function call"),
                 }
                 
                 assert!(default_statements.is_none());
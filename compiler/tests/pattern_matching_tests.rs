@@ -10,7 +10,7 @@ use w::rust_codegen::RustCodeGenerator;
 #[test]
 fn test_lexer_underscore() {
     let mut lexer = Lexer::new("_".to_string());
-    assert_eq!(lexer.next_token().unwrap(), Token::Underscore);
+    assert_eq!(lexer.next_token().unwrap().unwrap(), Token::Underscore);
 }
 
 // ============================================
@@ -45,7 +45,7 @@ fn test_parse_literal_number_pattern() {
             match &arms[0].0 {
                 Pattern::Literal(expr) => {
                     match expr.as_ref() {
-                        Expression::Number(n) => assert_eq!(*n, 42),
+                        Expression::Number(n, _) => assert_eq!(*n, 42),
                         _ => panic!("Expected number in literal pattern"),
                     }
                 }
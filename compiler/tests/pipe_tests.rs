@@ -1,5 +1,5 @@
 use w::lexer::{Lexer, Token};
-use w::ast::{Expression, Operator};
+use w::ast::{Expression, Operator, Radix};
 use w::parser::Parser;
 
 // ── Lexer tests ──
@@ -8,23 +8,23 @@ use w::parser::Parser;
 fn test_pipe_token() {
     let mut lexer = Lexer::new("x |> F[y]".to_string());
 
-    assert_eq!(lexer.next_token(), Some(Token::Identifier("x".to_string())));
-    assert_eq!(lexer.next_token(), Some(Token::Pipe));
-    assert_eq!(lexer.next_token(), Some(Token::Identifier("F".to_string())));
-    assert_eq!(lexer.next_token(), Some(Token::LeftBracket));
-    assert_eq!(lexer.next_token(), Some(Token::Identifier("y".to_string())));
-    assert_eq!(lexer.next_token(), Some(Token::RightBracket));
-    assert_eq!(lexer.next_token(), None);
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Identifier("x".to_string())));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Pipe));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Identifier("F".to_string())));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::LeftBracket));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Identifier("y".to_string())));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::RightBracket));
+    assert_eq!(lexer.next_token().unwrap(), None);
 }
 
 #[test]
 fn test_pipe_token_no_spaces() {
     let mut lexer = Lexer::new("x|>F".to_string());
 
-    assert_eq!(lexer.next_token(), Some(Token::Identifier("x".to_string())));
-    assert_eq!(lexer.next_token(), Some(Token::Pipe));
-    assert_eq!(lexer.next_token(), Some(Token::Identifier("F".to_string())));
-    assert_eq!(lexer.next_token(), None);
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Identifier("x".to_string())));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Pipe));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Identifier("F".to_string())));
+    assert_eq!(lexer.next_token().unwrap(), None);
 }
 
 // ── Parser tests ──
@@ -100,9 +100,9 @@ fn test_pipe_with_binary_op_lhs() {
             assert_eq!(arguments.len(), 1);
             match &arguments[0] {
                 Expression::BinaryOp { left, operator, right } => {
-                    assert_eq!(**left, Expression::Number(1));
+                    assert_eq!(**left, Expression::Number(1, Radix::Decimal));
                     assert_eq!(*operator, Operator::Add);
-                    assert_eq!(**right, Expression::Number(2));
+                    assert_eq!(**right, Expression::Number(2, Radix::Decimal));
                 }
                 other => panic!("Expected BinaryOp, got {:?}", other),
             }
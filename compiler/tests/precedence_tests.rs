@@ -0,0 +1,100 @@
+mod common;
+
+use common::{generate, parse};
+use w::ast::{Expression, Operator, Radix};
+
+fn binary(left: Expression, operator: Operator, right: Expression) -> Expression {
+    Expression::BinaryOp { left: Box::new(left), operator, right: Box::new(right) }
+}
+
+fn num(n: i64) -> Expression {
+    Expression::Number(n, Radix::Decimal)
+}
+
+// ============================================================================
+// Precedence Tests
+// ============================================================================
+
+#[test]
+fn test_multiplication_binds_tighter_than_addition() {
+    assert_eq!(parse("1 + 2 * 3"),
+        binary(num(1), Operator::Add, binary(num(2), Operator::Multiply, num(3))));
+}
+
+#[test]
+fn test_power_binds_tighter_than_multiplication() {
+    assert_eq!(parse("2 * 3 ^ 2"),
+        binary(num(2), Operator::Multiply, binary(num(3), Operator::Power, num(2))));
+}
+
+#[test]
+fn test_comparison_binds_looser_than_arithmetic() {
+    assert_eq!(parse("1 + 2 > 3 - 4"),
+        binary(binary(num(1), Operator::Add, num(2)), Operator::GreaterThan,
+            binary(num(3), Operator::Subtract, num(4))));
+}
+
+#[test]
+fn test_and_binds_tighter_than_or() {
+    let expr = parse("a || b && c");
+    match expr {
+        Expression::BinaryOp { operator: Operator::Or, right, .. } => {
+            assert!(matches!(*right, Expression::BinaryOp { operator: Operator::And, .. }), "got: {:?}", right);
+        }
+        other => panic!("Expected Or at the root, got: {:?}", other),
+    }
+}
+
+#[test]
+fn test_comparisons_bind_tighter_than_logical_operators() {
+    let rust_code = generate("InRange[x: Int32] := x > 0 && x < 10");
+    assert!(rust_code.contains("((x > 0) && (x < 10))"), "got: {}", rust_code);
+}
+
+// ============================================================================
+// Associativity Tests
+// ============================================================================
+
+#[test]
+fn test_subtraction_is_left_associative() {
+    assert_eq!(parse("10 - 4 - 3"),
+        binary(binary(num(10), Operator::Subtract, num(4)), Operator::Subtract, num(3)));
+}
+
+#[test]
+fn test_power_is_right_associative() {
+    assert_eq!(parse("2 ^ 3 ^ 2"),
+        binary(num(2), Operator::Power, binary(num(3), Operator::Power, num(2))));
+}
+
+#[test]
+fn test_mixed_levels_resume_at_lower_precedence() {
+    // 1 * 2 + 3 * 4 - 5 == ((1 * 2) + (3 * 4)) - 5
+    assert_eq!(parse("1 * 2 + 3 * 4 - 5"),
+        binary(
+            binary(binary(num(1), Operator::Multiply, num(2)), Operator::Add,
+                binary(num(3), Operator::Multiply, num(4))),
+            Operator::Subtract,
+            num(5)));
+}
+
+// ============================================================================
+// Grouping Tests
+// ============================================================================
+
+#[test]
+fn test_parentheses_group() {
+    assert_eq!(parse("(1 + 2) * 3"),
+        binary(binary(num(1), Operator::Add, num(2)), Operator::Multiply, num(3)));
+}
+
+#[test]
+fn test_trailing_comma_still_makes_single_element_tuple() {
+    assert_eq!(parse("(42,)"), Expression::Tuple(vec![num(42)]));
+}
+
+#[test]
+fn test_generated_arithmetic_respects_precedence() {
+    let rust_code = generate("F[x: Int32] := 1 + x * 3");
+    assert!(rust_code.contains("(1 + (x * 3))"), "got: {}", rust_code);
+}
@@ -0,0 +1,165 @@
+mod common;
+
+use common::{compile_and_run_output, generate};
+use w::ast::{Expression, StringPart};
+use w::parser::Parser;
+use w::rust_codegen::RustCodeGenerator;
+use w::type_inference::{TypeError, TypeInference};
+
+fn check(input: &str) -> Vec<TypeError> {
+    let mut parser = Parser::new(input.to_string());
+    let expressions = match parser.parse().unwrap() {
+        Expression::Program(expressions) => expressions,
+        other => vec![other],
+    };
+    TypeInference::new().check_program_errors(&expressions)
+}
+
+// ============================================================================
+// Print Fast Path Tests
+// ============================================================================
+
+#[test]
+fn test_print_without_arguments() {
+    let rust_code = generate("Print[]");
+    assert!(rust_code.contains("    println!();"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_print_string_literal_needs_no_allocation() {
+    let rust_code = generate(r#"Print["hi"]"#);
+
+    assert!(rust_code.contains(r#"println!("hi");"#), "got: {}", rust_code);
+    assert!(!rust_code.contains("to_string"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_print_folds_all_literal_kinds() {
+    let rust_code = generate(r#"Print["n", 42, 2.5, true]"#);
+    assert!(rust_code.contains(r#"println!("n 42 2.5 true");"#), "got: {}", rust_code);
+}
+
+#[test]
+fn test_print_literal_braces_are_escaped() {
+    let rust_code = generate(r#"Print["{{}}", x]"#);
+    assert!(rust_code.contains(r#"println!("{{}} {}", x);"#), "got: {}", rust_code);
+}
+
+#[test]
+fn test_print_mixes_literals_and_values() {
+    let rust_code = generate(r#"Print["total:", x, [1, 2]]"#);
+    assert!(rust_code.contains(r#"println!("total: {} {:?}", x, vec![1, 2]);"#), "got: {}", rust_code);
+}
+
+#[test]
+fn test_print_splices_interpolated_string() {
+    let rust_code = generate(r#"Print["x = {x}", "done"]"#);
+
+    assert!(rust_code.contains(r#"println!("x = {} done", x);"#), "got: {}", rust_code);
+    assert!(!rust_code.contains("format!"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_print_as_value_uses_fast_path() {
+    let rust_code = generate(r#"Greet[x: Int32] := Print["hello"]"#);
+    assert!(rust_code.contains(r#"println!("hello");"#), "got: {}", rust_code);
+}
+
+// ============================================================================
+// Format Template Tests
+// ============================================================================
+
+#[test]
+fn test_parse_template_placeholders() {
+    let mut parser = Parser::new(r#"Print["x = {}, y = {:.2}", x, y]"#.to_string());
+    let Some(Expression::FunctionCall { arguments, .. }) = parser.parse() else {
+        panic!("Expected a call");
+    };
+    assert_eq!(
+        arguments[0],
+        Expression::InterpolatedString(vec![
+            StringPart::Literal("x = ".to_string()),
+            StringPart::Placeholder { format_spec: None },
+            StringPart::Literal(", y = ".to_string()),
+            StringPart::Placeholder { format_spec: Some(".2".to_string()) },
+        ])
+    );
+}
+
+#[test]
+fn test_template_fills_placeholders_in_order() {
+    let rust_code = generate(r#"Print["x = {}, y = {:.2}, xs = {}", x, 2.5, [1]]"#);
+    assert!(rust_code.contains(r#"println!("x = {}, y = {:.2}, xs = {:?}", x, 2.5, vec![1]);"#), "got: {}", rust_code);
+}
+
+#[test]
+fn test_template_mixes_with_interpolation() {
+    let rust_code = generate(r#"Print["{x} and {}", y]"#);
+    assert!(rust_code.contains(r#"println!("{} and {}", x, y);"#), "got: {}", rust_code);
+}
+
+#[test]
+fn test_lone_string_and_escaped_braces_are_text() {
+    let rust_code = generate(r#"Print["{}"]"#);
+    assert!(rust_code.contains(r#"println!("{{}}");"#), "got: {}", rust_code);
+    let rust_code = generate(r#"Let[s, "{}"]"#);
+    assert!(rust_code.contains(r#"let s = "{}".to_string();"#), "got: {}", rust_code);
+}
+
+#[test]
+fn test_template_argument_count_checked() {
+    assert_eq!(check(r#"Print["{} of {}", 1, 2]"#), vec![]);
+    assert_eq!(
+        check(r#"Print["{} of {}", 1]"#),
+        vec![TypeError::FormatArguments { function: "Print".to_string(), placeholders: 2, arguments: 1 }]
+    );
+    assert_eq!(check(r#"Print["{}", 1, 2]"#).len(), 1);
+
+    let mut parser = Parser::new(r#"Print["{}", 1, 2]"#.to_string());
+    let expr = parser.parse().unwrap();
+    assert!(RustCodeGenerator::new().generate(&expr).is_err());
+}
+
+// ============================================================================
+// PrintErr and PrintInline Tests
+// ============================================================================
+
+#[test]
+fn test_print_err_uses_eprintln() {
+    let rust_code = generate(r#"PrintErr["warning:", x]"#);
+    assert!(rust_code.contains(r#"eprintln!("warning: {}", x);"#), "got: {}", rust_code);
+    let rust_code = generate("PrintErr[]");
+    assert!(rust_code.contains("    eprintln!();"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_print_inline_uses_print() {
+    let rust_code = generate(r#"PrintInline["{} > ", x]"#);
+    assert!(rust_code.contains(r#"print!("{} > ", x);"#), "got: {}", rust_code);
+    let rust_code = generate("PrintInline[]");
+    assert!(rust_code.contains(r#"    print!("");"#), "got: {}", rust_code);
+}
+
+#[test]
+fn test_print_err_template_argument_count_checked() {
+    assert_eq!(check(r#"PrintErr["{}"]"#), vec![]);
+    assert_eq!(
+        check(r#"PrintInline["{} {}", 1]"#),
+        vec![TypeError::FormatArguments { function: "PrintInline".to_string(), placeholders: 2, arguments: 1 }]
+    );
+}
+
+#[test]
+fn test_output_and_diagnostics_are_separate() {
+    let rust_code = generate(
+        "Warn[n: Int32] := PrintErr[\"skipped {n}\"]\n\
+         PrintInline[\"a\"]\n\
+         PrintInline[\"b\", 1]\n\
+         Print[]\n\
+         Warn[2]\n\
+         Print[\"done\"]",
+    );
+    let output = compile_and_run_output(&rust_code, "stderr");
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "ab 1\ndone\n");
+    assert_eq!(String::from_utf8(output.stderr).unwrap(), "skipped 2\n");
+}
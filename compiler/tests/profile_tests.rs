@@ -0,0 +1,127 @@
+mod common;
+
+use common::{compile_library, parse};
+use w::driver::{compile, CompileError, NoCallbacks};
+use w::profile::{check_profile, ProfileViolation};
+use w::rust_codegen::{CodegenOptions, Profile, RustCodeGenerator};
+
+const FIRMWARE: &str = "Struct[Reading, [sensor: UInt8, value: Int32]]
+Mix[a: Int32, b: UInt8] := a * 3 + Convert[b, Int32]
+Clamp[x: Int32, limit: Int32] := If[x > limit, Panic[\"too big\"], x]
+Small[n: Int32] := Convert[n, UInt8]
+Sum[xs: Array[Int32, 4]] := Fold[Function[{acc, x}, acc + x], 0, xs]";
+
+fn violations(input: &str) -> Vec<ProfileViolation> {
+    check_profile(&parse(input), Profile::Embedded, &[])
+}
+
+fn features(input: &str) -> Vec<String> {
+    violations(input).into_iter().map(|violation| violation.feature).collect()
+}
+
+fn generate(input: &str) -> String {
+    RustCodeGenerator::builder().profile(Profile::Embedded).build().generate(&parse(input)).unwrap()
+}
+
+// ============================================================================
+// Validator Tests
+// ============================================================================
+
+#[test]
+fn test_integer_subset_passes() {
+    assert_eq!(violations(FIRMWARE), vec![]);
+}
+
+#[test]
+fn test_standard_profile_allows_everything() {
+    assert_eq!(check_profile(&parse("Print[[1.5, 2.5]]"), Profile::Standard, &[]), vec![]);
+}
+
+#[test]
+fn test_heap_values_are_rejected() {
+    assert_eq!(features("F[n: Int32] := [n, n]"), vec!["A List literal"]);
+    assert_eq!(features("F[n: Int32] := \"sensor\""), vec!["A String literal"]);
+    assert_eq!(features("F[xs: List[Int32]] := Map[Function[{x}, x], xs]"), vec!["List[Int32]", "Map"]);
+}
+
+#[test]
+fn test_floats_are_rejected() {
+    assert_eq!(features("F[x: Float64] := x"), vec!["Float64"]);
+    assert_eq!(features("F[n: Int32] := Convert[n, Float32] * 2.0"), vec!["Convert to Float32", "A Float literal"]);
+    assert_eq!(features("F[n: Int32] := Math.Abs[n]"), vec!["Math.Abs"]);
+}
+
+#[test]
+fn test_io_is_rejected() {
+    assert_eq!(features("F[n: Int32] := Print[n]"), vec!["Print"]);
+    assert_eq!(features("Print[1]"), vec!["A top-level statement", "Print"]);
+}
+
+#[test]
+fn test_struct_fields_are_checked() {
+    assert_eq!(features("Struct[Sensor, [name: String, id: UInt8]]"), vec!["String"]);
+}
+
+#[test]
+fn test_every_violation_is_reported() {
+    let input = "Avg[xs: List[Int32]] := Fold[Function[{acc, x}, acc + x], 0, xs] / 2.0\nName[n: Int32] := \"sensor\"";
+    assert_eq!(features(input), vec!["List[Int32]", "A Float literal", "A String literal"]);
+}
+
+#[test]
+fn test_violation_message() {
+    let violation = violations("F[n: Int32] := Print[n]").remove(0);
+    assert_eq!(violation.code(), "E0201");
+    assert_eq!(
+        violation.to_string(),
+        "Print is not allowed by the embedded profile: there is no console, file system or process"
+    );
+}
+
+// ============================================================================
+// Codegen Tests
+// ============================================================================
+
+#[test]
+fn test_embedded_is_no_std_without_alloc() {
+    let rust_code = generate(FIRMWARE);
+    assert!(rust_code.starts_with("#![no_std]\n"), "got: {}", rust_code);
+    assert!(!rust_code.contains("alloc"), "got: {}", rust_code);
+    assert!(!rust_code.contains("fn main"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_literal_panic_message_does_not_allocate() {
+    let rust_code = generate(FIRMWARE);
+    assert!(rust_code.contains("panic!(\"{}\", \"too big\")"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_firmware_compiles_without_std() {
+    let rust_code = generate("Mix[a: Int32, b: UInt8] := a * 3 + Convert[b, Int32]\nSmall[n: Int32] := Convert[n, UInt8]");
+    assert!(compile_library(&rust_code, "firmware"), "rustc rejected: {}", rust_code);
+}
+
+// ============================================================================
+// Driver Tests
+// ============================================================================
+
+#[test]
+fn test_driver_reports_every_violation() {
+    let options = CodegenOptions { profile: Profile::Embedded, ..CodegenOptions::default() };
+    let error = compile("F[x: Float64] := Print[x]", &options, &mut NoCallbacks).unwrap_err();
+    assert_eq!(error.code(), Some("E0201"));
+    match &error {
+        CompileError::Profile(violations) => assert_eq!(violations.len(), 2, "got: {:?}", violations),
+        other => panic!("expected Profile, got: {:?}", other),
+    }
+    assert_eq!(error.to_string().lines().count(), 2, "got: {}", error);
+}
+
+#[test]
+fn test_driver_locates_violations() {
+    let options = CodegenOptions { profile: Profile::Embedded, ..CodegenOptions::default() };
+    let error = compile("Double[x: Int32] := x * 2\nHalf[x: Float64] := x / 2.0", &options, &mut NoCallbacks).unwrap_err();
+    let rendered = error.render("firmware.w", "");
+    assert!(rendered.lines().all(|line| line.starts_with("error[E0201]: firmware.w:2:1: ")), "got: {}", rendered);
+}
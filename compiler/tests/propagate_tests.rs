@@ -8,34 +8,34 @@ use w::rust_codegen::RustCodeGenerator;
 #[test]
 fn test_question_token() {
     let mut lexer = Lexer::new("?".to_string());
-    assert_eq!(lexer.next_token(), Some(Token::Question));
-    assert_eq!(lexer.next_token(), None);
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Question));
+    assert_eq!(lexer.next_token().unwrap(), None);
 }
 
 #[test]
 fn test_question_after_identifier() {
     let mut lexer = Lexer::new("x?".to_string());
-    assert_eq!(lexer.next_token(), Some(Token::Identifier("x".to_string())));
-    assert_eq!(lexer.next_token(), Some(Token::Question));
-    assert_eq!(lexer.next_token(), None);
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Identifier("x".to_string())));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Question));
+    assert_eq!(lexer.next_token().unwrap(), None);
 }
 
 #[test]
 fn test_question_after_function_call() {
     let mut lexer = Lexer::new("F[x]?".to_string());
-    assert_eq!(lexer.next_token(), Some(Token::Identifier("F".to_string())));
-    assert_eq!(lexer.next_token(), Some(Token::LeftBracket));
-    assert_eq!(lexer.next_token(), Some(Token::Identifier("x".to_string())));
-    assert_eq!(lexer.next_token(), Some(Token::RightBracket));
-    assert_eq!(lexer.next_token(), Some(Token::Question));
-    assert_eq!(lexer.next_token(), None);
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Identifier("F".to_string())));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::LeftBracket));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Identifier("x".to_string())));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::RightBracket));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Question));
+    assert_eq!(lexer.next_token().unwrap(), None);
 }
 
 #[test]
 fn test_question_with_spaces() {
     let mut lexer = Lexer::new("x ?".to_string());
-    assert_eq!(lexer.next_token(), Some(Token::Identifier("x".to_string())));
-    assert_eq!(lexer.next_token(), Some(Token::Question));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Identifier("x".to_string())));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Question));
 }
 
 // ==================== Parser Tests ====================
@@ -87,7 +87,7 @@ fn test_parse_propagate_binds_tighter_than_binary_op() {
     match expr {
         Expression::BinaryOp { left, operator: _, right } => {
             match *left {
-                Expression::Number(1) => {},
+                Expression::Number(1, _) => {},
                 other => panic!("Expected Number(1), got {:?}", other),
             }
             match *right {
@@ -0,0 +1,113 @@
+mod common;
+
+use common::{parse, try_generate};
+use w::ast::{Expression, Type};
+use w::lexer::{Lexer, Token};
+use w::rust_codegen::RustCodeGenerator;
+use w::type_inference::{TypeError, TypeInference};
+
+// ============================================================================
+// Lexer / Parser Tests
+// ============================================================================
+
+#[test]
+fn test_lex_dot_and_path_separator() {
+    let mut lexer = Lexer::new("Math.Sqrt Geometry::Area".to_string());
+
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Identifier("Math".to_string())));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Dot));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Identifier("Sqrt".to_string())));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Identifier("Geometry".to_string())));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::PathSeparator));
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::Identifier("Area".to_string())));
+}
+
+#[test]
+fn test_parse_qualified_call() {
+    let expr = parse("Math.Sqrt[x]");
+
+    assert_eq!(expr, Expression::FunctionCall {
+        function: Box::new(Expression::QualifiedName {
+            path: vec!["Math".to_string()],
+            name: "Sqrt".to_string(),
+        }),
+        arguments: vec![Expression::Identifier("x".to_string())],
+    });
+}
+
+#[test]
+fn test_dot_and_path_separator_are_equivalent() {
+    assert_eq!(parse("Outer.Inner.Helper[1]"), parse("Outer::Inner::Helper[1]"));
+}
+
+#[test]
+fn test_qualified_call_as_operand_and_pipe_target() {
+    let sum = parse("Math.Sqrt[x] + Math.Sqrt[y]");
+    assert!(matches!(sum, Expression::BinaryOp { .. }), "got: {:?}", sum);
+
+    let piped = parse("x |> Math.Sqrt");
+    assert_eq!(piped, parse("Math.Sqrt[x]"));
+}
+
+// ============================================================================
+// Codegen Tests
+// ============================================================================
+
+#[test]
+fn test_builtin_qualified_call_generates_rust_path() {
+    let rust_code = try_generate("Hypot[a: Float64, b: Float64] := Math.Sqrt[a * a]").unwrap();
+
+    assert!(rust_code.contains("f64::sqrt((a * a))"), "got: {}", rust_code);
+    assert!(rust_code.contains("-> f64"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_builtin_coerces_integer_literals() {
+    let rust_code = try_generate("Print[Math.Pow[2, 10]]").unwrap();
+
+    assert!(rust_code.contains("f64::powf(2.0, 10.0)"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_builtin_arity_is_checked() {
+    assert!(try_generate("Print[Math.Sqrt[1, 2]]").is_err());
+}
+
+#[test]
+fn test_unresolved_qualified_name_is_an_error() {
+    assert!(try_generate("Print[Unknown.Helper[1]]").is_err());
+}
+
+#[test]
+fn test_qualified_call_into_generated_module() {
+    let modules = vec![(
+        "Geometry".to_string(),
+        parse("Struct[Point, [x: Int32, y: Int32]]\nSquare[x: Int32] := x * x"),
+    )];
+    let entry = parse("Print[Geometry.Square[4]]\nPrint[Geometry::Point[1, 2]]");
+
+    let mut codegen = RustCodeGenerator::new();
+    let files = codegen.generate_modules(&entry, &modules).unwrap();
+    let main = &files[0].contents;
+
+    assert!(main.contains("crate::geometry::square(4)"), "got: {}", main);
+    assert!(main.contains("crate::geometry::Point { x: 1, y: 2 }"), "got: {}", main);
+}
+
+// ============================================================================
+// Type Inference Tests
+// ============================================================================
+
+#[test]
+fn test_infer_builtin_call() {
+    let mut inference = TypeInference::new();
+    let ty = inference.infer_expression(&parse("Math.Max[1.5, 2]")).unwrap();
+    assert_eq!(ty, Type::Float64);
+}
+
+#[test]
+fn test_infer_unknown_qualified_name() {
+    let mut inference = TypeInference::new();
+    let err = inference.infer_expression(&parse("Nope.Thing[1]")).unwrap_err();
+    assert_eq!(err, TypeError::UndefinedIdentifier("Nope.Thing".to_string()));
+}
@@ -0,0 +1,133 @@
+mod common;
+
+use common::{compile_and_run, generate, parse, program};
+use w::ast::Type;
+use w::type_inference::{TypeError, TypeInference};
+
+const FACT: &str = "Fact[n: Int32] := Cond[[n == 0, 1], [n * Fact[n - 1]]]\n";
+
+fn check(input: &str) -> Result<(), TypeError> {
+    TypeInference::new().check_program(&program(input))
+}
+
+/// The type `name` is bound to after checking `input`
+fn type_of(input: &str, name: &str) -> Type {
+    let mut inference = TypeInference::new();
+    inference.check_program(&program(input)).unwrap();
+    inference.env().lookup(name).cloned().unwrap()
+}
+
+// ============================================================================
+// Type Inference Tests
+// ============================================================================
+
+#[test]
+fn test_recursive_function_type() {
+    assert_eq!(type_of(FACT, "Fact"), Type::Function(vec![Type::Int32], Box::new(Type::Int32)));
+}
+
+#[test]
+fn test_recursive_call_before_base_case() {
+    let source = "Fib[n: Int32] := If[n >= 2, Fib[n - 1] + Fib[n - 2], n]";
+    assert_eq!(type_of(source, "Fib"), Type::Function(vec![Type::Int32], Box::new(Type::Int32)));
+}
+
+#[test]
+fn test_recursive_function_called_afterwards() {
+    let result = check(&format!("{}Print[Fact[5] + 1]", FACT));
+    assert!(result.is_ok(), "got: {:?}", result);
+}
+
+#[test]
+fn test_recursive_result_checked_against_base_case() {
+    let result = check("Count[n: Int32] := If[n == 0, 0, Count[n - 1] + \"more\"]");
+    assert!(matches!(result, Err(TypeError::TypeMismatch { .. })), "got: {:?}", result);
+}
+
+#[test]
+fn test_recursive_argument_types_checked() {
+    let result = check("Count[n: Int32] := If[n == 0, 0, Count[\"less\"]]");
+    assert!(matches!(result, Err(TypeError::TypeMismatch { .. })), "got: {:?}", result);
+}
+
+#[test]
+fn test_recursion_without_base_case_never_returns() {
+    assert_eq!(type_of("Loop[n: Int32] := Loop[n + 1]", "Loop"), Type::Function(vec![Type::Int32], Box::new(Type::Never)));
+}
+
+#[test]
+fn test_parameter_shadows_function_name() {
+    let source = "Apply[Apply: Int32] := Apply * 2";
+    assert_eq!(type_of(source, "Apply"), Type::Function(vec![Type::Int32], Box::new(Type::Int32)));
+}
+
+#[test]
+fn test_mutually_recursive_function_types() {
+    let source = "IsEven[n: Int32] := If[n == 0, true, IsOdd[n - 1]]\n\
+                  IsOdd[n: Int32] := If[n == 0, false, IsEven[n - 1]]";
+    assert_eq!(type_of(source, "IsEven"), Type::Function(vec![Type::Int32], Box::new(Type::Bool)));
+    assert_eq!(type_of(source, "IsOdd"), Type::Function(vec![Type::Int32], Box::new(Type::Bool)));
+}
+
+#[test]
+fn test_call_before_definition_checked() {
+    let result = check("Let[a, Half[\"four\"]]\nHalf[n: Int32] := n / 2");
+    assert!(matches!(result, Err(TypeError::TypeMismatch { .. })), "got: {:?}", result);
+    let result = check("Let[a, Half[4] + \"s\"]\nHalf[n: Int32] := n / 2");
+    assert!(matches!(result, Err(TypeError::TypeMismatch { .. })), "got: {:?}", result);
+}
+
+// ============================================================================
+// Cond Syntax Tests
+// ============================================================================
+
+#[test]
+fn test_cond_with_commas_matches_spaces() {
+    assert_eq!(
+        parse("Cond[[x > 1, 1], [x < 0, 2], [3]]"),
+        parse("Cond[[x > 1 1] [x < 0 2] [3]]")
+    );
+}
+
+// ============================================================================
+// Code Generation Tests
+// ============================================================================
+
+#[test]
+fn test_recursive_call_in_first_branch_has_return_type() {
+    let rust_code = generate("Down[n: Int32] := If[n > 0, Down[n - 1], \"done\"]");
+    assert!(rust_code.contains("fn down(n: i32) -> String {"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_call_to_later_function_has_return_type() {
+    let rust_code = generate("Wrap[n: Int32] := Later[n]\nLater[n: Int32] := \"x{n}\"");
+    assert!(rust_code.contains("fn wrap(n: i32) -> String {"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_mutually_recursive_return_types() {
+    let rust_code = generate(
+        "IsEven[n: Int32] := If[n == 0, IsOdd[n + 1], IsOdd[n - 1]]\n\
+         IsOdd[n: Int32] := If[n == 0, false, IsEven[n - 1]]",
+    );
+    assert!(rust_code.contains("fn is_even(n: i32) -> bool {"), "got: {}", rust_code);
+    assert!(rust_code.contains("fn is_odd(n: i32) -> bool {"), "got: {}", rust_code);
+}
+
+// ============================================================================
+// End-to-End Tests
+// ============================================================================
+
+#[test]
+fn test_recursive_functions_run() {
+    let source = format!(
+        "{}Fib[n: Int32] := If[n < 2, n, Fib[n - 1] + Fib[n - 2]]\n\
+         Describe[n: Int32] := If[n > 0, Countdown[n], \"liftoff\"]\n\
+         Countdown[n: Int32] := Describe[n - 1]\n\
+         Print[Fact[5]]\nPrint[Fib[10]]\nPrint[Describe[3]]",
+        FACT
+    );
+    check(&source).unwrap();
+    assert_eq!(compile_and_run(&generate(&source), "run"), "120\n55\nliftoff\n");
+}
@@ -0,0 +1,76 @@
+mod common;
+
+use common::{compile_and_run, parse, program};
+use w::rust_codegen::RustCodeGenerator;
+use w::type_inference::{TypeError, TypeInference};
+
+fn errors(input: &str) -> Vec<TypeError> {
+    TypeInference::new().check_program_errors(&program(input))
+}
+
+fn recursive(cycle: &[&str]) -> TypeError {
+    TypeError::RecursiveType(cycle.iter().map(|name| name.to_string()).collect())
+}
+
+// ============================================================================
+// Detection Tests
+// ============================================================================
+
+#[test]
+fn test_struct_containing_itself() {
+    assert_eq!(errors("Struct[Node, [value: Int32, next: Node]]"), vec![recursive(&["Node", "Node"])]);
+}
+
+#[test]
+fn test_option_field_is_inline() {
+    assert_eq!(errors("Struct[Node, [value: Int32, next: Option[Node]]]"), vec![recursive(&["Node", "Node"])]);
+    assert_eq!(errors("Struct[Step, [next: Result[Step, String]]]"), vec![recursive(&["Step", "Step"])]);
+}
+
+#[test]
+fn test_cycle_through_other_struct_reported_once() {
+    let errors = errors("Struct[Edge, [weight: Int32, to: Node]]\nStruct[Node, [name: String, first: Option[Edge]]]");
+    assert_eq!(errors, vec![recursive(&["Node", "Edge", "Node"])]);
+}
+
+#[test]
+fn test_recursive_enum() {
+    assert_eq!(errors("Enum[Tree, [Leaf, Branch[Tree, Tree]]]"), vec![recursive(&["Tree", "Tree"])]);
+}
+
+#[test]
+fn test_cycle_through_enum() {
+    let errors = errors("Enum[Shape, [Dot, Group[Scene]]]\nStruct[Scene, [main: Shape]]");
+    assert_eq!(errors, vec![recursive(&["Scene", "Shape", "Scene"])]);
+}
+
+#[test]
+fn test_recursive_type_message() {
+    let errors = errors("Struct[Node, [next: Node]]");
+    assert_eq!(errors[0].code(), "E0028");
+    assert_eq!(
+        errors[0].to_string(),
+        "Type Node contains itself (Node -> Node), so it would have infinite size; hold the recursive value in a List, e.g. List[Node]"
+    );
+}
+
+// ============================================================================
+// Collection Tests
+// ============================================================================
+
+#[test]
+fn test_collections_break_the_cycle() {
+    assert_eq!(errors("Struct[Node, [value: Int32, children: List[Node]]]"), vec![]);
+    assert_eq!(errors("Enum[Json, [Null, Items[List[Json]], Fields[Map[String, Json]]]]"), vec![]);
+}
+
+#[test]
+fn test_list_of_itself_runs() {
+    let source = "Struct[Node, [value: Int32, children: List[Node]]]\n\
+                  Let[leaf, Node[1, []]]\n\
+                  Let[root, Node[2, [leaf]]]\n\
+                  Print[root.value]";
+    assert_eq!(errors(source), vec![]);
+    let rust_code = RustCodeGenerator::new().generate(&parse(source)).unwrap();
+    assert_eq!(compile_and_run(&rust_code, "list"), "2\n");
+}
@@ -0,0 +1,115 @@
+use w::cancellation::CancellationToken;
+use w::driver::{CompileError, CompilerCallbacks, NoCallbacks, ParseStats};
+use w::rust_codegen::CodegenOptions;
+use w::session::Session;
+
+use std::sync::Arc;
+use std::thread;
+
+/// Counts how many compilations actually ran
+#[derive(Default)]
+struct ParseCounter(usize);
+
+impl CompilerCallbacks for ParseCounter {
+    fn on_parse_complete(&mut self, _stats: &ParseStats) {
+        self.0 += 1;
+    }
+}
+
+fn compile(session: &Session, source: &str) -> Result<String, CompileError> {
+    session.compile(source, &CodegenOptions::default(), &mut NoCallbacks, &CancellationToken::new())
+}
+
+// ============================================================================
+// Builtin Registry Tests
+// ============================================================================
+
+#[test]
+fn test_session_builtins() {
+    let session = Session::new();
+    assert!(session.builtins().iter().any(|f| f.module == "Math" && f.name == "Sqrt"));
+    let sqrt = session.lookup_builtin(&["Math".to_string()], "Sqrt").unwrap();
+    assert_eq!(sqrt.rust_path, "f64::sqrt");
+    assert!(session.lookup_builtin(&["Math".to_string()], "Missing").is_none());
+}
+
+// ============================================================================
+// Cache Tests
+// ============================================================================
+
+#[test]
+fn test_repeated_compilation_is_cached() {
+    let session = Session::new();
+    let mut counter = ParseCounter::default();
+    let options = CodegenOptions::default();
+    let token = CancellationToken::new();
+    let first = session.compile("Print[1]", &options, &mut counter, &token).unwrap();
+    let second = session.compile("Print[1]", &options, &mut counter, &token).unwrap();
+    assert_eq!(first, second);
+    assert_eq!(counter.0, 1);
+    assert_eq!(session.cached_count(), 1);
+}
+
+#[test]
+fn test_cache_distinguishes_options() {
+    let session = Session::new();
+    let narrow = CodegenOptions { indent_width: 2, ..CodegenOptions::default() };
+    let token = CancellationToken::new();
+    let default_code = session.compile("Print[1]", &CodegenOptions::default(), &mut NoCallbacks, &token).unwrap();
+    let narrow_code = session.compile("Print[1]", &narrow, &mut NoCallbacks, &token).unwrap();
+    assert_ne!(default_code, narrow_code);
+    assert_eq!(session.cached_count(), 2);
+}
+
+#[test]
+fn test_errors_are_cached() {
+    let session = Session::new();
+    let first = compile(&session, "missing + 1");
+    assert!(matches!(first, Err(CompileError::Type(_))), "got: {:?}", first);
+    assert_eq!(compile(&session, "missing + 1"), first);
+    assert_eq!(session.cached_count(), 1);
+}
+
+#[test]
+fn test_cancelled_compilation_is_not_cached() {
+    let session = Session::new();
+    let token = CancellationToken::new();
+    token.cancel();
+    let result = session.compile("Print[1]", &CodegenOptions::default(), &mut NoCallbacks, &token);
+    assert_eq!(result, Err(CompileError::Cancelled));
+    assert_eq!(session.cached_count(), 0);
+    assert!(compile(&session, "Print[1]").is_ok());
+}
+
+#[test]
+fn test_clear_cache() {
+    let session = Session::new();
+    compile(&session, "Print[1]").unwrap();
+    session.clear_cache();
+    assert_eq!(session.cached_count(), 0);
+}
+
+// ============================================================================
+// Threading Tests
+// ============================================================================
+
+#[test]
+fn test_session_is_send_and_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Session>();
+}
+
+#[test]
+fn test_session_shared_across_threads() {
+    let session = Arc::new(Session::new());
+    let handles: Vec<_> = (0..4)
+        .map(|i| {
+            let session = Arc::clone(&session);
+            thread::spawn(move || compile(&session, &format!("Print[{}]", i % 2)).unwrap())
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(session.cached_count(), 2);
+}
@@ -0,0 +1,122 @@
+mod common;
+
+use common::{compile_and_run, generate, parse};
+use w::ast::{Expression, Type};
+use w::type_inference::{TypeError, TypeInference};
+
+fn infer(input: &str) -> Result<Type, TypeError> {
+    TypeInference::new().infer_expression(&parse(input))
+}
+
+fn check(input: &str) -> Result<(), TypeError> {
+    let expressions = match parse(input) {
+        Expression::Program(expressions) => expressions,
+        other => vec![other],
+    };
+    TypeInference::new().check_program(&expressions)
+}
+
+// ============================================================================
+// Parser Tests
+// ============================================================================
+
+#[test]
+fn test_untyped_lambda_parameters_are_inferred() {
+    match parse("Function[{a, b: String}, a]") {
+        Expression::Lambda { parameters, .. } => {
+            assert_eq!(parameters[0].type_, Type::Unknown);
+            assert_eq!(parameters[1].type_, Type::String);
+        }
+        other => panic!("Expected Lambda, got {:?}", other),
+    }
+}
+
+// ============================================================================
+// Type Inference Tests
+// ============================================================================
+
+#[test]
+fn test_sort_with_keeps_element_type() {
+    assert_eq!(
+        infer("SortWith[Function[{a, b}, CharCount[a] - CharCount[b]], [\"bb\", \"a\"]]"),
+        Ok(Type::List(Box::new(Type::String))),
+    );
+}
+
+#[test]
+fn test_comparator_parameters_take_element_type() {
+    // a and b are Strings here, so subtracting them is an error
+    let result = infer("SortWith[Function[{a, b}, a - b], [\"bb\", \"a\"]]");
+    assert!(result.is_err(), "got: {:?}", result);
+}
+
+#[test]
+fn test_comparator_must_take_two_arguments() {
+    let result = infer("SortWith[Function[{a}, 0], [1, 2]]");
+    assert!(matches!(result, Err(TypeError::ArityMismatch { expected: 2, actual: 1, .. })), "got: {:?}", result);
+}
+
+#[test]
+fn test_comparator_must_return_int() {
+    let result = infer("SortWith[Function[{a, b}, a < b], [1, 2]]");
+    match result {
+        Err(TypeError::TypeMismatch { expected, actual, context }) => {
+            assert_eq!(expected, Type::Int32);
+            assert_eq!(actual, Type::Bool);
+            assert!(context.contains("negative, zero or positive"), "got: {}", context);
+        }
+        other => panic!("Expected TypeMismatch, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_annotated_comparator_parameter_must_match() {
+    let result = infer("SortWith[Function[{a: String, b}, 0], [1, 2]]");
+    assert!(matches!(result, Err(TypeError::TypeMismatch { .. })), "got: {:?}", result);
+}
+
+#[test]
+fn test_named_comparator_signature_is_checked() {
+    assert!(check("Desc[a: Int32, b: Int32] := b - a\nF[xs: List[Int32]] := SortWith[Desc, xs]").is_ok());
+
+    let result = check("Desc[a: Int32, b: Int32] := b - a\nF[xs: List[String]] := SortWith[Desc, xs]");
+    assert!(matches!(result, Err(TypeError::TypeMismatch { .. })), "got: {:?}", result);
+}
+
+#[test]
+fn test_sort_with_requires_list() {
+    let result = infer("SortWith[Function[{a, b}, 0], 5]");
+    assert!(matches!(result, Err(TypeError::TypeMismatch { .. })), "got: {:?}", result);
+}
+
+// ============================================================================
+// Codegen Tests
+// ============================================================================
+
+#[test]
+fn test_codegen_sort_with_lambda() {
+    let rust_code = generate("Print[SortWith[Function[{a, b}, a - b], [3, 1]]]");
+    assert!(rust_code.contains("let mut __w_0_sorted = vec![3, 1];"), "got: {}", rust_code);
+    assert!(rust_code.contains("__w_0_sorted.sort_by(|a, b| {"), "got: {}", rust_code);
+    assert!(rust_code.contains(".cmp(&0)"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_codegen_named_comparator_uses_fresh_names() {
+    let rust_code = generate("Desc[a: Int32, b: Int32] := b - a\nPrint[SortWith[Desc, [1, 2]]]");
+    assert!(rust_code.contains("desc(__w_1_a.clone(), __w_2_b.clone()).cmp(&0)"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_sort_with_runs_and_is_stable() {
+    let rust_code = generate(
+        "Desc[a: Int32, b: Int32] := b - a\n\
+         Print[SortWith[Function[{a, b}, a - b], [3, 1, 2]]]\n\
+         Print[SortWith[Desc, [3, 1, 2]]]\n\
+         Print[SortWith[Function[{a, b}, CharCount[a] - CharCount[b]], [\"ccc\", \"x\", \"bb\", \"a\"]]]",
+    );
+    assert_eq!(
+        compile_and_run(&rust_code, "stable"),
+        "[1, 2, 3]\n[3, 2, 1]\n[\"x\", \"a\", \"bb\", \"ccc\"]\n",
+    );
+}
@@ -0,0 +1,78 @@
+use w::lexer::{Lexer, Span, SpannedToken, Token};
+use w::parser::Parser;
+
+fn spans(input: &str) -> Vec<SpannedToken> {
+    let mut lexer = Lexer::new(input.to_string());
+    std::iter::from_fn(|| lexer.next_spanned_token().unwrap()).collect()
+}
+
+// ============================================================================
+// Lexer Span Tests
+// ============================================================================
+
+#[test]
+fn test_spans_on_single_line() {
+    let tokens = spans("Print[42]");
+
+    assert_eq!(tokens[0], SpannedToken {
+        token: Token::Identifier("Print".to_string()),
+        span: Span { start: 0, end: 5, line: 1, column: 1 },
+    });
+    assert_eq!(tokens[1].span, Span { start: 5, end: 6, line: 1, column: 6 });
+    assert_eq!(tokens[2].span, Span { start: 6, end: 8, line: 1, column: 7 });
+    assert_eq!(tokens[3].span, Span { start: 8, end: 9, line: 1, column: 9 });
+}
+
+#[test]
+fn test_spans_track_lines_and_skip_comments() {
+    let tokens = spans("x := 1\n  (* note *) y\n\n\"s\"");
+
+    assert_eq!(tokens[1].token, Token::Define);
+    assert_eq!((tokens[1].span.line, tokens[1].span.column), (1, 3));
+    assert_eq!(tokens[3].token, Token::Identifier("y".to_string()));
+    assert_eq!((tokens[3].span.line, tokens[3].span.column), (2, 14));
+    assert_eq!(tokens[4].span, Span { start: 23, end: 26, line: 4, column: 1 });
+}
+
+#[test]
+fn test_spans_count_characters_not_bytes() {
+    let tokens = spans("\"héllo\" x");
+    assert_eq!(tokens[1].span, Span { start: 8, end: 9, line: 1, column: 9 });
+}
+
+#[test]
+fn test_line_column_of_offset() {
+    let lexer = Lexer::new("ab\ncd\n".to_string());
+
+    assert_eq!(lexer.line_column(0), (1, 1));
+    assert_eq!(lexer.line_column(2), (1, 3));
+    assert_eq!(lexer.line_column(3), (2, 1));
+    assert_eq!(lexer.line_column(6), (3, 1));
+}
+
+#[test]
+fn test_span_display() {
+    let span = Span { start: 4, end: 5, line: 3, column: 7 };
+    assert_eq!(span.to_string(), "3:7");
+}
+
+// ============================================================================
+// Parser Span Tests
+// ============================================================================
+
+#[test]
+fn test_parser_reports_where_parsing_stopped() {
+    let mut parser = Parser::new("Print[1]\nPrint[1, ]]".to_string());
+
+    assert!(parser.parse().is_none());
+    let span = parser.current_span();
+    assert_eq!((span.line, span.column), (2, 11), "got: {:?}", span);
+}
+
+#[test]
+fn test_parser_span_at_end_of_input() {
+    let mut parser = Parser::new("Square[x: Int32] :=\n".to_string());
+
+    assert!(parser.parse().is_none());
+    assert_eq!(parser.current_span().line, 2);
+}
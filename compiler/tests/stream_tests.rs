@@ -0,0 +1,188 @@
+mod common;
+
+use common::{compile_and_run, compile_and_run_with_stdin, generate, parse};
+use w::ast::{Expression, Type};
+use w::type_inference::{TypeError, TypeInference};
+
+fn infer(input: &str) -> Result<Type, TypeError> {
+    TypeInference::new().infer_expression(&parse(input))
+}
+
+// ============================================================================
+// Parser Tests
+// ============================================================================
+
+#[test]
+fn test_parse_stream_type() {
+    match parse("Evens[s: Stream[Int32]] := Take[3, s]") {
+        Expression::FunctionDefinition { parameters, .. } => {
+            assert_eq!(parameters[0].type_, Type::Stream(Box::new(Type::Int32)));
+        }
+        other => panic!("Expected function definition, got: {:?}", other),
+    }
+}
+
+// ============================================================================
+// Type Inference Tests
+// ============================================================================
+
+#[test]
+fn test_iterate_is_a_stream_of_the_seed_type() {
+    let result = infer("Iterate[Function[{x}, x * 2], 1]");
+    assert_eq!(result, Ok(Type::Stream(Box::new(Type::Int32))));
+}
+
+#[test]
+fn test_repeat_is_a_stream() {
+    let result = infer("Repeat[\"hi\"]");
+    assert_eq!(result, Ok(Type::Stream(Box::new(Type::String))));
+}
+
+#[test]
+fn test_take_collects_a_stream_into_a_list() {
+    let result = infer("Take[5, Iterate[Function[{x}, x + 1], 0]]");
+    assert_eq!(result, Ok(Type::List(Box::new(Type::Int32))));
+}
+
+#[test]
+fn test_map_over_stream_stays_lazy() {
+    let result = infer("Map[Function[{x}, x > 2], Repeat[1]]");
+    assert_eq!(result, Ok(Type::Stream(Box::new(Type::Bool))));
+}
+
+#[test]
+fn test_iterate_function_must_return_seed_type() {
+    let result = infer("Iterate[Function[{x}, x > 2], 1]");
+    assert!(matches!(result, Err(TypeError::TypeMismatch { .. })), "got: {:?}", result);
+}
+
+#[test]
+fn test_take_count_must_be_int32() {
+    let result = infer("Take[\"five\", Repeat[1]]");
+    assert!(matches!(result, Err(TypeError::TypeMismatch { .. })), "got: {:?}", result);
+}
+
+#[test]
+fn test_printing_unbounded_stream_is_rejected() {
+    let result = infer("Print[Repeat[1]]");
+    assert_eq!(result, Err(TypeError::UnboundedStream("Print".to_string())));
+}
+
+#[test]
+fn test_for_loop_consumes_stream_one_element_at_a_time() {
+    let result = infer("For[x, Iterate[Function[{n}, n + 1], 0], Print[x]]");
+    assert_eq!(result, Ok(Type::Tuple(vec![])));
+}
+
+#[test]
+fn test_lines_is_a_stream_of_strings() {
+    let result = infer("Lines[]");
+    assert_eq!(result, Ok(Type::Stream(Box::new(Type::String))));
+}
+
+#[test]
+fn test_map_builtin_by_name_over_lines() {
+    let result = infer("Map[ToUpper, Lines[]]");
+    assert_eq!(result, Ok(Type::Stream(Box::new(Type::String))));
+}
+
+#[test]
+fn test_lines_takes_no_arguments() {
+    let result = infer("Lines[\"input.txt\"]");
+    assert!(matches!(result, Err(TypeError::ArityMismatch { .. })), "got: {:?}", result);
+}
+
+#[test]
+fn test_printing_all_lines_is_rejected() {
+    let result = infer("Print[Lines[]]");
+    assert_eq!(result, Err(TypeError::UnboundedStream("Print".to_string())));
+}
+
+#[test]
+fn test_folding_unbounded_stream_is_rejected() {
+    let result = infer("Fold[Function[{acc, x}, acc + x], 0, Repeat[1]]");
+    assert_eq!(result, Err(TypeError::UnboundedStream("Fold".to_string())));
+}
+
+#[test]
+fn test_unbounded_stream_error_suggests_take() {
+    let message = TypeError::UnboundedStream("Print".to_string()).to_string();
+    assert!(message.contains("Take[n, stream]"), "got: {}", message);
+}
+
+// ============================================================================
+// Code Generation Tests
+// ============================================================================
+
+#[test]
+fn test_codegen_repeat_and_take() {
+    let code = generate("Take[2, Repeat[0]]");
+    assert!(code.contains("std::iter::repeat(0).take(2 as usize).collect::<Vec<_>>()"), "got: {}", code);
+}
+
+#[test]
+fn test_codegen_map_over_stream_does_not_collect() {
+    let code = generate("Let[s, Map[Function[{x}, x + 1], Repeat[0]]]\nPrint[Take[1, s]]");
+    assert!(code.contains("std::iter::repeat(0).map(|x| (x + 1));"), "got: {}", code);
+    assert!(code.contains("s.take(1 as usize)"), "got: {}", code);
+}
+
+#[test]
+fn test_codegen_lines_reads_stdin_lazily() {
+    let code = generate("For[line, Lines[], Print[line]]");
+    assert!(code.contains("std::io::BufRead::lines(std::io::stdin().lock()).map_while(Result::ok)"), "got: {}", code);
+    assert!(!code.contains("collect"), "got: {}", code);
+}
+
+#[test]
+fn test_codegen_map_builtin_by_name() {
+    let code = generate("Map[ToUpper, [\"a\"]]");
+    assert!(code.contains(".map(|__w_0_x| __w_0_x.to_uppercase())"), "got: {}", code);
+}
+
+#[test]
+fn test_codegen_map_over_list_still_collects() {
+    let code = generate("Map[Function[{x}, x + 1], [1, 2]]");
+    assert!(code.contains(".into_iter().map(|x| (x + 1)).collect::<Vec<_>>()"), "got: {}", code);
+}
+
+// ============================================================================
+// End-to-End Tests
+// ============================================================================
+
+#[test]
+fn test_run_iterate_never_computes_past_the_bound() {
+    // A 32nd doubling would overflow Int32 and panic
+    let code = generate("Print[Fold[Function[{acc, x}, acc + x], 0, Take[31, Iterate[Function[{x}, x * 2], 1]]]]");
+    assert_eq!(compile_and_run(&code, "bound"), "2147483647\n");
+}
+
+#[test]
+fn test_run_filtered_stream() {
+    let code = generate(
+        "Let[big, Filter[Function[{x}, x > 2], Iterate[Function[{n}, n + 1], 0]]]\n\
+         Print[Take[3, Map[Function[{x}, x * 10], big]]]",
+    );
+    assert_eq!(compile_and_run(&code, "filter"), "[30, 40, 50]\n");
+}
+
+#[test]
+fn test_run_iterate_with_named_function() {
+    let code = generate(
+        "Next[n: Int32] := n + 3\n\
+         Print[Take[4, Iterate[Next, 1]]]",
+    );
+    assert_eq!(compile_and_run(&code, "named"), "[1, 4, 7, 10]\n");
+}
+
+#[test]
+fn test_run_uppercase_pipeline() {
+    let code = generate("For[line, Map[ToUpper, Lines[]], Print[line]]");
+    assert_eq!(compile_and_run_with_stdin(&code, "upper", "hello\nworld\n"), "HELLO\nWORLD\n");
+}
+
+#[test]
+fn test_run_take_lines() {
+    let code = generate("Print[Take[2, Filter[Function[{line}, line != \"\"], Lines[]]]]");
+    assert_eq!(compile_and_run_with_stdin(&code, "take_lines", "a\n\nb\nc\n"), "[\"a\", \"b\"]\n");
+}
@@ -0,0 +1,74 @@
+mod common;
+
+use common::{compile_and_run, generate, parse};
+use w::ast::Type;
+use w::stdlib::string;
+use w::type_inference::{TypeError, TypeInference};
+
+fn infer(input: &str) -> Result<Type, TypeError> {
+    TypeInference::new().infer_expression(&parse(input))
+}
+
+// ============================================================================
+// Codegen Tests
+// ============================================================================
+
+#[test]
+fn test_char_count_counts_chars_not_bytes() {
+    let rust_code = generate("Print[CharCount[\"héllo\"]]");
+    assert!(rust_code.contains(".chars().count()"), "got: {}", rust_code);
+    assert!(!rust_code.contains(".len()"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_chars_prints_as_debug_list() {
+    let rust_code = generate("Print[Chars[\"añ\"]]");
+    assert!(rust_code.contains("{:?}"), "got: {}", rust_code);
+}
+
+/// Counts and substrings go by chars, and a substring past the end is cut short
+#[test]
+fn test_string_builtins_run() {
+    let rust_code = generate(
+        "Print[CharCount[\"héllo\"]]\n\
+         Print[Substring[\"naïve café\", 2, 5]]\n\
+         Print[Substring[\"abc\", 1, 10]]\n\
+         Print[Chars[\"añ\"]]",
+    );
+    assert_eq!(compile_and_run(&rust_code, "run"), "5\nïve c\nbc\n['a', 'ñ']\n");
+}
+
+#[test]
+fn test_string_builtin_return_types() {
+    let rust_code = generate("Initials[s: String] := Substring[s, 0, 2]\nLetters[s: String] := Chars[s]");
+    assert!(rust_code.contains("fn initials(s: String) -> String"), "got: {}", rust_code);
+    assert!(rust_code.contains("fn letters(s: String) -> Vec<char>"), "got: {}", rust_code);
+}
+
+// ============================================================================
+// Type Inference Tests
+// ============================================================================
+
+#[test]
+fn test_infer_string_builtins() {
+    assert_eq!(infer("CharCount[\"abc\"]").unwrap(), Type::Int32);
+    assert_eq!(infer("Substring[\"abc\", 0, 1]").unwrap(), Type::String);
+    assert_eq!(infer("Chars[\"abc\"]").unwrap(), Type::List(Box::new(Type::Char)));
+}
+
+#[test]
+fn test_string_builtins_check_arguments() {
+    assert!(matches!(infer("CharCount[42]"), Err(TypeError::TypeMismatch { .. })));
+    assert!(matches!(infer("Substring[\"abc\", 1]"), Err(TypeError::ArityMismatch { .. })));
+}
+
+// ============================================================================
+// Stdlib Tests
+// ============================================================================
+
+#[test]
+fn test_stdlib_string_helpers_are_unicode_aware() {
+    assert_eq!(string::char_count("héllo"), 5);
+    assert_eq!(string::substring("héllo", 1, 3), "éll");
+    assert_eq!(string::chars("añ"), vec!['a', 'ñ']);
+}
@@ -0,0 +1,69 @@
+mod common;
+
+use common::{compile_and_run, generate, parse};
+use w::ast::Type;
+use w::type_inference::{TypeError, TypeInference};
+
+fn infer(input: &str) -> Result<Type, TypeError> {
+    TypeInference::new().infer_expression(&parse(input))
+}
+
+// ============================================================================
+// Type Inference Tests
+// ============================================================================
+
+#[test]
+fn test_string_add_is_string() {
+    assert_eq!(infer("\"a\" + \"b\""), Ok(Type::String));
+}
+
+#[test]
+fn test_string_plus_number_is_rejected() {
+    assert!(matches!(infer("\"a\" + 1"), Err(TypeError::TypeMismatch { .. })));
+    assert!(matches!(infer("1 + \"a\""), Err(TypeError::TypeMismatch { .. })));
+}
+
+#[test]
+fn test_only_add_concatenates() {
+    assert!(matches!(infer("\"ab\" - \"b\""), Err(TypeError::TypeMismatch { .. })));
+}
+
+#[test]
+fn test_strings_compare() {
+    assert_eq!(infer("\"a\" < \"b\""), Ok(Type::Bool));
+    assert_eq!(infer("\"a\" == \"b\""), Ok(Type::Bool));
+}
+
+// ============================================================================
+// Codegen Tests
+// ============================================================================
+
+#[test]
+fn test_chain_becomes_one_format() {
+    let rust_code = generate("Greet[name: String] := \"Hello, \" + name + \"!\"");
+    assert!(rust_code.contains("fn greet(name: String) -> String"), "got: {}", rust_code);
+    assert!(rust_code.contains("format!(\"Hello, {}!\", name)"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_literal_braces_are_escaped() {
+    let rust_code = generate("Wrap[s: String] := \"{\" + s + \"}\"");
+    assert!(rust_code.contains("format!(\"{{{}}}\", s)"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_numbers_still_add() {
+    let rust_code = generate("Inc[n: Int32] := n + 1");
+    assert!(rust_code.contains("(n + 1)"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_concatenation_runs() {
+    let input = "Greet[name: String] := \"Hello, \" + name + \"!\"\n\
+                 Let[a, \"ab\"]\n\
+                 Let[b, a + \"c\" + Greet[\"W\"]]\n\
+                 Print[b]\n\
+                 Print[a < b]\n\
+                 Print[a == \"ab\"]";
+    assert_eq!(compile_and_run(&generate(input), "runs"), "abcHello, W!\ntrue\ntrue\n");
+}
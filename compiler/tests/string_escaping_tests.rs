@@ -0,0 +1,101 @@
+mod common;
+
+use common::{compile_and_run, generate};
+use w::codegen_utils::{escape_format_braces, rust_string_literal};
+use w::lexer::{LexError, Lexer, Token};
+
+// ============================================================================
+// Literal Rendering Tests
+// ============================================================================
+
+#[test]
+fn test_plain_strings_use_ordinary_literals() {
+    assert_eq!(rust_string_literal("hello"), r#""hello""#);
+    assert_eq!(rust_string_literal(""), r#""""#);
+}
+
+#[test]
+fn test_quotes_and_backslashes_use_raw_strings() {
+    assert_eq!(rust_string_literal(r#"say "hi""#), r###"r#"say "hi""#"###);
+    assert_eq!(rust_string_literal(r"C:\path"), r###"r"C:\path""###);
+}
+
+#[test]
+fn test_raw_string_fence_outgrows_content() {
+    assert_eq!(rust_string_literal(r###"a"##b"###), r####"r###"a"##b"###"####);
+}
+
+#[test]
+fn test_control_characters_are_escaped() {
+    assert_eq!(rust_string_literal("a\"b\nc"), r#""a\"b\nc""#);
+    assert_eq!(rust_string_literal("tab\there"), r#""tab\there""#);
+}
+
+#[test]
+fn test_escape_format_braces() {
+    assert_eq!(escape_format_braces("{} {x}"), "{{}} {{x}}");
+}
+
+// ============================================================================
+// Lexer Escape Tests
+// ============================================================================
+
+#[test]
+fn test_lexer_processes_escapes() {
+    let mut lexer = Lexer::new(r#""q\"b\\n\n\tz""#.to_string());
+    assert_eq!(lexer.next_token().unwrap(), Some(Token::String("q\"b\\n\n\tz".to_string())));
+    assert_eq!(lexer.next_token().unwrap(), None);
+}
+
+#[test]
+fn test_lexer_rejects_unknown_escapes() {
+    let mut lexer = Lexer::new(r#""\d+""#.to_string());
+    assert!(matches!(lexer.next_token(), Err(LexError::InvalidEscape { escape: 'd', .. })));
+}
+
+// ============================================================================
+// Generated Code Tests (compiled with rustc)
+// ============================================================================
+
+#[test]
+fn test_adversarial_strings_print_verbatim() {
+    let rust_code = generate(r#"Print["{{}}", "{{0}} {{x:?}}", "quote \" here", "back\\slash", "C:\temp\new"]"#);
+
+    let stdout = compile_and_run(&rust_code, "print");
+    assert_eq!(stdout, "{} {0} {x:?} quote \" here back\\slash C:\temp\new\n");
+}
+
+#[test]
+fn test_strings_inside_values_print_verbatim() {
+    let rust_code = generate(
+        "Let[label, Match[\"}{\", [\"}{\", \"braces\"], [_, \"a\\\"b\"]]]\n\
+         Print[[\"say \\\"hi\\\"\", \"}{\", \"back\\\\slash\"]]\n\
+         Print[label, \"a\\\"b\"]",
+    );
+
+    let stdout = compile_and_run(&rust_code, "values");
+    assert_eq!(stdout, "[\"say \\\"hi\\\"\", \"}{\", \"back\\\\slash\"]\nbraces a\"b\n");
+}
+
+#[test]
+fn test_interpolation_with_quotes_and_braces() {
+    let rust_code = generate(
+        "Show[x: Int32] := \"\\\"{x}\\\" {{raw}} \\\\\"\nPrint[Show[7]]",
+    );
+    assert!(rust_code.contains("format!(r#\""), "got: {}", rust_code);
+
+    let stdout = compile_and_run(&rust_code, "interpolation");
+    assert_eq!(stdout, "\"7\" {raw} \\\n");
+}
+
+#[test]
+fn test_string_pattern_with_quotes() {
+    let rust_code = generate(r#"Quoted[s: String] := Match[s, ["\"", 1], [_, 0]]"#);
+    assert!(rust_code.contains(r##"s if s == r#"""#"##), "got: {}", rust_code);
+}
+
+#[test]
+fn test_log_message_is_not_a_format_string() {
+    let rust_code = generate(r#"LogInfo["50% {} done"]"#);
+    assert!(rust_code.contains(r#"__w_log::info("50% {} done".to_string())"#), "got: {}", rust_code);
+}
@@ -0,0 +1,110 @@
+mod common;
+
+use common::{generate, parse};
+use w::ast::{Expression, StringPart, Type};
+use w::optimizer::Optimizer;
+use w::rust_codegen::RustCodeGenerator;
+use w::type_inference::TypeInference;
+
+// ============================================================================
+// Parser Tests
+// ============================================================================
+
+#[test]
+fn test_parse_interpolated_string() {
+    let expr = parse(r#""value is {x}!""#);
+
+    assert_eq!(expr, Expression::InterpolatedString(vec![
+        StringPart::Literal("value is ".to_string()),
+        StringPart::Expression {
+            value: Expression::Identifier("x".to_string()),
+            format_spec: None,
+        },
+        StringPart::Literal("!".to_string()),
+    ]));
+}
+
+#[test]
+fn test_plain_string_stays_a_string() {
+    assert_eq!(parse(r#""hello""#), Expression::String("hello".to_string()));
+}
+
+#[test]
+fn test_non_expression_braces_stay_literal() {
+    assert_eq!(parse(r#""{} and {a: 1}""#), Expression::String("{} and {a: 1}".to_string()));
+}
+
+#[test]
+fn test_doubled_braces_are_escapes() {
+    assert_eq!(parse(r#""{{x}}""#), Expression::String("{x}".to_string()));
+}
+
+#[test]
+fn test_placeholder_with_call_and_format_spec() {
+    let expr = parse(r#""{Square[x]} {items:?}""#);
+
+    let Expression::InterpolatedString(parts) = expr else {
+        panic!("expected an interpolated string, got: {:?}", expr);
+    };
+    assert!(matches!(&parts[0], StringPart::Expression { value: Expression::FunctionCall { .. }, format_spec: None }));
+    assert_eq!(parts[2], StringPart::Expression {
+        value: Expression::Identifier("items".to_string()),
+        format_spec: Some("?".to_string()),
+    });
+}
+
+// ============================================================================
+// Codegen Tests
+// ============================================================================
+
+#[test]
+fn test_interpolation_generates_format() {
+    let rust_code = generate(r#"Greet[name: String] := "Hello, {name}!""#);
+
+    assert!(rust_code.contains(r#"format!("Hello, {}!", name)"#), "got: {}", rust_code);
+    assert!(rust_code.contains("-> String"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_interpolation_uses_debug_for_collections() {
+    let rust_code = generate(r#"Show[x: Int32] := "items: {[1, 2]} total: {x + 1}""#);
+
+    assert!(rust_code.contains(r#"format!("items: {:?} total: {}", vec![1, 2], (x + 1))"#),
+        "got: {}", rust_code);
+}
+
+#[test]
+fn test_interpolation_escapes_literal_braces() {
+    let rust_code = generate(r#"Show[x: Int32] := "{{{x}}} {}""#);
+
+    assert!(rust_code.contains(r#"format!("{{{}}} {{}}", x)"#), "got: {}", rust_code);
+}
+
+#[test]
+fn test_interpolation_format_spec() {
+    let rust_code = generate(r#"Show[x: Float64] := "{x:.2}""#);
+
+    assert!(rust_code.contains(r#"format!("{:.2}", x)"#), "got: {}", rust_code);
+}
+
+// ============================================================================
+// Type Inference / Optimizer Tests
+// ============================================================================
+
+#[test]
+fn test_interpolated_string_infers_as_string() {
+    let mut inference = TypeInference::new();
+    let ty = inference.infer_expression(&parse(r#""{1 + 2}""#)).unwrap();
+    assert_eq!(ty, Type::String);
+}
+
+#[test]
+fn test_cse_sees_calls_inside_placeholders() {
+    let expr = Optimizer::new().optimize(&parse(
+        "F[x: Int32] := x * x\nG[x: Int32] := \"{F[x]} {F[x]}\"",
+    ));
+    let mut codegen = RustCodeGenerator::new();
+    let rust_code = codegen.generate(&expr).unwrap();
+
+    assert!(rust_code.contains(r#"format!("{} {}", __w_0_cse, __w_0_cse)"#), "got: {}", rust_code);
+}
@@ -0,0 +1,129 @@
+mod common;
+
+use common::{compile_and_run, generate, parse};
+use w::ast::{Expression, Pattern, Type};
+use w::parser::Parser;
+use w::rust_codegen::RustCodeGenerator;
+use w::type_inference::{TypeError, TypeInference};
+
+fn check(input: &str) -> Result<(), TypeError> {
+    let expressions = match parse(input) {
+        Expression::Program(expressions) => expressions,
+        other => vec![other],
+    };
+    TypeInference::new().check_program(&expressions)
+}
+
+/// Patterns of the arms of a single Match expression
+fn arm_patterns(input: &str) -> Vec<Pattern> {
+    match parse(input) {
+        Expression::Match { arms, .. } => arms.into_iter().map(|(pattern, _)| pattern).collect(),
+        other => panic!("Expected Match expression, got: {:?}", other),
+    }
+}
+
+// ============================================================================
+// Parser Tests
+// ============================================================================
+
+#[test]
+fn test_parse_starts_with_pattern() {
+    let patterns = arm_patterns("Match[line, [StartsWith[\"#\"], 1], [_, 0]]");
+    assert_eq!(patterns[0], Pattern::StartsWith("#".to_string()));
+}
+
+#[test]
+fn test_parse_ends_with_pattern() {
+    let patterns = arm_patterns("Match[file, [EndsWith[\".w\"], 1], [_, 0]]");
+    assert_eq!(patterns[0], Pattern::EndsWith(".w".to_string()));
+}
+
+#[test]
+fn test_parse_affix_pattern_requires_string_literal() {
+    let mut parser = Parser::new("Match[line, [StartsWith[prefix], 1], [_, 0]]".to_string());
+    assert!(parser.parse().is_none());
+}
+
+#[test]
+fn test_display_affix_patterns() {
+    assert_eq!(Pattern::StartsWith("GET ".to_string()).to_string(), "StartsWith[\"GET \"]");
+    assert_eq!(Pattern::EndsWith(";".to_string()).to_string(), "EndsWith[\";\"]");
+}
+
+#[test]
+fn test_affix_patterns_are_refutable() {
+    assert!(!Pattern::StartsWith("a".to_string()).is_irrefutable());
+}
+
+// ============================================================================
+// Type Inference Tests
+// ============================================================================
+
+#[test]
+fn test_affix_patterns_match_strings() {
+    let result = check("Let[line, \"# note\"]\nMatch[line, [StartsWith[\"#\"], 1], [EndsWith[\";\"], 2], [_, 0]]");
+    assert_eq!(result, Ok(()));
+}
+
+#[test]
+fn test_affix_pattern_on_non_string_is_rejected() {
+    let result = check("Let[n, 5]\nMatch[n, [StartsWith[\"5\"], 1], [_, 0]]");
+    match result {
+        Err(TypeError::TypeMismatch { expected, actual, context }) => {
+            assert_eq!(expected, Type::String);
+            assert_eq!(actual, Type::Int32);
+            assert_eq!(context, "StartsWith[\"5\"] pattern");
+        }
+        other => panic!("Expected type mismatch, got: {:?}", other),
+    }
+}
+
+#[test]
+fn test_string_patterns_outside_match_are_rejected() {
+    for (input, construct) in [
+        ("LetElse[StartsWith[\"#\"], \"# note\", Print[\"no\"]]", "LetElse"),
+        ("LetElse[(\"a\", x), (\"a\", 1), Print[0]]", "LetElse"),
+        ("IfLet[Some[EndsWith[\";\"]], Some[\"a;\"], Print[1]]", "IfLet"),
+        ("WhileLet[Some[\"x\"], None, Print[1]]", "WhileLet"),
+    ] {
+        match check(input) {
+            Err(error @ TypeError::StringPatternOutsideMatch { .. }) => {
+                assert_eq!(error.code(), "E0033");
+                assert!(error.to_string().starts_with(&format!("{} cannot test the string pattern", construct)), "got: {}", error);
+            }
+            other => panic!("Expected StringPatternOutsideMatch for {}, got: {:?}", input, other),
+        }
+    }
+}
+
+// ============================================================================
+// Code Generation Tests
+// ============================================================================
+
+#[test]
+fn test_codegen_starts_with_guard() {
+    let code = generate("Let[line, \"# note\"]\nPrint[Match[line, [StartsWith[\"#\"], 1], [_, 0]]]");
+    assert!(code.contains("s if s.starts_with(\"#\") => 1"), "got: {}", code);
+}
+
+#[test]
+fn test_codegen_ends_with_guard() {
+    let code = generate("Let[line, \"a;\"]\nPrint[Match[line, [EndsWith[\";\"], 1], [_, 0]]]");
+    assert!(code.contains("s if s.ends_with(\";\") => 1"), "got: {}", code);
+}
+
+#[test]
+fn test_affix_pattern_cannot_be_a_let_binding() {
+    let mut codegen = RustCodeGenerator::new();
+    let result = codegen.generate(&parse("LetElse[StartsWith[\"#\"], \"# note\", Print[\"no\"]]"));
+    assert!(result.is_err(), "got: {:?}", result);
+}
+
+#[test]
+fn test_run_classify_lines() {
+    let code = generate(
+        "Let[lines, [\"# title\", \"x = 1;\", \"other\"]]\n\
+         For[line, lines, Print[Match[line, [StartsWith[\"#\"], \"comment\"], [EndsWith[\";\"], \"statement\"], [_, line]]]]",
+    );
+    assert_eq!(compile_and_run(&code, "classify"), "comment\nstatement\nother\n");
+}
@@ -0,0 +1,107 @@
+mod common;
+
+use common::{compile_and_run, parse, try_generate};
+use w::ast::{Expression, Pattern};
+use w::type_inference::{TypeError, TypeInference};
+
+fn check(input: &str) -> Result<(), TypeError> {
+    let expressions = match parse(input) {
+        Expression::Program(expressions) => expressions,
+        other => vec![other],
+    };
+    TypeInference::new().check_program(&expressions)
+}
+
+const POINT: &str = "Struct[Point, [x: Int32, y: Int32, z: Int32]]\n";
+
+// ============================================================================
+// Parser Tests
+// ============================================================================
+
+#[test]
+fn test_parse_rest_pattern() {
+    match parse("Match[p, [Point[x, ..], x]]") {
+        Expression::Match { arms, .. } => {
+            assert_eq!(arms[0].0, Pattern::Constructor {
+                name: "Point".to_string(),
+                patterns: vec![Pattern::Variable("x".to_string()), Pattern::Rest],
+            });
+        }
+        other => panic!("Expected Match, got: {:?}", other),
+    }
+}
+
+// ============================================================================
+// Codegen Tests
+// ============================================================================
+
+#[test]
+fn test_struct_pattern_uses_field_names() {
+    let rust_code = try_generate(&format!("{}GetX[p: Point] := Match[p, [Point[x, _, z], x + z]]", POINT)).unwrap();
+    assert!(rust_code.contains("Point { x, y: _, z }"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_struct_pattern_with_rest() {
+    let rust_code = try_generate(&format!("{}GetX[p: Point] := Match[p, [Point[x, ..], x]]", POINT)).unwrap();
+    assert!(rust_code.contains("Point { x, .. }"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_struct_pattern_renames_binding() {
+    let rust_code = try_generate(&format!("{}GetX[p: Point] := Match[p, [Point[a, b, ..], a + b]]", POINT)).unwrap();
+    assert!(rust_code.contains("Point { x: a, y: b, .. }"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_struct_pattern_without_rest_must_cover_all_fields() {
+    assert!(try_generate(&format!("{}GetX[p: Point] := Match[p, [Point[x], x]]", POINT)).is_err());
+}
+
+#[test]
+fn test_struct_pattern_runs() {
+    let rust_code = try_generate(&format!(
+        "{}Show[p: Point] := Match[p, [Point[0, y, ..], Print[y]], [Point[x, ..], Print[x]]]\nShow[Point[0, 5, 9]]\nShow[Point[3, 5, 9]]",
+        POINT,
+    )).unwrap();
+    assert_eq!(compile_and_run(&rust_code, "run"), "5\n3\n");
+}
+
+// ============================================================================
+// Type Inference Tests
+// ============================================================================
+
+#[test]
+fn test_struct_pattern_binds_field_types() {
+    assert!(check(&format!("{}GetX[p: Point] := Match[p, [Point[x, ..], x + 1]]", POINT)).is_ok());
+}
+
+#[test]
+fn test_struct_pattern_checks_field_types() {
+    let result = check(&format!("{}GetX[p: Point] := Match[p, [Point[\"a\", ..], 1]]", POINT));
+    assert!(matches!(result, Err(TypeError::TypeMismatch { .. })), "got: {:?}", result);
+}
+
+#[test]
+fn test_list_rest_pattern_type_checks() {
+    assert!(check("First[xs: List[Int32]] := Match[xs, [[first, ..], first], [_, 0]]").is_ok());
+    assert!(check("First[xs: List[Int32]] := Match[xs, [[.., x, ..], x], [_, 0]]").is_err());
+}
+
+#[test]
+fn test_struct_pattern_too_many_fields() {
+    let result = check(&format!("{}GetX[p: Point] := Match[p, [Point[a, b, c, d], a]]", POINT));
+    assert!(matches!(result, Err(TypeError::FieldCountMismatch { .. })), "got: {:?}", result);
+}
+
+#[test]
+fn test_struct_pattern_missing_fields_without_rest() {
+    let result = check(&format!("{}GetX[p: Point] := Match[p, [Point[a], a]]", POINT));
+    assert!(matches!(result, Err(TypeError::FieldCountMismatch { .. })), "got: {:?}", result);
+}
+
+#[test]
+fn test_struct_pattern_against_other_type() {
+    let result = check(&format!("{}GetX[n: Int32] := Match[n, [Point[x, ..], x]]", POINT));
+    assert!(matches!(result, Err(TypeError::TypeMismatch { .. })), "got: {:?}", result);
+}
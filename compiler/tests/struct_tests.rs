@@ -15,7 +15,7 @@ fn test_parse_struct_definition_simple() {
     assert!(result.is_some(), "Failed to parse struct definition");
 
     match result.unwrap() {
-        Expression::StructDefinition { name, fields } => {
+        Expression::StructDefinition { name, fields, .. } => {
             assert_eq!(name, "Point");
             assert_eq!(fields.len(), 2);
             assert_eq!(fields[0].name, "x");
@@ -36,7 +36,7 @@ fn test_parse_struct_definition_with_string() {
     assert!(result.is_some());
 
     match result.unwrap() {
-        Expression::StructDefinition { name, fields } => {
+        Expression::StructDefinition { name, fields, .. } => {
             assert_eq!(name, "Person");
             assert_eq!(fields.len(), 2);
             assert_eq!(fields[0].name, "name");
@@ -57,7 +57,7 @@ fn test_parse_struct_definition_with_float() {
     assert!(result.is_some());
 
     match result.unwrap() {
-        Expression::StructDefinition { name, fields } => {
+        Expression::StructDefinition { name, fields, .. } => {
             assert_eq!(name, "Circle");
             assert_eq!(fields.len(), 1);
             assert_eq!(fields[0].name, "radius");
@@ -265,7 +265,7 @@ fn test_empty_struct() {
     assert!(result.is_some());
 
     match result.unwrap() {
-        Expression::StructDefinition { name, fields } => {
+        Expression::StructDefinition { name, fields, .. } => {
             assert_eq!(name, "Empty");
             assert_eq!(fields.len(), 0);
         }
@@ -0,0 +1,77 @@
+mod common;
+
+use common::{compile_and_run, generate, parse};
+use w::ast::Type;
+use w::parser::Parser;
+use w::type_inference::{TypeError, TypeInference};
+
+fn infer(input: &str) -> Result<Type, TypeError> {
+    TypeInference::new().infer_expression(&parse(input))
+}
+
+// ============================================================================
+// Parser Tests
+// ============================================================================
+
+#[test]
+fn test_table_desugars_to_map_over_range() {
+    assert_eq!(
+        parse("Table[x * x, {x, 1, 10}]"),
+        parse("Map[Function[{x}, x * x], Range[1, 10]]")
+    );
+}
+
+#[test]
+fn test_table_condition_desugars_to_filter() {
+    assert_eq!(
+        parse("Table[x * x, {x, 1, 10}, x > 3]"),
+        parse("Map[Function[{x}, x * x], Filter[Function[{x}, x > 3], Range[1, 10]]]")
+    );
+}
+
+#[test]
+fn test_table_needs_iterator() {
+    let mut parser = Parser::new("Table[x * x, x]".to_string());
+    assert_eq!(parser.parse(), None);
+    let mut parser = Parser::new("Table[x * x, {x, 10}]".to_string());
+    assert_eq!(parser.parse(), None);
+}
+
+// ============================================================================
+// Type Inference Tests
+// ============================================================================
+
+#[test]
+fn test_range_is_list_of_bound_type() {
+    assert_eq!(infer("Range[1, 10]"), Ok(Type::List(Box::new(Type::Int32))));
+}
+
+#[test]
+fn test_range_bounds_are_integers() {
+    assert!(matches!(infer("Range[1.0, 2.0]"), Err(TypeError::TypeMismatch { .. })));
+    assert!(matches!(infer("Range[1, 2.0]"), Err(TypeError::TypeMismatch { .. })));
+}
+
+#[test]
+fn test_table_is_list() {
+    assert!(matches!(infer("Table[x * x, {x, 1, 10}]"), Ok(Type::List(_))));
+}
+
+// ============================================================================
+// Codegen Tests
+// ============================================================================
+
+#[test]
+fn test_range_is_inclusive() {
+    let rust_code = generate("Print[Range[3, 5]]");
+    assert!(rust_code.contains("println!(\"{:?}\", (3..=5).collect::<Vec<_>>());"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_table_runs() {
+    let input = "Print[Table[x * x, {x, 1, 5}]]\n\
+                 Print[Table[x * 10, {x, 1, 10}, x > 7]]\n\
+                 Let[n, 4]\n\
+                 Print[Table[i + n, {i, n, n + 2}]]";
+    assert_eq!(compile_and_run(&generate(input), "runs"), "[1, 4, 9, 16, 25]\n[80, 90, 100]\n[8, 9, 10]\n");
+}
@@ -0,0 +1,189 @@
+mod common;
+
+use common::{compile_and_run, generate, parse};
+use w::ast::{Expression, TraitMethod, Type, TypeAnnotation};
+use w::parser::Parser;
+use w::type_inference::{TypeError, TypeInference};
+
+fn check(input: &str) -> Result<(), TypeError> {
+    let expressions = match parse(input) {
+        Expression::Program(expressions) => expressions,
+        other => vec![other],
+    };
+    TypeInference::new().check_program(&expressions)
+}
+
+const PRINTABLE: &str = "Struct[Point, [x: Int32, y: Int32]]\n\
+                         Struct[Label, [text: String]]\n\
+                         Trait[Printable, [ToText[self] -> String, Weight[self, factor: Int32] -> Int32]]\n\
+                         Impl[Printable, Point,\n\
+                           ToText[self] := \"({self.x}, {self.y})\",\n\
+                           Weight[self, factor] := (self.x + self.y) * factor]\n\
+                         Impl[Printable, Label,\n\
+                           ToText[self] := self.text,\n\
+                           Weight[self, factor] := factor]\n";
+
+// ============================================================================
+// Parser Tests
+// ============================================================================
+
+#[test]
+fn test_parse_trait_definition() {
+    match parse("Trait[Printable, [ToText[self] -> String, Weight[self, factor: Int32] -> Int32]]") {
+        Expression::TraitDefinition { name, methods, .. } => {
+            assert_eq!(name, "Printable");
+            assert_eq!(methods, vec![
+                TraitMethod { name: "ToText".to_string(), parameters: vec![], return_type: Type::String },
+                TraitMethod {
+                    name: "Weight".to_string(),
+                    parameters: vec![TypeAnnotation { name: "factor".to_string(), type_: Type::Int32, default: None }],
+                    return_type: Type::Int32,
+                },
+            ]);
+        }
+        other => panic!("Expected trait definition, got: {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_impl_methods_without_self() {
+    match parse("Impl[Printable, Point, ToText[self] := \"p\", Weight[self, factor] := factor]") {
+        Expression::Impl { trait_name, type_name, methods } => {
+            assert_eq!(trait_name, "Printable");
+            assert_eq!(type_name, "Point");
+            assert_eq!(methods.len(), 2);
+            match &methods[1] {
+                Expression::FunctionDefinition { name, parameters, .. } => {
+                    assert_eq!(name, "Weight");
+                    assert_eq!(parameters, &vec![TypeAnnotation { name: "factor".to_string(), type_: Type::Unknown, default: None }]);
+                }
+                other => panic!("Expected method definition, got: {:?}", other),
+            }
+        }
+        other => panic!("Expected impl, got: {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_trait_method_requires_self() {
+    let mut parser = Parser::new("Trait[Printable, [ToText[x: Int32] -> String]]".to_string());
+    assert!(parser.parse().is_none());
+}
+
+#[test]
+fn test_parse_private_trait() {
+    match parse("Private[Trait[Shape, [Area[self] -> Float64]]]") {
+        Expression::TraitDefinition { visibility, .. } => assert_eq!(visibility, w::ast::Visibility::Private),
+        other => panic!("Expected trait definition, got: {:?}", other),
+    }
+}
+
+// ============================================================================
+// Type Inference Tests
+// ============================================================================
+
+#[test]
+fn test_trait_program_type_checks() {
+    let source = format!("{}Show[p: Printable] := ToText[p]\nPrint[Show[Point[1, 2]]]\nPrint[Weight[Label[\"a\"], 3]]", PRINTABLE);
+    assert_eq!(check(&source), Ok(()));
+}
+
+#[test]
+fn test_method_call_returns_declared_type() {
+    let source = format!("{}Let[p, Point[1, 2]]", PRINTABLE);
+    let mut expressions = match parse(&source) {
+        Expression::Program(expressions) => expressions,
+        other => vec![other],
+    };
+    expressions.push(parse("Weight[p, 2]"));
+    let last = expressions.pop().unwrap();
+    let mut inference = TypeInference::new();
+    inference.check_program(&expressions).unwrap();
+    assert_eq!(inference.infer_expression(&last), Ok(Type::Int32));
+}
+
+#[test]
+fn test_impl_of_undefined_trait_is_rejected() {
+    let result = check("Struct[Point, [x: Int32]]\nImpl[Drawable, Point, Draw[self] := 1]");
+    assert_eq!(result, Err(TypeError::UndefinedTrait("Drawable".to_string())));
+}
+
+#[test]
+fn test_impl_missing_method_is_rejected() {
+    let result = check("Struct[Point, [x: Int32]]\nTrait[Shape, [Area[self] -> Int32]]\nImpl[Shape, Point]");
+    assert_eq!(
+        result,
+        Err(TypeError::MissingTraitMethod {
+            trait_name: "Shape".to_string(),
+            type_name: "Point".to_string(),
+            method: "Area".to_string(),
+        })
+    );
+}
+
+#[test]
+fn test_impl_extra_method_is_rejected() {
+    let result = check(
+        "Struct[Point, [x: Int32]]\nTrait[Shape, [Area[self] -> Int32]]\nImpl[Shape, Point, Area[self] := 1, Perimeter[self] := 2]",
+    );
+    assert_eq!(
+        result,
+        Err(TypeError::UnknownTraitMethod { trait_name: "Shape".to_string(), method: "Perimeter".to_string() })
+    );
+}
+
+#[test]
+fn test_impl_method_result_must_match_trait() {
+    let result = check("Struct[Point, [x: Int32]]\nTrait[Shape, [Area[self] -> Int32]]\nImpl[Shape, Point, Area[self] := \"big\"]");
+    assert!(matches!(result, Err(TypeError::TypeMismatch { .. })), "got: {:?}", result);
+}
+
+#[test]
+fn test_passing_type_without_impl_is_rejected() {
+    let source = "Struct[Point, [x: Int32]]\nStruct[Other, [y: Int32]]\n\
+                  Trait[Shape, [Area[self] -> Int32]]\nImpl[Shape, Point, Area[self] := self.x]\n\
+                  Area[Other[1]]";
+    assert_eq!(
+        check(source),
+        Err(TypeError::TraitNotImplemented { trait_name: "Shape".to_string(), type_: Type::Custom("Other".to_string()) })
+    );
+}
+
+#[test]
+fn test_trait_typed_parameter_rejects_other_types() {
+    let source = format!("{}Show[p: Printable] := ToText[p]\nShow[5]", PRINTABLE);
+    assert!(matches!(check(&source), Err(TypeError::TypeMismatch { .. })), "got: {:?}", check(&source));
+}
+
+// ============================================================================
+// Code Generation Tests
+// ============================================================================
+
+#[test]
+fn test_codegen_trait_and_impl() {
+    let code = generate(PRINTABLE);
+    assert!(code.contains("trait Printable {\n    fn to_text(&self) -> String;\n    fn weight(&self, factor: i32) -> i32;\n}"), "got: {}", code);
+    assert!(code.contains("impl Printable for Point {"), "got: {}", code);
+    assert!(code.contains("fn weight(&self, factor: i32) -> i32 {"), "got: {}", code);
+}
+
+#[test]
+fn test_codegen_trait_typed_parameter_is_impl_trait() {
+    let code = generate(&format!("{}Show[p: Printable] := ToText[p]", PRINTABLE));
+    assert!(code.contains("fn show(p: impl Printable) -> String {\n    p.to_text()\n}"), "got: {}", code);
+}
+
+#[test]
+fn test_codegen_self_fields_are_cloned() {
+    let code = generate(PRINTABLE);
+    assert!(code.contains("self.text.clone()"), "got: {}", code);
+}
+
+#[test]
+fn test_run_trait_polymorphism() {
+    let code = generate(&format!(
+        "{}Show[p: Printable] := ToText[p]\nPrint[Show[Point[1, 2]]]\nPrint[Show[Label[\"hi\"]]]\nPrint[Weight[Point[1, 2], 10]]",
+        PRINTABLE
+    ));
+    assert_eq!(compile_and_run(&code, "polymorphism"), "(1, 2)\nhi\n30\n");
+}
@@ -0,0 +1,105 @@
+mod common;
+
+use common::{parse, try_generate};
+use w::ast::{Expression, Operator, Type};
+use w::lexer::{Lexer, Token};
+use w::type_inference::{TypeError, TypeInference};
+
+fn infer(input: &str) -> Result<Type, TypeError> {
+    let mut inference = TypeInference::new();
+    inference.infer_expression(&parse(input))
+}
+
+// ============================================================================
+// Logical Operator Lexing / Parsing Tests
+// ============================================================================
+
+#[test]
+fn test_lex_logical_operators() {
+    let mut lexer = Lexer::new("a && b || !c".to_string());
+    let mut tokens = Vec::new();
+    while let Some(token) = lexer.next_token().unwrap() {
+        tokens.push(token);
+    }
+
+    assert_eq!(tokens, vec![
+        Token::Identifier("a".to_string()),
+        Token::And,
+        Token::Identifier("b".to_string()),
+        Token::Or,
+        Token::Not,
+        Token::Identifier("c".to_string()),
+    ]);
+}
+
+#[test]
+fn test_parse_logical_and() {
+    match parse("a && b") {
+        Expression::BinaryOp { operator: Operator::And, .. } => {}
+        other => panic!("Expected And BinaryOp, got: {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_not() {
+    assert_eq!(parse("!done"), Expression::Not {
+        expr: Box::new(Expression::Identifier("done".to_string())),
+    });
+}
+
+// ============================================================================
+// Type Inference Tests
+// ============================================================================
+
+#[test]
+fn test_logical_operators_infer_bool() {
+    assert_eq!(infer("false && true").unwrap(), Type::Bool);
+    assert_eq!(infer("!true").unwrap(), Type::Bool);
+}
+
+#[test]
+fn test_logical_operator_rejects_numbers() {
+    assert!(matches!(infer("1 && true"), Err(TypeError::TypeMismatch { .. })));
+    assert!(matches!(infer("!1"), Err(TypeError::TypeMismatch { .. })));
+}
+
+#[test]
+fn test_numeric_condition_is_rejected_with_fix_it() {
+    let err = infer("Cond[[42 Print[\"The answer\"]]]").unwrap_err();
+    assert_eq!(err, TypeError::NonBoolCondition(Type::Int32));
+    assert!(err.to_string().contains("x != 0"), "got: {}", err);
+}
+
+#[test]
+fn test_bool_condition_is_accepted() {
+    assert!(infer("Cond[[!false Print[\"working\"]]]").is_ok());
+}
+
+// ============================================================================
+// Codegen Tests
+// ============================================================================
+
+#[test]
+fn test_generate_logical_operators() {
+    let rust_code = try_generate("Check[a: Bool, b: Bool] := a && !b || b").unwrap();
+    assert!(rust_code.contains("((a && !b) || b)"), "got: {}", rust_code);
+    assert!(rust_code.contains("-> bool"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_comparison_return_type_is_bool() {
+    let rust_code = try_generate("IsBig[x: Int32] := x > 10").unwrap();
+    assert!(rust_code.contains("fn is_big(x: i32) -> bool"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_numeric_condition_is_not_generated() {
+    assert!(try_generate("Cond[[42 Print[\"The answer\"]]]").is_err(),
+        "`if 42 {{` would not compile and must be rejected");
+}
+
+#[test]
+fn test_bool_condition_is_generated() {
+    let rust_code = try_generate("Cond[[!done Print[\"working\"]]]").unwrap();
+    assert!(rust_code.contains("if !done {"), "got: {}", rust_code);
+}
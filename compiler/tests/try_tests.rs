@@ -0,0 +1,193 @@
+mod common;
+
+use common::{compile_and_run, generate, parse};
+use w::ast::{Expression, Type};
+use w::parser::Parser;
+use w::rust_codegen::RustCodeGenerator;
+use w::type_inference::{TypeError, TypeInference};
+
+const PARSE_POSITIVE: &str = "ParsePositive[n: Int32] := If[n > 0, Ok[n], Err[\"not positive\"]]\n";
+
+fn check(input: &str) -> Result<(), TypeError> {
+    let expressions = match parse(input) {
+        Expression::Program(expressions) => expressions,
+        other => vec![other],
+    };
+    TypeInference::new().check_program(&expressions)
+}
+
+/// Type of the last function defined in `input`
+fn function_type(input: &str) -> Result<Type, TypeError> {
+    let expressions = match parse(input) {
+        Expression::Program(expressions) => expressions,
+        other => vec![other],
+    };
+    let mut inference = TypeInference::new();
+    let mut last = Err(TypeError::CannotInfer("empty program".to_string()));
+    for expr in &expressions {
+        last = inference.infer_expression(expr);
+    }
+    last
+}
+
+// ============================================================================
+// Parser Tests
+// ============================================================================
+
+#[test]
+fn test_parse_try() {
+    match parse("Try[F[x]]") {
+        Expression::Try { expr } => assert!(matches!(*expr, Expression::FunctionCall { .. }), "got: {:?}", expr),
+        other => panic!("expected Try, got: {:?}", other),
+    }
+}
+
+#[test]
+fn test_try_takes_one_argument() {
+    let mut parser = Parser::new("Try[a, b]".to_string());
+    assert_eq!(parser.parse(), None);
+}
+
+// ============================================================================
+// Type Inference Tests
+// ============================================================================
+
+#[test]
+fn test_try_unwraps_ok_type() {
+    let input = format!("{}Double[n: Int32] := Ok[Try[ParsePositive[n]] * 2]", PARSE_POSITIVE);
+    assert_eq!(
+        function_type(&input),
+        Ok(Type::Function(vec![Type::Int32], Box::new(Type::Result(Box::new(Type::Int32), Box::new(Type::String)))))
+    );
+}
+
+#[test]
+fn test_try_sets_error_type_of_err_only_body() {
+    let input = format!("{}Fail[n: Int32] := If[Try[ParsePositive[n]] > 10, Panic[\"big\"], Err[\"small\"]]", PARSE_POSITIVE);
+    assert_eq!(
+        function_type(&input),
+        Ok(Type::Function(vec![Type::Int32], Box::new(Type::Result(Box::new(Type::Unknown), Box::new(Type::String)))))
+    );
+}
+
+#[test]
+fn test_try_requires_result_function() {
+    let input = format!("{}Double[n: Int32] := Try[ParsePositive[n]] * 2", PARSE_POSITIVE);
+    assert_eq!(check(&input), Err(TypeError::TryWithoutResult(Some(("Double".to_string(), Type::Int32)))));
+}
+
+#[test]
+fn test_try_outside_function_is_rejected() {
+    let input = format!("{}Let[x, Try[ParsePositive[1]]]", PARSE_POSITIVE);
+    assert_eq!(check(&input), Err(TypeError::TryWithoutResult(None)));
+}
+
+#[test]
+fn test_try_in_lambda_is_rejected() {
+    let input = format!("{}F[n: Int32] := Ok[Take[3, Map[Function[{{x}}, Try[ParsePositive[x]]], Repeat[n]]]]", PARSE_POSITIVE);
+    assert_eq!(check(&input), Err(TypeError::TryWithoutResult(None)));
+}
+
+#[test]
+fn test_try_requires_result_operand() {
+    assert!(matches!(check("F[n: Int32] := Ok[Try[n]]"), Err(TypeError::TypeMismatch { .. })));
+}
+
+#[test]
+fn test_try_error_types_must_agree() {
+    let input = "Code[n: Int32] := Err[404]\nF[n: Int32] := Ok[Try[Code[n]]]";
+    match check(input) {
+        Err(TypeError::TypeMismatch { expected, actual, .. }) => {
+            assert_eq!((expected, actual), (Type::String, Type::Int32));
+        }
+        other => panic!("expected TypeMismatch, got: {:?}", other),
+    }
+}
+
+#[test]
+fn test_try_without_result_message() {
+    let input = format!("{}Double[n: Int32] := Try[ParsePositive[n]] * 2", PARSE_POSITIVE);
+    let error = check(&input).unwrap_err();
+    assert_eq!(error.code(), "E0022");
+    assert_eq!(
+        error.to_string(),
+        "Double uses Try but returns Int32; Try returns its Err early, so Double must return Result (e.g. Ok[value])"
+    );
+}
+
+#[test]
+fn test_propagate_requires_result_function() {
+    let input = format!("{}Double[n: Int32] := ParsePositive[n]? * 2", PARSE_POSITIVE);
+    let error = check(&input).unwrap_err();
+    assert_eq!(error, TypeError::PropagateMismatch {
+        operand: Type::Result(Box::new(Type::Int32), Box::new(Type::String)),
+        function: Some(("Double".to_string(), Type::Int32)),
+    });
+    assert_eq!(error.code(), "E0032");
+}
+
+#[test]
+fn test_propagate_on_option_requires_option_function() {
+    let input = "Next[n: Int32] := If[n < 9, Some[n + 1], None]\nTwice[n: Int32] := Ok[Next[n]? * 2]";
+    assert!(matches!(check(input), Err(TypeError::PropagateMismatch { function: Some(_), .. })));
+    let input = "Next[n: Int32] := If[n < 9, Some[n + 1], None]\nTwice[n: Int32] := Some[Next[n]? * 2]";
+    assert_eq!(check(input), Ok(()));
+}
+
+#[test]
+fn test_propagate_outside_function_is_rejected() {
+    let input = format!("{}Print[ParsePositive[1]?]", PARSE_POSITIVE);
+    assert!(matches!(check(&input), Err(TypeError::PropagateMismatch { function: None, .. })));
+}
+
+#[test]
+fn test_propagate_sets_error_type() {
+    let input = format!("{}Double[n: Int32] := Ok[ParsePositive[n]? * 2]", PARSE_POSITIVE);
+    assert_eq!(
+        function_type(&input),
+        Ok(Type::Function(vec![Type::Int32], Box::new(Type::Result(Box::new(Type::Int32), Box::new(Type::String)))))
+    );
+}
+
+// ============================================================================
+// Codegen Tests
+// ============================================================================
+
+#[test]
+fn test_try_generates_question_mark() {
+    let rust_code = generate(&format!("{}Double[n: Int32] := Ok[Try[ParsePositive[n]] * 2]", PARSE_POSITIVE));
+    assert!(rust_code.contains("Ok(((parse_positive(n))? * 2))"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_return_type_takes_error_type_from_try() {
+    let rust_code = generate(&format!("{}Double[n: Int32] := Ok[Try[ParsePositive[n]] * 2]", PARSE_POSITIVE));
+    assert!(rust_code.contains("fn parse_positive(n: i32) -> Result<i32, String>"), "got: {}", rust_code);
+    assert!(rust_code.contains("fn double(n: i32) -> Result<i32, String>"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_try_in_main_is_unsupported() {
+    let mut codegen = RustCodeGenerator::new();
+    assert!(codegen.generate(&parse(&format!("{}Print[Try[ParsePositive[1]]]", PARSE_POSITIVE))).is_err());
+}
+
+#[test]
+fn test_try_propagates_err() {
+    let input = format!(
+        "{}Double[n: Int32] := Ok[Try[ParsePositive[n]] * 2]\n\
+         Match[Double[3], [Ok[v], Print[v]], [Err[e], Print[e]]]\n\
+         Match[Double[0], [Ok[v], Print[v]], [Err[e], Print[e]]]",
+        PARSE_POSITIVE
+    );
+    assert_eq!(compile_and_run(&generate(&input), "propagate"), "6\nnot positive\n");
+}
+
+#[test]
+fn test_error_propagation_example_runs() {
+    let rust_code = generate(include_str!("../examples/error_propagation.w"));
+    assert_eq!(
+        compile_and_run(&rust_code, "error_propagation_example"),
+        "Ok(11)\nErr(\"not positive\")\nSome(60)\nSome(100)\nNone\n"
+    );
+}
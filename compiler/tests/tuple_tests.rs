@@ -1,6 +1,6 @@
 use w::lexer::{Lexer, Token};
 use w::parser::Parser;
-use w::ast::Expression;
+use w::ast::{Expression, Radix};
 use w::rust_codegen::RustCodeGenerator;
 
 // ============================================
@@ -10,26 +10,26 @@ use w::rust_codegen::RustCodeGenerator;
 #[test]
 fn test_lexer_parentheses() {
     let mut lexer = Lexer::new("()".to_string());
-    assert_eq!(lexer.next_token().unwrap(), Token::LeftParen);
-    assert_eq!(lexer.next_token().unwrap(), Token::RightParen);
+    assert_eq!(lexer.next_token().unwrap().unwrap(), Token::LeftParen);
+    assert_eq!(lexer.next_token().unwrap().unwrap(), Token::RightParen);
 }
 
 #[test]
 fn test_lexer_tuple_expression() {
     let mut lexer = Lexer::new("(1, 2)".to_string());
-    assert_eq!(lexer.next_token().unwrap(), Token::LeftParen);
-    assert_eq!(lexer.next_token().unwrap(), Token::Number(1));
-    assert_eq!(lexer.next_token().unwrap(), Token::Comma);
-    assert_eq!(lexer.next_token().unwrap(), Token::Number(2));
-    assert_eq!(lexer.next_token().unwrap(), Token::RightParen);
+    assert_eq!(lexer.next_token().unwrap().unwrap(), Token::LeftParen);
+    assert_eq!(lexer.next_token().unwrap().unwrap(), Token::Number(1, Radix::Decimal));
+    assert_eq!(lexer.next_token().unwrap().unwrap(), Token::Comma);
+    assert_eq!(lexer.next_token().unwrap().unwrap(), Token::Number(2, Radix::Decimal));
+    assert_eq!(lexer.next_token().unwrap().unwrap(), Token::RightParen);
 }
 
 #[test]
 fn test_lexer_parentheses_vs_comments() {
     // Ensure parentheses don't interfere with ML-style comments
     let mut lexer = Lexer::new("(* comment *) (1, 2)".to_string());
-    assert_eq!(lexer.next_token().unwrap(), Token::LeftParen);
-    assert_eq!(lexer.next_token().unwrap(), Token::Number(1));
+    assert_eq!(lexer.next_token().unwrap().unwrap(), Token::LeftParen);
+    assert_eq!(lexer.next_token().unwrap().unwrap(), Token::Number(1, Radix::Decimal));
 }
 
 // ============================================
@@ -58,7 +58,7 @@ fn test_parse_single_element_tuple() {
         Expression::Tuple(elements) => {
             assert_eq!(elements.len(), 1);
             match &elements[0] {
-                Expression::Number(n) => assert_eq!(*n, 42),
+                Expression::Number(n, _) => assert_eq!(*n, 42),
                 _ => panic!("Expected number in tuple"),
             }
         }
@@ -75,7 +75,7 @@ fn test_parse_two_element_tuple() {
         Expression::Tuple(elements) => {
             assert_eq!(elements.len(), 2);
             match &elements[0] {
-                Expression::Number(n) => assert_eq!(*n, 1),
+                Expression::Number(n, _) => assert_eq!(*n, 1),
                 _ => panic!("Expected number as first element"),
             }
             match &elements[1] {
@@ -96,7 +96,7 @@ fn test_parse_three_element_tuple() {
         Expression::Tuple(elements) => {
             assert_eq!(elements.len(), 3);
             match &elements[0] {
-                Expression::Number(n) => assert_eq!(*n, 42),
+                Expression::Number(n, _) => assert_eq!(*n, 42),
                 _ => panic!("Expected number"),
             }
             match &elements[1] {
@@ -104,7 +104,7 @@ fn test_parse_three_element_tuple() {
                 _ => panic!("Expected string"),
             }
             match &elements[2] {
-                Expression::Boolean(b) => assert_eq!(*b, true),
+                Expression::Boolean(b) => assert!(*b),
                 _ => panic!("Expected boolean"),
             }
         }
@@ -0,0 +1,101 @@
+mod common;
+
+use common::{parse, program};
+use w::ast::{Expression, Type};
+use w::type_inference::{TypeError, TypeInference};
+
+/// Check `definitions`, then infer the type of `expr` in the resulting scope
+fn infer_with(definitions: &str, expr: &str) -> Result<Type, TypeError> {
+    let mut inference = TypeInference::new();
+    inference.check_program(&program(definitions))?;
+    inference.infer_expression(&parse(expr))
+}
+
+/// The type of the single parameter of `F[x: <annotation>] := x`
+fn annotation(annotation: &str) -> Type {
+    match parse(&format!("F[x: {}] := x", annotation)) {
+        Expression::FunctionDefinition { parameters, .. } => parameters[0].type_.clone(),
+        other => panic!("Expected FunctionDefinition, got {:?}", other),
+    }
+}
+
+fn custom(name: &str) -> Type {
+    Type::Custom(name.to_string())
+}
+
+// ============================================================================
+// Parser Tests
+// ============================================================================
+
+#[test]
+fn test_custom_types_in_containers() {
+    assert_eq!(annotation("List[Point]"), Type::List(Box::new(custom("Point"))));
+    assert_eq!(annotation("Map[String, Shape]"), Type::Map(Box::new(Type::String), Box::new(custom("Shape"))));
+}
+
+#[test]
+fn test_option_and_result() {
+    assert_eq!(annotation("Option[Int32]"), Type::Option(Box::new(Type::Int32)));
+    assert_eq!(annotation("Result[Point, String]"), Type::Result(Box::new(custom("Point")), Box::new(Type::String)));
+}
+
+#[test]
+fn test_function_types() {
+    assert_eq!(annotation("Function[Int32, Bool]"), Type::Function(vec![Type::Int32], Box::new(Type::Bool)));
+    assert_eq!(
+        annotation("Function[Int32, String, Bool]"),
+        Type::Function(vec![Type::Int32, Type::String], Box::new(Type::Bool))
+    );
+    assert_eq!(annotation("Function[Int32]"), Type::Function(vec![], Box::new(Type::Int32)));
+}
+
+#[test]
+fn test_nested_generic_types() {
+    assert_eq!(
+        annotation("Map[String, List[Option[Point]]]"),
+        Type::Map(Box::new(Type::String), Box::new(Type::List(Box::new(Type::Option(Box::new(custom("Point")))))))
+    );
+    assert_eq!(
+        annotation("Option[Function[List[Int32], Result[Int32, String]]]"),
+        Type::Option(Box::new(Type::Function(
+            vec![Type::List(Box::new(Type::Int32))],
+            Box::new(Type::Result(Box::new(Type::Int32), Box::new(Type::String)))
+        )))
+    );
+}
+
+#[test]
+fn test_displayed_types_parse_back() {
+    for written in ["Option[List[Point]]", "Result[Map[String, Int64], String]", "Function[Int32, Option[Bool]]"] {
+        let type_ = annotation(written);
+        assert_eq!(annotation(&type_.to_string()), type_, "got: {}", type_);
+    }
+}
+
+#[test]
+fn test_struct_field_annotations() {
+    match parse("Struct[Route, [stops: List[Point], next: Option[Route]]]") {
+        Expression::StructDefinition { fields, .. } => {
+            assert_eq!(fields[0].type_, Type::List(Box::new(custom("Point"))));
+            assert_eq!(fields[1].type_, Type::Option(Box::new(custom("Route"))));
+        }
+        other => panic!("Expected StructDefinition, got {:?}", other),
+    }
+}
+
+// ============================================================================
+// Type Inference Tests
+// ============================================================================
+
+#[test]
+fn test_option_parameter_is_checked() {
+    let definitions = "Unwrap[o: Option[Int32]] := Match[o, [Some[x], x], [None, 0]]";
+    assert_eq!(infer_with(definitions, "Unwrap[Some[3]]"), Ok(Type::Int32));
+    assert!(matches!(infer_with(definitions, "Unwrap[3]"), Err(TypeError::TypeMismatch { .. })));
+}
+
+#[test]
+fn test_custom_type_inside_option() {
+    let definitions = "Struct[Point, [x: Int32, y: Int32]]\nOrigin[p: Option[Point]] := p";
+    assert_eq!(infer_with(definitions, "Origin[None]"), Ok(Type::Option(Box::new(custom("Point")))));
+}
@@ -0,0 +1,98 @@
+mod common;
+
+use common::parse;
+use w::ast::{Expression, Type};
+use w::type_inference::{TypeError, TypeInference};
+
+fn infer(input: &str) -> Result<Type, TypeError> {
+    TypeInference::new().infer_expression(&parse(input))
+}
+
+/// The type written after `x:` in a one-parameter function definition
+fn parse_type(source: &str) -> Type {
+    match parse(&format!("F[x: {}] := x", source)) {
+        Expression::FunctionDefinition { mut parameters, .. } => parameters.remove(0).type_,
+        other => panic!("Expected function definition, got: {:?}", other),
+    }
+}
+
+// ============================================================================
+// Rendering Tests
+// ============================================================================
+
+#[test]
+fn test_display_primitives() {
+    assert_eq!(Type::Int32.to_string(), "Int32");
+    assert_eq!(Type::UInt.to_string(), "UInt");
+    assert_eq!(Type::Float64.to_string(), "Float64");
+    assert_eq!(Type::Bytes.to_string(), "Bytes");
+    assert_eq!(Type::Never.to_string(), "Never");
+    assert_eq!(Type::Unknown.to_string(), "_");
+}
+
+#[test]
+fn test_display_tuples() {
+    assert_eq!(Type::Tuple(vec![]).to_string(), "()");
+    assert_eq!(Type::Tuple(vec![Type::Int32]).to_string(), "(Int32,)");
+    assert_eq!(Type::Tuple(vec![Type::Int32, Type::String]).to_string(), "(Int32, String)");
+}
+
+#[test]
+fn test_display_nested_generics() {
+    let ty = Type::Map(
+        Box::new(Type::String),
+        Box::new(Type::List(Box::new(Type::Option(Box::new(Type::Int64))))),
+    );
+    assert_eq!(ty.to_string(), "Map[String, List[Option[Int64]]]");
+    let ty = Type::Result(Box::new(Type::Array(Box::new(Type::UInt8), 4)), Box::new(Type::Custom("Error".to_string())));
+    assert_eq!(ty.to_string(), "Result[Array[UInt8, 4], Error]");
+}
+
+#[test]
+fn test_display_functions_and_type_variables() {
+    let ty = Type::Function(vec![Type::TypeVar("T".to_string()), Type::Int32], Box::new(Type::Bool));
+    assert_eq!(ty.to_string(), "Function['T, Int32, Bool]");
+    assert_eq!(Type::Function(vec![], Box::new(Type::String)).to_string(), "Function[String]");
+}
+
+#[test]
+fn test_display_round_trips_through_parser() {
+    for source in [
+        "List[Int32]",
+        "Map[String, List[Int32]]",
+        "BTreeMap[Int64, HashSet[Char]]",
+        "Array[Float32, 3]",
+        "Queue[Heap[UInt16]]",
+        "Stream[String]",
+        "Point",
+    ] {
+        assert_eq!(parse_type(source).to_string(), source);
+    }
+}
+
+// ============================================================================
+// Diagnostic Tests
+// ============================================================================
+
+#[test]
+fn test_type_mismatch_uses_surface_syntax() {
+    let error = infer("Cond[[true [1, 2]] [Some[\"x\"]]]").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Type mismatch in cond default branch: expected List[Int32], got Option[String]"
+    );
+}
+
+#[test]
+fn test_condition_error_uses_surface_syntax() {
+    let error = infer("Cond[[[true] 1]]").unwrap_err();
+    assert_eq!(error.to_string(), "Condition must be Bool, got List[Bool]");
+}
+
+#[test]
+fn test_diagnostics_never_show_debug_types() {
+    for source in ["Cond[[true (1, \"a\")] [()]]", "Heap[1.5]", "[1, 2] < [Some[1]]"] {
+        let message = infer(source).unwrap_err().to_string();
+        assert!(!message.contains("Tuple(") && !message.contains("List(") && !message.contains("Option("), "got: {}", message);
+    }
+}
@@ -450,7 +450,8 @@ Match[[1, 2, 3],
 fn test_match_list_pattern_variable_binding() {
     let input = r#"
 Match[[10, 20, 30],
-  [[first, second, third], first + second]
+  [[first, second, third], first + second],
+  [_, 0]
 ]
 "#;
     let mut parser = Parser::new(input.to_string());
@@ -467,7 +468,8 @@ Match[[10, 20, 30],
 fn test_match_list_pattern_type_error() {
     let input = r#"
 Match[["a", "b", "c"],
-  [[x, y, z], x]
+  [[x, y, z], x],
+  [_, ""]
 ]
 "#;
     let mut parser = Parser::new(input.to_string());
@@ -485,7 +487,8 @@ Match[["a", "b", "c"],
 fn test_match_nested_list_pattern() {
     let input = r#"
 Match[[[1, 2], [3, 4]],
-  [[first, second], first]
+  [[first, second], first],
+  [_, []]
 ]
 "#;
     let mut parser = Parser::new(input.to_string());
@@ -0,0 +1,178 @@
+mod common;
+
+use common::{compile_and_run, parse, program};
+use w::ast::{Expression, Type};
+use w::driver::{compile, NoCallbacks};
+use w::rust_codegen::{CodegenOptions, RustCodeGenerator};
+use w::type_inference::{number_nodes, NodeId, TypeError, TypeInference, TypeTable};
+
+use std::collections::HashMap;
+
+fn type_table(input: &str) -> TypeTable {
+    let expressions = program(input);
+    let mut inference = TypeInference::new();
+    assert_eq!(inference.check_program_errors(&expressions), vec![]);
+    inference.type_table(&expressions)
+}
+
+/// Type of each expression of `expressions` that `table` has one for,
+/// keyed by the expression's Debug form
+fn node_types(expressions: &[Expression], table: &TypeTable) -> HashMap<String, Type> {
+    fn collect(expr: &Expression, ids: &HashMap<usize, NodeId>, table: &TypeTable, into: &mut HashMap<String, Type>) {
+        if let Some(ty) = table.node(ids[&(expr as *const Expression as usize)]) {
+            into.insert(format!("{:?}", expr), ty.clone());
+        }
+        expr.for_each_child(&mut |child| collect(child, ids, table, into));
+    }
+    let mut ids = HashMap::new();
+    number_nodes(expressions, &mut ids);
+    let mut types = HashMap::new();
+    for expr in expressions {
+        collect(expr, &ids, table, &mut types);
+    }
+    types
+}
+
+/// Generate Rust for `input` with the types its type check settled on
+fn generate_typed(input: &str) -> String {
+    let mut codegen = RustCodeGenerator::builder().types(type_table(input)).build();
+    codegen.generate(&parse(input)).unwrap()
+}
+
+fn list(inner: Type) -> Type {
+    Type::List(Box::new(inner))
+}
+
+const DOUBLES: &str = "Doubles[xs: List[Int32]] := Map[Function[{x}, x * 2], xs]";
+
+// ============================================================================
+// Type Table Tests
+// ============================================================================
+
+#[test]
+fn test_table_has_functions_and_bindings() {
+    let table = type_table(&format!("{}\nLet[twos, Doubles[[1]]]", DOUBLES));
+    assert_eq!(table.lookup("Doubles"), Some(&Type::Function(vec![list(Type::Int32)], Box::new(list(Type::Int32)))));
+    assert_eq!(table.return_type("Doubles"), Some(&list(Type::Int32)));
+    assert_eq!(table.lookup("twos"), Some(&list(Type::Int32)));
+    assert_eq!(table.return_type("twos"), None);
+}
+
+#[test]
+fn test_later_statements_settle_types() {
+    let table = type_table("Let[names, []]\nLet[more, Push[names, \"a\"]]");
+    assert_eq!(table.lookup("names"), Some(&list(Type::String)));
+}
+
+#[test]
+fn test_unsettled_and_redefined_names_are_left_out() {
+    let table = type_table("Id[x: T] := x\nLet[n, 1]\nLet[n, \"one\"]");
+    for name in ["Id", "n"] {
+        assert_eq!(table.lookup(name), None, "{}", name);
+    }
+}
+
+#[test]
+fn test_table_has_expression_types() {
+    let source = "Let[names, []]\nLet[more, Push[names, \"a\"]]\nScale[xs: List[Int32]] := Map[Function[{x}, x * 2], xs]";
+    let expressions = program(source);
+    let mut inference = TypeInference::new();
+    assert_eq!(inference.check_program_errors(&expressions), vec![]);
+    let types = node_types(&expressions, &inference.type_table(&expressions));
+    // What later statements learn reaches the nodes checked before them
+    assert_eq!(types.get("List([])"), Some(&list(Type::String)));
+    assert_eq!(types.get(&format!("{:?}", parse("x * 2"))), Some(&Type::Int32));
+    assert_eq!(types.get(&format!("{:?}", parse("Map[Function[{x}, x * 2], xs]"))), Some(&list(Type::Int32)));
+}
+
+#[test]
+fn test_unsettled_expressions_are_left_out() {
+    // Which the type check reports, but the table is still built
+    let expressions = program("Let[empty, None]");
+    let mut inference = TypeInference::new();
+    let errors = inference.check_program_errors(&expressions);
+    assert!(matches!(errors.as_slice(), [TypeError::CannotInfer(_)]), "got: {:?}", errors);
+    let table = inference.type_table(&expressions);
+    assert_eq!(table.lookup("empty"), None);
+    assert_eq!(node_types(&expressions, &table).get("None"), None);
+}
+
+#[test]
+fn test_table_has_module_functions() {
+    let module = program("Doubles[xs: List[Int32]] := Map[Function[{x}, x * 2], xs]\nPrivate[Half[n: Int32] := n / 2]");
+    let expressions = program("Print[Geometry.Doubles[[1]]]");
+    let mut inference = TypeInference::new();
+    assert_eq!(inference.check_module("Geometry", &module), vec![]);
+    assert_eq!(inference.check_program_errors(&expressions), vec![]);
+    let table = inference.type_table(&expressions);
+    assert_eq!(table.module_item("Geometry", "Doubles"), Some(&Type::Function(vec![list(Type::Int32)], Box::new(list(Type::Int32)))));
+    assert_eq!(table.module_item("Geometry", "Half"), Some(&Type::Function(vec![Type::Int32], Box::new(Type::Int32))));
+    assert_eq!(table.lookup("Doubles"), None);
+}
+
+// ============================================================================
+// Code Generation Tests
+// ============================================================================
+
+#[test]
+fn test_return_types_come_from_table() {
+    let rust_code = generate_typed(DOUBLES);
+    assert!(rust_code.contains("fn doubles(xs: Vec<i32>) -> Vec<i32> {"), "got: {}", rust_code);
+
+    let rust_code = generate_typed("Total[xs: List[Int32]] := Fold[Function[{a, b}, a + b], 0, xs]");
+    assert!(rust_code.contains("fn total(xs: Vec<i32>) -> i32 {"), "got: {}", rust_code);
+
+    let rust_code = generate_typed("Pair[n: Int32] := Tuple[n, n]");
+    assert!(rust_code.contains("fn pair(n: i32) -> (i32, i32) {"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_without_table_codegen_checks_types() {
+    let rust_code = RustCodeGenerator::new().generate(&parse(DOUBLES)).unwrap();
+    assert!(rust_code.contains("fn doubles(xs: Vec<i32>) -> Vec<i32> {"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_module_return_types_come_from_table() {
+    let entry = parse("Print[Geometry.Doubles[[1]]]");
+    let modules = vec![("Geometry".to_string(), parse(DOUBLES))];
+    let rust_code = RustCodeGenerator::new().generate_single_file(&entry, &modules).unwrap();
+    assert!(rust_code.contains("pub fn doubles(xs: Vec<i32>) -> Vec<i32> {"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_placeholders_inside_functions_come_from_table() {
+    let source = "Show[xs: List[Int32]] := Block[Let[ys, Map[Function[{x}, x * 2], xs]], Let[pair, Tuple[1, 2]], Print[ys], Print[pair]]\nShow[[1, 2]]";
+    let rust_code = generate_typed(source);
+    assert!(rust_code.contains("println!(\"{:?}\", ys);"), "got: {}", rust_code);
+    assert_eq!(compile_and_run(&rust_code, "function_placeholders"), "[2, 4]\n(1, 2)\n");
+}
+
+#[test]
+fn test_placeholders_come_from_table() {
+    let rust_code = generate_typed(&format!("{}\nLet[twos, Doubles[[1]]]\nPrint[twos]\nPrint[Doubles[[2]]]", DOUBLES));
+    assert!(rust_code.contains("println!(\"{:?}\", twos);"), "got: {}", rust_code);
+    assert!(rust_code.contains("println!(\"{:?}\", doubles(vec![2]));"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_closure_return_type_is_left_to_codegen() {
+    let rust_code = generate_typed("Adder[n: Int32] := Function[{x}, x + n]");
+    assert!(!rust_code.contains("-> fn("), "got: {}", rust_code);
+}
+
+#[test]
+fn test_driver_runs_typed_program() {
+    let source = format!(
+        "{}\n\
+         Total[xs: List[Int32]] := Fold[Function[{{a, b}}, a + b], 0, xs]\n\
+         Names[] := [\"a\", \"b\"]\n\
+         Let[twos, Doubles[[1, 2]]]\n\
+         Print[twos]\n\
+         Print[Total[twos]]\n\
+         Print[Names[]]",
+        DOUBLES
+    );
+    let rust_code = compile(&source, &CodegenOptions::default(), &mut NoCallbacks).unwrap();
+    assert_eq!(compile_and_run(&rust_code, "driver"), "[2, 4]\n6\n[\"a\", \"b\"]\n");
+}
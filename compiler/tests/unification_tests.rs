@@ -0,0 +1,162 @@
+mod common;
+
+use common::{parse, program};
+use w::ast::Type;
+use w::type_inference::{TypeError, TypeInference};
+
+fn infer(input: &str) -> Result<Type, TypeError> {
+    TypeInference::new().infer_expression(&parse(input))
+}
+
+/// Check `definitions`, then infer the type of `expr` in the resulting scope
+fn infer_with(definitions: &str, expr: &str) -> Result<Type, TypeError> {
+    let mut inference = TypeInference::new();
+    inference.check_program(&program(definitions))?;
+    inference.infer_expression(&parse(expr))
+}
+
+/// The type `name` is bound to after checking `input`
+fn type_of(input: &str, name: &str) -> Type {
+    let mut inference = TypeInference::new();
+    inference.check_program(&program(input)).unwrap();
+    inference.env().lookup(name).cloned().unwrap()
+}
+
+fn list(element: Type) -> Type {
+    Type::List(Box::new(element))
+}
+
+fn function(params: Vec<Type>, ret: Type) -> Type {
+    Type::Function(params, Box::new(ret))
+}
+
+// ============================================================================
+// Lambda Tests
+// ============================================================================
+
+#[test]
+fn test_lambda_parameter_inferred_from_body() {
+    assert_eq!(infer("Function[{x}, x * 2]").unwrap(), function(vec![Type::Int32], Type::Int32));
+    assert_eq!(infer("Function[{s}, CharCount[s]]").unwrap(), function(vec![Type::String], Type::Int32));
+}
+
+#[test]
+fn test_let_bound_lambda_is_not_generic() {
+    let definitions = "Let[Id, Function[{x}, x]]";
+    assert_eq!(infer_with(definitions, "Id[1]").unwrap(), Type::Int32);
+    assert_eq!(infer_with(definitions, "Id[\"a\"]").unwrap(), Type::String);
+    let result = infer_with(definitions, "Tuple[Id[1], Id[true]]");
+    assert!(matches!(result, Err(TypeError::TypeMismatch { .. })), "got: {:?}", result);
+    let errors = TypeInference::new().check_program_errors(&program("Let[Id, Function[{x}, x]]\nPrint[Id[1], Id[\"a\"]]"));
+    assert_eq!(errors.len(), 1, "got: {:?}", errors);
+}
+
+#[test]
+fn test_let_bound_lambda_type_learns_from_use() {
+    let t = Type::TypeVar("?1".to_string());
+    assert_eq!(type_of("Let[Id, Function[{x}, x]]", "Id"), function(vec![t.clone()], t));
+    assert_eq!(type_of("Let[Id, Function[{x}, x]]\nLet[one, Id[1]]", "Id"), function(vec![Type::Int32], Type::Int32));
+    assert_eq!(type_of("Let[Double, Function[{x}, x * 2]]", "Double"), function(vec![Type::Int32], Type::Int32));
+}
+
+#[test]
+fn test_let_bound_lambda_checks_arguments() {
+    let result = infer_with("Let[Double, Function[{x}, x * 2]]", "Double[\"a\"]");
+    assert!(matches!(result, Err(TypeError::TypeMismatch { .. })), "got: {:?}", result);
+}
+
+// ============================================================================
+// Empty Value Tests
+// ============================================================================
+
+#[test]
+fn test_empty_list_learns_from_push() {
+    assert_eq!(type_of("Let[xs, []]\nPush[xs, 1]", "xs"), list(Type::Int32));
+}
+
+#[test]
+fn test_empty_list_push_conflict() {
+    let result = infer_with("Let[xs, []]\nPush[xs, 1]", "Push[xs, \"a\"]");
+    assert!(matches!(result, Err(TypeError::TypeMismatch { .. })), "got: {:?}", result);
+}
+
+#[test]
+fn test_none_takes_type_of_other_branch() {
+    assert_eq!(
+        infer_with("Let[c, true]", "If[c, None, Some[1]]").unwrap(),
+        Type::Option(Box::new(Type::Int32))
+    );
+}
+
+#[test]
+fn test_unconstrained_empty_list_is_unknown() {
+    assert_eq!(infer("[]").unwrap(), list(Type::Unknown));
+}
+
+// ============================================================================
+// Map Literal Tests
+// ============================================================================
+
+fn map(key: Type, value: Type) -> Type {
+    Type::Map(Box::new(key), Box::new(value))
+}
+
+#[test]
+fn test_map_literal_types_from_entries() {
+    assert_eq!(infer("{\"a\": 1, \"b\": 2}").unwrap(), map(Type::String, Type::Int32));
+    assert_eq!(type_of("F[] := {\"a\": 1}", "F"), function(vec![], map(Type::String, Type::Int32)));
+    assert_eq!(infer("{}").unwrap(), map(Type::Unknown, Type::Unknown));
+}
+
+#[test]
+fn test_map_literal_entries_must_agree() {
+    let result = infer("{1: \"a\", \"b\": \"c\"}");
+    assert!(matches!(result, Err(TypeError::TypeMismatch { ref context, .. }) if context == "map keys"), "got: {:?}", result);
+    let result = infer("{1: \"a\", 2: 3}");
+    assert!(matches!(result, Err(TypeError::TypeMismatch { ref context, .. }) if context == "map values"), "got: {:?}", result);
+}
+
+// ============================================================================
+// Map and Filter Tests
+// ============================================================================
+
+#[test]
+fn test_map_result_element_type() {
+    assert_eq!(infer("Map[x -> x * 2, [1, 2]]").unwrap(), list(Type::Int32));
+    assert_eq!(infer("Map[s -> CharCount[s], [\"a\", \"bc\"]]").unwrap(), list(Type::Int32));
+    assert_eq!(infer("Map[Function[{n}, \"x\"], [1, 2]]").unwrap(), list(Type::String));
+}
+
+#[test]
+fn test_filter_keeps_element_type() {
+    assert_eq!(infer("Filter[Function[{s}, s == \"a\"], [\"a\", \"b\"]]").unwrap(), list(Type::String));
+}
+
+#[test]
+fn test_map_checks_function_against_elements() {
+    let result = infer("Map[Function[{s}, CharCount[s]], [1, 2]]");
+    assert!(matches!(result, Err(TypeError::TypeMismatch { .. })), "got: {:?}", result);
+}
+
+#[test]
+fn test_filter_predicate_must_be_bool() {
+    let result = infer("Filter[Function[{n}, n + 1], [1, 2]]");
+    assert!(matches!(result, Err(TypeError::TypeMismatch { .. })), "got: {:?}", result);
+}
+
+// ============================================================================
+// Occurs Check Tests
+// ============================================================================
+
+#[test]
+fn test_list_cannot_contain_itself() {
+    let result = infer("Function[{xs}, Push[xs, xs]]");
+    assert!(matches!(result, Err(TypeError::TypeMismatch { .. })), "got: {:?}", result);
+}
+
+#[test]
+fn test_unresolved_type_displays_as_placeholder() {
+    assert_eq!(list(Type::TypeVar("?4".to_string())).to_string(), "List[_]");
+    assert_eq!(Type::TypeVar("T".to_string()).to_string(), "'T");
+    assert_eq!(Type::Custom("T".to_string()).to_string(), "T");
+}
@@ -0,0 +1,79 @@
+mod common;
+
+use common::{compile_and_run, generate, parse};
+use w::ast::{Expression, Type};
+use w::type_inference::{TypeError, TypeInference};
+
+fn infer(input: &str) -> Result<Type, TypeError> {
+    TypeInference::new().infer_expression(&parse(input))
+}
+
+// ============================================================================
+// Parser Tests
+// ============================================================================
+
+#[test]
+fn test_parse_unit_literal_is_empty_tuple() {
+    assert_eq!(parse("Unit"), Expression::Tuple(vec![]));
+    assert_eq!(parse("Unit"), parse("()"));
+}
+
+#[test]
+fn test_parse_unit_type_annotation() {
+    match parse("Ignore[x: Unit] := x") {
+        Expression::FunctionDefinition { parameters, .. } => assert_eq!(parameters[0].type_, Type::Tuple(vec![])),
+        other => panic!("Expected function definition, got: {:?}", other),
+    }
+}
+
+// ============================================================================
+// Type Inference Tests
+// ============================================================================
+
+#[test]
+fn test_discard_is_unit() {
+    assert_eq!(infer("Discard[Pop[Stack[1, 2]]]").unwrap(), Type::Tuple(vec![]));
+    assert_eq!(infer("Unit").unwrap(), Type::Tuple(vec![]));
+}
+
+#[test]
+fn test_discard_still_checks_its_argument() {
+    assert!(matches!(infer("Discard[1 + \"one\"]"), Err(TypeError::TypeMismatch { .. })));
+    assert!(matches!(infer("Discard[1, 2]"), Err(TypeError::ArityMismatch { .. })));
+}
+
+#[test]
+fn test_type_errors_name_unit() {
+    let error = infer("Cond[[true 1] [Print[\"one\"]]]").unwrap_err();
+    let message = error.to_string();
+    assert!(message.contains("expected Int32, got ()"), "got: {}", message);
+    assert!(!message.contains("Tuple([])"), "got: {}", message);
+}
+
+#[test]
+fn test_nested_unit_in_type_errors() {
+    let error = infer("Cond[[true Some[Unit]] [1]]").unwrap_err();
+    assert!(error.to_string().contains("Option[()]"), "got: {}", error);
+}
+
+// ============================================================================
+// Code Generation Tests
+// ============================================================================
+
+#[test]
+fn test_codegen_discard_statement() {
+    let rust_code = generate("Let[xs, Stack[1, 2]]\nDiscard[Pop[xs]]");
+    assert!(rust_code.contains("    let _ = xs.pop();\n"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_codegen_discard_value() {
+    let rust_code = generate("Drop[s: Stack[Int32]] := Discard[Pop[s]]");
+    assert!(rust_code.contains("{ let _ = s.pop(); }"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_run_discard_and_unit() {
+    let rust_code = generate("Let[xs, Stack[1, 2, 3]]\nDiscard[Pop[xs]]\nPrint[Pop[xs]]\nLet[u, Unit]\nPrint[u == ()]");
+    assert_eq!(compile_and_run(&rust_code, "run"), "Some(2)\ntrue\n");
+}
@@ -0,0 +1,125 @@
+mod common;
+
+use common::parse;
+use w::ast::{Expression, Visibility};
+use w::parser::Parser;
+use w::rust_codegen::RustCodeGenerator;
+use w::type_inference::{TypeError, TypeInference};
+
+fn geometry() -> Vec<(String, Expression)> {
+    vec![(
+        "Geometry".to_string(),
+        parse(
+            "Private[Struct[Cache, [hits: Int32]]]\n\
+             Private[Helper[x: Int32] := x + 1]\n\
+             Public[Area[x: Int32] := Geometry.Helper[x] * x]\n\
+             Volume[x: Int32] := x * x * x",
+        ),
+    )]
+}
+
+// ============================================================================
+// Parser Tests
+// ============================================================================
+
+#[test]
+fn test_definitions_default_to_public() {
+    match parse("Square[x: Int32] := x * x") {
+        Expression::FunctionDefinition { visibility, .. } => assert_eq!(visibility, Visibility::Public),
+        other => panic!("Expected FunctionDefinition, got: {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_private_function_and_struct() {
+    match parse("Private[Helper[x: Int32] := x]") {
+        Expression::FunctionDefinition { name, visibility, .. } => {
+            assert_eq!(name, "Helper");
+            assert_eq!(visibility, Visibility::Private);
+        }
+        other => panic!("Expected FunctionDefinition, got: {:?}", other),
+    }
+
+    match parse("Private[Struct[Cache, [hits: Int32]]]") {
+        Expression::StructDefinition { name, visibility, .. } => {
+            assert_eq!(name, "Cache");
+            assert_eq!(visibility, Visibility::Private);
+        }
+        other => panic!("Expected StructDefinition, got: {:?}", other),
+    }
+}
+
+#[test]
+fn test_visibility_modifier_requires_definition() {
+    let mut parser = Parser::new("Private[1 + 2]".to_string());
+    assert!(parser.parse().is_none());
+}
+
+// ============================================================================
+// Codegen Tests
+// ============================================================================
+
+#[test]
+fn test_module_codegen_mirrors_visibility() {
+    let mut codegen = RustCodeGenerator::new();
+    let files = codegen.generate_modules(&parse("Print[Area[2]]"), &geometry()).unwrap();
+    let module = &files[1].contents;
+
+    assert!(module.contains("\nstruct Cache {"), "got: {}", module);
+    assert!(module.contains("\nfn helper(x: i32)"), "got: {}", module);
+    assert!(module.contains("pub fn area(x: i32)"), "got: {}", module);
+    assert!(module.contains("pub fn volume(x: i32)"), "got: {}", module);
+}
+
+#[test]
+fn test_private_item_usable_inside_its_module() {
+    let mut codegen = RustCodeGenerator::new();
+    let files = codegen.generate_modules(&parse("Print[Geometry.Area[2]]"), &geometry()).unwrap();
+
+    assert!(files[1].contents.contains("crate::geometry::helper(x)"), "got: {}", files[1].contents);
+    assert!(files[0].contents.contains("crate::geometry::area(2)"), "got: {}", files[0].contents);
+}
+
+/// Type check the entry `input` after the `geometry()` module
+fn check_with_geometry(input: &str) -> Vec<TypeError> {
+    let mut inference = TypeInference::new();
+    for (name, module) in geometry() {
+        let Expression::Program(expressions) = module else { unreachable!() };
+        assert_eq!(inference.check_module(&name, &expressions), vec![]);
+    }
+    let Expression::Program(expressions) = parse(&format!("{}\nPrint[1]", input)) else { unreachable!() };
+    inference.check_program_errors(&expressions)
+}
+
+#[test]
+fn test_private_item_rejected_from_other_module() {
+    let private = |name: &str| TypeError::PrivateItem { name: name.to_string(), module: "Geometry".to_string() };
+    assert_eq!(check_with_geometry("Print[Geometry.Helper[2]]"), vec![private("Helper")]);
+    assert_eq!(check_with_geometry("Print[Helper[2]]"), vec![private("Helper")]);
+    assert_eq!(check_with_geometry("Let[h, Helper]"), vec![private("Helper")]);
+    assert_eq!(check_with_geometry("Let[c, Cache[1]]"), vec![private("Cache")]);
+    let error = private("Helper");
+    assert_eq!(error.code(), "E0030");
+    assert_eq!(error.to_string(), "Helper is private to module Geometry; mark it Public[...] there to use it here");
+}
+
+#[test]
+fn test_public_items_and_own_definitions_are_visible() {
+    assert_eq!(check_with_geometry("Print[Geometry.Area[2] + Volume[2]]"), vec![]);
+    assert_eq!(check_with_geometry("Helper[x: Int32] := x\nPrint[Helper[2]]"), vec![]);
+}
+
+#[test]
+fn test_undefined_module_item_rejected() {
+    let mut codegen = RustCodeGenerator::new();
+    assert!(codegen.generate_modules(&parse("Print[Geometry.Missing[2]]"), &geometry()).is_err());
+}
+
+#[test]
+fn test_private_struct_in_root_is_not_pub() {
+    let mut codegen = RustCodeGenerator::new();
+    let rust_code = codegen.generate(&parse("Private[Struct[Point, [x: Int32]]]")).unwrap();
+
+    assert!(rust_code.contains("\nstruct Point {"), "got: {}", rust_code);
+    assert!(!rust_code.contains("pub struct Point"), "got: {}", rust_code);
+}
@@ -0,0 +1,74 @@
+mod common;
+
+use common::{parse, try_generate};
+use w::ast::{Expression, Type};
+use w::parser::Parser;
+use w::type_inference::{TypeError, TypeInference};
+
+fn infer(input: &str) -> Result<Type, TypeError> {
+    TypeInference::new().infer_expression(&parse(input))
+}
+
+// ============================================================================
+// Parser Tests
+// ============================================================================
+
+#[test]
+fn test_parse_while_let() {
+    match parse("WhileLet[Some[x], Next[it], Print[x]]") {
+        Expression::WhileLet { value, .. } => {
+            assert!(matches!(*value, Expression::FunctionCall { .. }), "got: {:?}", value);
+        }
+        other => panic!("Expected WhileLet, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_while_let_requires_body() {
+    let mut parser = Parser::new("WhileLet[Some[x], Next[it]]".to_string());
+    assert!(parser.parse().is_none());
+}
+
+// ============================================================================
+// Type Inference Tests
+// ============================================================================
+
+#[test]
+fn test_while_let_is_unit() {
+    assert_eq!(infer("WhileLet[Some[x], Some[1], Print[x]]"), Ok(Type::Tuple(vec![])));
+}
+
+#[test]
+fn test_while_let_binds_pattern_in_body() {
+    let result = infer("WhileLet[Some[x], Some[\"s\"], !x]");
+    assert!(matches!(result, Err(TypeError::TypeMismatch { actual: Type::String, .. })), "got: {:?}", result);
+}
+
+#[test]
+fn test_while_let_pattern_must_match_value_type() {
+    let result = infer("WhileLet[Ok[x], Some[1], Print[x]]");
+    assert!(result.is_err(), "got: {:?}", result);
+}
+
+// ============================================================================
+// Codegen Tests
+// ============================================================================
+
+#[test]
+fn test_codegen_while_let_statement() {
+    let rust_code = try_generate("WhileLet[Some[x], Next[1], Print[x]]").unwrap();
+    assert!(rust_code.contains("while let Some(x) = next(1) {"), "got: {}", rust_code);
+    assert!(rust_code.contains("println!(\"{}\", x);"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_codegen_while_let_in_function_body() {
+    let rust_code = try_generate("Drain[n: Int32] := WhileLet[Some[x], Next[n], Print[x]]").unwrap();
+    assert!(rust_code.contains("fn drain(n: i32) {"), "got: {}", rust_code);
+    assert!(rust_code.contains("while let Some(x) = next(n) {"), "got: {}", rust_code);
+}
+
+#[test]
+fn test_codegen_rejects_string_literal_pattern() {
+    assert!(try_generate("WhileLet[(\"a\", x), Next[1], Print[x]]").is_err());
+}